@@ -0,0 +1,771 @@
+//! This module provides tweens, values that move from a start to an end over a duration along an easing curve, and `Animations`, which applies them to objects.
+//!
+//! A `Tween` is advanced by the time that passed, usually [`App::delta_time`](crate::app::App::delta_time), and returns its current value. `Animations` holds tweens attached to properties of the objects of a [`NyanObj`] (their position, the path they follow and their visibility) and named values such as the progress of a bar, advancing all of them once per frame with `update()`.
+//!
+//! # Structs
+//!
+//! - `Tween`: Interpolates between two values over a duration with an [`Easing`] curve.
+//! - `Animations`: Tweens attached to object positions, object visibility and named values.
+//! - `Shake`: A random offset that fades out over a duration.
+//!
+//! # Traits
+//!
+//! - `Lerp`: Values that can be interpolated: numbers, positions and flags.
+//!
+//! # Methods
+//!
+//! - `Tween::new(from, to, duration, easing)`: Creates a tween.
+//! - `delay(duration)` / `repeat()`: Wait before the tween starts and start it over when it ends.
+//! - `advance(dt)` / `value()` / `progress()` / `is_finished()` / `reset()`: Drive and query a tween.
+//! - `start()` / `end()`: Return the values at the start and the end of a tween.
+//! - `Animations::new()`: Creates an empty set of animations.
+//! - `position(id, tween)` / `move_to(objects, id, to, duration, easing)`: Animate the position of an object.
+//! - `follow(id, path, progress)`: Moves an object along a [`Path`].
+//! - `shake(objects, id, intensity, duration)` / `shake_screen(objects, intensity, duration)`: Shake an object or all of them, then put them back.
+//! - `Shake::new(intensity, duration)` / `advance(dt)` / `offset()`: Shake anything else, such as a camera.
+//! - `blink(objects, id, interval, duration)` / `flash(objects, id, interval, duration)`: Toggle the visibility or reverse video of an object for a while.
+//! - `visibility(id, tween)`: Shows or hides an object when its tween ends.
+//! - `value(name, tween)` / `get(name)`: Animate a named value and read it.
+//! - `update(objects, dt)`: Advances every tween and applies them to the objects.
+//! - `is_animating(id)` / `is_running()` / `cancel(id)` / `stop(objects, id)` / `clear()`: Query and stop animations.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::easing::Easing;
+use crate::nyan_obj::NyanObj;
+use crate::path::Path;
+use crate::style::{Attribute, NyanStyle};
+
+/// The `Lerp` trait is implemented by values a [`Tween`] can interpolate.
+pub trait Lerp: Copy {
+    /// Returns the value at the progress `t` (`0.0` for `from`, `1.0` for `to`). `t` may leave
+    /// `0.0..=1.0` for easing curves that overshoot.
+    fn lerp(from: Self, to: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        from + (to - from) * t
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        from + (to - from) * t as f64
+    }
+}
+
+impl Lerp for u16 {
+    /// Rounds to the nearest cell.
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        f32::lerp(from as f32, to as f32, t)
+            .round()
+            .clamp(0.0, u16::MAX as f32) as u16
+    }
+}
+
+impl Lerp for i32 {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        f64::lerp(from as f64, to as f64, t).round() as i32
+    }
+}
+
+impl Lerp for (u16, u16) {
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        (u16::lerp(from.0, to.0, t), u16::lerp(from.1, to.1, t))
+    }
+}
+
+impl Lerp for bool {
+    /// Switches from `from` to `to` when the tween ends.
+    fn lerp(from: Self, to: Self, t: f32) -> Self {
+        if t >= 1.0 {
+            to
+        } else {
+            from
+        }
+    }
+}
+
+/// `Tween` moves a value from `from` to `to` over a duration along an easing curve.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::animation::Tween;
+/// use nyan::easing::Easing;
+///
+/// let mut tween = Tween::new(0.0, 100.0, Duration::from_millis(400), Easing::OutQuad);
+/// assert_eq!(tween.advance(Duration::from_millis(200)), 75.0);
+/// assert_eq!(tween.progress(), 0.5);
+///
+/// tween.advance(Duration::from_millis(300));
+/// assert!(tween.is_finished());
+/// assert_eq!(tween.value(), 100.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    duration: Duration,
+    delay: Duration,
+    elapsed: Duration,
+    easing: Easing,
+    repeat: bool,
+}
+
+impl<T: Lerp> Tween<T> {
+    /// Creates a tween from `from` to `to` taking `duration`, eased by `easing`.
+    pub fn new(from: T, to: T, duration: Duration, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            delay: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            easing,
+            repeat: false,
+        }
+    }
+
+    /// Waits `duration` before the tween starts moving.
+    pub fn delay(self, duration: Duration) -> Self {
+        let mut tween = self;
+        tween.delay = duration;
+        tween
+    }
+
+    /// Starts the tween over whenever it ends, forever, e.g. for a patrol along a path. The
+    /// delay is only waited once.
+    pub fn repeat(self) -> Self {
+        let mut tween = self;
+        tween.repeat = true;
+        tween
+    }
+
+    /// Advances the tween by `dt`.
+    ///
+    /// # Returns
+    ///
+    /// The value after advancing.
+    pub fn advance(&mut self, dt: Duration) -> T {
+        let end = self.delay + self.duration;
+        self.elapsed += dt;
+        if self.repeat && !self.duration.is_zero() {
+            if self.elapsed >= end {
+                let over = (self.elapsed - end).as_nanos() % self.duration.as_nanos();
+                self.elapsed = self.delay + Duration::from_nanos(over as u64);
+            }
+        } else {
+            self.elapsed = self.elapsed.min(end);
+        }
+        self.value()
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> T {
+        T::lerp(self.from, self.to, self.easing.apply(self.progress()))
+    }
+
+    /// Returns the linear progress, from `0.0` before the start to `1.0` at the end.
+    pub fn progress(&self) -> f32 {
+        let running = self.elapsed.saturating_sub(self.delay);
+        if self.duration.is_zero() {
+            return if self.elapsed >= self.delay { 1.0 } else { 0.0 };
+        }
+        (running.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    /// Returns whether the tween reached its end. Repeating tweens never do.
+    pub fn is_finished(&self) -> bool {
+        !self.repeat && self.elapsed >= self.delay + self.duration
+    }
+
+    /// Starts the tween over, including its delay.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Returns the value at the start.
+    pub fn start(&self) -> T {
+        self.from
+    }
+
+    /// Returns the value at the end.
+    pub fn end(&self) -> T {
+        self.to
+    }
+}
+
+/// `Shake` jitters an offset at random, less and less until it comes to rest after a
+/// duration, e.g. to shake an object or a camera for damage feedback.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::animation::Shake;
+///
+/// let mut shake = Shake::new(3.0, Duration::from_millis(300));
+/// let (dx, dy) = shake.advance(Duration::from_millis(100));
+/// assert!(dx.abs() <= 3 && dy.abs() <= 2);
+///
+/// shake.advance(Duration::from_millis(200));
+/// assert!(shake.is_finished());
+/// assert_eq!(shake.offset(), (0, 0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shake {
+    intensity: f32,
+    duration: Duration,
+    elapsed: Duration,
+    offset: (i16, i16),
+    rng: u64,
+}
+
+impl Shake {
+    /// Creates a shake moving up to `intensity` cells sideways and half as far up and down
+    /// (cells are about twice as tall as wide), fading out over `duration`.
+    pub fn new(intensity: f32, duration: Duration) -> Self {
+        Self {
+            intensity: intensity.abs(),
+            duration,
+            elapsed: Duration::ZERO,
+            offset: (0, 0),
+            rng: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+
+    /// Seeds the random numbers, e.g. to make a shake repeatable.
+    pub fn seed(self, seed: u64) -> Self {
+        let mut shake = self;
+        // Xorshift gets stuck at zero.
+        shake.rng = seed.max(1);
+        shake
+    }
+
+    /// Advances the shake by `dt` and picks a new offset.
+    ///
+    /// # Returns
+    ///
+    /// The offset `(dx, dy)`, `(0, 0)` once the shake is finished.
+    pub fn advance(&mut self, dt: Duration) -> (i16, i16) {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+        let left = if self.duration.is_zero() {
+            0.0
+        } else {
+            1.0 - self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
+        let reach = self.intensity * left;
+        let dx = (reach * (self.random() * 2.0 - 1.0)).round();
+        let dy = (reach / 2.0 * (self.random() * 2.0 - 1.0)).round();
+        self.offset = (dx as i16, dy as i16);
+        self.offset
+    }
+
+    /// Returns the current offset.
+    pub fn offset(&self) -> (i16, i16) {
+        self.offset
+    }
+
+    /// Returns whether the shake came to rest.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Returns a random number in `0.0..1.0` (xorshift).
+    fn random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// A blink of an object: its visibility or its style toggles every interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Blink {
+    interval: Duration,
+    duration: Duration,
+    elapsed: Duration,
+    /// What the object looked like before, put back when the blink ends.
+    restore: Restore,
+}
+
+/// The look of an object a blink toggles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Restore {
+    Visible(bool),
+    Style(NyanStyle),
+}
+
+impl Blink {
+    /// Returns whether the object is toggled now: during the first interval, the third and
+    /// so on, until the blink ends.
+    fn is_toggled(&self) -> bool {
+        if self.elapsed >= self.duration || self.interval.is_zero() {
+            return false;
+        }
+        (self.elapsed.as_nanos() / self.interval.as_nanos()) & 1 == 0
+    }
+
+    /// Applies the current look to the object `id`.
+    fn apply(&self, objects: &mut NyanObj, id: &str) -> bool {
+        let toggled = self.is_toggled();
+        match self.restore {
+            Restore::Visible(visible) => objects
+                .set_visible(id.to_string(), visible != toggled)
+                .is_ok(),
+            Restore::Style(style) => {
+                let mut style = style;
+                if toggled {
+                    if style.attributes.contains(Attribute::Reverse) {
+                        style.attributes.remove(Attribute::Reverse);
+                    } else {
+                        style.attributes.insert(Attribute::Reverse);
+                    }
+                }
+                objects.set_style(id.to_string(), style).is_ok()
+            }
+        }
+    }
+}
+
+/// The property of an object a tween is attached to.
+#[derive(Clone, Debug, PartialEq)]
+enum Property {
+    Position(Tween<(u16, u16)>),
+    Visibility(Tween<bool>),
+    /// A path and the progress along it.
+    Path(Path, Tween<f32>),
+    /// A shake and the position the object returns to.
+    Shake(Shake, (u16, u16)),
+    Blink(Blink),
+}
+
+/// `Animations` advances tweens attached to objects and named values once per frame.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::animation::{Animations, Tween};
+/// use nyan::easing::Easing;
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+///
+/// let mut obj = NyanObj::new();
+/// obj.add_object("cat", Objects::new_text("=^.^="), (0, 5));
+///
+/// let second = Duration::from_secs(1);
+/// let mut animations = Animations::new();
+/// animations.move_to(&obj, "cat", (20, 5), second, Easing::Linear);
+/// animations.value("loading", Tween::new(0.0, 1.0, second * 2, Easing::Linear));
+///
+/// // Usually `app.delta_time()`, once per frame.
+/// animations.update(&mut obj, second / 2);
+/// assert_eq!(obj.coordinate("cat"), Some((10, 5)));
+/// assert_eq!(animations.get("loading"), Some(0.25));
+///
+/// animations.update(&mut obj, second);
+/// assert_eq!(obj.coordinate("cat"), Some((20, 5)));
+/// assert!(!animations.is_animating("cat"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Animations<'a> {
+    objects: Vec<(Cow<'a, str>, Property)>,
+    values: Vec<(Cow<'a, str>, Tween<f32>)>,
+}
+
+impl<'a> Animations<'a> {
+    /// Creates an empty set of animations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the object `id` along `tween`, replacing a running movement of it, also one
+    /// along a path.
+    pub fn position<P: Into<Cow<'a, str>>>(&mut self, id: P, tween: Tween<(u16, u16)>) {
+        let id = id.into();
+        self.objects
+            .retain(|(other, old)| *other != id || !matches!(old, Property::Path(..)));
+        self.attach(id, Property::Position(tween));
+    }
+
+    /// Moves the object `id` from where it is now to `to`.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no object with the given ID exists.
+    pub fn move_to<P: Into<Cow<'a, str>>>(
+        &mut self,
+        objects: &NyanObj,
+        id: P,
+        to: (u16, u16),
+        duration: Duration,
+        easing: Easing,
+    ) -> bool {
+        let id = id.into();
+        match objects.coordinate(id.to_string()) {
+            Some(from) => {
+                self.position(id, Tween::new(from, to, duration, easing));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the object `id` along `path`, at the progress of `progress`: usually a tween from
+    /// `0.0` to `1.0`, which may [`repeat`](Tween::repeat) to walk the path again and again.
+    /// Replaces a running movement of the object along a path; a running [`position`]
+    /// tween of it is stopped.
+    ///
+    /// [`position`]: Animations::position
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use nyan::animation::{Animations, Tween};
+    /// use nyan::easing::Easing;
+    /// use nyan::nyan_obj::NyanObj;
+    /// use nyan::objects::Objects;
+    /// use nyan::path::Path;
+    ///
+    /// let mut obj = NyanObj::new();
+    /// obj.add_object("enemy", Objects::new_text("<o>"), (0, 0));
+    ///
+    /// let patrol = Path::waypoints([(0.0, 0.0), (30.0, 0.0), (30.0, 10.0), (0.0, 10.0), (0.0, 0.0)]);
+    /// let lap = Tween::new(0.0, 1.0, Duration::from_secs(4), Easing::Linear).repeat();
+    /// let mut animations = Animations::new();
+    /// animations.follow("enemy", patrol, lap);
+    ///
+    /// animations.update(&mut obj, Duration::from_secs(1));
+    /// assert_eq!(obj.coordinate("enemy"), Some((20, 0)));
+    /// animations.update(&mut obj, Duration::from_secs(4));
+    /// assert_eq!(obj.coordinate("enemy"), Some((20, 0)));
+    /// assert!(animations.is_animating("enemy"));
+    /// ```
+    pub fn follow<P: Into<Cow<'a, str>>>(&mut self, id: P, path: Path, progress: Tween<f32>) {
+        let id = id.into();
+        self.objects
+            .retain(|(other, old)| *other != id || !matches!(old, Property::Position(_)));
+        self.attach(id, Property::Path(path, progress));
+    }
+
+    /// Shakes the object `id` up to `intensity` cells around where it is now for `duration`,
+    /// then puts it back there (see [`Shake`]).
+    ///
+    /// # Returns
+    ///
+    /// `false` if no object with the given ID exists.
+    pub fn shake<P: Into<Cow<'a, str>>>(
+        &mut self,
+        objects: &NyanObj,
+        id: P,
+        intensity: f32,
+        duration: Duration,
+    ) -> bool {
+        let id = id.into();
+        // A running shake is replaced, not shaken further from its current offset.
+        let origin = self
+            .objects
+            .iter()
+            .find_map(|(other, property)| match property {
+                Property::Shake(_, origin) if *other == id => Some(*origin),
+                _ => None,
+            });
+        match origin.or_else(|| objects.coordinate(id.to_string())) {
+            Some(origin) => {
+                let shake = Shake::new(intensity, duration);
+                self.attach(id, Property::Shake(shake, origin));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Shakes every object of `objects` together, as if the whole screen shook.
+    pub fn shake_screen(&mut self, objects: &NyanObj, intensity: f32, duration: Duration) {
+        let ids: Vec<String> = objects.ids().map(str::to_string).collect();
+        for id in ids {
+            self.shake(objects, id, intensity, duration);
+        }
+    }
+
+    /// Hides and shows the object `id` every `interval` for `duration`, starting hidden, then
+    /// leaves it as visible as it was. `Duration::MAX` blinks until it is stopped, e.g. for a
+    /// cursor.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no object with the given ID exists.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use nyan::animation::Animations;
+    /// use nyan::nyan_obj::NyanObj;
+    /// use nyan::objects::Objects;
+    ///
+    /// let mut obj = NyanObj::new();
+    /// obj.add_object("coin", Objects::new_text("$"), (4, 2));
+    ///
+    /// let ms = Duration::from_millis;
+    /// let mut animations = Animations::new();
+    /// animations.blink(&obj, "coin", ms(100), ms(400));
+    ///
+    /// animations.update(&mut obj, ms(50));
+    /// assert_eq!(obj.is_visible("coin"), Some(false));
+    /// animations.update(&mut obj, ms(100));
+    /// assert_eq!(obj.is_visible("coin"), Some(true));
+    /// animations.update(&mut obj, ms(300));
+    /// assert_eq!(obj.is_visible("coin"), Some(true));
+    /// assert!(!animations.is_animating("coin"));
+    /// ```
+    pub fn blink<P: Into<Cow<'a, str>>>(
+        &mut self,
+        objects: &NyanObj,
+        id: P,
+        interval: Duration,
+        duration: Duration,
+    ) -> bool {
+        let id = id.into();
+        let visible = self
+            .restore(&id, false)
+            .or_else(|| objects.is_visible(id.to_string()).map(Restore::Visible));
+        self.start_blink(id, visible, interval, duration)
+    }
+
+    /// Switches the object `id` to reverse video and back every `interval` for `duration`,
+    /// starting reversed, then gives it its style back. The object stays visible, so a flash
+    /// draws the eye to a warning without making it unreadable. A flash runs alongside a
+    /// blink of the same object, as they toggle different things.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no object with the given ID exists.
+    pub fn flash<P: Into<Cow<'a, str>>>(
+        &mut self,
+        objects: &NyanObj,
+        id: P,
+        interval: Duration,
+        duration: Duration,
+    ) -> bool {
+        let id = id.into();
+        let style = self
+            .restore(&id, true)
+            .or_else(|| objects.style(id.to_string()).map(Restore::Style));
+        self.start_blink(id, style, interval, duration)
+    }
+
+    /// Sets the visibility of the object `id` to the end of `tween` when it ends, e.g.
+    /// `Tween::new(true, false, Duration::from_secs(3), Easing::Linear)` hides it after three
+    /// seconds. The start of the tween is applied from the next update on.
+    pub fn visibility<P: Into<Cow<'a, str>>>(&mut self, id: P, tween: Tween<bool>) {
+        self.attach(id.into(), Property::Visibility(tween));
+    }
+
+    /// Animates the value `name`, replacing a previous animation of it.
+    pub fn value<P: Into<Cow<'a, str>>>(&mut self, name: P, tween: Tween<f32>) {
+        let name = name.into();
+        self.values.retain(|(other, _)| *other != name);
+        self.values.push((name, tween));
+    }
+
+    /// Returns the current value of `name`. Finished values keep their end value until they
+    /// are cancelled.
+    pub fn get(&self, name: &str) -> Option<f32> {
+        self.values
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, tween)| tween.value())
+    }
+
+    /// Advances every tween by `dt` and applies the object tweens. Object tweens are removed
+    /// when they end, as are the tweens of objects that no longer exist.
+    ///
+    /// # Returns
+    ///
+    /// `true` if anything was animated, so the screen needs to be redrawn. The objects are
+    /// marked dirty then (see [`NyanObj::mark_dirty`]).
+    pub fn update(&mut self, objects: &mut NyanObj, dt: Duration) -> bool {
+        let mut changed = false;
+        self.objects.retain_mut(|(id, property)| {
+            let applied = match property {
+                Property::Position(tween) => objects
+                    .move_object(id.to_string(), tween.advance(dt))
+                    .is_ok(),
+                Property::Visibility(tween) => objects
+                    .set_visible(id.to_string(), tween.advance(dt))
+                    .is_ok(),
+                Property::Path(path, tween) => match path.point_at(tween.advance(dt)) {
+                    Some((x, y)) => objects
+                        .move_object(
+                            id.to_string(),
+                            (
+                                x.round().clamp(0.0, u16::MAX as f32) as u16,
+                                y.round().clamp(0.0, u16::MAX as f32) as u16,
+                            ),
+                        )
+                        .is_ok(),
+                    None => false,
+                },
+                Property::Shake(shake, origin) => {
+                    let (dx, dy) = shake.advance(dt);
+                    let to = (
+                        origin.0.saturating_add_signed(dx),
+                        origin.1.saturating_add_signed(dy),
+                    );
+                    objects.move_object(id.to_string(), to).is_ok()
+                }
+                Property::Blink(blink) => {
+                    blink.elapsed = blink.elapsed.saturating_add(dt).min(blink.duration);
+                    blink.apply(objects, id)
+                }
+            };
+            changed |= applied;
+            applied && !property.is_finished()
+        });
+        for (_, tween) in &mut self.values {
+            if !tween.is_finished() {
+                tween.advance(dt);
+                changed = true;
+            }
+        }
+        if changed {
+            objects.mark_dirty();
+        }
+        changed
+    }
+
+    /// Returns whether the object or value `id` is being animated.
+    pub fn is_animating(&self, id: &str) -> bool {
+        self.objects.iter().any(|(other, _)| other == id)
+            || self
+                .values
+                .iter()
+                .any(|(other, tween)| other == id && !tween.is_finished())
+    }
+
+    /// Returns whether any tween is still running.
+    pub fn is_running(&self) -> bool {
+        !self.objects.is_empty() || self.values.iter().any(|(_, tween)| !tween.is_finished())
+    }
+
+    /// Stops the animations of the object or value `id`, leaving the object where it is.
+    ///
+    /// # Returns
+    ///
+    /// `true` if something was animated.
+    pub fn cancel(&mut self, id: &str) -> bool {
+        let before = self.objects.len() + self.values.len();
+        self.objects.retain(|(other, _)| other != id);
+        self.values.retain(|(other, _)| other != id);
+        self.objects.len() + self.values.len() != before
+    }
+
+    /// Stops the animations of the object `id` like [`Animations::cancel`], but puts back what
+    /// blinks and shakes changed: a blinking object looks like before and a shaken one is
+    /// back in place.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the object was animated.
+    pub fn stop(&mut self, objects: &mut NyanObj, id: &str) -> bool {
+        for (_, property) in self.objects.iter().filter(|(other, _)| other == id) {
+            // The object may have been removed meanwhile; there is nothing to put back then.
+            let _ = match property {
+                Property::Blink(blink) => {
+                    let done = Blink {
+                        elapsed: blink.duration,
+                        ..*blink
+                    };
+                    done.apply(objects, id);
+                    Ok(())
+                }
+                Property::Shake(_, origin) => objects.move_object(id.to_string(), *origin),
+                _ => Ok(()),
+            };
+        }
+        self.cancel(id)
+    }
+
+    /// Stops every animation.
+    pub fn clear(&mut self) {
+        self.objects.clear();
+        self.values.clear();
+    }
+
+    /// Returns what a running blink (or, with `style`, flash) of `id` puts back, so a new one
+    /// does not start from a toggled look.
+    fn restore(&self, id: &str, style: bool) -> Option<Restore> {
+        self.objects
+            .iter()
+            .find_map(|(other, property)| match property {
+                Property::Blink(blink)
+                    if other == id && matches!(blink.restore, Restore::Style(_)) == style =>
+                {
+                    Some(blink.restore)
+                }
+                _ => None,
+            })
+    }
+
+    /// Attaches a blink of `restore`, replacing a running one.
+    fn start_blink(
+        &mut self,
+        id: Cow<'a, str>,
+        restore: Option<Restore>,
+        interval: Duration,
+        duration: Duration,
+    ) -> bool {
+        match restore {
+            Some(restore) => {
+                let blink = Blink {
+                    interval,
+                    duration,
+                    elapsed: Duration::ZERO,
+                    restore,
+                };
+                self.attach(id, Property::Blink(blink));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Attaches a tween to an object, replacing one of the same property.
+    fn attach(&mut self, id: Cow<'a, str>, property: Property) {
+        self.objects
+            .retain(|(other, old)| *other != id || !old.replaced_by(&property));
+        self.objects.push((id, property));
+    }
+}
+
+impl Property {
+    /// Returns whether `other` animates the same thing as this property, so it replaces it.
+    fn replaced_by(&self, other: &Property) -> bool {
+        match (self, other) {
+            // A blink and a flash toggle different things, so they run side by side.
+            (Property::Blink(old), Property::Blink(new)) => {
+                std::mem::discriminant(&old.restore) == std::mem::discriminant(&new.restore)
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+
+    /// Returns whether the tween of the property ended.
+    fn is_finished(&self) -> bool {
+        match self {
+            Property::Position(tween) => tween.is_finished(),
+            Property::Visibility(tween) => tween.is_finished(),
+            Property::Path(_, tween) => tween.is_finished(),
+            Property::Shake(shake, _) => shake.is_finished(),
+            Property::Blink(blink) => blink.elapsed >= blink.duration,
+        }
+    }
+}
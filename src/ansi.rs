@@ -0,0 +1,231 @@
+//! This module provides a small streaming parser for the escape sequences nyan writes to the terminal.
+//!
+//! nyan keeps track of what its own output does to the terminal (for example, where the cursor ends up) without asking the terminal. The parser splits written bytes into printable characters, control characters and escape sequences. `VirtualCursor` applies them to a software cursor position.
+//!
+//! # Structs
+//!
+//! - `AnsiParser`: Splits a byte stream into `Token`s. Incomplete UTF-8 characters and escape sequences are kept until the next call.
+//! - `VirtualCursor`: A cursor position updated from `Token`s.
+//!
+//! # Enums
+//!
+//! - `Token`: A printable character, a control character or an escape sequence.
+
+use unicode_width::UnicodeWidthChar;
+
+/// A piece of terminal output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// A printable character.
+    Print(char),
+    /// A C0 control character such as `\n` or `\r`.
+    Control(u8),
+    /// A control sequence (`ESC [ ... final`), with its numeric parameters.
+    Csi {
+        params: Vec<u16>,
+        private: bool,
+        action: u8,
+    },
+    /// A two-byte escape sequence (`ESC byte`), e.g. `ESC 7` (save cursor position).
+    Esc(u8),
+}
+
+impl Token {
+    /// Returns the `index`-th parameter of a control sequence, or `default` if it is missing or zero.
+    pub(crate) fn param(params: &[u16], index: usize, default: u16) -> u16 {
+        match params.get(index) {
+            Some(0) | None => default,
+            Some(p) => *p,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+enum State {
+    #[default]
+    Ground,
+    Escape,
+    Csi,
+    /// Operating system command, skipped until `BEL` or `ESC \`.
+    Osc,
+    OscEscape,
+}
+
+/// `AnsiParser` splits a byte stream into `Token`s.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AnsiParser {
+    state: State,
+    utf8: Vec<u8>,
+    csi: Vec<u8>,
+}
+
+impl AnsiParser {
+    /// Creates a new parser.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds bytes into the parser, calling `f` for every complete token.
+    pub(crate) fn feed<F: FnMut(Token)>(&mut self, bytes: &[u8], mut f: F) {
+        for &byte in bytes {
+            match self.state {
+                State::Ground => self.ground(byte, &mut f),
+                State::Escape => match byte {
+                    b'[' => {
+                        self.csi.clear();
+                        self.state = State::Csi;
+                    }
+                    b']' => self.state = State::Osc,
+                    _ => {
+                        f(Token::Esc(byte));
+                        self.state = State::Ground;
+                    }
+                },
+                State::Csi => {
+                    if (0x40..=0x7e).contains(&byte) {
+                        f(self.finish_csi(byte));
+                        self.state = State::Ground;
+                    } else {
+                        self.csi.push(byte);
+                    }
+                }
+                State::Osc => match byte {
+                    0x07 => self.state = State::Ground,
+                    0x1b => self.state = State::OscEscape,
+                    _ => {}
+                },
+                State::OscEscape => {
+                    self.state = if byte == b'\\' {
+                        State::Ground
+                    } else {
+                        State::Osc
+                    };
+                }
+            }
+        }
+    }
+
+    fn ground<F: FnMut(Token)>(&mut self, byte: u8, f: &mut F) {
+        if !self.utf8.is_empty() || byte >= 0x80 {
+            self.utf8.push(byte);
+            match std::str::from_utf8(&self.utf8) {
+                Ok(s) => {
+                    if let Some(ch) = s.chars().next() {
+                        f(Token::Print(ch));
+                    }
+                    self.utf8.clear();
+                }
+                Err(e) if e.error_len().is_some() => {
+                    // Invalid UTF-8: drop the bytes instead of waiting forever.
+                    f(Token::Print(char::REPLACEMENT_CHARACTER));
+                    self.utf8.clear();
+                }
+                Err(_) => {}
+            }
+            return;
+        }
+
+        match byte {
+            0x1b => self.state = State::Escape,
+            0x00..=0x1f | 0x7f => f(Token::Control(byte)),
+            _ => f(Token::Print(byte as char)),
+        }
+    }
+
+    fn finish_csi(&mut self, action: u8) -> Token {
+        let private = self.csi.first() == Some(&b'?');
+        let body = if private {
+            &self.csi[1..]
+        } else {
+            &self.csi[..]
+        };
+        let params = body
+            .split(|b| *b == b';')
+            .map(|p| {
+                std::str::from_utf8(p)
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        Token::Csi {
+            params,
+            private,
+            action,
+        }
+    }
+}
+
+/// `VirtualCursor` is a software cursor position updated from terminal output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct VirtualCursor {
+    pub(crate) x: u16,
+    pub(crate) y: u16,
+    saved: (u16, u16),
+}
+
+impl VirtualCursor {
+    /// Moves the cursor to `(x, y)`.
+    pub(crate) fn set(&mut self, x: u16, y: u16) {
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Applies a token to the cursor position.
+    ///
+    /// `raw` tells whether the terminal is in raw mode, where `\n` moves down without returning
+    /// to the first column.
+    pub(crate) fn apply(&mut self, token: &Token, raw: bool) {
+        match token {
+            Token::Print(ch) => {
+                let width = ch.width().unwrap_or(0) as u16;
+                self.x = self.x.saturating_add(width);
+            }
+            Token::Control(b'\n') => {
+                self.y = self.y.saturating_add(1);
+                if !raw {
+                    self.x = 0;
+                }
+            }
+            Token::Control(b'\r') => self.x = 0,
+            Token::Control(0x08) => self.x = self.x.saturating_sub(1),
+            Token::Control(b'\t') => self.x = (self.x / 8).saturating_add(1).saturating_mul(8),
+            Token::Control(_) => {}
+            Token::Esc(b'7') => self.saved = (self.x, self.y),
+            Token::Esc(b'8') => (self.x, self.y) = self.saved,
+            Token::Esc(_) => {}
+            Token::Csi {
+                params,
+                private: false,
+                action,
+            } => {
+                let n = Token::param(params, 0, 1);
+                match action {
+                    b'H' | b'f' => {
+                        self.y = Token::param(params, 0, 1) - 1;
+                        self.x = Token::param(params, 1, 1) - 1;
+                    }
+                    b'A' => self.y = self.y.saturating_sub(n),
+                    b'B' => self.y = self.y.saturating_add(n),
+                    b'C' => self.x = self.x.saturating_add(n),
+                    b'D' => self.x = self.x.saturating_sub(n),
+                    b'E' => {
+                        self.y = self.y.saturating_add(n);
+                        self.x = 0;
+                    }
+                    b'F' => {
+                        self.y = self.y.saturating_sub(n);
+                        self.x = 0;
+                    }
+                    b'G' => self.x = n - 1,
+                    b'd' => self.y = n - 1,
+                    b's' => self.saved = (self.x, self.y),
+                    b'u' => (self.x, self.y) = self.saved,
+                    _ => {}
+                }
+            }
+            Token::Csi { .. } => {}
+        }
+    }
+}
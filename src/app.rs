@@ -9,32 +9,147 @@
 //! # Methods
 //!
 //! - `new(fps: u64)`: Creates a new `App` instance with a specified frames per second (FPS) value. The FPS cannot be 0, as it will default to 1.
+//! - `with_backend(fps: u64, backend)`: Creates an `App` drawing to another output device than the terminal, e.g. a `TestBackend` (see the `backend` module).
+//! - `size()`: Returns the screen size of the backend.
 //! - `alternate_screen()`: Enables the alternate screen (similar to full-screen mode) for the terminal.
 //! - `clear()`: Enables the feature to clear the terminal screen on each frame.
+//! - `buffered()`: Draws every frame into a cell buffer and writes only the cells that changed since the previous frame.
+//! - `skip_unchanged()` / `watch(scene)` / `request_redraw()`: Skips frames in which no watched scene changed and the terminal kept its size / watches a scene for changes / draws the next frame anyway.
 //! - `raw_mode()`: Enables raw mode, which disables input buffering and line editing.
 //! - `cursor()`: Controls the visibility of the terminal cursor.
+//! - `set_cursor_visible(visible: bool)`: Shows or hides the cursor at runtime, between frames.
+//! - `copy_to_clipboard(text)` / `request_clipboard()`: Writes to / asks for the system clipboard through the terminal (OSC 52).
+//! - `cursor_pos()`: Returns the software-tracked cursor position, updated by everything written through the `App`.
+//! - `move_cursor(moveto: Cursor)`: Moves the cursor through the `App`, keeping `cursor_pos()` up to date.
 //! - `fps(fps: u64)`: Sets the frames per second for terminal updates.
+//! - `clock(clock)` / `now()`: Sets the clock that measures the frames and paces them, e.g. a `MockClock` for tests and replays / returns its time.
+//! - `frame()`: Returns the number of the current frame, for animations driven by the frame clock.
+//! - `delta_time()`: Returns the time since the previous frame, for animations that advance by elapsed time.
+//! - `profile_frames()` / `profile()`: Measures the time spent in each phase of every frame, and returns the timings.
+//! - `error_overlay()` / `report(result)` / `frame_errors()`: Collects the errors reported while drawing a frame and shows them in a red panel over it, for development.
+//! - `debug_hud()` / `toggle_debug_hud()` / `observe(event)`: Shows the FPS, frame time, objects, open layers, terminal size and last input event in a panel on top of every frame, toggled with F12, for development.
+//! - `debug_scene(objects)` / `debug_layers(layers)`: Tells the debug HUD how many objects and open layers the frame has.
+//! - `screenshot()`: Returns the cells of the last frame of a buffered `App`, to export them as ANSI text or HTML.
+//! - `stats()`: Returns the size of the frame buffers, the pending timers and the approximate memory of the `App`.
+//! - `scheduler()`: Returns the scheduler whose callbacks (only) run before each frame (e.g. `app.scheduler().after_fn(...)`).
+//! - `default_style(style: NyanStyle)`: Sets the style every object inherits, applied before clearing so the whole screen shares its background.
+//! - `default_keybindings(keybindings)` / `default_theme(theme)`: Sets the actions of the app with their default keys / the default styles.
+//! - `load_config(path)`: Changes the keybindings and the theme as a TOML config file says (requires the `toml` feature).
+//! - `keybindings()` / `action(event)` / `theme()`: Return the keybindings / the action an event triggers / the theme, as configured.
+//! - `min_size(width, height)`: Sets the smallest usable terminal size; smaller terminals show a "please enlarge" message instead of the frame.
+//! - `is_too_small()`: Returns whether the terminal was smaller than the minimum size in the last frame.
+//! - `mouse_capture()`: Enables mouse event reporting (`NyanEvent::Mouse`).
+//! - `bracketed_paste()`: Enables bracketed paste, so pasted text arrives as a single `NyanEvent::Paste`.
+//! - `focus_change()`: Enables focus event reporting (`NyanEvent::FocusGained` / `NyanEvent::FocusLost`).
+//! - `draw_with(func: F)`: Same as `draw`, but passes the `App` to the closure so objects can be drawn through its writer.
 //! - `draw(func: F)`: Executes the drawing function (`func`), managing terminal settings like alternate screen, raw mode, cursor visibility, clearing the screen, and enforcing the FPS.
+//! - `run(state, events, draw, update)`: Runs the loop of an app: draws a frame, waits for the next event and handles it, until told to stop or the events end.
 //! - `exit()`: Exits the terminal drawing mode, restoring the original screen and cursor visibility.
 
-use anyhow::Result;
-use crossterm::{cursor, execute, terminal};
+use crossterm::{cursor, event, execute, queue, terminal};
 
-use std::{fmt::Debug, io, thread, time::Duration};
+use std::{
+    convert::Infallible,
+    fmt::Debug,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
 
-use crate::errors;
+use crate::ansi::{AnsiParser, VirtualCursor};
+use crate::backend::{Backend, StdoutBackend};
+use crate::border;
+use crate::buffer::Buffer;
+use crate::clipboard;
+use crate::clock::{Clock, SystemClock};
+use crate::color::NyanColor;
+use crate::cursor::Cursor;
+use crate::errors::{self, NyanError, NyanResult};
+use crate::event::{EventSource, NyanEvent};
+use crate::input::NyanInput;
+use crate::keybindings::Keybindings;
+use crate::layer::FloatLayer;
+use crate::layout::{self, Align};
+use crate::nyan_obj;
+use crate::profiler::{FrameTimings, Profiler};
+use crate::scheduler::Scheduler;
+use crate::stats::{AppStats, SceneStats};
+use crate::style::NyanStyle;
+use crate::theme::Theme;
 
 /// `NyanTerminal` is a struct that handles terminal control and drawing.
 /// It supports functionalities like enabling alternate screens, clearing the terminal,
 /// enabling raw mode, and controlling the cursor visibility and FPS.
 pub struct App {
-    stdout: io::Stdout,
+    /// The output device, written once per frame.
+    backend: Box<dyn Backend>,
     alternatescreen: bool,
     clear: bool,
     rawmode: bool,
     cursor: bool,
+    mousecapture: bool,
+    bracketedpaste: bool,
+    focuschange: bool,
     fps: u64,
     looped: bool,
+    frame: u64,
+    last_frame: Option<Instant>,
+    delta: Duration,
+    scheduler: Scheduler<Infallible>,
+    style: NyanStyle,
+    min_size: Option<(u16, u16)>,
+    too_small: bool,
+    parser: AnsiParser,
+    vcursor: VirtualCursor,
+    buffered: bool,
+    /// The cells the terminal shows.
+    front: Buffer,
+    /// The cells of the frame being drawn.
+    back: Buffer,
+    /// Whether a frame is being drawn, during which flushing is left to its end.
+    in_frame: bool,
+    profiler: Option<Profiler>,
+    /// When the previous frame was done, to measure the time between frames.
+    frame_end: Option<Instant>,
+    /// The time the drawing closure of the current frame took.
+    closure_time: Duration,
+    skip_unchanged: bool,
+    /// Whether the next frame is drawn even if nothing changed.
+    redraw: bool,
+    /// The keys and generations of the scenes watched before the last frame.
+    scenes: Vec<(u64, u64)>,
+    /// The keys and generations of the scenes watched since the last frame.
+    watched: Vec<(u64, u64)>,
+    /// The terminal size of the last frame drawn.
+    last_size: Option<(u16, u16)>,
+    /// Whether the errors reported during a frame are drawn over it.
+    error_overlay: bool,
+    /// The errors reported since the last frame ended.
+    errors: Vec<NyanError<'static>>,
+    /// The errors reported during the last frame.
+    frame_errors: Vec<NyanError<'static>>,
+    /// The areas `(x, y, width, height)` covered by the error overlay and the debug HUD in the
+    /// last frame.
+    overlays: Vec<(u16, u16, u16, u16)>,
+    /// The debug HUD and what it shows, if enabled.
+    hud: Option<DebugHud>,
+    /// The time the last frame took to draw and render.
+    frame_time: Duration,
+    /// Tells the time of the frames and waits between them.
+    clock: Box<dyn Clock>,
+    /// The actions of the app and their keys, as configured.
+    keybindings: Keybindings,
+    /// The styles of the app, as configured.
+    theme: Theme,
+}
+
+/// What the debug HUD of an `App` shows besides the app's own timings.
+#[derive(Debug, Default)]
+struct DebugHud {
+    shown: bool,
+    /// The last input event, described.
+    event: Option<String>,
+    scene: Option<SceneStats>,
+    layers: Option<usize>,
 }
 
 impl Debug for App {
@@ -47,21 +162,45 @@ impl Debug for App {
         fmt.debug_struct("NyanTerminal")
             .field("alternate_screen", &self.alternatescreen)
             .field("clear", &self.clear)
+            .field("buffered", &self.buffered)
             .field("raw_mode", &self.rawmode)
             .field("cursor", &cursor_state)
+            .field("mouse_capture", &self.mousecapture)
+            .field("bracketed_paste", &self.bracketedpaste)
+            .field("focus_change", &self.focuschange)
+            .field("default_style", &self.style)
+            .field("min_size", &self.min_size)
+            .field("cursor_pos", &self.cursor_pos())
+            .field("frame", &self.frame)
             .finish()
     }
 }
 
 impl io::Write for App {
     /// Writes bytes to the terminal output.
+    ///
+    /// The written bytes also update the software-tracked cursor position (see [`App::cursor_pos`]).
+    /// A buffered `App` (see [`App::buffered`]) draws them into the frame's cells instead.
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.stdout.write(buf)
+        let written = if self.buffered {
+            self.back.write(buf)?
+        } else {
+            self.backend.write(buf)?
+        };
+        let (vcursor, raw) = (&mut self.vcursor, self.rawmode);
+        self.parser
+            .feed(&buf[..written], |token| vcursor.apply(&token, raw));
+        Ok(written)
     }
 
     /// Flushes the output buffer to ensure all data is written.
+    ///
+    /// While a frame is drawn, the output is flushed once at its end instead.
     fn flush(&mut self) -> io::Result<()> {
-        self.stdout.flush()
+        if self.in_frame {
+            return Ok(());
+        }
+        self.backend.flush()
     }
 }
 
@@ -75,14 +214,57 @@ impl App {
     /// # Returns
     /// A new `NyanTerminal` instance.
     pub fn new(fps: u64) -> Self {
+        Self::with_backend(fps, StdoutBackend::new())
+    }
+
+    /// Creates an `App` drawing to `backend` instead of the terminal of the process, e.g. a
+    /// [`TestBackend`](crate::backend::TestBackend) in tests or a
+    /// [`WriterBackend`](crate::backend::WriterBackend) around a socket.
+    ///
+    /// # Returns
+    /// A new `App` with the given FPS, drawing to `backend`.
+    pub fn with_backend<B: Backend + 'static>(fps: u64, backend: B) -> Self {
         Self {
-            stdout: io::stdout(),
+            backend: Box::new(backend),
             alternatescreen: false,
             clear: false,
             rawmode: false,
             cursor: false,
+            mousecapture: false,
+            bracketedpaste: false,
+            focuschange: false,
             fps: fps.max(1), // Prevents FPS from being 0
             looped: false,
+            frame: 0,
+            last_frame: None,
+            delta: Duration::ZERO,
+            scheduler: Scheduler::new(),
+            style: NyanStyle::default(),
+            min_size: None,
+            too_small: false,
+            parser: AnsiParser::new(),
+            vcursor: VirtualCursor::default(),
+            buffered: false,
+            front: Buffer::new(0, 0),
+            back: Buffer::new(0, 0),
+            in_frame: false,
+            profiler: None,
+            frame_end: None,
+            closure_time: Duration::ZERO,
+            skip_unchanged: false,
+            redraw: false,
+            scenes: Vec::new(),
+            watched: Vec::new(),
+            last_size: None,
+            error_overlay: false,
+            errors: Vec::new(),
+            frame_errors: Vec::new(),
+            overlays: Vec::new(),
+            hud: None,
+            frame_time: Duration::ZERO,
+            clock: Box::new(SystemClock),
+            keybindings: Keybindings::new(),
+            theme: Theme::default(),
         }
     }
 
@@ -106,6 +288,71 @@ impl App {
         nyan
     }
 
+    /// Draws every frame into a buffer of cells instead of the terminal.
+    ///
+    /// At the end of a frame the buffer is compared with the previous frame, and only the
+    /// cells that changed are written, with as few cursor movements and style changes as
+    /// possible (see [`Buffer::diff`]). With [`App::clear`], the buffer is cleared instead of
+    /// the terminal, so the screen no longer flickers. Output written directly to
+    /// `std::io::stdout()` bypasses the buffer and may be overwritten.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance drawing through a buffer.
+    pub fn buffered(self) -> Self {
+        let mut nyan = self;
+        nyan.buffered = true;
+        nyan
+    }
+
+    /// Skips the frames in which nothing changed, for dashboards that sit idle most of the
+    /// time.
+    ///
+    /// A frame is drawn if a scene passed to [`App::watch`] was marked dirty since the
+    /// previous frame (by adding, moving, styling or changing its objects, by animations and
+    /// layouts, or with [`NyanObj::mark_dirty`](crate::nyan_obj::NyanObj::mark_dirty)), if
+    /// the terminal was resized, or after [`App::request_redraw`]. Otherwise the drawing
+    /// closure is not called and nothing is written; the frame is only paced and the
+    /// scheduler advanced. Frames are never skipped with [`App::clear`], as the screen would
+    /// stay blank, nor when no scene was watched since the previous frame, as the `App` cannot
+    /// tell whether anything changed.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance skipping unchanged frames.
+    pub fn skip_unchanged(self) -> Self {
+        let mut nyan = self;
+        nyan.skip_unchanged = true;
+        nyan
+    }
+
+    /// Draws the next frame even if no object changed (see [`App::skip_unchanged`]), e.g.
+    /// after drawing something that is not an object.
+    pub fn request_redraw(&mut self) {
+        self.redraw = true;
+    }
+
+    /// Draws the next frame if `scene` changed since the previous frame (see
+    /// [`App::skip_unchanged`]). Call it for every scene the app draws, before each frame,
+    /// e.g. after handling an event. A scene that was not watched before the previous frame
+    /// counts as changed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut nyan = App::new(30).skip_unchanged();
+    /// loop {
+    ///     nyan.watch(&obj);
+    ///     nyan.draw_with(|out| obj.draw_all_in(out).unwrap())?;
+    /// }
+    /// ```
+    pub fn watch(&mut self, scene: &nyan_obj::NyanObj) {
+        let seen = (scene.scene(), scene.generation());
+        if !self.scenes.contains(&seen) {
+            self.redraw = true;
+        }
+        if !self.watched.contains(&seen) {
+            self.watched.push(seen);
+        }
+    }
+
     /// Enables raw mode (disables input buffering and line editing).
     ///
     /// # Returns
@@ -128,6 +375,504 @@ impl App {
         nyan
     }
 
+    /// Shows or hides the cursor at runtime.
+    ///
+    /// Unlike [`App::hide_cursor`], which configures the cursor when the `App` is built, this can be
+    /// called between frames, e.g. to show the cursor in a text-input mode and hide it again
+    /// in a game mode. The change is applied immediately and kept for the following frames.
+    ///
+    /// # Arguments
+    /// - `visible`: Whether the cursor should be visible.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or failure of the operation.
+    pub fn set_cursor_visible(&mut self, visible: bool) -> NyanResult<()> {
+        self.cursor = !visible;
+        if visible {
+            execute!(self.backend, cursor::Show)?;
+        } else {
+            execute!(self.backend, cursor::Hide)?;
+        }
+        Ok(())
+    }
+
+    /// Puts `text` on the system clipboard through the terminal, with the OSC 52 escape
+    /// sequence (see the [`clipboard`](crate::clipboard) module). This also works over SSH,
+    /// but terminals without OSC 52 support ignore it.
+    ///
+    /// # Returns
+    /// A `Result` indicating whether the sequence was written.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let NyanEvent::Key(NyanInput::Key(NyanKey::Y)) = event {
+    ///     app.copy_to_clipboard(&selected_line)?;
+    /// }
+    /// ```
+    pub fn copy_to_clipboard(&mut self, text: &str) -> NyanResult<()> {
+        self.write_sequence(&clipboard::copy_sequence(text))
+    }
+
+    /// Asks the terminal for the contents of the system clipboard. Terminals that allow it
+    /// answer on the input, which a [remote session](crate::remote) reports as
+    /// `NyanEvent::Paste`.
+    ///
+    /// # Returns
+    /// A `Result` indicating whether the request was written.
+    pub fn request_clipboard(&mut self) -> NyanResult<()> {
+        self.write_sequence(clipboard::REQUEST)
+    }
+
+    /// Writes an escape sequence straight to the backend, past the frame buffer, flushing it
+    /// unless a frame is being drawn.
+    fn write_sequence(&mut self, sequence: &str) -> NyanResult<()> {
+        self.backend.write_all(sequence.as_bytes())?;
+        if !self.in_frame {
+            self.backend.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the cursor is currently configured to be visible.
+    pub fn cursor_visible(&self) -> bool {
+        !self.cursor
+    }
+
+    /// Returns the software-tracked cursor position `(x, y)`.
+    ///
+    /// The position is computed from everything written through the `App` (text, newlines and
+    /// cursor movements) instead of querying the terminal, so it is cheap to call at any time.
+    /// It is reset to `(0, 0)` at the start of every [`App::draw`]. Output written directly to
+    /// `std::io::stdout()` bypasses the `App` and is not tracked.
+    ///
+    /// # Example
+    /// ```ignore
+    /// write!(nyan, "Score: {score}")?;
+    /// let (x, y) = nyan.cursor_pos();
+    /// nyan.move_cursor(Cursor::Move(x + 2, y))?; // draw the next object after the score
+    /// ```
+    pub fn cursor_pos(&self) -> (u16, u16) {
+        (self.vcursor.x, self.vcursor.y)
+    }
+
+    /// Returns the number of the current frame.
+    ///
+    /// It is `0` during the first call to [`App::draw`] / [`App::draw_with`] and counts up by
+    /// one with every frame, so animations (e.g. [`Rainbow`](crate::gradient::Rainbow)) can
+    /// be driven by the frame clock.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Sets the clock the `App` reads the time from and waits on between frames, which drives
+    /// [`App::delta_time`], the scheduler and the FPS. A
+    /// [`MockClock`](crate::clock::MockClock) makes every frame last exactly `1 / fps`
+    /// without waiting, so tests and replays draw the same frames every time.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance using the clock.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use nyan::app::App;
+    /// use nyan::backend::TestBackend;
+    /// use nyan::clock::MockClock;
+    ///
+    /// let clock = MockClock::new();
+    /// let mut app = App::with_backend(10, TestBackend::new(8, 1)).clock(clock.clone());
+    /// app.draw(|| ()).unwrap();
+    /// app.draw(|| ()).unwrap();
+    /// assert_eq!(app.delta_time(), Duration::from_millis(100));
+    /// assert_eq!(clock.elapsed(), Duration::from_millis(200));
+    /// ```
+    pub fn clock<C: Clock + 'static>(self, clock: C) -> Self {
+        let mut nyan = self;
+        nyan.clock = Box::new(clock);
+        nyan
+    }
+
+    /// Returns the current time of the clock of the `App` (see [`App::clock`]), e.g. for
+    /// [`Notifications::update_at`](crate::notification::Notifications::update_at).
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Returns the time between the start of the previous frame and the start of the current
+    /// one, `Duration::ZERO` during the first frame.
+    ///
+    /// Animations such as [`Tween`](crate::animation::Tween) advance by it, so they run at the
+    /// same speed whatever the FPS and however long drawing takes.
+    pub fn delta_time(&self) -> Duration {
+        self.delta
+    }
+
+    /// Measures the time spent in each phase of every frame: between frames (polling for
+    /// input), in the drawing closure, rendering and flushing. The timings are available from
+    /// [`App::profile`].
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance measuring its frames.
+    pub fn profile_frames(self) -> Self {
+        let mut nyan = self;
+        nyan.profiler = Some(Profiler::new());
+        nyan
+    }
+
+    /// Returns the timings of the frames, or `None` unless the `App` measures them (see
+    /// [`App::profile_frames`]).
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let Some(profile) = app.profile() {
+    ///     status.set_right(profile.summary());
+    /// }
+    /// ```
+    pub fn profile(&self) -> Option<&Profiler> {
+        self.profiler.as_ref()
+    }
+
+    /// Returns the timings of the frames for changes, e.g. to reset them after loading a level.
+    pub fn profile_mut(&mut self) -> Option<&mut Profiler> {
+        self.profiler.as_mut()
+    }
+
+    /// Shows the errors reported with [`App::report`] during a frame in a red panel at the
+    /// bottom of the screen, drawn over the frame. Meant for development: a missing object or
+    /// a failed cursor movement becomes visible without aborting the frame.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance drawing the error overlay.
+    pub fn error_overlay(self) -> Self {
+        let mut nyan = self;
+        nyan.error_overlay = true;
+        nyan
+    }
+
+    /// Reports the error of `result`, if any, and returns its value. The errors reported while
+    /// a frame is drawn are returned by [`App::frame_errors`] afterwards, and drawn over the
+    /// frame with [`App::error_overlay`].
+    ///
+    /// # Returns
+    ///
+    /// - `Some(T)` with the value of `result` if it is `Ok`.
+    /// - `None` if it is an error, which is kept for the frame.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut app = App::new(30).buffered().error_overlay();
+    /// app.draw_with(|app| {
+    ///     let result = obj.draw_object_in(app, "title"); // a typo in the ID
+    ///     app.report(result);
+    /// })?;
+    /// ```
+    pub fn report<T>(&mut self, result: Result<T, NyanError<'_>>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.errors.push(error.into_owned());
+                None
+            }
+        }
+    }
+
+    /// Returns the errors reported with [`App::report`] while the last frame was drawn (or
+    /// before, since the frame before it).
+    pub fn frame_errors(&self) -> &[NyanError<'static>] {
+        &self.frame_errors
+    }
+
+    /// Draws the reported errors in a red box along the bottom of a screen of `size` cells,
+    /// leaving the cursor where it was.
+    fn draw_errors(&mut self, size: (u16, u16)) -> io::Result<()> {
+        let (width, height) = size;
+        if width < 4 || height < 3 {
+            return Ok(());
+        }
+        let count = self.errors.len();
+        let rows = count.min((height as usize / 2).max(3) - 2);
+        let title = match count {
+            1 => " 1 error ".to_string(),
+            _ => format!(" {count} errors "),
+        };
+        let lines: Vec<String> = self
+            .errors
+            .iter()
+            .enumerate()
+            .take(rows)
+            .map(|(row, error)| {
+                if row + 1 == rows && count > rows {
+                    format!("... and {} more", count - row)
+                } else {
+                    error.to_string()
+                }
+            })
+            .collect();
+        let style = NyanStyle::new()
+            .fg(NyanColor::BrightWhite)
+            .bg(NyanColor::Red)
+            .bold();
+        let top = height - lines.len() as u16 - 2;
+        self.draw_panel((0, top), width, &title, &lines, style)
+    }
+
+    /// Draws the debug HUD in a blue box at the top right of a screen of `size` cells,
+    /// leaving the cursor where it was.
+    fn draw_hud(&mut self, size: (u16, u16)) -> io::Result<()> {
+        let (width, height) = size;
+        let Some(hud) = &self.hud else {
+            return Ok(());
+        };
+        let fps = match self.delta.as_secs_f64() {
+            secs if secs > 0.0 => format!("{:.1}", 1.0 / secs),
+            _ => "-".to_string(),
+        };
+        let mut lines = vec![
+            format!("fps     {fps} (target {})", self.fps),
+            format!("frame   {:.1} ms", self.frame_time.as_secs_f64() * 1000.0),
+        ];
+        if let Some(scene) = hud.scene {
+            lines.push(format!(
+                "objects {} ({} visible)",
+                scene.objects, scene.visible
+            ));
+        }
+        if let Some(layers) = hud.layers {
+            lines.push(format!("layers  {layers} open"));
+        }
+        lines.push(format!("size    {width}x{height}"));
+        lines.push(format!("event   {}", hud.event.as_deref().unwrap_or("-")));
+
+        let inner = lines.iter().map(|line| line.chars().count() + 2).max();
+        let panel = (inner.unwrap_or(0) as u16 + 2).min(width);
+        if panel < 4 || height < lines.len() as u16 + 2 {
+            return Ok(());
+        }
+        let style = NyanStyle::new()
+            .fg(NyanColor::BrightWhite)
+            .bg(NyanColor::Blue);
+        self.draw_panel((width - panel, 0), panel, " debug ", &lines, style)
+    }
+
+    /// Draws `lines` in a bordered box of `width` cells at `pos`, with `title` on its top
+    /// border, and remembers the area to erase it in the next frame. Leaves the cursor where
+    /// it was.
+    fn draw_panel(
+        &mut self,
+        pos: (u16, u16),
+        width: u16,
+        title: &str,
+        lines: &[String],
+        style: NyanStyle,
+    ) -> io::Result<()> {
+        let inner = width as usize - 2;
+        let set = border::default_border().set();
+        let title = layout::clip_text(title, 0, inner);
+        let mut rows = vec![format!(
+            "{}{title}{}{}",
+            set.top_left,
+            set.horizontal
+                .to_string()
+                .repeat(inner - title.chars().count()),
+            set.top_right
+        )];
+        for line in lines {
+            let text = layout::clip_text(&format!(" {line}"), 0, inner);
+            rows.push(format!(
+                "{}{}{}",
+                set.vertical,
+                layout::align_text(&text, inner as u16, Align::Start),
+                set.vertical
+            ));
+        }
+        rows.push(format!(
+            "{}{}{}",
+            set.bottom_left,
+            set.horizontal.to_string().repeat(inner),
+            set.bottom_right
+        ));
+
+        let (x, y) = self.cursor_pos();
+        let (left, top) = pos;
+        self.overlays.push((left, top, width, rows.len() as u16));
+        for (row, line) in rows.iter().enumerate() {
+            Cursor::Move(left, top + row as u16).queue(self)?;
+            style.apply(self)?;
+            write!(self, "{line}")?;
+            NyanStyle::reset(self)?;
+        }
+        Cursor::Move(x, y).queue(self)
+    }
+
+    /// Blanks the areas the error overlay and the debug HUD covered in the last frame, as
+    /// objects drawn without clearing the screen only overwrite themselves.
+    fn erase_overlays(&mut self) -> io::Result<()> {
+        if self.overlays.is_empty() {
+            return Ok(());
+        }
+        NyanStyle::reset(self)?;
+        for (x, top, width, rows) in std::mem::take(&mut self.overlays) {
+            for y in top..top.saturating_add(rows) {
+                Cursor::Move(x, y).queue(self)?;
+                write!(self, "{}", " ".repeat(width as usize))?;
+            }
+        }
+        Cursor::Move(0, 0).queue(self)
+    }
+
+    /// Shows a debug HUD in the top right corner of every frame, drawn over it: the FPS, the
+    /// time the last frame took, the objects and open layers (see [`App::debug_scene`] and
+    /// [`App::debug_layers`]), the terminal size and the last input event. F12 hides and
+    /// shows it again in [`App::run`], or wherever the events are passed to [`App::observe`].
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance drawing the debug HUD.
+    pub fn debug_hud(self) -> Self {
+        let mut nyan = self;
+        nyan.hud = Some(DebugHud {
+            shown: true,
+            ..DebugHud::default()
+        });
+        nyan
+    }
+
+    /// Hides the debug HUD if it is shown, and shows it if it is hidden. Does nothing unless
+    /// it is enabled with [`App::debug_hud`].
+    pub fn toggle_debug_hud(&mut self) {
+        if let Some(hud) = &mut self.hud {
+            hud.shown = !hud.shown;
+            self.redraw = true;
+        }
+    }
+
+    /// Returns whether the debug HUD is drawn over the frames.
+    pub fn is_debug_hud_shown(&self) -> bool {
+        self.hud.as_ref().is_some_and(|hud| hud.shown)
+    }
+
+    /// Shows `event` as the last input event in the debug HUD, and toggles the HUD if it is
+    /// F12. [`App::run`] does this for every event; loops of their own call it before
+    /// handling an event.
+    ///
+    /// # Returns
+    /// `true` if the event toggled the HUD and should not be handled further.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let event = NyanEvent::get_event()?;
+    /// if app.observe(&event) {
+    ///     continue;
+    /// }
+    /// ```
+    pub fn observe<U>(&mut self, event: &NyanEvent<U>) -> bool {
+        let Some(hud) = &mut self.hud else {
+            return false;
+        };
+        let text = match event {
+            NyanEvent::Key(NyanInput::FunctionKey(12)) => {
+                self.toggle_debug_hud();
+                return true;
+            }
+            NyanEvent::Key(key) => format!("{key:?}"),
+            NyanEvent::Mouse(mouse) => format!("{mouse:?}"),
+            NyanEvent::Resize(width, height) => format!("Resize({width}, {height})"),
+            NyanEvent::Paste(text) => format!("Paste({} chars)", text.chars().count()),
+            NyanEvent::FocusGained => "FocusGained".to_string(),
+            NyanEvent::FocusLost => "FocusLost".to_string(),
+            NyanEvent::User(_) => "User".to_string(),
+            // Ticks arrive all the time and would hide the last real input.
+            NyanEvent::Tick => return false,
+        };
+        hud.event = Some(text.replace("NyanInput::", "").replace("NyanKey::", ""));
+        false
+    }
+
+    /// Shows the number of objects of `objects` in the debug HUD, e.g. at the start of the
+    /// drawing closure. The objects are not counted while the HUD is hidden.
+    pub fn debug_scene(&mut self, objects: &nyan_obj::NyanObj) {
+        if let Some(hud) = self.hud.as_mut().filter(|hud| hud.shown) {
+            hud.scene = Some(objects.stats());
+        }
+    }
+
+    /// Shows the number of open floats of `layers` in the debug HUD.
+    pub fn debug_layers(&mut self, layers: &FloatLayer) {
+        if let Some(hud) = &mut self.hud {
+            hud.layers = Some(layers.open_count());
+        }
+    }
+
+    /// Returns the cells of the last drawn frame, e.g. to export it with
+    /// [`Buffer::to_ansi`] or [`Buffer::to_html`] for docs and bug reports.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&Buffer)` if the `App` is buffered (see [`App::buffered`]).
+    /// - `None` otherwise, as the frames are written to the terminal right away.
+    ///
+    /// # Example
+    /// ```ignore
+    /// app.draw_with(|app| obj.draw_all_in(app).unwrap())?;
+    /// if let Some(frame) = app.screenshot() {
+    ///     std::fs::write("screenshot.html", frame.to_html())?;
+    /// }
+    /// ```
+    pub fn screenshot(&self) -> Option<&Buffer> {
+        self.buffered.then_some(&self.front)
+    }
+
+    /// Counts the cells of the frame buffers, the pending timers and the approximate memory of
+    /// the `App`. The objects of a scene are counted by
+    /// [`NyanObj::stats`](crate::nyan_obj::NyanObj::stats).
+    ///
+    /// See [`AppStats`].
+    pub fn stats(&self) -> AppStats {
+        let cells = |buffer: &Buffer| {
+            let (width, height) = buffer.size();
+            width as usize * height as usize
+        };
+        AppStats {
+            buffer_cells: if self.buffered {
+                cells(&self.front) + cells(&self.back)
+            } else {
+                0
+            },
+            timers: self.scheduler.len(),
+            frames: self.frame,
+            memory: std::mem::size_of::<Self>() + self.front.heap_size() + self.back.heap_size(),
+        }
+    }
+
+    /// Returns the scheduler of the `App`. It is advanced by [`App::delta_time`] at the start of
+    /// every frame, before the drawing closure runs, so its callbacks fire during the frame
+    /// loop.
+    ///
+    /// Only callbacks can be scheduled here (`after_fn` / `every_fn`): the `App` has no event
+    /// queue to deliver events to, so its event type is uninhabited and `after` / `every` cannot
+    /// be called. Use a [`Scheduler`] of your own with [`Scheduler::advance_into`] for events.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let saved = Rc::new(Cell::new(false));
+    /// let flag = saved.clone();
+    /// app.scheduler().every_fn(Duration::from_secs(30), move || flag.set(true));
+    /// ```
+    pub fn scheduler(&mut self) -> &mut Scheduler<Infallible> {
+        &mut self.scheduler
+    }
+
+    /// Moves the cursor through the `App`'s output, keeping [`App::cursor_pos`] up to date.
+    ///
+    /// # Arguments
+    /// - `moveto`: The cursor operation to execute.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or failure of the operation.
+    pub fn move_cursor(&mut self, moveto: Cursor) -> NyanResult<()> {
+        Cursor::move_cursor_in(self, moveto)
+    }
+
     /// Chenge fps.
     ///
     /// # Returns
@@ -138,6 +883,170 @@ impl App {
         nyan
     }
 
+    /// Sets the default style of the screen.
+    ///
+    /// Every object inherits this style unless its own style overrides it (see
+    /// [`NyanStyle::patch`]). The style is applied before the screen is cleared, so with
+    /// [`App::clear`] the whole screen gets a consistent background color.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance with the default style set.
+    pub fn default_style(self, style: NyanStyle) -> Self {
+        let mut nyan = self;
+        nyan.style = style;
+        nyan
+    }
+
+    /// Sets the actions of the application with their default keys. A config file loaded with
+    /// [`App::load_config`] can only bind these actions.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance with the keybindings set.
+    pub fn default_keybindings(self, keybindings: Keybindings) -> Self {
+        let mut nyan = self;
+        nyan.keybindings = keybindings;
+        nyan
+    }
+
+    /// Sets the default styles of the application, which a config file loaded with
+    /// [`App::load_config`] can change.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance with the theme set.
+    pub fn default_theme(self, theme: Theme) -> Self {
+        let mut nyan = self;
+        nyan.theme = theme;
+        nyan
+    }
+
+    /// Changes the keybindings and the theme as the TOML config file at `path` says, so end
+    /// users can customize the application without recompiling. A missing file keeps the
+    /// defaults. Requires the `toml` feature; see the `config` module for the format.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` if the file was read, `Ok(false)` if it does not exist.
+    /// - `Err(NyanError::InvalidConfig)` if it is invalid, e.g. binds an unknown action. The
+    ///   keybindings and the theme are left unchanged then.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let keys = Keybindings::new()
+    ///     .bind("quit", NyanInput::Esc)
+    ///     .bind("save", NyanInput::Ctrl(NyanKey::S));
+    /// let mut app = App::new(30).default_keybindings(keys).default_theme(Theme::detect());
+    /// app.load_config("nyan-app.toml")?;
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn load_config<P: AsRef<std::path::Path>>(&mut self, path: P) -> NyanResult<bool> {
+        let mut config = crate::config::Config::new(self.keybindings.clone(), self.theme);
+        let loaded = config.load(path)?;
+        self.keybindings = config.keybindings;
+        self.theme = config.theme;
+        Ok(loaded)
+    }
+
+    /// Returns the actions of the application and their keys, as configured.
+    pub fn keybindings(&self) -> &Keybindings {
+        &self.keybindings
+    }
+
+    /// Returns the action `event` triggers with the configured keys, or `None`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// match app.action(&event) {
+    ///     Some("quit") => return false,
+    ///     Some("save") => document.save()?,
+    ///     _ => {}
+    /// }
+    /// ```
+    pub fn action<U>(&self, event: &NyanEvent<U>) -> Option<&str> {
+        self.keybindings.action_for(event)
+    }
+
+    /// Returns the styles of the application, as configured.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Sets the smallest terminal size the application can be drawn in.
+    ///
+    /// While the terminal is smaller, [`App::draw`] / [`App::draw_with`] skip the drawing
+    /// closure and show a centered message asking to enlarge the terminal to `width` x
+    /// `height` instead of a corrupted layout. Normal drawing resumes with the first frame
+    /// after the terminal was resized to fit.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance with the minimum size set.
+    pub fn min_size(self, width: u16, height: u16) -> Self {
+        let mut nyan = self;
+        nyan.min_size = Some((width, height));
+        nyan
+    }
+
+    /// Returns whether the terminal was smaller than the minimum size (see [`App::min_size`])
+    /// in the last frame, so its drawing closure was skipped.
+    pub fn is_too_small(&self) -> bool {
+        self.too_small
+    }
+
+    /// Writes the "terminal too small" screen for a terminal of `size` cells, centered.
+    pub(crate) fn draw_too_small<W: Write>(
+        out: &mut W,
+        size: (u16, u16),
+        min: (u16, u16),
+    ) -> io::Result<()> {
+        let (width, height) = size;
+        let lines = [
+            "Terminal too small".to_string(),
+            format!("Please enlarge to {}x{}", min.0, min.1),
+            format!("(currently {width}x{height})"),
+        ];
+        let top = Align::Center.offset(height, lines.len() as u16);
+        for (row, line) in lines.iter().enumerate() {
+            let y = top + row as u16;
+            if y >= height {
+                break;
+            }
+            let line = layout::clip_text(line, 0, width as usize);
+            Cursor::Move(0, y).queue(out)?;
+            write!(out, "{}", layout::align_text(&line, width, Align::Center))?;
+        }
+        out.flush()
+    }
+
+    /// Enables mouse capture, so mouse actions are reported as `NyanEvent::Mouse`.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance with mouse capture enabled.
+    pub fn mouse_capture(self) -> Self {
+        let mut nyan = self;
+        nyan.mousecapture = true;
+        nyan
+    }
+
+    /// Enables bracketed paste, so pasted text is reported as a single `NyanEvent::Paste`
+    /// instead of a burst of key presses.
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance with bracketed paste enabled.
+    pub fn bracketed_paste(self) -> Self {
+        let mut nyan = self;
+        nyan.bracketedpaste = true;
+        nyan
+    }
+
+    /// Enables focus change reporting (`NyanEvent::FocusGained` / `NyanEvent::FocusLost`).
+    ///
+    /// # Returns
+    /// A new `NyanTerminal` instance with focus change reporting enabled.
+    pub fn focus_change(self) -> Self {
+        let mut nyan = self;
+        nyan.focuschange = true;
+        nyan
+    }
+
     /// Retrieves the current size of the terminal window.
     ///
     /// This function uses `crossterm` to get the terminal's width and height
@@ -145,7 +1054,7 @@ impl App {
     ///
     /// # Returns
     /// - `Ok((u16, u16))`: A tuple containing the terminal's width and height.
-    /// - `Err(anyhow::Error)`: If retrieving the terminal size fails.
+    /// - `Err(NyanError)`: If retrieving the terminal size fails.
     ///
     /// # Example
     /// ```
@@ -161,52 +1070,281 @@ impl App {
     ///
     /// # Errors
     /// This function will return an error if the terminal size cannot be determined.
-    pub fn get_terminal_size() -> anyhow::Result<(u16, u16)> {
+    pub fn get_terminal_size() -> NyanResult<(u16, u16)> {
         let (x, y) = crossterm::terminal::size()?;
         Ok((x, y))
     }
 
+    /// Returns the screen size of the backend in cells, which is the terminal size unless the
+    /// `App` was created with [`App::with_backend`].
+    pub fn size(&self) -> NyanResult<(u16, u16)> {
+        Ok(self.backend.size()?)
+    }
+
     /// Executes a function to draw the terminal content, handling setup and cleanup for terminal settings.
     /// It can manage alternate screens, raw mode, cursor visibility, clearing the terminal, and FPS control.
     ///
     /// # Arguments
     /// - `func`: A closure that handles the terminal drawing logic.
     ///
+    /// The closure draws to the terminal directly, e.g. with
+    /// [`NyanObj::draw_object`](crate::nyan_obj::NyanObj::draw_object), so what the `App` queued
+    /// for the frame (setting up and clearing the screen) is flushed before it runs.
+    ///
     /// # Returns
     /// A `Result` indicating success or failure of the operation.
-    pub fn draw<F: FnOnce()>(&mut self, func: F) -> Result<()> {
-        if let Err(e) = execute!(&self.stdout, cursor::MoveTo(0, 0)) {
-            return Err(errors::NyanError::DrawFailed(e.to_string().into()).into());
+    pub fn draw<F: FnOnce()>(&mut self, func: F) -> NyanResult<()> {
+        self.draw_with(|app| {
+            let flushed = app.backend.flush();
+            app.report(flushed.map_err(NyanError::from));
+            func()
+        })
+    }
+
+    /// Executes a function to draw the terminal content, passing the `App` itself to the closure.
+    ///
+    /// This behaves like [`App::draw`], but the closure receives the `App` so drawing can go
+    /// through its writer (the `App` implements `std::io::Write`), e.g. with
+    /// [`NyanObj::draw_object_in`](crate::nyan_obj::NyanObj::draw_object_in). Output written this
+    /// way keeps [`App::cursor_pos`] up to date, and is queued and written to the terminal with a
+    /// single flush at the end of the frame.
+    ///
+    /// # Arguments
+    /// - `func`: A closure that handles the terminal drawing logic.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or failure of the operation.
+    ///
+    /// # Example
+    /// ```ignore
+    /// nyan.draw_with(|out| {
+    ///     obj.draw_object_in(out, "text").unwrap();
+    /// })?;
+    /// ```
+    pub fn draw_with<F: FnOnce(&mut Self)>(&mut self, func: F) -> NyanResult<()> {
+        self.scenes = std::mem::take(&mut self.watched);
+        let changed = std::mem::take(&mut self.redraw);
+        // Without a watched scene there is no telling whether anything changed.
+        if self.skip_unchanged && !self.clear && self.looped && !self.scenes.is_empty() {
+            let size = self.backend.size()?;
+            if !changed && self.last_size == Some(size) {
+                self.tick();
+                self.pace();
+                return Ok(());
+            }
         }
 
+        let start = self.clock.now();
+        self.in_frame = true;
+        self.closure_time = Duration::ZERO;
+        // The default style is the base style during this App's frame only, so Apps drawing on
+        // other threads, or in between on this one, keep their own.
+        let base = NyanStyle::base();
+        NyanStyle::set_base(self.style);
+        let drawn = self.draw_frame(func);
+        NyanStyle::set_base(base);
+        let rendered = self.clock.now();
+        self.frame_time = rendered - start;
+        // Everything of the frame goes out at once.
+        self.in_frame = false;
+        self.backend.flush()?;
+        drawn?;
+
+        if let Some(profiler) = &mut self.profiler {
+            let end = self.clock.now();
+            profiler.record(FrameTimings {
+                input: self.frame_end.map_or(Duration::ZERO, |last| start - last),
+                closure: self.closure_time,
+                render: (rendered - start).saturating_sub(self.closure_time),
+                flush: end - rendered,
+            });
+        }
+
+        self.pace();
+        Ok(())
+    }
+
+    /// Runs the loop of an app: draws a frame with `draw`, waits for the next event of `events`
+    /// and hands it to `update`, over and over. Both closures get `state`, the data of the app,
+    /// and the `App` itself.
+    ///
+    /// The loop ends when `update` returns `false` or `events` ends, e.g. when the script of a
+    /// [`TestDriver`](crate::testing::TestDriver) is over.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` once the loop ended.
+    /// - An error if drawing or reading an event fails.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut events = EventQueue::<()>::new();
+    /// let mut count = 0;
+    /// app.run(
+    ///     &mut count,
+    ///     &mut events,
+    ///     |count, app| write!(app, "Pressed {count} times").unwrap(),
+    ///     |count, _, event| match event {
+    ///         NyanEvent::Key(NyanInput::Esc) => false,
+    ///         NyanEvent::Key(_) => {
+    ///             *count += 1;
+    ///             true
+    ///         }
+    ///         _ => true,
+    ///     },
+    /// )?;
+    /// ```
+    pub fn run<T, U, S, D, H>(
+        &mut self,
+        state: &mut T,
+        events: &mut S,
+        mut draw: D,
+        mut update: H,
+    ) -> NyanResult<()>
+    where
+        S: EventSource<U>,
+        D: FnMut(&mut T, &mut Self),
+        H: FnMut(&mut T, &mut Self, NyanEvent<U>) -> bool,
+    {
+        loop {
+            self.draw_with(|app| draw(state, app))?;
+            let Some(event) = events.next_event()? else {
+                return Ok(());
+            };
+            if self.observe(&event) {
+                continue;
+            }
+            if !update(state, self, event) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Measures the time since the previous frame and advances the scheduler by it.
+    fn tick(&mut self) {
+        let now = self.clock.now();
+        self.delta = self.last_frame.map_or(Duration::ZERO, |last| now - last);
+        self.last_frame = Some(now);
+        // Events cannot be scheduled on the App, so only callbacks fire.
+        self.scheduler.advance(self.delta);
+    }
+
+    /// Waits for the end of the frame.
+    fn pace(&mut self) {
+        // Convert FPS to milliseconds and sleep to maintain the FPS rate
+        let frame_duration = Duration::from_millis(1000 / self.fps);
+        self.clock.sleep(frame_duration);
+        self.frame_end = Some(self.clock.now());
+    }
+
+    /// Sets up the terminal and queues the output of a frame, without flushing it.
+    fn draw_frame<F: FnOnce(&mut Self)>(&mut self, func: F) -> NyanResult<()> {
+        if let Err(e) = queue!(self.backend, cursor::MoveTo(0, 0)) {
+            return Err(errors::NyanError::DrawFailed(e.to_string().into()));
+        }
+        self.vcursor.set(0, 0);
+
         if self.alternatescreen && !self.looped {
-            if let Err(e) = execute!(&self.stdout, terminal::EnterAlternateScreen) {
-                return Err(errors::NyanError::DrawFailed(e.to_string().into()).into());
+            if let Err(e) = queue!(self.backend, terminal::EnterAlternateScreen) {
+                return Err(errors::NyanError::DrawFailed(e.to_string().into()));
             }
         }
 
         if self.rawmode && !self.looped {
-            terminal::enable_raw_mode()?;
+            self.backend.enable_raw_mode()?;
+        }
+
+        if self.mousecapture && !self.looped {
+            queue!(self.backend, event::EnableMouseCapture)?;
+        }
+
+        if self.bracketedpaste && !self.looped {
+            queue!(self.backend, event::EnableBracketedPaste)?;
+        }
+
+        if self.focuschange && !self.looped {
+            queue!(self.backend, event::EnableFocusChange)?;
         }
 
         if !self.cursor {
-            execute!(&self.stdout, cursor::Show)?;
+            queue!(self.backend, cursor::Show)?;
         } else {
-            execute!(&self.stdout, cursor::Hide)?;
+            queue!(self.backend, cursor::Hide)?;
         }
 
-        if self.clear {
-            execute!(&self.stdout, terminal::Clear(terminal::ClearType::All))?
+        // Apply the default style first, so clearing fills the screen with its background.
+        NyanStyle::reset(&mut self.backend)?;
+
+        let size = self.backend.size()?;
+        if self.buffered {
+            if self.front.size() != size {
+                // The terminal was resized (or this is the first frame): start from a blank
+                // screen, which the buffers know.
+                queue!(self.backend, terminal::Clear(terminal::ClearType::All))?;
+                let front = Buffer::new(size.0, size.1);
+                self.front = if self.rawmode {
+                    front.raw_mode()
+                } else {
+                    front
+                };
+            }
+            self.back.clone_from(&self.front);
+            self.back.set_base_style(self.style);
+            if self.clear {
+                self.back.clear();
+            }
+            Cursor::Move(0, 0).queue(&mut self.back)?;
+            NyanStyle::reset(&mut self.back)?;
+        } else if self.clear {
+            queue!(self.backend, terminal::Clear(terminal::ClearType::All))?
         }
 
         self.looped = true;
+        let resized = self.last_size != Some(size);
+        self.last_size = Some(size);
 
-        func();
+        let too_small = self
+            .min_size
+            .is_some_and(|(width, height)| size.0 < width || size.1 < height);
+        if too_small != self.too_small || (too_small && resized) {
+            // Wipe the old frame (or the message) when switching between the two screens, or
+            // the message when it moves, but not every frame, which would flicker.
+            if self.buffered {
+                self.back.clear();
+            } else {
+                queue!(self.backend, terminal::Clear(terminal::ClearType::All))?;
+            }
+        }
+        self.too_small = too_small;
 
-        // Convert FPS to milliseconds and sleep to maintain the FPS rate
-        let frame_duration = Duration::from_millis(1000 / self.fps);
-        thread::sleep(frame_duration);
+        match self.min_size {
+            Some(min) if too_small => Self::draw_too_small(self, size, min)?,
+            _ => {
+                self.tick();
+                if !self.clear {
+                    self.erase_overlays()?;
+                }
+                let closure = self.clock.now();
+                func(self);
+                self.closure_time = self.clock.now() - closure;
+                self.frame = self.frame.wrapping_add(1);
+                if self.error_overlay && !self.errors.is_empty() {
+                    self.draw_errors(size)?;
+                }
+                if self.is_debug_hud_shown() {
+                    self.draw_hud(size)?;
+                }
+            }
+        }
+        self.frame_errors = std::mem::take(&mut self.errors);
 
+        if self.buffered {
+            self.back.diff(&self.front, &mut self.backend)?;
+            // Leave the cursor where the frame's output left it, e.g. in a text input.
+            let (x, y) = self.cursor_pos();
+            Cursor::Move(x, y).queue(&mut self.backend)?;
+            std::mem::swap(&mut self.front, &mut self.back);
+        }
         Ok(())
     }
 
@@ -214,16 +1352,31 @@ impl App {
     ///
     /// # Returns
     /// A `Result` indicating success or failure of the operation.
-    pub fn exit(self) -> Result<()> {
+    pub fn exit(mut self) -> NyanResult<()> {
+        // Back to the terminal's own colors, whatever the default style.
+        write!(self.backend, "\x1b[0m")?;
+
         execute!(
-            &self.stdout,
+            self.backend,
             cursor::MoveTo(0, 0),
             cursor::Show,
             terminal::LeaveAlternateScreen
         )?;
 
+        if self.mousecapture {
+            execute!(self.backend, event::DisableMouseCapture)?;
+        }
+
+        if self.bracketedpaste {
+            execute!(self.backend, event::DisableBracketedPaste)?;
+        }
+
+        if self.focuschange {
+            execute!(self.backend, event::DisableFocusChange)?;
+        }
+
         if self.rawmode {
-            terminal::disable_raw_mode()?;
+            self.backend.disable_raw_mode()?;
         }
 
         Ok(())
@@ -0,0 +1,272 @@
+//! This module provides the `Backend` trait, the output device an [`App`](crate::app::App) draws to, and its implementations.
+//!
+//! An `App` writes its frames as escape sequences to a backend, asks it for the size of the screen and turns raw mode on and off through it. By default this is the terminal of the process (`StdoutBackend`), but any backend can be given to [`App::with_backend`](crate::app::App::with_backend), so nyan can draw into memory for tests, to a socket, or into a host application.
+//!
+//! # Traits
+//!
+//! - `Backend`: A writer with a screen size and a raw mode.
+//!
+//! # Structs
+//!
+//! - `StdoutBackend`: The terminal of the process, through `crossterm`.
+//! - `TestBackend`: A screen of cells in memory, to check what an `App` drew.
+//! - `WriterBackend`: Any `std::io::Write` with a fixed screen size, e.g. a file or a `TcpStream`.
+//!
+//! # Methods
+//!
+//! - `StdoutBackend::new()`: Writes to the standard output through a large buffer.
+//! - `TestBackend::new(width, height)`: Creates an empty screen.
+//! - `TestBackend::screen()` / `output()` / `is_raw_mode()`: Inspect the screen, the bytes written and the mode.
+//! - `TestBackend::resize(width, height)`: Changes the size of the screen.
+//! - `WriterBackend::new(writer, width, height)`: Writes to `writer`.
+//! - `WriterBackend::resize(width, height)` / `get_ref()` / `into_inner()`: Change the size and access the writer.
+
+use std::fmt::Debug;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crossterm::terminal;
+
+use crate::buffer::Buffer;
+
+/// The `Backend` trait is the output device of an [`App`](crate::app::App).
+///
+/// Everything drawn is written to it as bytes with escape sequences (see [`Write`]). Only the
+/// screen size is required; the raw mode of devices without one does nothing.
+pub trait Backend: Write + Debug {
+    /// Returns the size of the screen in cells, `(width, height)`.
+    fn size(&self) -> io::Result<(u16, u16)>;
+
+    /// Turns off line buffering and echo of the input, if the device has them.
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Restores line buffering and echo of the input, if the device has them.
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `StdoutBackend` draws to the terminal of the process.
+#[derive(Debug)]
+pub struct StdoutBackend {
+    out: io::BufWriter<io::Stdout>,
+}
+
+impl StdoutBackend {
+    /// Creates a backend writing to the standard output, buffered so a frame goes out with a
+    /// single write.
+    pub fn new() -> Self {
+        Self {
+            out: io::BufWriter::with_capacity(1 << 16, io::stdout()),
+        }
+    }
+}
+
+impl Default for StdoutBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for StdoutBackend {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.out.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+impl Backend for StdoutBackend {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        terminal::size()
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        terminal::disable_raw_mode()
+    }
+}
+
+/// The state of a [`TestBackend`], shared by its clones.
+#[derive(Debug)]
+struct TestScreen {
+    screen: Buffer,
+    raw: bool,
+    output: Vec<u8>,
+}
+
+/// `TestBackend` draws into a screen of cells in memory, to check what an `App` drew without
+/// a terminal. Like a terminal, it starts in cooked mode, where `\n` also returns to the first
+/// column.
+///
+/// Clones share the same screen, so a clone kept by a test sees what the `App` drew.
+///
+/// # Example
+/// ```rust
+/// use nyan::app::App;
+/// use nyan::backend::TestBackend;
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+///
+/// let backend = TestBackend::new(20, 3);
+/// let mut app = App::with_backend(1000, backend.clone()).clear();
+///
+/// let mut obj = NyanObj::new();
+/// obj.add_object("title", Objects::new_text("nyan"), (2, 1));
+/// app.draw_with(|app| obj.draw_all_in(app).unwrap()).unwrap();
+///
+/// assert_eq!(backend.screen().get(2, 1).unwrap().ch, 'n');
+/// assert_eq!(backend.screen().to_ansi(), "\n  nyan\n\n");
+/// ```
+#[derive(Clone, Debug)]
+pub struct TestBackend {
+    state: Arc<Mutex<TestScreen>>,
+}
+
+impl TestBackend {
+    /// Creates a blank screen of `width` × `height` cells.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(TestScreen {
+                screen: Buffer::new(width, height),
+                raw: false,
+                output: Vec::new(),
+            })),
+        }
+    }
+
+    /// Returns the state, even if a thread panicked while drawing.
+    fn state(&self) -> MutexGuard<'_, TestScreen> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Returns a copy of the cells on the screen.
+    pub fn screen(&self) -> Buffer {
+        self.state().screen.clone()
+    }
+
+    /// Returns the bytes written so far, escape sequences included.
+    pub fn output(&self) -> Vec<u8> {
+        self.state().output.clone()
+    }
+
+    /// Changes the size of the screen, as if the terminal was resized. The cells are cleared.
+    pub fn resize(&self, width: u16, height: u16) {
+        let mut state = self.state();
+        state.screen = Buffer::new(width, height);
+        let raw = state.raw;
+        state.screen.set_raw(raw);
+    }
+
+    /// Returns whether the `App` turned on raw mode.
+    pub fn is_raw_mode(&self) -> bool {
+        self.state().raw
+    }
+
+    /// Turns raw mode on or off.
+    fn set_raw(&self, raw: bool) {
+        let mut state = self.state();
+        state.raw = raw;
+        state.screen.set_raw(raw);
+    }
+}
+
+impl Write for TestBackend {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.state();
+        state.output.extend_from_slice(buf);
+        state.screen.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Backend for TestBackend {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(self.state().screen.size())
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        self.set_raw(true);
+        Ok(())
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        self.set_raw(false);
+        Ok(())
+    }
+}
+
+/// `WriterBackend` draws to any writer, e.g. a file, a pipe or a `TcpStream`, with a screen
+/// size set by the application.
+///
+/// # Example
+/// ```rust
+/// use nyan::app::App;
+/// use nyan::backend::WriterBackend;
+///
+/// let mut app = App::with_backend(1000, WriterBackend::new(Vec::new(), 80, 24));
+/// app.draw_with(|app| {
+///     use std::io::Write;
+///     write!(app, "meow").unwrap();
+/// })
+/// .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct WriterBackend<W: Write + Debug> {
+    writer: W,
+    size: (u16, u16),
+}
+
+impl<W: Write + Debug> WriterBackend<W> {
+    /// Creates a backend writing to `writer`, whose screen is `width` × `height` cells.
+    pub fn new(writer: W, width: u16, height: u16) -> Self {
+        Self {
+            writer,
+            size: (width, height),
+        }
+    }
+
+    /// Changes the size of the screen, e.g. when the remote terminal was resized.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.size = (width, height);
+    }
+
+    /// Returns the writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the writer, giving up the backend.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write + Debug> Write for WriterBackend<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W: Write + Debug> Backend for WriterBackend<W> {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(self.size)
+    }
+}
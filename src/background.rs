@@ -0,0 +1,343 @@
+//! This module provides `Background`, animated procedural backdrops: a scrolling starfield, falling matrix glyphs and a plasma.
+//!
+//! A background fills a `width` x `height` field, is advanced by the time that passed (usually [`App::delta_time`](crate::app::App::delta_time)) and is drawn into a region of the screen before everything else, so the scene is drawn over it. How busy and how fast it is and its colors are configured with `density()`, `speed()` and `colors()`. The starfield and the matrix only draw their stars and glyphs, so draw them onto a screen that is cleared every frame; the plasma fills every cell.
+//!
+//! # Structs
+//!
+//! - `Background`: The kind of backdrop, its settings and its animation state.
+//!
+//! # Enums
+//!
+//! - `BackgroundKind`: Starfield, matrix or plasma.
+//!
+//! # Methods
+//!
+//! - `starfield(width, height)` / `matrix(width, height)` / `plasma(width, height)`: Create a background.
+//! - `density(density)` / `speed(speed)` / `colors(gradient)` / `seed(seed)`: Configure it.
+//! - `resize(width, height)`: Fits the background to a new screen size.
+//! - `advance(dt)`: Moves the animation on.
+//! - `draw_in(out, area)`: Draws the background into a region.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::cursor::Cursor;
+use crate::gradient::Gradient;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::widget;
+
+/// Glyphs the matrix rains, all one cell wide.
+const MATRIX_GLYPHS: &str = "0123456789ABCDEFｦｱｳｴｵｶｷｹｺｻｼｽｾｿﾀﾂﾃﾅﾆﾇﾈﾊﾋﾎﾏﾐﾑﾒﾓﾔﾕﾗﾘﾜ";
+
+/// Glyphs of the plasma, from its lowest to its highest values.
+const PLASMA_GLYPHS: &str = " .:-=+*#%@";
+
+/// The `BackgroundKind` enum tells what a [`Background`] shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BackgroundKind {
+    /// Stars drifting to the left, nearer ones faster and brighter.
+    Starfield,
+    /// Trails of glyphs falling down the columns.
+    Matrix,
+    /// Waves of color rolling over every cell.
+    Plasma,
+}
+
+/// A star of a starfield, or the head of a falling matrix trail.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Mote {
+    x: f32,
+    y: f32,
+    /// How near a star is, or how fast a trail falls, in `0.0..1.0`.
+    depth: f32,
+    /// The length of a trail.
+    length: u16,
+}
+
+/// `Background` is an animated, procedurally generated backdrop.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::background::Background;
+/// use nyan::rect::Rect;
+///
+/// let mut stars = Background::starfield(40, 10).density(0.1).speed(8.0).seed(7);
+/// stars.advance(Duration::from_millis(16));
+///
+/// let mut out = Vec::new();
+/// stars.draw_in(&mut out, Rect::new(0, 0, 40, 10)).unwrap();
+/// assert!(!out.is_empty());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Background {
+    kind: BackgroundKind,
+    width: u16,
+    height: u16,
+    density: f32,
+    speed: f32,
+    colors: Gradient,
+    motes: Vec<Mote>,
+    /// The seconds the animation has run, scaled by the speed for the plasma.
+    time: f32,
+    rng: u64,
+}
+
+impl Background {
+    /// Creates a background of `kind` filling `width` x `height` cells.
+    pub fn new(kind: BackgroundKind, width: u16, height: u16) -> Self {
+        let (density, speed, colors) = match kind {
+            BackgroundKind::Starfield => {
+                (0.03, 6.0, Gradient::new([(90, 90, 110), (255, 255, 255)]))
+            }
+            BackgroundKind::Matrix => (
+                0.5,
+                12.0,
+                Gradient::new([(0, 60, 0), (0, 200, 60), (200, 255, 200)]),
+            ),
+            BackgroundKind::Plasma => (
+                1.0,
+                1.0,
+                Gradient::new([(40, 0, 90), (200, 0, 120), (255, 140, 0), (255, 240, 120)]),
+            ),
+        };
+        let mut background = Self {
+            kind,
+            width,
+            height,
+            density,
+            speed,
+            colors,
+            motes: Vec::new(),
+            time: 0.0,
+            rng: 0x2545_f491_4f6c_dd1d,
+        };
+        background.populate();
+        background
+    }
+
+    /// Creates a starfield drifting to the left.
+    pub fn starfield(width: u16, height: u16) -> Self {
+        Self::new(BackgroundKind::Starfield, width, height)
+    }
+
+    /// Creates trails of glyphs falling down the columns.
+    pub fn matrix(width: u16, height: u16) -> Self {
+        Self::new(BackgroundKind::Matrix, width, height)
+    }
+
+    /// Creates a plasma filling every cell.
+    pub fn plasma(width: u16, height: u16) -> Self {
+        Self::new(BackgroundKind::Plasma, width, height)
+    }
+
+    /// Sets how busy the background is: the share of cells with a star, or of columns with
+    /// a falling trail, from `0.0` to `1.0`. The plasma always fills every cell.
+    pub fn density(self, density: f32) -> Self {
+        let mut background = self;
+        background.density = density.clamp(0.0, 1.0);
+        background.populate();
+        background
+    }
+
+    /// Sets how fast the background moves: the speed of the nearest stars and the fastest
+    /// trails in cells per second, or how many times faster than normal the plasma rolls.
+    pub fn speed(self, speed: f32) -> Self {
+        let mut background = self;
+        background.speed = speed.max(0.0);
+        background
+    }
+
+    /// Sets the colors: from far to near stars, from the tail to the head of a trail, or from
+    /// the lowest to the highest values of the plasma.
+    pub fn colors(self, gradient: Gradient) -> Self {
+        let mut background = self;
+        background.colors = gradient;
+        background
+    }
+
+    /// Seeds the random numbers, e.g. to make a background repeatable.
+    pub fn seed(self, seed: u64) -> Self {
+        let mut background = self;
+        // Xorshift gets stuck at zero.
+        background.rng = seed.max(1);
+        background.populate();
+        background
+    }
+
+    /// Fits the background to `width` x `height` cells, e.g. after the terminal was resized.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        if (width, height) != (self.width, self.height) {
+            self.width = width;
+            self.height = height;
+            self.populate();
+        }
+    }
+
+    /// Moves the animation on by `dt`.
+    pub fn advance(&mut self, dt: Duration) {
+        let seconds = dt.as_secs_f32();
+        self.time += seconds;
+        let (width, height) = (self.width as f32, self.height as f32);
+        for index in 0..self.motes.len() {
+            let mote = self.motes[index];
+            let distance = self.speed * (0.25 + 0.75 * mote.depth) * seconds;
+            match self.kind {
+                BackgroundKind::Starfield => {
+                    let mut star = mote;
+                    star.x -= distance;
+                    if star.x < 0.0 {
+                        // A new star comes in on the right.
+                        star.x += width;
+                        star.y = (self.random() * height).floor();
+                    }
+                    self.motes[index] = star;
+                }
+                BackgroundKind::Matrix => {
+                    let mut trail = mote;
+                    trail.y += distance;
+                    if trail.y - trail.length as f32 >= height {
+                        trail = self.trail(trail.x);
+                    }
+                    self.motes[index] = trail;
+                }
+                BackgroundKind::Plasma => {}
+            }
+        }
+    }
+
+    /// Draws the background into `area`, the top-left cell of the field in its top-left
+    /// corner. Cells outside of `area` are left alone.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the background was drawn.
+    /// - An error if writing fails.
+    pub fn draw_in<W: Write>(&self, out: &mut W, area: Rect) -> io::Result<()> {
+        let width = self.width.min(area.width);
+        let height = self.height.min(area.height);
+        match self.kind {
+            BackgroundKind::Starfield => {
+                for star in &self.motes {
+                    let (x, y) = (star.x as u16, star.y as u16);
+                    if x >= width || y >= height {
+                        continue;
+                    }
+                    let glyph = match star.depth {
+                        depth if depth > 0.8 => "*",
+                        depth if depth > 0.4 => "+",
+                        _ => ".",
+                    };
+                    let style = NyanStyle::new().fg(self.colors.color_at(star.depth));
+                    Cursor::Move(area.x + x, area.y + y).queue(out)?;
+                    widget::write_styled(out, style, glyph)?;
+                }
+            }
+            BackgroundKind::Matrix => {
+                let glyphs: Vec<char> = MATRIX_GLYPHS.chars().collect();
+                // Glyphs change a few times per second.
+                let tick = (self.time * 6.0) as u32;
+                for trail in &self.motes {
+                    let x = trail.x as u16;
+                    if x >= width {
+                        continue;
+                    }
+                    let head = trail.y.floor() as i32;
+                    for step in 0..trail.length as i32 {
+                        let y = head - step;
+                        if y < 0 || y >= height as i32 {
+                            continue;
+                        }
+                        let noise = hash(x as u32, y as u32, tick + step as u32);
+                        let glyph = glyphs[noise as usize % glyphs.len()];
+                        let t = 1.0 - step as f32 / trail.length as f32;
+                        let style = NyanStyle::new().fg(self.colors.color_at(t));
+                        Cursor::Move(area.x + x, area.y + y as u16).queue(out)?;
+                        widget::write_styled(out, style, glyph.encode_utf8(&mut [0; 4]))?;
+                    }
+                }
+            }
+            BackgroundKind::Plasma => {
+                let glyphs: Vec<char> = PLASMA_GLYPHS.chars().collect();
+                let t = self.time * self.speed;
+                for y in 0..height {
+                    Cursor::Move(area.x, area.y + y).queue(out)?;
+                    for x in 0..width {
+                        let (fx, fy) = (x as f32, y as f32 * 2.0);
+                        let value = (fx / 8.0 + t).sin()
+                            + (fy / 6.0 + t * 1.3).sin()
+                            + ((fx + fy) / 12.0 + t * 0.7).sin()
+                            + ((fx * fx + fy * fy).sqrt() / 8.0 - t).sin();
+                        let value = (value / 4.0 + 1.0) / 2.0;
+                        let glyph =
+                            glyphs[((value * glyphs.len() as f32) as usize).min(glyphs.len() - 1)];
+                        let style = NyanStyle::new().fg(self.colors.color_at(value));
+                        widget::write_styled(out, style, glyph.encode_utf8(&mut [0; 4]))?;
+                    }
+                }
+            }
+        }
+        out.flush()
+    }
+
+    /// Scatters the stars or trails over the field.
+    fn populate(&mut self) {
+        let (width, height) = (self.width as f32, self.height as f32);
+        self.motes.clear();
+        match self.kind {
+            BackgroundKind::Starfield => {
+                let count = (width * height * self.density).round() as usize;
+                for _ in 0..count {
+                    let star = Mote {
+                        x: self.random() * width,
+                        y: (self.random() * height).floor(),
+                        depth: self.random(),
+                        length: 1,
+                    };
+                    self.motes.push(star);
+                }
+            }
+            BackgroundKind::Matrix => {
+                for x in 0..self.width {
+                    if self.random() < self.density {
+                        let mut trail = self.trail(x as f32);
+                        // Start anywhere, so the screen is not empty at first.
+                        trail.y = self.random() * (height + trail.length as f32);
+                        self.motes.push(trail);
+                    }
+                }
+            }
+            BackgroundKind::Plasma => {}
+        }
+    }
+
+    /// Returns a new trail in column `x`, just above the field.
+    fn trail(&mut self, x: f32) -> Mote {
+        let length = 4 + (self.random() * (self.height as f32 / 2.0).max(1.0)) as u16;
+        Mote {
+            x,
+            y: 0.0,
+            depth: self.random(),
+            length,
+        }
+    }
+
+    /// Returns a random number in `0.0..1.0` (xorshift).
+    fn random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// Returns a number that looks random but is the same for the same inputs.
+fn hash(x: u32, y: u32, z: u32) -> u32 {
+    let mut hash =
+        x.wrapping_mul(0x9e37_79b1) ^ y.wrapping_mul(0x85eb_ca77) ^ z.wrapping_mul(0xc2b2_ae3d);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x2c1b_3c6d);
+    hash ^ (hash >> 12)
+}
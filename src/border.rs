@@ -0,0 +1,205 @@
+//! This module defines the `BorderStyle` enum, the border presets shared by every object that draws a box.
+//!
+//! A border is drawn from six characters (four corners, a horizontal and a vertical line), stored in a `BorderSet`. `BorderStyle` selects one of the built-in sets or a custom one. Objects that draw a box (such as `Objects::Panel`) take an optional `BorderStyle`; when none is given, the global default border is used, so switching the whole UI between unicode and ASCII borders is a single `set_default_border` call.
+//!
+//! # Enums
+//!
+//! - `BorderStyle`: A border preset (`Ascii`, `Single`, `Double`, `Rounded`, `Thick`) or a custom `BorderSet`.
+//!
+//! # Structs
+//!
+//! - `BorderSet`: The characters a border is drawn with.
+//!
+//! # Methods
+//!
+//! - `set()`: Returns the `BorderSet` of a style.
+//! - `lines(width, height)`: Returns the rows of a box of the given size.
+//! - `draw(out, area)`: Draws a box along the edges of the given `Rect`.
+//! - `default_border()` / `set_default_border(style)`: Get / set the border used when an object does not choose one.
+//!
+//! # Example
+//!
+//! ```rust
+//! use nyan::border::BorderStyle;
+//!
+//! assert_eq!(
+//!     BorderStyle::Rounded.lines(4, 3),
+//!     vec!["╭──╮", "│  │", "╰──╯"]
+//! );
+//! assert_eq!(BorderStyle::Ascii.lines(3, 2), vec!["+-+", "+-+"]);
+//! ```
+
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+use crate::cursor::Cursor;
+use crate::rect::Rect;
+
+/// `BorderSet` holds the characters a border is drawn with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorderSet {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+impl BorderSet {
+    /// `+`, `-` and `|`, for terminals without unicode support.
+    pub const ASCII: BorderSet = BorderSet {
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        horizontal: '-',
+        vertical: '|',
+    };
+
+    /// Thin single lines (`┌─┐`).
+    pub const SINGLE: BorderSet = BorderSet {
+        top_left: '┌',
+        top_right: '┐',
+        bottom_left: '└',
+        bottom_right: '┘',
+        horizontal: '─',
+        vertical: '│',
+    };
+
+    /// Double lines (`╔═╗`).
+    pub const DOUBLE: BorderSet = BorderSet {
+        top_left: '╔',
+        top_right: '╗',
+        bottom_left: '╚',
+        bottom_right: '╝',
+        horizontal: '═',
+        vertical: '║',
+    };
+
+    /// Thin lines with rounded corners (`╭─╮`).
+    pub const ROUNDED: BorderSet = BorderSet {
+        top_left: '╭',
+        top_right: '╮',
+        bottom_left: '╰',
+        bottom_right: '╯',
+        horizontal: '─',
+        vertical: '│',
+    };
+
+    /// Thick lines (`┏━┓`).
+    pub const THICK: BorderSet = BorderSet {
+        top_left: '┏',
+        top_right: '┓',
+        bottom_left: '┗',
+        bottom_right: '┛',
+        horizontal: '━',
+        vertical: '┃',
+    };
+}
+
+/// The `BorderStyle` enum selects the characters a border is drawn with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BorderStyle {
+    /// See [`BorderSet::ASCII`].
+    Ascii,
+    /// See [`BorderSet::SINGLE`].
+    #[default]
+    Single,
+    /// See [`BorderSet::DOUBLE`].
+    Double,
+    /// See [`BorderSet::ROUNDED`].
+    Rounded,
+    /// See [`BorderSet::THICK`].
+    Thick,
+    /// A custom set of characters.
+    Custom(BorderSet),
+}
+
+/// The border used by objects that do not choose one.
+static DEFAULT_BORDER: Mutex<BorderStyle> = Mutex::new(BorderStyle::Single);
+
+/// Returns the border used by objects that do not choose one (`BorderStyle::Single` unless
+/// changed with [`set_default_border`]).
+pub fn default_border() -> BorderStyle {
+    match DEFAULT_BORDER.lock() {
+        Ok(style) => *style,
+        Err(poisoned) => *poisoned.into_inner(),
+    }
+}
+
+/// Sets the border used by objects that do not choose one, e.g. `BorderStyle::Ascii` for
+/// terminals without unicode support.
+pub fn set_default_border(style: BorderStyle) {
+    match DEFAULT_BORDER.lock() {
+        Ok(mut border) => *border = style,
+        Err(poisoned) => *poisoned.into_inner() = style,
+    }
+}
+
+impl BorderStyle {
+    /// Returns the characters of this style.
+    pub fn set(&self) -> BorderSet {
+        match self {
+            BorderStyle::Ascii => BorderSet::ASCII,
+            BorderStyle::Single => BorderSet::SINGLE,
+            BorderStyle::Double => BorderSet::DOUBLE,
+            BorderStyle::Rounded => BorderSet::ROUNDED,
+            BorderStyle::Thick => BorderSet::THICK,
+            BorderStyle::Custom(set) => *set,
+        }
+    }
+
+    /// Returns the rows of a box that is `width` cells wide and `height` rows tall.
+    ///
+    /// Boxes smaller than 2x2 have no room for corners and are returned empty.
+    pub fn lines(&self, width: u16, height: u16) -> Vec<String> {
+        if width < 2 || height < 2 {
+            return Vec::new();
+        }
+        let set = self.set();
+        let inner = width as usize - 2;
+        let edge = |left: char, right: char| {
+            format!("{left}{}{right}", set.horizontal.to_string().repeat(inner))
+        };
+
+        let mut lines = Vec::with_capacity(height as usize);
+        lines.push(edge(set.top_left, set.top_right));
+        for _ in 2..height {
+            lines.push(format!(
+                "{}{}{}",
+                set.vertical,
+                " ".repeat(inner),
+                set.vertical
+            ));
+        }
+        lines.push(edge(set.bottom_left, set.bottom_right));
+        lines
+    }
+
+    /// Draws a box along the edges of `area`, leaving its inside untouched.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(std::io::Error)` if writing fails.
+    pub fn draw<W: Write>(&self, out: &mut W, area: Rect) -> io::Result<()> {
+        let lines = self.lines(area.width, area.height);
+        let last = lines.len().saturating_sub(1);
+        for (row, line) in lines.iter().enumerate() {
+            let y = area.y + row as u16;
+            Cursor::Move(area.x, y).queue(out)?;
+            if row == 0 || row == last {
+                write!(out, "{line}")?;
+            } else {
+                // Only the two sides, so content inside the box is not erased.
+                let set = self.set();
+                write!(out, "{}", set.vertical)?;
+                Cursor::Move(area.right() - 1, y).queue(out)?;
+                write!(out, "{}", set.vertical)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,578 @@
+//! This module provides `Buffer`, a grid of styled cells that output is drawn into instead of the terminal, and the diffing that brings the terminal up to date with it.
+//!
+//! A buffer implements `std::io::Write` and understands the output nyan writes: text, cursor movements, colors and attributes, and clearing. Everything an object would draw on the terminal ends up in the cells of the buffer. Comparing the buffer of a frame with the one of the previous frame gives the cells that changed, and `diff()` writes only those, with as few cursor movements and style changes as possible. This is what [`App::buffered`](crate::app::App::buffered) does every frame, so unchanged parts of the screen are never redrawn and clearing the screen no longer flickers.
+//!
+//! # Structs
+//!
+//! - `Buffer`: The cells, the cursor position and the current style of the output written into it.
+//! - `Cell`: A glyph and its style.
+//!
+//! # Methods
+//!
+//! - `new(width, height)`: Creates a buffer of blank cells.
+//! - `raw_mode()`: Makes `\n` keep the column, as in raw mode.
+//! - `base_style(style)`: Sets the style every cell inherits when written out.
+//! - `size()` / `resize(width, height)`: Query and change the size.
+//! - `get(x, y)` / `set(x, y, cell)`: Read and write single cells.
+//! - `clear()`: Blanks every cell.
+//! - `cursor()`: Returns where the output written into the buffer left the cursor.
+//! - `diff(previous, out)`: Writes what changed since `previous` to the terminal.
+//! - `to_text()`: Returns the glyphs of the cells without their styles, e.g. to compare frames in tests.
+//! - `to_ansi()` / `to_html()`: Export the cells as ANSI text (an `.ans` file) or a standalone HTML page with their colors, e.g. for screenshots in docs and bug reports.
+
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::ansi::{AnsiParser, Token, VirtualCursor};
+use crate::color::NyanColor;
+use crate::cursor::Cursor;
+use crate::style::{Attribute, NyanStyle};
+
+/// `Cell` is a position of a [`Buffer`].
+///
+/// The cell right of a wide glyph is covered by it and holds `'\0'`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cell {
+    /// The glyph shown in the cell.
+    pub ch: char,
+    /// The style the glyph is shown in, layered on the base style (see [`NyanStyle::base`]).
+    pub style: NyanStyle,
+}
+
+impl Default for Cell {
+    /// A blank cell.
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+            style: NyanStyle::new(),
+        }
+    }
+}
+
+/// `Buffer` is a screen of cells that output is written into.
+///
+/// # Example
+/// ```rust
+/// use std::io::Write;
+///
+/// use nyan::buffer::Buffer;
+///
+/// let mut previous = Buffer::new(12, 2);
+/// write!(previous, "Score: 10").unwrap();
+///
+/// let mut current = previous.clone();
+/// write!(current, "\x1b[1;8H25").unwrap();
+/// assert_eq!(current.get(7, 0).unwrap().ch, '2');
+///
+/// // Only the changed digits are written: a single cursor movement and "25".
+/// let mut out = Vec::new();
+/// current.diff(&previous, &mut out).unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap(), "\x1b[1;8H25");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Buffer {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+    parser: AnsiParser,
+    cursor: VirtualCursor,
+    /// The style set by the output so far.
+    style: NyanStyle,
+    /// The style the cells are layered on when they are written out.
+    base: NyanStyle,
+    /// Whether `\n` keeps the column, as in raw mode.
+    raw: bool,
+}
+
+impl Buffer {
+    /// Creates a `width` x `height` buffer of blank cells.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+            parser: AnsiParser::new(),
+            cursor: VirtualCursor::default(),
+            style: NyanStyle::new(),
+            base: NyanStyle::new(),
+            raw: false,
+        }
+    }
+
+    /// Makes `\n` move down without returning to the first column, as a terminal in raw mode
+    /// does.
+    pub fn raw_mode(self) -> Self {
+        let mut buffer = self;
+        buffer.raw = true;
+        buffer
+    }
+
+    /// Turns raw mode (see [`Buffer::raw_mode`]) on or off.
+    pub(crate) fn set_raw(&mut self, raw: bool) {
+        self.raw = raw;
+    }
+
+    /// Sets the style every cell inherits when the buffer is written out with
+    /// [`Buffer::diff`], [`Buffer::to_ansi`] or [`Buffer::to_html`], such as the default style
+    /// of the `App` that drew it (see [`NyanStyle::patch`]).
+    pub fn base_style(self, style: NyanStyle) -> Self {
+        let mut buffer = self;
+        buffer.base = style;
+        buffer
+    }
+
+    /// Changes the style set with [`Buffer::base_style`].
+    pub(crate) fn set_base_style(&mut self, style: NyanStyle) {
+        self.base = style;
+    }
+
+    /// Returns the `(width, height)` of the buffer in cells.
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Returns the bytes of the cells.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.cells.capacity() * std::mem::size_of::<Cell>()
+    }
+
+    /// Changes the size of the buffer to `width` x `height` and blanks every cell.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.width = width;
+        self.height = height;
+        self.cells.clear();
+        self.cells
+            .resize(width as usize * height as usize, Cell::default());
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` outside of the buffer.
+    pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
+        self.index(x, y).map(|index| &self.cells[index])
+    }
+
+    /// Replaces the cell at `(x, y)`.
+    ///
+    /// # Returns
+    ///
+    /// `false` if `(x, y)` is outside of the buffer.
+    pub fn set(&mut self, x: u16, y: u16, cell: Cell) -> bool {
+        match self.index(x, y) {
+            Some(index) => {
+                self.cells[index] = cell;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Blanks every cell and moves the cursor to the top-left corner.
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::default());
+        self.cursor.set(0, 0);
+    }
+
+    /// Returns the cursor position `(x, y)` the output written into the buffer left.
+    pub fn cursor(&self) -> (u16, u16) {
+        (self.cursor.x, self.cursor.y)
+    }
+
+    /// Writes the cells that differ from `previous` to `out`, which shows `previous` in the
+    /// base style (see [`NyanStyle::base`]) and is left in it. The cursor is moved only where
+    /// the changed cells are not next to each other, and the style is switched only where it
+    /// changes. A `previous` buffer of another size is treated as blank. `out` is not flushed,
+    /// so the changes go out together with the rest of a frame.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the changes were written.
+    /// - An error if writing fails.
+    pub fn diff<W: Write>(&self, previous: &Buffer, out: &mut W) -> io::Result<()> {
+        let same_size = previous.size() == self.size();
+        let blank = Cell::default();
+        let mut cursor = None;
+        let mut style = blank.style;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = y as usize * self.width as usize + x as usize;
+                let cell = &self.cells[index];
+                let old = if same_size {
+                    &previous.cells[index]
+                } else {
+                    &blank
+                };
+                if cell == old || cell.ch == '\0' {
+                    continue;
+                }
+                if cursor != Some((x, y)) {
+                    Cursor::Move(x, y).queue(out)?;
+                }
+                if style != cell.style {
+                    write!(out, "\x1b[0m")?;
+                    self.base.patch(cell.style).apply(out)?;
+                    style = cell.style;
+                }
+                write!(out, "{}", cell.ch)?;
+                let width = cell.ch.width().unwrap_or(0).max(1) as u16;
+                cursor = Some((x + width, y));
+            }
+        }
+        if style != blank.style {
+            write!(out, "\x1b[0m")?;
+            self.base.apply(out)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the glyphs of the cells without their colors and attributes, one line per row.
+    /// Spaces at the end of a row are left out.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// use nyan::buffer::Buffer;
+    ///
+    /// let mut frame = Buffer::new(8, 2);
+    /// write!(frame, "\x1b[31mnyan \x1b[2;3Hcat").unwrap();
+    /// assert_eq!(frame.to_text(), "nyan\n  cat\n");
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for row in self.rows() {
+            let line: String = row.map(|cell| cell.ch).collect();
+            text.push_str(line.trim_end_matches(' '));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Returns the cells as ANSI text, one line per row, with the escape sequences of their
+    /// colors and attributes, e.g. to save a frame as an `.ans` file or `cat` it into a
+    /// terminal. Blank cells at the end of a row are left out.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// use nyan::buffer::Buffer;
+    ///
+    /// let mut frame = Buffer::new(8, 2);
+    /// write!(frame, "\x1b[31mnyan").unwrap();
+    /// assert_eq!(frame.to_ansi(), "\x1b[31mnyan\x1b[0m\n\n");
+    /// ```
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+        for row in self.rows() {
+            let mut style = NyanStyle::new();
+            for cell in row {
+                let cell_style = self.base.patch(cell.style);
+                if cell_style != style {
+                    if !style.is_empty() {
+                        ansi.push_str("\x1b[0m");
+                    }
+                    if !cell_style.is_empty() {
+                        ansi.push_str(&format!("\x1b[{}m", cell_style.sgr()));
+                    }
+                    style = cell_style;
+                }
+                ansi.push(cell.ch);
+            }
+            if !style.is_empty() {
+                ansi.push_str("\x1b[0m");
+            }
+            ansi.push('\n');
+        }
+        ansi
+    }
+
+    /// Returns the cells as a standalone HTML page, a `<pre>` of styled `<span>`s on a black
+    /// background, e.g. to put a screenshot of an app into docs or a bug report. Colors look
+    /// as an xterm shows them (see [`NyanColor::to_rgb`]).
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// use nyan::buffer::Buffer;
+    ///
+    /// let mut frame = Buffer::new(8, 1);
+    /// write!(frame, "\x1b[1;38;2;255;128;0m<nyan>").unwrap();
+    /// let html = frame.to_html();
+    /// assert!(html.starts_with("<!DOCTYPE html>"));
+    /// assert!(html.contains(r#"<span style="color:#ff8000;font-weight:bold">&lt;nyan&gt;</span>"#));
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>nyan</title>\n",
+            "</head>\n<body style=\"margin:0;background:#000\">\n",
+            "<pre style=\"margin:0;padding:1em;color:#e5e5e5;background:#000;",
+            "font-family:monospace;line-height:1.2\">"
+        ));
+        for row in self.rows() {
+            let mut open: Option<String> = None;
+            for cell in row {
+                let css = css(self.base.patch(cell.style));
+                if open.as_ref() != Some(&css) {
+                    if open.is_some() {
+                        html.push_str("</span>");
+                    }
+                    if css.is_empty() {
+                        open = None;
+                    } else {
+                        html.push_str(&format!("<span style=\"{css}\">"));
+                        open = Some(css);
+                    }
+                }
+                match cell.ch {
+                    '<' => html.push_str("&lt;"),
+                    '>' => html.push_str("&gt;"),
+                    '&' => html.push_str("&amp;"),
+                    ch => html.push(ch),
+                }
+            }
+            if open.is_some() {
+                html.push_str("</span>");
+            }
+            html.push('\n');
+        }
+        html.push_str("</pre>\n</body>\n</html>\n");
+        html
+    }
+
+    /// Returns the rows of cells, without the cells covered by wide glyphs and the blank
+    /// cells at the end of each row.
+    fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &Cell>> {
+        let blank = Cell::default();
+        self.cells
+            .chunks(self.width.max(1) as usize)
+            .map(move |row| {
+                let end = row
+                    .iter()
+                    .rposition(|cell| *cell != blank)
+                    .map_or(0, |i| i + 1);
+                row[..end].iter().filter(|cell| cell.ch != '\0')
+            })
+    }
+
+    /// Returns the index of the cell at `(x, y)`, or `None` outside of the buffer.
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y as usize * self.width as usize + x as usize)
+    }
+
+    /// Applies a piece of output to the cells.
+    fn apply(&mut self, token: Token) {
+        match &token {
+            Token::Print(ch) => return self.print(*ch),
+            Token::Csi {
+                params,
+                private: false,
+                action,
+            } => match action {
+                b'm' => apply_sgr(&mut self.style, params),
+                b'J' => match params.first().copied().unwrap_or(0) {
+                    0 => self.erase_from(self.cursor.x, self.cursor.y),
+                    1 => self.erase_to(self.cursor.x, self.cursor.y),
+                    _ => self.erase_from(0, 0),
+                },
+                b'K' => {
+                    let y = self.cursor.y;
+                    match params.first().copied().unwrap_or(0) {
+                        0 => self.erase_line(y, self.cursor.x, self.width),
+                        1 => self.erase_line(y, 0, self.cursor.x.saturating_add(1)),
+                        _ => self.erase_line(y, 0, self.width),
+                    }
+                }
+                b'X' => {
+                    let n = Token::param(params, 0, 1);
+                    let (x, y) = (self.cursor.x, self.cursor.y);
+                    self.erase_line(y, x, x.saturating_add(n));
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        self.cursor.apply(&token, self.raw);
+    }
+
+    /// Puts a glyph at the cursor, wrapping at the right edge like a terminal.
+    fn print(&mut self, ch: char) {
+        let width = match ch.width() {
+            Some(0) | None => return,
+            Some(width) => width as u16,
+        };
+        if self.cursor.x.saturating_add(width) > self.width && self.cursor.x > 0 {
+            self.cursor.set(0, self.cursor.y.saturating_add(1));
+        }
+        let (x, y) = (self.cursor.x, self.cursor.y);
+        let cell = Cell {
+            ch,
+            style: self.style,
+        };
+        // Writing over half of a wide glyph leaves the other half blank.
+        if self.get(x, y).is_some_and(|cell| cell.ch == '\0') && x > 0 {
+            self.set(x - 1, y, self.blank());
+        }
+        let end = x + width;
+        if self.get(end, y).is_some_and(|cell| cell.ch == '\0') {
+            self.set(end, y, self.blank());
+        }
+        self.set(x, y, cell);
+        if width > 1 {
+            self.set(x + 1, y, Cell { ch: '\0', ..cell });
+        }
+        self.cursor.set(x.saturating_add(width), y);
+    }
+
+    /// Returns the cell left by erasing, which keeps the current background color.
+    fn blank(&self) -> Cell {
+        Cell {
+            ch: ' ',
+            style: NyanStyle {
+                bg: self.style.bg,
+                ..NyanStyle::new()
+            },
+        }
+    }
+
+    /// Erases from `(x, y)` to the end of the buffer.
+    fn erase_from(&mut self, x: u16, y: u16) {
+        self.erase_line(y, x, self.width);
+        for row in y.saturating_add(1)..self.height {
+            self.erase_line(row, 0, self.width);
+        }
+    }
+
+    /// Erases from the start of the buffer to `(x, y)`.
+    fn erase_to(&mut self, x: u16, y: u16) {
+        for row in 0..y.min(self.height) {
+            self.erase_line(row, 0, self.width);
+        }
+        self.erase_line(y, 0, x.saturating_add(1));
+    }
+
+    /// Erases the columns `from..to` of row `y`.
+    fn erase_line(&mut self, y: u16, from: u16, to: u16) {
+        let blank = self.blank();
+        for x in from..to.min(self.width) {
+            self.set(x, y, blank);
+        }
+    }
+}
+
+impl Write for Buffer {
+    /// Draws the output into the cells.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut parser = std::mem::take(&mut self.parser);
+        parser.feed(buf, |token| self.apply(token));
+        self.parser = parser;
+        Ok(buf.len())
+    }
+
+    /// Does nothing, as the output is drawn into the cells right away.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Returns the CSS declarations of a style, e.g. `color:#cd0000;font-weight:bold`.
+fn css(style: NyanStyle) -> String {
+    let hex = |(r, g, b): (u8, u8, u8)| format!("#{r:02x}{g:02x}{b:02x}");
+    let mut fg = style.fg.and_then(|color| color.to_rgb());
+    let mut bg = style.bg.and_then(|color| color.to_rgb());
+    let attributes = style.attributes;
+    if attributes.contains(Attribute::Reverse) {
+        // Unset colors are the ones of the page.
+        (fg, bg) = (
+            Some(bg.unwrap_or((0, 0, 0))),
+            Some(fg.unwrap_or((229, 229, 229))),
+        );
+    }
+
+    let mut css = Vec::new();
+    if let Some(fg) = fg {
+        css.push(format!("color:{}", hex(fg)));
+    }
+    if let Some(bg) = bg {
+        css.push(format!("background:{}", hex(bg)));
+    }
+    for (attribute, declaration) in [
+        (Attribute::Bold, "font-weight:bold"),
+        (Attribute::Dim, "opacity:0.6"),
+        (Attribute::Italic, "font-style:italic"),
+        (Attribute::Hidden, "visibility:hidden"),
+    ] {
+        if attributes.contains(attribute) {
+            css.push(declaration.to_string());
+        }
+    }
+    let lines: Vec<&str> = [
+        (Attribute::Underline, "underline"),
+        (Attribute::DoubleUnderline, "underline double"),
+        (Attribute::CrossedOut, "line-through"),
+        (Attribute::Overlined, "overline"),
+    ]
+    .into_iter()
+    .filter(|(attribute, _)| attributes.contains(*attribute))
+    .map(|(_, line)| line)
+    .collect();
+    if !lines.is_empty() {
+        css.push(format!("text-decoration:{}", lines.join(" ")));
+    }
+    css.join(";")
+}
+
+/// Applies the parameters of an SGR sequence (`ESC [ ... m`) to `style`.
+fn apply_sgr(style: &mut NyanStyle, params: &[u16]) {
+    let mut params = params.iter().copied();
+    while let Some(param) = params.next() {
+        match param {
+            0 => *style = NyanStyle::new(),
+            22 => {
+                style.attributes.remove(Attribute::Bold);
+                style.attributes.remove(Attribute::Dim);
+            }
+            23 => style.attributes.remove(Attribute::Italic),
+            24 => {
+                style.attributes.remove(Attribute::Underline);
+                style.attributes.remove(Attribute::DoubleUnderline);
+            }
+            25 => {
+                style.attributes.remove(Attribute::SlowBlink);
+                style.attributes.remove(Attribute::RapidBlink);
+            }
+            27 => style.attributes.remove(Attribute::Reverse),
+            28 => style.attributes.remove(Attribute::Hidden),
+            29 => style.attributes.remove(Attribute::CrossedOut),
+            55 => style.attributes.remove(Attribute::Overlined),
+            30..=37 => style.fg = Some(NyanColor::from_ansi_index((param - 30) as u8)),
+            90..=97 => style.fg = Some(NyanColor::from_ansi_index((param - 90 + 8) as u8)),
+            40..=47 => style.bg = Some(NyanColor::from_ansi_index((param - 40) as u8)),
+            100..=107 => style.bg = Some(NyanColor::from_ansi_index((param - 100 + 8) as u8)),
+            39 => style.fg = Some(NyanColor::Reset),
+            49 => style.bg = Some(NyanColor::Reset),
+            38 | 48 => {
+                let color = match params.next() {
+                    Some(5) => params.next().map(|i| NyanColor::Indexed(i as u8)),
+                    Some(2) => {
+                        let mut channel = || params.next().unwrap_or(0) as u8;
+                        Some(NyanColor::Rgb(channel(), channel(), channel()))
+                    }
+                    _ => None,
+                };
+                if param == 38 {
+                    style.fg = color.or(style.fg);
+                } else {
+                    style.bg = color.or(style.bg);
+                }
+            }
+            _ => {
+                if let Some(attribute) =
+                    Attribute::ALL.into_iter().find(|a| a.sgr() as u16 == param)
+                {
+                    style.attributes.insert(attribute);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! This module provides the `Button` widget, a label that triggers an action when it is activated.
+//!
+//! A button is activated with Enter or Space while it is focused, or by clicking it (pressing and releasing the left mouse button on it). Activation runs the button's callback and is also recorded, so applications that prefer polling can ask for it with `take_activation()`.
+//!
+//! # Structs
+//!
+//! - `Button`: A labelled button and its focus and press state.
+//!
+//! # Methods
+//!
+//! - `new(label)`: Creates a button.
+//! - `on_press(func)`: Sets the callback run on activation.
+//! - `theme(theme)`: Sets the colors of the button.
+//! - `label()` / `is_pressed()`: Query the button.
+//! - `press()`: Activates the button from code.
+//! - `take_activation()`: Returns whether the button was activated since the last call.
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::cursor::Cursor;
+use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::{NyanInput, NyanKey};
+use crate::layout::{self, Align};
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// `Button` is a label that runs an action when it is activated.
+///
+/// # Example
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use nyan::button::Button;
+/// use nyan::input::NyanInput;
+/// use nyan::widget::Widget;
+///
+/// let saved = Cell::new(0);
+/// let mut save = Button::new("Save").on_press(|| saved.set(saved.get() + 1));
+///
+/// assert!(!save.handle_input(&NyanInput::Enter)); // not focused yet
+/// save.set_focused(true);
+/// assert!(save.handle_input(&NyanInput::Enter));
+/// assert_eq!(saved.get(), 1);
+/// assert!(save.take_activation());
+/// assert!(!save.take_activation());
+/// ```
+pub struct Button<'a> {
+    label: Cow<'a, str>,
+    on_press: Option<Box<dyn FnMut() + 'a>>,
+    focused: bool,
+    /// Whether the left mouse button went down on the button and was not released yet.
+    pressed: bool,
+    activated: bool,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> Debug for Button<'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("Button")
+            .field("label", &self.label)
+            .field("focused", &self.focused)
+            .field("pressed", &self.pressed)
+            .field("activated", &self.activated)
+            .field("area", &self.area)
+            .finish()
+    }
+}
+
+impl<'a> Button<'a> {
+    /// Creates a button with the given label.
+    pub fn new<T: Into<Cow<'a, str>>>(label: T) -> Self {
+        Self {
+            label: label.into(),
+            on_press: None,
+            focused: false,
+            pressed: false,
+            activated: false,
+            theme: Theme::default(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Sets the callback that runs every time the button is activated.
+    pub fn on_press<F: FnMut() + 'a>(self, func: F) -> Self {
+        let mut button = self;
+        button.on_press = Some(Box::new(func));
+        button
+    }
+
+    /// Sets the colors of the button.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut button = self;
+        button.theme = theme;
+        button
+    }
+
+    /// Returns the label of the button.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns whether the button is held down with the mouse.
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Activates the button: runs its callback and records the activation.
+    pub fn press(&mut self) {
+        self.activated = true;
+        if let Some(func) = &mut self.on_press {
+            func();
+        }
+    }
+
+    /// Returns whether the button was activated since the last call, and forgets it.
+    pub fn take_activation(&mut self) -> bool {
+        std::mem::take(&mut self.activated)
+    }
+}
+
+impl<'a> Widget for Button<'a> {
+    /// The label in brackets, e.g. `[ Save ]`.
+    fn size(&self) -> (u16, u16) {
+        ((self.label.width() + 4).min(u16::MAX as usize) as u16, 1)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() {
+            return Ok(());
+        }
+        let style = if self.pressed {
+            self.theme.accent.reverse()
+        } else if self.focused {
+            self.theme.selection
+        } else {
+            NyanStyle::new()
+        };
+        let text = layout::align_text(
+            &format!("[ {} ]", self.label),
+            self.area.width,
+            Align::Center,
+        );
+        let text = layout::clip_text(&text, 0, self.area.width as usize);
+        Cursor::Move(self.area.x, self.area.y + self.area.height / 2).queue(&mut out)?;
+        widget::write_styled(out, style, &text)?;
+        out.flush()
+    }
+
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        if !self.focused {
+            return false;
+        }
+        match input {
+            NyanInput::Enter | NyanInput::Key(NyanKey::OtherKey(' ')) => {
+                self.press();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        let inside = self.area.contains(mouse.x, mouse.y);
+        match mouse.kind {
+            NyanMouseKind::Down(NyanMouseButton::Left) if inside => {
+                self.pressed = true;
+                true
+            }
+            NyanMouseKind::Up(NyanMouseButton::Left) if self.pressed => {
+                // Releasing outside the button cancels the click.
+                self.pressed = false;
+                if inside {
+                    self.press();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
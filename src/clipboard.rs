@@ -0,0 +1,91 @@
+//! This module provides access to the system clipboard through the terminal, with the OSC 52 escape sequence, so apps can offer "press y to copy" without running `xclip` or `pbcopy`.
+//!
+//! Writing to the clipboard works in most modern terminals (xterm, kitty, WezTerm, iTerm2, Windows Terminal, foot, ...), also over SSH, as the sequence travels with the output. Reading is allowed by fewer terminals: the app asks for the clipboard with `REQUEST`, and the terminal answers on the input with the same sequence, which the input of a [remote session](crate::remote) reports as `NyanEvent::Paste`. Terminals that do not support OSC 52 ignore it.
+//!
+//! # Constants
+//!
+//! - `REQUEST`: The sequence asking the terminal for the clipboard.
+//!
+//! # Functions
+//!
+//! - `copy_sequence(text)`: Returns the sequence that puts `text` on the clipboard.
+//! - `parse_reply(params)`: Returns the text of the terminal's answer to `REQUEST`.
+
+/// The sequence asking the terminal for the contents of the clipboard.
+pub const REQUEST: &str = "\x1b]52;c;?\x07";
+
+/// The characters of base64, as used by OSC 52.
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Returns the OSC 52 sequence that puts `text` on the clipboard. Writing it through an
+/// [`App`](crate::app::App) does this with
+/// [`App::copy_to_clipboard`](crate::app::App::copy_to_clipboard).
+///
+/// # Example
+/// ```rust
+/// use nyan::clipboard;
+///
+/// assert_eq!(clipboard::copy_sequence("nyan"), "\x1b]52;c;bnlhbg==\x07");
+/// ```
+pub fn copy_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", encode(text.as_bytes()))
+}
+
+/// Returns the text of the terminal's answer to [`REQUEST`], given the parameters of the OSC
+/// sequence (`52;c;<base64>`).
+///
+/// # Returns
+///
+/// - `Some(String)` with the contents of the clipboard.
+/// - `None` if the parameters are not an OSC 52 answer, or not valid base64.
+///
+/// # Example
+/// ```rust
+/// use nyan::clipboard;
+///
+/// assert_eq!(clipboard::parse_reply("52;c;bnlhbg=="), Some("nyan".to_string()));
+/// assert_eq!(clipboard::parse_reply("52;c;?"), None);
+/// ```
+pub fn parse_reply(params: &str) -> Option<String> {
+    let mut parts = params.splitn(3, ';');
+    if parts.next() != Some("52") {
+        return None;
+    }
+    let data = parts.nth(1)?;
+    let bytes = decode(data)?;
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Encodes `bytes` as base64, with padding.
+fn encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len() * 4 / 3 + 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(BASE64[(group >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+/// Decodes base64 `text`, with or without padding.
+fn decode(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+    let (mut group, mut bits) = (0u32, 0);
+    for ch in text.trim_end_matches('=').bytes() {
+        let value = BASE64.iter().position(|c| *c == ch)? as u32;
+        group = (group << 6 | value) & 0xffff;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((group >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
@@ -0,0 +1,113 @@
+//! This module provides the `Clock` trait, the source of time of an [`App`](crate::app::App), and its implementations.
+//!
+//! An `App` reads the time to measure [`App::delta_time`](crate::app::App::delta_time), which advances its scheduler and, through the app, animations, sprites and timers, and sleeps between frames to keep its FPS. By default this is the time of the system (`SystemClock`). A `MockClock` only moves when it is told to or when the app sleeps on it, without waiting, so tests and replays draw the same frames however fast the machine is.
+//!
+//! # Traits
+//!
+//! - `Clock`: Tells the time and waits.
+//!
+//! # Structs
+//!
+//! - `SystemClock`: The time of the system.
+//! - `MockClock`: A virtual time, moved by hand and by sleeping on it.
+//!
+//! # Methods
+//!
+//! - `MockClock::new()`: Creates a clock standing at the time it was created.
+//! - `MockClock::advance(duration)` / `elapsed()`: Move the clock forward / return how far it was moved.
+
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// `Clock` is the source of time of an [`App`](crate::app::App), given with
+/// [`App::clock`](crate::app::App::clock).
+pub trait Clock: Debug {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+
+    /// Waits for `duration`, e.g. until the next frame is due.
+    fn sleep(&self, duration: Duration);
+}
+
+/// `SystemClock` is the time of the system, the clock of an [`App`](crate::app::App) unless
+/// another is given.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// `MockClock` is a virtual time for tests and replays. It stands still until it is moved
+/// with [`MockClock::advance`], and sleeping on it moves it forward at once instead of
+/// waiting. Clones share the same time, so a test can keep one and give another to the `App`.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::clock::{Clock, MockClock};
+///
+/// let clock = MockClock::new();
+/// let start = clock.now();
+/// clock.sleep(Duration::from_secs(60)); // returns right away
+/// clock.advance(Duration::from_millis(500));
+/// assert_eq!(clock.now() - start, Duration::from_millis(60_500));
+/// assert_eq!(clock.elapsed(), Duration::from_millis(60_500));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockClock {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl MockClock {
+    /// Creates a clock standing at the time it is created.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let mut elapsed = match self.elapsed.lock() {
+            Ok(elapsed) => elapsed,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *elapsed += duration;
+    }
+
+    /// Returns how far the clock was moved since it was created.
+    pub fn elapsed(&self) -> Duration {
+        match self.elapsed.lock() {
+            Ok(elapsed) => *elapsed,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
@@ -0,0 +1,263 @@
+//! This module provides `Collisions`, which finds the tagged objects of a [`NyanObj`] that started to overlap each frame, so a game does not have to test every pair itself.
+//!
+//! Objects are given a tag such as `"player"`, `"enemy"` or `"bullet"`, and rules say which tags collide with which (a tag may collide with itself). Every frame, `update()` compares the bounds of the shown, tagged objects with a sweep along the x axis, which only tests objects whose columns overlap. A collision is reported once, when two objects start to overlap: it is returned as a [`Collision`] and passed to the callbacks of the rules it matches. While they keep overlapping, `is_colliding()` tells so.
+//!
+//! # Structs
+//!
+//! - `Collisions`: The tags, the rules and the overlapping pairs.
+//! - `Collision`: Two objects that started to overlap.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a collision system without tags or rules.
+//! - `tag(id, tag)` / `untag(id)` / `tag_of(id)`: Tag objects.
+//! - `between(a, b)` / `on(a, b, callback)`: Report collisions of objects tagged `a` with objects tagged `b`, and run a callback for them.
+//! - `update(objects)`: Finds the collisions that started and runs the callbacks.
+//! - `is_colliding(a, b)` / `colliding_with(id)`: Query the overlapping pairs.
+//! - `clear()`: Removes every tag and forgets the overlapping pairs.
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+use crate::nyan_obj::NyanObj;
+use crate::rect::Rect;
+
+/// `Collision` is a pair of objects that started to overlap. `a` has the first tag of the rule
+/// it matched and `b` the second one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Collision {
+    /// The object with the first tag of the rule.
+    pub a: String,
+    /// The object with the second tag of the rule.
+    pub b: String,
+}
+
+/// A callback run with the IDs of two colliding objects.
+type Callback<'a> = Box<dyn FnMut(&str, &str) + 'a>;
+
+/// Which tags collide, and what to do when they do.
+struct Rule<'a> {
+    a: Cow<'a, str>,
+    b: Cow<'a, str>,
+    callback: Option<Callback<'a>>,
+}
+
+/// `Collisions` reports when tagged objects start to overlap.
+///
+/// # Example
+/// ```rust
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// use nyan::collision::{Collision, Collisions};
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+///
+/// let mut obj = NyanObj::new();
+/// obj.add_object("cat", Objects::new_text("=^.^="), (0, 0));
+/// obj.add_object("fish", Objects::new_text("<><"), (10, 0));
+/// obj.add_object("dog", Objects::new_text("U^-^U"), (2, 0));
+///
+/// let eaten = Rc::new(Cell::new(0));
+/// let count = eaten.clone();
+/// let mut collisions = Collisions::new();
+/// collisions.tag("cat", "player");
+/// collisions.tag("fish", "food");
+/// collisions.tag("dog", "enemy");
+/// collisions.between("player", "enemy");
+/// collisions.on("player", "food", move |_, _| count.set(count.get() + 1));
+///
+/// let hit = Collision { a: "cat".into(), b: "dog".into() };
+/// assert_eq!(collisions.update(&obj), [hit]);
+///
+/// // Reported once, while the objects keep overlapping.
+/// obj.move_object("cat", (8, 0)).unwrap();
+/// assert_eq!(collisions.update(&obj).len(), 1);
+/// assert!(collisions.update(&obj).is_empty());
+/// assert_eq!(eaten.get(), 1);
+/// assert!(collisions.is_colliding("cat", "fish"));
+/// ```
+#[derive(Default)]
+pub struct Collisions<'a> {
+    tags: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    rules: Vec<Rule<'a>>,
+    /// The pairs that overlapped at the last update.
+    colliding: Vec<Collision>,
+}
+
+impl<'a> Debug for Collisions<'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules: Vec<_> = self
+            .rules
+            .iter()
+            .map(|rule| (&rule.a, &rule.b, rule.callback.is_some()))
+            .collect();
+        fmt.debug_struct("Collisions")
+            .field("tags", &self.tags)
+            .field("rules", &rules)
+            .field("colliding", &self.colliding)
+            .finish()
+    }
+}
+
+impl<'a> Collisions<'a> {
+    /// Creates a collision system without tags or rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tags the object `id`, replacing its previous tag.
+    pub fn tag<P: Into<Cow<'a, str>>, T: Into<Cow<'a, str>>>(&mut self, id: P, tag: T) {
+        let id = id.into();
+        self.tags.retain(|(other, _)| *other != id);
+        self.tags.push((id, tag.into()));
+    }
+
+    /// Removes the tag of the object `id`, e.g. when it is removed from the scene.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the object was tagged.
+    pub fn untag(&mut self, id: &str) -> bool {
+        let before = self.tags.len();
+        self.tags.retain(|(other, _)| other != id);
+        self.colliding
+            .retain(|collision| collision.a != id && collision.b != id);
+        self.tags.len() != before
+    }
+
+    /// Returns the tag of the object `id`, if it has one.
+    pub fn tag_of(&self, id: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(other, _)| other == id)
+            .map(|(_, tag)| tag.as_ref())
+    }
+
+    /// Reports collisions of objects tagged `a` with objects tagged `b`. `a` and `b` may be
+    /// the same tag.
+    pub fn between<P: Into<Cow<'a, str>>, Q: Into<Cow<'a, str>>>(&mut self, a: P, b: Q) {
+        self.rules.push(Rule {
+            a: a.into(),
+            b: b.into(),
+            callback: None,
+        });
+    }
+
+    /// Reports collisions of objects tagged `a` with objects tagged `b`, and runs `callback`
+    /// with the IDs of both (in the order of the tags) when one starts.
+    pub fn on<P, Q, F>(&mut self, a: P, b: Q, callback: F)
+    where
+        P: Into<Cow<'a, str>>,
+        Q: Into<Cow<'a, str>>,
+        F: FnMut(&str, &str) + 'a,
+    {
+        self.rules.push(Rule {
+            a: a.into(),
+            b: b.into(),
+            callback: Some(Box::new(callback)),
+        });
+    }
+
+    /// Finds the pairs of shown, tagged objects that overlap and match a rule, and runs the
+    /// callbacks of the pairs that did not overlap at the last update. Tags of objects that no
+    /// longer exist are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The collisions that started, in the order the objects are placed from left to right.
+    pub fn update(&mut self, objects: &NyanObj) -> Vec<Collision> {
+        let mut boxes: Vec<(&str, &str, Rect)> = self
+            .tags
+            .iter()
+            .filter(|(id, _)| objects.is_visible(id.to_string()) == Some(true))
+            .filter_map(|(id, tag)| {
+                let rect = objects.bounds(id.to_string())?;
+                (!rect.is_empty()).then_some((id.as_ref(), tag.as_ref(), rect))
+            })
+            .collect();
+        boxes.sort_by_key(|(_, _, rect)| rect.x);
+
+        let mut colliding = Vec::new();
+        for (index, &(id, tag, rect)) in boxes.iter().enumerate() {
+            // Sorted by x, so the first box starting right of this one ends the sweep.
+            for &(other, other_tag, other_rect) in boxes[index + 1..]
+                .iter()
+                .take_while(|(_, _, other)| other.x < rect.right())
+            {
+                if !rect.intersects(&other_rect) {
+                    continue;
+                }
+                let pair = self.rules.iter().find_map(|rule| {
+                    if rule.a == tag && rule.b == other_tag {
+                        Some((id, other))
+                    } else if rule.a == other_tag && rule.b == tag {
+                        Some((other, id))
+                    } else {
+                        None
+                    }
+                });
+                if let Some((a, b)) = pair {
+                    colliding.push(Collision {
+                        a: a.to_string(),
+                        b: b.to_string(),
+                    });
+                }
+            }
+        }
+
+        let started: Vec<Collision> = colliding
+            .iter()
+            .filter(|collision| !self.colliding.contains(collision))
+            .cloned()
+            .collect();
+        for collision in &started {
+            let tags = (self.tag_of(&collision.a), self.tag_of(&collision.b));
+            let (tag_a, tag_b) = (
+                tags.0.unwrap_or_default().to_string(),
+                tags.1.unwrap_or_default().to_string(),
+            );
+            for rule in &mut self.rules {
+                let Some(callback) = &mut rule.callback else {
+                    continue;
+                };
+                if rule.a == tag_a && rule.b == tag_b {
+                    callback(&collision.a, &collision.b);
+                } else if rule.a == tag_b && rule.b == tag_a {
+                    callback(&collision.b, &collision.a);
+                }
+            }
+        }
+        self.colliding = colliding;
+        started
+    }
+
+    /// Returns whether the objects `a` and `b` overlapped at the last update.
+    pub fn is_colliding(&self, a: &str, b: &str) -> bool {
+        self.colliding.iter().any(|collision| {
+            (collision.a == a && collision.b == b) || (collision.a == b && collision.b == a)
+        })
+    }
+
+    /// Returns the objects that overlapped the object `id` at the last update.
+    pub fn colliding_with(&self, id: &str) -> Vec<&str> {
+        self.colliding
+            .iter()
+            .filter_map(|collision| {
+                if collision.a == id {
+                    Some(collision.b.as_str())
+                } else if collision.b == id {
+                    Some(collision.a.as_str())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Removes every tag and forgets the overlapping pairs. The rules stay.
+    pub fn clear(&mut self) {
+        self.tags.clear();
+        self.colliding.clear();
+    }
+}
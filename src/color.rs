@@ -0,0 +1,348 @@
+//! This module defines the `NyanColor` enum, the colors used by the styling system.
+//!
+//! `NyanColor` covers the 16 standard ANSI colors, the 256-color palette and 24-bit RGB ("truecolor") values. Each color knows the SGR parameters that select it as a foreground or background color.
+//!
+//! Not every terminal supports truecolor. The color support of the terminal is detected once (from the `NO_COLOR`, `COLORTERM` and `TERM` environment variables) and can be overridden with `set_color_support`. When a style is applied, colors the terminal cannot show are downgraded to the nearest color it can show, or stripped entirely when `NO_COLOR` is set.
+//!
+//! # Enums
+//!
+//! - `NyanColor`: Represents a terminal color.
+//! - `ColorSupport`: Represents the range of colors a terminal can show.
+//!
+//! # Methods
+//!
+//! - `fg_code()`: Returns the SGR parameters that set the color as the foreground color.
+//! - `bg_code()`: Returns the SGR parameters that set the color as the background color.
+//! - `downgrade(support)`: Returns the nearest color that a terminal with the given support can show.
+//! - `color_support()` / `set_color_support(support)`: Get / override the color support used when applying styles.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The `NyanColor` enum represents a terminal color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NyanColor {
+    /// The terminal's default color.
+    Reset,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    /// A color of the 256-color palette.
+    Indexed(u8),
+    /// A 24-bit RGB color.
+    Rgb(u8, u8, u8),
+}
+
+/// The `ColorSupport` enum represents the range of colors a terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorSupport {
+    /// No colors at all, e.g. because `NO_COLOR` is set. Text attributes are still shown.
+    NoColor,
+    /// The 16 ANSI colors.
+    Ansi16,
+    /// The 256-color palette (which includes the 16 ANSI colors).
+    Ansi256,
+    /// 24-bit RGB colors.
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Detects the color support of the terminal from the environment.
+    ///
+    /// * A non-empty `NO_COLOR` (see <https://no-color.org>) or `TERM=dumb` means no colors.
+    /// * `COLORTERM=truecolor` or `COLORTERM=24bit` means truecolor.
+    /// * A `TERM` mentioning `256color` means the 256-color palette.
+    /// * Basic terminal types such as `TERM=linux`, `TERM=vt100` or a plain `TERM=xterm` mean the
+    ///   16 ANSI colors.
+    ///
+    /// Everything else is treated as a 256-color terminal.
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).unwrap_or_default();
+        let term = var("TERM");
+
+        if !var("NO_COLOR").is_empty() || term == "dumb" {
+            return ColorSupport::NoColor;
+        }
+        let colorterm = var("COLORTERM");
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorSupport::TrueColor;
+        }
+        if term.contains("256color") {
+            return ColorSupport::Ansi256;
+        }
+        match term.as_str() {
+            "linux" | "ansi" | "cons25" | "vt100" | "vt102" | "vt220" | "xterm" | "xterm-color"
+            | "screen" | "rxvt" | "eterm" => ColorSupport::Ansi16,
+            _ => ColorSupport::Ansi256,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            ColorSupport::NoColor => 1,
+            ColorSupport::Ansi16 => 2,
+            ColorSupport::Ansi256 => 3,
+            ColorSupport::TrueColor => 4,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(ColorSupport::NoColor),
+            2 => Some(ColorSupport::Ansi16),
+            3 => Some(ColorSupport::Ansi256),
+            4 => Some(ColorSupport::TrueColor),
+            _ => None,
+        }
+    }
+}
+
+/// The color support used when applying styles; `0` means not detected yet.
+static COLOR_SUPPORT: AtomicU8 = AtomicU8::new(0);
+
+/// Returns the color support used when applying styles.
+///
+/// It is detected with [`ColorSupport::detect`] on first use, unless it was set with
+/// [`set_color_support`].
+pub fn color_support() -> ColorSupport {
+    if let Some(support) = ColorSupport::from_u8(COLOR_SUPPORT.load(Ordering::Relaxed)) {
+        return support;
+    }
+    let support = ColorSupport::detect();
+    COLOR_SUPPORT.store(support.to_u8(), Ordering::Relaxed);
+    support
+}
+
+/// Overrides the color support used when applying styles.
+pub fn set_color_support(support: ColorSupport) {
+    COLOR_SUPPORT.store(support.to_u8(), Ordering::Relaxed);
+}
+
+/// The channel levels of the 6x6x6 color cube of the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The RGB values of the 16 ANSI colors (xterm defaults).
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+impl NyanColor {
+    /// Returns the SGR parameters that set this color as the foreground color.
+    pub fn fg_code(&self) -> String {
+        let mut code = String::new();
+        // Writing into a `String` cannot fail.
+        let _ = self.write_code(&mut code, false);
+        code
+    }
+
+    /// Returns the SGR parameters that set this color as the background color.
+    pub fn bg_code(&self) -> String {
+        let mut code = String::new();
+        let _ = self.write_code(&mut code, true);
+        code
+    }
+
+    /// Writes the SGR parameters of [`NyanColor::fg_code`] or, for the `background`,
+    /// [`NyanColor::bg_code`], without allocating.
+    pub(crate) fn write_code<W: fmt::Write>(&self, out: &mut W, background: bool) -> fmt::Result {
+        let layer = if background { 10 } else { 0 };
+        match self {
+            NyanColor::Indexed(i) => write!(out, "{};5;{i}", 38 + layer),
+            NyanColor::Rgb(r, g, b) => write!(out, "{};2;{r};{g};{b}", 38 + layer),
+            _ => match self.ansi_index() {
+                None => write!(out, "{}", 39 + layer),
+                Some(i @ 0..=7) => write!(out, "{}", 30 + layer + i),
+                Some(i) => write!(out, "{}", 90 + layer + i - 8),
+            },
+        }
+    }
+
+    /// Returns the nearest color that a terminal with the given color support can show.
+    ///
+    /// RGB colors are mapped to the nearest entry of the 256-color palette when truecolor is not
+    /// supported, and RGB and 256-color colors are mapped to the nearest ANSI color on 16-color
+    /// terminals. Without color support every color becomes `Reset`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::color::{ColorSupport, NyanColor};
+    ///
+    /// let red = NyanColor::Rgb(255, 0, 0).downgrade(ColorSupport::Ansi256);
+    /// assert_eq!(red, NyanColor::Indexed(196));
+    ///
+    /// let grey = NyanColor::Rgb(128, 128, 128).downgrade(ColorSupport::Ansi256);
+    /// assert_eq!(grey, NyanColor::Indexed(244));
+    ///
+    /// let orange = NyanColor::Rgb(250, 240, 10).downgrade(ColorSupport::Ansi16);
+    /// assert_eq!(orange, NyanColor::BrightYellow);
+    ///
+    /// assert_eq!(NyanColor::Indexed(1).downgrade(ColorSupport::Ansi16), NyanColor::Red);
+    /// assert_eq!(NyanColor::Red.downgrade(ColorSupport::NoColor), NyanColor::Reset);
+    /// ```
+    pub fn downgrade(self, support: ColorSupport) -> Self {
+        match (self, support) {
+            (_, ColorSupport::NoColor) => NyanColor::Reset,
+            (NyanColor::Rgb(r, g, b), ColorSupport::Ansi256) => {
+                NyanColor::Indexed(rgb_to_ansi256(r, g, b))
+            }
+            (NyanColor::Rgb(r, g, b), ColorSupport::Ansi16) => {
+                NyanColor::from_ansi_index(rgb_to_ansi16((r, g, b)))
+            }
+            (NyanColor::Indexed(i), ColorSupport::Ansi16) => {
+                NyanColor::from_ansi_index(rgb_to_ansi16(ansi256_to_rgb(i)))
+            }
+            (color, _) => color,
+        }
+    }
+
+    /// Returns the RGB value of this color, as an xterm shows it, or `None` for
+    /// [`NyanColor::Reset`], whose look depends on the terminal.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::color::NyanColor;
+    ///
+    /// assert_eq!(NyanColor::Rgb(1, 2, 3).to_rgb(), Some((1, 2, 3)));
+    /// assert_eq!(NyanColor::Red.to_rgb(), Some((205, 0, 0)));
+    /// assert_eq!(NyanColor::Indexed(196).to_rgb(), Some((255, 0, 0)));
+    /// assert_eq!(NyanColor::Reset.to_rgb(), None);
+    /// ```
+    pub fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        match self {
+            NyanColor::Reset => None,
+            NyanColor::Rgb(r, g, b) => Some((*r, *g, *b)),
+            NyanColor::Indexed(index) => Some(ansi256_to_rgb(*index)),
+            color => color.ansi_index().map(|index| ANSI_RGB[index as usize]),
+        }
+    }
+
+    /// Returns the ANSI color with the index `0..=15`.
+    pub(crate) fn from_ansi_index(index: u8) -> Self {
+        match index {
+            0 => NyanColor::Black,
+            1 => NyanColor::Red,
+            2 => NyanColor::Green,
+            3 => NyanColor::Yellow,
+            4 => NyanColor::Blue,
+            5 => NyanColor::Magenta,
+            6 => NyanColor::Cyan,
+            7 => NyanColor::White,
+            8 => NyanColor::BrightBlack,
+            9 => NyanColor::BrightRed,
+            10 => NyanColor::BrightGreen,
+            11 => NyanColor::BrightYellow,
+            12 => NyanColor::BrightBlue,
+            13 => NyanColor::BrightMagenta,
+            14 => NyanColor::BrightCyan,
+            _ => NyanColor::BrightWhite,
+        }
+    }
+
+    /// Returns the index `0..=15` of an ANSI color, or `None` for other colors.
+    fn ansi_index(&self) -> Option<u8> {
+        match self {
+            NyanColor::Reset | NyanColor::Indexed(_) | NyanColor::Rgb(..) => None,
+            NyanColor::Black => Some(0),
+            NyanColor::Red => Some(1),
+            NyanColor::Green => Some(2),
+            NyanColor::Yellow => Some(3),
+            NyanColor::Blue => Some(4),
+            NyanColor::Magenta => Some(5),
+            NyanColor::Cyan => Some(6),
+            NyanColor::White => Some(7),
+            NyanColor::BrightBlack => Some(8),
+            NyanColor::BrightRed => Some(9),
+            NyanColor::BrightGreen => Some(10),
+            NyanColor::BrightYellow => Some(11),
+            NyanColor::BrightBlue => Some(12),
+            NyanColor::BrightMagenta => Some(13),
+            NyanColor::BrightCyan => Some(14),
+            NyanColor::BrightWhite => Some(15),
+        }
+    }
+}
+
+/// Returns the squared distance between two RGB colors.
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Returns the RGB value of an entry of the 256-color palette.
+fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_RGB[index as usize],
+        16..=231 => {
+            let i = (index - 16) as usize;
+            (
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[(i / 6) % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// Returns the index `0..=15` of the ANSI color nearest to an RGB color.
+fn rgb_to_ansi16(rgb: (u8, u8, u8)) -> u8 {
+    (0..16)
+        .min_by_key(|i| distance(rgb, ANSI_RGB[*i as usize]))
+        .unwrap_or(0)
+}
+
+/// Returns the entry of the 256-color palette (color cube or grey ramp) nearest to an RGB color.
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let nearest_level = |c: u8| {
+        (0..6)
+            .min_by_key(|i| (CUBE_LEVELS[*i] as i32 - c as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    // The grey ramp 232..=255 covers the levels 8, 18, ..., 238.
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let grey_step = ((average.saturating_sub(3)) / 10).min(23);
+    let grey_level = (8 + grey_step * 10) as u8;
+    let grey_index = 232 + grey_step as usize;
+
+    if distance((r, g, b), (grey_level, grey_level, grey_level)) < distance((r, g, b), cube) {
+        grey_index as u8
+    } else {
+        cube_index as u8
+    }
+}
@@ -0,0 +1,224 @@
+//! This module provides `Config`, the keybindings and the theme of an application, read from a TOML file so end users can change them without recompiling.
+//!
+//! Requires the `toml` feature. A config file only lists what differs from the defaults of the application; everything else keeps its default:
+//!
+//! ```toml
+//! [keys]
+//! quit = ["ctrl+c", "q"]  # every key of an action
+//! save = "f2"             # or one
+//!
+//! [theme]
+//! base = "light"          # start from the light palette
+//! accent = { fg = "Magenta", attributes = ["Bold"] }
+//! error = { fg = { Rgb = [255, 80, 80] } }
+//! ```
+//!
+//! Keys are written as described in the `keybindings` module. The roles of the theme are those of [`Theme`]: `text`, `muted`, `accent`, `selection` and `error`. A file that names an unknown action, key or role, or binds one key to two actions, is rejected as a whole.
+//!
+//! # Structs
+//!
+//! - `Config`: The keybindings and the theme of an application.
+//!
+//! # Methods
+//!
+//! - `new(keybindings, theme)`: Creates a config from the defaults of an application.
+//! - `apply_toml_str(source)`: Changes the config as a TOML string says.
+//! - `load(path)`: Changes the config as a TOML file says, if it exists.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::errors::{NyanError, NyanResult};
+use crate::keybindings::{self, key_name, Keybindings};
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+
+/// `Config` holds the keybindings and the theme of an application.
+///
+/// # Example
+/// ```rust
+/// use nyan::config::Config;
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::keybindings::Keybindings;
+/// use nyan::theme::Theme;
+///
+/// let defaults = Keybindings::new()
+///     .bind("quit", NyanInput::Esc)
+///     .bind("save", NyanInput::Ctrl(NyanKey::S));
+/// let mut config = Config::new(defaults, Theme::dark());
+///
+/// config
+///     .apply_toml_str(
+///         r#"
+///         [keys]
+///         save = "f2"
+///
+///         [theme]
+///         accent = { fg = "Magenta" }
+///         "#,
+///     )
+///     .unwrap();
+/// assert_eq!(config.keybindings.action(&NyanInput::FunctionKey(2)), Some("save"));
+/// assert_eq!(config.keybindings.action(&NyanInput::Esc), Some("quit"));
+///
+/// let error = config.apply_toml_str("[keys]\nundo = \"ctrl+z\"").unwrap_err();
+/// assert!(error.to_string().contains("unknown action \"undo\""));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    /// The actions of the application and their keys.
+    pub keybindings: Keybindings,
+    /// The styles of the application.
+    pub theme: Theme,
+}
+
+/// The contents of a config file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    #[serde(default)]
+    keys: BTreeMap<String, KeysSpec>,
+    #[serde(default)]
+    theme: Option<ThemeSpec>,
+}
+
+/// The keys of an action in a config file: one key or a list of them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeysSpec {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// The theme in a config file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeSpec {
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    text: Option<NyanStyle>,
+    #[serde(default)]
+    muted: Option<NyanStyle>,
+    #[serde(default)]
+    accent: Option<NyanStyle>,
+    #[serde(default)]
+    selection: Option<NyanStyle>,
+    #[serde(default)]
+    error: Option<NyanStyle>,
+}
+
+impl Config {
+    /// Creates a config from the default keybindings and theme of an application. Only the
+    /// actions of `keybindings` can be bound by a config file.
+    pub fn new(keybindings: Keybindings, theme: Theme) -> Self {
+        Self { keybindings, theme }
+    }
+
+    /// Changes the keybindings and the theme as the TOML `source` says. Nothing changes if
+    /// the source is invalid.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` once the config is changed.
+    /// - `Err(NyanError::InvalidConfig)` if the source is not valid TOML, names an unknown
+    ///   action, key or role, or binds a key to two actions.
+    pub fn apply_toml_str(&mut self, source: &str) -> NyanResult<()> {
+        let file: File = toml::from_str(source).map_err(|e| invalid(e.to_string()))?;
+
+        let mut keybindings = self.keybindings.clone();
+        for (action, spec) in file.keys {
+            if !keybindings.contains(&action) {
+                let known: Vec<&str> = keybindings.actions().collect();
+                return Err(invalid(format!(
+                    "unknown action {action:?}, expected one of {known:?}"
+                )));
+            }
+            let names = match spec {
+                KeysSpec::One(name) => vec![name],
+                KeysSpec::Many(names) => names,
+            };
+            let keys = names
+                .iter()
+                .map(|name| keybindings::parse_key(name))
+                .collect::<NyanResult<Vec<_>>>()
+                .map_err(|e| invalid(format!("keys.{action}: {e}")))?;
+            keybindings.set(action, keys);
+        }
+        let actions: Vec<&str> = keybindings.actions().collect();
+        for (index, action) in actions.iter().enumerate() {
+            for key in keybindings.keys(action) {
+                if let Some(other) = actions[index + 1..]
+                    .iter()
+                    .find(|other| keybindings.keys(other).contains(key))
+                {
+                    return Err(invalid(format!(
+                        "{:?} is bound to both {action:?} and {other:?}",
+                        key_name(key)
+                    )));
+                }
+            }
+        }
+
+        let mut theme = self.theme;
+        if let Some(spec) = file.theme {
+            theme = match spec.base.as_deref() {
+                None => theme,
+                Some("dark") => Theme::dark(),
+                Some("light") => Theme::light(),
+                Some(base) => {
+                    return Err(invalid(format!(
+                        "unknown theme base {base:?}, expected \"dark\" or \"light\""
+                    )))
+                }
+            };
+            let roles = [
+                (&mut theme.text, spec.text),
+                (&mut theme.muted, spec.muted),
+                (&mut theme.accent, spec.accent),
+                (&mut theme.selection, spec.selection),
+                (&mut theme.error, spec.error),
+            ];
+            for (role, style) in roles {
+                if let Some(style) = style {
+                    *role = style;
+                }
+            }
+        }
+
+        self.keybindings = keybindings;
+        self.theme = theme;
+        Ok(())
+    }
+
+    /// Changes the keybindings and the theme as the TOML file at `path` says. A missing file
+    /// keeps the config as it is, so applications can ship without one.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` if the file was read, `Ok(false)` if it does not exist.
+    /// - `Err(NyanError::Io)` if it cannot be read.
+    /// - `Err(NyanError::InvalidConfig)` if it is invalid (see [`Config::apply_toml_str`]),
+    ///   with the path in the message.
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> NyanResult<bool> {
+        let path = path.as_ref();
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        self.apply_toml_str(&source).map_err(|e| match e {
+            NyanError::InvalidConfig(message) => invalid(format!("{}: {message}", path.display())),
+            e => e,
+        })?;
+        Ok(true)
+    }
+}
+
+/// Returns an `InvalidConfig` error with `message`.
+fn invalid(message: String) -> NyanError<'static> {
+    NyanError::InvalidConfig(message.into())
+}
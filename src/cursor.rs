@@ -4,22 +4,34 @@
 //!
 //! # Enum
 //!
-//! - `Cursor`: Represents cursor movement operations. It includes a variant `Move(u16, u16)` for moving the cursor to specific coordinates `(x, y)`.
+//! - `Cursor`: Represents cursor movement operations. It includes a variant `Move(u16, u16)` for moving the cursor to specific coordinates `(x, y)`, relative movements, and `SavePosition` / `RestorePosition` for temporarily jumping elsewhere and coming back.
+//!
+//! # Structs
+//!
+//! - `CursorAnimation`: An iterator that moves the cursor smoothly from one position to another, one step per frame.
 //!
 //! # Methods
 //!
 //! - `move_cursor(moveto: Cursor)`: Moves the cursor to the specified position. The position is defined by the `Cursor::Move(x, y)` variant. This method returns a result indicating success or failure.
+//! - `move_cursor_clamped(moveto: Cursor)`: Moves the cursor, clamping absolute positions to the terminal size.
+//! - `move_cursor_checked(moveto: Cursor)`: Moves the cursor, returning a `NyanError::OutOfBounds` error for positions outside the terminal.
+//! - `batch(ops: &[Cursor])`: Executes several cursor operations with a single flush.
+//! - `animate(from, to, frames, easing)`: Creates a `CursorAnimation` that moves the cursor smoothly across several frames.
+//! - `move_cursor_in(out, moveto)` / `batch_in(out, ops)`: Same as `move_cursor` / `batch`, but write to any `std::io::Write` instead of stdout.
 
-use crossterm::execute;
+use crossterm::{cursor, queue};
 use std::fmt::Debug;
+use std::io::Write;
 
-use crate::errors;
+use crate::easing::Easing;
+use crate::errors::{self, NyanResult};
 
 /// The `Cursor` enum represents cursor movement operations.
 ///
 /// Currently, it supports various cursor movements, such as moving the cursor to a specific `(x, y)` position,
-/// moving left, right, up, down, and moving to the next line.
+/// moving left, right, up, down, moving to the next or previous line, and moving to a specific column or row.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Cursor {
     /// Moves the cursor to the specified `(x, y)` coordinates.
     Move(u16, u16),
@@ -33,6 +45,16 @@ pub enum Cursor {
     MoveDown(u16),
     /// Moves the cursor to the next line by the specified number of units.
     MoveToNextLine(u16),
+    /// Moves the cursor to the previous line by the specified number of units.
+    MoveToPreviousLine(u16),
+    /// Moves the cursor to the specified column, keeping the current row.
+    MoveToColumn(u16),
+    /// Moves the cursor to the specified row, keeping the current column.
+    MoveToRow(u16),
+    /// Saves the current cursor position, so it can be restored with `RestorePosition`.
+    SavePosition,
+    /// Restores the cursor position saved by the last `SavePosition`.
+    RestorePosition,
 }
 
 impl Debug for Cursor {
@@ -56,6 +78,21 @@ impl Debug for Cursor {
             Cursor::MoveToNextLine(next) => {
                 write!(f, "Cursor::MoveToNextLine({next})")
             }
+            Cursor::MoveToPreviousLine(prev) => {
+                write!(f, "Cursor::MoveToPreviousLine({prev})")
+            }
+            Cursor::MoveToColumn(x) => {
+                write!(f, "Cursor::MoveToColumn({x})")
+            }
+            Cursor::MoveToRow(y) => {
+                write!(f, "Cursor::MoveToRow({y})")
+            }
+            Cursor::SavePosition => {
+                write!(f, "Cursor::SavePosition")
+            }
+            Cursor::RestorePosition => {
+                write!(f, "Cursor::RestorePosition")
+            }
         }
     }
 }
@@ -71,7 +108,7 @@ impl Cursor {
     ///
     /// # Returns
     /// * `Ok(())` on success.
-    /// * `Err(anyhow::Error)` if an error occurs while executing the movement.
+    /// * `Err(NyanError)` if an error occurs while executing the movement.
     ///
     /// # Example
     /// ```ignore
@@ -79,54 +116,241 @@ impl Cursor {
     /// ```
     ///
     /// This function executes the specified cursor movement operation.
-    pub fn move_cursor(moveto: Self) -> anyhow::Result<()> {
-        match moveto {
-            Cursor::Move(x, y) => {
-                return if let Err(e) = execute!(std::io::stdout(), crossterm::cursor::MoveTo(x, y))
-                {
-                    Err(errors::NyanError::Cursor(e.to_string().into()).into())
-                } else {
-                    Ok(())
-                };
-            }
-            Cursor::MoveLeft(x) => {
-                return if let Err(e) = execute!(std::io::stdout(), crossterm::cursor::MoveLeft(x)) {
-                    Err(errors::NyanError::Cursor(e.to_string().into()).into())
-                } else {
-                    Ok(())
-                };
-            }
-            Cursor::MoveRight(x) => {
-                return if let Err(e) = execute!(std::io::stdout(), crossterm::cursor::MoveRight(x))
-                {
-                    Err(errors::NyanError::Cursor(e.to_string().into()).into())
-                } else {
-                    Ok(())
-                };
-            }
-            Cursor::MoveUp(y) => {
-                return if let Err(e) = execute!(std::io::stdout(), crossterm::cursor::MoveUp(y)) {
-                    Err(errors::NyanError::Cursor(e.to_string().into()).into())
-                } else {
-                    Ok(())
-                }
-            }
-            Cursor::MoveDown(y) => {
-                return if let Err(e) = execute!(std::io::stdout(), crossterm::cursor::MoveDown(y)) {
-                    Err(errors::NyanError::Cursor(e.to_string().into()).into())
-                } else {
-                    Ok(())
-                };
-            }
-            Cursor::MoveToNextLine(next) => {
-                return if let Err(e) =
-                    execute!(std::io::stdout(), crossterm::cursor::MoveToNextLine(next))
-                {
-                    Err(errors::NyanError::Cursor(e.to_string().into()).into())
-                } else {
-                    Ok(())
-                };
-            }
+    pub fn move_cursor(moveto: Self) -> NyanResult<()> {
+        Self::move_cursor_in(&mut std::io::stdout(), moveto)
+    }
+
+    /// Moves the cursor by writing the movement to `out` instead of `std::io::stdout()`.
+    ///
+    /// This allows cursor operations to go through the `App`'s writer, a buffer, or any other
+    /// output (e.g. for headless rendering).
+    ///
+    /// # Arguments
+    /// * `out` - The writer to send the movement to.
+    /// * `moveto` - A `Cursor` enum variant specifying the target position.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(NyanError)` if an error occurs while writing the movement.
+    pub fn move_cursor_in<W: Write>(out: &mut W, moveto: Self) -> NyanResult<()> {
+        if let Err(e) = moveto.queue(out).and_then(|_| out.flush()) {
+            Err(errors::NyanError::Cursor(e.to_string().into()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Moves the cursor, clamping absolute positions to the current terminal size.
+    ///
+    /// Coordinates past the right or bottom edge are moved to the last column or row, so the
+    /// result is the same on every terminal instead of wrapping or being ignored.
+    ///
+    /// # Arguments
+    /// * `moveto` - A `Cursor` enum variant specifying the target position.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(NyanError)` if the terminal size cannot be determined or the movement fails.
+    pub fn move_cursor_clamped(moveto: Self) -> NyanResult<()> {
+        let (width, height) = crossterm::terminal::size()?;
+        Self::move_cursor(moveto.clamp(width, height))
+    }
+
+    /// Moves the cursor, refusing absolute positions outside the current terminal size.
+    ///
+    /// # Arguments
+    /// * `moveto` - A `Cursor` enum variant specifying the target position.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(NyanError)` containing [`NyanError::OutOfBounds`](errors::NyanError::OutOfBounds)
+    ///   if the target lies outside the terminal, or any other error if the movement fails.
+    pub fn move_cursor_checked(moveto: Self) -> NyanResult<()> {
+        let (width, height) = crossterm::terminal::size()?;
+        if !moveto.fits(width, height) {
+            let (x, y) = moveto.target();
+            return Err(errors::NyanError::OutOfBounds(
+                x.unwrap_or(0),
+                y.unwrap_or(0),
+                width,
+                height,
+            ));
+        }
+        Self::move_cursor(moveto)
+    }
+
+    /// Returns this operation with its absolute coordinates clamped to a `width` x `height` area.
+    ///
+    /// Relative movements are returned unchanged, since terminals already stop them at the edges.
+    pub fn clamp(self, width: u16, height: u16) -> Self {
+        let max_x = width.saturating_sub(1);
+        let max_y = height.saturating_sub(1);
+        match self {
+            Cursor::Move(x, y) => Cursor::Move(x.min(max_x), y.min(max_y)),
+            Cursor::MoveToColumn(x) => Cursor::MoveToColumn(x.min(max_x)),
+            Cursor::MoveToRow(y) => Cursor::MoveToRow(y.min(max_y)),
+            other => other,
+        }
+    }
+
+    /// Returns whether the absolute coordinates of this operation lie inside a `width` x `height` area.
+    ///
+    /// Relative movements always fit.
+    pub fn fits(self, width: u16, height: u16) -> bool {
+        let (x, y) = self.target();
+        !matches!(x, Some(x) if x >= width) && !matches!(y, Some(y) if y >= height)
+    }
+
+    /// Returns the absolute column and row this operation moves to, if any.
+    fn target(self) -> (Option<u16>, Option<u16>) {
+        match self {
+            Cursor::Move(x, y) => (Some(x), Some(y)),
+            Cursor::MoveToColumn(x) => (Some(x), None),
+            Cursor::MoveToRow(y) => (None, Some(y)),
+            _ => (None, None),
+        }
+    }
+
+    /// Executes several cursor operations with a single flush.
+    ///
+    /// `move_cursor` writes and flushes every operation on its own, which adds up when many
+    /// movements are done per frame. `batch` queues all operations and flushes them once.
+    ///
+    /// # Arguments
+    /// * `ops` - The cursor operations to execute, in order.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(NyanError)` if an error occurs while executing the movements.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Cursor::batch(&[Cursor::SavePosition, Cursor::Move(0, 0), Cursor::RestorePosition])?;
+    /// ```
+    pub fn batch(ops: &[Self]) -> NyanResult<()> {
+        Self::batch_in(&mut std::io::stdout(), ops)
+    }
+
+    /// Executes several cursor operations on `out` with a single flush.
+    ///
+    /// # Arguments
+    /// * `out` - The writer to send the movements to.
+    /// * `ops` - The cursor operations to execute, in order.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(NyanError)` if an error occurs while writing the movements.
+    pub fn batch_in<W: Write>(out: &mut W, ops: &[Self]) -> NyanResult<()> {
+        let result = ops
+            .iter()
+            .try_for_each(|op| op.queue(out))
+            .and_then(|_| out.flush());
+
+        if let Err(e) = result {
+            Err(errors::NyanError::Cursor(e.to_string().into()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Queues the crossterm command for this operation without flushing.
+    pub(crate) fn queue<W: Write>(self, out: &mut W) -> std::io::Result<()> {
+        match self {
+            Cursor::Move(x, y) => queue!(out, cursor::MoveTo(x, y)),
+            Cursor::MoveLeft(x) => queue!(out, cursor::MoveLeft(x)),
+            Cursor::MoveRight(x) => queue!(out, cursor::MoveRight(x)),
+            Cursor::MoveUp(y) => queue!(out, cursor::MoveUp(y)),
+            Cursor::MoveDown(y) => queue!(out, cursor::MoveDown(y)),
+            Cursor::MoveToNextLine(next) => queue!(out, cursor::MoveToNextLine(next)),
+            Cursor::MoveToPreviousLine(prev) => queue!(out, cursor::MoveToPreviousLine(prev)),
+            Cursor::MoveToColumn(x) => queue!(out, cursor::MoveToColumn(x)),
+            Cursor::MoveToRow(y) => queue!(out, cursor::MoveToRow(y)),
+            Cursor::SavePosition => queue!(out, cursor::SavePosition),
+            Cursor::RestorePosition => queue!(out, cursor::RestorePosition),
+        }
+    }
+}
+
+/// `CursorAnimation` moves the cursor smoothly from one position to another across several frames.
+///
+/// It is an iterator yielding one `Cursor::Move` per frame, ending exactly at the target position.
+/// Since [`App::draw`](crate::app::App::draw) moves the cursor to `(0, 0)` at the start of every
+/// frame, execute the step at the end of the draw closure, and make sure the cursor is visible
+/// (see [`App::set_cursor_visible`](crate::app::App::set_cursor_visible)).
+///
+/// # Example
+/// ```ignore
+/// let mut pointer = Cursor::animate((0, 0), (30, 10), 20, Easing::InOutQuad);
+///
+/// loop {
+///     nyan.draw_with(|out| {
+///         obj.draw_object_in(out, "menu").unwrap();
+///         if let Some(step) = pointer.next() {
+///             out.move_cursor(step).unwrap();
+///         }
+///     })?;
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CursorAnimation {
+    from: (u16, u16),
+    to: (u16, u16),
+    frames: u32,
+    frame: u32,
+    easing: Easing,
+}
+
+impl CursorAnimation {
+    /// Returns whether every step of the animation has been yielded.
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.frames
+    }
+}
+
+impl Iterator for CursorAnimation {
+    type Item = Cursor;
+
+    fn next(&mut self) -> Option<Cursor> {
+        if self.is_finished() {
+            return None;
+        }
+        self.frame += 1;
+
+        let t = self.frame as f32 / self.frames as f32;
+        let x = self.easing.lerp(self.from.0 as f32, self.to.0 as f32, t);
+        let y = self.easing.lerp(self.from.1 as f32, self.to.1 as f32, t);
+        Some(Cursor::Move(x.round() as u16, y.round() as u16))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.frames - self.frame) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl Cursor {
+    /// Creates an animation moving the cursor from `from` to `to` in `frames` steps.
+    ///
+    /// # Arguments
+    /// * `from` - The start position `(x, y)`.
+    /// * `to` - The target position `(x, y)`.
+    /// * `frames` - The number of frames the movement takes (at least 1).
+    /// * `easing` - The easing curve of the movement.
+    ///
+    /// # Returns
+    /// A [`CursorAnimation`] yielding one `Cursor::Move` per frame.
+    pub fn animate(
+        from: (u16, u16),
+        to: (u16, u16),
+        frames: u32,
+        easing: Easing,
+    ) -> CursorAnimation {
+        CursorAnimation {
+            from,
+            to,
+            frames: frames.max(1),
+            frame: 0,
+            easing,
         }
     }
 }
@@ -0,0 +1,453 @@
+//! This module provides the `Dialog` component: modal message boxes, confirmation prompts and input prompts.
+//!
+//! A dialog is drawn centered over the scene, which is dimmed below it, and takes all input until it is dismissed. `Dialog::info`, `Dialog::confirm` and `Dialog::input` create the three common kinds; `run()` shows one and blocks until the user answered, while `draw_over()` and the `Widget` methods allow driving a dialog from an existing event loop instead.
+//!
+//! # Structs
+//!
+//! - `Dialog`: A titled box with a message, an optional text field and a row of buttons.
+//!
+//! # Enums
+//!
+//! - `DialogAnswer`: How the user dismissed a dialog.
+//!
+//! # Methods
+//!
+//! - `info(title, message)`: A message with an OK button.
+//! - `confirm(title, message)`: A question with Yes / No buttons (Y / N also answer).
+//! - `input(title, message)`: A prompt with a text field and OK / Cancel buttons.
+//! - `field(func)`: Configures the text field of an input dialog (placeholder, validator, ...).
+//! - `center_in(area)`: Centers the dialog inside a region.
+//! - `answer()` / `take_answer()`: Return the answer once the dialog was dismissed.
+//! - `draw_over(objects, out)`: Draws the objects dimmed, then the dialog on top.
+//! - `run(app, objects)`: Shows the dialog and waits for the answer.
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::App;
+use crate::border;
+use crate::button::Button;
+use crate::cursor::Cursor;
+use crate::errors::NyanResult;
+use crate::event::{NyanEvent, NyanMouse};
+use crate::input::{NyanInput, NyanKey};
+use crate::layout::{self, Align};
+use crate::nyan_obj::NyanObj;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::text_input::TextInput;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// The widest a dialog's message is wrapped to, in cells.
+const MAX_MESSAGE_WIDTH: u16 = 60;
+
+/// The `DialogAnswer` enum tells how the user dismissed a dialog.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DialogAnswer {
+    /// The OK button of an info dialog.
+    Ok,
+    /// The Yes button (or Y) of a confirmation.
+    Yes,
+    /// The No button (or N) of a confirmation.
+    No,
+    /// The dialog was dismissed with Esc or the Cancel button.
+    Cancel,
+    /// The text entered into an input dialog, confirmed with Enter or OK.
+    Text(String),
+}
+
+impl DialogAnswer {
+    /// Returns whether the answer is `Yes`, e.g. for `if dialog.run(..)?.is_yes() { .. }`.
+    pub fn is_yes(&self) -> bool {
+        *self == DialogAnswer::Yes
+    }
+}
+
+/// The kind of a dialog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Kind {
+    Info,
+    Confirm,
+    Input,
+}
+
+/// `Dialog` is a modal box that asks the user something and waits for the answer.
+///
+/// # Example
+/// ```rust
+/// use nyan::dialog::{Dialog, DialogAnswer};
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::widget::Widget;
+///
+/// let mut dialog = Dialog::confirm("Quit", "Discard unsaved changes?");
+/// dialog.handle_input(&NyanInput::Key(NyanKey::Y));
+/// assert_eq!(dialog.take_answer(), Some(DialogAnswer::Yes));
+///
+/// let mut name = Dialog::input("Rename", "New name:");
+/// name.handle_paste("nyan.rs");
+/// name.handle_input(&NyanInput::Enter);
+/// assert_eq!(name.take_answer(), Some(DialogAnswer::Text("nyan.rs".into())));
+/// ```
+///
+/// Blocking until the user answered:
+/// ```ignore
+/// if Dialog::confirm("Quit", "Really quit?").run(&mut app, &obj)?.is_yes() {
+///     break;
+/// }
+/// ```
+pub struct Dialog<'a> {
+    kind: Kind,
+    title: Cow<'a, str>,
+    message: Cow<'a, str>,
+    input: Option<TextInput<'a>>,
+    buttons: Vec<(Button<'a>, DialogAnswer)>,
+    /// The button Enter activates.
+    current: usize,
+    answer: Option<DialogAnswer>,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> Debug for Dialog<'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("Dialog")
+            .field("kind", &self.kind)
+            .field("title", &self.title)
+            .field("message", &self.message)
+            .field("input", &self.input)
+            .field("current", &self.current)
+            .field("answer", &self.answer)
+            .field("area", &self.area)
+            .finish()
+    }
+}
+
+impl<'a> Dialog<'a> {
+    fn new<T: Into<Cow<'a, str>>, M: Into<Cow<'a, str>>>(
+        kind: Kind,
+        title: T,
+        message: M,
+        buttons: Vec<(&'static str, DialogAnswer)>,
+    ) -> Self {
+        let mut dialog = Self {
+            kind,
+            title: title.into(),
+            message: message.into(),
+            input: None,
+            buttons: buttons
+                .into_iter()
+                .map(|(label, answer)| (Button::new(label), answer))
+                .collect(),
+            current: 0,
+            answer: None,
+            theme: Theme::default(),
+            area: Rect::default(),
+        };
+        dialog.focus_button(0);
+        dialog
+    }
+
+    /// Creates a dialog that shows `message` until it is dismissed with OK.
+    pub fn info<T: Into<Cow<'a, str>>, M: Into<Cow<'a, str>>>(title: T, message: M) -> Self {
+        Self::new(Kind::Info, title, message, vec![("OK", DialogAnswer::Ok)])
+    }
+
+    /// Creates a dialog that asks a yes / no question. Esc answers `DialogAnswer::Cancel`.
+    pub fn confirm<T: Into<Cow<'a, str>>, M: Into<Cow<'a, str>>>(title: T, message: M) -> Self {
+        Self::new(
+            Kind::Confirm,
+            title,
+            message,
+            vec![("Yes", DialogAnswer::Yes), ("No", DialogAnswer::No)],
+        )
+    }
+
+    /// Creates a dialog that asks for a line of text, answered with `DialogAnswer::Text`.
+    pub fn input<T: Into<Cow<'a, str>>, M: Into<Cow<'a, str>>>(title: T, message: M) -> Self {
+        let mut dialog = Self::new(
+            Kind::Input,
+            title,
+            message,
+            vec![("OK", DialogAnswer::Ok), ("Cancel", DialogAnswer::Cancel)],
+        );
+        let mut input = TextInput::new().borderless();
+        input.set_focused(true);
+        dialog.input = Some(input);
+        dialog
+    }
+
+    /// Configures the text field of an input dialog, e.g.
+    /// `.field(|f| f.placeholder("name").max_length(32))`. Other dialogs are left unchanged.
+    pub fn field<F: FnOnce(TextInput<'a>) -> TextInput<'a>>(self, func: F) -> Self {
+        let mut dialog = self;
+        dialog.input = dialog.input.take().map(func);
+        dialog
+    }
+
+    /// Sets the colors of the dialog.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut dialog = self;
+        dialog.theme = theme;
+        for (button, _) in &mut dialog.buttons {
+            *button = std::mem::replace(button, Button::new("")).theme(theme);
+        }
+        dialog.input = dialog.input.take().map(|input| input.theme(theme));
+        dialog
+    }
+
+    /// Returns the answer once the dialog was dismissed.
+    pub fn answer(&self) -> Option<&DialogAnswer> {
+        self.answer.as_ref()
+    }
+
+    /// Returns the answer once the dialog was dismissed, and forgets it.
+    pub fn take_answer(&mut self) -> Option<DialogAnswer> {
+        self.answer.take()
+    }
+
+    /// Centers the dialog inside `area`, shrinking it if it does not fit.
+    pub fn center_in(&mut self, area: Rect) {
+        let (width, height) = self.size();
+        let size = (width.min(area.width), height.min(area.height));
+        let (x, y) = layout::center(area, size);
+        self.set_area(Rect::new(x, y, size.0, size.1));
+    }
+
+    /// Draws every object of `objects` dimmed, then the dialog on top.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if everything was drawn.
+    /// - An error if writing fails.
+    pub fn draw_over<W: Write>(&self, objects: &NyanObj, out: &mut W) -> NyanResult<()> {
+        for id in objects.ids() {
+            objects.draw_overlaid_in(out, id, NyanStyle::new().dim())?;
+        }
+        self.draw(out)?;
+        Ok(())
+    }
+
+    /// Shows the dialog centered on the terminal over the dimmed `objects` and waits until the
+    /// user answered. All input goes to the dialog meanwhile.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(DialogAnswer)` once the dialog was dismissed.
+    /// - An error if drawing or reading input fails.
+    pub fn run(self, app: &mut App, objects: &NyanObj) -> NyanResult<DialogAnswer> {
+        let mut dialog = self;
+        loop {
+            dialog.center_in(Rect::terminal()?);
+            let mut drawn = Ok(());
+            app.draw_with(|app| drawn = dialog.draw_over(objects, app))?;
+            drawn?;
+
+            let event = NyanEvent::<()>::get_event()?;
+            widget::handle_event(&mut dialog, &event);
+            if let Some(answer) = dialog.take_answer() {
+                return Ok(answer);
+            }
+        }
+    }
+
+    /// Moves the keyboard focus to the button at `index`.
+    fn focus_button(&mut self, index: usize) {
+        self.current = index;
+        for (i, (button, _)) in self.buttons.iter_mut().enumerate() {
+            button.set_focused(i == index);
+        }
+    }
+
+    /// Dismisses the dialog with the answer of the button at `index`.
+    fn choose(&mut self, index: usize) {
+        let answer = match (&self.buttons[index].1, &self.input) {
+            (DialogAnswer::Ok, Some(input)) => {
+                if !input.is_valid() {
+                    // Invalid text cannot be confirmed.
+                    return;
+                }
+                DialogAnswer::Text(input.text().to_string())
+            }
+            (answer, _) => answer.clone(),
+        };
+        self.answer = Some(answer);
+    }
+
+    /// Returns the message wrapped to the dialog's width.
+    fn lines(&self, width: u16) -> Vec<String> {
+        layout::wrap_text(&self.message, width)
+    }
+
+    /// Returns the width of the message area.
+    fn message_width(&self) -> u16 {
+        let widest = self.message.lines().map(|l| l.width()).max().unwrap_or(0);
+        let buttons: usize = self
+            .buttons
+            .iter()
+            .map(|(b, _)| b.size().0 as usize + 1)
+            .sum();
+        let title = self.title.width() + 2;
+        (widest.max(buttons).max(title).max(24) as u16).min(MAX_MESSAGE_WIDTH)
+    }
+
+    /// Lays out the buttons on the second row from the bottom, centered.
+    fn place_buttons(&mut self) {
+        let total: u16 = self.buttons.iter().map(|(b, _)| b.size().0 + 1).sum();
+        let row = self.area.bottom().saturating_sub(2);
+        let mut x = self.area.x + Align::Center.offset(self.area.width, total.saturating_sub(1));
+        for (button, _) in &mut self.buttons {
+            let width = button.size().0;
+            button.set_area(Rect::new(x, row, width, 1));
+            x += width + 1;
+        }
+    }
+}
+
+impl<'a> Widget for Dialog<'a> {
+    /// The message plus a border, a padding of one row / two columns, the text field and the
+    /// buttons.
+    fn size(&self) -> (u16, u16) {
+        let width = self.message_width();
+        let lines = self.lines(width).len() as u16;
+        let input = if self.input.is_some() { 2 } else { 0 };
+        (width + 6, lines + input + 6)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+        self.place_buttons();
+        let inner = Rect::new(
+            area.x + 3,
+            area.y + 2,
+            area.width.saturating_sub(6),
+            area.height,
+        );
+        let lines = self.lines(inner.width).len() as u16;
+        if let Some(input) = &mut self.input {
+            input.set_area(Rect::new(inner.x, inner.y + lines + 1, inner.width, 1));
+        }
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        let area = self.area;
+        if area.width < 2 || area.height < 2 {
+            return Ok(());
+        }
+
+        // Blank the inside, so the scene below does not show through.
+        let blank = " ".repeat(area.width as usize);
+        for y in area.y..area.bottom() {
+            Cursor::Move(area.x, y).queue(&mut out)?;
+            widget::write_styled(out, self.theme.text, &blank)?;
+        }
+        NyanStyle::base().patch(self.theme.accent).apply(&mut out)?;
+        border::default_border().draw(&mut out, area)?;
+        NyanStyle::reset(&mut out)?;
+
+        let title = layout::clip_text(
+            &format!(" {} ", self.title),
+            0,
+            area.width.saturating_sub(4) as usize,
+        );
+        Cursor::Move(area.x + 2, area.y).queue(&mut out)?;
+        widget::write_styled(out, self.theme.accent, &title)?;
+
+        let width = area.width.saturating_sub(6);
+        let bottom = area.bottom().saturating_sub(3);
+        for (row, line) in self.lines(width).iter().enumerate() {
+            let y = area.y + 2 + row as u16;
+            if y >= bottom {
+                break;
+            }
+            Cursor::Move(area.x + 3, y).queue(&mut out)?;
+            widget::write_styled(out, self.theme.text, line)?;
+        }
+
+        if let Some(input) = &self.input {
+            let field = input.area();
+            // The text field gets its own background, so it stands out from the message.
+            Cursor::Move(field.x, field.y).queue(&mut out)?;
+            widget::write_styled(out, self.theme.selection, &" ".repeat(field.width as usize))?;
+            input.draw(out)?;
+            if let Some(error) = input.error() {
+                let error = layout::clip_text(error, 0, field.width as usize);
+                Cursor::Move(field.x, field.y + 1).queue(&mut out)?;
+                widget::write_styled(out, self.theme.error, &error)?;
+            }
+        }
+        for (button, _) in &self.buttons {
+            button.draw(out)?;
+        }
+        out.flush()
+    }
+
+    /// Takes every key while the dialog is shown.
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        let count = self.buttons.len();
+        match input {
+            NyanInput::Esc => self.answer = Some(DialogAnswer::Cancel),
+            NyanInput::Enter => self.choose(self.current),
+            NyanInput::Tab => self.focus_button((self.current + 1) % count),
+            NyanInput::Shift(NyanInput::Tab) => {
+                self.focus_button((self.current + count - 1) % count)
+            }
+            NyanInput::Key(NyanKey::Y) if self.kind == Kind::Confirm => {
+                self.answer = Some(DialogAnswer::Yes)
+            }
+            NyanInput::Key(NyanKey::N) if self.kind == Kind::Confirm => {
+                self.answer = Some(DialogAnswer::No)
+            }
+            _ => match &mut self.input {
+                Some(field) => {
+                    field.handle_input(input);
+                }
+                None => match input {
+                    NyanInput::LeftAllow => self.focus_button(self.current.saturating_sub(1)),
+                    NyanInput::RightAllow => self.focus_button((self.current + 1).min(count - 1)),
+                    NyanInput::Key(NyanKey::OtherKey(' ')) => self.choose(self.current),
+                    _ => {}
+                },
+            },
+        }
+        true
+    }
+
+    /// Takes every mouse action while the dialog is shown; clicking a button answers.
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        if let Some(input) = &mut self.input {
+            input.handle_mouse(mouse);
+        }
+        let clicked = self.buttons.iter_mut().position(|(button, _)| {
+            button.handle_mouse(mouse);
+            button.take_activation()
+        });
+        if let Some(index) = clicked {
+            self.choose(index);
+        }
+        true
+    }
+
+    fn handle_paste(&mut self, text: &str) -> bool {
+        match &mut self.input {
+            Some(input) => input.handle_paste(text),
+            None => false,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    /// A dialog always has the focus while it is shown.
+    fn is_focused(&self) -> bool {
+        true
+    }
+}
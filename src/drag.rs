@@ -0,0 +1,185 @@
+//! This module provides the `DragController` struct, which lets objects in a `NyanObj` be moved by dragging them with the mouse.
+//!
+//! Only objects flagged as draggable can be moved. The controller hit-tests mouse presses against the objects, updates the stored coordinate of the grabbed object while the mouse is dragged, and reports `DragEvent`s so the application can react (e.g. raise a window-like panel or save a level layout).
+//!
+//! Mouse events are only reported when mouse capture is enabled on the `App` (see `App::mouse_capture`).
+//!
+//! # Structs
+//!
+//! - `DragController`: Tracks draggable objects and the object currently being dragged.
+//!
+//! # Enums
+//!
+//! - `DragEvent`: Notifications emitted when a drag starts, moves or ends.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a controller that drags with the left mouse button.
+//! - `button(button)`: Changes the mouse button used for dragging.
+//! - `set_draggable(id, draggable)`: Flags or unflags an object as draggable.
+//! - `handle_mouse(obj, mouse)`: Processes a mouse action and moves the grabbed object.
+//! - `handle_event(obj, event)`: Same as `handle_mouse`, but accepts any `NyanEvent` and ignores non-mouse events.
+
+use std::collections::HashSet;
+
+use crate::event::{NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::nyan_obj::NyanObj;
+
+/// Notifications emitted by `DragController`.
+///
+/// Each variant carries the id of the dragged object and its coordinate `(x, y)` at the time of the event.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DragEvent {
+    /// The object was grabbed.
+    Started { id: String, x: u16, y: u16 },
+    /// The object was moved to a new coordinate.
+    Moved { id: String, x: u16, y: u16 },
+    /// The object was released.
+    Ended { id: String, x: u16, y: u16 },
+}
+
+/// The object currently being dragged, and where it was grabbed relative to its coordinate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Grab {
+    id: String,
+    offset: (u16, u16),
+}
+
+/// `DragController` moves draggable objects of a `NyanObj` with the mouse.
+///
+/// # Example
+/// ```ignore
+/// let mut drag = DragController::new();
+/// drag.set_draggable("panel", true);
+///
+/// loop {
+///     let event = NyanEvent::get_event()?;
+///     if let Some(DragEvent::Ended { id, x, y }) = drag.handle_event(&mut obj, &event) {
+///         println!("{id} dropped at ({x}, {y})");
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DragController {
+    draggable: HashSet<String>,
+    button: NyanMouseButton,
+    grab: Option<Grab>,
+}
+
+impl Default for DragController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DragController {
+    /// Creates a new `DragController` that drags with the left mouse button.
+    pub fn new() -> Self {
+        Self {
+            draggable: HashSet::new(),
+            button: NyanMouseButton::Left,
+            grab: None,
+        }
+    }
+
+    /// Changes the mouse button used for dragging.
+    ///
+    /// # Returns
+    /// The controller with the new button set.
+    pub fn button(self, button: NyanMouseButton) -> Self {
+        let mut drag = self;
+        drag.button = button;
+        drag
+    }
+
+    /// Flags or unflags an object as draggable.
+    ///
+    /// # Arguments
+    /// * `id` - The identifier of the object.
+    /// * `draggable` - Whether the object can be dragged.
+    pub fn set_draggable<P: Into<String>>(&mut self, id: P, draggable: bool) {
+        let id = id.into();
+        if draggable {
+            self.draggable.insert(id);
+        } else {
+            self.draggable.remove(&id);
+        }
+    }
+
+    /// Returns whether an object is flagged as draggable.
+    pub fn is_draggable(&self, id: &str) -> bool {
+        self.draggable.contains(id)
+    }
+
+    /// Returns the id of the object currently being dragged.
+    pub fn dragging(&self) -> Option<&str> {
+        self.grab.as_ref().map(|grab| grab.id.as_str())
+    }
+
+    /// Processes a mouse action.
+    ///
+    /// Pressing the drag button over a draggable object grabs it, dragging moves it so the
+    /// grabbed cell stays under the pointer, and releasing the button drops it.
+    ///
+    /// # Arguments
+    /// * `obj` - The object collection containing the draggable objects.
+    /// * `mouse` - The mouse action to process.
+    ///
+    /// # Returns
+    /// * `Some(DragEvent)` if the action started, moved or ended a drag.
+    /// * `None` otherwise.
+    pub fn handle_mouse(&mut self, obj: &mut NyanObj, mouse: &NyanMouse) -> Option<DragEvent> {
+        match mouse.kind {
+            NyanMouseKind::Down(button) if button == self.button => {
+                let id = obj.hit_test(mouse.x, mouse.y)?.to_string();
+                if !self.draggable.contains(&id) {
+                    return None;
+                }
+
+                let (x, y) = obj.coordinate(id.clone())?;
+                self.grab = Some(Grab {
+                    id: id.clone(),
+                    offset: (mouse.x - x, mouse.y - y),
+                });
+                Some(DragEvent::Started { id, x, y })
+            }
+            NyanMouseKind::Drag(button) if button == self.button => {
+                let grab = self.grab.as_ref()?;
+                let x = mouse.x.saturating_sub(grab.offset.0);
+                let y = mouse.y.saturating_sub(grab.offset.1);
+
+                if obj.move_object(grab.id.clone(), (x, y)).is_err() {
+                    // The object was removed while it was being dragged.
+                    self.grab = None;
+                    return None;
+                }
+                Some(DragEvent::Moved {
+                    id: grab.id.clone(),
+                    x,
+                    y,
+                })
+            }
+            NyanMouseKind::Up(button) if button == self.button => {
+                let grab = self.grab.take()?;
+                let (x, y) = obj.coordinate(grab.id.clone())?;
+                Some(DragEvent::Ended { id: grab.id, x, y })
+            }
+            _ => None,
+        }
+    }
+
+    /// Processes any event, ignoring everything but mouse actions.
+    ///
+    /// See [`DragController::handle_mouse`].
+    pub fn handle_event<U>(
+        &mut self,
+        obj: &mut NyanObj,
+        event: &NyanEvent<U>,
+    ) -> Option<DragEvent> {
+        if let NyanEvent::Mouse(mouse) = event {
+            self.handle_mouse(obj, mouse)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! This module defines the `Easing` enum, a set of easing curves for smooth movements and animations.
+//!
+//! An easing curve maps the linear progress of an animation (`0.0` at the start, `1.0` at the end) to an eased progress, so movements can accelerate and decelerate instead of moving at a constant speed.
+//!
+//! # Enum
+//!
+//! - `Easing`: Represents an easing curve (linear, quadratic, cubic and sine variants).
+//!
+//! # Methods
+//!
+//! - `apply(t: f32)`: Returns the eased progress for the linear progress `t`.
+//! - `lerp(from, to, t)`: Interpolates between two values with the easing curve applied.
+
+/// The `Easing` enum represents an easing curve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Starts slowly and accelerates.
+    InQuad,
+    /// Starts quickly and decelerates.
+    OutQuad,
+    /// Accelerates until halfway, then decelerates.
+    InOutQuad,
+    /// Like `InQuad`, but stronger.
+    InCubic,
+    /// Like `OutQuad`, but stronger.
+    OutCubic,
+    /// Like `InOutQuad`, but stronger.
+    InOutCubic,
+    /// A gentle acceleration and deceleration following a sine curve.
+    InOutSine,
+}
+
+impl Easing {
+    /// Returns the eased progress for the linear progress `t`.
+    ///
+    /// # Arguments
+    /// * `t` - The linear progress, clamped to `0.0..=1.0`.
+    ///
+    /// # Returns
+    /// The eased progress, `0.0` at the start and `1.0` at the end.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::InQuad => t * t,
+            Easing::OutQuad => t * (2.0 - t),
+            Easing::InOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::InCubic => t * t * t,
+            Easing::OutCubic => 1.0 - (1.0 - t).powi(3),
+            Easing::InOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::InOutSine => -((std::f32::consts::PI * t).cos() - 1.0) / 2.0,
+        }
+    }
+
+    /// Interpolates between `from` and `to` with the easing curve applied.
+    ///
+    /// # Arguments
+    /// * `from` - The value at the start (`t = 0.0`).
+    /// * `to` - The value at the end (`t = 1.0`).
+    /// * `t` - The linear progress.
+    pub fn lerp(self, from: f32, to: f32, t: f32) -> f32 {
+        from + (to - from) * self.apply(t)
+    }
+}
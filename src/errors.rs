@@ -1,7 +1,37 @@
+//! This module provides `NyanError`, the error returned by the fallible functions of nyan, and the `NyanResult` alias.
+//!
+//! Each kind of failure is its own variant, so callers can match on it, e.g. to tell a missing object from a terminal that went away. Errors of the standard library's I/O are wrapped in `NyanError::Io`, so `?` works on them in functions returning `NyanResult`.
+//!
+//! # Enums
+//!
+//! - `NyanError`: The ways a nyan function can fail.
+//!
+//! # Types
+//!
+//! - `NyanResult<T>`: `Result<T, NyanError>`, returned by the public API.
+
 use std::borrow::Cow;
+use std::io;
+use std::sync::Arc;
 use thiserror::Error;
 
-#[derive(Error, Debug, Clone, PartialEq, Eq)]
+/// The result of the fallible functions of nyan.
+pub type NyanResult<T> = Result<T, NyanError<'static>>;
+
+/// The `NyanError` enum tells why a nyan function failed.
+///
+/// # Example
+/// ```rust
+/// use nyan::errors::NyanError;
+/// use nyan::nyan_obj::NyanObj;
+///
+/// let mut obj = NyanObj::new();
+/// match obj.set_visible("missing", false) {
+///     Err(NyanError::ObjectNotFound(id)) => assert_eq!(id, "missing"),
+///     other => panic!("unexpected {other:?}"),
+/// }
+/// ```
+#[derive(Error, Debug, Clone)]
 pub enum NyanError<'a> {
     #[error("Failed to draw {0}")]
     DrawFailed(Cow<'a, str>),
@@ -11,4 +41,74 @@ pub enum NyanError<'a> {
 
     #[error("Object with ID \"{0}\" is not found")]
     ObjectNotFound(Cow<'a, str>),
+
+    #[error("Position ({0}, {1}) is outside the terminal ({2}x{3})")]
+    OutOfBounds(u16, u16, u16, u16),
+
+    #[error("Invalid layout file: {0}")]
+    InvalidLayout(Cow<'a, str>),
+
+    /// A config file could not be read, e.g. because it binds an unknown key.
+    #[error("Invalid config file: {0}")]
+    InvalidConfig(Cow<'a, str>),
+
+    #[error("Invalid FIGlet font: {0}")]
+    InvalidFont(Cow<'a, str>),
+
+    #[error("Invalid image: {0}")]
+    InvalidImage(Cow<'a, str>),
+
+    #[error("Invalid data: {0}")]
+    InvalidData(Cow<'a, str>),
+
+    /// Reading or writing failed; shared so the error can be cloned.
+    #[error("I/O error: {0}")]
+    Io(#[source] Arc<io::Error>),
+
+    /// The terminal, or the thread or connection it is read through, stopped working.
+    #[error("Terminal error: {0}")]
+    Terminal(Cow<'a, str>),
+
+    /// A script failed to compile or to run.
+    #[error("Script error: {0}")]
+    Script(Cow<'a, str>),
+
+    /// The request is not supported, e.g. a file format whose feature is off.
+    #[error("Not supported: {0}")]
+    NotSupported(Cow<'a, str>),
+
+    /// Any other failure.
+    #[error("{0}")]
+    Other(Cow<'a, str>),
+}
+
+impl From<io::Error> for NyanError<'_> {
+    fn from(error: io::Error) -> Self {
+        NyanError::Io(Arc::new(error))
+    }
+}
+
+impl NyanError<'_> {
+    /// Returns the error with its text owned, so it can outlive what it borrows.
+    pub fn into_owned(self) -> NyanError<'static> {
+        let owned = |text: Cow<'_, str>| Cow::Owned(text.into_owned());
+        match self {
+            NyanError::DrawFailed(text) => NyanError::DrawFailed(owned(text)),
+            NyanError::Cursor(text) => NyanError::Cursor(owned(text)),
+            NyanError::ObjectNotFound(text) => NyanError::ObjectNotFound(owned(text)),
+            NyanError::OutOfBounds(x, y, width, height) => {
+                NyanError::OutOfBounds(x, y, width, height)
+            }
+            NyanError::InvalidLayout(text) => NyanError::InvalidLayout(owned(text)),
+            NyanError::InvalidConfig(text) => NyanError::InvalidConfig(owned(text)),
+            NyanError::InvalidFont(text) => NyanError::InvalidFont(owned(text)),
+            NyanError::InvalidImage(text) => NyanError::InvalidImage(owned(text)),
+            NyanError::InvalidData(text) => NyanError::InvalidData(owned(text)),
+            NyanError::Io(error) => NyanError::Io(error),
+            NyanError::Terminal(text) => NyanError::Terminal(owned(text)),
+            NyanError::Script(text) => NyanError::Script(owned(text)),
+            NyanError::NotSupported(text) => NyanError::NotSupported(owned(text)),
+            NyanError::Other(text) => NyanError::Other(owned(text)),
+        }
+    }
 }
@@ -0,0 +1,471 @@
+//! This module defines the `NyanEvent` enum, a single type covering everything that can happen in the terminal.
+//!
+//! Instead of polling keys, mouse and window changes through separate paths, applications can call `NyanEvent::get_event` once per frame and handle key presses, mouse actions, resizes, pasted text, focus changes and idle ticks in one `match`.
+//!
+//! Background threads can push their own events into the stream through an `EventSender`. Use an `EventQueue` instead of the plain `NyanEvent::get_event` to receive them interleaved with terminal events as `NyanEvent::User`.
+//!
+//! An `EventQueue` can also read the terminal on a thread of its own (see `EventQueue::input_thread`), so input is picked up as it arrives, many times per frame, instead of once per frame when the app gets around to polling. Then key response is not quantized to the frame duration of a slowly drawing app.
+//!
+//! Mouse, paste and focus events are only reported by the terminal when they are enabled on the `App` (see `App::mouse_capture`, `App::bracketed_paste` and `App::focus_change`).
+//!
+//! The events an app reacts to come from an `EventSource`: an `EventQueue` for the terminal, the input of a remote session, or a scripted [`TestDriver`](crate::testing::TestDriver) in tests. [`App::run`](crate::app::App::run) draws and handles them in a loop.
+//!
+//! # Traits
+//!
+//! - `EventSource`: Where the events of an app loop come from.
+//!
+//! # Enums
+//!
+//! - `NyanEvent`: Represents a single terminal event (key, mouse, resize, paste, focus or tick).
+//! - `NyanMouseKind`: Represents the kind of a mouse action (press, release, drag, move or scroll).
+//! - `NyanMouseButton`: Represents a mouse button.
+//!
+//! # Structs
+//!
+//! - `NyanMouse`: A mouse action together with the cell `(x, y)` it happened at.
+//! - `EventQueue`: Delivers terminal events and user-defined events from one place.
+//! - `EventSender`: A cloneable handle that other threads use to push user-defined events into an `EventQueue`.
+//!
+//! # Methods
+//!
+//! - `get_event`: Waits for 16 milliseconds using `poll` and returns the next event, or `NyanEvent::Tick` if nothing happened.
+//! - `drain_events`: Returns every event that is already pending without waiting.
+//! - `EventQueue::sender`: Returns an `EventSender` for the queue.
+//! - `EventQueue::input_thread`: Reads terminal events on a thread that polls at a given rate.
+//! - `EventSender::send`: Pushes a user-defined event into the queue.
+
+use std::borrow::Cow;
+use std::{
+    fmt::Debug,
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crossterm::event::{self, Event, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::errors::{NyanError, NyanResult};
+use crate::input::NyanInput;
+
+/// `NyanMouseButton` represents a mouse button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NyanMouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+impl From<MouseButton> for NyanMouseButton {
+    fn from(button: MouseButton) -> Self {
+        match button {
+            MouseButton::Left => Self::Left,
+            MouseButton::Right => Self::Right,
+            MouseButton::Middle => Self::Middle,
+        }
+    }
+}
+
+/// `NyanMouseKind` represents the kind of a mouse action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NyanMouseKind {
+    /// A button was pressed
+    Down(NyanMouseButton),
+    /// A button was released
+    Up(NyanMouseButton),
+    /// The mouse was moved while a button was held down
+    Drag(NyanMouseButton),
+    /// The mouse was moved without any button held down
+    Moved,
+    /// Scroll wheel actions
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+}
+
+/// `NyanMouse` is a mouse action and the terminal cell it happened at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NyanMouse {
+    /// The kind of the mouse action.
+    pub kind: NyanMouseKind,
+    /// The column of the mouse pointer.
+    pub x: u16,
+    /// The row of the mouse pointer.
+    pub y: u16,
+}
+
+impl From<MouseEvent> for NyanMouse {
+    fn from(mouse: MouseEvent) -> Self {
+        let kind = match mouse.kind {
+            MouseEventKind::Down(b) => NyanMouseKind::Down(b.into()),
+            MouseEventKind::Up(b) => NyanMouseKind::Up(b.into()),
+            MouseEventKind::Drag(b) => NyanMouseKind::Drag(b.into()),
+            MouseEventKind::Moved => NyanMouseKind::Moved,
+            MouseEventKind::ScrollUp => NyanMouseKind::ScrollUp,
+            MouseEventKind::ScrollDown => NyanMouseKind::ScrollDown,
+            MouseEventKind::ScrollLeft => NyanMouseKind::ScrollLeft,
+            MouseEventKind::ScrollRight => NyanMouseKind::ScrollRight,
+        };
+
+        Self {
+            kind,
+            x: mouse.column,
+            y: mouse.row,
+        }
+    }
+}
+
+/// `EventSource` is where [`App::run`](crate::app::App::run) reads the events of an app from.
+pub trait EventSource<U = ()> {
+    /// Returns the next event, waiting briefly for it like [`NyanEvent::get_event`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(NyanEvent))` with the event, or `NyanEvent::Tick` if nothing happened.
+    /// - `Ok(None)` once the source has ended, e.g. a script ran out of events.
+    /// - An error if reading an event fails.
+    fn next_event(&mut self) -> NyanResult<Option<NyanEvent<U>>>;
+}
+
+/// `NyanEvent` represents anything that happened in the terminal.
+///
+/// The type parameter `U` is the type of user-defined events sent through an `EventSender`.
+/// It defaults to `()` for applications that only handle terminal events.
+#[derive(Clone, PartialEq, Eq)]
+pub enum NyanEvent<U = ()> {
+    /// A key was pressed
+    Key(NyanInput<'static>),
+    /// A mouse action (requires `App::mouse_capture`)
+    Mouse(NyanMouse),
+    /// The terminal was resized to `(width, height)`
+    Resize(u16, u16),
+    /// Text was pasted (requires `App::bracketed_paste`)
+    Paste(String),
+    /// The terminal window gained focus (requires `App::focus_change`)
+    FocusGained,
+    /// The terminal window lost focus (requires `App::focus_change`)
+    FocusLost,
+    /// Nothing happened during the poll interval
+    Tick,
+    /// A user-defined event pushed through an `EventSender`
+    User(U),
+}
+
+impl<U: Debug> Debug for NyanEvent<U> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Key(k) => write!(fmt, "NyanEvent::Key({:?})", k),
+            Self::Mouse(m) => write!(fmt, "NyanEvent::Mouse({:?})", m),
+            Self::Resize(w, h) => write!(fmt, "NyanEvent::Resize({w}, {h})"),
+            Self::Paste(p) => write!(fmt, "NyanEvent::Paste({:?})", p),
+            Self::FocusGained => write!(fmt, "NyanEvent::FocusGained"),
+            Self::FocusLost => write!(fmt, "NyanEvent::FocusLost"),
+            Self::Tick => write!(fmt, "NyanEvent::Tick"),
+            Self::User(u) => write!(fmt, "NyanEvent::User({:?})", u),
+        }
+    }
+}
+
+impl<U> From<Event> for NyanEvent<U> {
+    /// Converts a crossterm `Event` into the matching `NyanEvent`.
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Key(key) => Self::Key(key.into()),
+            Event::Mouse(mouse) => Self::Mouse(mouse.into()),
+            Event::Resize(w, h) => Self::Resize(w, h),
+            Event::Paste(text) => Self::Paste(text),
+            Event::FocusGained => Self::FocusGained,
+            Event::FocusLost => Self::FocusLost,
+        }
+    }
+}
+
+impl NyanEvent {
+    /// `get_event` retrieves the next terminal event.
+    ///
+    /// Waits for 16 milliseconds using `poll`, the same interval as `NyanInput::get_input`.
+    ///
+    /// # Returns
+    /// * `Ok(NyanEvent)` - the event that happened, or `NyanEvent::Tick` if nothing happened
+    /// * `Err(NyanError)` - if reading the event fails
+    ///
+    /// # Example
+    /// ```ignore
+    /// match NyanEvent::get_event()? {
+    ///     NyanEvent::Key(NyanInput::Ctrl(NyanKey::C)) => break,
+    ///     NyanEvent::Resize(width, height) => println!("{width}x{height}"),
+    ///     _ => {}
+    /// }
+    /// ```
+    pub fn get_event() -> NyanResult<Self> {
+        if event::poll(Duration::from_millis(16))? {
+            return Ok(event::read()?.into());
+        }
+        Ok(Self::Tick)
+    }
+
+    /// `drain_events` retrieves every event that is already pending, without waiting.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<NyanEvent>)` - the pending events in the order they happened (empty if there are none)
+    /// * `Err(NyanError)` - if reading an event fails
+    pub fn drain_events() -> NyanResult<Vec<Self>> {
+        let mut events = Vec::new();
+        while event::poll(Duration::ZERO)? {
+            events.push(event::read()?.into());
+        }
+        Ok(events)
+    }
+}
+
+/// `EventSender` pushes user-defined events into an `EventQueue`.
+///
+/// It can be cloned and moved to other threads, so workers can wake the UI
+/// (e.g. when a download has finished).
+pub struct EventSender<U> {
+    sender: Sender<U>,
+}
+
+impl<U> Clone for EventSender<U> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<U> Debug for EventSender<U> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("EventSender").finish()
+    }
+}
+
+impl<U> EventSender<U> {
+    /// Pushes a user-defined event into the queue.
+    ///
+    /// # Arguments
+    /// * `event` - The event to deliver as `NyanEvent::User`.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(NyanError)` if the `EventQueue` has been dropped.
+    pub fn send(&self, event: U) -> NyanResult<()> {
+        if self.sender.send(event).is_err() {
+            return Err(NyanError::Terminal(Cow::Borrowed(
+                "the event queue has been dropped",
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// `EventQueue` delivers terminal events and user-defined events from one place.
+///
+/// # Example
+/// ```ignore
+/// let mut queue = EventQueue::new();
+/// let sender = queue.sender();
+///
+/// std::thread::spawn(move || {
+///     // ... download something ...
+///     sender.send("download finished").unwrap();
+/// });
+///
+/// loop {
+///     match queue.get_event()? {
+///         NyanEvent::User(message) => status = message,
+///         NyanEvent::Key(NyanInput::Ctrl(NyanKey::C)) => break,
+///         _ => {}
+///     }
+/// }
+/// ```
+pub struct EventQueue<U> {
+    sender: Sender<U>,
+    receiver: Receiver<U>,
+    input: Option<InputThread>,
+}
+
+/// A thread reading terminal events into a channel, stopped when it is dropped.
+struct InputThread {
+    events: Receiver<io::Result<Event>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl InputThread {
+    /// Starts reading terminal events, waking up `rate` times per second to check whether to
+    /// stop.
+    fn spawn(rate: u64) -> Self {
+        let (sender, events) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let interval = Duration::from_secs(1) / rate.clamp(1, u32::MAX as u64) as u32;
+        let stopped = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                let event = match event::poll(interval) {
+                    Ok(false) => continue,
+                    Ok(true) => event::read(),
+                    Err(e) => Err(e),
+                };
+                let failed = event.is_err();
+                // The queue is gone, or the terminal cannot be read any more.
+                if sender.send(event).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Self {
+            events,
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Waits up to `timeout` for the next event read by the thread.
+    fn next(&self, timeout: Duration) -> NyanResult<Option<Event>> {
+        match self.events.recv_timeout(timeout) {
+            Ok(event) => Ok(Some(event?)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err(NyanError::Terminal(Cow::Borrowed(
+                "the input thread has stopped",
+            ))),
+        }
+    }
+}
+
+impl Drop for InputThread {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl<U> Debug for EventQueue<U> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("EventQueue").finish()
+    }
+}
+
+impl<U> Default for EventQueue<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U> EventQueue<U> {
+    /// Creates a new, empty `EventQueue`.
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver,
+            input: None,
+        }
+    }
+
+    /// Reads terminal events on a thread of its own, polling `rate` times per second, e.g.
+    /// 240 while the app draws at 30 FPS.
+    ///
+    /// Events are taken from the terminal as soon as they arrive and wait in the queue until
+    /// `get_event` or `drain_events` is called, so the app can handle input between frames
+    /// and nothing is read late because a frame took long. The rate only bounds how long
+    /// the thread takes to notice that the queue was dropped. While the thread runs, read
+    /// input only through this queue, not through `NyanInput::get_input` or
+    /// `NyanEvent::get_event`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut app = App::new(30).raw_mode();
+    /// let mut queue: EventQueue<()> = EventQueue::new().input_thread(240);
+    ///
+    /// loop {
+    ///     for event in queue.drain_events()? {
+    ///         // ...
+    ///     }
+    ///     app.draw_with(|app| { /* ... */ })?;
+    /// }
+    /// ```
+    pub fn input_thread(self, rate: u64) -> Self {
+        let mut queue = self;
+        queue.input = Some(InputThread::spawn(rate));
+        queue
+    }
+
+    /// Returns a handle that pushes user-defined events into this queue.
+    pub fn sender(&self) -> EventSender<U> {
+        EventSender {
+            sender: self.sender.clone(),
+        }
+    }
+
+    /// Pushes a user-defined event into this queue from the current thread.
+    pub fn push(&self, event: U) {
+        // The receiver lives in `self`, so sending cannot fail here.
+        let _ = self.sender.send(event);
+    }
+
+    /// Retrieves the next event.
+    ///
+    /// Pending user events are delivered first. Otherwise this waits for 16 milliseconds
+    /// for a terminal event, like `NyanEvent::get_event`, and checks for user events
+    /// that arrived during the wait before reporting `NyanEvent::Tick`.
+    ///
+    /// # Returns
+    /// * `Ok(NyanEvent)` - the next event
+    /// * `Err(NyanError)` - if reading a terminal event fails
+    pub fn get_event(&mut self) -> NyanResult<NyanEvent<U>> {
+        if let Ok(user) = self.receiver.try_recv() {
+            return Ok(NyanEvent::User(user));
+        }
+
+        let timeout = Duration::from_millis(16);
+        if let Some(input) = &self.input {
+            if let Some(event) = input.next(timeout)? {
+                return Ok(event.into());
+            }
+        } else if event::poll(timeout)? {
+            return Ok(event::read()?.into());
+        }
+
+        if let Ok(user) = self.receiver.try_recv() {
+            return Ok(NyanEvent::User(user));
+        }
+
+        Ok(NyanEvent::Tick)
+    }
+
+    /// Retrieves every event that is already pending, without waiting.
+    ///
+    /// Pending terminal events come first, followed by pending user events.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<NyanEvent>)` - the pending events (empty if there are none)
+    /// * `Err(NyanError)` - if reading a terminal event fails
+    pub fn drain_events(&mut self) -> NyanResult<Vec<NyanEvent<U>>> {
+        let mut events = Vec::new();
+        if let Some(input) = &self.input {
+            while let Some(event) = input.next(Duration::ZERO)? {
+                events.push(event.into());
+            }
+        } else {
+            while event::poll(Duration::ZERO)? {
+                events.push(event::read()?.into());
+            }
+        }
+        events.extend(self.receiver.try_iter().map(NyanEvent::User));
+        Ok(events)
+    }
+}
+
+impl<U> EventSource<U> for EventQueue<U> {
+    fn next_event(&mut self) -> NyanResult<Option<NyanEvent<U>>> {
+        self.get_event().map(Some)
+    }
+}
@@ -0,0 +1,227 @@
+//! This module provides `FigletFont`, which loads FIGlet fonts (`.flf` files) at runtime and renders text with them as large letters built from characters, e.g. for titles and splash screens.
+//!
+//! Thousands of free FIGlet fonts exist, such as the `standard`, `slant` or `big` fonts that come with `figlet`. A font holds a glyph of several lines for each printable ASCII character, usually the German umlauts, and optionally any other Unicode character. The rendered text is a plain multi-line string, so it can be shown with `Objects::new_text` and styled, aligned and laid out like any other text.
+//!
+//! Glyphs are placed next to each other as the font asks for: at their full width, or moved together until they touch ("kerning"). Fonts that ask for their letters to be merged ("smushing") are kerned instead.
+//!
+//! # Structs
+//!
+//! - `FigletFont`: The glyphs and the layout of a font.
+//!
+//! # Methods
+//!
+//! - `FigletFont::parse(source)` / `FigletFont::load(path)`: Read a font from a string or a file.
+//! - `height()`: Returns the number of lines of each glyph.
+//! - `render(text)`: Returns the text in large letters.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::errors::{NyanError, NyanResult};
+
+/// The characters every FIGlet font has, in the order of the file: printable ASCII and the
+/// German umlauts.
+const REQUIRED: [u32; 102] = {
+    let mut chars = [0; 102];
+    let mut i = 0;
+    while i < 95 {
+        chars[i] = 32 + i as u32;
+        i += 1;
+    }
+    let german = [196, 214, 220, 228, 246, 252, 223];
+    while i < 102 {
+        chars[i] = german[i - 95];
+        i += 1;
+    }
+    chars
+};
+
+/// `FigletFont` is a FIGlet font, to render text in large letters.
+///
+/// # Example
+/// ```rust
+/// use nyan::figlet::FigletFont;
+/// use nyan::objects::Objects;
+///
+/// // A tiny font: "flf2a", the hardblank `$`, 2 lines high, and 1 comment line.
+/// let mut source = String::from("flf2a$ 2 2 4 0 1\nA font of dashes\n");
+/// for _ in ' '..='~' {
+///     source.push_str("--@\n--@@\n");
+/// }
+/// let font = FigletFont::parse(&source).unwrap();
+/// assert_eq!(font.height(), 2);
+/// assert_eq!(font.render("hi"), "----\n----");
+///
+/// let title = Objects::new_text(font.render("nyan"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FigletFont {
+    height: usize,
+    hardblank: char,
+    /// Whether glyphs are moved together until they touch.
+    kerning: bool,
+    glyphs: HashMap<char, Vec<String>>,
+}
+
+impl FigletFont {
+    /// Reads a font from the contents of a `.flf` file.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(FigletFont)` with the glyphs of the font.
+    /// - An error if the header is not a FIGlet header, or a glyph is cut short.
+    pub fn parse(source: &str) -> NyanResult<Self> {
+        let invalid = |message: &str| -> NyanError<'static> {
+            NyanError::InvalidFont(Cow::Owned(message.to_string()))
+        };
+        let mut lines = source.lines();
+        let header = lines.next().unwrap_or_default();
+        let Some(settings) = header.strip_prefix("flf2a") else {
+            return Err(invalid("the file does not start with \"flf2a\""));
+        };
+        let mut chars = settings.chars();
+        let hardblank = chars.next().ok_or_else(|| invalid("no hardblank"))?;
+        let numbers: Vec<i64> = chars
+            .as_str()
+            .split_whitespace()
+            .map_while(|number| number.parse().ok())
+            .collect();
+        let [height, _baseline, _max_length, old_layout, comment_lines, ..] = numbers[..] else {
+            return Err(invalid("the header lacks settings"));
+        };
+        let height = usize::try_from(height)
+            .ok()
+            .filter(|height| *height > 0)
+            .ok_or_else(|| invalid("the height is not positive"))?;
+        let mut lines = lines.skip(comment_lines.max(0) as usize);
+
+        let mut glyphs = HashMap::new();
+        let read_glyph = |lines: &mut dyn Iterator<Item = &str>| -> Option<Vec<String>> {
+            (0..height)
+                .map(|_| lines.next().map(strip_endmark))
+                .collect()
+        };
+        for code in REQUIRED {
+            match read_glyph(&mut lines) {
+                Some(glyph) => {
+                    glyphs.insert(char::from_u32(code).unwrap_or(' '), glyph);
+                }
+                // Many fonts leave out the umlauts.
+                None if code > 126 => break,
+                None => return Err(invalid("the file ends in the middle of the glyphs")),
+            }
+        }
+        // Code-tagged glyphs: a line with the code, then the glyph.
+        while let Some(tag) = lines.next() {
+            let Some(code) = tag.split_whitespace().next().and_then(parse_code) else {
+                continue;
+            };
+            let Some(glyph) = read_glyph(&mut lines) else {
+                return Err(invalid("the file ends in the middle of a glyph"));
+            };
+            if let Some(ch) = u32::try_from(code).ok().and_then(char::from_u32) {
+                glyphs.insert(ch, glyph);
+            }
+        }
+
+        Ok(Self {
+            height,
+            hardblank,
+            kerning: old_layout >= 0,
+            glyphs,
+        })
+    }
+
+    /// Reads a font from a `.flf` file.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(FigletFont)` with the glyphs of the font.
+    /// - An error if the file cannot be read or is not a FIGlet font.
+    pub fn load<P: AsRef<Path>>(path: P) -> NyanResult<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Returns the number of lines of each glyph, and so of each line of rendered text.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `text` in the large letters of the font. Each line of `text` becomes
+    /// [`FigletFont::height`] lines; characters the font does not have are left out, and
+    /// spaces at the end of the lines are removed.
+    pub fn render(&self, text: &str) -> String {
+        let mut rendered = Vec::new();
+        for line in text.lines() {
+            let mut rows = vec![String::new(); self.height];
+            for glyph in line.chars().filter_map(|ch| self.glyphs.get(&ch)) {
+                let overlap = if self.kerning {
+                    self.overlap(&rows, glyph)
+                } else {
+                    0
+                };
+                for (row, part) in rows.iter_mut().zip(glyph) {
+                    let keep = row.chars().count() - overlap.min(trailing_spaces(row));
+                    let cut: String = row.chars().take(keep).collect();
+                    let skip = overlap - (row.chars().count() - keep);
+                    *row = cut + part.chars().skip(skip).collect::<String>().as_str();
+                }
+            }
+            rendered.extend(
+                rows.into_iter()
+                    .map(|row| row.replace(self.hardblank, " ").trim_end().to_string()),
+            );
+        }
+        rendered.join("\n")
+    }
+
+    /// Returns how many columns `glyph` can move left onto `rows` before a row touches.
+    fn overlap(&self, rows: &[String], glyph: &[String]) -> usize {
+        rows.iter()
+            .zip(glyph)
+            .map(|(row, part)| {
+                let leading = part.chars().take_while(|ch| *ch == ' ').count();
+                trailing_spaces(row) + leading
+            })
+            .min()
+            .unwrap_or(0)
+            .min(
+                glyph
+                    .iter()
+                    .map(|part| part.chars().count())
+                    .min()
+                    .unwrap_or(0),
+            )
+    }
+}
+
+/// Returns the number of spaces at the end of `row`.
+fn trailing_spaces(row: &str) -> usize {
+    row.chars().rev().take_while(|ch| *ch == ' ').count()
+}
+
+/// Removes the endmark, the last character of a glyph line, repeated on the last line.
+fn strip_endmark(line: &str) -> String {
+    let line = line.trim_end_matches(['\r', '\n']);
+    match line.chars().last() {
+        Some(endmark) => line.trim_end_matches(endmark).to_string(),
+        None => String::new(),
+    }
+}
+
+/// Parses the code of a code-tagged glyph: decimal, hexadecimal (`0x`) or octal (`0`).
+fn parse_code(code: &str) -> Option<i64> {
+    let (negative, code) = match code.strip_prefix('-') {
+        Some(code) => (true, code),
+        None => (false, code),
+    };
+    let value = if let Some(hex) = code.strip_prefix("0x").or(code.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if code.len() > 1 && code.starts_with('0') {
+        i64::from_str_radix(&code[1..], 8).ok()?
+    } else {
+        code.parse().ok()?
+    };
+    Some(if negative { -value } else { value })
+}
@@ -0,0 +1,533 @@
+//! This module provides the `FilePicker` widget, which browses the file system and lets the user choose a file.
+//!
+//! The picker lists one directory at a time: a `..` entry leading to the parent, then the subdirectories, then the files, each group sorted by name. Typing filters the list by name (case-insensitively), Enter opens the highlighted directory or chooses the highlighted file, and BackSpace (with an empty filter) or Left goes up one directory. The chosen path is returned by `take_selection()`.
+//!
+//! # Structs
+//!
+//! - `FilePicker`: The listed directory, its entries, the filter and the highlight.
+//!
+//! # Methods
+//!
+//! - `new(dir)`: Creates a picker listing `dir`.
+//! - `extensions(exts)` / `show_hidden(show)` / `directories_only(only)` / `theme(theme)`: Configure what is listed and the colors.
+//! - `dir()` / `filter()` / `highlighted()` / `highlighted_path()` / `error()`: Query the picker.
+//! - `set_dir(dir)` / `parent()` / `refresh()`: Change or reread the listed directory.
+//! - `set_filter(text)`: Filters the listed names.
+//! - `take_selection()`: Returns the chosen path once, after it was chosen.
+
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::cursor::Cursor;
+use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::NyanInput;
+use crate::layout::{self, Align};
+use crate::rect::Rect;
+use crate::scrollbar::Scrollbar;
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// One entry of the listed directory.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Entry {
+    name: String,
+    is_dir: bool,
+}
+
+/// `FilePicker` is a widget for choosing a file (or, optionally, a directory).
+///
+/// # Example
+/// ```rust
+/// use nyan::file_picker::FilePicker;
+/// use nyan::input::NyanInput;
+/// use nyan::widget::Widget;
+///
+/// let mut picker = FilePicker::new(env!("CARGO_MANIFEST_DIR")).unwrap();
+/// picker.set_focused(true);
+///
+/// picker.set_filter("cargo.t");
+/// assert_eq!(picker.highlighted(), Some("Cargo.toml"));
+///
+/// picker.handle_input(&NyanInput::Enter);
+/// let path = picker.take_selection().unwrap();
+/// assert!(path.ends_with("Cargo.toml"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FilePicker<'a> {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    /// The indices into `entries` that pass the filters, in display order.
+    visible: Vec<usize>,
+    filter: String,
+    extensions: Vec<Cow<'a, str>>,
+    show_hidden: bool,
+    directories_only: bool,
+    /// The position in `visible` of the highlighted entry.
+    highlighted: usize,
+    /// The first visible entry shown.
+    offset: usize,
+    selection: Option<PathBuf>,
+    /// The last error from reading a directory, shown in the header.
+    error: Option<String>,
+    focused: bool,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> FilePicker<'a> {
+    /// Creates a picker listing the directory `dir`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(FilePicker)` if the directory could be read.
+    /// - An error if it does not exist or cannot be read.
+    pub fn new<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let mut picker = Self {
+            dir: PathBuf::new(),
+            entries: Vec::new(),
+            visible: Vec::new(),
+            filter: String::new(),
+            extensions: Vec::new(),
+            show_hidden: false,
+            directories_only: false,
+            highlighted: 0,
+            offset: 0,
+            selection: None,
+            error: None,
+            focused: false,
+            theme: Theme::default(),
+            area: Rect::default(),
+        };
+        picker.set_dir(dir)?;
+        Ok(picker)
+    }
+
+    /// Lists only files with one of the given extensions (without the dot, compared
+    /// case-insensitively). Directories are always listed.
+    pub fn extensions<T: Into<Cow<'a, str>>, I: IntoIterator<Item = T>>(self, exts: I) -> Self {
+        let mut picker = self;
+        picker.extensions = exts.into_iter().map(Into::into).collect();
+        picker.update_visible();
+        picker
+    }
+
+    /// Sets whether entries whose names start with a dot are listed (they are hidden by
+    /// default).
+    pub fn show_hidden(self, show: bool) -> Self {
+        let mut picker = self;
+        picker.show_hidden = show;
+        picker.update_visible();
+        picker
+    }
+
+    /// Sets whether only directories are listed. Enter then chooses the highlighted directory
+    /// and Right opens it.
+    pub fn directories_only(self, only: bool) -> Self {
+        let mut picker = self;
+        picker.directories_only = only;
+        picker.update_visible();
+        picker
+    }
+
+    /// Sets the colors of the picker.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut picker = self;
+        picker.theme = theme;
+        picker
+    }
+
+    /// Returns the listed directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the text the names are filtered by.
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Returns the name of the highlighted entry (`..` for the parent directory).
+    pub fn highlighted(&self) -> Option<&str> {
+        self.current().map(|entry| entry.name.as_str())
+    }
+
+    /// Returns the full path of the highlighted entry.
+    pub fn highlighted_path(&self) -> Option<PathBuf> {
+        self.current().map(|entry| self.path_of(entry))
+    }
+
+    /// Returns the error of the last directory that could not be opened.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Lists the directory `dir`, clearing the filter.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the directory was read.
+    /// - An error if it could not be read; the picker keeps listing the previous directory.
+    pub fn set_dir<P: AsRef<Path>>(&mut self, dir: P) -> io::Result<()> {
+        let dir = dir.as_ref();
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            entries.push(Entry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                // Follows symbolic links, so links to directories can be opened.
+                is_dir: entry.path().is_dir(),
+            });
+        }
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+        if dir.parent().is_some() {
+            entries.insert(
+                0,
+                Entry {
+                    name: "..".to_string(),
+                    is_dir: true,
+                },
+            );
+        }
+
+        self.dir = dir;
+        self.entries = entries;
+        self.filter.clear();
+        self.error = None;
+        self.highlighted = 0;
+        self.offset = 0;
+        self.update_visible();
+        Ok(())
+    }
+
+    /// Goes up to the parent directory, highlighting the directory that was left.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the parent was read, or if there is no parent.
+    /// - An error if the parent could not be read.
+    pub fn parent(&mut self) -> io::Result<()> {
+        let Some(parent) = self.dir.parent().map(Path::to_path_buf) else {
+            return Ok(());
+        };
+        let left = self
+            .dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned());
+        self.set_dir(parent)?;
+        if let Some(left) = left {
+            if let Some(index) = self
+                .visible
+                .iter()
+                .position(|&i| self.entries[i].name == left)
+            {
+                self.highlighted = index;
+                self.scroll_to_highlight();
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the listed directory again, keeping the filter.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the directory was read.
+    /// - An error if it could not be read.
+    pub fn refresh(&mut self) -> io::Result<()> {
+        let filter = std::mem::take(&mut self.filter);
+        let highlighted = self.highlighted;
+        self.set_dir(self.dir.clone())?;
+        self.set_filter(filter);
+        self.highlighted = highlighted.min(self.visible.len().saturating_sub(1));
+        Ok(())
+    }
+
+    /// Lists only the entries whose names contain `text`, ignoring case, and highlights the
+    /// first of them.
+    pub fn set_filter<T: Into<String>>(&mut self, text: T) {
+        self.filter = text.into();
+        self.update_visible();
+        // Skip `..` while filtering, so Enter chooses the first match.
+        self.highlighted = match self.visible.first() {
+            Some(&first) if !self.filter.is_empty() && self.entries[first].name == ".." => 1,
+            _ => 0,
+        }
+        .min(self.visible.len().saturating_sub(1));
+        self.offset = 0;
+    }
+
+    /// Returns the chosen path since the last call, and forgets it.
+    pub fn take_selection(&mut self) -> Option<PathBuf> {
+        self.selection.take()
+    }
+
+    /// Returns the highlighted entry.
+    fn current(&self) -> Option<&Entry> {
+        self.visible
+            .get(self.highlighted)
+            .map(|&index| &self.entries[index])
+    }
+
+    /// Returns the full path of `entry`.
+    fn path_of(&self, entry: &Entry) -> PathBuf {
+        if entry.name == ".." {
+            self.dir.parent().unwrap_or(&self.dir).to_path_buf()
+        } else {
+            self.dir.join(&entry.name)
+        }
+    }
+
+    /// Recomputes which entries pass the filters.
+    fn update_visible(&mut self) {
+        let filter = self.filter.to_lowercase();
+        self.visible = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                let name = entry.name.as_str();
+                if name == ".." {
+                    return true;
+                }
+                if !self.show_hidden && name.starts_with('.') {
+                    return false;
+                }
+                if !entry.is_dir {
+                    if self.directories_only {
+                        return false;
+                    }
+                    let extension = Path::new(name).extension().map(|e| e.to_string_lossy());
+                    let allowed = self.extensions.is_empty()
+                        || extension.is_some_and(|extension| {
+                            self.extensions
+                                .iter()
+                                .any(|e| e.eq_ignore_ascii_case(&extension))
+                        });
+                    if !allowed {
+                        return false;
+                    }
+                }
+                name.to_lowercase().contains(&filter)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        self.highlighted = self.highlighted.min(self.visible.len().saturating_sub(1));
+    }
+
+    /// Returns how many entries fit below the header.
+    fn rows(&self) -> usize {
+        self.area.height.saturating_sub(1) as usize
+    }
+
+    /// Moves the highlight by `delta` entries, staying inside the list.
+    fn move_highlight(&mut self, delta: isize) {
+        let last = self.visible.len().saturating_sub(1);
+        self.highlighted = self.highlighted.saturating_add_signed(delta).min(last);
+        self.scroll_to_highlight();
+    }
+
+    /// Scrolls so the highlighted entry is visible.
+    fn scroll_to_highlight(&mut self) {
+        let rows = self.rows().max(1);
+        if self.highlighted < self.offset {
+            self.offset = self.highlighted;
+        } else if self.highlighted >= self.offset + rows {
+            self.offset = self.highlighted + 1 - rows;
+        }
+    }
+
+    /// Opens the directory at `path`, recording the error if that fails.
+    fn open(&mut self, path: PathBuf) {
+        if let Err(e) = self.set_dir(&path) {
+            self.error = Some(format!("{}: {e}", path.display()));
+        }
+    }
+
+    /// Opens the highlighted directory, or chooses the highlighted entry.
+    fn activate(&mut self) {
+        let Some(entry) = self.current().cloned() else {
+            return;
+        };
+        let path = self.path_of(&entry);
+        if entry.is_dir && !(self.directories_only && entry.name != "..") {
+            if entry.name == ".." {
+                if let Err(e) = self.parent() {
+                    self.error = Some(e.to_string());
+                }
+            } else {
+                self.open(path);
+            }
+        } else {
+            self.selection = Some(path);
+        }
+    }
+}
+
+impl<'a> Widget for FilePicker<'a> {
+    /// Wide enough for the longest name, tall enough for a header and ten entries.
+    fn size(&self) -> (u16, u16) {
+        let longest = self
+            .entries
+            .iter()
+            .map(|entry| entry.name.width() + 2)
+            .max()
+            .unwrap_or(0)
+            .max(24);
+        (longest.min(u16::MAX as usize) as u16, 11)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+        self.scroll_to_highlight();
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() {
+            return Ok(());
+        }
+        let width = self.area.width as usize;
+
+        // The header: the directory, or what went wrong, and the filter.
+        let (header, style) = match &self.error {
+            Some(error) => (error.clone(), self.theme.error),
+            None if !self.filter.is_empty() => (
+                format!("{} ({})", self.dir.display(), self.filter),
+                self.theme.accent,
+            ),
+            None => (self.dir.display().to_string(), self.theme.muted),
+        };
+        // Long paths keep their end, which tells more than their beginning.
+        let header_width = header.width();
+        let header = layout::clip_text(&header, header_width.saturating_sub(width), width);
+        Cursor::Move(self.area.x, self.area.y).queue(&mut out)?;
+        widget::write_styled(
+            out,
+            style,
+            &layout::align_text(&header, self.area.width, Align::Start),
+        )?;
+
+        let rows = self.rows();
+        let bar = Scrollbar::new(self.visible.len(), rows, self.offset);
+        let bar_cells: Vec<char> = bar.cells(rows as u16).chars().collect();
+        let text_width = if bar.is_needed() { width - 1 } else { width };
+        for (row, cell) in bar_cells.into_iter().enumerate() {
+            let index = self.offset + row;
+            let (name, style) = match self.visible.get(index).map(|&i| &self.entries[i]) {
+                Some(entry) if entry.is_dir && entry.name != ".." => {
+                    (format!("{}/", entry.name), self.theme.accent)
+                }
+                Some(entry) => (entry.name.clone(), self.theme.text),
+                None => (String::new(), NyanStyle::new()),
+            };
+            let style = match (index == self.highlighted, self.focused) {
+                (true, true) if index < self.visible.len() => self.theme.selection,
+                (true, false) if index < self.visible.len() => NyanStyle::new().reverse(),
+                _ => style,
+            };
+            let line = layout::clip_text(&name, 0, text_width);
+            Cursor::Move(self.area.x, self.area.y + 1 + row as u16).queue(&mut out)?;
+            widget::write_styled(
+                out,
+                style,
+                &layout::align_text(&line, text_width as u16, Align::Start),
+            )?;
+            if bar.is_needed() {
+                write!(out, "{cell}")?;
+            }
+        }
+        out.flush()
+    }
+
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        if !self.focused {
+            return false;
+        }
+        let page = self.rows().max(1) as isize;
+        match input {
+            NyanInput::UpAllow => self.move_highlight(-1),
+            NyanInput::DownAllow => self.move_highlight(1),
+            NyanInput::PageUp => self.move_highlight(-page),
+            NyanInput::PageDown => self.move_highlight(page),
+            NyanInput::Home => self.move_highlight(isize::MIN),
+            NyanInput::End => self.move_highlight(isize::MAX),
+            NyanInput::Enter => self.activate(),
+            NyanInput::RightAllow => {
+                if let Some(entry) = self.current().filter(|e| e.is_dir).cloned() {
+                    let path = self.path_of(&entry);
+                    self.open(path);
+                }
+            }
+            NyanInput::LeftAllow => {
+                if let Err(e) = self.parent() {
+                    self.error = Some(e.to_string());
+                }
+            }
+            NyanInput::BackSpace if !self.filter.is_empty() => {
+                let mut filter = std::mem::take(&mut self.filter);
+                filter.pop();
+                self.set_filter(filter);
+            }
+            NyanInput::BackSpace => {
+                if let Err(e) = self.parent() {
+                    self.error = Some(e.to_string());
+                }
+            }
+            NyanInput::Esc if !self.filter.is_empty() => self.set_filter(""),
+            _ => match input.to_char() {
+                Some(c) => {
+                    let mut filter = std::mem::take(&mut self.filter);
+                    filter.push(c);
+                    self.set_filter(filter);
+                }
+                None => return false,
+            },
+        }
+        true
+    }
+
+    /// A click highlights an entry; clicking the highlighted entry again opens or chooses it.
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        if !self.area.contains(mouse.x, mouse.y) {
+            return false;
+        }
+        match mouse.kind {
+            NyanMouseKind::Down(NyanMouseButton::Left) if mouse.y > self.area.y => {
+                let index = self.offset + (mouse.y - self.area.y - 1) as usize;
+                if index >= self.visible.len() {
+                    return true;
+                }
+                if index == self.highlighted {
+                    self.activate();
+                } else {
+                    self.highlighted = index;
+                }
+            }
+            NyanMouseKind::ScrollUp => self.move_highlight(-1),
+            NyanMouseKind::ScrollDown => self.move_highlight(1),
+            _ => {}
+        }
+        true
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
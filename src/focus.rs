@@ -0,0 +1,301 @@
+//! This module provides the `FocusManager`, which decides which widget or object receives the keyboard.
+//!
+//! A focus manager keeps an ordered list of ids: the focus order. Tab moves the focus to the next id, Shift+Tab to the previous one (and, optionally, the arrow keys do as well), wrapping around at the ends. The widgets themselves stay owned by the application and are passed to `handle_event()` together with their ids whenever an event arrives; the manager gives each widget its focus state, sends keys and pasted text to the focused one only, and focuses a widget when it is clicked. Objects of a `NyanObj` can take part in the focus order too: `draw_focused()` redraws the focused object with a highlight style.
+//!
+//! # Structs
+//!
+//! - `FocusManager`: The focus order, the focused entry and how the focus is moved.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates an empty focus order.
+//! - `item(id)` / `push(id)` / `remove(id)`: Change the focus order.
+//! - `arrow_keys(on)` / `wrap(on)` / `focus_style(style)`: Configure the traversal and the highlight of focused objects.
+//! - `focused()` / `is_focused(id)`: Query the focus.
+//! - `focus(id)` / `focus_next()` / `focus_prev()` / `blur()`: Move the focus from code.
+//! - `handle_input(input)`: Moves the focus for Tab, Shift+Tab and, optionally, the arrow keys.
+//! - `sync(widgets)`: Tells every widget whether it is focused.
+//! - `handle_event(widgets, event)`: Routes an event to the widgets and moves the focus.
+//! - `draw_focused(objects, out)`: Draws the focused object with the focus style.
+
+use std::borrow::Cow;
+use std::io::Write;
+
+use crate::errors::NyanResult;
+use crate::event::{NyanEvent, NyanMouseButton, NyanMouseKind};
+use crate::input::NyanInput;
+use crate::nyan_obj::NyanObj;
+use crate::style::NyanStyle;
+use crate::widget::Widget;
+
+/// `FocusManager` moves the keyboard focus between widgets and objects.
+///
+/// # Example
+/// ```rust
+/// use nyan::button::Button;
+/// use nyan::event::NyanEvent;
+/// use nyan::focus::FocusManager;
+/// use nyan::input::NyanInput;
+/// use nyan::text_input::TextInput;
+/// use nyan::widget::Widget;
+///
+/// let mut name = TextInput::new();
+/// let mut ok = Button::new("OK");
+/// let mut focus = FocusManager::new().item("name").item("ok");
+///
+/// let mut route = |focus: &mut FocusManager, event: NyanEvent| {
+///     let mut widgets: [(&str, &mut dyn Widget); 2] = [("name", &mut name), ("ok", &mut ok)];
+///     focus.handle_event(&mut widgets, &event)
+/// };
+/// route(&mut focus, NyanEvent::Paste("nyan".into())); // goes to "name", focused first
+/// route(&mut focus, NyanEvent::Key(NyanInput::Tab));
+/// route(&mut focus, NyanEvent::Key(NyanInput::Enter)); // presses "OK"
+///
+/// assert_eq!(focus.focused(), Some("ok"));
+/// assert_eq!(name.text(), "nyan");
+/// assert!(ok.take_activation());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FocusManager<'a> {
+    order: Vec<Cow<'a, str>>,
+    focused: Option<usize>,
+    arrow_keys: bool,
+    wrap: bool,
+    focus_style: NyanStyle,
+}
+
+impl<'a> Default for FocusManager<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> FocusManager<'a> {
+    /// Creates a focus manager with an empty focus order.
+    pub fn new() -> Self {
+        Self {
+            order: Vec::new(),
+            focused: None,
+            arrow_keys: false,
+            wrap: true,
+            focus_style: NyanStyle::new().reverse(),
+        }
+    }
+
+    /// Appends `id` to the focus order. The first id is focused.
+    pub fn item<P: Into<Cow<'a, str>>>(self, id: P) -> Self {
+        let mut focus = self;
+        focus.push(id);
+        focus
+    }
+
+    /// Sets whether the arrow keys move the focus too (off by default). They only do when the
+    /// focused widget does not use them itself.
+    pub fn arrow_keys(self, on: bool) -> Self {
+        let mut focus = self;
+        focus.arrow_keys = on;
+        focus
+    }
+
+    /// Sets whether the focus wraps around from the last entry to the first and back (on by
+    /// default).
+    pub fn wrap(self, on: bool) -> Self {
+        let mut focus = self;
+        focus.wrap = on;
+        focus
+    }
+
+    /// Sets the style layered on the focused object by [`FocusManager::draw_focused`]
+    /// (reversed by default).
+    pub fn focus_style(self, style: NyanStyle) -> Self {
+        let mut focus = self;
+        focus.focus_style = style;
+        focus
+    }
+
+    /// Appends `id` to the focus order, focusing it if nothing is focused yet.
+    pub fn push<P: Into<Cow<'a, str>>>(&mut self, id: P) {
+        self.order.push(id.into());
+        if self.focused.is_none() {
+            self.focused = Some(0);
+        }
+    }
+
+    /// Removes `id` from the focus order. If it was focused, the next entry gets the focus.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `id` was in the focus order.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let Some(index) = self.position(id) else {
+            return false;
+        };
+        self.order.remove(index);
+        self.focused = match self.focused {
+            _ if self.order.is_empty() => None,
+            Some(focused) if focused > index => Some(focused - 1),
+            Some(focused) => Some(focused.min(self.order.len() - 1)),
+            None => None,
+        };
+        true
+    }
+
+    /// Returns the id of the focused entry.
+    pub fn focused(&self) -> Option<&str> {
+        self.focused.map(|index| self.order[index].as_ref())
+    }
+
+    /// Returns whether `id` has the focus.
+    pub fn is_focused(&self, id: &str) -> bool {
+        self.focused() == Some(id)
+    }
+
+    /// Gives the focus to `id`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `id` is in the focus order.
+    pub fn focus(&mut self, id: &str) -> bool {
+        match self.position(id) {
+            Some(index) => {
+                self.focused = Some(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the focus to the next entry.
+    pub fn focus_next(&mut self) {
+        self.step(true);
+    }
+
+    /// Moves the focus to the previous entry.
+    pub fn focus_prev(&mut self) {
+        self.step(false);
+    }
+
+    /// Takes the focus away from every entry. The next Tab focuses the first one again.
+    pub fn blur(&mut self) {
+        self.focused = None;
+    }
+
+    /// Moves the focus for Tab and Shift+Tab and, if enabled, the arrow keys (Down and Right
+    /// move forward, Up and Left backward).
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key is one that moves the focus.
+    pub fn handle_input(&mut self, input: &NyanInput) -> bool {
+        let forward = match input {
+            NyanInput::Tab => true,
+            NyanInput::Shift(NyanInput::Tab) => false,
+            NyanInput::DownAllow | NyanInput::RightAllow if self.arrow_keys => true,
+            NyanInput::UpAllow | NyanInput::LeftAllow if self.arrow_keys => false,
+            _ => return false,
+        };
+        self.step(forward);
+        true
+    }
+
+    /// Tells every widget in `widgets` whether its id is the focused one.
+    pub fn sync(&self, widgets: &mut [(&str, &mut dyn Widget)]) {
+        for (id, widget) in widgets.iter_mut() {
+            widget.set_focused(self.is_focused(id));
+        }
+    }
+
+    /// Routes `event` to `widgets`, each given with its id, and moves the focus.
+    ///
+    /// Keys go to the focused widget first; a key it does not use moves the focus if it is one
+    /// of the traversal keys (see [`FocusManager::handle_input`]). Pasted text goes to the
+    /// focused widget. Mouse actions go to every widget, and pressing a mouse button on a
+    /// focusable widget in the focus order focuses it. Afterwards, every widget is told whether
+    /// it is focused.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a widget used the event or the focus moved.
+    pub fn handle_event<U>(
+        &mut self,
+        widgets: &mut [(&str, &mut dyn Widget)],
+        event: &NyanEvent<U>,
+    ) -> bool {
+        // Widgets only handle keys while they know they are focused.
+        self.sync(widgets);
+        let focused = self.focused().map(str::to_string);
+        let mut target = widgets
+            .iter_mut()
+            .find(|(id, _)| Some(*id) == focused.as_deref())
+            .map(|(_, widget)| widget);
+
+        let used = match event {
+            NyanEvent::Key(input) => {
+                let used = target.as_mut().is_some_and(|w| w.handle_input(input));
+                used || self.handle_input(input)
+            }
+            NyanEvent::Paste(text) => target.is_some_and(|w| w.handle_paste(text)),
+            NyanEvent::Mouse(mouse) => {
+                if let NyanMouseKind::Down(NyanMouseButton::Left) = mouse.kind {
+                    let clicked = widgets.iter().find(|(id, widget)| {
+                        widget.is_focusable()
+                            && widget.area().contains(mouse.x, mouse.y)
+                            && self.position(id).is_some()
+                    });
+                    if let Some((id, _)) = clicked {
+                        let id = id.to_string();
+                        self.focus(&id);
+                        self.sync(widgets);
+                    }
+                }
+                let mut used = false;
+                for (_, widget) in widgets.iter_mut() {
+                    used |= widget.handle_mouse(mouse);
+                }
+                used
+            }
+            _ => false,
+        };
+        self.sync(widgets);
+        used
+    }
+
+    /// Draws the focused object of `objects` again with the focus style layered on its own
+    /// style. Call it after drawing the scene; it does nothing if the focused entry is not an
+    /// object.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the object was drawn, or if there is nothing to draw.
+    /// - An error if drawing fails.
+    pub fn draw_focused<W: Write>(&self, objects: &NyanObj, out: &mut W) -> NyanResult<()> {
+        match self.focused() {
+            Some(id) if objects.object(id).is_some() => {
+                objects.draw_overlaid_in(out, id, self.focus_style)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the position of `id` in the focus order.
+    fn position(&self, id: &str) -> Option<usize> {
+        self.order.iter().position(|entry| entry == id)
+    }
+
+    /// Moves the focus one entry forward or backward.
+    fn step(&mut self, forward: bool) {
+        let len = self.order.len();
+        if len == 0 {
+            return;
+        }
+        self.focused = Some(match (self.focused, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(index), true) if index + 1 < len => index + 1,
+            (Some(index), false) if index > 0 => index - 1,
+            (Some(index), _) if !self.wrap => index,
+            (Some(_), true) => 0,
+            (Some(_), false) => len - 1,
+        });
+    }
+}
@@ -0,0 +1,557 @@
+//! This module provides the `Form` container, which ties labels, input widgets and validation together.
+//!
+//! A form is a column of named fields, each a label with a `TextInput` or a `Select` next to it, followed by a submit button. It moves the focus between its fields (Tab, Shift+Tab, the arrow keys when the focused field does not use them, and Enter in a text field), runs the validators of every field when it is submitted and shows their messages below the fields. Once all fields are valid, `take_submission()` returns the values of all fields by name.
+//!
+//! # Structs
+//!
+//! - `Form`: The fields, the submit button, the errors and the focus.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates an empty form.
+//! - `text(name, label, input)` / `select(name, label, select)`: Add a field.
+//! - `validate_with(name, func)`: Adds a validator to a field.
+//! - `submit_label(label)` / `theme(theme)`: Configure the submit button and the colors.
+//! - `value(name)` / `values()`: Return the current values.
+//! - `text_field(name)` / `select_field(name)`: Give access to the widget of a field.
+//! - `validate()` / `errors()` / `error(name)`: Run the validators and query their messages.
+//! - `focused()` / `focus(name)`: Query and move the focus.
+//! - `submit()` / `take_submission()`: Submit the form and fetch the submitted values.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::button::Button;
+use crate::cursor::Cursor;
+use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::focus::FocusManager;
+use crate::input::NyanInput;
+use crate::layout;
+use crate::rect::Rect;
+use crate::select::Select;
+use crate::text_input::TextInput;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// The widget of a form field.
+#[derive(Debug)]
+enum Input<'a> {
+    Text(TextInput<'a>),
+    Select(Select<'a>),
+}
+
+impl<'a> Input<'a> {
+    fn widget(&self) -> &dyn Widget {
+        match self {
+            Input::Text(input) => input,
+            Input::Select(select) => select,
+        }
+    }
+
+    fn widget_mut(&mut self) -> &mut dyn Widget {
+        match self {
+            Input::Text(input) => input,
+            Input::Select(select) => select,
+        }
+    }
+
+    fn value(&self) -> String {
+        match self {
+            Input::Text(input) => input.text().to_string(),
+            Input::Select(select) => select.value().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+/// A validator of a form field.
+type Validator<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
+
+/// A labelled field of a form.
+struct Field<'a> {
+    name: Cow<'a, str>,
+    label: Cow<'a, str>,
+    input: Input<'a>,
+    validators: Vec<Validator<'a>>,
+    error: Option<String>,
+}
+
+/// `Form` lays out labelled fields with a submit button and validates them on submission.
+///
+/// # Example
+/// ```rust
+/// use nyan::form::Form;
+/// use nyan::input::NyanInput;
+/// use nyan::select::Select;
+/// use nyan::text_input::TextInput;
+/// use nyan::widget::Widget;
+///
+/// let mut form = Form::new()
+///     .text("name", "Name", TextInput::new().borderless())
+///     .validate_with("name", |name| {
+///         if name.is_empty() {
+///             Err("required".to_string())
+///         } else {
+///             Ok(())
+///         }
+///     })
+///     .select("size", "Size", Select::new(["S", "M", "L"]));
+///
+/// form.submit();
+/// assert_eq!(form.take_submission(), None);
+/// assert_eq!(form.error("name"), Some("required"));
+///
+/// form.handle_paste("nyan");
+/// form.handle_input(&NyanInput::Enter); // to "size"
+/// form.handle_input(&NyanInput::Tab); // to the submit button
+/// form.handle_input(&NyanInput::Enter);
+///
+/// let values = form.take_submission().unwrap();
+/// assert_eq!(values["name"], "nyan");
+/// assert_eq!(values["size"], "");
+/// ```
+pub struct Form<'a> {
+    fields: Vec<Field<'a>>,
+    submit: Button<'a>,
+    /// The focus order: the fields by index, then the submit button.
+    focus: FocusManager<'static>,
+    submission: Option<BTreeMap<String, String>>,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> Debug for Form<'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields: Vec<_> = self
+            .fields
+            .iter()
+            .map(|f| (&f.name, &f.input, &f.error))
+            .collect();
+        fmt.debug_struct("Form")
+            .field("fields", &fields)
+            .field("submit", &self.submit)
+            .field("focus", &self.focus)
+            .field("submission", &self.submission)
+            .field("area", &self.area)
+            .finish()
+    }
+}
+
+impl<'a> Default for Form<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Form<'a> {
+    /// Creates a form with no fields and a "Submit" button.
+    pub fn new() -> Self {
+        let mut form = Self {
+            fields: Vec::new(),
+            submit: Button::new("Submit"),
+            focus: FocusManager::new().item("0"),
+            submission: None,
+            theme: Theme::default(),
+            area: Rect::default(),
+        };
+        form.sync_focus();
+        form
+    }
+
+    /// Adds a text field called `name`, shown with `label`.
+    pub fn text<N, L>(self, name: N, label: L, input: TextInput<'a>) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        L: Into<Cow<'a, str>>,
+    {
+        self.field(name.into(), label.into(), Input::Text(input))
+    }
+
+    /// Adds a dropdown field called `name`, shown with `label`. Its value is the chosen option,
+    /// or an empty string.
+    pub fn select<N, L>(self, name: N, label: L, select: Select<'a>) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        L: Into<Cow<'a, str>>,
+    {
+        self.field(name.into(), label.into(), Input::Select(select))
+    }
+
+    /// Adds a validator to the field `name`, run with its value when the form is validated.
+    /// An `Err` holds the message shown below the field. Unknown names are ignored.
+    pub fn validate_with<F: Fn(&str) -> Result<(), String> + 'a>(
+        self,
+        name: &str,
+        func: F,
+    ) -> Self {
+        let mut form = self;
+        if let Some(field) = form.fields.iter_mut().find(|f| f.name == name) {
+            field.validators.push(Box::new(func));
+        }
+        form
+    }
+
+    /// Sets the label of the submit button.
+    pub fn submit_label<T: Into<Cow<'a, str>>>(self, label: T) -> Self {
+        let mut form = self;
+        form.submit = Button::new(label).theme(form.theme);
+        form.sync_focus();
+        form.place();
+        form
+    }
+
+    /// Sets the colors of the form and its widgets.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut form = self;
+        form.theme = theme;
+        form.submit = std::mem::replace(&mut form.submit, Button::new("")).theme(theme);
+        for field in &mut form.fields {
+            field.input = match std::mem::replace(&mut field.input, Input::Text(TextInput::new())) {
+                Input::Text(input) => Input::Text(input.theme(theme)),
+                Input::Select(select) => Input::Select(select.theme(theme)),
+            };
+        }
+        form
+    }
+
+    /// Returns the value of the field `name`.
+    pub fn value(&self, name: &str) -> Option<String> {
+        self.find(name).map(|field| field.input.value())
+    }
+
+    /// Returns the values of all fields by name.
+    pub fn values(&self) -> BTreeMap<String, String> {
+        self.fields
+            .iter()
+            .map(|field| (field.name.to_string(), field.input.value()))
+            .collect()
+    }
+
+    /// Returns the text input of the field `name`, e.g. to set its text.
+    pub fn text_field(&mut self, name: &str) -> Option<&mut TextInput<'a>> {
+        match self.fields.iter_mut().find(|f| f.name == name) {
+            Some(Field {
+                input: Input::Text(input),
+                ..
+            }) => Some(input),
+            _ => None,
+        }
+    }
+
+    /// Returns the dropdown of the field `name`.
+    pub fn select_field(&mut self, name: &str) -> Option<&mut Select<'a>> {
+        match self.fields.iter_mut().find(|f| f.name == name) {
+            Some(Field {
+                input: Input::Select(select),
+                ..
+            }) => Some(select),
+            _ => None,
+        }
+    }
+
+    /// Runs the validators of every field, including those of the text inputs themselves, and
+    /// keeps the first message of each field.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every field is valid.
+    pub fn validate(&mut self) -> bool {
+        for field in &mut self.fields {
+            let value = field.input.value();
+            let own = match &field.input {
+                Input::Text(input) => input.error().map(str::to_string),
+                Input::Select(_) => None,
+            };
+            field.error = own.or_else(|| {
+                field
+                    .validators
+                    .iter()
+                    .find_map(|validate| validate(&value).err())
+            });
+        }
+        self.fields.iter().all(|field| field.error.is_none())
+    }
+
+    /// Returns the messages of the invalid fields by name, as of the last validation.
+    pub fn errors(&self) -> BTreeMap<String, String> {
+        self.fields
+            .iter()
+            .filter_map(|f| Some((f.name.to_string(), f.error.clone()?)))
+            .collect()
+    }
+
+    /// Returns the message of the field `name`, as of the last validation.
+    pub fn error(&self, name: &str) -> Option<&str> {
+        self.find(name).and_then(|field| field.error.as_deref())
+    }
+
+    /// Returns the name of the focused field, or `None` while the submit button is focused.
+    pub fn focused(&self) -> Option<&str> {
+        self.focused_index()
+            .and_then(|index| self.fields.get(index))
+            .map(|field| field.name.as_ref())
+    }
+
+    /// Focuses the field `name`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the form has such a field.
+    pub fn focus(&mut self, name: &str) -> bool {
+        match self.fields.iter().position(|f| f.name == name) {
+            Some(index) => {
+                self.focus.focus(&index.to_string());
+                self.sync_focus();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Validates the form and, if every field is valid, records the values as submitted.
+    /// Otherwise the first invalid field is focused.
+    pub fn submit(&mut self) {
+        if self.validate() {
+            self.submission = Some(self.values());
+        } else if let Some(index) = self.fields.iter().position(|f| f.error.is_some()) {
+            self.focus.focus(&index.to_string());
+            self.sync_focus();
+        }
+    }
+
+    /// Returns the values submitted since the last call, and forgets them.
+    pub fn take_submission(&mut self) -> Option<BTreeMap<String, String>> {
+        self.submission.take()
+    }
+
+    /// Adds a field and its entry in the focus order.
+    fn field(self, name: Cow<'a, str>, label: Cow<'a, str>, input: Input<'a>) -> Self {
+        let mut form = self;
+        form.fields.push(Field {
+            name,
+            label,
+            input,
+            validators: Vec::new(),
+            error: None,
+        });
+        // The submit button always comes last.
+        form.focus.push(form.fields.len().to_string());
+        form.sync_focus();
+        form.place();
+        form
+    }
+
+    /// Returns the field `name`.
+    fn find(&self, name: &str) -> Option<&Field<'a>> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Returns the index of the focused field; the submit button has the index after the last
+    /// field.
+    fn focused_index(&self) -> Option<usize> {
+        self.focus.focused().and_then(|id| id.parse().ok())
+    }
+
+    /// Tells every widget whether it is focused.
+    fn sync_focus(&mut self) {
+        let focused = self.focused_index();
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            field.input.widget_mut().set_focused(focused == Some(index));
+        }
+        self.submit.set_focused(focused == Some(self.fields.len()));
+    }
+
+    /// Returns the width of the label column.
+    fn label_width(&self) -> u16 {
+        let widest = self
+            .fields
+            .iter()
+            .map(|f| f.label.width())
+            .max()
+            .unwrap_or(0);
+        (widest + 1).min(u16::MAX as usize) as u16
+    }
+
+    /// Places the widgets: each field right of its label with a row for its message below it,
+    /// then the submit button.
+    fn place(&mut self) {
+        let area = self.area;
+        let label_width = self.label_width();
+        let field_width = area.width.saturating_sub(label_width);
+        let mut y = area.y;
+        for field in &mut self.fields {
+            let (width, height) = field.input.widget().size();
+            let rect = Rect::new(area.x + label_width, y, width.min(field_width), height);
+            field.input.widget_mut().set_area(rect);
+            y = y.saturating_add(height + 1);
+        }
+        let (width, _) = self.submit.size();
+        self.submit.set_area(Rect::new(
+            area.x + label_width,
+            y,
+            width.min(field_width),
+            1,
+        ));
+    }
+}
+
+impl<'a> Widget for Form<'a> {
+    /// The labels next to the widest field, every field with a row for its message, and the
+    /// submit button.
+    fn size(&self) -> (u16, u16) {
+        let sizes = self.fields.iter().map(|f| f.input.widget().size());
+        let width = sizes
+            .clone()
+            .map(|(w, _)| w)
+            .chain([self.submit.size().0])
+            .max()
+            .unwrap_or(0);
+        let height = sizes.map(|(_, h)| h + 1).sum::<u16>() + 1;
+        (self.label_width().saturating_add(width), height)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+        self.place();
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() {
+            return Ok(());
+        }
+        let label_width = self.label_width() as usize;
+        let focused = self.focused_index();
+        for (index, field) in self.fields.iter().enumerate() {
+            let rect = field.input.widget().area();
+            let style = if focused == Some(index) {
+                self.theme.accent
+            } else {
+                self.theme.text
+            };
+            // The label sits on the middle row of boxed inputs.
+            Cursor::Move(self.area.x, rect.y + rect.height / 2).queue(&mut out)?;
+            widget::write_styled(out, style, &layout::clip_text(&field.label, 0, label_width))?;
+            if let Some(error) = &field.error {
+                // Messages may be wider than their field.
+                let width = self.area.right().saturating_sub(rect.x);
+                let error = layout::clip_text(error, 0, width as usize);
+                Cursor::Move(rect.x, rect.bottom()).queue(&mut out)?;
+                widget::write_styled(out, self.theme.error, &error)?;
+            }
+        }
+        for (index, field) in self.fields.iter().enumerate() {
+            if focused != Some(index) {
+                field.input.widget().draw(out)?;
+            }
+        }
+        self.submit.draw(out)?;
+        // The focused field comes last, so an open dropdown covers the fields below it.
+        if let Some(field) = focused.and_then(|index| self.fields.get(index)) {
+            field.input.widget().draw(out)?;
+        }
+        out.flush()
+    }
+
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        let Some(index) = self.focused_index() else {
+            let moved = self.focus.handle_input(input);
+            self.sync_focus();
+            return moved;
+        };
+        let used = match self.fields.get_mut(index) {
+            Some(field) => {
+                let used = field.input.widget_mut().handle_input(input);
+                if !used && *input == NyanInput::Enter && matches!(field.input, Input::Text(_)) {
+                    self.focus.focus_next();
+                    self.sync_focus();
+                    return true;
+                }
+                used
+            }
+            None => {
+                let used = self.submit.handle_input(input);
+                if self.submit.take_activation() {
+                    self.submit();
+                }
+                used
+            }
+        };
+        if used {
+            return true;
+        }
+        let moved = match input {
+            NyanInput::UpAllow => {
+                self.focus.focus_prev();
+                true
+            }
+            NyanInput::DownAllow => {
+                self.focus.focus_next();
+                true
+            }
+            _ => self.focus.handle_input(input),
+        };
+        self.sync_focus();
+        moved
+    }
+
+    /// Clicking a field focuses it; clicking the submit button submits the form.
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        if let NyanMouseKind::Down(NyanMouseButton::Left) = mouse.kind {
+            let clicked = self
+                .fields
+                .iter()
+                .position(|f| f.input.widget().area().contains(mouse.x, mouse.y));
+            let clicked = clicked.or_else(|| {
+                let button = self.submit.area().contains(mouse.x, mouse.y);
+                button.then_some(self.fields.len())
+            });
+            if let Some(index) = clicked {
+                self.focus.focus(&index.to_string());
+                self.sync_focus();
+            }
+        }
+        let mut used = false;
+        for field in &mut self.fields {
+            used |= field.input.widget_mut().handle_mouse(mouse);
+        }
+        used |= self.submit.handle_mouse(mouse);
+        if self.submit.take_activation() {
+            self.submit();
+        }
+        used
+    }
+
+    fn handle_paste(&mut self, text: &str) -> bool {
+        let index = self.focused_index();
+        match index.and_then(|index| self.fields.get_mut(index)) {
+            Some(field) => field.input.widget_mut().handle_paste(text),
+            None => false,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused_index().is_some()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        if focused {
+            if self.focus.focused().is_none() {
+                self.focus.focus_next();
+            }
+        } else {
+            self.focus.blur();
+        }
+        self.sync_focus();
+    }
+}
@@ -0,0 +1,481 @@
+//! This module provides `Gif`, a widget that decodes an animated GIF and plays its frames in the terminal — perfect for an actual nyan cat.
+//!
+//! Each terminal cell shows two pixels stacked on top of each other, with the half block `▀` in the color of the upper pixel on the background color of the lower one. Transparent pixels leave the terminal's background visible. Images larger than the widget's area are scaled down to fit it.
+//!
+//! A GIF is advanced by the time that passed, usually [`App::delta_time`](crate::app::App::delta_time), and shows each frame for the delay stored in the file. Frames with a delay shorter than 20 milliseconds are shown for 100 milliseconds, as browsers do. The animation loops forever. Images larger than 2048 × 2048 pixels are refused, so a file cannot make decoding take more memory than a terminal image needs.
+//!
+//! # Structs
+//!
+//! - `Gif`: The decoded frames, their delays and the playback state.
+//!
+//! # Methods
+//!
+//! - `Gif::decode(bytes)` / `Gif::load(path)`: Decode a GIF from its bytes or a file.
+//! - `advance(dt)`: Advances the playback by the time that passed.
+//! - `play()` / `pause()` / `is_playing()`: Control the playback.
+//! - `current()` / `set_frame(index)` / `len()` / `delay(index)`: Query and set the shown frame.
+//! - `dimensions()` / `pixel(x, y)`: Return the size of the image and the color of a pixel of the shown frame.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::color::NyanColor;
+use crate::cursor::Cursor;
+use crate::errors::{NyanError, NyanResult};
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::widget::{self, Widget};
+
+/// An RGB pixel; `None` is transparent.
+type Pixel = Option<(u8, u8, u8)>;
+
+/// The most pixels of a GIF that is decoded, 2048 × 2048. A terminal shows far fewer, and
+/// the size in the header of a file would otherwise decide how much memory decoding takes.
+const MAX_PIXELS: usize = 2048 * 2048;
+
+/// The most pixels of all frames together; later frames are dropped, as for a file cut short.
+const MAX_DECODED: usize = 16 * MAX_PIXELS;
+
+/// `Gif` plays the frames of an animated GIF.
+///
+/// # Example
+/// ```ignore
+/// let mut cat = Gif::load("nyan.gif")?;
+/// cat.set_area(Rect::new(0, 0, 40, 12));
+/// loop {
+///     cat.advance(app.delta_time());
+///     app.draw_with(|app| cat.draw(app).unwrap())?;
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gif {
+    width: u16,
+    height: u16,
+    frames: Vec<(Vec<Pixel>, Duration)>,
+    current: usize,
+    /// How long the current frame has been shown.
+    elapsed: Duration,
+    playing: bool,
+    area: Rect,
+}
+
+impl Gif {
+    /// Decodes a GIF from the bytes of a `.gif` file.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Gif)` with every frame of the image, playing from the first one.
+    /// - An error if the bytes are not a valid GIF, or it has no frames.
+    pub fn decode(bytes: &[u8]) -> NyanResult<Self> {
+        if let [b'G', b'I', b'F', _, _, _, w0, w1, h0, h1, ..] = *bytes {
+            let (width, height) = (u16::from_le_bytes([w0, w1]), u16::from_le_bytes([h0, h1]));
+            if width as usize * height as usize > MAX_PIXELS {
+                return Err(NyanError::InvalidImage(
+                    format!("the GIF is too large ({width} × {height} pixels)").into(),
+                ));
+            }
+        }
+        let (width, height, frames) = Decoder { bytes, pos: 0 }
+            .decode()
+            .ok_or(NyanError::InvalidImage(Cow::Borrowed("not a valid GIF")))?;
+        if frames.is_empty() {
+            return Err(NyanError::InvalidImage(Cow::Borrowed(
+                "the GIF has no frames",
+            )));
+        }
+        Ok(Self {
+            width,
+            height,
+            frames,
+            current: 0,
+            elapsed: Duration::ZERO,
+            playing: true,
+            area: Rect::new(0, 0, width, height.saturating_add(1) / 2),
+        })
+    }
+
+    /// Decodes a GIF from a file.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Gif)` with every frame of the image.
+    /// - An error if the file cannot be read or is not a valid GIF.
+    pub fn load<P: AsRef<Path>>(path: P) -> NyanResult<Self> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    /// Advances the playback by `dt`, moving on by as many frames as their delays fit in.
+    ///
+    /// # Returns
+    ///
+    /// `true` if another frame is shown now, so the GIF needs to be drawn again.
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        if !self.playing || self.frames.len() < 2 {
+            return false;
+        }
+        let before = self.current;
+        self.elapsed += dt;
+        // Skip whole loops at once after a long pause.
+        let total: Duration = self.frames.iter().map(|(_, delay)| *delay).sum();
+        if self.elapsed > total {
+            self.elapsed =
+                Duration::from_nanos((self.elapsed.as_nanos() % total.as_nanos()) as u64);
+        }
+        while self.elapsed >= self.frames[self.current].1 {
+            self.elapsed -= self.frames[self.current].1;
+            self.current = (self.current + 1) % self.frames.len();
+        }
+        self.current != before
+    }
+
+    /// Resumes the playback.
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    /// Pauses the playback at the shown frame.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Returns whether the GIF is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Returns the index of the shown frame.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Shows the frame at `index` from its start. Indices past the last frame are ignored.
+    pub fn set_frame(&mut self, index: usize) {
+        if index < self.frames.len() {
+            self.current = index;
+            self.elapsed = Duration::ZERO;
+        }
+    }
+
+    /// Returns the number of frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns whether the GIF has no frames, which a decoded GIF never has.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns how long the frame at `index` is shown.
+    pub fn delay(&self, index: usize) -> Option<Duration> {
+        self.frames.get(index).map(|(_, delay)| *delay)
+    }
+
+    /// Returns the `(width, height)` of the image in pixels.
+    pub fn dimensions(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Returns the color of the pixel at `(x, y)` in the shown frame, or `None` if it is
+    /// transparent or outside of the image.
+    pub fn pixel(&self, x: u16, y: u16) -> Option<(u8, u8, u8)> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.frames[self.current].0[y as usize * self.width as usize + x as usize]
+    }
+}
+
+impl Widget for Gif {
+    /// One cell per pixel across, one cell per two pixels down.
+    fn size(&self) -> (u16, u16) {
+        (self.width, self.height.saturating_add(1) / 2)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() || self.width == 0 || self.height == 0 {
+            return Ok(());
+        }
+        // Scale down, never up, keeping the aspect ratio.
+        let scale = f64::min(
+            1.0,
+            f64::min(
+                self.area.width as f64 / self.width as f64,
+                self.area.height as f64 * 2.0 / self.height as f64,
+            ),
+        );
+        let columns = ((self.width as f64 * scale).round() as u16).clamp(1, self.area.width);
+        let rows = ((self.height as f64 * scale).round() as u16)
+            .clamp(1, self.area.height.saturating_mul(2));
+        let sample = |column: u16, row: u16| -> Pixel {
+            if row >= rows {
+                return None;
+            }
+            let x = (column as u32 * self.width as u32 / columns as u32) as u16;
+            let y = (row as u32 * self.height as u32 / rows as u32) as u16;
+            self.pixel(x, y)
+        };
+
+        let rgb = |(r, g, b)| NyanColor::Rgb(r, g, b);
+        for line in 0..rows.saturating_add(1) / 2 {
+            Cursor::Move(self.area.x, self.area.y + line).queue(&mut out)?;
+            for column in 0..columns {
+                let (top, bottom) = (sample(column, line * 2), sample(column, line * 2 + 1));
+                let (glyph, style) = match (top, bottom) {
+                    (None, None) => (" ", NyanStyle::new()),
+                    (Some(top), None) => ("▀", NyanStyle::new().fg(rgb(top))),
+                    (None, Some(bottom)) => ("▄", NyanStyle::new().fg(rgb(bottom))),
+                    (Some(top), Some(bottom)) => {
+                        ("▀", NyanStyle::new().fg(rgb(top)).bg(rgb(bottom)))
+                    }
+                };
+                widget::write_styled(out, style, glyph)?;
+            }
+        }
+        out.flush()
+    }
+}
+
+/// Reads the frames of a GIF, composed onto its canvas.
+struct Decoder<'b> {
+    bytes: &'b [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    /// Returns the next `len` bytes.
+    fn take(&mut self, len: usize) -> Option<&[u8]> {
+        let bytes = self.bytes.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    /// Returns the next byte.
+    fn byte(&mut self) -> Option<u8> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    /// Returns the next little-endian `u16`.
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Returns a color table of `2^(bits + 1)` colors.
+    fn color_table(&mut self, bits: u8) -> Option<Vec<(u8, u8, u8)>> {
+        let colors = 2usize << bits;
+        let table = self.take(colors * 3)?;
+        Some(
+            table
+                .chunks(3)
+                .map(|rgb| (rgb[0], rgb[1], rgb[2]))
+                .collect(),
+        )
+    }
+
+    /// Returns the data of the sub-blocks that follow, up to the empty block that ends them.
+    fn sub_blocks(&mut self) -> Option<Vec<u8>> {
+        let mut data = Vec::new();
+        loop {
+            let len = self.byte()? as usize;
+            if len == 0 {
+                return Some(data);
+            }
+            data.extend_from_slice(self.take(len)?);
+        }
+    }
+
+    /// Returns the width, the height and the frames of the GIF.
+    #[allow(clippy::type_complexity)]
+    fn decode(mut self) -> Option<(u16, u16, Vec<(Vec<Pixel>, Duration)>)> {
+        if !matches!(self.take(6)?, b"GIF87a" | b"GIF89a") {
+            return None;
+        }
+        let (width, height) = (self.u16()?, self.u16()?);
+        let packed = self.byte()?;
+        self.take(2)?; // background color and aspect ratio
+        let global = if packed & 0x80 != 0 {
+            Some(self.color_table(packed & 7)?)
+        } else {
+            None
+        };
+
+        let mut canvas: Vec<Pixel> = vec![None; width as usize * height as usize];
+        let mut frames = Vec::new();
+        // The settings of the next image, from a graphic control extension.
+        let (mut delay, mut disposal, mut transparent) = (0u16, 0u8, None);
+        loop {
+            match self.byte() {
+                // The trailer; files cut short keep the frames read so far.
+                Some(0x3b) | None => break,
+                Some(0x21) => {
+                    let label = self.byte()?;
+                    let data = self.sub_blocks()?;
+                    if label == 0xf9 && data.len() >= 4 {
+                        disposal = (data[0] >> 2) & 7;
+                        delay = u16::from_le_bytes([data[1], data[2]]);
+                        transparent = (data[0] & 1 != 0).then_some(data[3]);
+                    }
+                }
+                Some(0x2c) if (frames.len() + 1) * canvas.len() > MAX_DECODED => break,
+                Some(0x2c) => {
+                    let (left, top) = (self.u16()? as usize, self.u16()? as usize);
+                    let (w, h) = (self.u16()? as usize, self.u16()? as usize);
+                    if w * h > MAX_PIXELS {
+                        return None;
+                    }
+                    let packed = self.byte()?;
+                    let local = if packed & 0x80 != 0 {
+                        Some(self.color_table(packed & 7)?)
+                    } else {
+                        None
+                    };
+                    let palette = local.as_ref().or(global.as_ref())?;
+                    let min_code_size = self.byte()?;
+                    let data = self.sub_blocks()?;
+                    let mut indices = lzw_decode(&data, min_code_size, w * h)?;
+                    indices.resize(w * h, 0);
+                    if packed & 0x40 != 0 {
+                        indices = deinterlace(&indices, w, h);
+                    }
+
+                    let previous = (disposal == 3).then(|| canvas.clone());
+                    let inside = |x: usize, y: usize| x < width as usize && y < height as usize;
+                    for (i, index) in indices.iter().enumerate() {
+                        let (x, y) = (left + i % w.max(1), top + i / w.max(1));
+                        if Some(*index) != transparent && inside(x, y) {
+                            canvas[y * width as usize + x] = palette.get(*index as usize).copied();
+                        }
+                    }
+                    let shown = match delay {
+                        0 | 1 => Duration::from_millis(100),
+                        centiseconds => Duration::from_millis(centiseconds as u64 * 10),
+                    };
+                    frames.push((canvas.clone(), shown));
+
+                    match (disposal, previous) {
+                        // Restore the background, which is transparent here.
+                        (2, _) => {
+                            for y in top..top + h {
+                                for x in left..left + w {
+                                    if inside(x, y) {
+                                        canvas[y * width as usize + x] = None;
+                                    }
+                                }
+                            }
+                        }
+                        (3, Some(previous)) => canvas = previous,
+                        _ => {}
+                    }
+                    (delay, disposal, transparent) = (0, 0, None);
+                }
+                Some(_) => return None,
+            }
+        }
+        Some((width, height, frames))
+    }
+}
+
+/// Decodes the LZW-compressed color indices of an image.
+fn lzw_decode(data: &[u8], min_code_size: u8, pixels: usize) -> Option<Vec<u8>> {
+    if !(1..=11).contains(&min_code_size) {
+        return None;
+    }
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    // Every code is a known code (`prefix`) followed by one index (`suffix`).
+    let mut prefix = vec![0u16; 4096];
+    let mut suffix = vec![0u8; 4096];
+    let mut first = vec![0u8; 4096];
+    let mut length = vec![0u16; 4096];
+    for code in 0..clear {
+        (
+            suffix[code as usize],
+            first[code as usize],
+            length[code as usize],
+        ) = (code as u8, code as u8, 1);
+    }
+
+    let mut out = Vec::with_capacity(pixels);
+    let (mut code_size, mut next, mut previous) = (min_code_size + 1, end + 1, None::<u16>);
+    let (mut bits, mut count) = (0u32, 0u8);
+    let mut bytes = data.iter();
+    loop {
+        while count < code_size {
+            match bytes.next() {
+                Some(byte) => bits |= (*byte as u32) << count,
+                None => return Some(out),
+            }
+            count += 8;
+        }
+        let code = (bits & ((1 << code_size) - 1)) as u16;
+        bits >>= code_size;
+        count -= code_size;
+
+        if code == clear {
+            (code_size, next, previous) = (min_code_size + 1, end + 1, None);
+            continue;
+        }
+        if code == end {
+            return Some(out);
+        }
+        let known = code < next && code != clear && code != end;
+        match previous {
+            Some(previous) if next < 4096 && (known || code == next) => {
+                let added = next as usize;
+                prefix[added] = previous;
+                first[added] = first[previous as usize];
+                suffix[added] = if known {
+                    first[code as usize]
+                } else {
+                    first[previous as usize]
+                };
+                length[added] = length[previous as usize] + 1;
+                next += 1;
+                if next == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            _ if !known => return None,
+            _ => {}
+        }
+
+        // Write the indices of the code, from the last one back to the first.
+        let start = out.len();
+        out.resize(start + length[code as usize] as usize, 0);
+        let mut entry = code;
+        for slot in out[start..].iter_mut().rev() {
+            *slot = suffix[entry as usize];
+            entry = prefix[entry as usize];
+        }
+        previous = Some(code);
+        if out.len() >= pixels {
+            out.truncate(pixels);
+            return Some(out);
+        }
+    }
+}
+
+/// Puts the rows of an interlaced image, stored in four passes, back in order.
+fn deinterlace(indices: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let mut rows = Vec::with_capacity(height);
+    for (start, step) in [(0, 8), (4, 8), (2, 4), (1, 2)] {
+        rows.extend((start..height).step_by(step));
+    }
+    let mut ordered = vec![0; indices.len()];
+    for (stored, row) in rows.into_iter().enumerate() {
+        let (from, to) = (stored * width, row * width);
+        ordered[to..to + width].copy_from_slice(&indices[from..from + width]);
+    }
+    ordered
+}
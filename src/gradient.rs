@@ -0,0 +1,197 @@
+//! This module provides generators for per-character colors: gradients and the (animated) rainbow of the classic nyan-cat trail.
+//!
+//! Both generators turn a string into `(NyanStyle, text)` spans, one per character, which can be passed straight to `Objects::new_styled_text`. The rainbow takes a frame number (e.g. `App::frame()`), so redrawing it every frame makes the colors flow along the text.
+//!
+//! # Structs
+//!
+//! - `Gradient`: Blends between two or more RGB colors along the text.
+//! - `Rainbow`: Cycles through the hues of the color wheel along the text and over time.
+//!
+//! # Methods
+//!
+//! - `Gradient::new(stops)`: Creates a gradient through the given colors.
+//! - `Gradient::easing(easing)`: Sets the easing curve used to blend between the colors.
+//! - `Gradient::color_at(t)`: Returns the color at position `t` (`0.0..=1.0`).
+//! - `Gradient::spans(text)`: Colors each character of `text` along the gradient.
+//! - `Rainbow::new()`: Creates a rainbow with the default speed and spread.
+//! - `Rainbow::speed(degrees)` / `Rainbow::spread(degrees)`: Set how fast the hues move per frame / how far they change per character.
+//! - `Rainbow::color_at(index, frame)`: Returns the color of the character at `index` in the given frame.
+//! - `Rainbow::spans(text, frame)`: Colors each character of `text` for the given frame.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let rainbow = Rainbow::new();
+//! app.draw_with(|app| {
+//!     let trail = Objects::new_styled_text(rainbow.spans("=========", app.frame()));
+//!     trail.draw(app).unwrap();
+//! })?;
+//! ```
+
+use std::borrow::Cow;
+
+use crate::color::NyanColor;
+use crate::easing::Easing;
+use crate::style::NyanStyle;
+
+/// Splits `text` into one span per character, styled with `color(index)`.
+fn per_char<'a, F: FnMut(usize) -> NyanColor>(
+    text: &'a str,
+    mut color: F,
+) -> Vec<(NyanStyle, Cow<'a, str>)> {
+    let mut spans = Vec::with_capacity(text.len());
+    for (index, (start, ch)) in text.char_indices().enumerate() {
+        let end = start + ch.len_utf8();
+        let style = if ch.is_whitespace() {
+            NyanStyle::new()
+        } else {
+            NyanStyle::new().fg(color(index))
+        };
+        spans.push((style, Cow::Borrowed(&text[start..end])));
+    }
+    spans
+}
+
+/// `Gradient` blends between two or more RGB colors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(u8, u8, u8)>,
+    easing: Easing,
+}
+
+impl Gradient {
+    /// Creates a gradient through the given colors, evenly spaced from start to end.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::color::NyanColor;
+    /// use nyan::gradient::Gradient;
+    ///
+    /// let gradient = Gradient::new([(255, 0, 0), (0, 0, 255)]);
+    /// assert_eq!(gradient.color_at(0.0), NyanColor::Rgb(255, 0, 0));
+    /// assert_eq!(gradient.color_at(0.5), NyanColor::Rgb(128, 0, 128));
+    /// assert_eq!(gradient.color_at(1.0), NyanColor::Rgb(0, 0, 255));
+    /// ```
+    pub fn new<I: IntoIterator<Item = (u8, u8, u8)>>(stops: I) -> Self {
+        Self {
+            stops: stops.into_iter().collect(),
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Sets the easing curve used to blend between two neighbouring colors.
+    pub fn easing(self, easing: Easing) -> Self {
+        let mut gradient = self;
+        gradient.easing = easing;
+        gradient
+    }
+
+    /// Returns the color at position `t`, from `0.0` (first color) to `1.0` (last color).
+    ///
+    /// A gradient without colors is `NyanColor::Reset`.
+    pub fn color_at(&self, t: f32) -> NyanColor {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return NyanColor::Reset,
+        };
+        if self.stops.len() == 1 || t <= 0.0 {
+            return NyanColor::Rgb(first.0, first.1, first.2);
+        }
+        if t >= 1.0 {
+            return NyanColor::Rgb(last.0, last.1, last.2);
+        }
+
+        let segments = (self.stops.len() - 1) as f32;
+        let index = ((t * segments) as usize).min(self.stops.len() - 2);
+        let local = t * segments - index as f32;
+        let (from, to) = (self.stops[index], self.stops[index + 1]);
+        let mix = |a: u8, b: u8| self.easing.lerp(a as f32, b as f32, local).round() as u8;
+        NyanColor::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+    }
+
+    /// Colors each character of `text` along the gradient, from its first to its last character.
+    ///
+    /// Whitespace is left unstyled.
+    pub fn spans<'a>(&self, text: &'a str) -> Vec<(NyanStyle, Cow<'a, str>)> {
+        let last = text.chars().count().saturating_sub(1).max(1) as f32;
+        per_char(text, |index| self.color_at(index as f32 / last))
+    }
+}
+
+/// `Rainbow` cycles through the hues of the color wheel, along the text and over time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rainbow {
+    speed: f32,
+    spread: f32,
+}
+
+impl Default for Rainbow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rainbow {
+    /// Creates a rainbow that moves 10° per frame and changes 30° per character.
+    pub fn new() -> Self {
+        Self {
+            speed: 10.0,
+            spread: 30.0,
+        }
+    }
+
+    /// Sets how many degrees the hues move per frame (`0.0` for a still rainbow).
+    pub fn speed(self, degrees: f32) -> Self {
+        let mut rainbow = self;
+        rainbow.speed = degrees;
+        rainbow
+    }
+
+    /// Sets how many degrees the hue changes from one character to the next.
+    pub fn spread(self, degrees: f32) -> Self {
+        let mut rainbow = self;
+        rainbow.spread = degrees;
+        rainbow
+    }
+
+    /// Returns the color of the character at `index` in the given frame.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::color::NyanColor;
+    /// use nyan::gradient::Rainbow;
+    ///
+    /// let rainbow = Rainbow::new().spread(120.0);
+    /// assert_eq!(rainbow.color_at(0, 0), NyanColor::Rgb(255, 0, 0));
+    /// assert_eq!(rainbow.color_at(1, 0), NyanColor::Rgb(0, 255, 0));
+    /// assert_eq!(rainbow.color_at(2, 0), NyanColor::Rgb(0, 0, 255));
+    /// ```
+    pub fn color_at(&self, index: usize, frame: u64) -> NyanColor {
+        let hue = (index as f64 * self.spread as f64 + frame as f64 * self.speed as f64)
+            .rem_euclid(360.0);
+        hue_to_rgb(hue as f32)
+    }
+
+    /// Colors each character of `text` for the given frame (e.g. `App::frame()`).
+    ///
+    /// Whitespace is left unstyled.
+    pub fn spans<'a>(&self, text: &'a str, frame: u64) -> Vec<(NyanStyle, Cow<'a, str>)> {
+        per_char(text, |index| self.color_at(index, frame))
+    }
+}
+
+/// Returns the fully saturated, fully bright color of a hue in degrees.
+fn hue_to_rgb(hue: f32) -> NyanColor {
+    let sector = hue / 60.0;
+    let x = 1.0 - (sector % 2.0 - 1.0).abs();
+    let (r, g, b) = match sector as u8 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let channel = |c: f32| (c * 255.0).round() as u8;
+    NyanColor::Rgb(channel(r), channel(g), channel(b))
+}
@@ -14,14 +14,20 @@
 //! # Methods
 //!
 //! - `get_input`: Asynchronously retrieves the keyboard input. It waits for 16 milliseconds using `poll` and returns a `NyanInput` value representing the key pressed.
+//! - `to_char`: Returns the typed character of a `NyanInput` with its original case, including non-ASCII characters.
+//! - `shifted`: Returns a `NyanInput` of an alphabet key with Shift.
+//! - `NyanKey::from_char` / `NyanKey::to_char`: Convert between characters and `NyanKey` values.
 
 use std::{fmt::Debug, time::Duration};
 
-use crossterm::event::{self, KeyCode, KeyModifiers};
+use crossterm::event::{self, KeyCode, KeyEvent, KeyModifiers};
+
+use crate::errors::NyanResult;
 
 /// `NyanKey` represents individual keyboard keys.
 ///
-/// It includes alphabet keys (`A-Z`) and unrecognized keys (`NoKeys(char)`).
+/// It includes alphabet keys (`A-Z`) and unrecognized keys (`OtherKey(char)`).
+/// `OtherKey` keeps the character exactly as it was typed, so non-ASCII input is preserved.
 #[allow(unused)]
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum NyanKey {
@@ -88,6 +94,85 @@ impl Debug for NyanKey {
     }
 }
 
+impl NyanKey {
+    /// Converts a character into a `NyanKey`.
+    ///
+    /// ASCII letters of either case map to the alphabet keys (`A-Z`). Every other character,
+    /// including non-ASCII characters entered through an IME, is kept unchanged in `OtherKey`.
+    ///
+    /// # Arguments
+    /// * `ch` - The character to convert.
+    ///
+    /// # Returns
+    /// The matching `NyanKey`.
+    pub fn from_char(ch: char) -> Self {
+        match ch.to_ascii_lowercase() {
+            'a' => NyanKey::A,
+            'b' => NyanKey::B,
+            'c' => NyanKey::C,
+            'd' => NyanKey::D,
+            'e' => NyanKey::E,
+            'f' => NyanKey::F,
+            'g' => NyanKey::G,
+            'h' => NyanKey::H,
+            'i' => NyanKey::I,
+            'j' => NyanKey::J,
+            'k' => NyanKey::K,
+            'l' => NyanKey::L,
+            'm' => NyanKey::M,
+            'n' => NyanKey::N,
+            'o' => NyanKey::O,
+            'p' => NyanKey::P,
+            'q' => NyanKey::Q,
+            'r' => NyanKey::R,
+            's' => NyanKey::S,
+            't' => NyanKey::T,
+            'u' => NyanKey::U,
+            'v' => NyanKey::V,
+            'w' => NyanKey::W,
+            'x' => NyanKey::X,
+            'y' => NyanKey::Y,
+            'z' => NyanKey::Z,
+            _ => NyanKey::OtherKey(ch),
+        }
+    }
+
+    /// Returns the character of this key.
+    ///
+    /// Alphabet keys are returned in lowercase; `OtherKey` returns its stored character as is.
+    pub fn to_char(self) -> char {
+        match self {
+            Self::A => 'a',
+            Self::B => 'b',
+            Self::C => 'c',
+            Self::D => 'd',
+            Self::E => 'e',
+            Self::F => 'f',
+            Self::G => 'g',
+            Self::H => 'h',
+            Self::I => 'i',
+            Self::J => 'j',
+            Self::K => 'k',
+            Self::L => 'l',
+            Self::M => 'm',
+            Self::N => 'n',
+            Self::O => 'o',
+            Self::P => 'p',
+            Self::Q => 'q',
+            Self::R => 'r',
+            Self::S => 's',
+            Self::T => 't',
+            Self::U => 'u',
+            Self::V => 'v',
+            Self::W => 'w',
+            Self::X => 'x',
+            Self::Y => 'y',
+            Self::Z => 'z',
+            Self::OtherKey(c) => c,
+        }
+    }
+}
+
 /// `NyanInput` represents keyboard inputs.
 ///
 /// It supports special keys and modifier keys (`Shift`, `Ctrl`, `Alt`).
@@ -153,81 +238,163 @@ impl<'a> Debug for NyanInput<'a> {
     }
 }
 
+/// The alphabet keys, from `A` to `Z`.
+const LETTERS: [NyanKey; 26] = [
+    NyanKey::A,
+    NyanKey::B,
+    NyanKey::C,
+    NyanKey::D,
+    NyanKey::E,
+    NyanKey::F,
+    NyanKey::G,
+    NyanKey::H,
+    NyanKey::I,
+    NyanKey::J,
+    NyanKey::K,
+    NyanKey::L,
+    NyanKey::M,
+    NyanKey::N,
+    NyanKey::O,
+    NyanKey::P,
+    NyanKey::Q,
+    NyanKey::R,
+    NyanKey::S,
+    NyanKey::T,
+    NyanKey::U,
+    NyanKey::V,
+    NyanKey::W,
+    NyanKey::X,
+    NyanKey::Y,
+    NyanKey::Z,
+];
+
+/// The inputs the `Shift` inputs of the alphabet keys point to: each key alone, with Ctrl and
+/// with Alt. They are static, so shifted letters are converted without allocating.
+static SHIFTED_LETTERS: [[NyanInput<'static>; 3]; 26] = shifted_letters();
+
+const fn shifted_letters() -> [[NyanInput<'static>; 3]; 26] {
+    let mut inputs = [[NyanInput::Null; 3]; 26];
+    let mut i = 0;
+    while i < LETTERS.len() {
+        let key = LETTERS[i];
+        inputs[i] = [
+            NyanInput::Key(key),
+            NyanInput::Ctrl(key),
+            NyanInput::Alt(key),
+        ];
+        i += 1;
+    }
+    inputs
+}
+
 impl<'a> NyanInput<'a> {
+    /// Returns the character that was typed, with its original case.
+    ///
+    /// `Key` and `Shift` inputs yield their character (uppercase for shifted alphabet keys),
+    /// so text fields can be fed directly from input events, including non-ASCII text.
+    ///
+    /// # Returns
+    /// * `Some(char)` - for `Key` and `Shift` inputs
+    /// * `None` - for modifier combinations and special keys
+    pub fn to_char(&self) -> Option<char> {
+        match self {
+            Self::Key(k) => Some(k.to_char()),
+            Self::Shift(NyanInput::Key(NyanKey::OtherKey(c))) => Some(*c),
+            Self::Shift(NyanInput::Key(k)) => Some(k.to_char().to_ascii_uppercase()),
+            _ => None,
+        }
+    }
+
+    /// Returns this input with Shift, e.g. `Shift(&Ctrl(S))` for `Ctrl(S)`.
+    ///
+    /// # Returns
+    /// * `Some(NyanInput::Shift)` - for `Key`, `Ctrl` and `Alt` inputs of alphabet keys
+    /// * `None` - for other inputs, whose Shift input would have to be allocated
+    pub fn shifted(&self) -> Option<NyanInput<'static>> {
+        let (key, modifier) = match *self {
+            Self::Key(key) => (key, 0),
+            Self::Ctrl(key) => (key, 1),
+            Self::Alt(key) => (key, 2),
+            _ => return None,
+        };
+        let letter = LETTERS.iter().position(|letter| *letter == key)?;
+        Some(NyanInput::Shift(&SHIFTED_LETTERS[letter][modifier]))
+    }
+
     /// `get_input` asynchronously retrieves keyboard input.
     ///
     /// Waits for 16 milliseconds using `poll` and returns `NyanInput` if a key is pressed.
     ///
     /// # Returns
     /// * `Ok(NyanInput)` - on success
-    /// * `Err(anyhow::Error)` - if reading input fails
+    /// * `Err(NyanError)` - if reading input fails
     #[allow(unused)]
-    pub fn get_input() -> anyhow::Result<Self> {
+    pub fn get_input() -> NyanResult<Self> {
         if event::poll(Duration::from_millis(16))? {
             if let event::Event::Key(key) = event::read()? {
-                let nyan_input = match key.code {
-                    KeyCode::Char(ch) => {
-                        let nyan_key = match ch.to_ascii_lowercase() {
-                            'a' => NyanKey::A,
-                            'b' => NyanKey::B,
-                            'c' => NyanKey::C,
-                            'd' => NyanKey::D,
-                            'e' => NyanKey::E,
-                            'f' => NyanKey::F,
-                            'g' => NyanKey::G,
-                            'h' => NyanKey::H,
-                            'i' => NyanKey::I,
-                            'j' => NyanKey::J,
-                            'k' => NyanKey::K,
-                            'l' => NyanKey::L,
-                            'm' => NyanKey::M,
-                            'n' => NyanKey::N,
-                            'o' => NyanKey::O,
-                            'p' => NyanKey::P,
-                            'q' => NyanKey::Q,
-                            'r' => NyanKey::R,
-                            's' => NyanKey::S,
-                            't' => NyanKey::T,
-                            'u' => NyanKey::U,
-                            'v' => NyanKey::V,
-                            'w' => NyanKey::W,
-                            'x' => NyanKey::X,
-                            'y' => NyanKey::Y,
-                            'z' => NyanKey::Z,
-                            p => NyanKey::OtherKey(p),
-                        };
-                        if key.modifiers.contains(KeyModifiers::CONTROL) {
-                            Self::Ctrl(nyan_key)
-                        } else if key.modifiers.contains(KeyModifiers::ALT) {
-                            Self::Alt(nyan_key)
-                        } else if key.modifiers.contains(KeyModifiers::SHIFT) {
-                            Self::Shift(Box::leak(Box::new(NyanInput::Key(nyan_key))))
-                        } else {
-                            Self::Key(nyan_key)
-                        }
-                    }
-                    KeyCode::Left => Self::LeftAllow,
-                    KeyCode::Right => Self::RightAllow,
-                    KeyCode::Up => Self::UpAllow,
-                    KeyCode::Down => Self::DownAllow,
-                    KeyCode::Enter => Self::Enter,
-                    KeyCode::Backspace => Self::BackSpace,
-                    KeyCode::Tab => Self::Tab,
-                    KeyCode::Esc => Self::Esc,
-                    KeyCode::End => Self::End,
-                    KeyCode::Insert => Self::Insert,
-                    KeyCode::CapsLock => Self::CapsLock,
-                    KeyCode::Home => Self::Home,
-                    KeyCode::PageUp => Self::PageUp,
-                    KeyCode::PageDown => Self::PageDown,
-                    KeyCode::Delete => Self::Delete,
-                    KeyCode::F(f) => Self::FunctionKey(f),
-                    KeyCode::Null => Self::Null,
-                    _ => return Ok(Self::Null),
-                };
-                return Ok(nyan_input);
+                return Ok(key.into());
             }
         }
         Ok(Self::Null)
     }
 }
+
+impl<'a> From<KeyEvent> for NyanInput<'a> {
+    /// Converts a crossterm `KeyEvent` into the matching `NyanInput`.
+    ///
+    /// Keys that nyan does not model are converted into `NyanInput::Null`.
+    fn from(key: KeyEvent) -> Self {
+        match key.code {
+            KeyCode::Char(ch) => {
+                let nyan_key = NyanKey::from_char(ch);
+                let input = if key.modifiers.contains(KeyModifiers::CONTROL) {
+                    Self::Ctrl(nyan_key)
+                } else if key.modifiers.contains(KeyModifiers::ALT) {
+                    Self::Alt(nyan_key)
+                } else {
+                    Self::Key(nyan_key)
+                };
+                // Uppercase letters typed with Caps Lock arrive without the Shift modifier,
+                // so the case of the char itself decides here. Shifted symbols are already
+                // told apart by their char and stay plain keys.
+                if key.modifiers.contains(KeyModifiers::SHIFT) || ch.is_ascii_uppercase() {
+                    input.shifted().unwrap_or(input)
+                } else {
+                    input
+                }
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down
+                if key.modifiers.contains(KeyModifiers::SHIFT) =>
+            {
+                Self::Shift(match key.code {
+                    KeyCode::Left => &NyanInput::LeftAllow,
+                    KeyCode::Right => &NyanInput::RightAllow,
+                    KeyCode::Up => &NyanInput::UpAllow,
+                    _ => &NyanInput::DownAllow,
+                })
+            }
+            KeyCode::Left => Self::LeftAllow,
+            KeyCode::Right => Self::RightAllow,
+            KeyCode::Up => Self::UpAllow,
+            KeyCode::Down => Self::DownAllow,
+            KeyCode::Enter => Self::Enter,
+            KeyCode::Backspace => Self::BackSpace,
+            // Terminals report Shift+Tab as a key of its own.
+            KeyCode::BackTab => Self::Shift(&NyanInput::Tab),
+            KeyCode::Tab if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                Self::Shift(&NyanInput::Tab)
+            }
+            KeyCode::Tab => Self::Tab,
+            KeyCode::Esc => Self::Esc,
+            KeyCode::End => Self::End,
+            KeyCode::Insert => Self::Insert,
+            KeyCode::CapsLock => Self::CapsLock,
+            KeyCode::Home => Self::Home,
+            KeyCode::PageUp => Self::PageUp,
+            KeyCode::PageDown => Self::PageDown,
+            KeyCode::Delete => Self::Delete,
+            KeyCode::F(f) => Self::FunctionKey(f),
+            _ => Self::Null,
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! This module provides `Interner`, which maps string ids to small integer keys, so ids used every frame are compared as numbers instead of strings.
+//!
+//! An interner can be used e.g. to key a `HashMap` by `Id` in app code. (A [`NyanObj`](crate::nyan_obj::NyanObj) maps the ids of its objects to their positions directly, so looking an object up hashes its id once, and forgets the ids of removed objects.)
+//!
+//! Interned strings are kept for the life of the interner, so it suits a small, fixed set of ids, such as `"frame"` and `"hud_score"`.
+//!
+//! # Structs
+//!
+//! - `Interner`: The interned strings and their keys.
+//! - `Id`: The key of an interned string.
+//!
+//! # Methods
+//!
+//! - `Interner::new()`: Creates an empty interner.
+//! - `intern(name)`: Returns the key of a string, interning it if it is new.
+//! - `get(name)`: Returns the key of a string interned before.
+//! - `resolve(id)`: Returns the string of a key.
+//! - `len()` / `is_empty()`: Count the interned strings.
+
+use std::collections::HashMap;
+
+/// `Id` is the key of a string interned by an [`Interner`]. Keys are only meaningful to the
+/// interner that made them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Id(u32);
+
+impl Id {
+    /// Returns the key as a number, counting up from `0` in the order the strings were
+    /// interned.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// `Interner` maps strings to small integer keys.
+///
+/// # Example
+/// ```rust
+/// use nyan::intern::Interner;
+///
+/// let mut ids = Interner::new();
+/// let score = ids.intern("hud_score");
+/// assert_eq!(ids.intern("hud_score"), score);
+/// assert_ne!(ids.intern("frame"), score);
+///
+/// assert_eq!(ids.get("hud_score"), Some(score));
+/// assert_eq!(ids.get("menu"), None);
+/// assert_eq!(ids.resolve(score), Some("hud_score"));
+/// assert_eq!(ids.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Interner {
+    keys: HashMap<Box<str>, Id>,
+    names: Vec<Box<str>>,
+}
+
+impl Interner {
+    /// Creates an interner without strings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the key of `name`, interning it first if it is new.
+    pub fn intern(&mut self, name: &str) -> Id {
+        if let Some(id) = self.keys.get(name) {
+            return *id;
+        }
+        let id = Id(self.names.len() as u32);
+        self.names.push(name.into());
+        self.keys.insert(name.into(), id);
+        id
+    }
+
+    /// Returns the key of `name`, or `None` if it was never interned. This does not allocate.
+    pub fn get(&self, name: &str) -> Option<Id> {
+        self.keys.get(name).copied()
+    }
+
+    /// Returns the string of `id`, or `None` if `id` was made by another interner.
+    pub fn resolve(&self, id: Id) -> Option<&str> {
+        self.names.get(id.index()).map(|name| name.as_ref())
+    }
+
+    /// Returns the number of interned strings.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns whether no string was interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
@@ -0,0 +1,244 @@
+//! This module provides `Keybindings`, a registry that maps named actions to the keys that trigger them, and the key names used to write keys as text.
+//!
+//! An application registers its actions with their default keys and asks the registry which action an input event triggers, instead of matching keys itself. The keys can then be changed without recompiling, e.g. by a config file (see [`App::load_config`](crate::app::App::load_config)), and shown in help texts and command palettes with `hint`.
+//!
+//! Keys are written as in most editors: a key name, optionally after `ctrl+`, `alt+` or `shift+`. Names are case-insensitive: `q`, `?`, `space`, `enter`, `esc`, `tab`, `backspace`, `delete`, `insert`, `home`, `end`, `pageup`, `pagedown`, `up`, `down`, `left`, `right` and `f1` to `f24`. A single uppercase letter (`Q`) is the same as `shift+q`.
+//!
+//! # Structs
+//!
+//! - `Keybindings`: The actions of an application and their keys.
+//!
+//! # Functions
+//!
+//! - `parse_key(text)`: Reads a key such as `"ctrl+s"`.
+//! - `key_name(key)`: Writes a key as text, e.g. `"Ctrl+S"`.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a registry without actions.
+//! - `bind(action, key)`: Adds a default key to an action, registering it.
+//! - `set(action, keys)`: Replaces the keys of an action.
+//! - `action(key)` / `action_for(event)`: Return the action a key or an event triggers.
+//! - `keys(action)` / `hint(action)`: Return the keys of an action / the first one as text.
+//! - `actions()` / `contains(action)`: List / look up the registered actions.
+
+use std::borrow::Cow;
+
+use crate::errors::{NyanError, NyanResult};
+use crate::event::NyanEvent;
+use crate::input::{NyanInput, NyanKey};
+
+/// `Keybindings` maps named actions to the keys that trigger them, in the order the actions
+/// were registered.
+///
+/// # Example
+/// ```rust
+/// use nyan::event::NyanEvent;
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::keybindings::{parse_key, Keybindings};
+///
+/// let mut keys = Keybindings::new()
+///     .bind("quit", NyanInput::Ctrl(NyanKey::C))
+///     .bind("quit", NyanInput::Esc)
+///     .bind("save", NyanInput::Ctrl(NyanKey::S));
+///
+/// let event: NyanEvent = NyanEvent::Key(NyanInput::Esc);
+/// assert_eq!(keys.action_for(&event), Some("quit"));
+/// assert_eq!(keys.hint("save").as_deref(), Some("Ctrl+S"));
+///
+/// keys.set("save", vec![parse_key("f2").unwrap()]);
+/// assert_eq!(keys.action(&NyanInput::FunctionKey(2)), Some("save"));
+/// assert_eq!(keys.action(&NyanInput::Ctrl(NyanKey::S)), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Keybindings {
+    bindings: Vec<(Cow<'static, str>, Vec<NyanInput<'static>>)>,
+}
+
+impl Keybindings {
+    /// Creates a registry without actions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `key` to the keys of `action`, registering the action if it is new.
+    ///
+    /// # Returns
+    /// The registry with the key bound.
+    pub fn bind<P: Into<Cow<'static, str>>>(self, action: P, key: NyanInput<'static>) -> Self {
+        let mut keys = self;
+        let action = action.into();
+        match keys.bindings.iter_mut().find(|(name, _)| *name == action) {
+            Some((_, bound)) => bound.push(key),
+            None => keys.bindings.push((action, vec![key])),
+        }
+        keys
+    }
+
+    /// Replaces the keys of `action`, registering the action if it is new. An empty list
+    /// leaves the action without keys.
+    pub fn set<P: Into<Cow<'static, str>>>(&mut self, action: P, keys: Vec<NyanInput<'static>>) {
+        let action = action.into();
+        match self.bindings.iter_mut().find(|(name, _)| *name == action) {
+            Some((_, bound)) => *bound = keys,
+            None => self.bindings.push((action, keys)),
+        }
+    }
+
+    /// Returns the action `key` triggers, or `None` if it is not bound. Symbols count as the
+    /// same key whether the terminal reports them with Shift or not.
+    pub fn action(&self, key: &NyanInput) -> Option<&str> {
+        let key = unshift(*key);
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.iter().any(|bound| unshift(*bound) == key))
+            .map(|(name, _)| name.as_ref())
+    }
+
+    /// Returns the action a key event triggers, or `None` for other events and unbound keys.
+    pub fn action_for<U>(&self, event: &NyanEvent<U>) -> Option<&str> {
+        match event {
+            NyanEvent::Key(key) => self.action(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the keys of `action`, empty if it has none or is not registered.
+    pub fn keys(&self, action: &str) -> &[NyanInput<'static>] {
+        self.bindings
+            .iter()
+            .find(|(name, _)| name == action)
+            .map_or(&[], |(_, keys)| keys.as_slice())
+    }
+
+    /// Returns the first key of `action` as text, e.g. as the hint of a command in a
+    /// [`CommandPalette`](crate::palette::CommandPalette).
+    pub fn hint(&self, action: &str) -> Option<String> {
+        self.keys(action).first().map(key_name)
+    }
+
+    /// Returns the registered actions, in the order they were registered.
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.bindings.iter().map(|(name, _)| name.as_ref())
+    }
+
+    /// Returns whether `action` is registered.
+    pub fn contains(&self, action: &str) -> bool {
+        self.bindings.iter().any(|(name, _)| name == action)
+    }
+}
+
+/// Returns a shifted symbol as the symbol itself, as terminals differ in reporting Shift for it.
+fn unshift<'a>(key: NyanInput<'a>) -> NyanInput<'a> {
+    match key {
+        NyanInput::Shift(NyanInput::Key(NyanKey::OtherKey(c))) => {
+            NyanInput::Key(NyanKey::OtherKey(*c))
+        }
+        key => key,
+    }
+}
+
+/// The keys that have a name, with the name they are written with.
+const NAMED_KEYS: [(&str, NyanInput<'static>); 16] = [
+    ("Space", NyanInput::Key(NyanKey::OtherKey(' '))),
+    ("Enter", NyanInput::Enter),
+    ("Esc", NyanInput::Esc),
+    ("Tab", NyanInput::Tab),
+    ("Backspace", NyanInput::BackSpace),
+    ("Delete", NyanInput::Delete),
+    ("Insert", NyanInput::Insert),
+    ("Home", NyanInput::Home),
+    ("End", NyanInput::End),
+    ("PageUp", NyanInput::PageUp),
+    ("PageDown", NyanInput::PageDown),
+    ("Up", NyanInput::UpAllow),
+    ("Down", NyanInput::DownAllow),
+    ("Left", NyanInput::LeftAllow),
+    ("Right", NyanInput::RightAllow),
+    ("CapsLock", NyanInput::CapsLock),
+];
+
+/// Reads a key written as text, such as `"q"`, `"ctrl+s"`, `"shift+tab"` or `"F5"` (see the
+/// module documentation for all names).
+///
+/// # Returns
+///
+/// - `Ok(NyanInput)` with the key.
+/// - `Err(NyanError::InvalidData)` if the text is not a key.
+///
+/// # Example
+/// ```rust
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::keybindings::parse_key;
+///
+/// assert_eq!(parse_key("ctrl+s").unwrap(), NyanInput::Ctrl(NyanKey::S));
+/// assert_eq!(parse_key("Shift+Tab").unwrap(), NyanInput::Shift(&NyanInput::Tab));
+/// assert!(parse_key("hyper+x").is_err());
+/// ```
+pub fn parse_key(text: &str) -> NyanResult<NyanInput<'static>> {
+    let invalid = || NyanError::InvalidData(format!("unknown key {text:?}").into());
+    // A lone "+" is the key itself, not a modifier without a key.
+    let (modifier, name) = match text.rsplit_once('+') {
+        Some((modifier, name)) if !name.is_empty() => (Some(modifier), name),
+        Some((modifier, _)) if !modifier.is_empty() => (Some(&modifier[..modifier.len() - 1]), "+"),
+        _ => (None, text),
+    };
+    let mut chars = name.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(ch), None) if ch.is_ascii_uppercase() && modifier.is_none() => {
+            let key = NyanInput::Key(NyanKey::from_char(ch));
+            key.shifted().unwrap_or(key)
+        }
+        (Some(ch), None) => NyanInput::Key(NyanKey::from_char(ch)),
+        _ => match NAMED_KEYS
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(name))
+        {
+            Some((_, key)) => *key,
+            None => {
+                let number = name
+                    .strip_prefix(['f', 'F'])
+                    .and_then(|n| n.parse::<u8>().ok())
+                    .filter(|n| (1..=24).contains(n))
+                    .ok_or_else(invalid)?;
+                NyanInput::FunctionKey(number)
+            }
+        },
+    };
+
+    let Some(modifier) = modifier else {
+        return Ok(key);
+    };
+    match (modifier.to_ascii_lowercase().as_str(), key) {
+        ("ctrl", NyanInput::Key(key)) => Ok(NyanInput::Ctrl(key)),
+        ("alt", NyanInput::Key(key)) => Ok(NyanInput::Alt(key)),
+        ("shift", key @ NyanInput::Key(_)) => Ok(key.shifted().unwrap_or(key)),
+        ("shift", NyanInput::Tab) => Ok(NyanInput::Shift(&NyanInput::Tab)),
+        ("shift", NyanInput::UpAllow) => Ok(NyanInput::Shift(&NyanInput::UpAllow)),
+        ("shift", NyanInput::DownAllow) => Ok(NyanInput::Shift(&NyanInput::DownAllow)),
+        ("shift", NyanInput::LeftAllow) => Ok(NyanInput::Shift(&NyanInput::LeftAllow)),
+        ("shift", NyanInput::RightAllow) => Ok(NyanInput::Shift(&NyanInput::RightAllow)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Writes a key as text, e.g. `"Ctrl+S"`, `"Shift+Tab"` or `"q"`. [`parse_key`] reads it back.
+pub fn key_name(key: &NyanInput) -> String {
+    let letter = |key: NyanKey| match key {
+        NyanKey::OtherKey(' ') => "Space".to_string(),
+        key => key.to_char().to_uppercase().to_string(),
+    };
+    match key {
+        NyanInput::Ctrl(key) => format!("Ctrl+{}", letter(*key)),
+        NyanInput::Alt(key) => format!("Alt+{}", letter(*key)),
+        NyanInput::Shift(NyanInput::Key(key)) => format!("Shift+{}", letter(*key)),
+        NyanInput::Shift(key) => format!("Shift+{}", key_name(key)),
+        NyanInput::Key(NyanKey::OtherKey(' ')) => "Space".to_string(),
+        NyanInput::Key(key) => key.to_char().to_string(),
+        NyanInput::FunctionKey(number) => format!("F{number}"),
+        key => NAMED_KEYS
+            .iter()
+            .find(|(_, named)| named == key)
+            .map_or_else(String::new, |(name, _)| name.to_string()),
+    }
+}
@@ -0,0 +1,300 @@
+//! This module provides the `FloatLayer`, a layer of floating objects (dialogs, popups, menus) drawn above the normal layout.
+//!
+//! Objects of a `NyanObj` are registered as floats and stay hidden until they are opened. An open float is positioned by an `Anchor` (centered, at a fixed position, or next to another object), drawn after all other objects, and takes input: a modal float captures all input while it is open, a non-modal float (such as a popup menu) the mouse actions on it. Closed floats are not drawn at all.
+//!
+//! # Structs
+//!
+//! - `FloatLayer`: The floats of a `NyanObj` and which of them are open.
+//!
+//! # Enums
+//!
+//! - `Anchor`: Where a float is placed.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a layer without floats.
+//! - `float(id)`: Registers an object as a float, hidden until it is opened.
+//! - `open(id, anchor)` / `open_modal(id, anchor)`: Open an object as a float on top of everything else.
+//! - `close(id)` / `close_top()`: Close a float.
+//! - `is_open(id)` / `open_count()` / `top()` / `modal()`: Query the open floats.
+//! - `apply(objects, area)`: Positions the open floats inside `area`.
+//! - `draw_in(objects, out)`: Draws the normal objects, dimmed below a modal float, then the open floats.
+//! - `event_target(objects, event)`: Returns the float an event belongs to, or `None` if the normal objects may handle it.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use crate::errors::NyanResult;
+use crate::event::NyanEvent;
+use crate::layout::{self, Layout};
+use crate::nyan_obj::NyanObj;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+
+/// The `Anchor` enum tells where a float is placed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Anchor<'a> {
+    /// In the middle of the region, e.g. for dialogs.
+    Center,
+    /// With its top-left corner at a fixed cell.
+    At(u16, u16),
+    /// Below another object, left-aligned with it; above it if there is no room below.
+    Below(Cow<'a, str>),
+    /// Above another object, left-aligned with it; below it if there is no room above.
+    Above(Cow<'a, str>),
+    /// Right of another object, top-aligned with it; left of it if there is no room on the right.
+    RightOf(Cow<'a, str>),
+}
+
+/// An open float.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Float<'a> {
+    id: Cow<'a, str>,
+    anchor: Anchor<'a>,
+    modal: bool,
+}
+
+/// `FloatLayer` draws floating objects above the normal layout and routes input to them.
+///
+/// # Example
+/// ```ignore
+/// let mut floats = FloatLayer::new().float("confirm");
+/// floats.open_modal("confirm", Anchor::Center);
+/// floats.apply(&mut obj, Rect::terminal()?)?;
+///
+/// loop {
+///     let event = NyanEvent::get_event()?;
+///     match floats.event_target(&obj, &event) {
+///         Some("confirm") => { /* handle the dialog */ }
+///         Some(_) => {}
+///         None => { /* handle the rest of the screen */ }
+///     }
+///     app.draw_with(|app| floats.draw_in(&obj, app).unwrap())?;
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FloatLayer<'a> {
+    /// The ids of all floats, open or not.
+    floats: HashSet<Cow<'a, str>>,
+    /// The open floats, from the bottom to the top.
+    open: Vec<Float<'a>>,
+}
+
+impl<'a> FloatLayer<'a> {
+    /// Creates a layer without floats.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the object `id` as a float. It is not drawn until it is opened.
+    pub fn float<P: Into<Cow<'a, str>>>(self, id: P) -> Self {
+        let mut layer = self;
+        layer.floats.insert(id.into());
+        layer
+    }
+
+    fn push(&mut self, id: Cow<'a, str>, anchor: Anchor<'a>, modal: bool) {
+        self.floats.insert(id.clone());
+        self.open.retain(|f| f.id != id);
+        self.open.push(Float { id, anchor, modal });
+    }
+
+    /// Opens the object `id` as a float on top of all other floats, registering it if needed.
+    ///
+    /// The float takes the mouse actions on it; everything else still goes to the objects
+    /// below. Opening an open float again moves it to the top.
+    pub fn open<P: Into<Cow<'a, str>>>(&mut self, id: P, anchor: Anchor<'a>) {
+        self.push(id.into(), anchor, false);
+    }
+
+    /// Opens the object `id` as a modal float on top of all other floats.
+    ///
+    /// While it is open, it captures all input, and the objects below it are drawn dimmed.
+    pub fn open_modal<P: Into<Cow<'a, str>>>(&mut self, id: P, anchor: Anchor<'a>) {
+        self.push(id.into(), anchor, true);
+    }
+
+    /// Closes the float `id`.
+    ///
+    /// # Returns
+    /// `true` if the float was open.
+    pub fn close(&mut self, id: &str) -> bool {
+        let before = self.open.len();
+        self.open.retain(|f| f.id != id);
+        self.open.len() != before
+    }
+
+    /// Closes the topmost float and returns its id, e.g. when Esc is pressed.
+    pub fn close_top(&mut self) -> Option<Cow<'a, str>> {
+        self.open.pop().map(|f| f.id)
+    }
+
+    /// Returns whether the object `id` is an open float.
+    pub fn is_open(&self, id: &str) -> bool {
+        self.open.iter().any(|f| f.id == id)
+    }
+
+    /// Returns the number of open floats, the layers drawn above the normal objects.
+    pub fn open_count(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Returns the id of the topmost open float.
+    pub fn top(&self) -> Option<&str> {
+        self.open.last().map(|f| f.id.as_ref())
+    }
+
+    /// Returns the id of the topmost open modal float, which captures all input.
+    pub fn modal(&self) -> Option<&str> {
+        self.open
+            .iter()
+            .rev()
+            .find(|f| f.modal)
+            .map(|f| f.id.as_ref())
+    }
+
+    /// Positions every open float inside `area` according to its anchor.
+    ///
+    /// Floats are kept inside `area` where possible. Call this after opening a float and after
+    /// the terminal was resized (the layer is a [`Layout`], so a
+    /// [`LayoutManager`](crate::layout::LayoutManager) can do the latter).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if all floats were positioned.
+    /// - An error of type [`NyanError::ObjectNotFound`](crate::errors::NyanError) if a float
+    ///   does not exist in `objects`.
+    pub fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        for float in &self.open {
+            let size = objects.outer_size(float.id.clone()).ok_or_else(|| {
+                crate::errors::NyanError::ObjectNotFound(float.id.to_string().into())
+            })?;
+            let (x, y) = Self::place(objects, &float.anchor, size, area);
+            objects.move_object(float.id.clone(), (x, y))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the top-left corner of a float of `size` cells.
+    fn place(
+        objects: &NyanObj<'a>,
+        anchor: &Anchor<'a>,
+        size: (u16, u16),
+        area: Rect,
+    ) -> (u16, u16) {
+        let (width, height) = size;
+        // Keeps the float inside the region if it fits.
+        let clamp_x = |x: u16| x.min(area.right().saturating_sub(width)).max(area.x);
+        let clamp_y = |y: u16| y.min(area.bottom().saturating_sub(height)).max(area.y);
+        let target = |id: &Cow<'a, str>| objects.bounds(id.clone()).unwrap_or(area);
+
+        match anchor {
+            Anchor::Center => layout::center(area, size),
+            Anchor::At(x, y) => (*x, *y),
+            Anchor::Below(id) => {
+                let t = target(id);
+                let y = if t.bottom().saturating_add(height) <= area.bottom() {
+                    t.bottom()
+                } else {
+                    t.y.saturating_sub(height)
+                };
+                (clamp_x(t.x), clamp_y(y))
+            }
+            Anchor::Above(id) => {
+                let t = target(id);
+                let y = if t.y >= area.y.saturating_add(height) {
+                    t.y - height
+                } else {
+                    t.bottom()
+                };
+                (clamp_x(t.x), clamp_y(y))
+            }
+            Anchor::RightOf(id) => {
+                let t = target(id);
+                let x = if t.right().saturating_add(width) <= area.right() {
+                    t.right()
+                } else {
+                    t.x.saturating_sub(width)
+                };
+                (clamp_x(x), clamp_y(t.y))
+            }
+        }
+    }
+
+    /// Draws the scene: every object that is not a float, then the open floats from the bottom
+    /// to the top. Objects below a modal float are drawn dimmed, and closed floats are not
+    /// drawn.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the scene was drawn.
+    /// - An error if a float does not exist in `objects`, or if writing fails.
+    pub fn draw_in<W: Write>(&self, objects: &NyanObj<'a>, out: &mut W) -> NyanResult<()> {
+        let modal = self.open.iter().rposition(|f| f.modal);
+        let dim = |below_modal: bool| {
+            if below_modal {
+                NyanStyle::new().dim()
+            } else {
+                NyanStyle::new()
+            }
+        };
+
+        for id in objects.ids().filter(|id| !self.is_float(id)) {
+            objects.draw_overlaid_in(out, id, dim(modal.is_some()))?;
+        }
+        for (index, float) in self.open.iter().enumerate() {
+            let below_modal = modal.is_some_and(|m| index < m);
+            objects.draw_overlaid_in(out, &float.id, dim(below_modal))?;
+        }
+        Ok(())
+    }
+
+    /// Draws the scene to stdout. See [`FloatLayer::draw_in`].
+    pub fn draw(&self, objects: &NyanObj<'a>) -> NyanResult<()> {
+        self.draw_in(objects, &mut io::stdout())
+    }
+
+    /// Returns whether the object `id` is a float, open or not.
+    pub fn is_float(&self, id: &str) -> bool {
+        self.floats.contains(id)
+    }
+
+    /// Returns the float an event belongs to.
+    ///
+    /// * While a modal float is open, it gets every key, mouse and paste event.
+    /// * Otherwise, mouse actions on an open float belong to the topmost float under the
+    ///   pointer.
+    ///
+    /// # Returns
+    /// * `Some(id)` of the float that should handle the event.
+    /// * `None` if the normal objects may handle the event.
+    pub fn event_target<U>(&self, objects: &NyanObj<'a>, event: &NyanEvent<U>) -> Option<&str> {
+        let input = matches!(
+            event,
+            NyanEvent::Key(_) | NyanEvent::Mouse(_) | NyanEvent::Paste(_)
+        );
+        if let Some(modal) = self.modal() {
+            return input.then_some(modal);
+        }
+
+        let NyanEvent::Mouse(mouse) = event else {
+            return None;
+        };
+        self.open
+            .iter()
+            .rev()
+            .find(|f| {
+                objects
+                    .bounds(f.id.clone())
+                    .is_some_and(|r| r.contains(mouse.x, mouse.y))
+            })
+            .map(|f| f.id.as_ref())
+    }
+}
+
+impl<'a> Layout<'a> for FloatLayer<'a> {
+    fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        FloatLayer::apply(self, objects, area)
+    }
+}
@@ -0,0 +1,1021 @@
+//! This module provides layout managers, which compute where objects go instead of hand-tuned coordinates.
+//!
+//! A layout splits a region (usually the whole terminal, see `Rect::terminal()`) into smaller `Rect`s and can move the objects of a `NyanObj` to them. Because the positions are computed from the region every time, calling the layout again after the terminal was resized puts everything in the right place.
+//!
+//! # Structs
+//!
+//! - `AspectRatio`: Keeps a region at a fixed visual aspect ratio, accounting for terminal cells being about twice as tall as wide.
+//! - `Dock`: Reserves rows or columns at the edges of a region for persistent bars, and lays out the rest (the content area) with another layout.
+//! - `Grid`: Places objects into the cells of a grid of equally sized rows and columns, with gaps and spans.
+//! - `LayoutManager`: Keeps a set of layouts and applies them again whenever the terminal is resized.
+//! - `Stack`: Lays out objects one after another, left to right (`hstack()`) or top to bottom (`vstack()`).
+//!
+//! # Traits
+//!
+//! - `Layout`: Anything that can place objects inside a region (implemented by `Grid` and `Stack`).
+//!
+//! # Enums
+//!
+//! - `Direction`: The direction a `Stack` lays out its children in.
+//! - `Side`: An edge of a region that a `Dock` reserves space at.
+//! - `Align`: Where something smaller than a region goes inside it: at the start, centered, or at the end.
+//! - `StackSize`: How much space a child of a `Stack` gets: a fixed size, its own size, or a share of the remaining space.
+//!
+//! # Methods
+//!
+//! - `Grid::new(rows, columns)`: Creates a grid.
+//! - `Grid::gap(column_gap, row_gap)`: Sets the space between columns and rows.
+//! - `Grid::place(id, row, column)` / `Grid::place_span(id, row, column, row_span, column_span)`: Assign an object to a cell or a range of cells.
+//! - `Grid::cell(area, row, column)` / `Grid::span(area, row, column, row_span, column_span)`: Return the `Rect` of a cell or range of cells.
+//! - `Grid::layout(area)`: Returns the `Rect` of every placed object.
+//! - `Grid::apply(objects, area)` / `Grid::apply_terminal(objects)`: Move the placed objects to their cells.
+//! - `hstack()` / `vstack()`: Create a horizontal / vertical `Stack`.
+//! - `Stack::spacing(cells)`: Sets the space between two children.
+//! - `Stack::fixed(id, cells)` / `Stack::auto(id)` / `Stack::flex(id, weight)`: Add a child with a fixed size, its own size, or a weighted share of the remaining space.
+//! - `Stack::layout(area, objects)`: Returns the `Rect` of every child.
+//! - `Stack::apply(objects, area)` / `Stack::apply_terminal(objects)`: Move the children to their places.
+//! - `Dock::new()`: Creates a dock without reserved space.
+//! - `Dock::top(id, rows)` / `Dock::bottom(id, rows)` / `Dock::left(id, columns)` / `Dock::right(id, columns)`: Reserve space at an edge for an object.
+//! - `Dock::reserve(side, cells)`: Reserves space at an edge without placing an object there.
+//! - `Dock::content(layout)`: Sets the layout of the content area.
+//! - `Dock::content_area(area)` / `Dock::regions(area)`: Return the content area / the reserved regions.
+//! - `Dock::apply(objects, area)`: Fits the docked objects into their regions and applies the content layout.
+//! - `AspectRatio::new(width, height)`: Creates a region that looks `width` : `height` on screen.
+//! - `AspectRatio::cell_ratio(ratio)` / `AspectRatio::align(horizontal, vertical)`: Set the shape of a cell / where the region goes.
+//! - `AspectRatio::object(id)` / `AspectRatio::content(layout)`: Fit an object / apply a layout to the region.
+//! - `AspectRatio::region(area)`: Returns the largest region with the ratio inside `area`.
+//! - `LayoutManager::new()` / `LayoutManager::layout(layout)`: Create a manager and register layouts that fill the terminal.
+//! - `LayoutManager::relayout(objects, area)`: Applies every layout to `area` and marks the objects dirty.
+//! - `LayoutManager::handle_event(objects, event)`: Relayouts on `NyanEvent::Resize`.
+//! - `align(area, size, horizontal, vertical)` / `center(area, size)`: Return where something of the given size goes inside a region.
+//! - `align_text(text, width, align)`: Pads a line of text to `width` cells, by display width.
+//! - `wrap_text(text, width)`: Word-wraps text into lines of at most `width` cells.
+//! - `clip_text(text, start, width)`: Cuts `width` cells out of a line of text, starting at cell `start`.
+//! - `NyanObj::align_object(id, area, horizontal, vertical)` / `NyanObj::center_object(id, area)`: Move an object inside a region.
+//!
+//! # Example
+//!
+//! ```rust
+//! use nyan::layout::Grid;
+//! use nyan::rect::Rect;
+//!
+//! let grid = Grid::new(2, 2).gap(2, 1);
+//! let area = Rect::new(0, 0, 42, 21);
+//!
+//! assert_eq!(grid.cell(area, 0, 0), Rect::new(0, 0, 20, 10));
+//! assert_eq!(grid.cell(area, 1, 1), Rect::new(22, 11, 20, 10));
+//! assert_eq!(grid.span(area, 0, 0, 1, 2), Rect::new(0, 0, 42, 10));
+//! ```
+
+use std::borrow::Cow;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::errors::NyanResult;
+use crate::event::NyanEvent;
+use crate::nyan_obj::NyanObj;
+use crate::rect::Rect;
+
+/// The `Align` enum tells where something smaller than a region goes inside it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    /// At the left / top.
+    #[default]
+    Start,
+    /// In the middle. An odd leftover cell goes after the content.
+    Center,
+    /// At the right / bottom.
+    End,
+}
+
+impl Align {
+    /// Returns the offset of `size` cells aligned inside `length` cells.
+    pub fn offset(self, length: u16, size: u16) -> u16 {
+        let free = length.saturating_sub(size);
+        match self {
+            Align::Start => 0,
+            Align::Center => free / 2,
+            Align::End => free,
+        }
+    }
+}
+
+/// Returns the top-left corner of something `size` (`(width, height)`) cells large aligned
+/// inside `area`.
+///
+/// Content larger than `area` starts at the area's corner.
+///
+/// # Example
+/// ```rust
+/// use nyan::layout::{align, center, Align};
+/// use nyan::rect::Rect;
+///
+/// let area = Rect::new(0, 0, 80, 24);
+/// assert_eq!(center(area, (20, 4)), (30, 10));
+/// assert_eq!(align(area, (20, 1), Align::End, Align::End), (60, 23));
+/// ```
+pub fn align(area: Rect, size: (u16, u16), horizontal: Align, vertical: Align) -> (u16, u16) {
+    (
+        area.x + horizontal.offset(area.width, size.0),
+        area.y + vertical.offset(area.height, size.1),
+    )
+}
+
+/// Returns the top-left corner of something `size` cells large centered inside `area`.
+pub fn center(area: Rect, size: (u16, u16)) -> (u16, u16) {
+    align(area, size, Align::Center, Align::Center)
+}
+
+/// Pads a single line of `text` with spaces to `width` cells, aligned as given.
+///
+/// The display width is used, so wide characters (e.g. CJK) count as two cells. Text wider
+/// than `width` is returned unchanged.
+///
+/// # Example
+/// ```rust
+/// use nyan::layout::{align_text, Align};
+///
+/// assert_eq!(align_text("nyan", 8, Align::Center), "  nyan  ");
+/// assert_eq!(align_text("ねこ", 6, Align::End), "  ねこ");
+/// ```
+pub fn align_text(text: &str, width: u16, align: Align) -> String {
+    let text_width = text.width().min(u16::MAX as usize) as u16;
+    let before = align.offset(width, text_width) as usize;
+    let after = (width.saturating_sub(text_width) as usize).saturating_sub(before);
+    format!("{}{text}{}", " ".repeat(before), " ".repeat(after))
+}
+
+/// Cuts `width` cells out of a single line of `text`, starting at cell `start`, e.g. to show a
+/// horizontally scrolled line.
+///
+/// The display width is used. A wide character cut in half by either edge is replaced by a
+/// space, so the result is never wider than `width` cells; it is shorter if the line ends
+/// first.
+///
+/// # Example
+/// ```rust
+/// use nyan::layout::clip_text;
+///
+/// assert_eq!(clip_text("nyan cat", 2, 4), "an c");
+/// assert_eq!(clip_text("ねこ", 1, 2), "  ");
+/// ```
+pub fn clip_text(text: &str, start: usize, width: usize) -> String {
+    let end = start.saturating_add(width);
+    let mut clipped = String::new();
+    let mut cell = 0;
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        let next = cell + ch_width;
+        if cell >= end {
+            break;
+        }
+        if cell >= start && next <= end {
+            clipped.push(ch);
+        } else if next > start {
+            // Half of a wide character is visible.
+            clipped.push(' ');
+        }
+        cell = next;
+    }
+    clipped
+}
+
+/// Word-wraps `text` into lines of at most `width` cells.
+///
+/// Lines are broken at whitespace; a word wider than `width` is split between characters.
+/// Existing line breaks are kept, and runs of whitespace inside a line collapse to one space.
+/// The display width is used, so wide characters (e.g. CJK) count as two cells. A `width` of
+/// `0` does not wrap at all.
+///
+/// # Example
+/// ```rust
+/// use nyan::layout::wrap_text;
+///
+/// assert_eq!(wrap_text("the quick brown fox", 10), ["the quick", "brown fox"]);
+/// assert_eq!(wrap_text("nyancat", 4), ["nyan", "cat"]);
+/// assert_eq!(wrap_text("a\n\nb", 10), ["a", "", "b"]);
+/// ```
+pub fn wrap_text(text: &str, width: u16) -> Vec<String> {
+    if width == 0 {
+        return text.lines().map(str::to_string).collect();
+    }
+    let width = width as usize;
+
+    let mut lines = Vec::new();
+    for source in text.lines() {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for word in source.split_whitespace() {
+            let word_width = word.width();
+            if line_width > 0 && line_width + 1 + word_width <= width {
+                line.push(' ');
+                line.push_str(word);
+                line_width += 1 + word_width;
+                continue;
+            }
+            if line_width > 0 {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            if word_width <= width {
+                line.push_str(word);
+                line_width = word_width;
+                continue;
+            }
+            // Splits a word that does not fit on a line of its own.
+            for ch in word.chars() {
+                let ch_width = ch.width().unwrap_or(0);
+                if line_width > 0 && line_width + ch_width > width {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(ch);
+                line_width += ch_width;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Splits `length` cells into `count` tracks separated by `gap` cells.
+///
+/// Returns the `(offset, size)` of every track. Cells that do not divide evenly go to the first
+/// tracks, so the tracks differ in size by at most one cell.
+fn tracks(length: u16, count: u16, gap: u16) -> Vec<(u16, u16)> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let gaps = gap.saturating_mul(count - 1);
+    let available = length.saturating_sub(gaps);
+    let (size, rest) = (available / count, available % count);
+
+    let mut offset = 0u16;
+    (0..count)
+        .map(|i| {
+            let track = size + u16::from(i < rest);
+            let result = (offset, track);
+            offset = offset.saturating_add(track).saturating_add(gap);
+            result
+        })
+        .collect()
+}
+
+/// An object assigned to a range of grid cells.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Placement<'a> {
+    id: Cow<'a, str>,
+    row: u16,
+    column: u16,
+    row_span: u16,
+    column_span: u16,
+}
+
+/// `Grid` places objects into the cells of a grid of equally sized rows and columns.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<'a> {
+    rows: u16,
+    columns: u16,
+    column_gap: u16,
+    row_gap: u16,
+    placements: Vec<Placement<'a>>,
+}
+
+impl<'a> Grid<'a> {
+    /// Creates a grid with `rows` rows and `columns` columns and no gaps.
+    pub fn new(rows: u16, columns: u16) -> Self {
+        Self {
+            rows,
+            columns,
+            column_gap: 0,
+            row_gap: 0,
+            placements: Vec::new(),
+        }
+    }
+
+    /// Sets the number of empty cells between two columns and between two rows.
+    pub fn gap(self, column_gap: u16, row_gap: u16) -> Self {
+        let mut grid = self;
+        grid.column_gap = column_gap;
+        grid.row_gap = row_gap;
+        grid
+    }
+
+    /// Assigns the object `id` to the cell at `row`, `column`.
+    pub fn place<P: Into<Cow<'a, str>>>(self, id: P, row: u16, column: u16) -> Self {
+        self.place_span(id, row, column, 1, 1)
+    }
+
+    /// Assigns the object `id` to `row_span` rows and `column_span` columns, starting at the
+    /// cell at `row`, `column`.
+    pub fn place_span<P: Into<Cow<'a, str>>>(
+        self,
+        id: P,
+        row: u16,
+        column: u16,
+        row_span: u16,
+        column_span: u16,
+    ) -> Self {
+        let mut grid = self;
+        grid.placements.push(Placement {
+            id: id.into(),
+            row,
+            column,
+            row_span,
+            column_span,
+        });
+        grid
+    }
+
+    /// Returns the rectangle of the cell at `row`, `column` when the grid fills `area`.
+    pub fn cell(&self, area: Rect, row: u16, column: u16) -> Rect {
+        self.span(area, row, column, 1, 1)
+    }
+
+    /// Returns the rectangle covering `row_span` rows and `column_span` columns, starting at the
+    /// cell at `row`, `column`, when the grid fills `area`.
+    ///
+    /// The gaps between the covered cells belong to the rectangle. Spans reaching past the last
+    /// row or column are cut off; a span starting outside the grid is empty.
+    pub fn span(&self, area: Rect, row: u16, column: u16, row_span: u16, column_span: u16) -> Rect {
+        let columns = tracks(area.width, self.columns, self.column_gap);
+        let rows = tracks(area.height, self.rows, self.row_gap);
+
+        let range = |tracks: &[(u16, u16)], start: u16, span: u16| {
+            let first = tracks.get(start as usize)?;
+            let end = (start as usize + span.max(1) as usize).min(tracks.len()) - 1;
+            let last = tracks[end];
+            Some((first.0, last.0 + last.1 - first.0))
+        };
+
+        match (
+            range(&columns, column, column_span),
+            range(&rows, row, row_span),
+        ) {
+            (Some((x, width)), Some((y, height))) => {
+                Rect::new(area.x + x, area.y + y, width, height)
+            }
+            _ => Rect::new(area.x, area.y, 0, 0),
+        }
+    }
+
+    /// Returns the rectangle of every placed object when the grid fills `area`, in the order
+    /// the objects were placed.
+    pub fn layout(&self, area: Rect) -> Vec<(&str, Rect)> {
+        self.placements
+            .iter()
+            .map(|p| {
+                let rect = self.span(area, p.row, p.column, p.row_span, p.column_span);
+                (p.id.as_ref(), rect)
+            })
+            .collect()
+    }
+
+    /// Moves every placed object to its cells when the grid fills `area`, resizing resizable
+    /// objects to fill them (see [`NyanObj::fit_object`]).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if all objects were moved.
+    /// - An error of type [`NyanError::ObjectNotFound`](crate::errors::NyanError) if a placed
+    ///   object does not exist in `objects`.
+    pub fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        for p in &self.placements {
+            let rect = self.span(area, p.row, p.column, p.row_span, p.column_span);
+            objects.fit_object(p.id.clone(), rect)?;
+        }
+        Ok(())
+    }
+
+    /// Moves every placed object to its cells when the grid fills the whole terminal.
+    ///
+    /// Call this again after a `NyanEvent::Resize` to follow the new terminal size.
+    pub fn apply_terminal(&self, objects: &mut NyanObj<'a>) -> NyanResult<()> {
+        self.apply(objects, Rect::terminal()?)
+    }
+}
+
+/// The `Direction` enum tells which way a [`Stack`] lays out its children.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left to right.
+    #[default]
+    Horizontal,
+    /// Top to bottom.
+    Vertical,
+}
+
+/// The `StackSize` enum tells how much space a child of a [`Stack`] gets along the stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StackSize {
+    /// Exactly this many cells.
+    Fixed(u16),
+    /// The size of the object itself, including its padding and margin (see
+    /// [`NyanObj::outer_size`]).
+    Auto,
+    /// A share of the space left after all `Fixed` and `Auto` children, in proportion to the
+    /// weights of all `Flex` children.
+    Flex(u16),
+}
+
+/// A child of a [`Stack`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct StackChild<'a> {
+    id: Cow<'a, str>,
+    size: StackSize,
+}
+
+/// `Stack` lays out objects one after another, with optional spacing between them.
+///
+/// Every child fills the stack across its direction (a horizontal stack's children are as
+/// tall as the stack). Along the direction, children take a fixed size, their own size, or a
+/// weighted share of the remaining space, so toolbars and sidebars follow the terminal size
+/// without hand-tuned coordinates.
+///
+/// # Example
+/// ```rust
+/// use nyan::layout::hstack;
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::rect::Rect;
+///
+/// // A 20-cell sidebar, then the content and a preview splitting the rest 2:1.
+/// let stack = hstack()
+///     .spacing(1)
+///     .fixed("sidebar", 20)
+///     .flex("content", 2)
+///     .flex("preview", 1);
+///
+/// let rects = stack.layout(Rect::new(0, 0, 80, 24), &NyanObj::new());
+/// assert_eq!(rects[0].1, Rect::new(0, 0, 20, 24));
+/// assert_eq!(rects[1].1, Rect::new(21, 0, 39, 24));
+/// assert_eq!(rects[2].1, Rect::new(61, 0, 19, 24));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stack<'a> {
+    direction: Direction,
+    spacing: u16,
+    children: Vec<StackChild<'a>>,
+}
+
+/// Creates a horizontal stack, laying out its children left to right.
+pub fn hstack<'a>() -> Stack<'a> {
+    Stack::new(Direction::Horizontal)
+}
+
+/// Creates a vertical stack, laying out its children top to bottom.
+pub fn vstack<'a>() -> Stack<'a> {
+    Stack::new(Direction::Vertical)
+}
+
+impl<'a> Stack<'a> {
+    /// Creates an empty stack laying out its children in `direction`.
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            spacing: 0,
+            children: Vec::new(),
+        }
+    }
+
+    /// Sets the number of empty cells between two children.
+    pub fn spacing(self, cells: u16) -> Self {
+        let mut stack = self;
+        stack.spacing = cells;
+        stack
+    }
+
+    /// Adds a child of the given size.
+    pub fn child<P: Into<Cow<'a, str>>>(self, id: P, size: StackSize) -> Self {
+        let mut stack = self;
+        stack.children.push(StackChild {
+            id: id.into(),
+            size,
+        });
+        stack
+    }
+
+    /// Adds a child that is exactly `cells` cells long.
+    pub fn fixed<P: Into<Cow<'a, str>>>(self, id: P, cells: u16) -> Self {
+        self.child(id, StackSize::Fixed(cells))
+    }
+
+    /// Adds a child that is as long as the object itself.
+    pub fn auto<P: Into<Cow<'a, str>>>(self, id: P) -> Self {
+        self.child(id, StackSize::Auto)
+    }
+
+    /// Adds a child that gets `weight` shares of the remaining space.
+    pub fn flex<P: Into<Cow<'a, str>>>(self, id: P, weight: u16) -> Self {
+        self.child(id, StackSize::Flex(weight))
+    }
+
+    /// Returns the rectangle of every child when the stack fills `area`, in the order the
+    /// children were added.
+    ///
+    /// `objects` is used to measure `Auto` children; children missing from it are zero cells
+    /// long. Children that do not fit anymore are cut off or empty.
+    pub fn layout(&self, area: Rect, objects: &NyanObj<'a>) -> Vec<(&str, Rect)> {
+        let horizontal = self.direction == Direction::Horizontal;
+        let length = if horizontal { area.width } else { area.height };
+
+        let own_size = |child: &StackChild<'a>| match child.size {
+            StackSize::Fixed(cells) => cells,
+            StackSize::Auto => objects
+                .outer_size(child.id.clone())
+                .map(|(w, h)| if horizontal { w } else { h })
+                .unwrap_or(0),
+            StackSize::Flex(_) => 0,
+        };
+
+        let gaps = self
+            .spacing
+            .saturating_mul(self.children.len().saturating_sub(1) as u16);
+        let used = self
+            .children
+            .iter()
+            .fold(gaps, |sum, c| sum.saturating_add(own_size(c)));
+        let free = length.saturating_sub(used) as u32;
+        let weights: u32 = self
+            .children
+            .iter()
+            .map(|c| match c.size {
+                StackSize::Flex(w) => w as u32,
+                _ => 0,
+            })
+            .sum();
+
+        // The cells lost to rounding go to the first flexible children.
+        let mut rest = free
+            - self.children.iter().fold(0, |sum, c| match c.size {
+                StackSize::Flex(w) if weights > 0 => sum + free * w as u32 / weights,
+                _ => sum,
+            });
+
+        let end = length as u32;
+        let mut offset = 0u32;
+        let mut rects = Vec::with_capacity(self.children.len());
+        for child in &self.children {
+            let mut size = match child.size {
+                StackSize::Flex(w) if weights > 0 && w > 0 => {
+                    let extra = u32::from(rest > 0);
+                    rest -= extra;
+                    free * w as u32 / weights + extra
+                }
+                _ => own_size(child) as u32,
+            };
+            let start = offset.min(end);
+            size = size.min(end - start);
+
+            let rect = if horizontal {
+                Rect::new(area.x + start as u16, area.y, size as u16, area.height)
+            } else {
+                Rect::new(area.x, area.y + start as u16, area.width, size as u16)
+            };
+            rects.push((child.id.as_ref(), rect));
+            offset = start + size + self.spacing as u32;
+        }
+        rects
+    }
+
+    /// Moves every child to its place when the stack fills `area`, resizing resizable objects
+    /// to fill it (see [`NyanObj::fit_object`]).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if all children were moved.
+    /// - An error of type [`NyanError::ObjectNotFound`](crate::errors::NyanError) if a child
+    ///   does not exist in `objects`.
+    pub fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        let places: Vec<(Cow<'a, str>, Rect)> = self
+            .layout(area, objects)
+            .into_iter()
+            .zip(&self.children)
+            .map(|((_, rect), child)| (child.id.clone(), rect))
+            .collect();
+        for (id, rect) in places {
+            objects.fit_object(id, rect)?;
+        }
+        Ok(())
+    }
+
+    /// Moves every child to its place when the stack fills the whole terminal.
+    pub fn apply_terminal(&self, objects: &mut NyanObj<'a>) -> NyanResult<()> {
+        self.apply(objects, Rect::terminal()?)
+    }
+}
+
+/// The `Layout` trait is implemented by everything that can place objects inside a region.
+pub trait Layout<'a> {
+    /// Moves (and resizes) the objects this layout manages so they fill `area`.
+    fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()>;
+}
+
+impl<'a> Layout<'a> for Grid<'a> {
+    fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        Grid::apply(self, objects, area)
+    }
+}
+
+impl<'a> Layout<'a> for Stack<'a> {
+    fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        Stack::apply(self, objects, area)
+    }
+}
+
+/// `LayoutManager` keeps a set of layouts that fill the terminal and applies them again
+/// whenever the terminal is resized.
+///
+/// # Example
+/// ```ignore
+/// let mut layouts = LayoutManager::new().layout(grid);
+/// layouts.relayout(&mut obj, Rect::terminal()?)?;
+///
+/// loop {
+///     let event = NyanEvent::get_event()?;
+///     layouts.handle_event(&mut obj, &event)?; // the grid follows the new terminal size
+///     if obj.is_dirty() {
+///         app.draw_with(|app| obj.draw_all_in(app).unwrap())?;
+///         obj.clear_dirty();
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct LayoutManager<'a> {
+    layouts: Vec<Box<dyn Layout<'a> + 'a>>,
+    area: Rect,
+}
+
+impl<'a> std::fmt::Debug for LayoutManager<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LayoutManager")
+            .field("layouts", &self.layouts.len())
+            .field("area", &self.area)
+            .finish()
+    }
+}
+
+impl<'a> LayoutManager<'a> {
+    /// Creates a manager without layouts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layout. Layouts are applied in the order they were added.
+    pub fn layout<L: Layout<'a> + 'a>(self, layout: L) -> Self {
+        let mut manager = self;
+        manager.layouts.push(Box::new(layout));
+        manager
+    }
+
+    /// Returns the region the layouts were last applied to.
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// Applies every layout to `area` and marks the objects dirty, so they are drawn again.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if all layouts were applied.
+    /// - The first error of a layout, e.g. if it refers to an object that does not exist.
+    pub fn relayout(&mut self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        self.area = area;
+        for layout in &self.layouts {
+            layout.apply(objects, area)?;
+        }
+        objects.mark_dirty();
+        Ok(())
+    }
+
+    /// Applies every layout to the new terminal size if `event` is a `NyanEvent::Resize`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` if the objects were laid out again.
+    /// - `Ok(false)` for all other events.
+    /// - The first error of a layout.
+    pub fn handle_event<U>(
+        &mut self,
+        objects: &mut NyanObj<'a>,
+        event: &NyanEvent<U>,
+    ) -> NyanResult<bool> {
+        if let NyanEvent::Resize(width, height) = event {
+            self.relayout(objects, Rect::new(0, 0, *width, *height))?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
+
+/// The `Side` enum names an edge of a region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Space reserved at an edge of a [`Dock`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Docked<'a> {
+    side: Side,
+    cells: u16,
+    id: Option<Cow<'a, str>>,
+}
+
+/// `Dock` reserves rows and columns at the edges of a region for persistent bars, such as a
+/// header and a status bar, and exposes what is left as the content area.
+///
+/// Reserved space is taken from the edges in the order it was added, so the first reservation
+/// spans the whole region and later ones only the rest. The content area is laid out with
+/// another layout, so content never draws over the bars.
+///
+/// # Example
+/// ```rust
+/// use nyan::layout::{vstack, Dock};
+/// use nyan::rect::Rect;
+///
+/// let dock = Dock::new()
+///     .top("header", 1)
+///     .bottom("status", 1)
+///     .left("sidebar", 20)
+///     .content(vstack().flex("editor", 1));
+///
+/// let area = Rect::new(0, 0, 80, 24);
+/// assert_eq!(dock.content_area(area), Rect::new(20, 1, 60, 22));
+///
+/// let regions = dock.regions(area);
+/// assert_eq!(regions[1], (Some("status"), Rect::new(0, 23, 80, 1)));
+/// assert_eq!(regions[2], (Some("sidebar"), Rect::new(0, 1, 20, 22)));
+/// ```
+#[derive(Default)]
+pub struct Dock<'a> {
+    docked: Vec<Docked<'a>>,
+    content: Option<Box<dyn Layout<'a> + 'a>>,
+}
+
+impl<'a> std::fmt::Debug for Dock<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dock")
+            .field("docked", &self.docked)
+            .field("content", &self.content.is_some())
+            .finish()
+    }
+}
+
+impl<'a> Dock<'a> {
+    /// Creates a dock without reserved space; the content area is the whole region.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(self, side: Side, cells: u16, id: Option<Cow<'a, str>>) -> Self {
+        let mut dock = self;
+        dock.docked.push(Docked { side, cells, id });
+        dock
+    }
+
+    /// Reserves `rows` rows at the top for the object `id`.
+    pub fn top<P: Into<Cow<'a, str>>>(self, id: P, rows: u16) -> Self {
+        self.push(Side::Top, rows, Some(id.into()))
+    }
+
+    /// Reserves `rows` rows at the bottom for the object `id`.
+    pub fn bottom<P: Into<Cow<'a, str>>>(self, id: P, rows: u16) -> Self {
+        self.push(Side::Bottom, rows, Some(id.into()))
+    }
+
+    /// Reserves `columns` columns on the left for the object `id`.
+    pub fn left<P: Into<Cow<'a, str>>>(self, id: P, columns: u16) -> Self {
+        self.push(Side::Left, columns, Some(id.into()))
+    }
+
+    /// Reserves `columns` columns on the right for the object `id`.
+    pub fn right<P: Into<Cow<'a, str>>>(self, id: P, columns: u16) -> Self {
+        self.push(Side::Right, columns, Some(id.into()))
+    }
+
+    /// Reserves `cells` rows or columns at `side` without placing an object there, e.g. for
+    /// something drawn by hand.
+    pub fn reserve(self, side: Side, cells: u16) -> Self {
+        self.push(side, cells, None)
+    }
+
+    /// Sets the layout of the content area.
+    pub fn content<L: Layout<'a> + 'a>(self, layout: L) -> Self {
+        let mut dock = self;
+        dock.content = Some(Box::new(layout));
+        dock
+    }
+
+    /// Returns every reserved region when the dock fills `area`, in the order they were added,
+    /// and the content area that is left.
+    fn split(&self, area: Rect) -> (Vec<(Option<&str>, Rect)>, Rect) {
+        let mut rest = area;
+        let regions = self
+            .docked
+            .iter()
+            .map(|d| {
+                let region = match d.side {
+                    Side::Top => {
+                        let rows = d.cells.min(rest.height);
+                        rest.y += rows;
+                        rest.height -= rows;
+                        Rect::new(rest.x, rest.y - rows, rest.width, rows)
+                    }
+                    Side::Bottom => {
+                        let rows = d.cells.min(rest.height);
+                        rest.height -= rows;
+                        Rect::new(rest.x, rest.bottom(), rest.width, rows)
+                    }
+                    Side::Left => {
+                        let columns = d.cells.min(rest.width);
+                        rest.x += columns;
+                        rest.width -= columns;
+                        Rect::new(rest.x - columns, rest.y, columns, rest.height)
+                    }
+                    Side::Right => {
+                        let columns = d.cells.min(rest.width);
+                        rest.width -= columns;
+                        Rect::new(rest.right(), rest.y, columns, rest.height)
+                    }
+                };
+                (d.id.as_deref(), region)
+            })
+            .collect();
+        (regions, rest)
+    }
+
+    /// Returns every reserved region when the dock fills `area`, in the order they were added.
+    pub fn regions(&self, area: Rect) -> Vec<(Option<&str>, Rect)> {
+        self.split(area).0
+    }
+
+    /// Returns the content area: what is left of `area` after all reservations.
+    pub fn content_area(&self, area: Rect) -> Rect {
+        self.split(area).1
+    }
+
+    /// Fits every docked object into its region (see [`NyanObj::fit_object`]) and applies the
+    /// content layout to the content area.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if everything was placed.
+    /// - An error of type [`NyanError::ObjectNotFound`](crate::errors::NyanError) if a docked
+    ///   object does not exist in `objects`, or the first error of the content layout.
+    pub fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        let (_, content) = self.split(area);
+        for (docked, (_, region)) in self.docked.iter().zip(self.regions(area)) {
+            if let Some(id) = &docked.id {
+                objects.fit_object(id.clone(), region)?;
+            }
+        }
+        if let Some(layout) = &self.content {
+            layout.apply(objects, content)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Layout<'a> for Dock<'a> {
+    fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        Dock::apply(self, objects, area)
+    }
+}
+
+/// `AspectRatio` keeps a region at a fixed visual aspect ratio inside the space it is given,
+/// e.g. for game boards and images.
+///
+/// Terminal cells are about twice as tall as they are wide, so a region that looks square
+/// is twice as many columns wide as it is rows tall. The cell ratio can be changed for fonts
+/// that differ. The region is the largest one with the ratio that fits, aligned inside the
+/// available space (centered by default), and can hold an object or another layout.
+///
+/// # Example
+/// ```rust
+/// use nyan::layout::AspectRatio;
+/// use nyan::rect::Rect;
+///
+/// let board = AspectRatio::new(1, 1).object("board");
+///
+/// assert_eq!(board.region(Rect::new(0, 0, 80, 24)), Rect::new(16, 0, 48, 24));
+/// assert_eq!(board.region(Rect::new(0, 0, 40, 24)), Rect::new(0, 2, 40, 20));
+/// ```
+pub struct AspectRatio<'a> {
+    ratio: f32,
+    cell_ratio: f32,
+    horizontal: Align,
+    vertical: Align,
+    id: Option<Cow<'a, str>>,
+    content: Option<Box<dyn Layout<'a> + 'a>>,
+}
+
+impl<'a> std::fmt::Debug for AspectRatio<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AspectRatio")
+            .field("ratio", &self.ratio)
+            .field("cell_ratio", &self.cell_ratio)
+            .field("horizontal", &self.horizontal)
+            .field("vertical", &self.vertical)
+            .field("id", &self.id)
+            .field("content", &self.content.is_some())
+            .finish()
+    }
+}
+
+impl<'a> AspectRatio<'a> {
+    /// Creates a region that looks `width` : `height` on screen, e.g. `(16, 9)`.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            ratio: width as f32 / height.max(1) as f32,
+            cell_ratio: 2.0,
+            horizontal: Align::Center,
+            vertical: Align::Center,
+            id: None,
+            content: None,
+        }
+    }
+
+    /// Sets how many times taller than wide a terminal cell is (`2.0` by default).
+    pub fn cell_ratio(self, ratio: f32) -> Self {
+        let mut aspect = self;
+        aspect.cell_ratio = ratio;
+        aspect
+    }
+
+    /// Sets where the region goes inside the available space.
+    pub fn align(self, horizontal: Align, vertical: Align) -> Self {
+        let mut aspect = self;
+        aspect.horizontal = horizontal;
+        aspect.vertical = vertical;
+        aspect
+    }
+
+    /// Fits the object `id` into the region.
+    pub fn object<P: Into<Cow<'a, str>>>(self, id: P) -> Self {
+        let mut aspect = self;
+        aspect.id = Some(id.into());
+        aspect
+    }
+
+    /// Lays out the region with another layout.
+    pub fn content<L: Layout<'a> + 'a>(self, layout: L) -> Self {
+        let mut aspect = self;
+        aspect.content = Some(Box::new(layout));
+        aspect
+    }
+
+    /// Returns the largest region with the aspect ratio that fits into `area`.
+    pub fn region(&self, area: Rect) -> Rect {
+        // The number of columns per row for the ratio.
+        let columns_per_row = self.ratio * self.cell_ratio;
+        if !(columns_per_row.is_finite() && columns_per_row > 0.0) {
+            return Rect::new(area.x, area.y, 0, 0);
+        }
+
+        let mut size = (
+            (area.height as f32 * columns_per_row).round(),
+            area.height as f32,
+        );
+        if size.0 > area.width as f32 {
+            size = (
+                area.width as f32,
+                (area.width as f32 / columns_per_row).round(),
+            );
+        }
+        let size = (
+            (size.0 as u16).min(area.width),
+            (size.1 as u16).min(area.height),
+        );
+        let (x, y) = align(area, size, self.horizontal, self.vertical);
+        Rect::new(x, y, size.0, size.1)
+    }
+
+    /// Fits the object into the region (see [`NyanObj::fit_object`]) and applies the content
+    /// layout to it.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if everything was placed.
+    /// - An error of type [`NyanError::ObjectNotFound`](crate::errors::NyanError) if the object
+    ///   does not exist in `objects`, or the first error of the content layout.
+    pub fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        let region = self.region(area);
+        if let Some(id) = &self.id {
+            objects.fit_object(id.clone(), region)?;
+        }
+        if let Some(layout) = &self.content {
+            layout.apply(objects, region)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Layout<'a> for AspectRatio<'a> {
+    fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        AspectRatio::apply(self, objects, area)
+    }
+}
@@ -64,12 +64,80 @@
 //!
 //! ```
 
+pub mod animation;
+mod ansi;
 pub mod app;
+pub mod backend;
+pub mod background;
+pub mod border;
+pub mod buffer;
+pub mod button;
+pub mod clipboard;
+pub mod clock;
+pub mod collision;
+pub mod color;
+#[cfg(feature = "toml")]
+pub mod config;
 pub mod cursor;
+pub mod dialog;
+pub mod drag;
+pub mod easing;
 pub mod errors;
+pub mod event;
+pub mod figlet;
+pub mod file_picker;
+pub mod focus;
+pub mod form;
+pub mod gif;
+pub mod gradient;
 pub mod input;
+pub mod intern;
+pub mod keybindings;
+pub mod layer;
+pub mod layout;
+pub mod list_view;
+#[cfg(any(feature = "toml", feature = "yaml"))]
+pub mod loader;
+pub mod log_view;
+#[cfg(feature = "log")]
+pub mod logger;
+pub mod menu;
+pub mod notification;
 pub mod nyan_obj;
 pub mod objects;
+pub mod palette;
+pub mod particle;
+pub mod path;
+pub mod physics;
+pub mod plugin;
+pub mod profiler;
+pub mod rect;
+pub mod remote;
+pub mod renderer;
+pub mod scene;
+pub mod scheduler;
+#[cfg(feature = "rhai")]
+pub mod script;
+pub mod scrollbar;
+pub mod select;
+pub mod split;
+pub mod sprite;
+pub mod stats;
+pub mod status_bar;
+pub mod style;
+pub mod table_view;
+pub mod tabs;
+pub mod testing;
+pub mod text_input;
+pub mod theme;
+pub mod tilemap;
+pub mod timeline;
+pub mod tooltip;
+pub mod typewriter;
+pub mod vim;
+pub mod widget;
+#[cfg(feature = "wasm")]
+pub mod xterm;
 
 #[cfg(test)]
 mod tests {
@@ -82,6 +150,7 @@ mod tests {
     };
 
     #[test]
+    #[allow(unused_variables)]
     fn test() {
         let mut nyan = App::new(60)
             .clear()
@@ -127,4 +196,3512 @@ mod tests {
 
         nyan.exit().unwrap();
     }
+
+    #[test]
+    fn key_event_keeps_original_char() {
+        use crate::event::NyanEvent;
+        use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+        let upper: NyanInput = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::NONE).into();
+        assert_eq!(upper, NyanInput::Shift(&NyanInput::Key(NyanKey::A)));
+        assert_eq!(upper.to_char(), Some('A'));
+
+        let kana: NyanInput = KeyEvent::new(KeyCode::Char('あ'), KeyModifiers::NONE).into();
+        assert_eq!(kana, NyanInput::Key(NyanKey::OtherKey('あ')));
+        assert_eq!(kana.to_char(), Some('あ'));
+
+        let event: NyanEvent = Event::Resize(80, 24).into();
+        assert_eq!(event, NyanEvent::Resize(80, 24));
+    }
+
+    #[test]
+    fn key_events_keep_shift_with_ctrl_and_alt() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let convert = |ch, modifiers| -> NyanInput<'static> {
+            KeyEvent::new(KeyCode::Char(ch), modifiers).into()
+        };
+
+        assert_eq!(
+            convert('s', KeyModifiers::CONTROL),
+            NyanInput::Ctrl(NyanKey::S)
+        );
+        assert_eq!(
+            convert('S', KeyModifiers::CONTROL),
+            NyanInput::Shift(&NyanInput::Ctrl(NyanKey::S))
+        );
+        assert_eq!(
+            convert('s', KeyModifiers::CONTROL | KeyModifiers::SHIFT),
+            NyanInput::Shift(&NyanInput::Ctrl(NyanKey::S))
+        );
+        assert_eq!(
+            convert('X', KeyModifiers::ALT),
+            NyanInput::Shift(&NyanInput::Alt(NyanKey::X))
+        );
+        assert_eq!(
+            convert('q', KeyModifiers::SHIFT),
+            NyanInput::Shift(&NyanInput::Key(NyanKey::Q))
+        );
+        // Shifted symbols are told apart by their char.
+        assert_eq!(
+            convert('!', KeyModifiers::SHIFT),
+            NyanInput::Key(NyanKey::OtherKey('!'))
+        );
+
+        // Shifted letters point to static inputs instead of allocating one per key press.
+        let (NyanInput::Shift(first), NyanInput::Shift(second)) = (
+            convert('A', KeyModifiers::NONE),
+            convert('A', KeyModifiers::SHIFT),
+        ) else {
+            panic!("uppercase letters are Shift inputs");
+        };
+        assert!(std::ptr::eq(first, second));
+        assert_eq!(NyanInput::Tab.shifted(), None);
+    }
+
+    #[test]
+    fn hit_test_finds_topmost_object() {
+        let mut obj = NyanObj::new();
+        obj.add_object("back", Objects::new_text("0123456789"), (0, 0));
+        obj.add_object("front", Objects::new_text("abc\nde"), (2, 0));
+        obj.add_object("air", Objects::Air, (5, 1));
+
+        assert_eq!(obj.hit_test(3, 0), Some("front"));
+        assert_eq!(obj.hit_test(8, 0), Some("back"));
+        assert_eq!(obj.hit_test(5, 1), None);
+        assert_eq!(obj.hit_test(0, 2), None);
+    }
+
+    #[test]
+    fn virtual_cursor_follows_output() {
+        use crate::ansi::{AnsiParser, VirtualCursor};
+
+        let mut parser = AnsiParser::new();
+        let mut vcursor = VirtualCursor::default();
+        let mut out = Vec::new();
+
+        Cursor::Move(4, 2).queue(&mut out).unwrap();
+        out.extend_from_slice("ab猫".as_bytes());
+        Cursor::SavePosition.queue(&mut out).unwrap();
+        Cursor::MoveToNextLine(3).queue(&mut out).unwrap();
+        Cursor::MoveRight(1).queue(&mut out).unwrap();
+        assert!(out.len() > 9);
+
+        // Feed the output in two halves to split escape sequences and UTF-8 characters.
+        let (head, tail) = out.split_at(9);
+        for chunk in [head, tail] {
+            parser.feed(chunk, |token| vcursor.apply(&token, true));
+        }
+        assert_eq!((vcursor.x, vcursor.y), (1, 5));
+
+        Cursor::RestorePosition.queue(&mut out).unwrap();
+        parser.feed(&out[out.len() - 2..], |token| vcursor.apply(&token, true));
+        assert_eq!((vcursor.x, vcursor.y), (8, 2));
+    }
+
+    #[test]
+    fn styled_text_writes_each_span_with_its_style() {
+        use crate::color::{self, ColorSupport, NyanColor};
+        use crate::objects::Objects;
+        use crate::style::NyanStyle;
+
+        color::set_color_support(ColorSupport::TrueColor);
+
+        let text = Objects::new_styled_text(vec![
+            (NyanStyle::new().fg(NyanColor::Red), "error"),
+            (NyanStyle::new(), ": "),
+            (NyanStyle::new().bold(), "disk full"),
+        ]);
+
+        let mut out = Vec::new();
+        text.draw(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[31merror\x1b[0m: \x1b[1mdisk full\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn fill_paints_the_whole_bounding_box() {
+        use crate::color::{self, ColorSupport, NyanColor};
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+
+        color::set_color_support(ColorSupport::TrueColor);
+
+        let mut obj = NyanObj::new();
+        obj.add_object("row", Objects::new_list(["File", "Quit"], 2), (0, 0));
+        obj.set_fill("row", Some(NyanColor::Blue)).unwrap();
+        assert_eq!(obj.fill("row"), Some(Some(NyanColor::Blue)));
+
+        let mut out = Vec::new();
+        obj.draw_object_in(&mut out, "row").unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[1;1H\x1b7\x1b[44m    \x1b[4D\x1b[1B    \x1b[0m\x1b8"));
+        assert!(out.contains("\x1b[44mFile"));
+    }
+
+    #[test]
+    fn modal_dims_everything_else() {
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("background", Objects::new_text("menu"), (0, 0));
+        obj.add_object("dialog", Objects::new_text("Quit?"), (2, 1));
+        obj.add_object("footer", Objects::new_text("help"), (0, 3));
+
+        let mut out = Vec::new();
+        obj.draw_modal_in(&mut out, "dialog").unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains("\x1b[2mmenu"));
+        assert!(out.contains("\x1b[2mhelp"));
+        assert!(out.ends_with("\x1b[2;3HQuit?\n"));
+        assert!(obj.draw_modal_in(&mut Vec::new(), "missing").is_err());
+    }
+
+    #[test]
+    fn grid_moves_objects_to_their_cells() {
+        use crate::layout::Grid;
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::rect::Rect;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("cpu", Objects::new_text("CPU"), (0, 0));
+        obj.add_object("mem", Objects::new_text("MEM"), (0, 0));
+        obj.add_object("log", Objects::new_text("LOG"), (0, 0));
+
+        let grid = Grid::new(2, 3)
+            .gap(1, 0)
+            .place("cpu", 0, 0)
+            .place("mem", 0, 2)
+            .place_span("log", 1, 0, 1, 3);
+        grid.apply(&mut obj, Rect::new(0, 0, 80, 24)).unwrap();
+
+        assert_eq!(obj.coordinate("cpu"), Some((0, 0)));
+        assert_eq!(obj.coordinate("mem"), Some((54, 0)));
+        assert_eq!(obj.coordinate("log"), Some((0, 12)));
+        assert_eq!(
+            grid.layout(Rect::new(0, 0, 80, 24))[2].1,
+            Rect::new(0, 12, 80, 12)
+        );
+    }
+
+    #[test]
+    fn stacks_share_space_and_cut_off_what_does_not_fit() {
+        use crate::errors::NyanError;
+        use crate::layout::{hstack, vstack};
+        use crate::rect::Rect;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("title", Objects::new_text("nyan"), (0, 0));
+
+        // Fixed and auto children first; the cell lost to rounding goes to the first flex.
+        let stack = vstack()
+            .spacing(1)
+            .fixed("header", 1)
+            .auto("title")
+            .flex("a", 1)
+            .flex("b", 1);
+        assert_eq!(
+            stack.layout(Rect::new(0, 0, 10, 10), &obj),
+            vec![
+                ("header", Rect::new(0, 0, 10, 1)),
+                ("title", Rect::new(0, 2, 10, 1)),
+                ("a", Rect::new(0, 4, 10, 3)),
+                ("b", Rect::new(0, 8, 10, 2)),
+            ]
+        );
+
+        // Children past the end are cut off, then empty, inside an offset area.
+        let stack = hstack().fixed("a", 6).fixed("b", 6).fixed("c", 3);
+        assert_eq!(
+            stack.layout(Rect::new(2, 1, 8, 2), &obj),
+            vec![
+                ("a", Rect::new(2, 1, 6, 2)),
+                ("b", Rect::new(8, 1, 2, 2)),
+                ("c", Rect::new(10, 1, 0, 2)),
+            ]
+        );
+
+        // Zero areas, zero weights and missing auto children take no space.
+        let stack = hstack().spacing(2).flex("a", 1).flex("b", 3);
+        assert_eq!(
+            stack.layout(Rect::new(3, 3, 0, 0), &obj),
+            vec![("a", Rect::new(3, 3, 0, 0)), ("b", Rect::new(3, 3, 0, 0))]
+        );
+        let stack = hstack().flex("a", 0).auto("missing").fixed("c", 2);
+        assert_eq!(
+            stack.layout(Rect::new(0, 0, 10, 1), &obj),
+            vec![
+                ("a", Rect::new(0, 0, 0, 1)),
+                ("missing", Rect::new(0, 0, 0, 1)),
+                ("c", Rect::new(0, 0, 2, 1)),
+            ]
+        );
+
+        // Huge fixed sizes and spacing do not overflow.
+        let stack = vstack().spacing(u16::MAX).fixed("a", u16::MAX).flex("b", 1);
+        assert_eq!(
+            stack.layout(Rect::new(0, 0, 4, 4), &obj),
+            vec![("a", Rect::new(0, 0, 4, 4)), ("b", Rect::new(0, 4, 4, 0))]
+        );
+
+        let missing = vstack()
+            .fixed("menu", 1)
+            .apply(&mut obj, Rect::new(0, 0, 4, 4));
+        assert!(matches!(missing, Err(NyanError::ObjectNotFound(id)) if id == "menu"));
+    }
+
+    #[test]
+    fn dock_reserves_edges_in_order_and_lays_out_the_rest() {
+        use crate::errors::NyanError;
+        use crate::layout::{vstack, Dock, Side};
+        use crate::rect::Rect;
+
+        // The first reservation spans the whole region, later ones only what is left.
+        let dock = Dock::new().left("side", 10).top("header", 2);
+        let area = Rect::new(0, 0, 40, 10);
+        assert_eq!(
+            dock.regions(area),
+            vec![
+                (Some("side"), Rect::new(0, 0, 10, 10)),
+                (Some("header"), Rect::new(10, 0, 30, 2)),
+            ]
+        );
+        assert_eq!(dock.content_area(area), Rect::new(10, 2, 30, 8));
+
+        // Space reserved without an object, on the right of an offset region.
+        let dock = Dock::new().reserve(Side::Right, 3).right("scroll", 1);
+        let area = Rect::new(5, 5, 20, 4);
+        assert_eq!(
+            dock.regions(area),
+            vec![
+                (None, Rect::new(22, 5, 3, 4)),
+                (Some("scroll"), Rect::new(21, 5, 1, 4)),
+            ]
+        );
+        assert_eq!(dock.content_area(area), Rect::new(5, 5, 16, 4));
+
+        // Reservations larger than the region get what is left, down to nothing.
+        let dock = Dock::new()
+            .top("header", 6)
+            .bottom("status", 6)
+            .left("side", 3);
+        let area = Rect::new(0, 0, 20, 10);
+        assert_eq!(
+            dock.regions(area),
+            vec![
+                (Some("header"), Rect::new(0, 0, 20, 6)),
+                (Some("status"), Rect::new(0, 6, 20, 4)),
+                (Some("side"), Rect::new(0, 6, 3, 0)),
+            ]
+        );
+        assert_eq!(dock.content_area(area), Rect::new(3, 6, 17, 0));
+        let empty = Rect::new(2, 2, 0, 0);
+        assert_eq!(
+            dock.regions(empty)[1],
+            (Some("status"), Rect::new(2, 2, 0, 0))
+        );
+        assert_eq!(dock.content_area(empty), empty);
+        assert_eq!(Dock::new().content_area(area), area);
+
+        // Docked objects fill their regions and the content layout the rest.
+        let mut obj = NyanObj::new();
+        obj.add_object("header", Objects::new_panel(1, 1, None), (0, 0));
+        obj.add_object("status", Objects::new_panel(1, 1, None), (0, 0));
+        obj.add_object("body", Objects::new_panel(1, 1, None), (0, 0));
+        let dock = Dock::new()
+            .top("header", 1)
+            .bottom("status", 1)
+            .content(vstack().flex("body", 1));
+        dock.apply(&mut obj, Rect::new(0, 0, 30, 8)).unwrap();
+        assert_eq!(obj.bounds("header"), Some(Rect::new(0, 0, 30, 1)));
+        assert_eq!(obj.bounds("status"), Some(Rect::new(0, 7, 30, 1)));
+        assert_eq!(obj.bounds("body"), Some(Rect::new(0, 1, 30, 6)));
+
+        let missing = Dock::new()
+            .top("menu", 1)
+            .apply(&mut obj, Rect::new(0, 0, 30, 8));
+        assert!(matches!(missing, Err(NyanError::ObjectNotFound(id)) if id == "menu"));
+    }
+
+    #[test]
+    fn aspect_ratio_fits_aligns_and_handles_degenerate_areas() {
+        use crate::errors::NyanError;
+        use crate::layout::{hstack, Align, AspectRatio};
+        use crate::rect::Rect;
+
+        // Width-bound and height-bound regions, aligned to the edges.
+        let square = AspectRatio::new(1, 1).align(Align::Start, Align::End);
+        assert_eq!(
+            square.region(Rect::new(0, 0, 80, 24)),
+            Rect::new(0, 0, 48, 24)
+        );
+        assert_eq!(
+            square.region(Rect::new(0, 0, 40, 24)),
+            Rect::new(0, 4, 40, 20)
+        );
+        assert_eq!(
+            square.region(Rect::new(3, 2, 40, 24)),
+            Rect::new(3, 6, 40, 20)
+        );
+
+        // Square cells, e.g. for a font that is as wide as it is tall.
+        let wide = AspectRatio::new(16, 9).cell_ratio(1.0);
+        assert_eq!(
+            wide.region(Rect::new(0, 0, 32, 32)),
+            Rect::new(0, 7, 32, 18)
+        );
+
+        // Empty areas and ratios without a size give empty regions.
+        assert_eq!(
+            AspectRatio::new(1, 1).region(Rect::new(5, 5, 0, 0)),
+            Rect::new(5, 5, 0, 0)
+        );
+        assert_eq!(
+            AspectRatio::new(0, 1).region(Rect::new(5, 5, 10, 10)),
+            Rect::new(5, 5, 0, 0)
+        );
+        assert_eq!(
+            AspectRatio::new(1, 1)
+                .cell_ratio(f32::NAN)
+                .region(Rect::new(5, 5, 10, 10)),
+            Rect::new(5, 5, 0, 0)
+        );
+        // A zero height counts as one, and extreme ratios stay inside the area.
+        assert_eq!(
+            AspectRatio::new(2, 0).region(Rect::new(0, 0, 80, 24)),
+            Rect::new(0, 2, 80, 20)
+        );
+        assert_eq!(
+            AspectRatio::new(u16::MAX, 1).region(Rect::new(0, 0, 80, 24)),
+            Rect::new(0, 12, 80, 0)
+        );
+        assert_eq!(
+            AspectRatio::new(1, u16::MAX).region(Rect::new(0, 0, 80, 24)),
+            Rect::new(40, 0, 0, 24)
+        );
+
+        // The object fills the region, which the content layout splits.
+        let mut obj = NyanObj::new();
+        obj.add_object("board", Objects::new_panel(1, 1, None), (0, 0));
+        obj.add_object("left", Objects::new_panel(1, 1, None), (0, 0));
+        obj.add_object("right", Objects::new_panel(1, 1, None), (0, 0));
+        let board = AspectRatio::new(1, 1)
+            .object("board")
+            .content(hstack().flex("left", 1).flex("right", 1));
+        board.apply(&mut obj, Rect::new(0, 0, 80, 24)).unwrap();
+        assert_eq!(obj.bounds("board"), Some(Rect::new(16, 0, 48, 24)));
+        assert_eq!(obj.bounds("left"), Some(Rect::new(16, 0, 24, 24)));
+        assert_eq!(obj.bounds("right"), Some(Rect::new(40, 0, 24, 24)));
+
+        let missing = AspectRatio::new(1, 1)
+            .object("image")
+            .apply(&mut obj, Rect::new(0, 0, 80, 24));
+        assert!(matches!(missing, Err(NyanError::ObjectNotFound(id)) if id == "image"));
+    }
+
+    #[test]
+    fn center_object_uses_display_width() {
+        use crate::layout::Align;
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::rect::Rect;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("title", Objects::new_text("にゃん"), (0, 0));
+
+        obj.center_object("title", Rect::new(10, 0, 20, 5)).unwrap();
+        assert_eq!(obj.coordinate("title"), Some((17, 2)));
+
+        obj.align_object("title", Rect::new(0, 0, 20, 5), Align::End, Align::Start)
+            .unwrap();
+        assert_eq!(obj.coordinate("title"), Some((14, 0)));
+    }
+
+    #[test]
+    fn padding_and_margin_offset_the_object() {
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::rect::{Edges, Rect};
+
+        let mut obj = NyanObj::new();
+        obj.add_object("button", Objects::new_text("OK"), (10, 5));
+        obj.set_margin("button", Edges::new(1, 0, 0, 2)).unwrap();
+        obj.set_padding("button", Edges::symmetric(0, 1)).unwrap();
+
+        assert_eq!(obj.bounds("button"), Some(Rect::new(12, 6, 4, 1)));
+        assert_eq!(obj.outer_size("button"), Some((6, 2)));
+        assert_eq!(obj.hit_test(12, 6), Some("button"));
+        assert_eq!(obj.hit_test(10, 5), None);
+
+        let mut out = Vec::new();
+        obj.draw_object_in(&mut out, "button").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[6;11H\x1b[2C\x1b[1B\x1b[1COK\n"
+        );
+    }
+
+    #[test]
+    fn layout_manager_follows_resize() {
+        use crate::event::NyanEvent;
+        use crate::layout::{vstack, LayoutManager};
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("header", Objects::new_text("nyan"), (0, 0));
+        obj.add_object("body", Objects::new_panel(1, 1, None), (0, 0));
+        obj.clear_dirty();
+
+        let mut layouts = LayoutManager::new().layout(vstack().fixed("header", 1).flex("body", 1));
+        let resized = layouts
+            .handle_event(&mut obj, &NyanEvent::<()>::Resize(40, 10))
+            .unwrap();
+
+        assert!(resized);
+        assert!(obj.is_dirty());
+        assert_eq!(obj.coordinate("body"), Some((0, 1)));
+        assert_eq!(
+            obj.bounds("body").map(|r| (r.width, r.height)),
+            Some((40, 9))
+        );
+        assert!(!layouts
+            .handle_event(&mut obj, &NyanEvent::<()>::Tick)
+            .unwrap());
+    }
+
+    #[test]
+    fn paragraph_reflows_to_its_region() {
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::rect::Rect;
+
+        let mut obj = NyanObj::new();
+        let text = "nyan cat flies through space";
+        obj.add_object("text", Objects::new_paragraph(text, 0), (0, 0));
+
+        obj.fit_object("text", Rect::new(2, 1, 10, 5)).unwrap();
+        assert_eq!(obj.bounds("text"), Some(Rect::new(2, 1, 10, 4)));
+
+        obj.fit_object("text", Rect::new(2, 1, 20, 5)).unwrap();
+        assert_eq!(obj.bounds("text"), Some(Rect::new(2, 1, 20, 2)));
+
+        let mut out = Vec::new();
+        obj.draw_object_in(&mut out, "text").unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("nyan cat flies      "));
+        assert!(out.contains("through space       "));
+    }
+
+    #[test]
+    fn scroll_view_draws_scrollbars_and_follows_the_wheel() {
+        use crate::event::{NyanMouse, NyanMouseKind};
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+
+        let text = "0123456789\nabc\nde\nf\ng\nh";
+        let mut obj = NyanObj::new();
+        obj.add_object("view", Objects::new_scroll_view(text, 5, 4), (0, 0));
+
+        let mut out = Vec::new();
+        obj.draw_object_in(&mut out, "view").unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("0123█"));
+        assert!(out.contains("abc █"));
+        assert!(out.contains("de  ░"));
+        assert!(out.contains("██░░ "));
+
+        let wheel = |kind| NyanMouse { kind, x: 1, y: 1 };
+        assert_eq!(
+            obj.route_scroll(&wheel(NyanMouseKind::ScrollDown)),
+            Some("view")
+        );
+        assert_eq!(
+            obj.route_scroll(&wheel(NyanMouseKind::ScrollRight)),
+            Some("view")
+        );
+        assert_eq!(obj.object("view").unwrap().scroll_offset(), Some(1));
+        assert_eq!(obj.object("view").unwrap().scroll_column(), Some(1));
+    }
+
+    #[test]
+    fn tabs_switch_and_hide_other_contents() {
+        use crate::event::{NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::input::{NyanInput, NyanKey};
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::rect::Rect;
+        use crate::tabs::Tabs;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("files", Objects::new_text("main.rs"), (0, 0));
+        obj.add_object("search", Objects::new_text("grep"), (0, 0));
+
+        let mut tabs = Tabs::new().tab("Files", "files").tab("Search", "search");
+        tabs.apply(&mut obj, Rect::new(0, 0, 40, 10)).unwrap();
+        assert_eq!(obj.coordinate("search"), Some((0, 1)));
+        assert_eq!(
+            tabs.title_regions(Rect::new(0, 0, 40, 10)),
+            [Rect::new(0, 0, 7, 1), Rect::new(8, 0, 8, 1)]
+        );
+        assert!(tabs.is_hidden("search"));
+
+        let click = NyanEvent::<()>::Mouse(NyanMouse {
+            kind: NyanMouseKind::Down(NyanMouseButton::Left),
+            x: 10,
+            y: 0,
+        });
+        assert!(tabs.handle_event(&click));
+        assert_eq!(tabs.selected_id(), Some("search"));
+        assert!(tabs.handle_input(&NyanInput::Alt(NyanKey::OtherKey('1'))));
+        assert_eq!(tabs.selected(), 0);
+
+        let mut out = Vec::new();
+        tabs.draw_in(&obj, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("main.rs"));
+        assert!(!out.contains("grep"));
+        assert!(out.contains(" Search "));
+    }
+
+    #[test]
+    fn too_small_screen_is_cleared_only_when_it_changes() {
+        use crate::backend::TestBackend;
+        use crate::clock::MockClock;
+
+        let backend = TestBackend::new(30, 5);
+        let mut app = App::with_backend(60, backend.clone())
+            .clock(MockClock::new())
+            .min_size(80, 24);
+        let clears = |backend: &TestBackend| {
+            let output = String::from_utf8_lossy(&backend.output()).into_owned();
+            output.matches("\x1b[2J").count()
+        };
+        for _ in 0..3 {
+            app.draw(|| ()).unwrap();
+        }
+        assert_eq!(clears(&backend), 1);
+
+        // The message moves with the size, and the app is drawn again once it fits.
+        backend.resize(40, 6);
+        app.draw(|| ()).unwrap();
+        app.draw(|| ()).unwrap();
+        assert_eq!(clears(&backend), 2);
+        backend.resize(80, 24);
+        app.draw(|| ()).unwrap();
+        app.draw(|| ()).unwrap();
+        assert_eq!(clears(&backend), 3);
+    }
+
+    #[test]
+    fn too_small_screen_is_centered() {
+        let mut out = Vec::new();
+        App::draw_too_small(&mut out, (30, 5), (80, 24)).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[2;1H      Terminal too small      "));
+        assert!(out.contains("\x1b[3;1H   Please enlarge to 80x24    "));
+        assert!(out.contains("(currently 30x5)"));
+    }
+
+    #[test]
+    fn text_input_scrolls_to_the_cursor() {
+        use crate::border::BorderStyle;
+        use crate::rect::Rect;
+        use crate::text_input::TextInput;
+        use crate::widget::{self, Widget};
+
+        let mut input = TextInput::new()
+            .border(Some(BorderStyle::Ascii))
+            .placeholder("name");
+        input.set_area(Rect::new(0, 0, 6, 3));
+        input.set_focused(true);
+
+        assert!(widget::handle_event(
+            &mut input,
+            &crate::event::NyanEvent::<()>::Paste("nyancat".into())
+        ));
+        assert!(input.handle_input(&NyanInput::LeftAllow));
+        assert_eq!(input.cursor(), 6);
+
+        let mut out = Vec::new();
+        input.draw(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        // 4 cells wide: the cursor on "t" is the last visible cell.
+        assert!(out.contains("\x1b[2;2Hnca\x1b[7mt\x1b[0m"));
+        assert!(out.contains("+----+"));
+    }
+
+    #[test]
+    fn select_popup_opens_below_and_takes_clicks() {
+        use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::rect::Rect;
+        use crate::select::Select;
+        use crate::widget::Widget;
+
+        let mut select = Select::new(["red", "green", "blue"]).placeholder("color");
+        select.set_area(Rect::new(2, 1, 8, 1));
+        let click = |x, y| NyanMouse {
+            kind: NyanMouseKind::Down(NyanMouseButton::Left),
+            x,
+            y,
+        };
+
+        assert!(select.handle_mouse(&click(3, 1)));
+        assert!(select.is_open());
+        assert_eq!(select.popup_area(), Rect::new(2, 2, 8, 3));
+
+        let mut out = Vec::new();
+        select.draw(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("color"));
+        assert!(out.contains("\x1b[5;3H"));
+
+        assert!(select.handle_mouse(&click(4, 4)));
+        assert!(!select.is_open());
+        assert_eq!(select.value(), Some("blue"));
+    }
+
+    #[test]
+    fn menu_popups_nest_and_take_clicks() {
+        use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::menu::{MenuBar, MenuItem};
+        use crate::rect::Rect;
+        use crate::widget::Widget;
+
+        let mut bar = MenuBar::new().menu("Edit", []).menu(
+            "View",
+            [
+                MenuItem::action("Zoom", "zoom"),
+                MenuItem::submenu("Theme", [MenuItem::action("Dark", "dark")]),
+            ],
+        );
+        bar.set_area(Rect::new(0, 0, 40, 1));
+        let click = |x, y| NyanMouse {
+            kind: NyanMouseKind::Down(NyanMouseButton::Left),
+            x,
+            y,
+        };
+
+        assert!(bar.handle_mouse(&click(7, 0)));
+        assert!(bar.handle_mouse(&click(8, 3)));
+        assert_eq!(
+            bar.popup_regions(),
+            [Rect::new(6, 1, 11, 4), Rect::new(17, 2, 8, 3)]
+        );
+        assert!(bar.handle_mouse(&click(18, 3)));
+        assert_eq!(bar.take_action().as_deref(), Some("dark"));
+        assert!(!bar.is_open());
+    }
+
+    #[test]
+    fn dialog_centers_over_the_dimmed_scene() {
+        use crate::dialog::{Dialog, DialogAnswer};
+        use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::rect::Rect;
+        use crate::widget::Widget;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("status", Objects::new_text("ready"), (0, 0));
+
+        let mut dialog = Dialog::confirm("Delete", "Delete file?");
+        dialog.center_in(Rect::new(0, 0, 80, 24));
+        assert_eq!(dialog.area(), Rect::new(25, 8, 30, 7));
+
+        let mut out = Vec::new();
+        dialog.draw_over(&obj, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\x1b[2mready"));
+        assert!(out.contains("Delete file?"));
+
+        let mouse = |kind| NyanMouse { kind, x: 42, y: 13 };
+        dialog.handle_mouse(&mouse(NyanMouseKind::Down(NyanMouseButton::Left)));
+        dialog.handle_mouse(&mouse(NyanMouseKind::Up(NyanMouseButton::Left)));
+        assert_eq!(dialog.take_answer(), Some(DialogAnswer::No));
+    }
+
+    #[test]
+    fn file_picker_enters_directories_and_filters() {
+        use crate::file_picker::FilePicker;
+        use crate::widget::Widget;
+
+        let root = std::env::temp_dir().join(format!("nyan-picker-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        for file in ["notes.txt", "main.rs", ".hidden", "src/lib.rs"] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let mut picker = FilePicker::new(&root).unwrap().extensions(["rs"]);
+        picker.set_focused(true);
+        assert_eq!(picker.highlighted(), Some(".."));
+        picker.handle_input(&NyanInput::DownAllow);
+        assert_eq!(picker.highlighted(), Some("src"));
+        picker.handle_input(&NyanInput::DownAllow);
+        assert_eq!(picker.highlighted(), Some("main.rs"));
+
+        picker.handle_input(&NyanInput::Home);
+        picker.handle_input(&NyanInput::DownAllow);
+        picker.handle_input(&NyanInput::Enter);
+        assert!(picker.dir().ends_with("src"));
+        picker.handle_input(&NyanInput::Key(NyanKey::L));
+        assert_eq!(picker.highlighted(), Some("lib.rs"));
+        picker.handle_input(&NyanInput::BackSpace);
+        picker.handle_input(&NyanInput::BackSpace);
+        assert_eq!(picker.highlighted(), Some("src"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn list_view_draws_markers_and_a_scrollbar() {
+        use crate::list_view::ListView;
+        use crate::rect::Rect;
+        use crate::widget::Widget;
+
+        let mut list = ListView::new(["one", "two", "three", "four"]).multi_select(true);
+        list.set_area(Rect::new(0, 0, 12, 2));
+        list.toggle_mark(1);
+        list.select(1);
+
+        let mut out = Vec::new();
+        list.draw(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("  [ ] one"));
+        assert!(out.contains("> [x] two"));
+        assert!(out.contains('█'));
+        assert!(!out.contains("three"));
+    }
+
+    #[test]
+    fn buttons_activate_on_keys_and_completed_clicks_only() {
+        use std::cell::Cell;
+
+        use crate::button::Button;
+        use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::input::{NyanInput, NyanKey};
+        use crate::rect::Rect;
+        use crate::widget::Widget;
+
+        let presses = Cell::new(0);
+        let mut button = Button::new("Save").on_press(|| presses.set(presses.get() + 1));
+        assert_eq!(button.size(), (8, 1));
+        assert_eq!(Button::new("にゃん").size(), (10, 1));
+
+        // Keys only reach a focused button, and only Enter and Space activate it.
+        let space = NyanInput::Key(NyanKey::OtherKey(' '));
+        assert!(!button.handle_input(&space));
+        button.set_focused(true);
+        assert!(button.handle_input(&space));
+        assert!(button.handle_input(&NyanInput::Enter));
+        assert!(!button.handle_input(&NyanInput::Key(NyanKey::X)));
+        assert_eq!(presses.get(), 2);
+        assert!(button.take_activation());
+        assert!(!button.take_activation());
+
+        // A click is a press and a release of the left button on it.
+        button.set_area(Rect::new(2, 1, 8, 3));
+        let mouse = |kind, x, y| NyanMouse { kind, x, y };
+        let down = NyanMouseKind::Down(NyanMouseButton::Left);
+        let up = NyanMouseKind::Up(NyanMouseButton::Left);
+        assert!(!button.handle_mouse(&mouse(down, 10, 1)));
+        assert!(!button.handle_mouse(&mouse(up, 5, 2)));
+        assert!(!button.handle_mouse(&mouse(NyanMouseKind::Down(NyanMouseButton::Right), 5, 2)));
+        assert!(!button.is_pressed());
+
+        // Releasing outside cancels the click.
+        assert!(button.handle_mouse(&mouse(down, 2, 1)));
+        assert!(button.is_pressed());
+        assert!(button.handle_mouse(&mouse(up, 10, 3)));
+        assert!(!button.is_pressed() && !button.take_activation());
+        assert!(button.handle_mouse(&mouse(down, 9, 3)));
+        assert!(button.handle_mouse(&mouse(up, 9, 3)));
+        assert!(button.take_activation());
+        button.press();
+        assert_eq!(presses.get(), 4);
+
+        // The label is centered on the middle row, and cut off in narrow areas.
+        let draw = |button: &Button| {
+            let mut out = Vec::new();
+            button.draw(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        button.set_focused(false);
+        button.set_area(Rect::new(0, 0, 10, 3));
+        assert_eq!(draw(&button), "\x1b[2;1H [ Save ] ");
+        button.set_area(Rect::new(0, 0, 4, 1));
+        assert_eq!(draw(&button), "\x1b[1;1H[ Sa");
+        button.set_area(Rect::new(0, 0, 0, 1));
+        assert_eq!(draw(&button), "");
+    }
+
+    #[test]
+    fn table_view_sizes_columns_and_sorts_on_header_clicks() {
+        use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::layout::StackSize;
+        use crate::rect::Rect;
+        use crate::table_view::{Column, SortOrder, TableView};
+        use crate::widget::Widget;
+
+        let mut table = TableView::new([
+            Column::new("Id").width(StackSize::Fixed(4)),
+            Column::new("Name").width(StackSize::Flex(1)),
+        ])
+        .row(["2", "beta"])
+        .row(["1", "alpha"]);
+        table.set_area(Rect::new(0, 0, 20, 3));
+        assert_eq!(table.column_widths(), vec![4, 15]);
+
+        let click = |x| NyanMouse {
+            kind: NyanMouseKind::Down(NyanMouseButton::Left),
+            x,
+            y: 0,
+        };
+        assert!(table.handle_mouse(&click(8)));
+        assert!(table.handle_mouse(&click(8)));
+        assert_eq!(table.sorted(), Some((1, SortOrder::Descending)));
+
+        let mut out = Vec::new();
+        table.draw(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Name ▼"));
+        assert!(out.find("beta").unwrap() < out.find("alpha").unwrap());
+    }
+
+    #[test]
+    fn table_view_sorts_numeric_columns_with_nan_cells() {
+        use crate::table_view::{Column, SortOrder, TableView};
+
+        let mut table = TableView::new([Column::new("Value")]);
+        for row in 0..64 {
+            let value = if row % 5 == 0 {
+                "NaN".to_string()
+            } else {
+                ((row * 37) % 64).to_string()
+            };
+            table.push_row([value]);
+        }
+        // A comparison that is not a total order makes the sort panic.
+        table.sort_by(0, SortOrder::Ascending);
+
+        let values: Vec<f64> = (0..table.len())
+            .map(|position| {
+                table.select(position);
+                table.selected_row().unwrap()[0].parse().unwrap()
+            })
+            .collect();
+        let numbers = values.iter().take_while(|value| !value.is_nan()).count();
+        assert_eq!(numbers, 64 - 13);
+        assert!(values[..numbers].windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(values[numbers..].iter().all(|value| value.is_nan()));
+    }
+
+    #[test]
+    fn focus_follows_back_tab_and_clicks() {
+        use crate::event::{NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::focus::FocusManager;
+        use crate::list_view::ListView;
+        use crate::rect::Rect;
+        use crate::widget::Widget;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let back_tab: NyanInput = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT).into();
+        assert_eq!(back_tab, NyanInput::Shift(&NyanInput::Tab));
+
+        let mut obj = NyanObj::new();
+        obj.add_object("help", Objects::new_text("Help"), (0, 5));
+        let mut list = ListView::new(["a", "b"]);
+        list.set_area(Rect::new(0, 0, 10, 2));
+        let mut focus = FocusManager::new().item("list").item("help");
+
+        let mut widgets: [(&str, &mut dyn Widget); 1] = [("list", &mut list)];
+        let event: NyanEvent = NyanEvent::Key(back_tab);
+        assert!(focus.handle_event(&mut widgets, &event));
+        assert_eq!(focus.focused(), Some("help"));
+
+        let mut out = Vec::new();
+        focus.draw_focused(&obj, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("\x1b[7mHelp"));
+
+        let click: NyanEvent = NyanEvent::Mouse(NyanMouse {
+            kind: NyanMouseKind::Down(NyanMouseButton::Left),
+            x: 1,
+            y: 1,
+        });
+        focus.handle_event(&mut widgets, &click);
+        assert_eq!(focus.focused(), Some("list"));
+        assert!(list.is_focused());
+    }
+
+    #[test]
+    fn form_places_fields_beside_labels_and_shows_errors() {
+        use crate::form::Form;
+        use crate::rect::Rect;
+        use crate::text_input::TextInput;
+        use crate::widget::Widget;
+
+        let mut form = Form::new()
+            .text("user", "User", TextInput::new().borderless().width(10))
+            .text(
+                "port",
+                "Port",
+                TextInput::new().borderless().width(5).value("http"),
+            )
+            .validate_with("port", |port| {
+                port.parse::<u16>()
+                    .map(|_| ())
+                    .map_err(|_| "not a number".to_string())
+            });
+        form.set_area(Rect::new(2, 1, 30, 10));
+        assert_eq!(form.size(), (15, 5));
+
+        form.submit();
+        assert_eq!(form.focused(), Some("port"));
+        assert_eq!(form.errors().len(), 1);
+
+        let mut out = Vec::new();
+        form.draw(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\x1b[2;3H\x1b[37mUser"));
+        assert!(out.contains("\x1b[5;8H"));
+        assert!(out.contains("not a number"));
+    }
+
+    #[test]
+    fn toasts_wrap_and_take_the_color_of_their_level() {
+        use crate::color::{self, ColorSupport};
+        use crate::layout::Align;
+        use crate::notification::Notifications;
+        use crate::rect::Rect;
+
+        color::set_color_support(ColorSupport::TrueColor);
+
+        let mut toasts = Notifications::new()
+            .corner(Align::End, Align::Start)
+            .max_width(12);
+        let id = toasts.warning("low battery level");
+        toasts.error("offline");
+        assert_eq!(
+            toasts.regions(Rect::new(0, 0, 40, 10)),
+            vec![Rect::new(29, 3, 11, 5), Rect::new(29, 0, 11, 3)]
+        );
+
+        let mut out = Vec::new();
+        toasts.draw_in(&mut out, Rect::new(0, 0, 40, 10)).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\x1b[1;93m"));
+        assert!(out.contains("battery"));
+
+        assert!(toasts.dismiss(id));
+        assert!(!toasts.dismiss(id));
+        assert_eq!(toasts.len(), 1);
+    }
+
+    #[test]
+    fn status_bar_truncates_with_an_ellipsis() {
+        use crate::rect::Rect;
+        use crate::status_bar::StatusBar;
+        use crate::style::NyanStyle;
+        use crate::widget::Widget;
+
+        let mut status = StatusBar::new()
+            .left("file", "a_very_long_file_name.rs")
+            .center("message", "saved")
+            .right("position", "12:4");
+        status.set_area(Rect::new(0, 0, 20, 1));
+        assert_eq!(status.line(), " a_very_long_… 12:4 ");
+
+        status.set_area(Rect::new(0, 0, 40, 1));
+        assert_eq!(status.line().chars().count(), 40);
+        assert!(status.line().contains("saved"));
+
+        status.set_style("position", NyanStyle::new().bold());
+        let mut out = Vec::new();
+        status.draw(&mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("\x1b[1;7m12:4"));
+    }
+
+    #[test]
+    fn log_view_follows_until_scrolled_back() {
+        use crate::color::{self, ColorSupport};
+        use crate::log_view::LogView;
+        use crate::rect::Rect;
+        use crate::widget::Widget;
+
+        color::set_color_support(ColorSupport::TrueColor);
+
+        let mut log = LogView::new(100);
+        log.set_area(Rect::new(0, 0, 30, 2));
+        log.set_focused(true);
+        for n in 0..5 {
+            log.push_line(format!("INFO line {n}"));
+        }
+        log.push_line("ERROR line 5");
+
+        let mut out = Vec::new();
+        log.draw(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("line 4"));
+        assert!(out.contains("\x1b[1;91mERROR line 5"));
+        assert!(!out.contains("line 3"));
+
+        log.handle_input(&NyanInput::UpAllow);
+        assert!(!log.is_following());
+        log.push_line("INFO line 6");
+        let mut out = Vec::new();
+        log.draw(&mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("line 3"));
+
+        log.handle_input(&NyanInput::End);
+        assert!(log.is_following());
+    }
+
+    #[test]
+    fn command_palette_ranks_matches_and_executes_clicks() {
+        use crate::color::{self, ColorSupport};
+        use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::palette::CommandPalette;
+        use crate::rect::Rect;
+        use crate::widget::Widget;
+
+        color::set_color_support(ColorSupport::TrueColor);
+
+        let mut palette = CommandPalette::new()
+            .command("split", "View: Split Editor")
+            .command("settings", "Preferences: Open Settings")
+            .command_with_hint("save", "File: Save", "Ctrl+S");
+        palette.place_in(Rect::new(0, 0, 100, 30));
+        assert_eq!(palette.area(), Rect::new(20, 4, 60, 7));
+
+        // Closed palettes ignore keys and draw nothing.
+        assert!(!palette.handle_input(&NyanInput::Key(NyanKey::S)));
+        let mut out = Vec::new();
+        palette.draw(&mut out).unwrap();
+        assert!(out.is_empty());
+
+        palette.open();
+        palette.handle_paste("set");
+        // Consecutive letters at a word start beat letters scattered over several words.
+        assert_eq!(
+            palette.matches(),
+            vec!["Preferences: Open Settings", "View: Split Editor"]
+        );
+        palette.handle_input(&NyanInput::DownAllow);
+        assert_eq!(palette.highlighted(), Some("split"));
+
+        let mut out = Vec::new();
+        palette.draw(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains("File: Save"));
+        assert!(out.contains("\x1b[1;96mS"));
+
+        // The first row of the list, below the field and the rule.
+        palette.handle_mouse(&NyanMouse {
+            kind: NyanMouseKind::Down(NyanMouseButton::Left),
+            x: 30,
+            y: 7,
+        });
+        assert!(!palette.is_open());
+        assert_eq!(palette.take_command().as_deref(), Some("settings"));
+        assert_eq!(palette.take_command(), None);
+    }
+
+    #[test]
+    fn tooltips_prefer_hover_and_stay_on_screen() {
+        use crate::event::{NyanMouse, NyanMouseKind};
+        use crate::rect::Rect;
+        use crate::tooltip::Tooltips;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("name", Objects::new_text("name"), (0, 0));
+        obj.add_object("plain", Objects::new_text("plain"), (10, 0));
+        obj.add_object("ok", Objects::new_text("ok"), (28, 0));
+        let mut tips = Tooltips::new()
+            .max_width(12)
+            .tooltip("name", "Your full name")
+            .tooltip("ok", "Submit");
+        let screen = Rect::new(0, 0, 30, 3);
+        let moved = |x, y| NyanMouse {
+            kind: NyanMouseKind::Moved,
+            x,
+            y,
+        };
+
+        tips.set_focus(Some("name"));
+        // Wrapped to fit 12 cells with its padding, below the object.
+        assert_eq!(tips.region(&obj, screen), Some(Rect::new(0, 1, 11, 2)));
+
+        // Objects without a tooltip leave the focused one's shown.
+        assert!(!tips.handle_mouse(&obj, &moved(11, 0)));
+        assert_eq!(tips.active(), Some("name"));
+
+        assert!(tips.handle_mouse(&obj, &moved(29, 0)));
+        assert_eq!(tips.region(&obj, screen), Some(Rect::new(22, 1, 8, 1)));
+
+        let mut out = Vec::new();
+        tips.draw_in(&obj, &mut out, screen).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\x1b[2;23H"));
+        assert!(out.contains(" Submit "));
+
+        tips.remove("ok");
+        assert_eq!(tips.active(), Some("name"));
+    }
+
+    #[test]
+    fn animations_hide_objects_and_honor_delays() {
+        use std::time::Duration;
+
+        use crate::animation::{Animations, Tween};
+        use crate::easing::Easing;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("toast", Objects::new_text("saved"), (0, 0));
+        obj.add_object("ball", Objects::new_text("o"), (0, 2));
+        let ms = Duration::from_millis;
+
+        let mut animations = Animations::new();
+        animations.visibility("toast", Tween::new(true, false, ms(300), Easing::Linear));
+        animations.position(
+            "ball",
+            Tween::new((0, 2), (10, 2), ms(100), Easing::Linear).delay(ms(200)),
+        );
+
+        assert!(animations.update(&mut obj, ms(200)));
+        assert_eq!(obj.coordinate("ball"), Some((0, 2)));
+        assert_eq!(obj.is_visible("toast"), Some(true));
+
+        obj.clear_dirty();
+        animations.update(&mut obj, ms(100));
+        assert!(obj.is_dirty());
+        assert_eq!(obj.coordinate("ball"), Some((10, 2)));
+        assert_eq!(obj.is_visible("toast"), Some(false));
+        assert!(!animations.is_running());
+        assert!(!animations.update(&mut obj, ms(100)));
+
+        // Hidden objects are neither drawn nor hit.
+        let mut out = Vec::new();
+        obj.draw_all_in(&mut out).unwrap();
+        assert!(!String::from_utf8(out).unwrap().contains("saved"));
+        assert_eq!(obj.hit_test(1, 0), None);
+    }
+
+    #[test]
+    fn sprites_play_once_and_keep_their_size() {
+        use std::time::Duration;
+
+        use crate::sprite::{AnimatedSprite, PlayMode};
+
+        let ms = Duration::from_millis;
+        let mut obj = NyanObj::new();
+        obj.add_object(
+            "boom",
+            Objects::new_sprite(
+                AnimatedSprite::new()
+                    .frame("*", ms(100))
+                    .frame("\\|/\n-*-\n/|\\", ms(100))
+                    .mode(PlayMode::Once),
+            ),
+            (0, 0),
+        );
+        assert_eq!(obj.bounds("boom").unwrap().width, 3);
+        assert_eq!(obj.bounds("boom").unwrap().height, 3);
+
+        obj.clear_dirty();
+        assert!(!obj.advance(ms(50)));
+        assert!(obj.advance(ms(50)));
+        assert!(obj.is_dirty());
+        assert!(!obj.advance(ms(500)));
+
+        let sprite = obj.object_mut("boom").unwrap().sprite_mut().unwrap();
+        assert!(sprite.is_finished());
+        assert_eq!(sprite.current(), 1);
+        sprite.play();
+        assert_eq!(sprite.current(), 0);
+
+        // The first frame is padded to the size of the largest one.
+        let mut out = Vec::new();
+        obj.draw_all_in(&mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("*  "));
+    }
+
+    #[test]
+    fn emitters_spawn_at_their_rate_and_fall() {
+        use std::time::Duration;
+
+        use crate::color::{self, ColorSupport};
+        use crate::gradient::Gradient;
+        use crate::particle::Emitter;
+        use crate::rect::Rect;
+
+        color::set_color_support(ColorSupport::TrueColor);
+
+        let ms = Duration::from_millis;
+        let mut rain = Emitter::new((4.0, 0.0))
+            .rate(10.0)
+            .gravity(20.0)
+            .lifetime(ms(1000))
+            .glyphs("|.")
+            .colors(Gradient::new([(0, 0, 255)]))
+            .seed(7);
+
+        // Ten per second: one particle after 100 ms, none for the remaining fraction.
+        assert!(rain.advance(ms(150)));
+        assert_eq!(rain.len(), 1);
+        rain.set_emitting(false);
+
+        // Gravity adds 2 cells/s every step: 0.2 + 0.4 + ... + 1.0 = 3 cells down.
+        for _ in 0..5 {
+            rain.advance(ms(100));
+        }
+        assert_eq!(rain.positions(), vec![(4, 3)]);
+
+        let mut out = Vec::new();
+        rain.draw_in(&mut out, Rect::new(0, 0, 10, 10)).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("\x1b[38;2;0;0;255m."));
+
+        // Off-area particles are not drawn, dead ones disappear.
+        let mut out = Vec::new();
+        rain.draw_in(&mut out, Rect::new(0, 0, 10, 2)).unwrap();
+        assert!(!String::from_utf8(out).unwrap().contains('.'));
+        assert!(!rain.advance(ms(500)));
+    }
+
+    #[test]
+    fn scheduler_fires_into_the_event_queue() {
+        use std::time::Duration;
+
+        use crate::event::{EventQueue, NyanEvent};
+        use crate::scheduler::Scheduler;
+
+        let ms = Duration::from_millis;
+        let mut queue = EventQueue::new();
+        let mut scheduler = Scheduler::new();
+        let blink = scheduler.every(ms(250), "blink");
+        let hide = scheduler.after(ms(600), "hide");
+
+        assert_eq!(scheduler.advance_into(ms(100), &queue), 0);
+        assert_eq!(scheduler.remaining(hide), Some(ms(500)));
+
+        // Several intervals in one frame fire once each.
+        assert_eq!(scheduler.advance_into(ms(400), &queue), 2);
+        assert!(scheduler.cancel(hide));
+        assert!(!scheduler.cancel(hide));
+        assert_eq!(scheduler.remaining(blink), Some(ms(250)));
+
+        // Pending user events are delivered before the terminal is polled.
+        for _ in 0..2 {
+            assert!(matches!(queue.get_event(), Ok(NyanEvent::User("blink"))));
+        }
+        assert_eq!(scheduler.len(), 1);
+    }
+
+    #[test]
+    fn bodies_move_smoothly_and_keep_to_their_bounds() {
+        use std::time::Duration;
+
+        use crate::physics::{Body, Edge};
+        use crate::rect::Rect;
+
+        let ms = Duration::from_millis;
+        let screen = Rect::new(0, 0, 20, 10);
+        let mut obj = NyanObj::new();
+        obj.add_object("cat", Objects::new_text("=^.^="), (0, 0));
+        obj.add_object("rock", Objects::new_text("#"), (5, 0));
+        obj.set_body("cat", Body::new(2.5, 0.0).bounded(screen, Edge::Wrap))
+            .unwrap();
+        obj.set_body(
+            "rock",
+            Body::new(0.0, 0.0)
+                .acceleration(0.0, 40.0)
+                .bounded(screen, Edge::Clamp),
+        )
+        .unwrap();
+
+        // Slower than a cell per frame, but the fractions add up.
+        obj.advance(ms(100));
+        assert_eq!(obj.coordinate("cat"), Some((0, 0)));
+        obj.advance(ms(100));
+        assert_eq!(obj.coordinate("cat"), Some((1, 0)));
+
+        // Moving an object by hand restarts its body from there.
+        obj.move_object("cat", (19, 0)).unwrap();
+        obj.advance(ms(800));
+        assert_eq!(obj.coordinate("cat"), Some((1, 0)));
+
+        // The rock falls onto the bottom row and stays there.
+        for _ in 0..10 {
+            obj.advance(ms(100));
+        }
+        assert_eq!(obj.coordinate("rock"), Some((5, 9)));
+        assert_eq!(obj.body("rock").unwrap().velocity.1, 0.0);
+
+        assert!(obj.remove_body("rock").is_some());
+        assert!(obj.body("rock").is_none());
+    }
+
+    #[test]
+    fn objects_follow_curves_and_give_up_other_movements() {
+        use std::time::Duration;
+
+        use crate::animation::{Animations, Tween};
+        use crate::easing::Easing;
+        use crate::path::Path;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("moon", Objects::new_text("o"), (0, 0));
+        let ms = Duration::from_millis;
+
+        let mut animations = Animations::new();
+        animations.position(
+            "moon",
+            Tween::new((0, 0), (50, 50), ms(1000), Easing::Linear),
+        );
+        let orbit = Path::ellipse((20.0, 10.0), (10.0, 5.0));
+        animations.follow(
+            "moon",
+            orbit,
+            Tween::new(0.0, 1.0, ms(1000), Easing::Linear),
+        );
+
+        animations.update(&mut obj, ms(250));
+        assert_eq!(obj.coordinate("moon"), Some((20, 15)));
+        animations.update(&mut obj, ms(750));
+        assert_eq!(obj.coordinate("moon"), Some((30, 10)));
+        assert!(!animations.is_animating("moon"));
+
+        let arc = Path::bezier((0.0, 0.0), (0.0, 8.0), (8.0, 8.0), (8.0, 0.0));
+        assert_eq!(arc.point_at(0.5), Some((4.0, 6.0)));
+        assert_eq!(Path::waypoints([]).point_at(0.5), None);
+    }
+
+    #[test]
+    fn collisions_sweep_tags_and_skip_hidden_objects() {
+        use crate::collision::{Collision, Collisions};
+
+        let mut obj = NyanObj::new();
+        obj.add_object("a", Objects::new_text("###"), (0, 0));
+        obj.add_object("b", Objects::new_text("###"), (2, 0));
+        obj.add_object("c", Objects::new_text("###"), (2, 1));
+        obj.add_object("wall", Objects::new_text("|"), (1, 0));
+        assert!(obj.collides("a", "b"));
+        assert!(!obj.collides("a", "c"));
+
+        let mut collisions = Collisions::new();
+        for id in ["a", "b", "c"] {
+            collisions.tag(id, "rock");
+        }
+        collisions.between("rock", "rock");
+        let pair = |a: &str, b: &str| Collision {
+            a: a.into(),
+            b: b.into(),
+        };
+        assert_eq!(collisions.update(&obj), [pair("a", "b")]);
+        assert_eq!(collisions.colliding_with("b"), ["a"]);
+
+        // Untagged objects never collide, hidden ones stop colliding.
+        obj.set_visible("b", false).unwrap();
+        assert!(collisions.update(&obj).is_empty());
+        assert!(!collisions.is_colliding("a", "b"));
+        obj.set_visible("b", true).unwrap();
+        assert_eq!(collisions.update(&obj), [pair("a", "b")]);
+        assert!(collisions.untag("b"));
+        assert!(collisions.colliding_with("a").is_empty());
+    }
+
+    #[test]
+    fn tile_maps_draw_only_what_the_camera_sees() {
+        use crate::color::NyanColor;
+        use crate::rect::Rect;
+        use crate::style::NyanStyle;
+        use crate::tilemap::{Camera, TileMap};
+
+        let mut map = TileMap::from_text("abcdef\nghijkl\nmn")
+            .style('~', NyanStyle::new().fg(NyanColor::Blue));
+        assert_eq!(map.size(), (6, 3));
+        assert!(map.is_solid(6, 0));
+        assert!(map.set(1, 2, '~'));
+        assert_eq!(
+            map.get(1, 2).unwrap().style,
+            NyanStyle::new().fg(NyanColor::Blue)
+        );
+
+        let mut camera = Camera::new(Rect::new(10, 5, 3, 2));
+        camera.look_at(4, 1);
+        let mut out = Vec::new();
+        map.draw_in(&mut out, &camera).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("kl "));
+        assert!(out.contains("\x1b[7;11H   "));
+        assert!(!out.contains('j'));
+
+        assert_eq!(camera.to_map(11, 6), Some((5, 2)));
+        camera.scroll_by(-10, 0);
+        assert_eq!(camera.position(), (0, 1));
+        camera.center_on(5, 2, map.size());
+        assert_eq!(camera.position(), (3, 1));
+    }
+
+    #[test]
+    fn scene_transitions_switch_scenes_in_the_middle() {
+        use crate::rect::Rect;
+        use crate::scene::{SceneStack, Transition};
+
+        let scene = |text: &'static str| {
+            let mut obj = NyanObj::new();
+            obj.add_object("text", Objects::new_text(text), (0, 0));
+            obj
+        };
+        let area = Rect::new(0, 0, 8, 2);
+        let frame = |scenes: &mut SceneStack| {
+            let mut out = Vec::new();
+            scenes.draw_in(&mut out, area).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let mut scenes = SceneStack::new();
+        scenes.push(scene("home"));
+        scenes.push_with(scene("away"), Transition::fade(6));
+        assert!(frame(&mut scenes).contains("home"));
+        // Dimmed, then covered: the new scene shows from the middle on.
+        assert!(frame(&mut scenes).contains("\x1b[2mhome"));
+        let covered = frame(&mut scenes);
+        assert!(covered.contains("away") && covered.contains("\x1b[1;1H        "));
+        for _ in 0..3 {
+            frame(&mut scenes);
+        }
+        assert!(!scenes.is_transitioning());
+
+        // A popped scene is shown until the middle of the transition.
+        assert!(scenes.pop_with(Transition::dissolve(4)));
+        assert_eq!(scenes.len(), 1);
+        assert!(frame(&mut scenes).contains("away"));
+        assert!(frame(&mut scenes).contains("home"));
+        assert!(scenes.pop().is_some());
+        assert!(!scenes.pop_with(Transition::wipe(2)));
+    }
+
+    #[test]
+    fn timelines_loop_seek_and_pause() {
+        use std::time::Duration;
+
+        use crate::easing::Easing;
+        use crate::timeline::Timeline;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("bar", Objects::new_text("="), (0, 0));
+        let ms = Duration::from_millis;
+
+        let mut timeline = Timeline::new()
+            .value("alpha", ms(200), 0.0, Easing::Linear)
+            .value("alpha", ms(600), 1.0, Easing::InQuad)
+            .position("bar", ms(0), (0, 0), Easing::Linear)
+            .position("bar", ms(800), (8, 0), Easing::Linear)
+            .position("ghost", ms(400), (1, 1), Easing::Linear)
+            .looping();
+        assert_eq!(timeline.duration(), ms(800));
+        assert_eq!(timeline.get("alpha"), Some(0.0));
+
+        assert!(timeline.update(&mut obj, ms(400)));
+        assert_eq!(timeline.get("alpha"), Some(0.25));
+        assert_eq!(obj.coordinate("bar"), Some((4, 0)));
+
+        // Looping wraps around past the end.
+        timeline.update(&mut obj, ms(500));
+        assert_eq!(timeline.elapsed(), ms(100));
+        assert_eq!(obj.coordinate("bar"), Some((1, 0)));
+
+        timeline.pause();
+        assert!(!timeline.update(&mut obj, ms(100)));
+        timeline.seek(ms(2000));
+        assert_eq!(timeline.elapsed(), ms(800));
+        assert!(!timeline.is_finished());
+    }
+
+    #[test]
+    fn backgrounds_stay_inside_their_area() {
+        use std::time::Duration;
+
+        use crate::background::Background;
+        use crate::rect::Rect;
+
+        let moves = |out: &[u8]| {
+            let out = String::from_utf8(out.to_vec()).unwrap();
+            out.split("\x1b[")
+                .filter_map(|code| code.split_once('H'))
+                .filter_map(|(at, _)| at.split_once(';'))
+                .map(|(y, x)| (x.parse::<u16>().unwrap(), y.parse::<u16>().unwrap()))
+                .collect::<Vec<_>>()
+        };
+        let area = Rect::new(5, 2, 10, 4);
+        let mut backgrounds = [
+            Background::starfield(20, 8).density(1.0).seed(3),
+            Background::matrix(20, 8).density(1.0).seed(3),
+            Background::plasma(20, 8),
+        ];
+        for background in &mut backgrounds {
+            background.advance(Duration::from_millis(500));
+            let mut out = Vec::new();
+            background.draw_in(&mut out, area).unwrap();
+            let cells = moves(&out);
+            assert!(!cells.is_empty());
+            assert!(cells.iter().all(|&(x, y)| area.contains(x - 1, y - 1)));
+        }
+
+        // The plasma writes whole rows.
+        let mut out = Vec::new();
+        backgrounds[2].resize(3, 2);
+        backgrounds[2].draw_in(&mut out, area).unwrap();
+        assert_eq!(moves(&out), [(6, 3), (6, 4)]);
+    }
+
+    #[test]
+    fn shakes_move_objects_together_and_put_them_back() {
+        use std::time::Duration;
+
+        use crate::animation::Animations;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("hp", Objects::new_text("HP 3"), (10, 5));
+        obj.add_object("map", Objects::new_text("#.#"), (20, 8));
+        let ms = Duration::from_millis;
+
+        let mut animations = Animations::new();
+        animations.shake_screen(&obj, 4.0, ms(200));
+        let mut moved = false;
+        for _ in 0..9 {
+            animations.update(&mut obj, ms(20));
+            let (x, y) = obj.coordinate("hp").unwrap();
+            assert!((6..=14).contains(&x) && (3..=7).contains(&y));
+            assert_eq!(obj.coordinate("map"), Some((x + 10, y + 3)));
+            moved |= (x, y) != (10, 5);
+        }
+        assert!(moved);
+
+        // Shaking again keeps the original position.
+        assert!(animations.shake(&obj, "hp", 2.0, ms(100)));
+        animations.update(&mut obj, ms(100));
+        assert_eq!(obj.coordinate("hp"), Some((10, 5)));
+        assert!(!animations.is_animating("hp"));
+        animations.update(&mut obj, ms(100));
+        assert_eq!(obj.coordinate("map"), Some((20, 8)));
+        assert!(!animations.shake(&obj, "missing", 1.0, ms(100)));
+    }
+
+    #[test]
+    fn typewriters_keep_their_box_while_typing() {
+        use std::time::Duration;
+
+        use crate::typewriter::Typewriter;
+
+        let mut obj = NyanObj::new();
+        let text = Typewriter::new("ab\ncd").rate(10);
+        obj.add_object("intro", Objects::new_typewriter(text), (2, 1));
+        assert_eq!(
+            obj.bounds("intro"),
+            Some(crate::rect::Rect::new(2, 1, 2, 2))
+        );
+
+        assert!(obj.advance(Duration::from_millis(400)));
+        let mut out = Vec::new();
+        obj.draw_all_in(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("ab") && out.contains("c "));
+        assert!(!out.contains('d'));
+
+        let typewriter = obj.object_mut("intro").unwrap().typewriter_mut().unwrap();
+        typewriter.skip();
+        assert!(typewriter.is_finished());
+        typewriter.reset();
+        assert_eq!(typewriter.revealed(), "");
+        assert!(!typewriter.advance(Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn flashes_reverse_styles_and_stop_puts_them_back() {
+        use std::time::Duration;
+
+        use crate::animation::Animations;
+        use crate::color::NyanColor;
+        use crate::style::{Attribute, NyanStyle};
+
+        let mut obj = NyanObj::new();
+        let red = NyanStyle::new().fg(NyanColor::Red);
+        obj.add_object("warning", Objects::new_text("LOW HP"), (0, 0));
+        obj.set_style("warning", red).unwrap();
+        obj.add_object("cursor", Objects::new_text("_"), (0, 1));
+        let ms = Duration::from_millis;
+
+        let mut animations = Animations::new();
+        assert!(animations.flash(&obj, "warning", ms(100), ms(300)));
+        animations.update(&mut obj, ms(10));
+        let style = obj.style("warning").unwrap();
+        assert!(style.attributes.contains(Attribute::Reverse));
+        assert_eq!(style.fg, Some(NyanColor::Red));
+        assert!(obj.is_visible("warning").unwrap());
+
+        // Restarting keeps the style from before the first flash.
+        assert!(animations.flash(&obj, "warning", ms(100), ms(300)));
+        animations.update(&mut obj, ms(300));
+        assert_eq!(obj.style("warning"), Some(red));
+
+        animations.blink(&obj, "cursor", ms(500), Duration::MAX);
+        animations.update(&mut obj, ms(10));
+        assert_eq!(obj.is_visible("cursor"), Some(false));
+        assert!(animations.stop(&mut obj, "cursor"));
+        assert_eq!(obj.is_visible("cursor"), Some(true));
+        assert!(!animations.is_running());
+        assert!(!animations.blink(&obj, "missing", ms(100), ms(100)));
+
+        // A flash of a blinking object keeps the blink, and both put back their own look.
+        animations.blink(&obj, "cursor", ms(100), ms(400));
+        animations.update(&mut obj, ms(10));
+        assert_eq!(obj.is_visible("cursor"), Some(false));
+        assert!(animations.flash(&obj, "cursor", ms(100), ms(200)));
+        animations.update(&mut obj, ms(10));
+        assert_eq!(obj.is_visible("cursor"), Some(false));
+        let style = obj.style("cursor").unwrap();
+        assert!(style.attributes.contains(Attribute::Reverse));
+        animations.update(&mut obj, ms(100));
+        assert_eq!(obj.is_visible("cursor"), Some(true));
+        animations.update(&mut obj, ms(400));
+        assert_eq!(obj.is_visible("cursor"), Some(true));
+        assert_eq!(obj.style("cursor"), Some(NyanStyle::new()));
+        assert!(!animations.is_running());
+    }
+
+    #[test]
+    fn buffer_diff_writes_only_changed_cells() {
+        use std::io::Write;
+
+        use crate::buffer::Buffer;
+        use crate::color::NyanColor;
+        use crate::nyan_obj::NyanObj;
+        use crate::style::NyanStyle;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("title", Objects::new_text("nyan"), (1, 0));
+        obj.set_style("title", NyanStyle::new().fg(NyanColor::Red))
+            .unwrap();
+        let mut previous = Buffer::new(8, 2);
+        obj.draw_all_in(&mut previous).unwrap();
+        assert_eq!(previous.get(1, 0).unwrap().ch, 'n');
+        assert_eq!(previous.get(1, 0).unwrap().style.fg, Some(NyanColor::Red));
+        assert_eq!(previous.get(5, 0).unwrap().style, NyanStyle::new());
+
+        // An unchanged frame writes nothing.
+        let mut out = Vec::new();
+        previous.clone().diff(&previous, &mut out).unwrap();
+        assert!(out.is_empty());
+
+        // A wide glyph takes two cells; overwriting its half blanks the other one.
+        let mut current = previous.clone();
+        write!(current, "\x1b[2;1H猫!\x1b[2;2Hx").unwrap();
+        assert_eq!(current.get(0, 1).unwrap().ch, ' ');
+        assert_eq!(current.get(1, 1).unwrap().ch, 'x');
+        let mut out = Vec::new();
+        current.diff(&previous, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\x1b[2;2Hx!");
+
+        // Clearing erases the title in a single run.
+        let mut cleared = previous.clone();
+        write!(cleared, "\x1b[2J").unwrap();
+        let mut out = Vec::new();
+        cleared.diff(&previous, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\x1b[1;2H    ");
+    }
+
+    #[test]
+    fn cached_objects_reuse_their_output_until_they_change() {
+        use crate::style::NyanStyle;
+
+        let mut obj = NyanObj::new();
+        obj.add_object(
+            "report",
+            Objects::new_list(vec!["cpu  12%", "mem  40%"], 2),
+            (0, 0),
+        );
+        let mut plain = Vec::new();
+        obj.draw_object_in(&mut plain, "report").unwrap();
+
+        obj.set_cached("report", true).unwrap();
+        assert_eq!(obj.is_cached("report"), Some(true));
+        for _ in 0..2 {
+            let mut out = Vec::new();
+            obj.draw_object_in(&mut out, "report").unwrap();
+            assert_eq!(out, plain);
+        }
+
+        // Moving keeps the output; changing the object draws it again.
+        obj.move_object("report", (3, 1)).unwrap();
+        let mut out = Vec::new();
+        obj.draw_object_in(&mut out, "report").unwrap();
+        // Only the cursor movement to the new coordinate, "\x1b[2;4H", differs.
+        assert_eq!(&out[..6], b"\x1b[2;4H");
+        assert_eq!(out[6..], plain[6..]);
+        if let Some(Objects::List { items, .. }) = obj.object_mut("report") {
+            items[0] = "cpu  99%".into();
+        }
+        obj.set_style("report", NyanStyle::new().bold()).unwrap();
+        let mut out = Vec::new();
+        obj.draw_object_in(&mut out, "report").unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("cpu  99%") && out.contains("\x1b[1m"));
+        assert_eq!(obj.is_cached("missing"), None);
+    }
+
+    #[test]
+    fn draws_objects_by_borrowed_ids() {
+        use crate::color::{self, ColorSupport, NyanColor};
+        use crate::style::NyanStyle;
+
+        color::set_color_support(ColorSupport::TrueColor);
+        let mut obj = NyanObj::new();
+        obj.add_object("hp", Objects::new_text("HP 3"), (2, 1));
+        obj.set_style("hp", NyanStyle::new().fg(NyanColor::Red).bold())
+            .unwrap();
+
+        // An id built at runtime is borrowed, not moved or copied.
+        let id = String::from("hp");
+        let mut out = Vec::new();
+        obj.draw_object_in(&mut out, &id).unwrap();
+        obj.draw_with_move_in(&mut out, &id, Cursor::Move(0, 0))
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[2;3H\x1b[1;31mHP 3\n\x1b[0m"));
+        assert!(out.contains("\x1b[1;1H\x1b[1;31mHP 3"));
+
+        let missing = String::from("mp");
+        let error = obj.draw_object_in(&mut Vec::new(), &missing).unwrap_err();
+        assert_eq!(error.to_string(), "Object with ID \"mp\" is not found");
+    }
+
+    #[test]
+    fn profiler_summarizes_the_average_frame() {
+        use std::time::Duration;
+
+        use crate::profiler::{FrameTimings, Profiler};
+
+        let mut profiler = Profiler::new();
+        assert_eq!(profiler.average(), None);
+        assert_eq!(
+            profiler.summary(),
+            "input 0.0ms  draw 0.0ms  render 0.0ms  flush 0.0ms"
+        );
+
+        let us = Duration::from_micros;
+        for closure in [us(3000), us(5000), us(4000)] {
+            profiler.record(FrameTimings {
+                input: us(1500),
+                closure,
+                render: us(200),
+                flush: us(100),
+            });
+        }
+        assert_eq!(profiler.worst().unwrap().closure, us(5000));
+        assert_eq!(profiler.last().unwrap().closure, us(4000));
+        assert_eq!(
+            profiler.summary(),
+            "input 1.5ms  draw 4.0ms  render 0.2ms  flush 0.1ms"
+        );
+
+        profiler.reset();
+        assert_eq!(profiler.frames(), 0);
+        assert_eq!(profiler.last(), None);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_drawing_matches_sequential_drawing() {
+        use crate::buffer::Buffer;
+
+        let mut obj = NyanObj::new();
+        for row in 0..40u16 {
+            let text = format!("row {row} {}", "=".repeat(row as usize));
+            obj.add_object(
+                format!("row{row}"),
+                Objects::new_paragraph(text, 12),
+                (row % 7, row),
+            );
+        }
+        obj.set_cached("row3", true).unwrap();
+
+        let mut sequential = Buffer::new(40, 80);
+        obj.draw_all_in(&mut sequential).unwrap();
+        let mut parallel = Buffer::new(40, 80);
+        obj.draw_all_parallel_in(&mut parallel).unwrap();
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn changing_objects_marks_the_scene_dirty() {
+        use crate::style::NyanStyle;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("clock", Objects::new_text("12:00"), (0, 0));
+        let changes: [&dyn Fn(&mut NyanObj); 4] = [
+            &|obj| obj.move_object("clock", (1, 0)).unwrap(),
+            &|obj| obj.set_style("clock", NyanStyle::new().bold()).unwrap(),
+            &|obj| obj.set_visible("clock", false).unwrap(),
+            &|obj| {
+                obj.object_mut("clock");
+            },
+        ];
+        for change in changes {
+            obj.clear_dirty();
+            let generation = obj.generation();
+            change(&mut obj);
+            assert!(obj.is_dirty());
+            assert!(obj.generation() > generation);
+        }
+
+        // Looking does not change anything.
+        obj.clear_dirty();
+        let _ = obj.coordinate("clock");
+        let mut out = Vec::new();
+        obj.draw_all_in(&mut out).unwrap();
+        assert!(!obj.is_dirty());
+    }
+
+    #[test]
+    fn apps_skip_frames_only_when_their_own_scenes_are_unchanged() {
+        use crate::backend::TestBackend;
+        use crate::clock::MockClock;
+
+        let app = || {
+            App::with_backend(60, TestBackend::new(10, 1))
+                .clock(MockClock::new())
+                .skip_unchanged()
+        };
+        let frame = |app: &mut App, scene: &NyanObj| {
+            app.watch(scene);
+            let mut drawn = false;
+            app.draw_with(|_| drawn = true).unwrap();
+            drawn
+        };
+        let (mut first, mut second) = (app(), app());
+        let mut a = NyanObj::new();
+        a.add_object("a", Objects::new_text("a"), (0, 0));
+        let mut b = NyanObj::new();
+        b.add_object("b", Objects::new_text("b"), (0, 0));
+
+        assert!(frame(&mut first, &a) && frame(&mut second, &b));
+        assert!(!frame(&mut first, &a) && !frame(&mut second, &b));
+
+        // A change of the second scene redraws only the app that draws it.
+        b.move_object("b", (1, 0)).unwrap();
+        assert!(!frame(&mut first, &a));
+        assert!(frame(&mut second, &b));
+        assert!(!frame(&mut second, &b));
+
+        // A scene built anew counts as changed, even with as many changes as the old one.
+        let mut rebuilt = NyanObj::new();
+        rebuilt.add_object("a", Objects::new_text("A"), (0, 0));
+        assert_eq!(rebuilt.generation(), a.generation());
+        assert!(frame(&mut first, &rebuilt));
+
+        // An app that watches no scene keeps drawing every frame.
+        let mut unwatched = app();
+        for _ in 0..3 {
+            let mut drawn = false;
+            unwatched.draw_with(|_| drawn = true).unwrap();
+            assert!(drawn);
+        }
+    }
+
+    #[test]
+    fn damaged_regions_repaint_only_what_changed() {
+        use crate::rect::Rect;
+        use crate::style::NyanStyle;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("clock", Objects::new_text("12:00"), (0, 0));
+        obj.add_object("cat", Objects::new_text("=^.^="), (0, 4));
+        obj.add_object("badge", Objects::new_text("[on]"), (3, 0));
+        obj.clear_dirty();
+        assert!(obj.damage().is_empty());
+
+        // The clock is repainted, and the badge drawn over it, but the cat is left alone.
+        obj.set_style("clock", NyanStyle::new().bold()).unwrap();
+        assert_eq!(
+            obj.damage(),
+            vec![Rect::new(0, 0, 5, 1), Rect::new(0, 0, 5, 1)]
+        );
+        let mut out = Vec::new();
+        obj.draw_damaged_in(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("12:00") && out.contains("[on]"));
+        assert!(!out.contains("=^.^="));
+
+        // A hidden object leaves its box to be cleared, and nothing is drawn there.
+        obj.clear_dirty();
+        obj.set_visible("cat", false).unwrap();
+        assert_eq!(obj.damage(), vec![Rect::new(0, 4, 5, 1)]);
+        let mut out = Vec::new();
+        obj.draw_damaged_in(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\x1b[5;1H     "));
+        assert!(!out.contains("=^.^=") && !out.contains("12:00"));
+
+        // Setting what is already set damages nothing.
+        obj.clear_dirty();
+        obj.move_object("clock", (0, 0)).unwrap();
+        assert!(obj.damage().is_empty());
+    }
+
+    #[test]
+    fn damage_stays_bounded_without_clear_dirty() {
+        use crate::rect::Rect;
+
+        let position = |frame: u32| ((frame % 70) as u16, (frame % 20) as u16);
+        let mut obj = NyanObj::new();
+        obj.add_object("cat", Objects::new_text("=^.^="), (0, 0));
+        obj.clear_dirty();
+
+        // Frames drawn with draw_all_in never mark the scene as drawn.
+        for frame in 0..100_000 {
+            obj.move_object("cat", position(frame)).unwrap();
+            if frame % 1000 == 0 {
+                obj.draw_all_in(&mut Vec::new()).unwrap();
+            }
+            assert!(obj.damage().len() <= 65);
+        }
+
+        // The merged damage still covers the boxes the cat left.
+        let damage = obj.damage();
+        for frame in 99_000..100_000 {
+            let (x, y) = position(frame);
+            let left = Rect::new(x, y, 5, 1);
+            assert!(damage.iter().any(|rect| rect.x <= left.x
+                && rect.y <= left.y
+                && rect.right() >= left.right()
+                && rect.bottom() >= left.bottom()));
+        }
+        obj.clear_dirty();
+        assert!(obj.damage().is_empty());
+    }
+
+    #[test]
+    fn input_thread_keeps_user_events_and_stops_on_drop() {
+        use crate::event::{EventQueue, NyanEvent};
+
+        let mut queue = EventQueue::new().input_thread(240);
+        queue.sender().send("loaded").unwrap();
+        assert!(matches!(queue.get_event(), Ok(NyanEvent::User("loaded"))));
+
+        // Dropping the queue stops the thread instead of leaving it polling.
+        drop(queue);
+    }
+
+    #[test]
+    fn scene_snapshots_are_independent_of_the_scene() {
+        use crate::app::App;
+        use crate::renderer::Renderer;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("score", Objects::new_text("meow"), (0, 0));
+        obj.set_cached("score", true).unwrap();
+        let snapshot = obj.clone();
+        *obj.object_mut("score").unwrap() = Objects::new_text("purr");
+
+        let mut out = Vec::new();
+        snapshot.draw_all_in(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("meow") && !out.contains("purr"));
+
+        // Without a terminal the render thread stops on its first frame, which `stop()`
+        // reports instead of hanging.
+        let renderer = Renderer::spawn(|| App::new(60));
+        let _ = renderer.submit(snapshot);
+        let _ = renderer.stop();
+    }
+
+    #[test]
+    fn stats_count_objects_and_cached_output() {
+        let mut obj = NyanObj::new();
+        for index in 0..3 {
+            let id = format!("temp{index}");
+            obj.add_object(id, Objects::new_text(format!("{index}")), (0, index));
+        }
+        obj.set_cached("temp0", true).unwrap();
+        let before = obj.stats();
+        assert_eq!((before.objects, before.visible, before.cached), (3, 3, 0));
+
+        // Cached output is counted once it was drawn.
+        let mut out = Vec::new();
+        obj.draw_all_in(&mut out).unwrap();
+        let drawn = obj.stats();
+        assert_eq!(drawn.cached, 1);
+        assert!(drawn.cached_bytes > 0);
+        assert!(drawn.memory >= before.memory + drawn.cached_bytes);
+        assert!(drawn
+            .to_string()
+            .starts_with("3 objects (3 visible)  1 cached"));
+    }
+
+    #[test]
+    fn objects_are_found_by_indexed_ids() {
+        let mut obj = NyanObj::new();
+        obj.add_object("frame", Objects::new_text("+--+"), (0, 0));
+        obj.add_object(String::from("hud_score"), Objects::new_text("42"), (0, 1));
+
+        // Lookups hash the id once, whether it is borrowed or owned.
+        assert_eq!(obj.coordinate("hud_score"), Some((0, 1)));
+        assert_eq!(obj.coordinate(String::from("frame")), Some((0, 0)));
+        assert_eq!(obj.coordinate("hud"), None);
+        assert!(obj.move_object("menu", (1, 1)).is_err());
+
+        // A snapshot keeps the positions of its ids.
+        let snapshot = obj.clone();
+        assert_eq!(snapshot.bounds("hud_score"), obj.bounds("hud_score"));
+        assert_eq!(snapshot.ids().collect::<Vec<_>>(), ["frame", "hud_score"]);
+
+        // Removing an object moves the later ones, and duplicate ids find the first object.
+        obj.add_object("hud_score", Objects::new_text("7"), (0, 2));
+        obj.remove_object("frame").unwrap();
+        assert_eq!(obj.coordinate("frame"), None);
+        assert_eq!(obj.coordinate("hud_score"), Some((0, 1)));
+        obj.remove_object("hud_score").unwrap();
+        assert_eq!(obj.coordinate("hud_score"), Some((0, 2)));
+
+        // The ids of removed temporary objects are forgotten.
+        let mut memory = Vec::new();
+        for round in 0..3 {
+            for i in 0..100 {
+                let id = format!("particle_{round}_{i}");
+                obj.add_object(id, Objects::new_text("*"), (1, 1));
+            }
+            for i in 0..100 {
+                obj.remove_object(format!("particle_{round}_{i}")).unwrap();
+            }
+            memory.push(obj.stats().memory);
+        }
+        assert_eq!(memory[1], memory[0]);
+        assert_eq!(memory[2], memory[0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn scenes_round_trip_through_serde() {
+        use crate::color::NyanColor;
+        use crate::cursor::Cursor;
+        use crate::rect::Edges;
+        use crate::style::NyanStyle;
+
+        let style = NyanStyle::new()
+            .fg(NyanColor::Rgb(255, 128, 0))
+            .bold()
+            .italic();
+        let mut obj = NyanObj::new();
+        obj.add_object("title", Objects::new_text("nyan"), (2, 1));
+        obj.add_object("menu", Objects::new_list(["Start", "Quit"], 2), (2, 3));
+        obj.set_style("title", style).unwrap();
+        obj.set_padding("menu", Edges::all(1)).unwrap();
+        obj.set_cached("menu", true).unwrap();
+
+        let json = serde_json::to_string(&obj).unwrap();
+        assert!(json.contains(r#""attributes":["Bold","Italic"]"#));
+        let loaded: NyanObj = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.ids().collect::<Vec<_>>(), ["title", "menu"]);
+        assert_eq!(loaded.style("title"), Some(style));
+        assert_eq!(loaded.bounds("menu"), obj.bounds("menu"));
+        assert_eq!(loaded.is_cached("menu"), Some(true));
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+
+        // Blocks cannot be drawn yet, so scenes with one are refused.
+        let error = serde_json::from_str::<NyanObj>(r#"[{ "id": "wall", "object": "Block" }]"#)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("Not supported: object \"wall\" is a block"));
+
+        let moves: Vec<Cursor> =
+            serde_json::from_str(r#"[{"Move":[1,2]},"SavePosition"]"#).unwrap();
+        assert_eq!(moves, [Cursor::Move(1, 2), Cursor::SavePosition]);
+    }
+
+    #[cfg(all(feature = "toml", feature = "yaml"))]
+    #[test]
+    fn layout_files_load_objects_and_layouts() {
+        use crate::loader;
+        use crate::rect::Rect;
+        use crate::style::NyanStyle;
+
+        let toml = r#"
+            [[objects]]
+            id = "title"
+            text = "nyan"
+            style = { fg = "Cyan", attributes = ["Bold"] }
+
+            [[objects]]
+            id = "menu"
+            type = "list"
+            items = ["Start", "Quit"]
+            height = 2
+            padding = { left = 2 }
+
+            [layout]
+            type = "vstack"
+            spacing = 1
+            children = [{ id = "title" }, { id = "menu", flex = 1 }]
+        "#;
+        let yaml = r#"
+            objects:
+              - id: title
+                text: nyan
+                style: { fg: Cyan, attributes: [Bold] }
+              - id: menu
+                type: list
+                items: [Start, Quit]
+                height: 2
+                padding: { left: 2 }
+            layout:
+              type: vstack
+              spacing: 1
+              children: [{ id: title }, { id: menu, flex: 1 }]
+        "#;
+        for mut screen in [
+            loader::from_toml_str(toml).unwrap(),
+            loader::from_yaml_str(yaml).unwrap(),
+        ] {
+            let style = NyanStyle::new().fg(crate::color::NyanColor::Cyan).bold();
+            assert_eq!(screen.objects.style("title"), Some(style));
+            screen.relayout(Rect::new(0, 0, 20, 10)).unwrap();
+            assert_eq!(screen.objects.coordinate("menu"), Some((0, 2)));
+            assert_eq!(screen.objects.padding("menu").unwrap().left, 2);
+        }
+
+        // Objects without what their type needs are reported by id.
+        let error = loader::from_toml_str("[[objects]]\nid = \"log\"\ntype = \"list\"")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("\"log\" needs a height"));
+
+        // Blocks cannot be drawn yet, so they fail to load instead of on the first frame.
+        let error =
+            loader::from_toml_str("[[objects]]\nid = \"wall\"\ntype = \"block\"").unwrap_err();
+        assert!(matches!(error, crate::errors::NyanError::InvalidLayout(_)));
+        assert!(error.to_string().contains("\"wall\" is a block"));
+    }
+
+    #[test]
+    fn frames_export_as_ansi_and_html() {
+        use crate::buffer::Buffer;
+        use std::io::Write;
+
+        let mut frame = Buffer::new(10, 3);
+        write!(frame, "\x1b[7mcat\x1b[0m & 猫\r\n\r\n\x1b[4;34mnyan").unwrap();
+
+        let ansi = frame.to_ansi();
+        assert_eq!(ansi, "\x1b[7mcat\x1b[0m & 猫\n\n\x1b[4;34mnyan\x1b[0m\n");
+
+        let html = frame.to_html();
+        assert!(
+            html.contains(r#"<span style="color:#000000;background:#e5e5e5">cat</span> &amp; 猫"#)
+        );
+        assert!(
+            html.contains(r#"<span style="color:#0000ee;text-decoration:underline">nyan</span>"#)
+        );
+        assert!(html.ends_with("</pre>\n</body>\n</html>\n"));
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn logger_keeps_the_latest_records_for_the_log_view() {
+        use crate::log_view::{LogLevel, LogView};
+        use crate::logger::NyanLogger;
+        use log::Log;
+
+        let logger = NyanLogger::new(2).level(log::LevelFilter::Info);
+        let buffer = logger.buffer();
+        let log = |level, message: &str| {
+            logger.log(
+                &log::Record::builder()
+                    .level(level)
+                    .target("app")
+                    .args(format_args!("{message}"))
+                    .build(),
+            )
+        };
+        log(log::Level::Debug, "skipped");
+        log(log::Level::Info, "dropped");
+        log(log::Level::Warn, "low memory");
+        log(log::Level::Error, "boom");
+        assert_eq!(buffer.len(), 2);
+
+        let mut view = LogView::new(10);
+        assert_eq!(buffer.drain_into(&mut view), 2);
+        assert!(buffer.is_empty());
+        view.set_min_level(LogLevel::Error);
+        assert_eq!(view.visible_lines(), ["ERROR app: boom"]);
+    }
+
+    #[test]
+    fn apps_draw_to_a_test_backend() {
+        use crate::app::App;
+        use crate::backend::TestBackend;
+
+        let backend = TestBackend::new(12, 2);
+        let mut app = App::with_backend(1000, backend.clone())
+            .buffered()
+            .raw_mode();
+        assert_eq!(app.size().unwrap(), (12, 2));
+
+        let mut obj = NyanObj::new();
+        obj.add_object("score", Objects::new_text("Score: 10"), (0, 0));
+        obj.add_object("lives", Objects::new_text("x3"), (0, 1));
+        app.draw_with(|app| obj.draw_all_in(app).unwrap()).unwrap();
+        assert!(backend.is_raw_mode());
+        assert_eq!(backend.screen().to_ansi(), "Score: 10\nx3\n");
+
+        // Only the changed digits are written in the next frame.
+        *obj.object_mut("score").unwrap() = Objects::new_text("Score: 25");
+        let written = backend.output().len();
+        app.draw_with(|app| obj.draw_all_in(app).unwrap()).unwrap();
+        assert_eq!(backend.screen().to_ansi(), "Score: 25\nx3\n");
+        let frame = String::from_utf8_lossy(&backend.output()[written..]).into_owned();
+        assert!(frame.contains("\x1b[1;8H25") && !frame.contains("Score"));
+
+        app.exit().unwrap();
+        assert!(!backend.is_raw_mode());
+    }
+
+    #[test]
+    fn remote_sessions_decode_client_input() {
+        use crate::backend::Backend;
+        use crate::event::{NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::input::{NyanInput, NyanKey};
+        use crate::remote;
+
+        // A telnet WILL NAWS (skipped), keys, mouse, a paste, resizes and a lone Esc.
+        let mut bytes = vec![255, 251, 31];
+        bytes.extend_from_slice(b"Hi\r\0\x1b[1;2A\x1b[3~\x1bOP\x1b[15~\x03");
+        bytes.extend_from_slice(b"\x1b[<0;5;3M\x1b[<65;1;1M");
+        bytes.extend_from_slice(b"\x1b[200~pasted\r\ntext\x1b[201~\x1b[8;30;100t\x1b");
+        bytes.extend_from_slice(&[255, 250, 31, 0, 120, 0, 40, 255, 240]);
+
+        let (backend, mut input) = remote::session(std::io::Cursor::new(bytes), Vec::new(), 80, 24);
+        let mut events = Vec::new();
+        while let Ok(event) = input.get_event() {
+            if event != NyanEvent::Tick {
+                events.push(event);
+            }
+        }
+        assert!(!input.is_connected());
+        let mouse = |kind, x, y| NyanEvent::Mouse(NyanMouse { kind, x, y });
+        assert_eq!(
+            events,
+            [
+                NyanEvent::Key(NyanInput::Shift(&NyanInput::Key(NyanKey::H))),
+                NyanEvent::Key(NyanInput::Key(NyanKey::I)),
+                NyanEvent::Key(NyanInput::Enter),
+                NyanEvent::Key(NyanInput::Shift(&NyanInput::UpAllow)),
+                NyanEvent::Key(NyanInput::Delete),
+                NyanEvent::Key(NyanInput::FunctionKey(1)),
+                NyanEvent::Key(NyanInput::FunctionKey(5)),
+                NyanEvent::Key(NyanInput::Ctrl(NyanKey::C)),
+                mouse(NyanMouseKind::Down(NyanMouseButton::Left), 4, 2),
+                mouse(NyanMouseKind::ScrollDown, 0, 0),
+                NyanEvent::Paste("pasted\r\ntext".to_string()),
+                NyanEvent::Resize(100, 30),
+                NyanEvent::Key(NyanInput::Esc),
+                NyanEvent::Resize(120, 40),
+            ]
+        );
+        assert_eq!(backend.size().unwrap(), (120, 40));
+    }
+
+    #[test]
+    fn remote_pastes_are_bounded() {
+        use crate::event::NyanEvent;
+        use crate::remote;
+
+        // A paste that does not end within the limit is reported in parts.
+        let text = "nyan ねこ ".repeat(200_000);
+        let mut bytes = b"\x1b[200~".to_vec();
+        bytes.extend_from_slice(text.as_bytes());
+        bytes.extend_from_slice(b"\x1b[201~");
+
+        let (_, mut input) = remote::session(std::io::Cursor::new(bytes), Vec::new(), 80, 24);
+        let mut pastes = Vec::new();
+        while let Ok(event) = input.get_event() {
+            if let NyanEvent::Paste(part) = event {
+                pastes.push(part);
+            }
+        }
+        assert!(pastes.len() > 1);
+        assert!(pastes.iter().all(|part| part.len() <= (1 << 20) + 1024));
+        assert_eq!(pastes.concat(), text);
+    }
+
+    #[test]
+    fn remote_subnegotiations_and_sizes_are_bounded() {
+        use crate::backend::Backend;
+        use crate::event::NyanEvent;
+        use crate::input::{NyanInput, NyanKey};
+        use crate::remote;
+
+        // A subnegotiation that never ends is given up on, and input goes on.
+        let mut bytes = vec![255, 250, 0];
+        bytes.extend_from_slice(&[b'.'; 300]);
+        // A window of 65535 × 65535 cells, and the same as an xterm report.
+        bytes.extend_from_slice(&[255, 250, 31, 255, 255, 255, 255, 255, 240]);
+        bytes.extend_from_slice(b"\x1b[8;65535;65535tq");
+
+        let (backend, mut input) = remote::session(std::io::Cursor::new(bytes), Vec::new(), 80, 24);
+        let mut events = Vec::new();
+        while let Ok(event) = input.get_event() {
+            if !matches!(
+                event,
+                NyanEvent::Tick | NyanEvent::Key(NyanInput::Key(NyanKey::OtherKey('.')))
+            ) {
+                events.push(event);
+            }
+        }
+        assert_eq!(
+            events,
+            [
+                NyanEvent::Resize(1000, 1000),
+                NyanEvent::Resize(1000, 1000),
+                NyanEvent::Key(NyanInput::Key(NyanKey::Q)),
+            ]
+        );
+        assert_eq!(backend.size().unwrap(), (1000, 1000));
+    }
+
+    #[test]
+    fn remote_letters_are_shifted_without_leaking() {
+        use crate::event::NyanEvent;
+        use crate::input::{NyanInput, NyanKey};
+        use crate::remote;
+
+        let (_, mut input) = remote::session(std::io::Cursor::new(b"AA\x1bQ"), Vec::new(), 80, 24);
+        let mut keys = Vec::new();
+        while let Ok(event) = input.get_event() {
+            if let NyanEvent::Key(key) = event {
+                keys.push(key);
+            }
+        }
+        assert_eq!(
+            keys,
+            [
+                NyanInput::Shift(&NyanInput::Key(NyanKey::A)),
+                NyanInput::Shift(&NyanInput::Key(NyanKey::A)),
+                NyanInput::Shift(&NyanInput::Alt(NyanKey::Q)),
+            ]
+        );
+        // Uppercase letters share one static input instead of leaking one each.
+        let (NyanInput::Shift(first), NyanInput::Shift(second)) = (keys[0], keys[1]) else {
+            panic!("uppercase letters are Shift inputs");
+        };
+        assert!(std::ptr::eq(first, second));
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn xterm_input_is_decoded_and_resizes_the_backend() {
+        use crate::backend::Backend;
+        use crate::event::NyanEvent;
+        use crate::input::NyanInput;
+        use crate::xterm::Xterm;
+        use wasm_bindgen::JsCast;
+
+        // Nothing is written, so no JavaScript is needed.
+        let xterm = Xterm::new(wasm_bindgen::JsValue::NULL.unchecked_into(), 80, 24);
+        let (backend, mut input) = (xterm.backend(), xterm.input());
+        xterm.feed("a\x1b[B");
+        xterm.resize(100, 30);
+
+        assert_eq!(backend.size().unwrap(), (100, 30));
+        assert_eq!(
+            input.drain_events(),
+            [
+                NyanEvent::Key(NyanInput::Key(NyanKey::A)),
+                NyanEvent::Key(NyanInput::DownAllow),
+                NyanEvent::Resize(100, 30),
+            ]
+        );
+        assert_eq!(input.get_event(), NyanEvent::Tick);
+    }
+
+    #[test]
+    fn clipboard_is_copied_and_read_with_osc_52() {
+        use crate::app::App;
+        use crate::backend::TestBackend;
+        use crate::clipboard;
+        use crate::event::NyanEvent;
+        use crate::remote;
+
+        let backend = TestBackend::new(10, 2);
+        let mut app = App::with_backend(1000, backend.clone());
+        app.copy_to_clipboard("nyan 猫!").unwrap();
+        app.request_clipboard().unwrap();
+        assert_eq!(
+            backend.output(),
+            b"\x1b]52;c;bnlhbiDnjKsh\x07\x1b]52;c;?\x07".to_vec()
+        );
+
+        for text in ["", "a", "ab", "abc", "nyan 猫!"] {
+            let sequence = clipboard::copy_sequence(text);
+            let params = &sequence[2..sequence.len() - 1];
+            assert_eq!(clipboard::parse_reply(params).as_deref(), Some(text));
+        }
+
+        // The answer of the terminal, ended with ST instead of BEL.
+        let answer: &[u8] = b"\x1b]52;c;bnlhbiDnjKsh\x1b\\";
+        let (_backend, mut input) = remote::session(answer, Vec::new(), 80, 24);
+        assert_eq!(
+            input.get_event().unwrap(),
+            NyanEvent::Paste("nyan 猫!".into())
+        );
+    }
+
+    #[test]
+    fn figlet_fonts_render_kerned_and_code_tagged_glyphs() {
+        use crate::figlet::FigletFont;
+
+        // `#` for the letters, hardblanks for the space, `?` for the rest.
+        let mut source = String::from("flf2a$ 2 1 6 0 2\nA test font\nwith two comments\n");
+        for ch in ' '..='~' {
+            let glyph = match ch {
+                ' ' => "$@\n$@@\n",
+                'i' => " #@\n #@@\n",
+                'l' => "# @\n##@@\n",
+                _ => "??@\n??@@\n",
+            };
+            source.push_str(glyph);
+        }
+        // The German umlauts (empty here), then a code-tagged glyph.
+        source.push_str(&"@\n@@\n".repeat(7));
+        source.push_str("0x732B  CAT\n=^.^=#\n(   )##\n");
+
+        let font = FigletFont::parse(&source).unwrap();
+        // `i` moves onto the space at the end of `l`; the hardblank keeps its space.
+        assert_eq!(font.render("li i"), "# # #\n### #");
+        assert_eq!(font.render("猫\nl"), "=^.^=\n(   )\n#\n##");
+        assert_eq!(font.render("é"), "\n");
+
+        let full_width = source.replacen(" 0 2\n", " -1 2\n", 1);
+        let font = FigletFont::parse(&full_width).unwrap();
+        assert_eq!(font.render("li"), "#  #\n## #");
+
+        assert!(FigletFont::parse("flf2a$ 2 1 6 0 0\nx@\n").is_err());
+        assert!(FigletFont::parse("not a font").is_err());
+    }
+
+    #[test]
+    fn oversized_gifs_are_refused_before_decoding() {
+        use crate::errors::NyanError;
+        use crate::gif::Gif;
+
+        // Only a header, claiming 65535 × 65535 pixels.
+        let header = b"GIF89a\xff\xff\xff\xff";
+        assert_eq!(header.len(), 10);
+        match Gif::decode(header) {
+            Err(NyanError::InvalidImage(message)) => assert!(message.contains("too large")),
+            other => panic!("expected InvalidImage, got {other:?}"),
+        }
+        // A small image cut short is refused for that instead.
+        match Gif::decode(b"GIF89a\x02\x00\x02\x00") {
+            Err(NyanError::InvalidImage(message)) => assert_eq!(message, "not a valid GIF"),
+            other => panic!("expected InvalidImage, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn gifs_decode_compose_and_play_their_frames() {
+        use crate::buffer::Buffer;
+        use crate::gif::Gif;
+        use crate::widget::Widget;
+        use std::time::Duration;
+
+        // An image whose LZW data clears the table before every index, so every code has 3 bits.
+        let image = |left: u16, top: u16, width: u16, height: u16, indices: &[u8]| {
+            let mut codes = Vec::new();
+            for index in indices {
+                codes.extend([4, *index]);
+            }
+            codes.push(5);
+            let mut data = vec![0u8; codes.len() * 3 / 8 + 1];
+            for (i, code) in codes.iter().enumerate() {
+                let bit = i * 3;
+                data[bit / 8] |= code << (bit % 8);
+                if bit % 8 > 5 {
+                    data[bit / 8 + 1] |= code >> (8 - bit % 8);
+                }
+            }
+            let mut bytes = vec![0x2c];
+            for value in [left, top, width, height] {
+                bytes.extend(value.to_le_bytes());
+            }
+            bytes.extend([0, 2, data.len() as u8]);
+            bytes.extend(data);
+            bytes.push(0);
+            bytes
+        };
+        let control = |delay: u16, transparent: Option<u8>| {
+            let [low, high] = delay.to_le_bytes();
+            let flags = u8::from(transparent.is_some());
+            vec![0x21, 0xf9, 4, flags, low, high, transparent.unwrap_or(0), 0]
+        };
+
+        let mut file = b"GIF89a\x02\x00\x02\x00\x81\x00\x00".to_vec();
+        file.extend([255, 0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0]); // red, green, blue, black
+        file.extend(control(5, Some(3)));
+        file.extend(image(0, 0, 2, 2, &[0, 3, 2, 1])); // the top right pixel is transparent
+        file.extend(control(0, None));
+        file.extend(image(1, 0, 1, 1, &[1])); // paints it green
+        file.push(0x3b);
+
+        let mut gif = Gif::decode(&file).unwrap();
+        assert_eq!(
+            (gif.dimensions(), gif.len(), gif.size()),
+            ((2, 2), 2, (2, 1))
+        );
+        assert_eq!(gif.delay(0), Some(Duration::from_millis(50)));
+        assert_eq!(gif.delay(1), Some(Duration::from_millis(100)));
+        assert_eq!(gif.pixel(0, 0), Some((255, 0, 0)));
+        assert_eq!(gif.pixel(1, 0), None);
+        assert_eq!(gif.pixel(0, 1), Some((0, 0, 255)));
+
+        let draw = |gif: &Gif| {
+            let mut frame = Buffer::new(2, 1);
+            gif.draw(&mut frame).unwrap();
+            [frame.get(0, 0).unwrap().ch, frame.get(1, 0).unwrap().ch]
+        };
+        assert_eq!(draw(&gif), ['▀', '▄']);
+
+        assert!(gif.advance(Duration::from_millis(60)));
+        assert_eq!(gif.current(), 1);
+        assert_eq!(gif.pixel(1, 0), Some((0, 255, 0)));
+        assert_eq!(draw(&gif), ['▀', '▀']);
+        // Back to the first frame, then one and a half loops of 150 ms on.
+        assert!(gif.advance(Duration::from_millis(100)));
+        assert_eq!(gif.current(), 0);
+        assert!(gif.advance(Duration::from_millis(225)));
+        assert_eq!(gif.current(), 1);
+
+        assert!(Gif::decode(b"GIF89a").is_err());
+        assert!(Gif::decode(b"PNG").is_err());
+    }
+
+    #[cfg(all(feature = "csv", feature = "json"))]
+    #[test]
+    fn tables_and_lists_fill_from_csv_and_json() {
+        use crate::layout::Align;
+        use crate::list_view::ListView;
+        use crate::table_view::{SortOrder, TableView};
+
+        let csv = "name,size,note\n\"b, c.txt\",20,\"said \"\"hi\"\"\"\na.out,9\n";
+        let mut table = TableView::from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(
+            table.selected_row().unwrap(),
+            ["b, c.txt", "20", "said \"hi\""]
+        );
+        table.sort_by(1, SortOrder::Ascending);
+        table.select(0);
+        assert_eq!(table.selected_row().unwrap(), ["a.out", "9", ""]);
+        assert_eq!(
+            TableView::from_csv("a,b\n1,x\n".as_bytes()).unwrap(),
+            TableView::new([
+                crate::table_view::Column::new("a").align(Align::End),
+                crate::table_view::Column::new("b"),
+            ])
+            .row(["1", "x"])
+        );
+
+        let value = serde_json::json!({
+            "data": {
+                "cats": [
+                    { "name": "mike", "age": 3 },
+                    { "name": "tama", "toys": ["ball"], "age": null },
+                ],
+                "tags": ["calm", 7, true, null],
+            }
+        });
+        let table = TableView::from_json(&value, "/data/cats").unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.selected_row().unwrap(), ["3", "mike", ""]);
+        let list = ListView::from_json(&value, "/data/tags").unwrap();
+        assert_eq!(list.items(), ["calm", "7", "true", ""]);
+
+        for path in ["/data/missing", "/data", "/data/tags"] {
+            let error = TableView::from_json(&value, path).unwrap_err();
+            assert!(
+                matches!(error, crate::errors::NyanError::InvalidData(_)),
+                "{path}"
+            );
+        }
+        assert!(ListView::from_json(&value, "/data/cats/0").is_err());
+    }
+
+    #[test]
+    fn errors_are_typed_and_wrap_io_errors() {
+        use crate::errors::{NyanError, NyanResult};
+        use std::borrow::Cow;
+        use std::io;
+
+        let mut obj = NyanObj::new();
+        match obj.set_visible("ghost", false) {
+            Err(NyanError::ObjectNotFound(id)) => assert_eq!(id, "ghost"),
+            other => panic!("expected ObjectNotFound, got {other:?}"),
+        }
+
+        fn read() -> NyanResult<()> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "gone"))?;
+            Ok(())
+        }
+        let error = read().unwrap_err();
+        match &error {
+            NyanError::Io(source) => assert_eq!(source.kind(), io::ErrorKind::BrokenPipe),
+            other => panic!("expected Io, got {other:?}"),
+        }
+        assert_eq!(error.to_string(), "I/O error: gone");
+        assert!(std::error::Error::source(&error).is_some());
+
+        let id = String::from("menu");
+        let borrowed = NyanError::ObjectNotFound(Cow::Borrowed(&id));
+        let owned: NyanError<'static> = borrowed.clone().into_owned();
+        drop(id);
+        assert_eq!(owned.to_string(), "Object with ID \"menu\" is not found");
+    }
+
+    #[test]
+    fn error_overlay_shows_the_errors_of_a_frame() {
+        use crate::app::App;
+        use crate::backend::TestBackend;
+        use crate::color::NyanColor;
+        use crate::cursor::Cursor;
+        use crate::errors::NyanError;
+
+        let backend = TestBackend::new(40, 8);
+        let mut app = App::with_backend(1000, backend.clone())
+            .buffered()
+            .error_overlay();
+        let mut obj = NyanObj::new();
+        obj.add_object("title", Objects::new_text("nyan"), (0, 0));
+
+        app.draw_with(|app| {
+            let drawn = obj.draw_object_in(app, "title");
+            assert_eq!(app.report(drawn), Some(()));
+            let missing = obj.draw_object_in(app, "ttile");
+            assert_eq!(app.report(missing), None);
+            let moved = app.move_cursor(Cursor::Move(2, 1));
+            app.report(moved);
+        })
+        .unwrap();
+        assert!(matches!(
+            app.frame_errors(),
+            [NyanError::ObjectNotFound(id)] if id == "ttile"
+        ));
+        assert_eq!(app.cursor_pos(), (2, 1));
+
+        let screen = backend.screen();
+        let row = |y: u16| -> String { (0..40).map(|x| screen.get(x, y).unwrap().ch).collect() };
+        assert!(row(0).starts_with("nyan"));
+        assert!(row(5).contains(" 1 error "));
+        assert!(row(6).contains(" Object with ID \"ttile\" is not found"));
+        assert_eq!(screen.get(1, 6).unwrap().style.bg, Some(NyanColor::Red));
+        assert_eq!(row(4).trim(), "");
+
+        // The errors last one frame.
+        app.draw_with(|app| obj.draw_object_in(app, "title").unwrap())
+            .unwrap();
+        assert!(app.frame_errors().is_empty());
+        assert!(!backend.screen().to_ansi().contains("error"));
+    }
+
+    #[test]
+    fn frames_are_compared_with_text_and_snapshots() {
+        use crate::testing;
+        use std::panic;
+
+        let mut scene = NyanObj::new();
+        scene.add_object("score", Objects::new_text("Score: 10"), (0, 0));
+        scene.add_object("lives", Objects::new_text("x3"), (1, 2));
+        let frame = testing::render_scene(&scene, 12, 4).unwrap();
+        crate::assert_frame_eq!(frame, "\nScore: 10   \n\n x3\n\n");
+        assert!(testing::render_scene(&scene, 0, 0).is_ok());
+
+        let failure =
+            panic::catch_unwind(|| crate::assert_frame_eq!(frame, "Score: 25\n\n x3")).unwrap_err();
+        let message = failure.downcast_ref::<String>().unwrap();
+        assert_eq!(
+            message,
+            "the frame does not match the expected text:\n\
+             -   0 |Score: 25\n+   0 |Score: 10\n    1 |\n    2 | x3\n"
+        );
+
+        let dir = std::env::temp_dir().join(format!("nyan-snapshots-{}", std::process::id()));
+        let path = dir.join("nested").join("hud.txt");
+
+        // A missing snapshot fails, and is only written when asked for.
+        let failure = panic::catch_unwind(|| testing::assert_snapshot(&frame, &path)).unwrap_err();
+        let message = failure.downcast_ref::<String>().unwrap();
+        assert!(
+            message.contains("hud.txt does not exist (set NYAN_UPDATE_SNAPSHOTS=1 to write it)")
+        );
+        assert!(!path.exists());
+        std::env::set_var(testing::UPDATE_VAR, "1");
+        testing::assert_snapshot(&frame, &path);
+        std::env::remove_var(testing::UPDATE_VAR);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "Score: 10\n\n x3\n"
+        );
+        testing::assert_snapshot(&frame, &path);
+
+        *scene.object_mut("score").unwrap() = Objects::new_text("Score: 25");
+        let changed = testing::render_scene(&scene, 12, 4).unwrap();
+        let failure =
+            panic::catch_unwind(|| testing::assert_snapshot(&changed, &path)).unwrap_err();
+        let message = failure.downcast_ref::<String>().unwrap();
+        assert!(message.contains("hud.txt (set NYAN_UPDATE_SNAPSHOTS=1 to update it)"));
+        assert!(message.contains("-   0 |Score: 10\n+   0 |Score: 25\n"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn mock_clocks_pace_frames_without_waiting() {
+        use crate::app::App;
+        use crate::backend::TestBackend;
+        use crate::clock::{Clock, MockClock};
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use std::time::{Duration, Instant};
+
+        let clock = MockClock::new();
+        let mut app = App::with_backend(2, TestBackend::new(4, 1)).clock(clock.clone());
+        let fired = Rc::new(Cell::new(false));
+        let when = Rc::clone(&fired);
+        app.scheduler()
+            .after_fn(Duration::from_millis(1200), move || when.set(true));
+
+        let wall = Instant::now();
+        let mut deltas = Vec::new();
+        for _ in 0..4 {
+            app.draw(|| ()).unwrap();
+            deltas.push((app.delta_time(), fired.get()));
+        }
+        assert!(wall.elapsed() < Duration::from_millis(1000));
+        let half = Duration::from_millis(500);
+        // The timer is due 1.2 s in, during the fourth frame.
+        assert_eq!(
+            deltas,
+            [
+                (Duration::ZERO, false),
+                (half, false),
+                (half, false),
+                (half, true)
+            ]
+        );
+        assert_eq!(clock.elapsed(), Duration::from_secs(2));
+        assert_eq!(app.now(), clock.now());
+    }
+
+    #[test]
+    fn scripted_drivers_play_into_the_app_loop() {
+        use crate::event::NyanEvent;
+        use crate::input::{NyanInput, NyanKey};
+        use crate::testing::TestDriver;
+        use std::io::Write;
+        use std::panic::{self, AssertUnwindSafe};
+        use std::time::Duration;
+
+        let mut driver = TestDriver::new(16, 2)
+            .type_text("Hi!")
+            .user(7)
+            .wait(Duration::from_secs(1))
+            .resize(20, 3)
+            .expect("ticks: 10")
+            .key(NyanInput::Esc)
+            .key(NyanInput::Enter);
+        let mut app = driver.app(10);
+        let mut seen: Vec<NyanEvent<i32>> = Vec::new();
+        app.run(
+            &mut seen,
+            &mut driver,
+            |seen, app| {
+                let ticks = seen.iter().filter(|e| **e == NyanEvent::Tick).count();
+                write!(app, "ticks: {ticks}").unwrap();
+            },
+            |seen, _, event| {
+                let go_on = event != NyanEvent::Key(NyanInput::Esc);
+                seen.push(event);
+                go_on
+            },
+        )
+        .unwrap();
+
+        // The loop stopped at Esc, before the last key of the script.
+        assert!(!driver.is_done());
+        assert_eq!(seen.len(), 3 + 1 + 10 + 1 + 1);
+        assert_eq!(
+            seen[0],
+            NyanEvent::Key(NyanInput::Shift(&NyanInput::Key(NyanKey::H)))
+        );
+        assert_eq!(seen[1], NyanEvent::Key(NyanInput::Key(NyanKey::I)));
+        assert_eq!(
+            seen[2],
+            NyanEvent::Key(NyanInput::Key(NyanKey::OtherKey('!')))
+        );
+        assert_eq!(seen[3], NyanEvent::User(7));
+        assert_eq!(seen[14], NyanEvent::Resize(20, 3));
+        assert_eq!(driver.screen().size(), (20, 3));
+        assert_eq!(driver.clock().elapsed(), Duration::from_millis(1600));
+
+        let mut driver: TestDriver = TestDriver::new(8, 1).expect("bye");
+        let mut app = driver.app(10);
+        let failure = panic::catch_unwind(AssertUnwindSafe(|| {
+            app.run(
+                &mut (),
+                &mut driver,
+                |_, app| write!(app, "hi").unwrap(),
+                |_, _, _| true,
+            )
+        }))
+        .unwrap_err();
+        let message = failure.downcast_ref::<String>().unwrap();
+        assert_eq!(message, "the screen does not show \"bye\":\nhi\n");
+    }
+
+    #[test]
+    fn debug_hud_shows_the_frame_and_toggles_with_f12() {
+        use crate::event::NyanEvent;
+        use crate::input::{NyanInput, NyanKey};
+        use crate::layer::{Anchor, FloatLayer};
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::testing::TestDriver;
+        use std::io::Write;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("title", Objects::new_text("nyan"), (0, 0));
+        obj.add_object("menu", Objects::new_text("menu"), (0, 1));
+        obj.set_visible("menu", false).unwrap();
+        let mut floats = FloatLayer::new().float("menu");
+        floats.open("menu", Anchor::Center);
+
+        let mut driver: TestDriver = TestDriver::new(44, 10)
+            .key(NyanInput::Key(NyanKey::A))
+            .expect("event   Key(A)")
+            .expect("objects 2 (1 visible)")
+            .expect("layers  1 open")
+            .expect("size    44x10")
+            .key(NyanInput::FunctionKey(12));
+        let mut app = driver.app(10).debug_hud();
+        assert!(app.is_debug_hud_shown());
+        let mut handled = Vec::new();
+        app.run(
+            &mut handled,
+            &mut driver,
+            |_, app| {
+                app.debug_scene(&obj);
+                app.debug_layers(&floats);
+                write!(app, "hello").unwrap();
+            },
+            |handled, _, event| {
+                handled.push(event);
+                true
+            },
+        )
+        .unwrap();
+
+        // F12 only toggled the HUD, and its panel was erased.
+        assert_eq!(handled, vec![NyanEvent::Key(NyanInput::Key(NyanKey::A))]);
+        assert!(!app.is_debug_hud_shown());
+        crate::testing::assert_frame(&driver.screen(), "hello");
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn config_files_rebind_keys_and_restyle_the_theme() {
+        use crate::app::App;
+        use crate::color::NyanColor;
+        use crate::errors::NyanError;
+        use crate::event::NyanEvent;
+        use crate::input::{NyanInput, NyanKey};
+        use crate::keybindings::{key_name, parse_key, Keybindings};
+        use crate::theme::{Background, Theme};
+
+        for name in [
+            "q",
+            "Shift+Q",
+            "Ctrl+S",
+            "Alt+Space",
+            "Shift+Tab",
+            "F12",
+            "PageDown",
+            "?",
+        ] {
+            assert_eq!(key_name(&parse_key(name).unwrap()), name);
+        }
+        assert_eq!(parse_key("Q").unwrap(), parse_key("shift+q").unwrap());
+        assert!(parse_key("ctrl+f13").is_err());
+
+        let keys = Keybindings::new()
+            .bind("quit", NyanInput::Esc)
+            .bind("save", NyanInput::Ctrl(NyanKey::S))
+            .bind("help", NyanInput::FunctionKey(1));
+        let mut app = App::new(30)
+            .default_keybindings(keys)
+            .default_theme(Theme::dark());
+        let dir = std::env::temp_dir().join(format!("nyan-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("app.toml");
+
+        // Without a file, the defaults stay.
+        assert!(!app.load_config(&path).unwrap());
+        assert_eq!(
+            app.action(&NyanEvent::<()>::Key(NyanInput::Esc)),
+            Some("quit")
+        );
+
+        std::fs::write(
+            &path,
+            r#"
+            [keys]
+            quit = ["ctrl+c", "q"]
+            help = "?"
+
+            [theme]
+            base = "light"
+            accent = { fg = "Magenta", attributes = ["Bold"] }
+            "#,
+        )
+        .unwrap();
+        assert!(app.load_config(&path).unwrap());
+        let key = |key: NyanInput<'static>| app.action(&NyanEvent::<()>::Key(key));
+        assert_eq!(key(NyanInput::Ctrl(NyanKey::C)), Some("quit"));
+        assert_eq!(key(NyanInput::Esc), None);
+        assert_eq!(key(NyanInput::Ctrl(NyanKey::S)), Some("save"));
+        assert_eq!(
+            key(NyanInput::Shift(&NyanInput::Key(NyanKey::OtherKey('?')))),
+            Some("help")
+        );
+        assert_eq!(app.keybindings().hint("quit").as_deref(), Some("Ctrl+C"));
+        assert_eq!(app.theme().background, Background::Light);
+        assert_eq!(app.theme().accent.fg, Some(NyanColor::Magenta));
+        assert_eq!(app.theme().error, Theme::light().error);
+
+        // Invalid files are rejected as a whole, naming what is wrong.
+        for (source, message) in [
+            ("[keys]\nundo = \"ctrl+z\"", "unknown action \"undo\""),
+            (
+                "[keys]\nsave = \"ctrl+\"",
+                "keys.save: Invalid data: unknown key",
+            ),
+            (
+                "[keys]\nsave = \"q\"",
+                "\"q\" is bound to both \"quit\" and \"save\"",
+            ),
+            (
+                "[theme]\nborder = { fg = \"Red\" }",
+                "unknown field `border`",
+            ),
+            (
+                "[theme]\naccent = { fg = \"Purple\" }",
+                "unknown variant `Purple`",
+            ),
+        ] {
+            std::fs::write(&path, source).unwrap();
+            match app.load_config(&path) {
+                Err(NyanError::InvalidConfig(error)) => {
+                    assert!(error.contains(message), "{error}");
+                    assert!(error.starts_with(&path.display().to_string()));
+                }
+                other => panic!("unexpected {other:?}"),
+            }
+        }
+        assert_eq!(app.keybindings().hint("save").as_deref(), Some("Ctrl+S"));
+        assert_eq!(app.theme().accent.fg, Some(NyanColor::Magenta));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn custom_objects_are_drawn_by_registered_renderers() {
+        use crate::errors::NyanError;
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::plugin::{self, ObjectRenderer, Props};
+        use crate::testing;
+
+        /// A framed meter, `value` of `max` cells filled.
+        struct Meter;
+
+        impl ObjectRenderer for Meter {
+            fn lines(&self, props: &Props) -> Vec<String> {
+                let max = props.parse::<usize>("max").unwrap_or(4);
+                let value = props.parse::<usize>("value").unwrap_or(0).min(max);
+                let label = props.get("label").unwrap_or("");
+                vec![
+                    label.to_string(),
+                    format!("[{}{}]", "=".repeat(value), " ".repeat(max - value)),
+                ]
+            }
+        }
+
+        plugin::register("test-meter", Meter);
+        assert!(plugin::is_registered("test-meter"));
+        let mut scene = NyanObj::new();
+        let props = Props::new().with("label", "hp").with("value", "3");
+        scene.add_object("hp", Objects::new_custom("test-meter", props), (1, 0));
+        scene.add_object("end", Objects::new_text("!"), (8, 1));
+        assert_eq!(scene.outer_size("hp"), Some((6, 2)));
+
+        let frame = testing::render_scene(&scene, 10, 2).unwrap();
+        testing::assert_frame(&frame, " hp\n [=== ] !");
+
+        // Props change what is drawn; other kinds and removed renderers do not draw.
+        let mut object = Objects::new_custom("test-meter", Props::new().with("max", "2"));
+        object.props_mut().unwrap().set("value", "9");
+        let mut out = Vec::new();
+        object.draw(&mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("[==]"));
+        assert!(Objects::new_text("x").props_mut().is_none());
+
+        assert!(plugin::unregister("test-meter"));
+        assert!(!plugin::unregister("test-meter"));
+        assert_eq!(scene.outer_size("hp"), Some((0, 0)));
+        assert!(matches!(
+            testing::render_scene(&scene, 10, 2),
+            Err(NyanError::DrawFailed(_) | NyanError::Io(_))
+        ));
+    }
+
+    #[cfg(feature = "rhai")]
+    #[test]
+    fn scripts_build_scenes_and_answer_events() {
+        use crate::errors::NyanError;
+        use crate::event::{NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::input::{NyanInput, NyanKey};
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::script::Script;
+        use std::time::Duration;
+
+        let mut script = Script::new(
+            r#"
+            add_panel("frame", 10, 3, 0, 0);
+
+            fn init() {
+                this.time = 0.0;
+                add_text("clock", "0", 1, 1);
+                add_custom("hp", "gauge", #{ value: 3, max: 5 }, 0, 4);
+                print("ready");
+            }
+            fn update(dt) {
+                this.time += dt;
+                set_text("clock", `${this.time}`);
+            }
+            fn on_key(key) {
+                switch key {
+                    "q" => quit(),
+                    "Ctrl+H" => hide("frame"),
+                    "x" => remove("hp"),
+                    "p" => set_text("frame", "oops"),
+                    _ => set_prop("hp", "value", 1),
+                }
+            }
+            fn on_click(x, y) { move_to("clock", x, y); }
+            fn on_resize(width, height) { throw `too small: ${width}x${height}`; }
+            "#,
+        )
+        .unwrap();
+        let mut scene = NyanObj::new();
+        script.start(&mut scene).unwrap();
+        assert_eq!(scene.ids().collect::<Vec<_>>(), ["frame", "clock", "hp"]);
+        assert_eq!(script.take_printed(), ["ready"]);
+        assert!(script.take_printed().is_empty());
+
+        script
+            .update(&mut scene, Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(scene.object("clock"), Some(Objects::Text(t)) if t == "0.5"));
+
+        let key = |key: NyanInput<'static>| NyanEvent::<()>::Key(key);
+        let props = |scene: &NyanObj| format!("{:?}", scene.object("hp").unwrap());
+        assert!(props(&scene).contains("\"value\", \"3\""));
+        script
+            .handle_event(&mut scene, &key(NyanInput::Enter))
+            .unwrap();
+        assert!(props(&scene).contains("\"value\", \"1\""));
+        script
+            .handle_event(&mut scene, &key(NyanInput::Ctrl(NyanKey::H)))
+            .unwrap();
+        assert_eq!(scene.is_visible("frame"), Some(false));
+
+        let click = NyanEvent::<()>::Mouse(NyanMouse {
+            kind: NyanMouseKind::Down(NyanMouseButton::Left),
+            x: 4,
+            y: 2,
+        });
+        assert!(script.handle_event(&mut scene, &click).unwrap());
+        assert_eq!(scene.coordinate("clock"), Some((4, 2)));
+        assert!(!script
+            .handle_event(&mut scene, &NyanEvent::<()>::FocusGained)
+            .unwrap());
+
+        // Failures name the hook, and objects the script gets wrong are reported.
+        match script.handle_event(&mut scene, &NyanEvent::<()>::Resize(8, 2)) {
+            Err(NyanError::Script(error)) => {
+                assert!(error.contains("on_resize") && error.contains("too small: 8x2"))
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        let result = script.handle_event(&mut scene, &key(NyanInput::Key(NyanKey::P)));
+        assert!(matches!(result, Err(NyanError::Script(e)) if e.contains("not a text object")));
+        script
+            .handle_event(&mut scene, &key(NyanInput::Key(NyanKey::X)))
+            .unwrap();
+        let result = script.handle_event(&mut scene, &key(NyanInput::Key(NyanKey::Y)));
+        assert!(matches!(result, Err(NyanError::ObjectNotFound(id)) if id == "hp"));
+
+        assert!(!script.wants_quit());
+        script
+            .handle_event(&mut scene, &key(NyanInput::Key(NyanKey::Q)))
+            .unwrap();
+        assert!(script.wants_quit());
+        assert!(matches!(
+            Script::new("fn init( {"),
+            Err(NyanError::Script(_))
+        ));
+    }
+
+    #[cfg(feature = "rhai")]
+    #[test]
+    fn scripts_are_limited_and_change_all_or_nothing() {
+        use crate::errors::NyanError;
+        use crate::event::NyanEvent;
+        use crate::input::{NyanInput, NyanKey};
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::script::Script;
+        use std::time::Duration;
+
+        let mut script = Script::new(
+            r#"
+            fn deeper(n) { deeper(n + 1) }
+            fn init() { add_text("a", "a", 0, 0); }
+            fn update(dt) { loop { } }
+            fn on_key(key) {
+                switch key {
+                    "d" => deeper(0),
+                    "s" => { let s = "nyan"; loop { s += s; } }
+                    "l" => { let list = []; loop { list.push(0); } }
+                    "m" => { move_to("a", 1, 1); move_to("missing", 0, 0); }
+                    "r" => { remove("a"); set_text("a", "gone"); }
+                    _ => { add_text("b", "", 2, 2); set_text("b", "b"); hide("b"); }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+        let mut scene = NyanObj::new();
+        script.start(&mut scene).unwrap();
+
+        // Runaway scripts fail instead of hanging or filling the memory.
+        let key = |key| NyanEvent::<()>::Key(NyanInput::Key(key));
+        let script_error = |error: Option<NyanError>| matches!(error, Some(NyanError::Script(_)));
+        assert!(script_error(
+            script.update(&mut scene, Duration::ZERO).err()
+        ));
+        assert!(script_error(
+            script.handle_event(&mut scene, &key(NyanKey::D)).err()
+        ));
+        assert!(script_error(
+            script.handle_event(&mut scene, &key(NyanKey::S)).err()
+        ));
+        assert!(script_error(
+            script.handle_event(&mut scene, &key(NyanKey::L)).err()
+        ));
+
+        // A hook whose changes fail partway applies none of them.
+        let result = script.handle_event(&mut scene, &key(NyanKey::M));
+        assert!(matches!(result, Err(NyanError::ObjectNotFound(id)) if id == "missing"));
+        assert_eq!(scene.coordinate("a"), Some((0, 0)));
+        let result = script.handle_event(&mut scene, &key(NyanKey::R));
+        assert!(matches!(result, Err(NyanError::ObjectNotFound(id)) if id == "a"));
+        assert!(matches!(scene.object("a"), Some(Objects::Text(t)) if t == "a"));
+
+        // Changes to objects added by the same hook are fine.
+        script.handle_event(&mut scene, &key(NyanKey::B)).unwrap();
+        assert!(matches!(scene.object("b"), Some(Objects::Text(t)) if t == "b"));
+        assert_eq!(scene.is_visible("b"), Some(false));
+    }
+
+    #[test]
+    fn vim_parser_combines_counts_and_operators() {
+        use crate::vim::{VimCommand, VimParser};
+
+        let command = |count, operator, motion| VimCommand {
+            count,
+            operator,
+            motion,
+        };
+        let feed = |parser: &mut VimParser, keys: &str| -> Vec<VimCommand> {
+            keys.chars()
+                .filter_map(|c| parser.feed(NyanInput::Key(NyanKey::OtherKey(c))))
+                .collect()
+        };
+        let mut parser = VimParser::new();
+
+        // Counts, a leading zero as a motion, and zeros inside a count.
+        assert_eq!(feed(&mut parser, "12j"), [command(Some(12), None, 'j')]);
+        assert_eq!(feed(&mut parser, "0"), [command(None, None, '0')]);
+        assert_eq!(feed(&mut parser, "10k"), [command(Some(10), None, 'k')]);
+        assert_eq!(feed(&mut parser, "l")[0].count_or_one(), 1);
+
+        // Operators: doubled for the whole line, with counts on both sides multiplied.
+        assert_eq!(feed(&mut parser, "dd"), [command(None, Some('d'), 'd')]);
+        assert_eq!(
+            feed(&mut parser, "2d3w"),
+            [command(Some(6), Some('d'), 'w')]
+        );
+        assert_eq!(feed(&mut parser, "d0"), [command(None, Some('d'), '0')]);
+        assert_eq!(feed(&mut parser, "y$"), [command(None, Some('y'), '$')]);
+        assert_eq!(feed(&mut parser, "dy"), [command(None, Some('d'), 'y')]);
+        assert_eq!(feed(&mut parser, "c2>"), [command(Some(2), Some('c'), '>')]);
+
+        // Counts too large for a usize saturate instead of overflowing.
+        let huge = feed(&mut parser, "99999999999999999999999j");
+        assert_eq!(huge, [command(Some(usize::MAX), None, 'j')]);
+        let huge = feed(&mut parser, "9999999999d9999999999w");
+        assert_eq!(huge, [command(Some(usize::MAX), Some('d'), 'w')]);
+
+        // Shifted letters are motions; Esc and keys without a character start over.
+        let g = NyanInput::Key(NyanKey::G);
+        assert_eq!(feed(&mut parser, "5"), []);
+        assert_eq!(
+            parser.feed(NyanInput::Shift(&g)),
+            Some(command(Some(5), None, 'G'))
+        );
+        assert_eq!(feed(&mut parser, "2d3"), []);
+        assert_eq!(parser.pending(), "2d3");
+        assert_eq!(parser.feed(NyanInput::Esc), None);
+        assert_eq!(parser.pending(), "");
+        assert_eq!(feed(&mut parser, "4"), []);
+        assert_eq!(parser.feed(NyanInput::Ctrl(NyanKey::R)), None);
+        assert_eq!(feed(&mut parser, "w"), [command(None, None, 'w')]);
+
+        // Custom operators replace the default ones.
+        let mut parser = VimParser::new().operators(&['g']);
+        assert_eq!(feed(&mut parser, "gu"), [command(None, Some('g'), 'u')]);
+        assert_eq!(feed(&mut parser, "3d"), [command(Some(3), None, 'd')]);
+    }
+
+    #[test]
+    fn cursor_targets_are_clamped_and_checked_against_the_area() {
+        // Inside the area, nothing changes.
+        assert_eq!(Cursor::Move(3, 2).clamp(10, 5), Cursor::Move(3, 2));
+        assert!(Cursor::Move(9, 4).fits(10, 5));
+        assert!(Cursor::MoveToColumn(9).fits(10, 5));
+
+        // Outside it, the coordinates are clamped to the last column and row.
+        assert_eq!(Cursor::Move(30, 20).clamp(10, 5), Cursor::Move(9, 4));
+        assert_eq!(
+            Cursor::MoveToColumn(10).clamp(10, 5),
+            Cursor::MoveToColumn(9)
+        );
+        assert_eq!(Cursor::MoveToRow(7).clamp(10, 5), Cursor::MoveToRow(4));
+        assert!(!Cursor::Move(10, 0).fits(10, 5));
+        assert!(!Cursor::Move(0, 5).fits(10, 5));
+        assert!(!Cursor::MoveToRow(5).fits(10, 5));
+
+        // Nothing fits in an empty area, and everything is clamped to its corner.
+        assert_eq!(Cursor::Move(3, 2).clamp(0, 0), Cursor::Move(0, 0));
+        assert!(!Cursor::Move(0, 0).fits(0, 0));
+        assert!(!Cursor::MoveToColumn(0).fits(0, 5));
+
+        // Relative movements are left alone.
+        assert_eq!(Cursor::MoveLeft(50).clamp(10, 5), Cursor::MoveLeft(50));
+        assert!(Cursor::MoveDown(50).fits(0, 0));
+    }
+
+    #[test]
+    fn cursor_animations_take_their_frames_and_end_at_the_target() {
+        use crate::easing::Easing;
+
+        for frames in [0, 1, 2, 7, 20] {
+            let animation = Cursor::animate((2, 1), (30, 10), frames, Easing::InOutQuad);
+            // Zero frames take one, so the cursor still gets there.
+            let expected = frames.max(1) as usize;
+            assert_eq!(animation.size_hint(), (expected, Some(expected)));
+
+            let steps: Vec<Cursor> = animation.collect();
+            assert_eq!(steps.len(), expected);
+            assert_eq!(steps.last(), Some(&Cursor::Move(30, 10)));
+        }
+
+        let mut animation = Cursor::animate((0, 0), (4, 0), 4, Easing::Linear);
+        assert_eq!(
+            animation.by_ref().collect::<Vec<_>>(),
+            [1, 2, 3, 4].map(|x| Cursor::Move(x, 0))
+        );
+        assert!(animation.is_finished());
+        assert_eq!(animation.next(), None);
+    }
+
+    #[test]
+    fn default_styles_belong_to_their_app() {
+        use crate::backend::TestBackend;
+        use crate::style::NyanStyle;
+        use std::thread;
+
+        // Apps on two threads draw at the same time, each with its own default style.
+        let draw = |style: NyanStyle| {
+            move || {
+                let backend = TestBackend::new(4, 1);
+                let mut app = App::with_backend(1000, backend.clone())
+                    .buffered()
+                    .default_style(style);
+                let mut obj = NyanObj::new();
+                obj.add_object("cat", Objects::new_text("nyan"), (0, 0));
+                for _ in 0..20 {
+                    app.draw_with(|app| obj.draw_all_in(app).unwrap()).unwrap();
+                }
+                // The base style is only set during the frames.
+                assert_eq!(NyanStyle::base(), NyanStyle::new());
+                let frame = app.screenshot().unwrap().to_ansi();
+                (frame, backend.screen().to_ansi())
+            }
+        };
+        let bold = thread::spawn(draw(NyanStyle::new().bold()));
+        let italic = thread::spawn(draw(NyanStyle::new().italic()));
+        let (bold, italic) = (bold.join().unwrap(), italic.join().unwrap());
+        assert_eq!(bold.0, "\x1b[1mnyan\x1b[0m\n");
+        assert_eq!(bold.1, "\x1b[1mnyan\x1b[0m\n");
+        assert_eq!(italic.0, "\x1b[3mnyan\x1b[0m\n");
+        assert_eq!(italic.1, "\x1b[3mnyan\x1b[0m\n");
+    }
+
+    #[test]
+    fn draw_sends_the_setup_before_objects_drawn_to_the_terminal() {
+        use crate::backend::Backend;
+        use crate::clock::MockClock;
+        use std::io::{self, Write};
+        use std::sync::{Arc, Mutex};
+
+        /// A terminal that only receives what the App flushed, like a buffered stdout.
+        #[derive(Debug)]
+        struct Terminal {
+            queued: Vec<u8>,
+            received: Arc<Mutex<Vec<u8>>>,
+        }
+        impl Write for Terminal {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.queued.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                self.received.lock().unwrap().append(&mut self.queued);
+                Ok(())
+            }
+        }
+        impl Backend for Terminal {
+            fn size(&self) -> io::Result<(u16, u16)> {
+                Ok((20, 2))
+            }
+        }
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let terminal = Terminal {
+            queued: Vec::new(),
+            received: Arc::clone(&received),
+        };
+        let mut nyan = App::with_backend(30, terminal)
+            .clock(MockClock::new())
+            .clear();
+        // What draw_object writes goes to the terminal directly.
+        nyan.draw(|| received.lock().unwrap().extend_from_slice(b"HELLOWORLD"))
+            .unwrap();
+
+        let received = String::from_utf8(received.lock().unwrap().clone()).unwrap();
+        let clear = received.find("\x1b[2J").unwrap();
+        let text = received.find("HELLOWORLD").unwrap();
+        assert!(clear < text, "{received:?}");
+    }
+
+    #[test]
+    fn split_pane_divider_follows_drag_and_keys() {
+        use crate::event::{NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::input::NyanInput;
+        use crate::layout::Direction;
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::rect::Rect;
+        use crate::split::SplitPane;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("tree", Objects::new_panel(1, 1, None), (0, 0));
+        obj.add_object("editor", Objects::new_panel(1, 1, None), (0, 0));
+
+        let area = Rect::new(0, 0, 40, 10);
+        let mut split = SplitPane::new(Direction::Horizontal, "tree", "editor").min_size(5);
+        split.apply(&mut obj, area).unwrap();
+        assert_eq!(obj.coordinate("editor"), Some((20, 0)));
+
+        let mouse = |kind, x| NyanEvent::<()>::Mouse(NyanMouse { kind, x, y: 3 });
+        assert!(!split.handle_event(&mouse(NyanMouseKind::Down(NyanMouseButton::Left), 19)));
+        assert!(split.is_dragging());
+        assert!(split.handle_event(&mouse(NyanMouseKind::Drag(NyanMouseButton::Left), 2)));
+        split.handle_event(&mouse(NyanMouseKind::Up(NyanMouseButton::Left), 2));
+        split.apply(&mut obj, area).unwrap();
+        assert_eq!(obj.bounds("tree"), Some(Rect::new(0, 0, 5, 10)));
+
+        assert!(split.handle_input(&NyanInput::Shift(&NyanInput::RightAllow)));
+        split.apply(&mut obj, area).unwrap();
+        assert_eq!(obj.coordinate("editor"), Some((7, 0)));
+    }
+
+    #[test]
+    fn float_layer_places_and_captures() {
+        use crate::event::{NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+        use crate::layer::{Anchor, FloatLayer};
+        use crate::nyan_obj::NyanObj;
+        use crate::objects::Objects;
+        use crate::rect::Rect;
+
+        let mut obj = NyanObj::new();
+        obj.add_object("file", Objects::new_text("File"), (2, 0));
+        obj.add_object("menu", Objects::new_list(["Open", "Quit"], 2), (0, 0));
+        obj.add_object("confirm", Objects::new_text("Really quit?"), (0, 0));
+
+        let area = Rect::new(0, 0, 40, 10);
+        let mut floats = FloatLayer::new().float("menu").float("confirm");
+
+        let mut out = Vec::new();
+        floats.draw_in(&obj, &mut out).unwrap();
+        assert!(!String::from_utf8(out).unwrap().contains("Quit"));
+
+        floats.open("menu", Anchor::Below("file".into()));
+        floats.apply(&mut obj, area).unwrap();
+        assert_eq!(obj.coordinate("menu"), Some((2, 1)));
+
+        let click = |x, y| {
+            NyanEvent::<()>::Mouse(NyanMouse {
+                kind: NyanMouseKind::Down(NyanMouseButton::Left),
+                x,
+                y,
+            })
+        };
+        assert_eq!(floats.event_target(&obj, &click(3, 2)), Some("menu"));
+        assert_eq!(floats.event_target(&obj, &click(30, 8)), None);
+
+        floats.open_modal("confirm", Anchor::Center);
+        floats.apply(&mut obj, area).unwrap();
+        assert_eq!(obj.coordinate("confirm"), Some((14, 4)));
+        assert_eq!(floats.event_target(&obj, &click(30, 8)), Some("confirm"));
+
+        assert_eq!(floats.close_top().as_deref(), Some("confirm"));
+        assert!(floats.is_open("menu"));
+    }
 }
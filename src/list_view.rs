@@ -0,0 +1,385 @@
+//! This module provides the `ListView` widget, a scrollable list with a selected item and optional multi-selection.
+//!
+//! Unlike `Objects::List`, which only draws its items, a list view keeps its own state: the selected item (the cursor), the scroll offset and, in multi-select mode, a set of marked items. It handles Up / Down / PageUp / PageDown / Home / End and the mouse itself, keeps the selected item in view and draws a scrollbar when the items do not fit.
+//!
+//! # Structs
+//!
+//! - `ListView`: The items and the selection, marks and scroll state.
+//!
+//! # Methods
+//!
+//! - `new(items)`: Creates a list with the first item selected.
+//! - `from_json(value, path)`: Creates a list of the values of a JSON array (`json` feature).
+//! - `multi_select(on)` / `highlight_symbol(symbol)` / `theme(theme)`: Configure marking, the selection marker and the colors.
+//! - `items()` / `len()` / `is_empty()` / `set_items(items)` / `push(item)`: Access the items.
+//! - `selected()` / `selected_item()` / `select(index)`: Query and move the selection.
+//! - `offset()`: Returns the first visible item.
+//! - `marked()` / `is_marked(index)` / `toggle_mark(index)` / `clear_marks()`: Manage marks in multi-select mode.
+//! - `take_activation()`: Returns the item activated with Enter or a click on the selection.
+
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::cursor::Cursor;
+#[cfg(feature = "json")]
+use crate::errors::NyanResult;
+use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::{NyanInput, NyanKey};
+use crate::layout::{self, Align};
+use crate::rect::Rect;
+use crate::scrollbar::Scrollbar;
+use crate::style::NyanStyle;
+#[cfg(feature = "json")]
+use crate::table_view;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// `ListView` is a scrollable list that tracks its selection.
+///
+/// # Example
+/// ```rust
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::list_view::ListView;
+/// use nyan::rect::Rect;
+/// use nyan::widget::Widget;
+///
+/// let mut list = ListView::new(["apple", "banana", "cherry", "date"]).multi_select(true);
+/// list.set_area(Rect::new(0, 0, 20, 2));
+/// list.set_focused(true);
+///
+/// list.handle_input(&NyanInput::Key(NyanKey::OtherKey(' '))); // mark "apple"
+/// list.handle_input(&NyanInput::End);
+/// list.handle_input(&NyanInput::Key(NyanKey::OtherKey(' '))); // mark "date"
+///
+/// assert_eq!(list.selected_item(), Some("date"));
+/// assert_eq!(list.offset(), 2); // scrolled to keep "date" visible
+/// assert_eq!(list.marked(), vec![0, 3]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ListView<'a> {
+    items: Vec<Cow<'a, str>>,
+    selected: usize,
+    offset: usize,
+    marked: BTreeSet<usize>,
+    multi_select: bool,
+    highlight_symbol: Cow<'a, str>,
+    activated: Option<usize>,
+    focused: bool,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> ListView<'a> {
+    /// Creates a list of `items` with the first one selected.
+    pub fn new<T: Into<Cow<'a, str>>, I: IntoIterator<Item = T>>(items: I) -> Self {
+        Self {
+            items: items.into_iter().map(Into::into).collect(),
+            selected: 0,
+            offset: 0,
+            marked: BTreeSet::new(),
+            multi_select: false,
+            highlight_symbol: Cow::Borrowed("> "),
+            activated: None,
+            focused: false,
+            theme: Theme::default(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Sets whether items can be marked with Space, each drawn with a `[x]` / `[ ]` box.
+    pub fn multi_select(self, on: bool) -> Self {
+        let mut list = self;
+        list.multi_select = on;
+        if !on {
+            list.marked.clear();
+        }
+        list
+    }
+
+    /// Sets the marker drawn before the selected item (`"> "` by default). Other items are
+    /// indented by its width.
+    pub fn highlight_symbol<T: Into<Cow<'a, str>>>(self, symbol: T) -> Self {
+        let mut list = self;
+        list.highlight_symbol = symbol.into();
+        list
+    }
+
+    /// Sets the colors of the list.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut list = self;
+        list.theme = theme;
+        list
+    }
+
+    /// Returns the items.
+    pub fn items(&self) -> &[Cow<'a, str>] {
+        &self.items
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the list has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Replaces the items, keeping the selection and the marks that are still in range.
+    pub fn set_items<T: Into<Cow<'a, str>>, I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.items = items.into_iter().map(Into::into).collect();
+        let len = self.items.len();
+        self.marked.retain(|&index| index < len);
+        self.select(self.selected);
+    }
+
+    /// Appends an item.
+    pub fn push<T: Into<Cow<'a, str>>>(&mut self, item: T) {
+        self.items.push(item.into());
+    }
+
+    /// Returns the index of the selected item, or `None` if the list is empty.
+    pub fn selected(&self) -> Option<usize> {
+        (!self.items.is_empty()).then_some(self.selected)
+    }
+
+    /// Returns the selected item.
+    pub fn selected_item(&self) -> Option<&str> {
+        self.items.get(self.selected).map(|item| item.as_ref())
+    }
+
+    /// Selects the item at `index` (clamped to the last item) and scrolls it into view.
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+        self.scroll_to_selected();
+    }
+
+    /// Returns the index of the first visible item.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the indices of the marked items, in ascending order.
+    pub fn marked(&self) -> Vec<usize> {
+        self.marked.iter().copied().collect()
+    }
+
+    /// Returns whether the item at `index` is marked.
+    pub fn is_marked(&self, index: usize) -> bool {
+        self.marked.contains(&index)
+    }
+
+    /// Marks the item at `index`, or unmarks it if it is marked. Does nothing unless
+    /// multi-select is on.
+    pub fn toggle_mark(&mut self, index: usize) {
+        if !self.multi_select || index >= self.items.len() {
+            return;
+        }
+        if !self.marked.remove(&index) {
+            self.marked.insert(index);
+        }
+    }
+
+    /// Unmarks all items.
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    /// Returns the item activated (with Enter, or by clicking the selected item) since the last
+    /// call, and forgets it.
+    pub fn take_activation(&mut self) -> Option<usize> {
+        self.activated.take()
+    }
+
+    /// Returns how many items fit in the area.
+    fn rows(&self) -> usize {
+        self.area.height as usize
+    }
+
+    /// Moves the selection by `delta` items.
+    fn move_selection(&mut self, delta: isize) {
+        self.select(self.selected.saturating_add_signed(delta));
+    }
+
+    /// Scrolls so the selected item is visible.
+    fn scroll_to_selected(&mut self) {
+        let rows = self.rows().max(1);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + rows {
+            self.offset = self.selected + 1 - rows;
+        }
+    }
+
+    /// Scrolls by `delta` items without moving the selection.
+    fn scroll_by(&mut self, delta: isize) {
+        let max = self.items.len().saturating_sub(self.rows());
+        self.offset = self.offset.saturating_add_signed(delta).min(max);
+    }
+}
+
+#[cfg(feature = "json")]
+impl ListView<'static> {
+    /// Creates a list of the values of the array found at `path` in `value`, a JSON pointer
+    /// such as `"/data/tags"` (`""` for `value` itself). Strings are shown as they are, `null`
+    /// as an empty item and everything else as JSON.
+    ///
+    /// Requires the `json` feature.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ListView)` with an item for each value, the first one selected.
+    /// - An error if nothing is found at `path`, or it is not an array.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "json")]
+    /// # {
+    /// use nyan::list_view::ListView;
+    ///
+    /// let value = serde_json::json!({ "cats": { "names": ["mike", "tama", 3] } });
+    /// let list = ListView::from_json(&value, "/cats/names").unwrap();
+    /// assert_eq!(list.items(), ["mike", "tama", "3"]);
+    /// # }
+    /// ```
+    pub fn from_json(value: &serde_json::Value, path: &str) -> NyanResult<Self> {
+        let items = table_view::json_array(value, path)?;
+        Ok(Self::new(items.iter().map(table_view::json_text)))
+    }
+}
+
+impl<'a> Widget for ListView<'a> {
+    /// Wide enough for the longest item with its markers, as tall as the list.
+    fn size(&self) -> (u16, u16) {
+        let markers = self.highlight_symbol.width() + if self.multi_select { 4 } else { 0 };
+        let longest = self
+            .items
+            .iter()
+            .map(|item| item.width())
+            .max()
+            .unwrap_or(0);
+        let height = self.items.len().min(u16::MAX as usize) as u16;
+        ((longest + markers).min(u16::MAX as usize) as u16, height)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+        self.scroll_to_selected();
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() {
+            return Ok(());
+        }
+        let rows = self.rows();
+        let bar = Scrollbar::new(self.items.len(), rows, self.offset);
+        let width = self.area.width as usize - usize::from(bar.is_needed());
+        let indent = " ".repeat(self.highlight_symbol.width());
+
+        for (row, cell) in bar.cells(rows as u16).chars().enumerate() {
+            let index = self.offset + row;
+            let (line, style) = match self.items.get(index) {
+                Some(item) => {
+                    let selected = index == self.selected;
+                    let symbol = if selected {
+                        self.highlight_symbol.as_ref()
+                    } else {
+                        &indent
+                    };
+                    let check = match (self.multi_select, self.is_marked(index)) {
+                        (false, _) => "",
+                        (true, true) => "[x] ",
+                        (true, false) => "[ ] ",
+                    };
+                    let style = match (selected, self.focused) {
+                        (true, true) => self.theme.selection,
+                        (true, false) => NyanStyle::new().reverse(),
+                        _ if self.is_marked(index) => self.theme.accent,
+                        _ => self.theme.text,
+                    };
+                    (format!("{symbol}{check}{item}"), style)
+                }
+                None => (String::new(), NyanStyle::new()),
+            };
+            let line = layout::clip_text(&line, 0, width);
+            Cursor::Move(self.area.x, self.area.y + row as u16).queue(&mut out)?;
+            widget::write_styled(
+                out,
+                style,
+                &layout::align_text(&line, width as u16, Align::Start),
+            )?;
+            if bar.is_needed() {
+                widget::write_styled(out, self.theme.muted, &cell.to_string())?;
+            }
+        }
+        out.flush()
+    }
+
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        if !self.focused || self.items.is_empty() {
+            return false;
+        }
+        let page = self.rows().max(1) as isize;
+        match input {
+            NyanInput::UpAllow => self.move_selection(-1),
+            NyanInput::DownAllow => self.move_selection(1),
+            NyanInput::PageUp => self.move_selection(-page),
+            NyanInput::PageDown => self.move_selection(page),
+            NyanInput::Home => self.select(0),
+            NyanInput::End => self.select(usize::MAX),
+            NyanInput::Enter => self.activated = Some(self.selected),
+            NyanInput::Key(NyanKey::OtherKey(' ')) if self.multi_select => {
+                self.toggle_mark(self.selected)
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// A click selects an item; clicking the selected item activates it, or toggles its mark
+    /// in multi-select mode. The wheel scrolls without moving the selection.
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        if !self.area.contains(mouse.x, mouse.y) {
+            return false;
+        }
+        match mouse.kind {
+            NyanMouseKind::Down(NyanMouseButton::Left) => {
+                let index = self.offset + (mouse.y - self.area.y) as usize;
+                if index >= self.items.len() {
+                    return true;
+                }
+                if index != self.selected {
+                    self.select(index);
+                } else if self.multi_select {
+                    self.toggle_mark(index);
+                } else {
+                    self.activated = Some(index);
+                }
+            }
+            NyanMouseKind::ScrollUp => self.scroll_by(-1),
+            NyanMouseKind::ScrollDown => self.scroll_by(1),
+            _ => {}
+        }
+        true
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
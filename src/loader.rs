@@ -0,0 +1,370 @@
+//! This module provides a loader for declarative UI files, which describe the objects of a screen and their layout in TOML or YAML instead of code.
+//!
+//! A layout file lists objects, each with an id, a type, its text or items, a position, a style and the other settings of [`NyanObj`], and optionally a layout (a stack or a grid) that places them. Loading it gives a [`Screen`]: the objects, and a [`LayoutManager`] with the layout, so the screen follows the terminal size like one built in code. People who do not write Rust can tweak screens this way, and prototypes can change without a rebuild.
+//!
+//! Requires the `toml` feature for TOML files and the `yaml` feature for YAML files.
+//!
+//! ```toml
+//! [[objects]]
+//! id = "title"
+//! text = "nyan"
+//! style = { fg = "Cyan", attributes = ["Bold"] }
+//!
+//! [[objects]]
+//! id = "menu"
+//! type = "list"
+//! items = ["Start", "Options", "Quit"]
+//! height = 3
+//! padding = { left = 2 }
+//!
+//! [layout]
+//! type = "vstack"
+//! spacing = 1
+//! children = [{ id = "title" }, { id = "menu", flex = 1 }]
+//! ```
+//!
+//! # Object types
+//!
+//! `text` (the default), `paragraph` (`text`, `width`), `scrollable_text` (`text`, `height`), `list` (`items`, `height`), `scroll_view` (`text`, `width`, `height`), `panel` (`width`, `height`, `border`) and `air`. `block` is refused, as blocks cannot be drawn yet. Every object may have a `position = [x, y]`, `style`, `fill`, `padding`, `margin`, `visible` and `cached`.
+//!
+//! # Layouts
+//!
+//! - `vstack` / `hstack`: `spacing` and `children`, each with an `id` and a `fixed` size or a `flex` weight (its own size otherwise).
+//! - `grid`: `rows`, `columns`, `gap = [columns, rows]` and `cells`, each with an `id`, a `row`, a `column` and optionally a `row_span` and a `column_span`.
+//!
+//! # Structs
+//!
+//! - `Screen`: The objects and the layout read from a file.
+//!
+//! # Functions
+//!
+//! - `from_toml_str(source)` / `from_yaml_str(source)`: Read a screen from a string.
+//! - `load(path)`: Reads a screen from a `.toml`, `.yaml` or `.yml` file.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::border::BorderStyle;
+use crate::color::NyanColor;
+use crate::errors::{NyanError, NyanResult};
+use crate::layout::{self, Grid, LayoutManager, Stack};
+use crate::nyan_obj::NyanObj;
+use crate::objects::Objects;
+use crate::rect::Edges;
+use crate::style::NyanStyle;
+
+/// `Screen` holds the objects and the layout of a layout file.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "toml")]
+/// # {
+/// use nyan::loader;
+/// use nyan::rect::Rect;
+///
+/// let mut screen = loader::from_toml_str(
+///     r#"
+///     [[objects]]
+///     id = "title"
+///     text = "nyan"
+///     position = [2, 1]
+///
+///     [[objects]]
+///     id = "menu"
+///     type = "list"
+///     items = ["Start", "Quit"]
+///     height = 2
+///     "#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(screen.objects.coordinate("title"), Some((2, 1)));
+/// screen.relayout(Rect::new(0, 0, 80, 24)).unwrap();
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Screen {
+    /// The objects, in the order of the file.
+    pub objects: NyanObj<'static>,
+    /// The layout of the file, if any. Apply it with [`Screen::relayout`] or
+    /// [`LayoutManager::handle_event`].
+    pub layouts: LayoutManager<'static>,
+}
+
+impl std::fmt::Debug for Screen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Screen")
+            .field("objects", &self.objects.ids().collect::<Vec<_>>())
+            .field("layouts", &self.layouts)
+            .finish()
+    }
+}
+
+impl Screen {
+    /// Places the objects with the layout of the file inside `area`, e.g. [`Rect::terminal`].
+    ///
+    /// [`Rect::terminal`]: crate::rect::Rect::terminal
+    pub fn relayout(&mut self, area: crate::rect::Rect) -> NyanResult<()> {
+        self.layouts.relayout(&mut self.objects, area)
+    }
+}
+
+/// The contents of a layout file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    #[serde(default)]
+    objects: Vec<ObjectSpec>,
+    #[serde(default)]
+    layout: Option<LayoutSpec>,
+}
+
+/// The type of an object in a layout file.
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Kind {
+    #[default]
+    Text,
+    Paragraph,
+    ScrollableText,
+    List,
+    ScrollView,
+    Panel,
+    Block,
+    Air,
+}
+
+/// An object in a layout file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ObjectSpec {
+    id: String,
+    #[serde(rename = "type", default)]
+    kind: Kind,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    items: Vec<String>,
+    #[serde(default)]
+    width: Option<u16>,
+    #[serde(default)]
+    height: Option<u16>,
+    #[serde(default)]
+    border: Option<BorderStyle>,
+    #[serde(default)]
+    position: (u16, u16),
+    #[serde(default)]
+    style: NyanStyle,
+    #[serde(default)]
+    fill: Option<NyanColor>,
+    #[serde(default)]
+    padding: Edges,
+    #[serde(default)]
+    margin: Edges,
+    #[serde(default = "visible")]
+    visible: bool,
+    #[serde(default)]
+    cached: bool,
+}
+
+/// Objects are visible unless the file says otherwise.
+fn visible() -> bool {
+    true
+}
+
+/// The layout in a layout file.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum LayoutSpec {
+    Vstack {
+        #[serde(default)]
+        spacing: u16,
+        children: Vec<ChildSpec>,
+    },
+    Hstack {
+        #[serde(default)]
+        spacing: u16,
+        children: Vec<ChildSpec>,
+    },
+    Grid {
+        rows: u16,
+        columns: u16,
+        #[serde(default)]
+        gap: (u16, u16),
+        cells: Vec<CellSpec>,
+    },
+}
+
+/// A child of a stack in a layout file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChildSpec {
+    id: String,
+    #[serde(default)]
+    fixed: Option<u16>,
+    #[serde(default)]
+    flex: Option<u16>,
+}
+
+/// A cell of a grid in a layout file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CellSpec {
+    id: String,
+    row: u16,
+    column: u16,
+    #[serde(default = "one")]
+    row_span: u16,
+    #[serde(default = "one")]
+    column_span: u16,
+}
+
+/// Cells span one row and one column unless the file says otherwise.
+fn one() -> u16 {
+    1
+}
+
+/// Reads a screen from a TOML string. Requires the `toml` feature.
+///
+/// # Returns
+///
+/// - `Ok(Screen)` with the objects and the layout of the file.
+/// - An error if the file is not valid TOML, or an object lacks what its type needs.
+#[cfg(feature = "toml")]
+pub fn from_toml_str(source: &str) -> NyanResult<Screen> {
+    build(toml::from_str(source).map_err(|e| invalid(e.to_string()))?)
+}
+
+/// Reads a screen from a YAML string. Requires the `yaml` feature.
+///
+/// # Returns
+///
+/// - `Ok(Screen)` with the objects and the layout of the file.
+/// - An error if the file is not valid YAML, or an object lacks what its type needs.
+#[cfg(feature = "yaml")]
+pub fn from_yaml_str(source: &str) -> NyanResult<Screen> {
+    build(serde_yaml::from_str(source).map_err(|e| invalid(e.to_string()))?)
+}
+
+/// Reads a screen from a layout file, as TOML or YAML depending on its extension (`.toml`,
+/// `.yaml` or `.yml`).
+///
+/// # Returns
+///
+/// - `Ok(Screen)` with the objects and the layout of the file.
+/// - An error if the file cannot be read, its format is unknown (or its feature is off), or
+///   it is not valid.
+pub fn load<P: AsRef<Path>>(path: P) -> NyanResult<Screen> {
+    let path = path.as_ref();
+    let source = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        #[cfg(feature = "toml")]
+        Some("toml") => from_toml_str(&source),
+        #[cfg(feature = "yaml")]
+        Some("yaml" | "yml") => from_yaml_str(&source),
+        _ => Err(NyanError::NotSupported(
+            format!("unknown layout file format: {}", path.display()).into(),
+        )),
+    }
+}
+
+/// Returns the error of an invalid layout file.
+fn invalid(message: String) -> NyanError<'static> {
+    NyanError::InvalidLayout(message.into())
+}
+
+/// Creates the objects and the layout of a file.
+fn build(file: File) -> NyanResult<Screen> {
+    let mut screen = Screen::default();
+    for spec in file.objects {
+        let object = object(&spec)?;
+        let id = spec.id;
+        screen.objects.add_object(id.clone(), object, spec.position);
+        screen.objects.set_style(id.clone(), spec.style)?;
+        screen.objects.set_fill(id.clone(), spec.fill)?;
+        screen.objects.set_padding(id.clone(), spec.padding)?;
+        screen.objects.set_margin(id.clone(), spec.margin)?;
+        screen.objects.set_visible(id.clone(), spec.visible)?;
+        screen.objects.set_cached(id, spec.cached)?;
+    }
+
+    let layouts = LayoutManager::new();
+    screen.layouts = match file.layout {
+        None => layouts,
+        Some(LayoutSpec::Vstack { spacing, children }) => {
+            layouts.layout(stack(layout::vstack(), spacing, children))
+        }
+        Some(LayoutSpec::Hstack { spacing, children }) => {
+            layouts.layout(stack(layout::hstack(), spacing, children))
+        }
+        Some(LayoutSpec::Grid {
+            rows,
+            columns,
+            gap,
+            cells,
+        }) => {
+            let mut grid = Grid::new(rows, columns).gap(gap.0, gap.1);
+            for cell in cells {
+                grid = grid.place_span(
+                    cell.id,
+                    cell.row,
+                    cell.column,
+                    cell.row_span,
+                    cell.column_span,
+                );
+            }
+            layouts.layout(grid)
+        }
+    };
+    Ok(screen)
+}
+
+/// Creates the object of `spec`.
+fn object(spec: &ObjectSpec) -> NyanResult<Objects<'static>> {
+    let text = || match &spec.text {
+        Some(text) => Ok(text.clone()),
+        None => Err(invalid(format!("object \"{}\" needs a text", spec.id))),
+    };
+    let size = |name: &str, value: Option<u16>| match value {
+        Some(value) => Ok(value),
+        None => Err(invalid(format!("object \"{}\" needs a {name}", spec.id))),
+    };
+    Ok(match spec.kind {
+        Kind::Text => Objects::new_text(text()?),
+        Kind::Paragraph => Objects::new_paragraph(text()?, size("width", spec.width)?),
+        Kind::ScrollableText => Objects::new_scrollable_text(text()?, size("height", spec.height)?),
+        Kind::List => Objects::new_list(spec.items.clone(), size("height", spec.height)?),
+        Kind::ScrollView => Objects::new_scroll_view(
+            text()?,
+            size("width", spec.width)?,
+            size("height", spec.height)?,
+        ),
+        Kind::Panel => Objects::new_panel(
+            size("width", spec.width)?,
+            size("height", spec.height)?,
+            spec.border,
+        ),
+        // Blocks cannot be drawn yet, so a scene with one would fail on its first frame.
+        Kind::Block => {
+            return Err(invalid(format!(
+                "object \"{}\" is a block, which cannot be drawn yet",
+                spec.id
+            )))
+        }
+        Kind::Air => Objects::Air,
+    })
+}
+
+/// Adds the children of a stack in a layout file to `stack`.
+fn stack(stack: Stack<'static>, spacing: u16, children: Vec<ChildSpec>) -> Stack<'static> {
+    let mut stack = stack.spacing(spacing);
+    for child in children {
+        stack = match (child.fixed, child.flex) {
+            (Some(cells), _) => stack.fixed(child.id, cells),
+            (None, Some(weight)) => stack.flex(child.id, weight),
+            (None, None) => stack.auto(child.id),
+        };
+    }
+    stack
+}
@@ -0,0 +1,355 @@
+//! This module provides the `LogView` widget, a scrolling view of log lines kept in a bounded buffer.
+//!
+//! A log view stores the most recent lines up to its capacity, dropping the oldest ones, and colors each line by its `LogLevel`. In follow mode (the default) the view stays at the end and scrolls along as lines are appended; scrolling up leaves follow mode and End (or `F`) enters it again. The shown lines can be filtered by a minimum level and by a substring.
+//!
+//! # Structs
+//!
+//! - `LogView`: The buffered lines, the filters and the scroll state.
+//!
+//! # Enums
+//!
+//! - `LogLevel`: The severity of a log line, from `Trace` to `Error`.
+//!
+//! # Methods
+//!
+//! - `LogLevel::detect(line)`: Guesses the level of a line from its text.
+//! - `new(capacity)`: Creates an empty view keeping at most `capacity` lines.
+//! - `theme(theme)`: Sets the colors of the view.
+//! - `push(level, line)` / `push_line(line)`: Append a line with a given or detected level.
+//! - `clear()` / `len()` / `is_empty()`: Manage the buffer.
+//! - `set_min_level(level)` / `set_filter(text)`: Filter the shown lines.
+//! - `follow(on)` / `is_following()`: Control follow mode.
+//! - `visible_lines()`: Returns the lines that pass the filters.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::color::NyanColor;
+use crate::cursor::Cursor;
+use crate::event::{NyanMouse, NyanMouseKind};
+use crate::input::{NyanInput, NyanKey};
+use crate::layout::{self, Align};
+use crate::rect::Rect;
+use crate::scrollbar::Scrollbar;
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// The `LogLevel` enum tells how severe a log line is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// Very detailed tracing output.
+    Trace,
+    /// Output for debugging.
+    Debug,
+    /// Normal messages.
+    #[default]
+    Info,
+    /// Something unexpected that is not an error.
+    Warn,
+    /// Something failed.
+    Error,
+}
+
+impl LogLevel {
+    /// Guesses the level of `line` from the first level name in its first few words, e.g.
+    /// `"[WARN] disk almost full"` or `"12:00:01 ERROR failed"`. Lines without one are `Info`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::log_view::LogLevel;
+    ///
+    /// assert_eq!(LogLevel::detect("2024-01-01 [error] boom"), LogLevel::Error);
+    /// assert_eq!(LogLevel::detect("WARNING: low memory"), LogLevel::Warn);
+    /// assert_eq!(LogLevel::detect("listening on :8080"), LogLevel::Info);
+    /// ```
+    pub fn detect(line: &str) -> Self {
+        line.split_whitespace()
+            .take(4)
+            .find_map(|word| {
+                let word = word.trim_matches(|c: char| !c.is_ascii_alphabetic());
+                match word.to_ascii_uppercase().as_str() {
+                    "TRACE" => Some(LogLevel::Trace),
+                    "DEBUG" => Some(LogLevel::Debug),
+                    "INFO" => Some(LogLevel::Info),
+                    "WARN" | "WARNING" => Some(LogLevel::Warn),
+                    "ERROR" | "ERR" | "FATAL" => Some(LogLevel::Error),
+                    _ => None,
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the style of a line of this level in `theme`.
+    fn style(self, theme: &Theme) -> NyanStyle {
+        match self {
+            LogLevel::Trace => theme.muted.dim(),
+            LogLevel::Debug => theme.muted,
+            LogLevel::Info => theme.text,
+            LogLevel::Warn => NyanStyle::new().fg(NyanColor::BrightYellow),
+            LogLevel::Error => theme.error,
+        }
+    }
+}
+
+/// `LogView` shows the latest lines of a log and follows new ones.
+///
+/// # Example
+/// ```rust
+/// use nyan::log_view::{LogLevel, LogView};
+/// use nyan::rect::Rect;
+/// use nyan::widget::Widget;
+///
+/// let mut log = LogView::new(3);
+/// log.set_area(Rect::new(0, 0, 40, 2));
+/// for line in ["[INFO] start", "[DEBUG] poll", "[ERROR] timeout", "[INFO] retry"] {
+///     log.push_line(line);
+/// }
+/// assert_eq!(log.len(), 3); // "[INFO] start" was dropped
+///
+/// log.set_min_level(LogLevel::Info);
+/// assert_eq!(log.visible_lines(), ["[ERROR] timeout", "[INFO] retry"]);
+///
+/// log.set_filter("time");
+/// assert_eq!(log.visible_lines(), ["[ERROR] timeout"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LogView {
+    lines: VecDeque<(LogLevel, String)>,
+    capacity: usize,
+    min_level: LogLevel,
+    filter: String,
+    follow: bool,
+    /// The first shown line, counted among the lines that pass the filters.
+    offset: usize,
+    focused: bool,
+    theme: Theme,
+    area: Rect,
+}
+
+impl LogView {
+    /// Creates an empty view that keeps the last `capacity` lines, in follow mode.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(capacity.min(4096)),
+            capacity: capacity.max(1),
+            min_level: LogLevel::Trace,
+            filter: String::new(),
+            follow: true,
+            offset: 0,
+            focused: false,
+            theme: Theme::default(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Sets the colors of the view.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut log = self;
+        log.theme = theme;
+        log
+    }
+
+    /// Appends a line with the given level, dropping the oldest line if the buffer is full.
+    /// Text with several lines is split into several entries.
+    pub fn push<T: AsRef<str>>(&mut self, level: LogLevel, line: T) {
+        for line in line.as_ref().lines() {
+            if self.lines.len() == self.capacity {
+                let dropped = self.lines.pop_front();
+                // Keep the same lines on screen while scrolled back.
+                if dropped.is_some_and(|(level, line)| self.passes(level, &line)) {
+                    self.offset = self.offset.saturating_sub(1);
+                }
+            }
+            self.lines.push_back((level, line.to_string()));
+        }
+        if self.follow {
+            self.scroll_to_end();
+        }
+    }
+
+    /// Appends a line with the level detected from its text (see [`LogLevel::detect`]).
+    pub fn push_line<T: AsRef<str>>(&mut self, line: T) {
+        let line = line.as_ref();
+        self.push(LogLevel::detect(line), line);
+    }
+
+    /// Removes all lines.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.offset = 0;
+    }
+
+    /// Returns the number of buffered lines, shown or not.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Shows only lines of `level` or more severe.
+    pub fn set_min_level(&mut self, level: LogLevel) {
+        self.min_level = level;
+        self.refilter();
+    }
+
+    /// Shows only lines containing `text`, ignoring case. An empty text shows every line.
+    pub fn set_filter<T: Into<String>>(&mut self, text: T) {
+        self.filter = text.into().to_lowercase();
+        self.refilter();
+    }
+
+    /// Enters or leaves follow mode. Entering it scrolls to the end.
+    pub fn follow(&mut self, on: bool) {
+        self.follow = on;
+        if on {
+            self.scroll_to_end();
+        }
+    }
+
+    /// Returns whether the view follows new lines.
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    /// Returns the lines that pass the filters, from the oldest to the newest.
+    pub fn visible_lines(&self) -> Vec<&str> {
+        self.filtered().map(|(_, line)| line.as_str()).collect()
+    }
+
+    /// Returns whether a line passes the filters.
+    fn passes(&self, level: LogLevel, line: &str) -> bool {
+        level >= self.min_level
+            && (self.filter.is_empty() || line.to_lowercase().contains(&self.filter))
+    }
+
+    /// Returns the lines that pass the filters.
+    fn filtered(&self) -> impl Iterator<Item = &(LogLevel, String)> {
+        self.lines
+            .iter()
+            .filter(|(level, line)| self.passes(*level, line))
+    }
+
+    /// Keeps the scroll position valid after the filters changed.
+    fn refilter(&mut self) {
+        if self.follow {
+            self.scroll_to_end();
+        } else {
+            self.offset = self.offset.min(self.max_offset());
+        }
+    }
+
+    /// Returns the offset that shows the last line at the bottom.
+    fn max_offset(&self) -> usize {
+        self.filtered()
+            .count()
+            .saturating_sub(self.area.height as usize)
+    }
+
+    /// Scrolls so the last line is shown.
+    fn scroll_to_end(&mut self) {
+        self.offset = self.max_offset();
+    }
+
+    /// Scrolls by `delta` lines; scrolling up leaves follow mode, reaching the end enters it.
+    fn scroll_by(&mut self, delta: isize) {
+        let max = self.max_offset();
+        self.offset = self.offset.saturating_add_signed(delta).min(max);
+        self.follow = self.offset == max;
+    }
+}
+
+impl Widget for LogView {
+    /// As wide as the longest line, as tall as the buffer.
+    fn size(&self) -> (u16, u16) {
+        let widest = self
+            .lines
+            .iter()
+            .map(|(_, line)| line.width())
+            .max()
+            .unwrap_or(0);
+        (
+            widest.min(u16::MAX as usize) as u16,
+            self.lines.len().min(u16::MAX as usize) as u16,
+        )
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+        self.refilter();
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() {
+            return Ok(());
+        }
+        let rows = self.area.height as usize;
+        let shown: Vec<_> = self.filtered().skip(self.offset).take(rows).collect();
+        let bar = Scrollbar::new(self.filtered().count(), rows, self.offset);
+        let width = self.area.width - u16::from(bar.is_needed());
+
+        for (row, cell) in bar.cells(rows as u16).chars().enumerate() {
+            let (text, style) = match shown.get(row) {
+                Some((level, line)) => (line.as_str(), level.style(&self.theme)),
+                None => ("", NyanStyle::new()),
+            };
+            let text = layout::clip_text(text, 0, width as usize);
+            Cursor::Move(self.area.x, self.area.y + row as u16).queue(&mut out)?;
+            widget::write_styled(out, style, &layout::align_text(&text, width, Align::Start))?;
+            if bar.is_needed() {
+                widget::write_styled(out, self.theme.muted, &cell.to_string())?;
+            }
+        }
+        out.flush()
+    }
+
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        if !self.focused {
+            return false;
+        }
+        let page = self.area.height.max(1) as isize;
+        match input {
+            NyanInput::UpAllow => self.scroll_by(-1),
+            NyanInput::DownAllow => self.scroll_by(1),
+            NyanInput::PageUp => self.scroll_by(-page),
+            NyanInput::PageDown => self.scroll_by(page),
+            NyanInput::Home => self.scroll_by(isize::MIN),
+            NyanInput::End | NyanInput::Shift(NyanInput::Key(NyanKey::F)) => self.follow(true),
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        if !self.area.contains(mouse.x, mouse.y) {
+            return false;
+        }
+        match mouse.kind {
+            NyanMouseKind::ScrollUp => self.scroll_by(-3),
+            NyanMouseKind::ScrollDown => self.scroll_by(3),
+            _ => return false,
+        }
+        true
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
@@ -0,0 +1,189 @@
+//! This module provides `NyanLogger`, a target of the `log` crate that keeps the records in memory for a `LogView`, so libraries logging through the `log` facade show their output inside the TUI instead of writing over the screen.
+//!
+//! The logger appends each record to a `LogBuffer`, a ring buffer shared with the application that drops the oldest records when it is full. Logging may happen on any thread; the application moves the new records into its [`LogView`] once per frame with `LogBuffer::drain_into`.
+//!
+//! Requires the `log` feature.
+//!
+//! # Structs
+//!
+//! - `NyanLogger`: The `log::Log` implementation.
+//! - `LogBuffer`: The records logged but not yet shown.
+//!
+//! # Methods
+//!
+//! - `NyanLogger::new(capacity)`: Creates a logger keeping at most `capacity` records.
+//! - `level(filter)`: Sets the most detailed level that is kept.
+//! - `buffer()`: Returns the buffer the logger appends to.
+//! - `install()`: Makes the logger the target of the `log` macros and returns its buffer.
+//! - `LogBuffer::drain_into(view)`: Moves the records into a `LogView`.
+//! - `LogBuffer::len()` / `is_empty()`: Count the records waiting to be shown.
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::errors::{NyanError, NyanResult};
+use crate::log_view::{LogLevel, LogView};
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Trace => LogLevel::Trace,
+            Level::Debug => LogLevel::Debug,
+            Level::Info => LogLevel::Info,
+            Level::Warn => LogLevel::Warn,
+            Level::Error => LogLevel::Error,
+        }
+    }
+}
+
+/// `LogBuffer` holds the records of a [`NyanLogger`] until they are shown.
+///
+/// Clones share the same records.
+#[derive(Clone, Debug)]
+pub struct LogBuffer {
+    records: Arc<Mutex<VecDeque<(LogLevel, String)>>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    /// Returns the records, even if a thread panicked while logging.
+    fn records(&self) -> MutexGuard<'_, VecDeque<(LogLevel, String)>> {
+        match self.records.lock() {
+            Ok(records) => records,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Appends a record, dropping the oldest one if the buffer is full.
+    fn push(&self, level: LogLevel, line: String) {
+        let mut records = self.records();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back((level, line));
+    }
+
+    /// Moves the records into `view`, from the oldest to the newest. Call it once per frame,
+    /// before drawing the view.
+    ///
+    /// # Returns
+    ///
+    /// The number of records moved.
+    pub fn drain_into(&self, view: &mut LogView) -> usize {
+        // Take the records first, so logging threads do not wait for the view.
+        let records = std::mem::take(&mut *self.records());
+        let count = records.len();
+        for (level, line) in records {
+            view.push(level, line);
+        }
+        count
+    }
+
+    /// Returns the number of records waiting to be shown.
+    pub fn len(&self) -> usize {
+        self.records().len()
+    }
+
+    /// Returns whether no records are waiting to be shown.
+    pub fn is_empty(&self) -> bool {
+        self.records().is_empty()
+    }
+}
+
+/// `NyanLogger` appends the records of the `log` crate to a [`LogBuffer`].
+///
+/// Each record becomes a line like `WARN  net: connection lost`, colored by its level in the
+/// view.
+///
+/// # Example
+/// ```rust
+/// use log::Log;
+/// use nyan::log_view::LogView;
+/// use nyan::logger::NyanLogger;
+///
+/// let logger = NyanLogger::new(100).level(log::LevelFilter::Info);
+/// let buffer = logger.buffer();
+/// // Usually `logger.install()`, after which `log::warn!` and friends end up here.
+/// logger.log(
+///     &log::Record::builder()
+///         .level(log::Level::Warn)
+///         .target("net")
+///         .args(format_args!("connection lost"))
+///         .build(),
+/// );
+///
+/// let mut view = LogView::new(1000);
+/// assert_eq!(buffer.drain_into(&mut view), 1);
+/// assert_eq!(view.visible_lines(), ["WARN  net: connection lost"]);
+/// ```
+#[derive(Debug)]
+pub struct NyanLogger {
+    buffer: LogBuffer,
+    level: LevelFilter,
+}
+
+impl NyanLogger {
+    /// Creates a logger that keeps the last `capacity` records until they are shown, of every
+    /// level.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buffer: LogBuffer {
+                records: Arc::new(Mutex::new(VecDeque::new())),
+                capacity: capacity.max(1),
+            },
+            level: LevelFilter::Trace,
+        }
+    }
+
+    /// Keeps only records of `filter` or more severe, e.g. `LevelFilter::Info` to leave out
+    /// debug output.
+    pub fn level(self, filter: LevelFilter) -> Self {
+        let mut logger = self;
+        logger.level = filter;
+        logger
+    }
+
+    /// Returns the buffer the logger appends to.
+    pub fn buffer(&self) -> LogBuffer {
+        self.buffer.clone()
+    }
+
+    /// Makes the logger the target of the `log` macros and sets their maximum level.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(LogBuffer)` with the buffer to drain into a [`LogView`].
+    /// - An error if a logger was already installed.
+    pub fn install(self) -> NyanResult<LogBuffer> {
+        let buffer = self.buffer();
+        let level = self.level;
+        log::set_boxed_logger(Box::new(self))
+            .map_err(|e| NyanError::Other(Cow::Owned(e.to_string())))?;
+        log::set_max_level(level);
+        Ok(buffer)
+    }
+}
+
+impl Log for NyanLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{:<5} {}: {}",
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        self.buffer.push(record.level().into(), line);
+    }
+
+    fn flush(&self) {}
+}
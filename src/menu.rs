@@ -0,0 +1,497 @@
+//! This module provides the `MenuBar` widget, a row of menus with popup submenus across the top of the screen.
+//!
+//! Each menu of the bar is a list of `MenuItem`s: actions identified by an id, nested submenus and separators. A menu is opened with Alt and the first letter of its title, with Enter or Down while the bar is focused, or by clicking its title. Inside the popups, Up / Down move the highlight, Right / Enter open a submenu, Left closes it (or moves to the previous menu), and Enter or a click on an action closes all popups and emits the action's id, which the application collects with `take_action()`.
+//!
+//! # Structs
+//!
+//! - `MenuBar`: The menus and which popups are open.
+//!
+//! # Enums
+//!
+//! - `MenuItem`: An entry of a menu: an action, a submenu or a separator.
+//!
+//! # Methods
+//!
+//! - `MenuBar::new()`: Creates a bar without menus.
+//! - `MenuBar::menu(title, items)`: Adds a menu.
+//! - `MenuBar::theme(theme)`: Sets the colors of the bar.
+//! - `MenuBar::open(index)` / `MenuBar::close()` / `MenuBar::is_open()`: Open and close menus.
+//! - `MenuBar::take_action()`: Returns the id of the action chosen since the last call.
+//! - `MenuBar::title_regions()` / `MenuBar::popup_regions()`: Return where the titles and the open popups are drawn.
+//! - `MenuItem::action(label, id)` / `MenuItem::submenu(label, items)` / `MenuItem::separator()`: Create menu entries.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::border;
+use crate::cursor::Cursor;
+use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::NyanInput;
+use crate::layout::{self, Align};
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// The `MenuItem` enum is an entry of a menu.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MenuItem<'a> {
+    /// An action; choosing it emits `id`.
+    Action {
+        label: Cow<'a, str>,
+        id: Cow<'a, str>,
+    },
+    /// A nested menu, opened to the right of its parent.
+    Submenu {
+        label: Cow<'a, str>,
+        items: Vec<MenuItem<'a>>,
+    },
+    /// A line between groups of entries. It cannot be highlighted.
+    Separator,
+}
+
+impl<'a> MenuItem<'a> {
+    /// Creates an action entry that emits `id` when it is chosen.
+    pub fn action<T: Into<Cow<'a, str>>, P: Into<Cow<'a, str>>>(label: T, id: P) -> Self {
+        Self::Action {
+            label: label.into(),
+            id: id.into(),
+        }
+    }
+
+    /// Creates an entry that opens a nested menu.
+    pub fn submenu<T: Into<Cow<'a, str>>, I: IntoIterator<Item = MenuItem<'a>>>(
+        label: T,
+        items: I,
+    ) -> Self {
+        Self::Submenu {
+            label: label.into(),
+            items: items.into_iter().collect(),
+        }
+    }
+
+    /// Creates a separator line.
+    pub fn separator() -> Self {
+        Self::Separator
+    }
+
+    /// Returns the label of the entry (empty for separators).
+    pub fn label(&self) -> &str {
+        match self {
+            MenuItem::Action { label, .. } | MenuItem::Submenu { label, .. } => label,
+            MenuItem::Separator => "",
+        }
+    }
+}
+
+/// A menu of the bar.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Menu<'a> {
+    title: Cow<'a, str>,
+    items: Vec<MenuItem<'a>>,
+}
+
+/// `MenuBar` is a row of menus with popup submenus.
+///
+/// # Example
+/// ```rust
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::menu::{MenuBar, MenuItem};
+/// use nyan::rect::Rect;
+/// use nyan::widget::Widget;
+///
+/// let mut bar = MenuBar::new()
+///     .menu("File", [
+///         MenuItem::action("Open", "open"),
+///         MenuItem::submenu("Recent", [MenuItem::action("notes.txt", "recent:notes")]),
+///         MenuItem::separator(),
+///         MenuItem::action("Quit", "quit"),
+///     ])
+///     .menu("Help", [MenuItem::action("About", "about")]);
+/// bar.set_area(Rect::new(0, 0, 80, 1));
+///
+/// bar.handle_input(&NyanInput::Alt(NyanKey::F)); // open "File"
+/// bar.handle_input(&NyanInput::DownAllow); // "Recent"
+/// bar.handle_input(&NyanInput::RightAllow); // open the submenu
+/// bar.handle_input(&NyanInput::Enter);
+///
+/// assert_eq!(bar.take_action().as_deref(), Some("recent:notes"));
+/// assert!(!bar.is_open());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MenuBar<'a> {
+    menus: Vec<Menu<'a>>,
+    /// The highlighted menu of the bar.
+    current: usize,
+    /// The highlighted entry of every open popup, from the menu to the innermost submenu.
+    /// Empty while no menu is open.
+    open: Vec<usize>,
+    action: Option<Cow<'a, str>>,
+    focused: bool,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> MenuBar<'a> {
+    /// Creates a bar without menus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a menu titled `title`.
+    pub fn menu<T: Into<Cow<'a, str>>, I: IntoIterator<Item = MenuItem<'a>>>(
+        self,
+        title: T,
+        items: I,
+    ) -> Self {
+        let mut bar = self;
+        bar.menus.push(Menu {
+            title: title.into(),
+            items: items.into_iter().collect(),
+        });
+        bar
+    }
+
+    /// Sets the colors of the bar.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut bar = self;
+        bar.theme = theme;
+        bar
+    }
+
+    /// Returns whether a menu is open.
+    pub fn is_open(&self) -> bool {
+        !self.open.is_empty()
+    }
+
+    /// Opens the menu at `index`, with its first entry highlighted.
+    pub fn open(&mut self, index: usize) {
+        if let Some(menu) = self.menus.get(index) {
+            self.current = index;
+            self.open = vec![first_selectable(&menu.items, 0)];
+        }
+    }
+
+    /// Closes all popups.
+    pub fn close(&mut self) {
+        self.open.clear();
+    }
+
+    /// Returns the id of the action chosen since the last call, and forgets it.
+    pub fn take_action(&mut self) -> Option<Cow<'a, str>> {
+        self.action.take()
+    }
+
+    /// Returns the rectangle of every menu title in the bar, on the first row of the area.
+    pub fn title_regions(&self) -> Vec<Rect> {
+        let mut x = self.area.x;
+        self.menus
+            .iter()
+            .map(|menu| {
+                let width = (menu.title.width() + 2).min(u16::MAX as usize) as u16;
+                let width = width.min(self.area.right().saturating_sub(x));
+                let title = Rect::new(x, self.area.y, width, self.area.height.min(1));
+                x = x.saturating_add(width);
+                title
+            })
+            .collect()
+    }
+
+    /// Returns the entries of the popup at `level` (0 is the menu itself).
+    fn items(&self, level: usize) -> &[MenuItem<'a>] {
+        let mut items: &[MenuItem<'a>] = match self.menus.get(self.current) {
+            Some(menu) => &menu.items,
+            None => return &[],
+        };
+        for &index in &self.open[..level] {
+            match items.get(index) {
+                Some(MenuItem::Submenu { items: inner, .. }) => items = inner,
+                _ => return &[],
+            }
+        }
+        items
+    }
+
+    /// Returns the rectangle of every open popup, including its border, from the menu to the
+    /// innermost submenu.
+    pub fn popup_regions(&self) -> Vec<Rect> {
+        let mut regions: Vec<Rect> = Vec::with_capacity(self.open.len());
+        for level in 0..self.open.len() {
+            let items = self.items(level);
+            let (x, y) = match regions.last() {
+                // A submenu opens right of its entry, so its first entry is level with it.
+                Some(parent) => (parent.right(), parent.y + self.open[level - 1] as u16),
+                None => {
+                    let title = self.title_regions().get(self.current).copied();
+                    let x = title.map_or(self.area.x, |t| t.x);
+                    (x, self.area.y.saturating_add(1))
+                }
+            };
+            let (width, height) = popup_size(items);
+            regions.push(Rect::new(x, y, width, height));
+        }
+        regions
+    }
+
+    /// Moves the highlight of the innermost popup by one entry, skipping separators.
+    fn move_highlight(&mut self, down: bool) {
+        let level = self.open.len() - 1;
+        let items = self.items(level);
+        let len = items.len();
+        let mut index = self.open[level];
+        for _ in 0..len {
+            index = if down {
+                (index + 1) % len
+            } else {
+                (index + len - 1) % len
+            };
+            if items[index] != MenuItem::Separator {
+                break;
+            }
+        }
+        self.open[level] = index;
+    }
+
+    /// Activates the highlighted entry of the innermost popup: opens a submenu or emits an
+    /// action.
+    fn activate(&mut self) {
+        let level = self.open.len() - 1;
+        match self.items(level).get(self.open[level]) {
+            Some(MenuItem::Submenu { items, .. }) => {
+                let first = first_selectable(items, 0);
+                self.open.push(first);
+            }
+            Some(MenuItem::Action { id, .. }) => {
+                self.action = Some(id.clone());
+                self.close();
+            }
+            _ => {}
+        }
+    }
+
+    /// Switches to the next / previous menu of the bar, keeping it open if a menu was open.
+    fn switch(&mut self, forward: bool) {
+        let len = self.menus.len();
+        if len == 0 {
+            return;
+        }
+        let next = if forward {
+            (self.current + 1) % len
+        } else {
+            (self.current + len - 1) % len
+        };
+        if self.is_open() {
+            self.open(next);
+        } else {
+            self.current = next;
+        }
+    }
+}
+
+/// Returns the index of the first entry at or after `from` that is not a separator.
+fn first_selectable(items: &[MenuItem], from: usize) -> usize {
+    items
+        .iter()
+        .skip(from)
+        .position(|item| *item != MenuItem::Separator)
+        .map_or(0, |index| index + from)
+}
+
+/// Returns the size of a popup with a border around `items`.
+fn popup_size(items: &[MenuItem]) -> (u16, u16) {
+    let submenus = items
+        .iter()
+        .any(|item| matches!(item, MenuItem::Submenu { .. }));
+    let label = items.iter().map(|i| i.label().width()).max().unwrap_or(0);
+    // A space on each side of the label, room for the arrow and the border.
+    let width = label + 2 + if submenus { 2 } else { 0 } + 2;
+    (
+        width.min(u16::MAX as usize) as u16,
+        (items.len() + 2).min(u16::MAX as usize) as u16,
+    )
+}
+
+impl<'a> Widget for MenuBar<'a> {
+    /// A single row with all titles.
+    fn size(&self) -> (u16, u16) {
+        let width: usize = self.menus.iter().map(|m| m.title.width() + 2).sum();
+        (width.min(u16::MAX as usize) as u16, 1)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() {
+            return Ok(());
+        }
+        let bar_style = self.theme.text.reverse();
+        Cursor::Move(self.area.x, self.area.y).queue(&mut out)?;
+        widget::write_styled(out, bar_style, &" ".repeat(self.area.width as usize))?;
+        for (index, (menu, title)) in self.menus.iter().zip(self.title_regions()).enumerate() {
+            let style = if index == self.current && (self.is_open() || self.focused) {
+                self.theme.selection
+            } else {
+                bar_style
+            };
+            let text = layout::clip_text(&format!(" {} ", menu.title), 0, title.width as usize);
+            Cursor::Move(title.x, title.y).queue(&mut out)?;
+            widget::write_styled(out, style, &text)?;
+        }
+
+        let set = border::default_border().set();
+        for (level, region) in self.popup_regions().into_iter().enumerate() {
+            NyanStyle::base().patch(self.theme.text).apply(&mut out)?;
+            border::default_border().draw(&mut out, region)?;
+            NyanStyle::reset(&mut out)?;
+
+            let inner = region.width.saturating_sub(2);
+            for (row, item) in self.items(level).iter().enumerate() {
+                let y = region.y + 1 + row as u16;
+                let (text, style) = match item {
+                    MenuItem::Separator => {
+                        Cursor::Move(region.x + 1, y).queue(&mut out)?;
+                        let line = set.horizontal.to_string().repeat(inner as usize);
+                        widget::write_styled(out, self.theme.muted, &line)?;
+                        continue;
+                    }
+                    MenuItem::Submenu { label, .. } => {
+                        let label =
+                            layout::align_text(label, inner.saturating_sub(4), Align::Start);
+                        (format!(" {label} ▸ "), self.theme.text)
+                    }
+                    MenuItem::Action { label, .. } => {
+                        let label =
+                            layout::align_text(label, inner.saturating_sub(2), Align::Start);
+                        (format!(" {label} "), self.theme.text)
+                    }
+                };
+                let style = if row == self.open[level] {
+                    self.theme.selection
+                } else {
+                    style
+                };
+                Cursor::Move(region.x + 1, y).queue(&mut out)?;
+                widget::write_styled(out, style, &text)?;
+            }
+        }
+        out.flush()
+    }
+
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        if let NyanInput::Alt(key) = input {
+            let letter = key.to_char().to_ascii_lowercase();
+            let found = self.menus.iter().position(|menu| {
+                menu.title
+                    .chars()
+                    .find(|c| c.is_alphanumeric())
+                    .is_some_and(|c| c.to_ascii_lowercase() == letter)
+            });
+            if let Some(index) = found {
+                self.open(index);
+                return true;
+            }
+            return false;
+        }
+
+        if !self.is_open() {
+            if !self.focused {
+                return false;
+            }
+            match input {
+                NyanInput::LeftAllow => self.switch(false),
+                NyanInput::RightAllow => self.switch(true),
+                NyanInput::Enter | NyanInput::DownAllow => self.open(self.current),
+                _ => return false,
+            }
+            return true;
+        }
+
+        match input {
+            NyanInput::UpAllow => self.move_highlight(false),
+            NyanInput::DownAllow => self.move_highlight(true),
+            NyanInput::Enter => self.activate(),
+            NyanInput::RightAllow => {
+                let level = self.open.len() - 1;
+                match self.items(level).get(self.open[level]) {
+                    Some(MenuItem::Submenu { .. }) => self.activate(),
+                    _ => self.switch(true),
+                }
+            }
+            NyanInput::LeftAllow if self.open.len() > 1 => {
+                self.open.pop();
+            }
+            NyanInput::LeftAllow => self.switch(false),
+            NyanInput::Esc => {
+                self.open.pop();
+            }
+            // Open menus keep all keys to themselves.
+            _ => {}
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        let (x, y) = (mouse.x, mouse.y);
+        let title = self.title_regions().iter().position(|t| t.contains(x, y));
+        match mouse.kind {
+            NyanMouseKind::Down(NyanMouseButton::Left) => {
+                if let Some(index) = title {
+                    if self.is_open() && index == self.current {
+                        self.close();
+                    } else {
+                        self.open(index);
+                    }
+                    return true;
+                }
+                let popups = self.popup_regions();
+                let Some(level) = popups.iter().rposition(|p| p.contains(x, y)) else {
+                    if self.is_open() {
+                        // A click anywhere else closes the menus.
+                        self.close();
+                        return true;
+                    }
+                    return false;
+                };
+                let row = y.saturating_sub(popups[level].y + 1) as usize;
+                if y > popups[level].y && row < self.items(level).len() {
+                    self.open.truncate(level + 1);
+                    self.open[level] = row;
+                    self.activate();
+                }
+                true
+            }
+            NyanMouseKind::Moved if self.is_open() => match title {
+                // Moving over another title switches to its menu.
+                Some(index) if index != self.current => {
+                    self.open(index);
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if !focused {
+            self.close();
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! This module provides `Notifications`, a manager of toasts: short messages that pop up in a corner of the screen and disappear on their own.
+//!
+//! Every toast has a severity `Level`, which decides its color, and a lifetime after which it expires. Toasts are stacked in a corner of the screen, the newest closest to the corner, and drawn in boxes on top of everything else, so draw them after the scene. Expired toasts are removed by `update()`, typically called once per frame.
+//!
+//! # Structs
+//!
+//! - `Notifications`: The shown toasts and where and how they are drawn.
+//! - `ToastId`: Identifies a toast, e.g. to dismiss it early.
+//!
+//! # Enums
+//!
+//! - `Level`: The severity of a toast: info, success, warning or error.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a manager without toasts, stacking them in the bottom-right corner.
+//! - `corner(horizontal, vertical)` / `max_width(cells)` / `max_visible(count)` / `theme(theme)`: Configure where and how toasts are drawn.
+//! - `push(message, level, duration)`: Shows a toast for `duration`.
+//! - `info(message)` / `success(message)` / `warning(message)` / `error(message)`: Show a toast for the default duration of its level.
+//! - `dismiss(id)` / `clear()`: Remove toasts early.
+//! - `update()` / `update_at(now)`: Remove expired toasts.
+//! - `len()` / `is_empty()` / `messages()`: Query the shown toasts.
+//! - `regions(area)`: Returns the box of every visible toast.
+//! - `draw_in(out, area)`: Draws the visible toasts.
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::border;
+use crate::color::NyanColor;
+use crate::cursor::Cursor;
+use crate::layout::{self, Align};
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+use crate::widget;
+
+/// The `Level` enum tells how severe a toast is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Level {
+    /// Something the user may want to know.
+    Info,
+    /// Something finished successfully.
+    Success,
+    /// Something may need the user's attention.
+    Warning,
+    /// Something failed.
+    Error,
+}
+
+impl Level {
+    /// Returns how long a toast of this level is shown by default: errors stay longer.
+    pub fn default_duration(self) -> Duration {
+        match self {
+            Level::Info | Level::Success => Duration::from_secs(3),
+            Level::Warning => Duration::from_secs(5),
+            Level::Error => Duration::from_secs(8),
+        }
+    }
+
+    /// Returns the style of a toast of this level in `theme`.
+    fn style(self, theme: &Theme) -> NyanStyle {
+        match self {
+            Level::Info => theme.accent,
+            Level::Success => NyanStyle::new().fg(NyanColor::BrightGreen).bold(),
+            Level::Warning => NyanStyle::new().fg(NyanColor::BrightYellow).bold(),
+            Level::Error => theme.error,
+        }
+    }
+}
+
+/// `ToastId` identifies a toast shown by [`Notifications`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ToastId(u64);
+
+/// A shown toast.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Toast<'a> {
+    id: ToastId,
+    message: Cow<'a, str>,
+    level: Level,
+    expires: Instant,
+}
+
+/// `Notifications` shows stacked toasts that expire on their own.
+///
+/// # Example
+/// ```rust
+/// use std::time::{Duration, Instant};
+///
+/// use nyan::notification::{Level, Notifications};
+/// use nyan::rect::Rect;
+///
+/// let mut toasts = Notifications::new();
+/// toasts.push("Saved", Level::Success, Duration::from_secs(2));
+/// toasts.error("Disk almost full");
+///
+/// // The newest toast sits in the bottom-right corner, the older one above it.
+/// let regions = toasts.regions(Rect::new(0, 0, 80, 24));
+/// assert_eq!(regions[1], Rect::new(60, 21, 20, 3));
+/// assert_eq!(regions[0].bottom(), 21);
+///
+/// toasts.update_at(Instant::now() + Duration::from_secs(3));
+/// assert_eq!(toasts.messages().collect::<Vec<_>>(), ["Disk almost full"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Notifications<'a> {
+    /// The shown toasts, from the oldest to the newest.
+    toasts: VecDeque<Toast<'a>>,
+    next_id: u64,
+    horizontal: Align,
+    vertical: Align,
+    max_width: u16,
+    max_visible: usize,
+    theme: Theme,
+}
+
+impl<'a> Default for Notifications<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Notifications<'a> {
+    /// Creates a manager without toasts that stacks them in the bottom-right corner.
+    pub fn new() -> Self {
+        Self {
+            toasts: VecDeque::new(),
+            next_id: 0,
+            horizontal: Align::End,
+            vertical: Align::End,
+            max_width: 40,
+            max_visible: 5,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Sets the corner (or edge) the toasts are stacked in, e.g. `(Align::End, Align::Start)`
+    /// for the top-right corner. Toasts grow away from the vertical edge; `Align::Center`
+    /// stacks them downwards from the middle.
+    pub fn corner(self, horizontal: Align, vertical: Align) -> Self {
+        let mut toasts = self;
+        toasts.horizontal = horizontal;
+        toasts.vertical = vertical;
+        toasts
+    }
+
+    /// Sets the widest a toast may be, including its border (40 cells by default). Longer
+    /// messages are wrapped.
+    pub fn max_width(self, cells: u16) -> Self {
+        let mut toasts = self;
+        toasts.max_width = cells.max(5);
+        toasts
+    }
+
+    /// Sets how many toasts are drawn at most (5 by default); older ones wait until newer ones
+    /// expire.
+    pub fn max_visible(self, count: usize) -> Self {
+        let mut toasts = self;
+        toasts.max_visible = count;
+        toasts
+    }
+
+    /// Sets the colors of the toasts.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut toasts = self;
+        toasts.theme = theme;
+        toasts
+    }
+
+    /// Shows a toast with `message` for `duration`.
+    ///
+    /// # Returns
+    ///
+    /// The id of the toast, to dismiss it early.
+    pub fn push<T: Into<Cow<'a, str>>>(
+        &mut self,
+        message: T,
+        level: Level,
+        duration: Duration,
+    ) -> ToastId {
+        let id = ToastId(self.next_id);
+        self.next_id += 1;
+        self.toasts.push_back(Toast {
+            id,
+            message: message.into(),
+            level,
+            expires: Instant::now() + duration,
+        });
+        id
+    }
+
+    /// Shows an info toast for the default duration (see [`Level::default_duration`]).
+    pub fn info<T: Into<Cow<'a, str>>>(&mut self, message: T) -> ToastId {
+        self.push(message, Level::Info, Level::Info.default_duration())
+    }
+
+    /// Shows a success toast for the default duration.
+    pub fn success<T: Into<Cow<'a, str>>>(&mut self, message: T) -> ToastId {
+        self.push(message, Level::Success, Level::Success.default_duration())
+    }
+
+    /// Shows a warning toast for the default duration.
+    pub fn warning<T: Into<Cow<'a, str>>>(&mut self, message: T) -> ToastId {
+        self.push(message, Level::Warning, Level::Warning.default_duration())
+    }
+
+    /// Shows an error toast for the default duration.
+    pub fn error<T: Into<Cow<'a, str>>>(&mut self, message: T) -> ToastId {
+        self.push(message, Level::Error, Level::Error.default_duration())
+    }
+
+    /// Removes the toast `id` before it expires.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the toast was still shown.
+    pub fn dismiss(&mut self, id: ToastId) -> bool {
+        let before = self.toasts.len();
+        self.toasts.retain(|toast| toast.id != id);
+        self.toasts.len() != before
+    }
+
+    /// Removes all toasts.
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+    }
+
+    /// Removes the toasts that expired by now.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a toast was removed, so the screen needs to be redrawn.
+    pub fn update(&mut self) -> bool {
+        self.update_at(Instant::now())
+    }
+
+    /// Removes the toasts that expired by `now`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a toast was removed.
+    pub fn update_at(&mut self, now: Instant) -> bool {
+        let before = self.toasts.len();
+        self.toasts.retain(|toast| toast.expires > now);
+        self.toasts.len() != before
+    }
+
+    /// Returns the number of shown toasts, including those waiting to become visible.
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    /// Returns whether no toast is shown.
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    /// Returns the messages of the shown toasts, from the oldest to the newest.
+    pub fn messages(&self) -> impl Iterator<Item = &str> {
+        self.toasts.iter().map(|toast| toast.message.as_ref())
+    }
+
+    /// Returns the box of every visible toast inside `area`, from the oldest to the newest.
+    /// Toasts that do not fit are left out.
+    pub fn regions(&self, area: Rect) -> Vec<Rect> {
+        self.layout(area)
+            .into_iter()
+            .map(|(_, rect, _)| rect)
+            .collect()
+    }
+
+    /// Draws the visible toasts inside `area`. Draw them after everything else.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the toasts were drawn.
+    /// - An error if writing fails.
+    pub fn draw_in<W: Write>(&self, out: &mut W, area: Rect) -> io::Result<()> {
+        for (toast, rect, lines) in self.layout(area) {
+            let style = toast.level.style(&self.theme);
+            let blank = " ".repeat(rect.width.saturating_sub(2) as usize);
+            NyanStyle::base().patch(style).apply(out)?;
+            border::default_border().draw(out, rect)?;
+            NyanStyle::reset(out)?;
+            for (row, line) in lines.iter().enumerate() {
+                let line = layout::align_text(line, rect.width.saturating_sub(4), Align::Start);
+                Cursor::Move(rect.x + 1, rect.y + 1 + row as u16).queue(out)?;
+                write!(out, " ")?;
+                widget::write_styled(out, self.theme.text, &line)?;
+                write!(out, " ")?;
+            }
+            if lines.is_empty() {
+                Cursor::Move(rect.x + 1, rect.y + 1).queue(out)?;
+                write!(out, "{blank}")?;
+            }
+        }
+        out.flush()
+    }
+
+    /// Returns the visible toasts with their boxes and wrapped messages.
+    fn layout(&self, area: Rect) -> Vec<(&Toast<'a>, Rect, Vec<String>)> {
+        let max_width = self.max_width.min(area.width);
+        if max_width < 5 {
+            return Vec::new();
+        }
+        let mut placed = Vec::new();
+        let mut used = 0u16;
+        // The newest toast is placed first, closest to the corner.
+        for toast in self.toasts.iter().rev().take(self.max_visible) {
+            let lines = layout::wrap_text(&toast.message, max_width - 4);
+            let width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16 + 4;
+            let height = lines.len().max(1) as u16 + 2;
+            if used + height > area.height {
+                break;
+            }
+            let x = area.x + self.horizontal.offset(area.width, width);
+            let y = match self.vertical {
+                Align::Start => area.y + used,
+                Align::Center => area.y + area.height / 2 + used,
+                Align::End => area.bottom() - used - height,
+            };
+            if y + height > area.bottom() {
+                break;
+            }
+            used += height;
+            placed.push((toast, Rect::new(x, y, width, height), lines));
+        }
+        placed.reverse();
+        placed
+    }
+}
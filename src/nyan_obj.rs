@@ -8,8 +8,11 @@ Each object is represented by the [`Objects`] enum, which supports various types
 - **Text:** A textual object that prints a string.
 - **Air:** An empty (non-visible) object.
 - **Block:** A block object (drawing functionality is not yet implemented).
+- **ScrollableText / List / ScrollView:** Multi-line objects that show a window of their lines and can be
+  scrolled, e.g. with the mouse wheel through [`NyanObj::route_scroll`].
 
-Objects are stored along with a unique identifier (as a `Cow<str>`) and display coordinates. The module provides methods to add, remove, update, and draw these objects.
+Objects are stored along with a unique identifier (as a `Cow<str>`), display coordinates, an optional style, padding and margin (see [`NyanObj::set_padding`] / [`NyanObj::set_margin`]) and an optional background fill that paints the whole bounding box (see [`NyanObj::set_fill`]), and can be hidden without being removed (see [`NyanObj::set_visible`]). Objects that rarely change, such as large tables and paragraphs, can be cached, so their output is formatted once and reused until they change (see [`NyanObj::set_cached`]). Objects can carry a [`Body`] that moves them every frame (see [`NyanObj::advance`]). The module provides methods to add, remove, update, and draw these objects, and to find the object under a given cell (e.g. a mouse click).
+The whole scene can be drawn at once with [`NyanObj::draw_all`] (with the `rayon` feature, large scenes can be formatted on several threads with `draw_all_parallel_in`), or with everything but a modal object dimmed with [`NyanObj::draw_modal`], and after small changes only the regions that changed can be repainted with [`NyanObj::draw_damaged_in`]. With the `serde` feature, a scene can be serialized and deserialized as a list of its objects.
 
 # Examples
 
@@ -37,10 +40,37 @@ fn main() -> Result<(), Box<dyn Error>>{
 ```
 */
 
-use crate::cursor::{self, Cursor};
-use crate::errors::{self, NyanError};
+use crate::color::{self, ColorSupport, NyanColor};
+use crate::cursor::Cursor;
+use crate::errors::{self, NyanError, NyanResult};
+use crate::event::{NyanEvent, NyanMouse, NyanMouseKind};
+use crate::layout::{self, Align};
 use crate::objects::Objects;
+use crate::physics::Body;
+use crate::rect::{Edges, Rect};
+use crate::stats::{self, SceneStats};
+use crate::style::NyanStyle;
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// The key of the next collection, which tells collections apart (see [`NyanObj::scene`]).
+static NEXT_SCENE: AtomicU64 = AtomicU64::new(0);
+
+/// The most regions kept in the damage of a collection. Past it, the damage is merged into
+/// the box around it, so scenes that are never marked as drawn do not grow it without limit.
+const MAX_DAMAGE: usize = 64;
+
+/// The output of a cached object, and the styles it was drawn with.
+#[derive(Clone)]
+struct Render {
+    /// The overlay, the base style and the color support the output depends on.
+    key: (NyanStyle, NyanStyle, ColorSupport),
+    bytes: Vec<u8>,
+}
 
 /// Internal structure representing a single object entry in the collection.
 ///
@@ -48,10 +78,47 @@ use std::borrow::Cow;
 /// - An object of type [`Objects`].
 /// - A unique identifier stored as a `Cow<str>`.
 /// - The display coordinate as a tuple `(x, y)`.
+/// - The [`NyanStyle`] applied around the object's output.
 struct NyanObjs<'a> {
     object: Objects<'a>,
     id: Cow<'a, str>,
     coordinate: (u16, u16),
+    style: NyanStyle,
+    fill: Option<NyanColor>,
+    padding: Edges,
+    margin: Edges,
+    visible: bool,
+    body: Option<Body>,
+    /// The position of a moving object with sub-cell precision.
+    position: (f32, f32),
+    /// Whether the output is kept and reused (see [`NyanObj::set_cached`]).
+    cached: bool,
+    /// The kept output of a cached object, drawn relative to the cursor.
+    render: Mutex<Option<Render>>,
+    /// Whether the object changed since the collection was last drawn, so its box needs to
+    /// be repainted (see [`NyanObj::damage`]).
+    damaged: bool,
+}
+
+impl<'a> Clone for NyanObjs<'a> {
+    fn clone(&self) -> Self {
+        let render = self.render.lock().unwrap_or_else(PoisonError::into_inner);
+        Self {
+            object: self.object.clone(),
+            id: self.id.clone(),
+            coordinate: self.coordinate,
+            style: self.style,
+            fill: self.fill,
+            padding: self.padding,
+            margin: self.margin,
+            visible: self.visible,
+            body: self.body,
+            position: self.position,
+            cached: self.cached,
+            render: Mutex::new(render.clone()),
+            damaged: self.damaged,
+        }
+    }
 }
 
 impl<'a> NyanObjs<'a> {
@@ -71,8 +138,51 @@ impl<'a> NyanObjs<'a> {
             object,
             id,
             coordinate,
+            style: NyanStyle::default(),
+            fill: None,
+            padding: Edges::default(),
+            margin: Edges::default(),
+            visible: true,
+            body: None,
+            position: (coordinate.0 as f32, coordinate.1 as f32),
+            cached: false,
+            render: Mutex::new(None),
+            damaged: true,
         }
     }
+
+    /// Drops the kept output, as the object will look different.
+    fn changed(&mut self) {
+        *self
+            .render
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// Returns the box of the object: its size plus padding, offset from the coordinate by
+    /// the margin.
+    fn rect(&self) -> Rect {
+        let (width, height) = self.object.size();
+        Rect::new(
+            self.coordinate.0.saturating_add(self.margin.left),
+            self.coordinate.1.saturating_add(self.margin.top),
+            width.saturating_add(self.padding.horizontal()),
+            height.saturating_add(self.padding.vertical()),
+        )
+    }
+
+    /// Returns the size of the box including the margin.
+    fn outer_size(&self) -> (u16, u16) {
+        let (width, height) = self.object.size();
+        (
+            width
+                .saturating_add(self.padding.horizontal())
+                .saturating_add(self.margin.horizontal()),
+            height
+                .saturating_add(self.padding.vertical())
+                .saturating_add(self.margin.vertical()),
+        )
+    }
 }
 
 /// A collection of drawable objects identified by unique string IDs.
@@ -81,14 +191,41 @@ impl<'a> NyanObjs<'a> {
 /// Each stored object includes its type (defined by the [`Objects`] enum), a unique ID, and
 /// a coordinate for drawing.
 ///
+/// A clone is a snapshot of the scene, e.g. to hand to a
+/// [`Renderer`](crate::renderer::Renderer) on another thread.
+///
 /// # Type Parameters
 ///
 /// - `'a`: Lifetime for the stored object data.
+#[derive(Clone)]
 pub struct NyanObj<'a> {
     /// Internal storage for objects.
     ///
     /// Each element holds the object, its unique identifier, and its drawing coordinate.
     inner: Vec<NyanObjs<'a>>,
+
+    /// Whether the objects need to be drawn again (see [`NyanObj::is_dirty`]).
+    dirty: bool,
+
+    /// The key of the collection, shared by its clones (see [`NyanObj::scene`]).
+    scene: u64,
+
+    /// How often the collection was marked dirty (see [`NyanObj::generation`]).
+    generation: u64,
+
+    /// The boxes objects covered before they moved, changed size or were hidden or removed,
+    /// which need to be cleared (see [`NyanObj::damage`]).
+    damage: Vec<Rect>,
+
+    /// The position of the (first) object with each id, so lookups hash the id once instead
+    /// of comparing it to every object's id.
+    index: HashMap<Box<str>, usize>,
+}
+
+impl<'a> Default for NyanObj<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<'a> NyanObj<'a> {
@@ -98,7 +235,14 @@ impl<'a> NyanObj<'a> {
     ///
     /// A new instance of [`NyanObj`] with no stored objects.
     pub fn new() -> Self {
-        Self { inner: Vec::new() }
+        Self {
+            inner: Vec::new(),
+            dirty: true,
+            scene: NEXT_SCENE.fetch_add(1, Ordering::Relaxed),
+            generation: 0,
+            damage: Vec::new(),
+            index: HashMap::new(),
+        }
     }
 
     /// Adds a new object to the collection with a specified coordinate.
@@ -114,8 +258,12 @@ impl<'a> NyanObj<'a> {
         object: Objects<'a>,
         coordinate: (u16, u16),
     ) {
-        self.inner
-            .push(NyanObjs::new(object, id.into(), coordinate));
+        let id = id.into();
+        self.index
+            .entry(id.as_ref().into())
+            .or_insert(self.inner.len());
+        self.inner.push(NyanObjs::new(object, id, coordinate));
+        self.mark_dirty();
     }
 
     /// Adds a new object to the collection with a default coordinate of `(0, 0)`.
@@ -131,7 +279,12 @@ impl<'a> NyanObj<'a> {
         id: P,
         object: Objects<'a>,
     ) {
-        self.inner.push(NyanObjs::new(object, id.into(), (0, 0)));
+        let id = id.into();
+        self.index
+            .entry(id.as_ref().into())
+            .or_insert(self.inner.len());
+        self.inner.push(NyanObjs::new(object, id, (0, 0)));
+        self.mark_dirty();
     }
 
     /// Removes an object from the collection by its unique identifier.
@@ -144,18 +297,17 @@ impl<'a> NyanObj<'a> {
     ///
     /// - `Ok(())` if the object was found and removed.
     /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
-    pub fn remove_object<P: Into<Cow<'a, str>> + Clone>(
-        &'static mut self,
-        id: P,
-    ) -> anyhow::Result<()> {
+    pub fn remove_object<P: Into<Cow<'a, str>> + Clone>(&mut self, id: P) -> NyanResult<()> {
         let cid = id.clone().into();
 
         // Find the index of the object with the specified ID.
-        if let Some(o) = self.inner.iter().position(|f| f.id == cid) {
+        if let Some(o) = self.get(&cid) {
+            self.touch(o);
             self.inner.remove(o);
+            self.reindex();
             Ok(())
         } else {
-            Err(NyanError::ObjectNotFound(id.into()).into())
+            Err(NyanError::ObjectNotFound(cid.into_owned().into()))
         }
     }
 
@@ -172,12 +324,584 @@ impl<'a> NyanObj<'a> {
     ///
     /// - `Ok(())` if the object was successfully removed.
     /// - An error if the object with the given ID does not exist.
-    pub fn update_object<P: Into<Cow<'a, str>>>(&'static mut self, id: P) -> anyhow::Result<()> {
+    pub fn update_object<P: Into<Cow<'a, str>>>(&mut self, id: P) -> NyanResult<()> {
         let cid = id.into();
         self.remove_object(cid)?;
         Ok(())
     }
 
+    /// Returns the stored drawing coordinate of an object.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The identifier of the object.
+    ///
+    /// # Returns
+    ///
+    /// - `Some((x, y))` if the object is found.
+    /// - `None` if no object with the given ID exists.
+    pub fn coordinate<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<(u16, u16)> {
+        self.get(&id.into())
+            .map(|index| self.inner[index].coordinate)
+    }
+
+    /// Returns a reference to an object, e.g. to read its scroll offset.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&Objects)` if the object is found.
+    /// - `None` if no object with the given ID exists.
+    pub fn object<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<&Objects<'a>> {
+        self.get(&id.into()).map(|index| &self.inner[index].object)
+    }
+
+    /// Returns a mutable reference to an object, e.g. to scroll it with
+    /// [`Objects::handle_scroll_input`].
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&mut Objects)` if the object is found.
+    /// - `None` if no object with the given ID exists.
+    pub fn object_mut<P: Into<Cow<'a, str>>>(&mut self, id: P) -> Option<&mut Objects<'a>> {
+        let index = self.get(&id.into())?;
+        self.touch(index);
+        let obj = &mut self.inner[index];
+        obj.changed();
+        Some(&mut obj.object)
+    }
+
+    /// Moves an object to a new drawing coordinate.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The identifier of the object to move.
+    /// - `coordinate`: A tuple `(x, y)` specifying the new drawing position.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the object was moved.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn move_object<P: Into<Cow<'a, str>>>(
+        &mut self,
+        id: P,
+        coordinate: (u16, u16),
+    ) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            if self.inner[index].coordinate != coordinate {
+                self.touch(index);
+                self.inner[index].coordinate = coordinate;
+            }
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Moves an object to the top-left corner of `area` and resizes it to fill `area`.
+    ///
+    /// The object's margin and padding are kept inside `area`; only resizable objects (see
+    /// [`Objects::resize`]) change their size. Layouts use this to place their objects.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the object was moved.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn fit_object<P: Into<Cow<'a, str>>>(&mut self, id: P, area: Rect) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            self.touch(index);
+            let obj = &mut self.inner[index];
+            obj.changed();
+            let content = area.inner(obj.margin).inner(obj.padding);
+            obj.coordinate = (area.x, area.y);
+            obj.object.resize(content.width, content.height);
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Advances every animated object (see [`Objects::advance`]) and moves every object with a
+    /// body (see [`NyanObj::set_body`]) by `dt`, usually
+    /// [`App::delta_time`](crate::app::App::delta_time) once per frame.
+    ///
+    /// # Returns
+    ///
+    /// `true` if an object changed, in which case the collection is marked dirty.
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        let mut changed = false;
+        for obj in &mut self.inner {
+            let before = obj.rect();
+            let mut moved = false;
+            if obj.object.advance(dt) {
+                obj.changed();
+                moved = true;
+            }
+            let size = obj.object.size();
+            let Some(body) = &mut obj.body else {
+                Self::damage_entry(&mut self.damage, obj, before, moved);
+                changed |= moved;
+                continue;
+            };
+            // Start over from the coordinate if the object was moved by other means.
+            let cell = |(x, y): (f32, f32)| {
+                let clamp = |v: f32| v.round().clamp(0.0, u16::MAX as f32) as u16;
+                (clamp(x), clamp(y))
+            };
+            if cell(obj.position) != obj.coordinate {
+                obj.position = (obj.coordinate.0 as f32, obj.coordinate.1 as f32);
+            }
+            obj.position = body.step(obj.position, size, dt);
+            let coordinate = cell(obj.position);
+            moved |= coordinate != obj.coordinate;
+            obj.coordinate = coordinate;
+            Self::damage_entry(&mut self.damage, obj, before, moved);
+            changed |= moved;
+        }
+        if changed {
+            self.mark_dirty();
+        }
+        changed
+    }
+
+    /// Returns whether the objects need to be drawn again.
+    ///
+    /// A new collection is dirty. Layouts mark it dirty when they move objects after a
+    /// resize (see [`LayoutManager`](crate::layout::LayoutManager)).
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Marks the objects as needing to be drawn again. Adding, removing, moving, styling,
+    /// scrolling and changing objects through the collection marks it, too.
+    ///
+    /// This also advances the [`NyanObj::generation`] of the collection.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+        self.generation += 1;
+    }
+
+    /// Returns how often the collection was marked dirty. Unlike [`NyanObj::is_dirty`], it is
+    /// not reset by [`NyanObj::clear_dirty`], so several observers can each compare it with the
+    /// generation they saw last, e.g. [`App::watch`](crate::app::App::watch).
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the key of the collection, which differs from the keys of every other
+    /// collection and is shared by its clones.
+    pub(crate) fn scene(&self) -> u64 {
+        self.scene
+    }
+
+    /// Marks the objects as drawn, e.g. after drawing a frame. This also forgets the damage
+    /// (see [`NyanObj::damage`]).
+    pub fn clear_dirty(&mut self) {
+        self.dirty = false;
+        self.damage.clear();
+        for obj in &mut self.inner {
+            obj.damaged = false;
+        }
+    }
+
+    /// Counts the objects, the cached output and the approximate memory of the collection,
+    /// e.g. to notice temporary objects that are never removed.
+    ///
+    /// See [`SceneStats`].
+    pub fn stats(&self) -> SceneStats {
+        let mut scene = SceneStats {
+            objects: self.inner.len(),
+            memory: std::mem::size_of::<Self>()
+                + self.inner.capacity() * std::mem::size_of::<NyanObjs>()
+                + self.damage.capacity() * std::mem::size_of::<Rect>()
+                + self.index.capacity() * std::mem::size_of::<(Box<str>, usize)>()
+                + self.index.keys().map(|id| id.len()).sum::<usize>(),
+            ..SceneStats::default()
+        };
+        for obj in &self.inner {
+            scene.visible += obj.visible as usize;
+            scene.memory += stats::owned(&obj.id) + obj.object.heap_size();
+            let render = obj.render.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(render) = &*render {
+                scene.cached += 1;
+                scene.cached_bytes += render.bytes.len();
+                scene.memory += render.bytes.capacity();
+            }
+        }
+        scene
+    }
+
+    /// Returns the regions of the screen that changed since the collection was last marked as
+    /// drawn (see [`NyanObj::clear_dirty`]): the boxes objects left when they moved, shrank or
+    /// were hidden or removed, and the boxes of objects that were added or changed.
+    ///
+    /// Only changes made through the collection are tracked; [`NyanObj::mark_dirty`] alone
+    /// damages nothing. A new collection is damaged wherever it has objects. Boxes that are
+    /// already damaged are not repeated, and many boxes are merged into the box around them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::nyan_obj::NyanObj;
+    /// use nyan::objects::Objects;
+    /// use nyan::rect::Rect;
+    ///
+    /// let mut obj = NyanObj::new();
+    /// obj.add_object("clock", Objects::new_text("12:00"), (0, 0));
+    /// obj.add_object("cat", Objects::new_text("=^.^="), (0, 2));
+    /// obj.clear_dirty();
+    ///
+    /// obj.move_object("cat", (3, 2)).unwrap();
+    /// assert_eq!(obj.damage(), vec![Rect::new(0, 2, 5, 1), Rect::new(3, 2, 5, 1)]);
+    /// ```
+    pub fn damage(&self) -> Vec<Rect> {
+        let current = self
+            .inner
+            .iter()
+            .filter(|obj| obj.damaged && obj.visible)
+            .map(|obj| obj.rect());
+        self.damage
+            .iter()
+            .copied()
+            .chain(current)
+            .filter(|rect| !rect.is_empty())
+            .collect()
+    }
+
+    /// Records that the object at `index` is about to change: the box it covers now is
+    /// cleared and the object is repainted on the next [`NyanObj::draw_damaged_in`].
+    fn touch(&mut self, index: usize) {
+        let obj = &mut self.inner[index];
+        if obj.visible {
+            Self::add_damage(&mut self.damage, obj.rect());
+        }
+        obj.damaged = true;
+        self.mark_dirty();
+    }
+
+    /// Adds `rect` to `damage`, unless it is already covered, merging the damage into one box
+    /// once it holds [`MAX_DAMAGE`] regions.
+    fn add_damage(damage: &mut Vec<Rect>, rect: Rect) {
+        let covers = |outer: &Rect| {
+            outer.x <= rect.x
+                && outer.y <= rect.y
+                && outer.right() >= rect.right()
+                && outer.bottom() >= rect.bottom()
+        };
+        if rect.is_empty() || damage.iter().any(covers) {
+            return;
+        }
+        damage.push(rect);
+        if damage.len() > MAX_DAMAGE {
+            let (x, y) = damage
+                .iter()
+                .fold((u16::MAX, u16::MAX), |(x, y), r| (x.min(r.x), y.min(r.y)));
+            let (right, bottom) = damage.iter().fold((0, 0), |(right, bottom), r| {
+                (right.max(r.right()), bottom.max(r.bottom()))
+            });
+            *damage = vec![Rect::new(x, y, right - x, bottom - y)];
+        }
+    }
+
+    /// Records the box `before` of an object that moved or changed while advancing.
+    fn damage_entry(damage: &mut Vec<Rect>, obj: &mut NyanObjs<'a>, before: Rect, moved: bool) {
+        if moved {
+            if obj.visible {
+                Self::add_damage(damage, before);
+            }
+            obj.damaged = true;
+        }
+    }
+
+    /// Moves an object so it is aligned inside `area`, based on its size (see [`Objects::size`])
+    /// including padding and margin.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The identifier of the object.
+    /// - `area`: The region to align the object in, e.g. [`Rect::terminal`].
+    /// - `horizontal`: Where the object goes horizontally (left, center or right).
+    /// - `vertical`: Where the object goes vertically (top, center or bottom).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the object was moved.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    ///
+    /// # Example
+    /// ```ignore
+    /// obj.add_object_with_default("title", Objects::new_text("nyan"));
+    /// obj.align_object("title", Rect::terminal()?, Align::Center, Align::Start)?;
+    /// ```
+    pub fn align_object<P: Into<Cow<'a, str>>>(
+        &mut self,
+        id: P,
+        area: Rect,
+        horizontal: Align,
+        vertical: Align,
+    ) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            let size = self.inner[index].outer_size();
+            let coordinate = layout::align(area, size, horizontal, vertical);
+            if self.inner[index].coordinate != coordinate {
+                self.touch(index);
+                self.inner[index].coordinate = coordinate;
+            }
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Moves an object to the center of `area`, horizontally and vertically.
+    ///
+    /// See [`NyanObj::align_object`].
+    pub fn center_object<P: Into<Cow<'a, str>>>(&mut self, id: P, area: Rect) -> NyanResult<()> {
+        self.align_object(id, area, Align::Center, Align::Center)
+    }
+
+    /// Attaches a style to an object.
+    ///
+    /// The style is applied right before the object is drawn and reset right after it.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The identifier of the object.
+    /// - `style`: The [`NyanStyle`] to draw the object with.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the style was attached.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn set_style<P: Into<Cow<'a, str>>>(&mut self, id: P, style: NyanStyle) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            if self.inner[index].style != style {
+                self.touch(index);
+                self.inner[index].style = style;
+                self.inner[index].changed();
+            }
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Returns the style attached to an object.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(NyanStyle)` if the object is found.
+    /// - `None` if no object with the given ID exists.
+    pub fn style<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<NyanStyle> {
+        self.get(&id.into()).map(|index| self.inner[index].style)
+    }
+
+    /// Sets the padding of an object: empty cells between its content and the edges of its box.
+    ///
+    /// The padding belongs to the object's box, so it is part of [`NyanObj::bounds`], is hit by
+    /// [`NyanObj::hit_test`] and is painted by the fill (see [`NyanObj::set_fill`]).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the padding was set.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn set_padding<P: Into<Cow<'a, str>>>(&mut self, id: P, padding: Edges) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            self.touch(index);
+            self.inner[index].padding = padding;
+            self.inner[index].changed();
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Returns the padding of an object, or `None` if no object with the given ID exists.
+    pub fn padding<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<Edges> {
+        self.get(&id.into()).map(|index| self.inner[index].padding)
+    }
+
+    /// Sets the margin of an object: empty cells between its coordinate (e.g. the position a
+    /// layout assigned to it) and its box.
+    ///
+    /// The margin is not part of [`NyanObj::bounds`], but layouts and
+    /// [`NyanObj::align_object`] make room for it.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the margin was set.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn set_margin<P: Into<Cow<'a, str>>>(&mut self, id: P, margin: Edges) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            self.touch(index);
+            self.inner[index].margin = margin;
+            self.inner[index].changed();
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Returns the margin of an object, or `None` if no object with the given ID exists.
+    pub fn margin<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<Edges> {
+        self.get(&id.into()).map(|index| self.inner[index].margin)
+    }
+
+    /// Returns the size of an object's box including its margin, i.e. the space it needs in a
+    /// layout, or `None` if no object with the given ID exists.
+    pub fn outer_size<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<(u16, u16)> {
+        self.get(&id.into())
+            .map(|index| self.inner[index].outer_size())
+    }
+
+    /// Sets the background color painted across an object's whole bounding box.
+    ///
+    /// Without a fill only the cells that the object prints get a background color, so
+    /// lines of different lengths leave gaps. With a fill the rectangle returned by
+    /// [`NyanObj::bounds`] is painted first, which makes highlighted rows and selected menu
+    /// items render as a solid bar. The fill is also the object's background unless its style
+    /// sets one. Pass `None` to remove the fill.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The identifier of the object.
+    /// - `fill`: The background color, or `None`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the fill was set.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn set_fill<P: Into<Cow<'a, str>>>(
+        &mut self,
+        id: P,
+        fill: Option<NyanColor>,
+    ) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            self.touch(index);
+            self.inner[index].fill = fill;
+            self.inner[index].changed();
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Returns the background fill of an object.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(Some(NyanColor))` if the object is found and has a fill.
+    /// - `Some(None)` if the object is found but has no fill.
+    /// - `None` if no object with the given ID exists.
+    pub fn fill<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<Option<NyanColor>> {
+        self.get(&id.into()).map(|index| self.inner[index].fill)
+    }
+
+    /// Shows or hides an object. Hidden objects are skipped when drawing and by
+    /// [`NyanObj::hit_test`], but keep their place in the collection.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the visibility was set.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn set_visible<P: Into<Cow<'a, str>>>(&mut self, id: P, visible: bool) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            if self.inner[index].visible != visible {
+                self.touch(index);
+                self.inner[index].visible = visible;
+            }
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Returns whether an object is shown, or `None` if no object with the given ID exists.
+    pub fn is_visible<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<bool> {
+        self.get(&id.into()).map(|index| self.inner[index].visible)
+    }
+
+    /// Keeps the output of an object once it is drawn and reuses it in the following frames,
+    /// instead of formatting the object again, e.g. for a large table or paragraph that rarely
+    /// changes. The output is drawn again after the object changes through the collection:
+    /// [`NyanObj::object_mut`], a new style, padding, margin or fill, scrolling, resizing or an
+    /// animation step. Moving or hiding the object keeps it.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the object was found.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn set_cached<P: Into<Cow<'a, str>>>(&mut self, id: P, cached: bool) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            self.inner[index].cached = cached;
+            self.inner[index].changed();
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Returns whether the output of an object is cached (see [`NyanObj::set_cached`]).
+    ///
+    /// # Returns
+    ///
+    /// - `Some(bool)` if the object is found.
+    /// - `None` if no object with the given ID exists.
+    pub fn is_cached<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<bool> {
+        self.get(&id.into()).map(|index| self.inner[index].cached)
+    }
+
+    /// Attaches a body to an object, so [`NyanObj::advance`] moves it with the body's velocity
+    /// and acceleration.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the body was attached.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn set_body<P: Into<Cow<'a, str>>>(&mut self, id: P, body: Body) -> NyanResult<()> {
+        let id = id.into();
+        if let Some(index) = self.get(&id) {
+            self.inner[index].body = Some(body);
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Returns the body of an object, or `None` if it has none or no object with the given ID
+    /// exists.
+    pub fn body<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<&Body> {
+        self.inner[self.get(&id.into())?].body.as_ref()
+    }
+
+    /// Returns the body of an object mutably, e.g. to push it with a new velocity.
+    pub fn body_mut<P: Into<Cow<'a, str>>>(&mut self, id: P) -> Option<&mut Body> {
+        let index = self.get(&id.into())?;
+        self.inner[index].body.as_mut()
+    }
+
+    /// Detaches the body of an object, which stops where it is.
+    ///
+    /// # Returns
+    ///
+    /// The removed body, or `None` if the object had none.
+    pub fn remove_body<P: Into<Cow<'a, str>>>(&mut self, id: P) -> Option<Body> {
+        let index = self.get(&id.into())?;
+        self.inner[index].body.take()
+    }
+
+    /// Returns the identifiers of all objects, in the order they were added.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.inner.iter().map(|obj| obj.id.as_ref())
+    }
+
     /// Retrieves the index of an object in the collection by its unique identifier.
     ///
     /// This is an internal helper method.
@@ -190,9 +914,152 @@ impl<'a> NyanObj<'a> {
     ///
     /// - `Some(index)` if the object is found.
     /// - `None` if no object with the given ID exists.
-    pub(self) fn get<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<usize> {
+    pub(self) fn get(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    /// Rebuilds the positions of the ids after objects were removed, which also forgets the
+    /// ids no object has any more.
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (i, obj) in self.inner.iter().enumerate() {
+            self.index.entry(obj.id.as_ref().into()).or_insert(i);
+        }
+    }
+
+    /// Returns the rectangle an object occupies on the screen.
+    ///
+    /// The rectangle starts at the object's stored coordinate offset by its margin, and has the
+    /// object's size (see [`Objects::size`]) plus its padding.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The identifier of the object.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(Rect)` if the object is found.
+    /// - `None` if no object with the given ID exists.
+    pub fn bounds<P: Into<Cow<'a, str>>>(&self, id: P) -> Option<Rect> {
+        Some(self.inner[self.get(&id.into())?].rect())
+    }
+
+    /// Returns whether the bounds of two shown objects overlap.
+    ///
+    /// # Returns
+    ///
+    /// `false` if either object does not exist, is hidden or occupies no cells.
+    pub fn collides<P: Into<Cow<'a, str>>, Q: Into<Cow<'a, str>>>(&self, a: P, b: Q) -> bool {
+        let (Some(a), Some(b)) = (self.get(&a.into()), self.get(&b.into())) else {
+            return false;
+        };
+        let (a, b) = (&self.inner[a], &self.inner[b]);
+        a.visible && b.visible && a.rect().intersects(&b.rect())
+    }
+
+    /// Finds the topmost object whose bounds contain the cell `(x, y)`.
+    ///
+    /// Objects added later are considered to be on top of objects added earlier.
+    /// Objects that occupy no cells (such as [`Objects::Air`]) and hidden objects are never hit.
+    ///
+    /// # Parameters
+    ///
+    /// - `x`: The column to test, e.g. [`NyanMouse::x`](crate::event::NyanMouse).
+    /// - `y`: The row to test, e.g. [`NyanMouse::y`](crate::event::NyanMouse).
+    ///
+    /// # Returns
+    ///
+    /// - `Some(id)` of the topmost object under the point.
+    /// - `None` if no object covers the point.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if let NyanEvent::Mouse(mouse) = NyanEvent::get_event()? {
+    ///     if let Some(id) = obj.hit_test(mouse.x, mouse.y) {
+    ///         selected = id.to_string();
+    ///     }
+    /// }
+    /// ```
+    pub fn hit_test(&self, x: u16, y: u16) -> Option<&str> {
+        self.inner
+            .iter()
+            .rev()
+            .find(|obj| obj.visible && obj.rect().contains(x, y))
+            .map(|obj| obj.id.as_ref())
+    }
+
+    /// Scrolls a `ScrollableText`, `List` or `ScrollView` object by `delta` lines.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The identifier of the object to scroll.
+    /// - `delta`: The number of lines to scroll; negative values scroll up.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the object was found (objects that cannot scroll are left unchanged).
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    pub fn scroll_object<P: Into<Cow<'a, str>>>(&mut self, id: P, delta: isize) -> NyanResult<()> {
         let id = id.into();
-        self.inner.iter().position(|f| f.id == id)
+        if let Some(index) = self.get(&id) {
+            self.touch(index);
+            self.inner[index].object.scroll_by(delta);
+            self.inner[index].changed();
+            Ok(())
+        } else {
+            Err(NyanError::ObjectNotFound(id.into_owned().into()))
+        }
+    }
+
+    /// Routes a scroll wheel action to the scrollable object under the mouse pointer.
+    ///
+    /// If the topmost object under the pointer (see [`NyanObj::hit_test`]) is a
+    /// `ScrollableText`, `List` or `ScrollView`, its scroll offset is moved by one line per wheel
+    /// step (a `ScrollView` also scrolls sideways). Other mouse actions are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `mouse`: The mouse action to route.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(id)` of the object that was scrolled.
+    /// - `None` if the action was not a scroll over a scrollable object.
+    pub fn route_scroll(&mut self, mouse: &NyanMouse) -> Option<&str> {
+        if !matches!(
+            mouse.kind,
+            NyanMouseKind::ScrollUp
+                | NyanMouseKind::ScrollDown
+                | NyanMouseKind::ScrollLeft
+                | NyanMouseKind::ScrollRight
+        ) {
+            return None;
+        }
+
+        let index = self
+            .inner
+            .iter()
+            .rposition(|obj| obj.rect().contains(mouse.x, mouse.y))?;
+
+        if !self.inner[index].object.scroll_mouse(mouse) {
+            return None;
+        }
+        // Scrolling keeps the size, so the box can be recorded afterwards.
+        self.touch(index);
+        let obj = &mut self.inner[index];
+        obj.changed();
+        Some(obj.id.as_ref())
+    }
+
+    /// Routes an event to the scrollable object under the mouse pointer.
+    ///
+    /// See [`NyanObj::route_scroll`]. Events other than mouse actions are ignored.
+    pub fn route_event<U>(&mut self, event: &NyanEvent<U>) -> Option<&str> {
+        if let NyanEvent::Mouse(mouse) = event {
+            self.route_scroll(mouse)
+        } else {
+            None
+        }
     }
 
     /// Draws the object associated with the given ID at its stored coordinate.
@@ -202,6 +1069,7 @@ impl<'a> NyanObj<'a> {
     /// 2. Moves the cursor to the object's stored coordinate.
     /// 3. Draws the object based on its type:
     ///    - **Text:** Prints the text to the console.
+    ///    - **ScrollableText / List:** Prints the visible lines, one below the other.
     ///    - **Air:** Does nothing.
     ///    - **Block:** Not yet implemented (invokes `todo!()`).
     ///
@@ -213,35 +1081,32 @@ impl<'a> NyanObj<'a> {
     ///
     /// - `Ok(())` if the object was successfully drawn.
     /// - An error if the object is not found or if moving the cursor fails.
-    pub fn draw_object<P: Into<Cow<'static, str>>>(&self, id: P) -> anyhow::Result<()> {
-        let id = id.into();
-        if let Some(object_index) = self.get(id.clone()) {
-            let obj = &self.inner[object_index];
-
-            // Attempt to move the cursor to the object's coordinate.
-            if let Err(e) =
-                cursor::Cursor::move_cursor(Cursor::Move(obj.coordinate.0, obj.coordinate.1))
-            {
-                return Err(errors::NyanError::Cursor(e.to_string().into()).into());
-            }
+    pub fn draw_object<P: AsRef<str>>(&self, id: P) -> NyanResult<()> {
+        self.draw_object_in(&mut io::stdout(), id)
+    }
 
-            // Draw the object based on its type.
-            match &obj.object {
-                // For a Text object, print its content.
-                Objects::Text(t) => {
-                    println!("{}", t.as_ref());
-                }
-                // For an Air object, no drawing is performed.
-                Objects::Air => {}
-                // For a Block object, drawing functionality is not yet implemented.
-                Objects::Block => {
-                    todo!()
-                }
-            }
-            Ok(())
-        } else {
-            // Object not found.
-            Err(NyanError::ObjectNotFound(id).into())
+    /// Draws the object associated with the given ID at its stored coordinate, writing to `out`.
+    ///
+    /// This behaves like [`NyanObj::draw_object`], but the cursor movement and the object are
+    /// written to any writer instead of `std::io::stdout()`. Pass the [`App`](crate::app::App)
+    /// (see [`App::draw_with`](crate::app::App::draw_with)) to draw through its output, or a
+    /// `Vec<u8>` to render headlessly.
+    ///
+    /// # Parameters
+    ///
+    /// - `out`: The writer to draw to.
+    /// - `id`: The identifier of the object to draw.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the object was successfully drawn.
+    /// - An error if the object is not found or if moving the cursor fails.
+    pub fn draw_object_in<W: Write, P: AsRef<str>>(&self, out: &mut W, id: P) -> NyanResult<()> {
+        let id = id.as_ref();
+        match self.get(id) {
+            Some(index) => self.draw_at(out, index, NyanStyle::new()),
+            // The id is only copied for the error.
+            None => Err(NyanError::ObjectNotFound(id.to_string().into())),
         }
     }
 
@@ -251,6 +1116,7 @@ impl<'a> NyanObj<'a> {
     /// using the object's stored coordinate. After moving the cursor, it draws the object according
     /// to its type:
     /// - **Text:** Prints the text.
+    /// - **ScrollableText / List:** Prints the visible lines, one below the other.
     /// - **Air:** Does nothing.
     /// - **Block:** Not yet implemented.
     ///
@@ -269,31 +1135,453 @@ impl<'a> NyanObj<'a> {
     /// let cursor_pos = Cursor::new(10, 5);
     /// nyan.draw_with_move("text_object", cursor_pos)?;
     /// ```
-    pub fn draw_with_move<P: Into<Cow<'static, str>> + Clone>(
+    pub fn draw_with_move<P: AsRef<str>>(&self, id: P, moveto: Cursor) -> NyanResult<()> {
+        self.draw_with_move_in(&mut io::stdout(), id, moveto)
+    }
+
+    /// Draws an object at a specified cursor position, writing to `out`.
+    ///
+    /// This behaves like [`NyanObj::draw_with_move`], but writes to any writer instead of
+    /// `std::io::stdout()`.
+    ///
+    /// # Parameters
+    ///
+    /// - `out`: The writer to draw to.
+    /// - `id`: The unique identifier of the object to draw.
+    /// - `moveto`: A [`Cursor`] specifying the new cursor position.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the object was successfully drawn.
+    /// - An error if the object is not found or if moving the cursor fails.
+    pub fn draw_with_move_in<W: Write, P: AsRef<str>>(
         &self,
+        out: &mut W,
         id: P,
         moveto: Cursor,
-    ) -> anyhow::Result<()> {
-        let cid = id.clone().into();
+    ) -> NyanResult<()> {
+        let id = id.as_ref();
+        let Some(index) = self.get(id) else {
+            return Err(errors::NyanError::ObjectNotFound(id.to_string().into()));
+        };
+
+        // Move the cursor to the specified position.
+        if let Err(e) = moveto.queue(out) {
+            return Err(errors::NyanError::Cursor(e.to_string().into()));
+        }
+
+        // Draw the object based on its type.
+        self.draw_entry(out, index, NyanStyle::new())?;
+        Ok(())
+    }
+
+    /// Draws every object at its stored coordinate, in the order they were added, writing to
+    /// `out`.
+    ///
+    /// # Parameters
+    ///
+    /// - `out`: The writer to draw to.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if all objects were drawn.
+    /// - An error if moving the cursor or writing fails.
+    pub fn draw_all_in<W: Write>(&self, out: &mut W) -> NyanResult<()> {
+        for index in 0..self.inner.len() {
+            self.draw_at(out, index, NyanStyle::new())?;
+        }
+        Ok(())
+    }
+
+    /// Draws every object at its stored coordinate, in the order they were added.
+    pub fn draw_all(&self) -> NyanResult<()> {
+        self.draw_all_in(&mut io::stdout())
+    }
 
-        if let Some(object_index) = self.get(cid) {
-            // Move the cursor to the specified position.
-            Cursor::move_cursor(moveto)?;
-
-            // Draw the object based on its type.
-            match &self.inner[object_index].object {
-                Objects::Text(t) => {
-                    println!("{}", t.as_ref());
-                }
-                Objects::Air => {}
-                Objects::Block => {
-                    todo!()
-                }
+    /// Repaints only the regions that changed since the collection was last drawn (see
+    /// [`NyanObj::damage`]), writing to `out`.
+    ///
+    /// The damaged regions are cleared to the base style, then the objects overlapping them
+    /// are drawn again in the order they were added, together with the objects above those.
+    /// The rest of the screen is left alone, so a small update, like a ticking clock, does
+    /// not flicker the whole screen as clearing it every frame (see
+    /// [`App::clear`](crate::app::App::clear)) would. Call [`NyanObj::clear_dirty`] afterwards, and draw the whole scene once after
+    /// the terminal was cleared or resized.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the damaged regions were repainted.
+    /// - An error if moving the cursor or writing fails.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if obj.is_dirty() {
+    ///     obj.draw_damaged_in(app)?;
+    ///     obj.clear_dirty();
+    /// }
+    /// ```
+    pub fn draw_damaged_in<W: Write>(&self, out: &mut W) -> NyanResult<()> {
+        let damage = self.damage();
+        if damage.is_empty() {
+            return Ok(());
+        }
+
+        NyanStyle::reset(out)?;
+        for rect in &damage {
+            for row in rect.y..rect.bottom() {
+                Cursor::Move(rect.x, row).queue(out)?;
+                write!(out, "{:1$}", "", rect.width as usize)?;
             }
-        } else {
-            return Err(errors::NyanError::ObjectNotFound(id.into()).into());
         }
 
+        // An object drawn again covers the objects beneath it, so the ones above it are drawn
+        // again, too.
+        let mut drawn: Vec<Rect> = Vec::new();
+        for (index, obj) in self.inner.iter().enumerate() {
+            if !obj.visible {
+                continue;
+            }
+            let rect = obj.rect();
+            if damage
+                .iter()
+                .chain(&drawn)
+                .any(|other| rect.intersects(other))
+            {
+                self.draw_at(out, index, NyanStyle::new())?;
+                drawn.push(rect);
+            }
+        }
+        out.flush()?;
         Ok(())
     }
+
+    /// Draws every object like [`NyanObj::draw_all_in`], but formats the objects in parallel
+    /// on the rayon thread pool first. Only the finished output is written to `out`, in the
+    /// order the objects were added, so overlapping objects look the same either way.
+    ///
+    /// This pays off for large scenes, with many objects or objects that are expensive to
+    /// format; for a few small objects the threads cost more than they save. Requires the
+    /// `rayon` feature.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if all objects were drawn.
+    /// - An error if moving the cursor or writing fails.
+    #[cfg(feature = "rayon")]
+    pub fn draw_all_parallel_in<W: Write>(&self, out: &mut W) -> NyanResult<()> {
+        use rayon::prelude::*;
+
+        let rendered: Vec<io::Result<Vec<u8>>> = (0..self.inner.len())
+            .into_par_iter()
+            .map(|index| {
+                let mut bytes = Vec::new();
+                let (x, y) = self.inner[index].coordinate;
+                Cursor::Move(x, y).queue(&mut bytes)?;
+                self.draw_entry(&mut bytes, index, NyanStyle::new())?;
+                Ok(bytes)
+            })
+            .collect();
+        for bytes in rendered {
+            out.write_all(&bytes?)?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Draws the scene beneath a modal object dimmed, then the modal object on top, writing
+    /// to `out`.
+    ///
+    /// Every object except `id` is drawn with the [`Attribute::Dim`](crate::style::Attribute)
+    /// attribute layered on its own style, so a dialog stands out without re-styling the
+    /// other objects. The modal object is drawn last with its normal style.
+    ///
+    /// # Parameters
+    ///
+    /// - `out`: The writer to draw to.
+    /// - `id`: The identifier of the modal object.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the scene was drawn.
+    /// - An error of type [`NyanError::ObjectNotFound`] if no object with the given ID exists.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if confirm_open {
+    ///     obj.draw_modal_in(app, "confirm")?;
+    /// } else {
+    ///     obj.draw_all_in(app)?;
+    /// }
+    /// ```
+    pub fn draw_modal_in<W: Write, P: Into<Cow<'a, str>>>(
+        &self,
+        out: &mut W,
+        id: P,
+    ) -> NyanResult<()> {
+        let id = id.into();
+        let Some(modal) = self.get(&id) else {
+            return Err(NyanError::ObjectNotFound(id.into_owned().into()));
+        };
+
+        let dim = NyanStyle::new().dim();
+        for index in (0..self.inner.len()).filter(|i| *i != modal) {
+            self.draw_at(out, index, dim)?;
+        }
+        self.draw_at(out, modal, NyanStyle::new())
+    }
+
+    /// Draws the scene beneath a modal object dimmed, then the modal object on top.
+    ///
+    /// See [`NyanObj::draw_modal_in`].
+    pub fn draw_modal<P: Into<Cow<'a, str>>>(&self, id: P) -> NyanResult<()> {
+        self.draw_modal_in(&mut io::stdout(), id)
+    }
+
+    /// Draws every object at its stored coordinate with `overlay` layered on its style.
+    pub(crate) fn draw_all_overlaid_in<W: Write>(
+        &self,
+        out: &mut W,
+        overlay: NyanStyle,
+    ) -> NyanResult<()> {
+        for index in 0..self.inner.len() {
+            self.draw_at(out, index, overlay)?;
+        }
+        Ok(())
+    }
+
+    /// Draws the object `id` at its stored coordinate with `overlay` layered on its style.
+    pub(crate) fn draw_overlaid_in<W: Write>(
+        &self,
+        out: &mut W,
+        id: &str,
+        overlay: NyanStyle,
+    ) -> NyanResult<()> {
+        match self.get(id) {
+            Some(index) => self.draw_at(out, index, overlay),
+            None => Err(NyanError::ObjectNotFound(id.to_string().into())),
+        }
+    }
+
+    /// Moves the cursor to the stored coordinate of the object at `index` and draws it.
+    fn draw_at<W: Write>(&self, out: &mut W, index: usize, overlay: NyanStyle) -> NyanResult<()> {
+        let (x, y) = self.inner[index].coordinate;
+        // Queued without a flush of its own; the object flushes once it is drawn.
+        if let Err(e) = Cursor::Move(x, y).queue(out) {
+            return Err(errors::NyanError::Cursor(e.to_string().into()));
+        }
+        self.draw_entry(out, index, overlay)?;
+        Ok(())
+    }
+
+    /// Moves the cursor right by the left edge and down by the top edge.
+    fn offset_cursor<W: Write>(out: &mut W, edges: Edges) -> io::Result<()> {
+        // A zero count would still move one cell.
+        if edges.left > 0 {
+            Cursor::MoveRight(edges.left).queue(out)?;
+        }
+        if edges.top > 0 {
+            Cursor::MoveDown(edges.top).queue(out)?;
+        }
+        Ok(())
+    }
+
+    /// Paints a `size` rectangle with `fill` at the cursor position, leaving the cursor there.
+    fn paint_fill<W: Write>(out: &mut W, fill: NyanColor, size: (u16, u16)) -> io::Result<()> {
+        let (width, height) = size;
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        Cursor::SavePosition.queue(out)?;
+        NyanStyle::base()
+            .patch(NyanStyle::new().bg(fill))
+            .apply(out)?;
+        for row in 0..height {
+            write!(out, "{:1$}", "", width as usize)?;
+            if row + 1 < height {
+                Cursor::MoveLeft(width).queue(out)?;
+                Cursor::MoveDown(1).queue(out)?;
+            }
+        }
+        NyanStyle::reset(out)?;
+        Cursor::RestorePosition.queue(out)
+    }
+
+    /// Draws the object at `index` at the current cursor position, wrapped in its style with
+    /// `overlay` layered on top.
+    fn draw_entry<W: Write>(
+        &self,
+        out: &mut W,
+        index: usize,
+        overlay: NyanStyle,
+    ) -> io::Result<()> {
+        let entry = &self.inner[index];
+        if !entry.visible {
+            return Ok(());
+        }
+        if !entry.cached {
+            return Self::render_entry(out, entry, overlay);
+        }
+
+        let key = (overlay, NyanStyle::base(), color::color_support());
+        let mut render = entry.render.lock().unwrap_or_else(PoisonError::into_inner);
+        let bytes = match &mut *render {
+            Some(render) if render.key == key => &render.bytes,
+            render => {
+                // The output only moves the cursor relative to where it starts, so it can be
+                // reused wherever the object is.
+                let mut bytes = Vec::new();
+                Self::render_entry(&mut bytes, entry, overlay)?;
+                &render.insert(Render { key, bytes }).bytes
+            }
+        };
+        out.write_all(bytes)?;
+        out.flush()
+    }
+
+    /// Writes the object `entry` at the current cursor position, wrapped in its style with
+    /// `overlay` layered on top.
+    fn render_entry<W: Write>(
+        out: &mut W,
+        entry: &NyanObjs<'a>,
+        overlay: NyanStyle,
+    ) -> io::Result<()> {
+        Self::offset_cursor(out, entry.margin)?;
+
+        let mut style = entry.style.patch(overlay);
+        if let Some(fill) = entry.fill {
+            if style.bg.is_none() {
+                style.bg = Some(fill);
+            }
+            let rect = entry.rect();
+            Self::paint_fill(out, fill, (rect.width, rect.height))?;
+        }
+        Self::offset_cursor(out, entry.padding)?;
+
+        // Objects inherit the base style of the App, overriding only what they set themselves.
+        NyanStyle::base().patch(style).apply(out)?;
+        entry.object.draw(out)?;
+        if !style.is_empty() {
+            NyanStyle::reset(out)?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// An object of a serialized scene, borrowed from the collection.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct EntryRef<'b, 'a> {
+    id: &'b str,
+    object: &'b Objects<'a>,
+    coordinate: (u16, u16),
+    style: NyanStyle,
+    fill: Option<NyanColor>,
+    padding: Edges,
+    margin: Edges,
+    visible: bool,
+    body: Option<Body>,
+    cached: bool,
+}
+
+/// An object of a deserialized scene. Everything but the id and the object is optional.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Entry<'a> {
+    id: Cow<'a, str>,
+    object: Objects<'a>,
+    #[serde(default)]
+    coordinate: (u16, u16),
+    #[serde(default)]
+    style: NyanStyle,
+    #[serde(default)]
+    fill: Option<NyanColor>,
+    #[serde(default)]
+    padding: Edges,
+    #[serde(default)]
+    margin: Edges,
+    #[serde(default = "visible")]
+    visible: bool,
+    #[serde(default)]
+    body: Option<Body>,
+    #[serde(default)]
+    cached: bool,
+}
+
+/// Objects are visible unless a scene says otherwise.
+#[cfg(feature = "serde")]
+fn visible() -> bool {
+    true
+}
+
+/// A scene is written as a list of its objects in drawing order, each with its id, coordinate,
+/// style, fill, padding, margin, visibility, body and whether it is cached. Requires the
+/// `serde` feature.
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for NyanObj<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.inner.iter().map(|obj| EntryRef {
+            id: &obj.id,
+            object: &obj.object,
+            coordinate: obj.coordinate,
+            style: obj.style,
+            fill: obj.fill,
+            padding: obj.padding,
+            margin: obj.margin,
+            visible: obj.visible,
+            body: obj.body,
+            cached: obj.cached,
+        }))
+    }
+}
+
+/// A scene is read from a list of objects, as it is written. Requires the `serde` feature.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use nyan::nyan_obj::NyanObj;
+///
+/// let json = r#"[
+///     { "id": "title", "object": { "Text": "nyan" }, "coordinate": [2, 1] },
+///     { "id": "hint", "object": { "Text": "press q" }, "visible": false }
+/// ]"#;
+/// let obj: NyanObj = serde_json::from_str(json).unwrap();
+/// assert_eq!(obj.coordinate("title"), Some((2, 1)));
+/// assert_eq!(obj.is_visible("hint"), Some(false));
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+impl<'de, 'a> serde::Deserialize<'de> for NyanObj<'a> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut scene = Self::new();
+        for entry in Vec::<Entry<'a>>::deserialize(deserializer)? {
+            // Blocks cannot be drawn yet, so a scene with one would fail on its first frame.
+            if let Objects::Block = entry.object {
+                return Err(serde::de::Error::custom(NyanError::NotSupported(
+                    format!(
+                        "object \"{}\" is a block, which cannot be drawn yet",
+                        entry.id
+                    )
+                    .into(),
+                )));
+            }
+            scene
+                .index
+                .entry(entry.id.as_ref().into())
+                .or_insert(scene.inner.len());
+            let mut obj = NyanObjs::new(entry.object, entry.id, entry.coordinate);
+            obj.style = entry.style;
+            obj.fill = entry.fill;
+            obj.padding = entry.padding;
+            obj.margin = entry.margin;
+            obj.visible = entry.visible;
+            obj.body = entry.body;
+            obj.cached = entry.cached;
+            scene.inner.push(obj);
+        }
+        Ok(scene)
+    }
 }
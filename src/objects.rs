@@ -8,15 +8,67 @@
 //!     - `Block`: A block object (potentially used for drawing a visual element).
 //!     - `Air`: An air object, representing an empty or invisible entity.
 //!     - `Text`: A text object, containing a string slice (`&'a str`), used for displaying text in the terminal.
+//!     - `StyledText`: A text object made of styled spans, so one line can mix several colors and attributes.
+//!     - `Paragraph`: A text object that is word-wrapped to a width, re-wrapped whenever it is resized.
+//!     - `ScrollableText`: A multi-line text object that shows `height` lines at a time, starting at a scroll offset.
+//!     - `ScrollView`: A fixed-size view of text that may be taller and wider than the view, with scrollbars.
+//!     - `Panel`: A box of a given size, drawn with a `BorderStyle` (or the default border).
+//!     - `List`: A list of items that shows `height` items at a time, starting at a scroll offset.
+//!     - `Sprite`: An `AnimatedSprite` that shows one of its frames, advanced with `advance(dt)`.
+//!     - `Typewriter`: A `Typewriter` that reveals its text a character at a time, advanced with `advance(dt)`.
+//!     - `Custom`: An object of a kind added by another crate, drawn by the renderer registered for it (see the `plugin` module).
 //!
 //! # Methods
 //!
+//! - `new_text(text)`: Creates a `Text` object.
+//! - `new_styled_text(spans)`: Creates a `StyledText` object from `(NyanStyle, text)` spans.
+//! - `new_paragraph(text, width)`: Creates a `Paragraph` object.
+//! - `new_scrollable_text(text, height)`: Creates a `ScrollableText` object.
+//! - `new_list(items, height)`: Creates a `List` object.
+//! - `new_scroll_view(text, width, height)`: Creates a `ScrollView` object.
+//! - `new_panel(width, height, border)`: Creates a `Panel` object.
+//! - `new_sprite(sprite)`: Creates a `Sprite` object.
+//! - `new_typewriter(typewriter)`: Creates a `Typewriter` object.
+//! - `new_custom(kind, props)` / `props_mut()`: Creates a `Custom` object / changes its props.
+//! - `advance(dt)` / `sprite_mut()` / `typewriter_mut()`: Advance a `Sprite` or `Typewriter` object and control it.
+//! - `resize(width, height)`: Resizes a `Panel` or `ScrollView`, re-wraps a `Paragraph`, or changes the visible height of a `ScrollableText` or `List` object.
+//! - `scroll_by(delta)`: Scrolls a `ScrollableText`, `List` or `ScrollView` object by `delta` lines.
+//! - `scroll_columns_by(delta)`: Scrolls a `ScrollView` object sideways by `delta` columns.
+//! - `scroll_to_top()` / `scroll_to_bottom()`: Scroll to the first / last page.
+//! - `handle_scroll_input(input)` / `handle_scroll_event(event)`: Scroll with the arrow keys, Page Up / Page Down, Home / End and the mouse wheel.
+//! - `draw(out)`: Writes the object at the current cursor position.
+//! - `size()`: Returns the `(width, height)` in cells that the object occupies when drawn.
 //! - `Debug`: Provides a custom debug implementation for the `Objects` enum. It formats the enum variants in a human-readable way, displaying the respective type and data (if applicable).
 
 use std::borrow::Cow;
 use std::fmt::Debug;
+use std::io::Write;
+use std::time::Duration;
 
-#[derive(PartialEq, Eq, Hash)]
+use crossterm::{cursor, queue};
+use unicode_width::UnicodeWidthStr;
+
+use crate::border::{self, BorderStyle};
+use crate::event::{NyanEvent, NyanMouse, NyanMouseKind};
+use crate::input::NyanInput;
+use crate::layout;
+use crate::plugin::{self, Props};
+use crate::scrollbar::Scrollbar;
+use crate::sprite::AnimatedSprite;
+use crate::stats;
+use crate::style::NyanStyle;
+use crate::typewriter::Typewriter;
+
+/// The scrollbars of a `ScrollView`; a scrollbar is `None` if the text fits on its axis.
+struct ScrollBars {
+    vertical: Option<Scrollbar>,
+    horizontal: Option<Scrollbar>,
+    /// The `(width, height)` left for the text.
+    view: (u16, u16),
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The `Objects` enum represents different types of objects.
 /// It can be a `Block`, `Air`, or a `Text` object containing a `AsRef<str>`.
 pub enum Objects<'a> {
@@ -28,6 +80,58 @@ pub enum Objects<'a> {
 
     /// Represents a text object that contains a string.
     Text(Cow<'a, str>),
+
+    /// Represents a text object made of styled spans, written one after another.
+    StyledText(Vec<(NyanStyle, Cow<'a, str>)>),
+
+    /// Represents a text object that is word-wrapped to `width` cells when drawn. A `width` of
+    /// `0` does not wrap.
+    Paragraph { text: Cow<'a, str>, width: u16 },
+
+    /// Represents a multi-line text object that shows `height` lines, starting at line `offset`.
+    ScrollableText {
+        text: Cow<'a, str>,
+        height: u16,
+        offset: usize,
+    },
+
+    /// Represents a list that shows `height` items, starting at item `offset`.
+    List {
+        items: Vec<Cow<'a, str>>,
+        height: u16,
+        offset: usize,
+    },
+
+    /// Represents a `width` x `height` view of `text`, scrolled down by `offset` lines and right
+    /// by `column` cells. A scrollbar is drawn on each axis the text does not fit on.
+    ScrollView {
+        text: Cow<'a, str>,
+        width: u16,
+        height: u16,
+        offset: usize,
+        column: usize,
+    },
+
+    /// Represents a `width` x `height` box. `None` uses the default border
+    /// (see [`border::default_border`]).
+    Panel {
+        width: u16,
+        height: u16,
+        border: Option<BorderStyle>,
+    },
+
+    /// Represents an animated sprite, showing its current frame.
+    Sprite(AnimatedSprite<'a>),
+
+    /// Represents text revealed a character at a time, showing what is typed so far.
+    Typewriter(Typewriter<'a>),
+
+    /// Represents an object of a kind registered by another crate, drawn from its `props` by
+    /// the renderer of `kind` (see [`plugin::register`]).
+    Custom {
+        kind: Cow<'a, str>,
+        props: Props<'a>,
+    },
 }
 
 impl<'a> Debug for Objects<'a> {
@@ -48,6 +152,85 @@ impl<'a> Debug for Objects<'a> {
             Objects::Text(t) => {
                 write!(fmt, "Objects::Text({})", t.as_ref())
             }
+
+            // Formats the StyledText variant, displaying the contained spans
+            Objects::StyledText(spans) => {
+                write!(fmt, "Objects::StyledText({:?})", spans)
+            }
+
+            // Formats the Paragraph variant, displaying the contained text and wrap width
+            Objects::Paragraph { text, width } => {
+                write!(fmt, "Objects::Paragraph({}, width: {width})", text.as_ref())
+            }
+
+            // Formats the ScrollableText variant, displaying the contained text and scroll state
+            Objects::ScrollableText {
+                text,
+                height,
+                offset,
+            } => {
+                write!(
+                    fmt,
+                    "Objects::ScrollableText({}, height: {height}, offset: {offset})",
+                    text.as_ref()
+                )
+            }
+
+            // Formats the List variant, displaying the items and scroll state
+            Objects::List {
+                items,
+                height,
+                offset,
+            } => {
+                write!(
+                    fmt,
+                    "Objects::List({:?}, height: {height}, offset: {offset})",
+                    items
+                )
+            }
+
+            // Formats the ScrollView variant, displaying the contained text, size and scroll state
+            Objects::ScrollView {
+                text,
+                width,
+                height,
+                offset,
+                column,
+            } => {
+                write!(
+                    fmt,
+                    "Objects::ScrollView({}, {width}x{height}, offset: {offset}, column: {column})",
+                    text.as_ref()
+                )
+            }
+
+            // Formats the Panel variant, displaying its size and border
+            Objects::Panel {
+                width,
+                height,
+                border,
+            } => {
+                write!(
+                    fmt,
+                    "Objects::Panel({width}x{height}, border: {:?})",
+                    border
+                )
+            }
+
+            // Formats the Sprite variant, displaying its frames and playback state
+            Objects::Sprite(sprite) => {
+                write!(fmt, "Objects::Sprite({:?})", sprite)
+            }
+
+            // Formats the Typewriter variant, displaying its text and what is shown of it
+            Objects::Typewriter(typewriter) => {
+                write!(fmt, "Objects::Typewriter({:?})", typewriter)
+            }
+
+            // Formats the Custom variant, displaying its kind and props
+            Objects::Custom { kind, props } => {
+                write!(fmt, "Objects::Custom({kind:?}, {props:?})")
+            }
         }
     }
 }
@@ -56,4 +239,678 @@ impl<'a> Objects<'a> {
     pub fn new_text<T: Into<Cow<'a, str>>>(text: T) -> Self {
         Self::Text(text.into())
     }
+
+    /// Creates a `StyledText` object from `(style, text)` spans.
+    ///
+    /// Each span is drawn with its own style layered on the base style, so a single line can
+    /// contain several colors and attributes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::color::NyanColor;
+    /// use nyan::objects::Objects;
+    /// use nyan::style::NyanStyle;
+    ///
+    /// let text = Objects::new_styled_text(vec![
+    ///     (NyanStyle::new().fg(NyanColor::Green), "OK"),
+    ///     (NyanStyle::new(), " all tests passed"),
+    /// ]);
+    /// assert_eq!(text.size(), (19, 1));
+    /// ```
+    pub fn new_styled_text<T: Into<Cow<'a, str>>, I: IntoIterator<Item = (NyanStyle, T)>>(
+        spans: I,
+    ) -> Self {
+        Self::StyledText(
+            spans
+                .into_iter()
+                .map(|(style, text)| (style, text.into()))
+                .collect(),
+        )
+    }
+
+    /// Creates a `Paragraph` object that word-wraps `text` to `width` cells.
+    ///
+    /// Unlike text wrapped once by hand, a paragraph keeps its original text and wraps it again
+    /// whenever it is resized, so a paragraph placed by a layout always fills the width of its
+    /// region (see [`NyanObj::fit_object`](crate::nyan_obj::NyanObj::fit_object)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::objects::Objects;
+    ///
+    /// let mut text = Objects::new_paragraph("the quick brown fox", 10);
+    /// assert_eq!(text.size(), (10, 2));
+    ///
+    /// text.resize(20, 1);
+    /// assert_eq!(text.size(), (20, 1));
+    /// ```
+    pub fn new_paragraph<T: Into<Cow<'a, str>>>(text: T, width: u16) -> Self {
+        Self::Paragraph {
+            text: text.into(),
+            width,
+        }
+    }
+
+    /// Creates a `ScrollableText` object that shows `height` lines of `text` at a time.
+    pub fn new_scrollable_text<T: Into<Cow<'a, str>>>(text: T, height: u16) -> Self {
+        Self::ScrollableText {
+            text: text.into(),
+            height,
+            offset: 0,
+        }
+    }
+
+    /// Creates a `List` object that shows `height` of `items` at a time.
+    pub fn new_list<T: Into<Cow<'a, str>>, I: IntoIterator<Item = T>>(
+        items: I,
+        height: u16,
+    ) -> Self {
+        Self::List {
+            items: items.into_iter().map(Into::into).collect(),
+            height,
+            offset: 0,
+        }
+    }
+
+    /// Creates a `ScrollView` object, a `width` x `height` view of `text`.
+    ///
+    /// The text may be taller and wider than the view. The view then reserves its last column
+    /// and / or row for a scrollbar (see [`Scrollbar`]) showing which part is visible.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::input::NyanInput;
+    /// use nyan::objects::Objects;
+    ///
+    /// let log = (1..=100).map(|i| format!("line {i}")).collect::<Vec<_>>().join("\n");
+    /// let mut view = Objects::new_scroll_view(log, 20, 10);
+    ///
+    /// assert!(view.handle_scroll_input(&NyanInput::PageDown));
+    /// assert_eq!(view.scroll_offset(), Some(9));
+    /// view.scroll_to_bottom();
+    /// assert_eq!(view.scroll_offset(), Some(90));
+    /// ```
+    pub fn new_scroll_view<T: Into<Cow<'a, str>>>(text: T, width: u16, height: u16) -> Self {
+        Self::ScrollView {
+            text: text.into(),
+            width,
+            height,
+            offset: 0,
+            column: 0,
+        }
+    }
+
+    /// Creates a `Panel` object, a `width` x `height` box.
+    ///
+    /// Pass `None` as `border` to follow the default border (see
+    /// [`border::set_default_border`]).
+    pub fn new_panel(width: u16, height: u16, border: Option<BorderStyle>) -> Self {
+        Self::Panel {
+            width,
+            height,
+            border,
+        }
+    }
+
+    /// Creates a `Sprite` object from an animated sprite.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use nyan::objects::Objects;
+    /// use nyan::sprite::AnimatedSprite;
+    ///
+    /// let second = Duration::from_secs(1);
+    /// let mut spinner = Objects::new_sprite(
+    ///     AnimatedSprite::new().frame("|", second).frame("/", second),
+    /// );
+    /// assert!(spinner.advance(second));
+    /// assert_eq!(spinner.sprite_mut().unwrap().current_frame(), "/");
+    /// ```
+    pub fn new_sprite(sprite: AnimatedSprite<'a>) -> Self {
+        Self::Sprite(sprite)
+    }
+
+    /// Returns the sprite of a `Sprite` object, e.g. to pause it.
+    pub fn sprite_mut(&mut self) -> Option<&mut AnimatedSprite<'a>> {
+        match self {
+            Objects::Sprite(sprite) => Some(sprite),
+            _ => None,
+        }
+    }
+
+    /// Creates a `Typewriter` object, as large as its whole text from the start.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use nyan::objects::Objects;
+    /// use nyan::typewriter::Typewriter;
+    ///
+    /// let mut intro = Objects::new_typewriter(Typewriter::new("Once upon a time").rate(20));
+    /// assert_eq!(intro.size(), (16, 1));
+    /// assert!(intro.advance(Duration::from_millis(200)));
+    /// assert_eq!(intro.typewriter_mut().unwrap().revealed(), "Once");
+    /// ```
+    pub fn new_typewriter(typewriter: Typewriter<'a>) -> Self {
+        Self::Typewriter(typewriter)
+    }
+
+    /// Returns the typewriter of a `Typewriter` object, e.g. to skip to the end.
+    pub fn typewriter_mut(&mut self) -> Option<&mut Typewriter<'a>> {
+        match self {
+            Objects::Typewriter(typewriter) => Some(typewriter),
+            _ => None,
+        }
+    }
+
+    /// Creates a `Custom` object of `kind`, drawn by the renderer registered for the kind with
+    /// [`plugin::register`]. Until one is registered, the object takes no space and drawing
+    /// it fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::objects::Objects;
+    /// use nyan::plugin::Props;
+    ///
+    /// let mut gauge = Objects::new_custom("unregistered", Props::new().with("value", "0.4"));
+    /// assert_eq!(gauge.size(), (0, 0));
+    /// assert!(gauge.draw(&mut Vec::new()).is_err());
+    ///
+    /// gauge.props_mut().unwrap().set("value", "0.5");
+    /// ```
+    pub fn new_custom<K: Into<Cow<'a, str>>>(kind: K, props: Props<'a>) -> Self {
+        Self::Custom {
+            kind: kind.into(),
+            props,
+        }
+    }
+
+    /// Returns the props of a `Custom` object, e.g. to change the value a gauge shows.
+    pub fn props_mut(&mut self) -> Option<&mut Props<'a>> {
+        match self {
+            Objects::Custom { props, .. } => Some(props),
+            _ => None,
+        }
+    }
+
+    /// Advances a `Sprite` or `Typewriter` object by `dt`. Other objects are left unchanged.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the object shows another frame or more of its text now.
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        match self {
+            Objects::Sprite(sprite) => sprite.advance(dt),
+            Objects::Typewriter(typewriter) => typewriter.advance(dt),
+            _ => false,
+        }
+    }
+
+    /// Returns the size `(width, height)` in cells that the object occupies when drawn.
+    ///
+    /// The width of a `Text` object is the display width of its longest line, so wide
+    /// characters (e.g. CJK) count as two cells. `Air` occupies no cells and `Block` one cell.
+    /// A `Paragraph` is as wide as its wrap width and as tall as its wrapped lines.
+    /// `ScrollableText` and `List` objects are as wide as their longest line and as tall as
+    /// their `height`. A `Sprite` is as large as its largest frame, and a `Typewriter` as its
+    /// whole text.
+    pub fn size(&self) -> (u16, u16) {
+        let (width, height) = match self {
+            Objects::Air => (0, 0),
+            Objects::Block => (1, 1),
+            Objects::Text(t) => (
+                t.lines().map(|l| l.width()).max().unwrap_or(0),
+                t.lines().count(),
+            ),
+            Objects::StyledText(spans) => {
+                let text: String = spans.iter().map(|(_, t)| t.as_ref()).collect();
+                (
+                    text.lines().map(|l| l.width()).max().unwrap_or(0),
+                    text.lines().count(),
+                )
+            }
+            Objects::Paragraph { text, width: 0 } => (
+                text.lines().map(|l| l.width()).max().unwrap_or(0),
+                text.lines().count(),
+            ),
+            Objects::Paragraph { text, width } => {
+                (*width as usize, layout::wrap_text(text, *width).len())
+            }
+            Objects::ScrollableText { text, height, .. } => (
+                text.lines().map(|l| l.width()).max().unwrap_or(0),
+                *height as usize,
+            ),
+            Objects::List { items, height, .. } => (
+                items.iter().map(|i| i.width()).max().unwrap_or(0),
+                *height as usize,
+            ),
+            Objects::ScrollView { width, height, .. } | Objects::Panel { width, height, .. } => {
+                (*width as usize, *height as usize)
+            }
+            Objects::Sprite(sprite) => {
+                let (width, height) = sprite.size();
+                (width as usize, height as usize)
+            }
+            Objects::Typewriter(typewriter) => {
+                let (width, height) = typewriter.size();
+                (width as usize, height as usize)
+            }
+            Objects::Custom { kind, props } => {
+                let (width, height) =
+                    plugin::renderer(kind).map_or((0, 0), |renderer| renderer.size(props));
+                (width as usize, height as usize)
+            }
+        };
+        (
+            width.min(u16::MAX as usize) as u16,
+            height.min(u16::MAX as usize) as u16,
+        )
+    }
+
+    /// Returns whether the object can be scrolled (`ScrollableText`, `List` and `ScrollView`).
+    pub fn is_scrollable(&self) -> bool {
+        matches!(
+            self,
+            Objects::ScrollableText { .. } | Objects::List { .. } | Objects::ScrollView { .. }
+        )
+    }
+
+    /// Returns the scroll offset in lines of a `ScrollableText`, `List` or `ScrollView` object.
+    pub fn scroll_offset(&self) -> Option<usize> {
+        match self {
+            Objects::ScrollableText { offset, .. }
+            | Objects::List { offset, .. }
+            | Objects::ScrollView { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// Returns the horizontal scroll offset in cells of a `ScrollView` object.
+    pub fn scroll_column(&self) -> Option<usize> {
+        match self {
+            Objects::ScrollView { column, .. } => Some(*column),
+            _ => None,
+        }
+    }
+
+    /// Returns the scrollbars of a `ScrollView` object and the size of the area left for the
+    /// text.
+    fn scrollbars(&self) -> Option<ScrollBars> {
+        let Objects::ScrollView {
+            text,
+            width,
+            height,
+            offset,
+            column,
+        } = self
+        else {
+            return None;
+        };
+        let lines = text.lines().count();
+        let columns = text.lines().map(|l| l.width()).max().unwrap_or(0);
+
+        // Each scrollbar takes space from the other axis, so check twice.
+        let (mut vertical, mut horizontal) = (false, false);
+        for _ in 0..2 {
+            vertical = lines > height.saturating_sub(u16::from(horizontal)) as usize;
+            horizontal = columns > width.saturating_sub(u16::from(vertical)) as usize;
+        }
+        let view = (
+            width.saturating_sub(u16::from(vertical)),
+            height.saturating_sub(u16::from(horizontal)),
+        );
+        Some(ScrollBars {
+            vertical: vertical.then(|| Scrollbar::new(lines, view.1 as usize, *offset)),
+            horizontal: horizontal.then(|| Scrollbar::new(columns, view.0 as usize, *column)),
+            view,
+        })
+    }
+
+    /// Returns whether the object can be resized (`Panel`, `ScrollView`, `Paragraph`,
+    /// `ScrollableText` and `List`).
+    pub fn is_resizable(&self) -> bool {
+        matches!(
+            self,
+            Objects::Panel { .. }
+                | Objects::ScrollView { .. }
+                | Objects::Paragraph { .. }
+                | Objects::ScrollableText { .. }
+                | Objects::List { .. }
+        )
+    }
+
+    /// Resizes the object, e.g. to fill the region a layout assigned to it.
+    ///
+    /// `Panel` and `ScrollView` objects take both `width` and `height`. A `Paragraph` is re-wrapped to `width` and grows
+    /// as tall as its text needs. `ScrollableText` and `List` objects are as wide
+    /// as their content, so only their visible `height` changes and the scroll offset is
+    /// clamped to the new height. Other objects are left unchanged.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        match self {
+            Objects::Panel {
+                width: w,
+                height: h,
+                ..
+            } => {
+                *w = width;
+                *h = height;
+            }
+            Objects::ScrollView {
+                width: w,
+                height: h,
+                ..
+            } => {
+                *w = width;
+                *h = height;
+                self.scroll_by(0);
+                self.scroll_columns_by(0);
+            }
+            Objects::Paragraph { width: w, .. } => {
+                *w = width;
+            }
+            Objects::ScrollableText { height: h, .. } | Objects::List { height: h, .. } => {
+                *h = height;
+                self.scroll_by(0);
+            }
+            _ => {}
+        }
+    }
+
+    /// Scrolls a `ScrollableText`, `List` or `ScrollView` object by `delta` lines.
+    ///
+    /// Negative values scroll up. The offset is clamped so the last page stays filled.
+    /// Other objects are left unchanged.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let (len, height, offset) = match self {
+            Objects::ScrollableText {
+                text,
+                height,
+                offset,
+            } => (text.lines().count(), *height as usize, offset),
+            Objects::List {
+                items,
+                height,
+                offset,
+            } => (items.len(), *height as usize, offset),
+            Objects::ScrollView { .. } => {
+                let view = self.scrollbars().map_or(0, |bars| bars.view.1);
+                let Objects::ScrollView { text, offset, .. } = self else {
+                    return;
+                };
+                (text.lines().count(), view as usize, offset)
+            }
+            _ => return,
+        };
+
+        let max = len.saturating_sub(height);
+        *offset = offset.saturating_add_signed(delta).min(max);
+    }
+
+    /// Scrolls a `ScrollView` object sideways by `delta` cells.
+    ///
+    /// Negative values scroll left. The offset is clamped so the widest line still ends inside
+    /// the view. Other objects are left unchanged.
+    pub fn scroll_columns_by(&mut self, delta: isize) {
+        let view = match self.scrollbars() {
+            Some(bars) => bars.view.0 as usize,
+            None => return,
+        };
+        if let Objects::ScrollView { text, column, .. } = self {
+            let columns = text.lines().map(|l| l.width()).max().unwrap_or(0);
+            let max = columns.saturating_sub(view);
+            *column = column.saturating_add_signed(delta).min(max);
+        }
+    }
+
+    /// Scrolls a scrollable object back to its first line.
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_by(isize::MIN);
+    }
+
+    /// Scrolls a scrollable object to its last page.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_by(isize::MAX);
+    }
+
+    /// Returns the number of lines a scrollable object shows at a time, i.e. one page.
+    fn page(&self) -> usize {
+        match self {
+            Objects::ScrollableText { height, .. } | Objects::List { height, .. } => {
+                *height as usize
+            }
+            _ => self.scrollbars().map_or(0, |bars| bars.view.1 as usize),
+        }
+    }
+
+    /// Scrolls a scrollable object with the keyboard.
+    ///
+    /// The up and down arrows scroll one line, Page Up and Page Down one page (keeping one line
+    /// of context), Home and End to the top and the bottom. The left and right arrows scroll a
+    /// `ScrollView` sideways.
+    ///
+    /// # Returns
+    /// `true` if the key is a scroll key and the object is scrollable, whether or not the
+    /// offset changed.
+    pub fn handle_scroll_input(&mut self, input: &NyanInput) -> bool {
+        if !self.is_scrollable() {
+            return false;
+        }
+        let page = self.page().saturating_sub(1).max(1) as isize;
+        match input {
+            NyanInput::UpAllow => self.scroll_by(-1),
+            NyanInput::DownAllow => self.scroll_by(1),
+            NyanInput::PageUp => self.scroll_by(-page),
+            NyanInput::PageDown => self.scroll_by(page),
+            NyanInput::Home => self.scroll_to_top(),
+            NyanInput::End => self.scroll_to_bottom(),
+            NyanInput::LeftAllow if self.scroll_column().is_some() => self.scroll_columns_by(-1),
+            NyanInput::RightAllow if self.scroll_column().is_some() => self.scroll_columns_by(1),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Scrolls a scrollable object with the keyboard (see [`Objects::handle_scroll_input`]) or
+    /// the mouse wheel. Mouse actions are not hit-tested; use
+    /// [`NyanObj::route_scroll`](crate::nyan_obj::NyanObj::route_scroll) for that.
+    ///
+    /// # Returns
+    /// `true` if the event scrolled the object.
+    pub fn handle_scroll_event<U>(&mut self, event: &NyanEvent<U>) -> bool {
+        match event {
+            NyanEvent::Key(input) => self.handle_scroll_input(input),
+            NyanEvent::Mouse(mouse) => self.scroll_mouse(mouse),
+            _ => false,
+        }
+    }
+
+    /// Returns the approximate bytes the object owns besides its own size: owned text and
+    /// the lists holding it (see [`SceneStats`](crate::stats::SceneStats)).
+    pub(crate) fn heap_size(&self) -> usize {
+        match self {
+            Objects::Air | Objects::Block | Objects::Panel { .. } => 0,
+            Objects::Text(text)
+            | Objects::Paragraph { text, .. }
+            | Objects::ScrollableText { text, .. }
+            | Objects::ScrollView { text, .. } => stats::owned(text),
+            Objects::StyledText(spans) => {
+                spans.capacity() * std::mem::size_of::<(NyanStyle, Cow<str>)>()
+                    + spans
+                        .iter()
+                        .map(|(_, text)| stats::owned(text))
+                        .sum::<usize>()
+            }
+            Objects::List { items, .. } => {
+                items.capacity() * std::mem::size_of::<Cow<str>>()
+                    + items.iter().map(stats::owned).sum::<usize>()
+            }
+            Objects::Sprite(sprite) => sprite.heap_size(),
+            Objects::Typewriter(typewriter) => typewriter.heap_size(),
+            Objects::Custom { kind, props } => stats::owned(kind) + props.heap_size(),
+        }
+    }
+
+    /// Scrolls a scrollable object one step for a mouse wheel action.
+    ///
+    /// # Returns
+    /// `true` if the action was a wheel action the object can scroll along.
+    pub(crate) fn scroll_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        if !self.is_scrollable() {
+            return false;
+        }
+        match mouse.kind {
+            NyanMouseKind::ScrollUp => self.scroll_by(-1),
+            NyanMouseKind::ScrollDown => self.scroll_by(1),
+            NyanMouseKind::ScrollLeft if self.scroll_column().is_some() => {
+                self.scroll_columns_by(-1)
+            }
+            NyanMouseKind::ScrollRight if self.scroll_column().is_some() => {
+                self.scroll_columns_by(1)
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Writes the object at the current cursor position.
+    ///
+    /// Multi-line `Paragraph`, `ScrollableText` and `List` objects keep each visible line aligned
+    /// under the starting column and pad it to the object's width, so scrolled-away content is
+    /// overwritten.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(std::io::Error)` if writing fails.
+    pub fn draw<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        match self {
+            Objects::Text(t) => {
+                writeln!(out, "{}", t.as_ref())?;
+            }
+            Objects::StyledText(spans) => {
+                for (style, text) in spans {
+                    NyanStyle::base().patch(*style).apply(out)?;
+                    write!(out, "{}", text.as_ref())?;
+                    if !style.is_empty() {
+                        NyanStyle::reset(out)?;
+                    }
+                }
+                writeln!(out)?;
+            }
+            Objects::Air => {}
+            // Drawing functionality is not yet implemented for blocks.
+            Objects::Block => {
+                todo!()
+            }
+            Objects::Paragraph { text, width } => {
+                let lines = layout::wrap_text(text, *width);
+                self.draw_lines(out, &lines, lines.len() as u16)?;
+            }
+            Objects::ScrollableText {
+                text,
+                height,
+                offset,
+            } => {
+                self.draw_lines(out, text.lines().skip(*offset), *height)?;
+            }
+            Objects::List {
+                items,
+                height,
+                offset,
+            } => {
+                self.draw_lines(out, items.iter().skip(*offset), *height)?;
+            }
+            Objects::ScrollView {
+                text,
+                height,
+                offset,
+                column,
+                ..
+            } => {
+                let Some(ScrollBars {
+                    vertical,
+                    horizontal,
+                    view: (view_width, view_height),
+                }) = self.scrollbars()
+                else {
+                    return out.flush();
+                };
+                let bar = vertical.map(|bar| bar.cells(view_height).chars().collect::<Vec<_>>());
+                let source: Vec<&str> = text.lines().collect();
+
+                let mut lines = Vec::with_capacity(*height as usize);
+                for row in 0..view_height as usize {
+                    let line = source.get(offset + row).copied().unwrap_or("");
+                    let mut line = layout::align_text(
+                        &layout::clip_text(line, *column, view_width as usize),
+                        view_width,
+                        layout::Align::Start,
+                    );
+                    if let Some(bar) = &bar {
+                        line.push(bar[row]);
+                    }
+                    lines.push(line);
+                }
+                if let Some(horizontal) = horizontal {
+                    lines.push(horizontal.cells(view_width));
+                }
+                self.draw_lines(out, &lines, *height)?;
+            }
+            Objects::Panel {
+                width,
+                height,
+                border,
+            } => {
+                let lines = border
+                    .unwrap_or_else(border::default_border)
+                    .lines(*width, *height);
+                self.draw_lines(out, &lines, *height)?;
+            }
+            Objects::Sprite(sprite) => {
+                // Every frame is padded to the largest one, so smaller frames erase larger ones.
+                self.draw_lines(out, sprite.current_frame().lines(), sprite.size().1)?;
+            }
+            Objects::Typewriter(typewriter) => {
+                // Untyped cells are blank, so the whole text area is cleared.
+                self.draw_lines(out, typewriter.revealed().lines(), typewriter.size().1)?;
+            }
+            Objects::Custom { kind, props } => {
+                let renderer = plugin::renderer(kind).ok_or_else(|| {
+                    std::io::Error::other(format!("no renderer is registered for {kind:?}"))
+                })?;
+                let (_, height) = renderer.size(props);
+                self.draw_lines(out, renderer.lines(props), height)?;
+            }
+        }
+        out.flush()
+    }
+
+    /// Writes the first `height` of `lines`, each one below the previous one. Missing lines
+    /// are left blank.
+    fn draw_lines<W: Write, L: AsRef<str>>(
+        &self,
+        out: &mut W,
+        lines: impl IntoIterator<Item = L>,
+        height: u16,
+    ) -> std::io::Result<()> {
+        let (width, _) = self.size();
+        let mut lines = lines.into_iter();
+        for row in 0..height as usize {
+            let line = lines.next();
+            let line = line.as_ref().map_or("", |line| line.as_ref());
+            let padding = (width as usize).saturating_sub(line.width());
+            // Padded by the formatter, without a string of spaces.
+            write!(out, "{line}{:padding$}", "")?;
+
+            if row + 1 < height as usize {
+                // Return to the starting column on the next row.
+                if width > 0 {
+                    queue!(out, cursor::MoveLeft(width))?;
+                }
+                queue!(out, cursor::MoveDown(1))?;
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,507 @@
+//! This module provides the `CommandPalette` widget, a popup that finds a command by typing a few letters of its name.
+//!
+//! The palette is a box with a text field above a list of commands. Typing filters the commands with fuzzy matching: the typed letters must appear in the command's title in order, but not necessarily next to each other, and the best matches (consecutive letters, letters at word starts) come first, with the matched letters highlighted. Up and Down move the highlight, Enter executes the highlighted command and Esc closes the palette. The executed command is returned by `take_command()`.
+//!
+//! # Structs
+//!
+//! - `CommandPalette`: The commands, the query, the matches and whether the palette is open.
+//!
+//! # Functions
+//!
+//! - `fuzzy_match(pattern, text)`: Scores how well a pattern matches a text and returns the matched characters.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates an empty, closed palette.
+//! - `command(id, title)` / `command_with_hint(id, title, hint)`: Add a command, optionally with a hint such as its key binding.
+//! - `placeholder(text)` / `max_visible(rows)` / `theme(theme)`: Configure the palette.
+//! - `open()` / `close()` / `is_open()`: Show and hide the palette.
+//! - `query()` / `matches()` / `highlighted()`: Query the search.
+//! - `place_in(area)`: Places the palette at the top of a region, centered.
+//! - `take_command()`: Returns the id of the executed command once.
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::io::{self, Write};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::border;
+use crate::cursor::Cursor;
+use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::NyanInput;
+use crate::layout::{self, Align};
+use crate::rect::{Edges, Rect};
+use crate::style::NyanStyle;
+use crate::text_input::TextInput;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// Scores how well `pattern` fuzzily matches `text`, ignoring case.
+///
+/// Every character of `pattern` has to appear in `text` in the same order. Matches of
+/// consecutive characters and of characters at the start of a word score higher, gaps lower.
+///
+/// # Returns
+///
+/// - `Some((score, positions))` with the character indices of `text` that matched.
+/// - `None` if `pattern` does not match.
+///
+/// # Example
+/// ```rust
+/// use nyan::palette::fuzzy_match;
+///
+/// let (score, positions) = fuzzy_match("of", "Open File").unwrap();
+/// assert_eq!(positions, vec![0, 5]);
+/// assert!(score > fuzzy_match("of", "Show profile").unwrap().0);
+/// assert_eq!(fuzzy_match("fo", "Open File"), None);
+/// ```
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<(i32, Vec<usize>)> {
+    let lowercase = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let text: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = text.iter().map(|&c| lowercase(c)).collect();
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(lowercase)
+        .collect();
+    // Prefer shorter texts among equally good matches.
+    let length_penalty = (text.len() / 8) as i32;
+    if pattern.is_empty() {
+        return Some((-length_penalty, Vec::new()));
+    }
+
+    // best[i][j]: the best score of the first i + 1 characters of the pattern with the last
+    // one matched at j, and where the character before it was matched.
+    let mut best: Vec<Vec<Option<(i32, usize)>>> = vec![vec![None; text.len()]; pattern.len()];
+    for (i, &wanted) in pattern.iter().enumerate() {
+        for (j, &ch) in lower.iter().enumerate() {
+            if ch != wanted {
+                continue;
+            }
+            let word_start = j == 0 || !text[j - 1].is_alphanumeric();
+            let bonus = if word_start { 2 } else { 0 };
+            best[i][j] = if i == 0 {
+                let step = if word_start { 6 } else { -(j.min(5) as i32) };
+                Some((step + bonus, 0))
+            } else {
+                (0..j)
+                    .filter_map(|k| {
+                        let (score, _) = best[i - 1][k]?;
+                        let step = if k + 1 == j {
+                            8
+                        } else if word_start {
+                            6
+                        } else {
+                            -((j - k - 1).min(5) as i32)
+                        };
+                        Some((score + step + bonus, k))
+                    })
+                    .max_by_key(|&(score, _)| score)
+            };
+        }
+    }
+
+    let last = pattern.len() - 1;
+    let (mut j, (score, _)) = best[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, cell)| Some((j, (*cell)?)))
+        .max_by_key(|&(_, (score, _))| score)?;
+    let mut positions = vec![0; pattern.len()];
+    for i in (0..=last).rev() {
+        positions[i] = j;
+        j = best[i][j].map_or(0, |(_, previous)| previous);
+    }
+    Some((score - length_penalty, positions))
+}
+
+/// A command of the palette.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Command<'a> {
+    id: Cow<'a, str>,
+    title: Cow<'a, str>,
+    hint: Cow<'a, str>,
+}
+
+/// `CommandPalette` is a popup for finding and executing commands by name.
+///
+/// # Example
+/// ```rust
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::palette::CommandPalette;
+/// use nyan::widget::Widget;
+///
+/// let mut palette = CommandPalette::new()
+///     .command_with_hint("file.open", "Open File", "Ctrl+O")
+///     .command("file.save", "Save File")
+///     .command("view.zoom", "Zoom In");
+/// palette.open();
+///
+/// for key in [NyanKey::S, NyanKey::F] {
+///     palette.handle_input(&NyanInput::Key(key));
+/// }
+/// assert_eq!(palette.matches(), vec!["Save File"]);
+///
+/// palette.handle_input(&NyanInput::Enter);
+/// assert!(!palette.is_open());
+/// assert_eq!(palette.take_command().as_deref(), Some("file.save"));
+/// ```
+pub struct CommandPalette<'a> {
+    commands: Vec<Command<'a>>,
+    input: TextInput<'a>,
+    /// The matching commands, best first: the index of the command and its matched characters.
+    matches: Vec<(usize, Vec<usize>)>,
+    highlighted: usize,
+    /// The first match shown.
+    offset: usize,
+    max_visible: u16,
+    open: bool,
+    chosen: Option<Cow<'a, str>>,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> Debug for CommandPalette<'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("CommandPalette")
+            .field("commands", &self.commands)
+            .field("query", &self.input.text())
+            .field("matches", &self.matches)
+            .field("highlighted", &self.highlighted)
+            .field("open", &self.open)
+            .field("chosen", &self.chosen)
+            .field("area", &self.area)
+            .finish()
+    }
+}
+
+impl<'a> Default for CommandPalette<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> CommandPalette<'a> {
+    /// Creates a closed palette without commands.
+    pub fn new() -> Self {
+        let mut input = TextInput::new().borderless().placeholder("Type a command");
+        input.set_focused(true);
+        Self {
+            commands: Vec::new(),
+            input,
+            matches: Vec::new(),
+            highlighted: 0,
+            offset: 0,
+            max_visible: 10,
+            open: false,
+            chosen: None,
+            theme: Theme::default(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Adds a command, identified by `id` and found by `title`.
+    pub fn command<P: Into<Cow<'a, str>>, T: Into<Cow<'a, str>>>(self, id: P, title: T) -> Self {
+        self.command_with_hint(id, title, "")
+    }
+
+    /// Adds a command with a hint drawn at the right edge, e.g. its key binding.
+    pub fn command_with_hint<P, T, H>(self, id: P, title: T, hint: H) -> Self
+    where
+        P: Into<Cow<'a, str>>,
+        T: Into<Cow<'a, str>>,
+        H: Into<Cow<'a, str>>,
+    {
+        let mut palette = self;
+        palette.commands.push(Command {
+            id: id.into(),
+            title: title.into(),
+            hint: hint.into(),
+        });
+        palette.refilter();
+        palette
+    }
+
+    /// Sets the text shown in the empty field.
+    pub fn placeholder<T: Into<Cow<'a, str>>>(self, text: T) -> Self {
+        let mut palette = self;
+        palette.input = palette.input.placeholder(text);
+        palette
+    }
+
+    /// Sets how many commands are listed at a time (10 by default); longer lists scroll.
+    pub fn max_visible(self, rows: u16) -> Self {
+        let mut palette = self;
+        palette.max_visible = rows.max(1);
+        palette
+    }
+
+    /// Sets the colors of the palette.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut palette = self;
+        palette.theme = theme;
+        palette.input = palette.input.theme(theme);
+        palette
+    }
+
+    /// Opens the palette with an empty query.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.input.clear();
+        self.refilter();
+    }
+
+    /// Closes the palette without executing a command.
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Returns whether the palette is open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Returns the typed query.
+    pub fn query(&self) -> &str {
+        self.input.text()
+    }
+
+    /// Returns the titles of the matching commands, best first.
+    pub fn matches(&self) -> Vec<&str> {
+        self.matches
+            .iter()
+            .map(|(index, _)| self.commands[*index].title.as_ref())
+            .collect()
+    }
+
+    /// Returns the id of the highlighted command.
+    pub fn highlighted(&self) -> Option<&str> {
+        self.matches
+            .get(self.highlighted)
+            .map(|(index, _)| self.commands[*index].id.as_ref())
+    }
+
+    /// Places the palette in `area`: centered, a fifth of the way down and at most 60 cells
+    /// wide, like the palettes of editors.
+    pub fn place_in(&mut self, area: Rect) {
+        let (width, height) = self.size();
+        let width = width.min(area.width);
+        let height = height.min(area.height);
+        let x = area.x + Align::Center.offset(area.width, width);
+        let y = area.y + (area.height.saturating_sub(height)) / 5;
+        self.set_area(Rect::new(x, y, width, height));
+    }
+
+    /// Returns the id of the command executed since the last call, and forgets it.
+    pub fn take_command(&mut self) -> Option<Cow<'a, str>> {
+        self.chosen.take()
+    }
+
+    /// Matches the commands against the query and highlights the best one.
+    fn refilter(&mut self) {
+        let query = self.input.text();
+        let mut matches: Vec<(i32, usize, Vec<usize>)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                let (score, positions) = fuzzy_match(query, &command.title)?;
+                Some((score, index, positions))
+            })
+            .collect();
+        // Best score first; equal scores keep the order the commands were added in.
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.matches = matches
+            .into_iter()
+            .map(|(_, index, positions)| (index, positions))
+            .collect();
+        self.highlighted = 0;
+        self.offset = 0;
+    }
+
+    /// Moves the highlight by `delta` matches, keeping it visible.
+    fn move_highlight(&mut self, delta: isize) {
+        let last = self.matches.len().saturating_sub(1);
+        self.highlighted = self.highlighted.saturating_add_signed(delta).min(last);
+        let rows = self.list_area().height.max(1) as usize;
+        if self.highlighted < self.offset {
+            self.offset = self.highlighted;
+        } else if self.highlighted >= self.offset + rows {
+            self.offset = self.highlighted + 1 - rows;
+        }
+    }
+
+    /// Executes the highlighted command.
+    fn execute(&mut self) {
+        if let Some(id) = self.highlighted() {
+            self.chosen = Some(Cow::Owned(id.to_string()));
+            self.close();
+        }
+    }
+
+    /// Returns the area of the command list inside the box, below the field and a rule.
+    fn list_area(&self) -> Rect {
+        let inner = self.area.inner(Edges::all(1));
+        Rect::new(
+            inner.x,
+            inner.y + 2,
+            inner.width,
+            inner.height.saturating_sub(2),
+        )
+    }
+}
+
+impl<'a> Widget for CommandPalette<'a> {
+    /// 60 cells wide; the field, a rule and up to `max_visible` commands in a box.
+    fn size(&self) -> (u16, u16) {
+        let rows = (self.commands.len() as u16).clamp(1, self.max_visible);
+        (60, rows + 4)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+        let inner = area.inner(Edges::all(1));
+        self.input.set_area(Rect::new(
+            inner.x + 2,
+            inner.y,
+            inner.width.saturating_sub(2),
+            1,
+        ));
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if !self.open || self.area.width < 4 || self.area.height < 4 {
+            return Ok(());
+        }
+        let inner = self.area.inner(Edges::all(1));
+        let blank = " ".repeat(inner.width as usize);
+        for y in inner.y..inner.bottom() {
+            Cursor::Move(inner.x, y).queue(&mut out)?;
+            write!(out, "{blank}")?;
+        }
+        NyanStyle::base().patch(self.theme.accent).apply(&mut out)?;
+        border::default_border().draw(&mut out, self.area)?;
+        NyanStyle::reset(&mut out)?;
+
+        Cursor::Move(inner.x, inner.y).queue(&mut out)?;
+        widget::write_styled(out, self.theme.accent, ">")?;
+        self.input.draw(out)?;
+        Cursor::Move(inner.x, inner.y + 1).queue(&mut out)?;
+        widget::write_styled(out, self.theme.muted, &"─".repeat(inner.width as usize))?;
+
+        let list = self.list_area();
+        if self.matches.is_empty() {
+            Cursor::Move(list.x + 1, list.y).queue(&mut out)?;
+            widget::write_styled(out, self.theme.muted, "No matching commands")?;
+        }
+        let width = list.width as usize;
+        for row in 0..list.height as usize {
+            let position = self.offset + row;
+            let Some((index, positions)) = self.matches.get(position) else {
+                break;
+            };
+            let command = &self.commands[*index];
+            let base = if position == self.highlighted {
+                self.theme.selection
+            } else {
+                self.theme.text
+            };
+            let hint_width = command.hint.width();
+            let title_width = width.saturating_sub(hint_width + 3);
+            Cursor::Move(list.x, list.y + row as u16).queue(&mut out)?;
+            widget::write_styled(out, base, " ")?;
+            // The matched characters are drawn in the accent style.
+            let mut used = 0;
+            for (i, ch) in command.title.chars().enumerate() {
+                let ch_width = ch.width().unwrap_or(0);
+                if used + ch_width > title_width {
+                    break;
+                }
+                used += ch_width;
+                let style = if positions.contains(&i) {
+                    base.patch(self.theme.accent)
+                } else {
+                    base
+                };
+                widget::write_styled(out, style, ch.encode_utf8(&mut [0; 4]))?;
+            }
+            let gap = width.saturating_sub(1 + used + hint_width + 1);
+            widget::write_styled(out, base, &" ".repeat(gap))?;
+            let hint = layout::clip_text(&command.hint, 0, width.saturating_sub(used + 2));
+            widget::write_styled(out, base.patch(self.theme.muted), &hint)?;
+            widget::write_styled(out, base, " ")?;
+        }
+        out.flush()
+    }
+
+    /// Takes every key while the palette is open.
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        if !self.open {
+            return false;
+        }
+        let page = self.max_visible as isize;
+        match input {
+            NyanInput::Esc => self.close(),
+            NyanInput::Enter => self.execute(),
+            NyanInput::UpAllow => self.move_highlight(-1),
+            NyanInput::DownAllow | NyanInput::Tab => self.move_highlight(1),
+            NyanInput::PageUp => self.move_highlight(-page),
+            NyanInput::PageDown => self.move_highlight(page),
+            _ => {
+                let before = self.input.text().to_string();
+                self.input.handle_input(input);
+                if self.input.text() != before {
+                    self.refilter();
+                }
+            }
+        }
+        true
+    }
+
+    /// A click on a command executes it; a click outside closes the palette.
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        if !self.open {
+            return false;
+        }
+        let list = self.list_area();
+        match mouse.kind {
+            NyanMouseKind::Down(NyanMouseButton::Left) if list.contains(mouse.x, mouse.y) => {
+                let position = self.offset + (mouse.y - list.y) as usize;
+                if position < self.matches.len() {
+                    self.highlighted = position;
+                    self.execute();
+                }
+            }
+            NyanMouseKind::Down(NyanMouseButton::Left) if !self.area.contains(mouse.x, mouse.y) => {
+                self.close()
+            }
+            NyanMouseKind::ScrollUp => self.move_highlight(-1),
+            NyanMouseKind::ScrollDown => self.move_highlight(1),
+            _ => {}
+        }
+        true
+    }
+
+    fn handle_paste(&mut self, text: &str) -> bool {
+        if !self.open {
+            return false;
+        }
+        self.input.handle_paste(text);
+        self.refilter();
+        true
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    /// The palette has the focus while it is open.
+    fn is_focused(&self) -> bool {
+        self.open
+    }
+}
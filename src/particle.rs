@@ -0,0 +1,328 @@
+//! This module provides `Emitter`, a particle system for explosions, confetti, rain and the sparkling trail behind a flying cat.
+//!
+//! An emitter spawns particles at its origin (or anywhere in a spawn area around it), either continuously at a rate or all at once with `burst()`. Every particle flies with a random velocity around the emitter's base velocity, falls with the gravity and dies after its lifetime. While it ages it steps through the emitter's glyphs and, with a gradient, through its colors. Particles are drawn on top of the scene; draw them after it, onto a screen that is cleared every frame.
+//!
+//! # Structs
+//!
+//! - `Emitter`: The particles and how they are spawned, moved and drawn.
+//!
+//! # Methods
+//!
+//! - `new(origin)`: Creates an emitter that spawns nothing until configured.
+//! - `explosion(origin)` / `confetti(origin)` / `rain(width)`: Create emitters for common effects.
+//! - `rate(per_second)` / `lifetime(duration)` / `velocity(vx, vy)` / `spread(x, y)` / `gravity(g)` / `spawn_area(width, height)`: Configure how particles are spawned and moved.
+//! - `glyphs(text)` / `colors(gradient)` / `seed(seed)`: Configure how particles look and the random numbers.
+//! - `set_origin(origin)` / `set_emitting(on)` / `burst(count)`: Control the emitter.
+//! - `advance(dt)`: Spawns, moves and ages the particles.
+//! - `len()` / `is_empty()` / `positions()`: Query the live particles.
+//! - `draw_in(out, area)`: Draws the particles inside a region.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::cursor::Cursor;
+use crate::gradient::Gradient;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::widget;
+
+/// A live particle, positioned in cells and moving in cells per second.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Particle {
+    x: f32,
+    y: f32,
+    vx: f32,
+    vy: f32,
+    age: Duration,
+}
+
+/// `Emitter` spawns, moves and draws particles.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::particle::Emitter;
+///
+/// let mut sparks = Emitter::new((10.0, 5.0))
+///     .velocity(8.0, 0.0)
+///     .lifetime(Duration::from_millis(500))
+///     .glyphs("*+.");
+/// sparks.burst(3);
+///
+/// sparks.advance(Duration::from_millis(250));
+/// assert_eq!(sparks.positions(), vec![(12, 5); 3]);
+///
+/// sparks.advance(Duration::from_millis(250));
+/// assert!(sparks.is_empty());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Emitter {
+    particles: Vec<Particle>,
+    origin: (f32, f32),
+    spawn_area: (f32, f32),
+    rate: f32,
+    /// Particles owed by the rate but not spawned yet, below one.
+    pending: f32,
+    emitting: bool,
+    lifetime: Duration,
+    velocity: (f32, f32),
+    spread: (f32, f32),
+    gravity: f32,
+    glyphs: Vec<char>,
+    colors: Option<Gradient>,
+    rng: u64,
+}
+
+impl Emitter {
+    /// Creates an emitter at `origin` (in cells) that spawns no particles until a rate is set or
+    /// `burst()` is called. Particles live a second, stand still and are drawn as `*`.
+    pub fn new(origin: (f32, f32)) -> Self {
+        Self {
+            particles: Vec::new(),
+            origin,
+            spawn_area: (0.0, 0.0),
+            rate: 0.0,
+            pending: 0.0,
+            emitting: true,
+            lifetime: Duration::from_secs(1),
+            velocity: (0.0, 0.0),
+            spread: (0.0, 0.0),
+            gravity: 0.0,
+            glyphs: vec!['*'],
+            colors: None,
+            rng: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+
+    /// Creates an emitter for an explosion: call `burst()` to set it off.
+    pub fn explosion(origin: (f32, f32)) -> Self {
+        Self::new(origin)
+            .lifetime(Duration::from_millis(800))
+            .spread(24.0, 12.0)
+            .gravity(6.0)
+            .glyphs("@*+:.")
+            .colors(Gradient::new([
+                (255, 255, 200),
+                (255, 160, 0),
+                (120, 20, 0),
+            ]))
+    }
+
+    /// Creates an emitter that keeps throwing colorful confetti up from `origin`.
+    pub fn confetti(origin: (f32, f32)) -> Self {
+        Self::new(origin)
+            .rate(40.0)
+            .lifetime(Duration::from_secs(2))
+            .velocity(0.0, -14.0)
+            .spread(12.0, 4.0)
+            .gravity(14.0)
+            .glyphs("▪•▫·")
+            .colors(Gradient::new([
+                (255, 80, 80),
+                (255, 220, 60),
+                (80, 220, 120),
+                (80, 160, 255),
+                (220, 100, 255),
+            ]))
+    }
+
+    /// Creates an emitter of rain falling from the top row across `width` cells.
+    pub fn rain(width: u16) -> Self {
+        Self::new((0.0, 0.0))
+            .spawn_area(width as f32, 0.0)
+            .rate(width as f32)
+            .lifetime(Duration::from_secs(3))
+            .velocity(-2.0, 18.0)
+            .spread(0.5, 4.0)
+            .glyphs("|")
+            .colors(Gradient::new([(120, 160, 255), (60, 90, 200)]))
+    }
+
+    /// Spawns `per_second` particles every second while emitting.
+    pub fn rate(self, per_second: f32) -> Self {
+        let mut emitter = self;
+        emitter.rate = per_second.max(0.0);
+        emitter
+    }
+
+    /// Sets how long a particle lives.
+    pub fn lifetime(self, duration: Duration) -> Self {
+        let mut emitter = self;
+        emitter.lifetime = duration;
+        emitter
+    }
+
+    /// Sets the base velocity of new particles, in cells per second. Positive `vy` is down.
+    pub fn velocity(self, vx: f32, vy: f32) -> Self {
+        let mut emitter = self;
+        emitter.velocity = (vx, vy);
+        emitter
+    }
+
+    /// Sets how far the velocity of a new particle may differ from the base velocity, at random,
+    /// in both directions of each axis.
+    pub fn spread(self, x: f32, y: f32) -> Self {
+        let mut emitter = self;
+        emitter.spread = (x.abs(), y.abs());
+        emitter
+    }
+
+    /// Sets the acceleration pulling the particles down, in cells per second squared.
+    pub fn gravity(self, g: f32) -> Self {
+        let mut emitter = self;
+        emitter.gravity = g;
+        emitter
+    }
+
+    /// Spawns particles anywhere in a `width` x `height` area from the origin instead of at the
+    /// origin itself.
+    pub fn spawn_area(self, width: f32, height: f32) -> Self {
+        let mut emitter = self;
+        emitter.spawn_area = (width.max(0.0), height.max(0.0));
+        emitter
+    }
+
+    /// Sets the glyphs a particle steps through while it ages, from birth to death.
+    pub fn glyphs(self, text: &str) -> Self {
+        let mut emitter = self;
+        emitter.glyphs = text.chars().collect();
+        if emitter.glyphs.is_empty() {
+            emitter.glyphs.push('*');
+        }
+        emitter
+    }
+
+    /// Colors the particles along `gradient`, from birth to death. Without colors particles
+    /// are drawn in the base style.
+    pub fn colors(self, gradient: Gradient) -> Self {
+        let mut emitter = self;
+        emitter.colors = Some(gradient);
+        emitter
+    }
+
+    /// Seeds the random numbers, e.g. to make an effect repeatable.
+    pub fn seed(self, seed: u64) -> Self {
+        let mut emitter = self;
+        // Xorshift gets stuck at zero.
+        emitter.rng = seed.max(1);
+        emitter
+    }
+
+    /// Moves the emitter; live particles keep flying where they are.
+    pub fn set_origin(&mut self, origin: (f32, f32)) {
+        self.origin = origin;
+    }
+
+    /// Starts or stops spawning particles at the rate. Live particles keep flying.
+    pub fn set_emitting(&mut self, on: bool) {
+        self.emitting = on;
+        self.pending = 0.0;
+    }
+
+    /// Spawns `count` particles at once.
+    pub fn burst(&mut self, count: usize) {
+        for _ in 0..count {
+            self.spawn();
+        }
+    }
+
+    /// Spawns particles at the rate, moves the particles by `dt` and removes the dead ones.
+    ///
+    /// # Returns
+    ///
+    /// `true` if any particle is alive, so the screen needs to be redrawn.
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        let seconds = dt.as_secs_f32();
+        let (lifetime, gravity) = (self.lifetime, self.gravity);
+        self.particles.retain_mut(|particle| {
+            particle.age += dt;
+            particle.vy += gravity * seconds;
+            particle.x += particle.vx * seconds;
+            particle.y += particle.vy * seconds;
+            particle.age < lifetime
+        });
+
+        if self.emitting && self.rate > 0.0 {
+            self.pending += self.rate * seconds;
+            while self.pending >= 1.0 {
+                self.pending -= 1.0;
+                self.spawn();
+            }
+        }
+        !self.particles.is_empty()
+    }
+
+    /// Returns the number of live particles.
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Returns whether no particle is alive.
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Returns the cells of the live particles, rounded; particles left or above the screen
+    /// are left out.
+    pub fn positions(&self) -> Vec<(u16, u16)> {
+        self.particles.iter().filter_map(Self::cell).collect()
+    }
+
+    /// Draws the live particles that are inside `area`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the particles were drawn.
+    /// - An error if writing fails.
+    pub fn draw_in<W: Write>(&self, out: &mut W, area: Rect) -> io::Result<()> {
+        for particle in &self.particles {
+            let Some((x, y)) = Self::cell(particle) else {
+                continue;
+            };
+            if !area.contains(x, y) {
+                continue;
+            }
+            let age = particle.age.as_secs_f32() / self.lifetime.as_secs_f32().max(f32::EPSILON);
+            let glyph =
+                self.glyphs[((age * self.glyphs.len() as f32) as usize).min(self.glyphs.len() - 1)];
+            let style = match &self.colors {
+                Some(gradient) => NyanStyle::new().fg(gradient.color_at(age)),
+                None => NyanStyle::new(),
+            };
+            Cursor::Move(x, y).queue(out)?;
+            widget::write_styled(out, style, glyph.encode_utf8(&mut [0; 4]))?;
+        }
+        out.flush()
+    }
+
+    /// Returns the cell of a particle, or `None` if it is off the screen.
+    fn cell(particle: &Particle) -> Option<(u16, u16)> {
+        let (x, y) = (particle.x.round(), particle.y.round());
+        let range = 0.0..=u16::MAX as f32;
+        (range.contains(&x) && range.contains(&y)).then_some((x as u16, y as u16))
+    }
+
+    /// Spawns a particle.
+    fn spawn(&mut self) {
+        let x = self.origin.0 + self.spawn_area.0 * self.random();
+        let y = self.origin.1 + self.spawn_area.1 * self.random();
+        let vx = self.velocity.0 + self.spread.0 * (self.random() * 2.0 - 1.0);
+        let vy = self.velocity.1 + self.spread.1 * (self.random() * 2.0 - 1.0);
+        self.particles.push(Particle {
+            x,
+            y,
+            vx,
+            vy,
+            age: Duration::ZERO,
+        });
+    }
+
+    /// Returns a random number in `0.0..1.0` (xorshift).
+    fn random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
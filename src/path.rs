@@ -0,0 +1,167 @@
+//! This module provides `Path`, a route through the screen an object can follow, for cutscenes, demos and enemy movement patterns.
+//!
+//! A path is either a list of waypoints, walked at an even speed along its straight segments, or a parametric curve: a function from the progress `t` (`0.0` at the start, `1.0` at the end) to a point. Paths are followed with [`Animations::follow`](crate::animation::Animations::follow), which moves an object along a path over a duration; positions are in cells, and the point of the path is where the top-left corner of the object goes.
+//!
+//! # Structs
+//!
+//! - `Path`: Waypoints or a curve, and the point at any progress along it.
+//!
+//! # Methods
+//!
+//! - `waypoints(points)`: Creates a path along straight segments.
+//! - `curve(f)`: Creates a path from a parametric curve.
+//! - `bezier(start, control1, control2, end)`: Creates a cubic Bézier curve.
+//! - `ellipse(center, radii)`: Creates a full turn around an ellipse, e.g. to circle an object.
+//! - `point_at(t)`: Returns the point at a progress.
+//! - `is_empty()`: Returns whether the path has no points.
+
+use std::f32::consts::TAU;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// The shape of a path.
+#[derive(Clone)]
+enum Shape {
+    /// The waypoints and the distance along the path to each of them.
+    Waypoints(Vec<((f32, f32), f32)>),
+    Curve(Rc<dyn Fn(f32) -> (f32, f32)>),
+}
+
+/// `Path` is a route of waypoints or a parametric curve.
+///
+/// # Example
+/// ```rust
+/// use nyan::path::Path;
+///
+/// // 10 cells right, then 10 cells down: halfway is the corner.
+/// let path = Path::waypoints([(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+/// assert_eq!(path.point_at(0.5), Some((10.0, 0.0)));
+/// assert_eq!(path.point_at(0.75), Some((10.0, 5.0)));
+///
+/// let wave = Path::curve(|t| (t * 40.0, 10.0 + (t * 12.0).sin() * 3.0));
+/// assert_eq!(wave.point_at(0.0), Some((0.0, 10.0)));
+/// ```
+#[derive(Clone)]
+pub struct Path {
+    shape: Shape,
+}
+
+impl Debug for Path {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.shape {
+            Shape::Waypoints(points) => {
+                let points: Vec<_> = points.iter().map(|(point, _)| point).collect();
+                fmt.debug_tuple("Waypoints").field(&points).finish()
+            }
+            Shape::Curve(_) => fmt.write_str("Curve(<fn>)"),
+        }
+    }
+}
+
+impl PartialEq for Path {
+    /// Paths of waypoints are equal if their waypoints are; curves only if they are the same
+    /// function, shared by clones of one path.
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.shape, &other.shape) {
+            (Shape::Waypoints(a), Shape::Waypoints(b)) => a == b,
+            (Shape::Curve(a), Shape::Curve(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Path {
+    /// Creates a path through `points`, along straight segments. Followed over a duration, the
+    /// path is walked at an even speed, so longer segments take longer.
+    pub fn waypoints<I: IntoIterator<Item = (f32, f32)>>(points: I) -> Self {
+        let mut distance = 0.0;
+        let mut previous: Option<(f32, f32)> = None;
+        let points = points
+            .into_iter()
+            .map(|point| {
+                if let Some((x, y)) = previous {
+                    distance += ((point.0 - x).powi(2) + (point.1 - y).powi(2)).sqrt();
+                }
+                previous = Some(point);
+                (point, distance)
+            })
+            .collect();
+        Self {
+            shape: Shape::Waypoints(points),
+        }
+    }
+
+    /// Creates a path from a parametric curve, called with the progress from `0.0` to `1.0`.
+    /// Easing curves that overshoot call it slightly outside of that range.
+    pub fn curve<F: Fn(f32) -> (f32, f32) + 'static>(f: F) -> Self {
+        Self {
+            shape: Shape::Curve(Rc::new(f)),
+        }
+    }
+
+    /// Creates a cubic Bézier curve from `start` to `end`, pulled towards the two control points.
+    pub fn bezier(
+        start: (f32, f32),
+        control1: (f32, f32),
+        control2: (f32, f32),
+        end: (f32, f32),
+    ) -> Self {
+        Self::curve(move |t| {
+            let u = 1.0 - t;
+            let weights = [u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t];
+            let points = [start, control1, control2, end];
+            points
+                .iter()
+                .zip(weights)
+                .fold((0.0, 0.0), |(x, y), (point, weight)| {
+                    (x + point.0 * weight, y + point.1 * weight)
+                })
+        })
+    }
+
+    /// Creates a full clockwise turn around an ellipse with the radii `(rx, ry)`, starting and
+    /// ending to the right of `center`. Cells are about twice as tall as wide, so
+    /// `(2.0 * r, r)` looks round.
+    pub fn ellipse(center: (f32, f32), radii: (f32, f32)) -> Self {
+        Self::curve(move |t| {
+            let angle = t * TAU;
+            (
+                center.0 + radii.0 * angle.cos(),
+                center.1 + radii.1 * angle.sin(),
+            )
+        })
+    }
+
+    /// Returns the point at the progress `t`. Paths of waypoints clamp `t` to `0.0..=1.0`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(point)` with the point on the path.
+    /// - `None` if the path has no waypoints.
+    pub fn point_at(&self, t: f32) -> Option<(f32, f32)> {
+        let points = match &self.shape {
+            Shape::Curve(f) => return Some(f(t)),
+            Shape::Waypoints(points) => points,
+        };
+        let &(last, total) = points.last()?;
+        let distance = t.clamp(0.0, 1.0) * total;
+        let index = points.partition_point(|(_, at)| *at < distance);
+        if index == 0 {
+            return Some(points[0].0);
+        }
+        let Some(&(to, to_at)) = points.get(index) else {
+            return Some(last);
+        };
+        let (from, from_at) = points[index - 1];
+        let part = (distance - from_at) / (to_at - from_at);
+        Some((
+            from.0 + (to.0 - from.0) * part,
+            from.1 + (to.1 - from.1) * part,
+        ))
+    }
+
+    /// Returns whether the path has no points, i.e. is made of no waypoints.
+    pub fn is_empty(&self) -> bool {
+        matches!(&self.shape, Shape::Waypoints(points) if points.is_empty())
+    }
+}
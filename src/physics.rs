@@ -0,0 +1,159 @@
+//! This module provides `Body`, the velocity and acceleration an object of a [`NyanObj`](crate::nyan_obj::NyanObj) moves with, and `Edge`, what happens when it reaches the edge of its bounds.
+//!
+//! Attach a body to an object with [`NyanObj::set_body`](crate::nyan_obj::NyanObj::set_body); [`NyanObj::advance`](crate::nyan_obj::NyanObj::advance) then moves it every frame by the time that passed. Positions are tracked with sub-cell precision, so slow objects move smoothly from cell to cell. Velocities are in cells per second and accelerations in cells per second squared; positive `y` is down.
+//!
+//! # Structs
+//!
+//! - `Body`: The velocity, acceleration and bounds of a moving object.
+//!
+//! # Enums
+//!
+//! - `Edge`: What an object does at the edge of its bounds: nothing, stop, wrap around or bounce.
+//!
+//! # Methods
+//!
+//! - `new(vx, vy)`: Creates a body moving at a constant velocity.
+//! - `acceleration(ax, ay)`: Sets the acceleration, e.g. gravity.
+//! - `bounded(bounds, edge)`: Keeps the object inside a region.
+//! - `step(position, size, dt)`: Integrates a position by `dt`.
+
+use std::time::Duration;
+
+use crate::rect::Rect;
+
+/// The `Edge` enum tells what a [`Body`] does at the edge of its bounds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edge {
+    /// Moves on past the edge.
+    #[default]
+    Pass,
+    /// Stops at the edge, losing the velocity towards it.
+    Clamp,
+    /// Comes back in on the opposite side.
+    Wrap,
+    /// Bounces off the edge, reversing the velocity towards it.
+    Bounce,
+}
+
+/// `Body` moves an object with a velocity and an acceleration.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+/// use nyan::physics::{Body, Edge};
+/// use nyan::rect::Rect;
+///
+/// let mut obj = NyanObj::new();
+/// obj.add_object("ball", Objects::new_text("o"), (8, 0));
+/// let screen = Rect::new(0, 0, 10, 5);
+/// obj.set_body("ball", Body::new(4.0, 0.0).bounded(screen, Edge::Bounce)).unwrap();
+///
+/// // 8 + 4 = 12 is past the last column (9), so the ball bounces back to 6.
+/// obj.advance(Duration::from_secs(1));
+/// assert_eq!(obj.coordinate("ball"), Some((6, 0)));
+/// assert_eq!(obj.body("ball").unwrap().velocity, (-4.0, 0.0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Body {
+    /// The velocity `(vx, vy)` in cells per second.
+    pub velocity: (f32, f32),
+    /// The acceleration `(ax, ay)` in cells per second squared.
+    pub acceleration: (f32, f32),
+    /// The region the object's box is kept in, if any.
+    pub bounds: Option<Rect>,
+    /// What happens at the edges of `bounds`.
+    pub edge: Edge,
+}
+
+impl Body {
+    /// Creates a body moving at `(vx, vy)` cells per second, without acceleration or bounds.
+    pub fn new(vx: f32, vy: f32) -> Self {
+        Self {
+            velocity: (vx, vy),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the acceleration in cells per second squared, e.g. `(0.0, 20.0)` for gravity.
+    pub fn acceleration(self, ax: f32, ay: f32) -> Self {
+        let mut body = self;
+        body.acceleration = (ax, ay);
+        body
+    }
+
+    /// Keeps the object's box inside `bounds`, doing `edge` at its edges.
+    pub fn bounded(self, bounds: Rect, edge: Edge) -> Self {
+        let mut body = self;
+        body.bounds = Some(bounds);
+        body.edge = edge;
+        body
+    }
+
+    /// Integrates `position`, the top-left corner of a box of `size`, by `dt`: the velocity
+    /// changes by the acceleration first, then moves the position, then the edges apply.
+    ///
+    /// # Returns
+    ///
+    /// The new position.
+    pub fn step(&mut self, position: (f32, f32), size: (u16, u16), dt: Duration) -> (f32, f32) {
+        let seconds = dt.as_secs_f32();
+        self.velocity.0 += self.acceleration.0 * seconds;
+        self.velocity.1 += self.acceleration.1 * seconds;
+        let mut x = position.0 + self.velocity.0 * seconds;
+        let mut y = position.1 + self.velocity.1 * seconds;
+
+        if let Some(bounds) = self.bounds {
+            x = self.edge_axis(x, bounds.x, bounds.width, size.0, Axis::X);
+            y = self.edge_axis(y, bounds.y, bounds.height, size.1, Axis::Y);
+        }
+        (x, y)
+    }
+
+    /// Applies the edge behavior on one axis, for a box of `size` cells in `start..start + len`.
+    fn edge_axis(&mut self, pos: f32, start: u16, len: u16, size: u16, axis: Axis) -> f32 {
+        let min = start as f32;
+        // The last position that keeps the whole box inside.
+        let max = (start as f32 + len as f32 - size as f32).max(min);
+        let velocity = match axis {
+            Axis::X => &mut self.velocity.0,
+            Axis::Y => &mut self.velocity.1,
+        };
+        match self.edge {
+            Edge::Pass => pos,
+            Edge::Clamp => {
+                if (pos < min && *velocity < 0.0) || (pos > max && *velocity > 0.0) {
+                    *velocity = 0.0;
+                }
+                pos.clamp(min, max)
+            }
+            Edge::Wrap => {
+                let span = len.max(1) as f32;
+                min + (pos - min).rem_euclid(span)
+            }
+            Edge::Bounce => {
+                if pos < min {
+                    *velocity = velocity.abs();
+                    (min + (min - pos)).min(max)
+                } else if pos > max {
+                    *velocity = -velocity.abs();
+                    (max - (pos - max)).max(min)
+                } else {
+                    pos
+                }
+            }
+        }
+    }
+}
+
+/// The axis an edge is on.
+#[derive(Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+}
@@ -0,0 +1,190 @@
+//! This module provides the `ObjectRenderer` trait and a registry of renderers, so other crates can add kinds of objects to nyan.
+//!
+//! A crate that provides a widget, e.g. a gauge, implements `ObjectRenderer` for it and registers the renderer under a kind name. Objects of that kind are created with [`Objects::new_custom`](crate::objects::Objects::new_custom) from the kind and their `Props`, the named values the renderer reads (`value = "0.4"`), and are stored, placed, styled and drawn by a `NyanObj` like any other object. The registry is global, so a widget crate can register its kinds once, e.g. from an `init()` function the application calls at startup.
+//!
+//! # Traits
+//!
+//! - `ObjectRenderer`: Turns the props of an object of one kind into the lines it shows.
+//!
+//! # Structs
+//!
+//! - `Props`: The named values of a custom object.
+//!
+//! # Functions
+//!
+//! - `register(kind, renderer)` / `unregister(kind)`: Add / remove the renderer of a kind.
+//! - `renderer(kind)` / `is_registered(kind)`: Look up the renderer of a kind.
+//!
+//! # Methods
+//!
+//! - `Props::new()` / `with(name, value)`: Create props / add a value.
+//! - `Props::get(name)` / `parse(name)` / `set(name, value)`: Read, read as a number or other type, and change a value.
+
+use std::borrow::Cow;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use unicode_width::UnicodeWidthStr;
+
+/// `ObjectRenderer` draws the objects of one kind, registered with [`register`].
+///
+/// Only [`ObjectRenderer::lines`] is required. The lines are drawn below each other at the
+/// position of the object, padded to its width, in the style of the object.
+///
+/// # Example
+/// ```rust
+/// use nyan::objects::Objects;
+/// use nyan::plugin::{self, ObjectRenderer, Props};
+///
+/// /// A horizontal bar filled to `value` (0.0 to 1.0) of its `width`.
+/// struct Gauge;
+///
+/// impl ObjectRenderer for Gauge {
+///     fn lines(&self, props: &Props) -> Vec<String> {
+///         let width = props.parse::<usize>("width").unwrap_or(10);
+///         let value = props.parse::<f64>("value").unwrap_or(0.0).clamp(0.0, 1.0);
+///         let filled = (value * width as f64).round() as usize;
+///         vec![format!("{}{}", "#".repeat(filled), "-".repeat(width - filled))]
+///     }
+/// }
+///
+/// plugin::register("gauge", Gauge);
+///
+/// let gauge = Objects::new_custom("gauge", Props::new().with("value", "0.4"));
+/// assert_eq!(gauge.size(), (10, 1));
+///
+/// let mut out = Vec::new();
+/// gauge.draw(&mut out).unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap(), "####------");
+/// ```
+pub trait ObjectRenderer: Send + Sync {
+    /// Returns the lines an object with `props` shows, from the top.
+    fn lines(&self, props: &Props) -> Vec<String>;
+
+    /// Returns the size `(width, height)` in cells of an object with `props`. By default, the
+    /// width of its widest line and the number of its lines.
+    fn size(&self, props: &Props) -> (u16, u16) {
+        let lines = self.lines(props);
+        let width = lines.iter().map(|line| line.width()).max().unwrap_or(0);
+        (
+            width.min(u16::MAX as usize) as u16,
+            lines.len().min(u16::MAX as usize) as u16,
+        )
+    }
+}
+
+/// The renderers of the custom kinds, by kind.
+static RENDERERS: Mutex<Vec<(String, Arc<dyn ObjectRenderer>)>> = Mutex::new(Vec::new());
+
+/// Registers `renderer` for the objects of `kind`, replacing the renderer registered for it
+/// before, if any.
+pub fn register<R: ObjectRenderer + 'static>(kind: &str, renderer: R) {
+    let renderer: Arc<dyn ObjectRenderer> = Arc::new(renderer);
+    let mut renderers = match RENDERERS.lock() {
+        Ok(renderers) => renderers,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    match renderers.iter_mut().find(|(name, _)| name == kind) {
+        Some((_, registered)) => *registered = renderer,
+        None => renderers.push((kind.to_string(), renderer)),
+    }
+}
+
+/// Removes the renderer of `kind`. Objects of the kind are not drawn afterwards.
+///
+/// # Returns
+/// `true` if a renderer was registered for the kind.
+pub fn unregister(kind: &str) -> bool {
+    let mut renderers = match RENDERERS.lock() {
+        Ok(renderers) => renderers,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let before = renderers.len();
+    renderers.retain(|(name, _)| name != kind);
+    renderers.len() != before
+}
+
+/// Returns the renderer registered for `kind`.
+pub fn renderer(kind: &str) -> Option<Arc<dyn ObjectRenderer>> {
+    let renderers = match RENDERERS.lock() {
+        Ok(renderers) => renderers,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    renderers
+        .iter()
+        .find(|(name, _)| name == kind)
+        .map(|(_, renderer)| Arc::clone(renderer))
+}
+
+/// Returns whether a renderer is registered for `kind`.
+pub fn is_registered(kind: &str) -> bool {
+    renderer(kind).is_some()
+}
+
+/// `Props` holds the named values of a custom object, in the order they were added. Values
+/// are text, so they can come from a file as well as from code; [`Props::parse`] reads them
+/// as numbers or other types.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Props<'a> {
+    values: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+}
+
+impl<'a> Props<'a> {
+    /// Creates props without values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value `name`.
+    ///
+    /// # Returns
+    /// The props with the value set.
+    pub fn with<N: Into<Cow<'a, str>>, V: Into<Cow<'a, str>>>(self, name: N, value: V) -> Self {
+        let mut props = self;
+        props.set(name, value);
+        props
+    }
+
+    /// Sets the value `name`, replacing its previous value.
+    pub fn set<N: Into<Cow<'a, str>>, V: Into<Cow<'a, str>>>(&mut self, name: N, value: V) {
+        let name = name.into();
+        let value = value.into();
+        match self.values.iter_mut().find(|(known, _)| *known == name) {
+            Some((_, old)) => *old = value,
+            None => self.values.push((name, value)),
+        }
+    }
+
+    /// Returns the value `name`, or `None` if it is not set.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(known, _)| known == name)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Returns the value `name` parsed as a `T`, e.g. a number, or `None` if it is not set or
+    /// does not parse.
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name).and_then(|value| value.parse().ok())
+    }
+
+    /// Returns the names and values, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_ref(), value.as_ref()))
+    }
+
+    /// Returns the bytes owned by the names and values, for
+    /// [`SceneStats`](crate::stats::SceneStats).
+    pub(crate) fn heap_size(&self) -> usize {
+        self.values.capacity() * std::mem::size_of::<(Cow<str>, Cow<str>)>()
+            + self
+                .values
+                .iter()
+                .map(|(name, value)| crate::stats::owned(name) + crate::stats::owned(value))
+                .sum::<usize>()
+    }
+}
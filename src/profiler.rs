@@ -0,0 +1,135 @@
+//! This module provides `Profiler`, which measures where the time of every frame goes, to find what makes an app slow.
+//!
+//! A frame is split into four phases: the time between two frames, where an app polls for input and updates its state (`input`); the drawing closure passed to [`App::draw_with`](crate::app::App::draw_with) (`closure`); setting up the terminal and diffing the buffer (`render`); and writing the frame to the terminal (`flush`). An `App` built with [`App::profile_frames`](crate::app::App::profile_frames) records the phases of every frame, available from [`App::profile`](crate::app::App::profile). Besides the last frame, a profiler keeps the average and the slowest frame, and `summary()` formats them for a status line or a debug overlay.
+//!
+//! # Structs
+//!
+//! - `Profiler`: The timings of the last frame, their average and the slowest frame.
+//! - `FrameTimings`: The time spent in each phase of a frame.
+//!
+//! # Methods
+//!
+//! - `Profiler::new()`: Creates a profiler without frames.
+//! - `record(timings)`: Adds the timings of a frame.
+//! - `last()` / `average()` / `worst()` / `frames()`: Query the timings.
+//! - `reset()`: Forgets every frame.
+//! - `summary()`: Formats the average timings in one line.
+//! - `FrameTimings::total()`: Returns the time of a whole frame.
+
+use std::time::Duration;
+
+/// `FrameTimings` holds the time spent in each phase of a frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FrameTimings {
+    /// The time between the end of the previous frame and the start of this one, where the
+    /// app polls for input and updates its state.
+    pub input: Duration,
+    /// The time spent in the drawing closure.
+    pub closure: Duration,
+    /// The time spent setting up the terminal and diffing the buffer.
+    pub render: Duration,
+    /// The time spent writing the frame to the terminal.
+    pub flush: Duration,
+}
+
+impl FrameTimings {
+    /// Returns the time of the whole frame, all phases together.
+    pub fn total(&self) -> Duration {
+        self.input + self.closure + self.render + self.flush
+    }
+}
+
+/// `Profiler` keeps the timings of the frames of an app.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::profiler::{FrameTimings, Profiler};
+///
+/// let ms = Duration::from_millis;
+/// let mut profiler = Profiler::new();
+/// profiler.record(FrameTimings { input: ms(2), closure: ms(8), render: ms(1), flush: ms(1) });
+/// profiler.record(FrameTimings { input: ms(2), closure: ms(4), render: ms(1), flush: ms(1) });
+///
+/// assert_eq!(profiler.frames(), 2);
+/// assert_eq!(profiler.last().unwrap().closure, ms(4));
+/// assert_eq!(profiler.worst().unwrap().total(), ms(12));
+/// assert_eq!(profiler.average().unwrap().closure, ms(6));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profiler {
+    last: Option<FrameTimings>,
+    worst: Option<FrameTimings>,
+    /// The sum of the timings of every frame, for the average.
+    sum: FrameTimings,
+    frames: u32,
+}
+
+impl Profiler {
+    /// Creates a profiler without frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the timings of a frame.
+    pub fn record(&mut self, timings: FrameTimings) {
+        self.last = Some(timings);
+        match self.worst {
+            Some(worst) if worst.total() >= timings.total() => {}
+            _ => self.worst = Some(timings),
+        }
+        self.sum.input += timings.input;
+        self.sum.closure += timings.closure;
+        self.sum.render += timings.render;
+        self.sum.flush += timings.flush;
+        self.frames += 1;
+    }
+
+    /// Returns the timings of the last frame, or `None` before the first one.
+    pub fn last(&self) -> Option<FrameTimings> {
+        self.last
+    }
+
+    /// Returns the timings of the slowest frame, or `None` before the first one.
+    pub fn worst(&self) -> Option<FrameTimings> {
+        self.worst
+    }
+
+    /// Returns the average timings of the frames, or `None` before the first one.
+    pub fn average(&self) -> Option<FrameTimings> {
+        if self.frames == 0 {
+            return None;
+        }
+        Some(FrameTimings {
+            input: self.sum.input / self.frames,
+            closure: self.sum.closure / self.frames,
+            render: self.sum.render / self.frames,
+            flush: self.sum.flush / self.frames,
+        })
+    }
+
+    /// Returns the number of frames recorded.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Forgets every frame, e.g. after loading a level, so the average is not skewed by it.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the average timings in one line, in milliseconds, e.g.
+    /// `"input 2.0ms  draw 6.0ms  render 1.0ms  flush 1.0ms"`.
+    pub fn summary(&self) -> String {
+        let average = self.average().unwrap_or_default();
+        let ms = |phase: Duration| phase.as_secs_f64() * 1000.0;
+        format!(
+            "input {:.1}ms  draw {:.1}ms  render {:.1}ms  flush {:.1}ms",
+            ms(average.input),
+            ms(average.closure),
+            ms(average.render),
+            ms(average.flush)
+        )
+    }
+}
@@ -0,0 +1,171 @@
+//! This module defines the `Rect` struct, a rectangular region of terminal cells.
+//!
+//! `Rect` describes where an object is drawn and how much space it occupies. It is used for hit-testing mouse positions against objects.
+//!
+//! # Structs
+//!
+//! - `Rect`: A rectangle given by its top-left corner `(x, y)` and its size `(width, height)` in cells.
+//! - `Edges`: A number of cells on each side of a rectangle, used for padding and margins.
+//!
+//! # Methods
+//!
+//! - `new(x, y, width, height)`: Creates a new `Rect`.
+//! - `terminal()`: Returns a `Rect` covering the whole terminal.
+//! - `contains(x, y)`: Returns whether the cell `(x, y)` lies inside the rectangle.
+//! - `intersects(other)`: Returns whether two rectangles overlap.
+//! - `right()` / `bottom()`: Return the column / row just past the rectangle.
+//! - `inner(edges)` / `outer(edges)`: Shrink / grow the rectangle by the given edges.
+
+/// A rectangular region of terminal cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    /// The column of the top-left corner.
+    pub x: u16,
+    /// The row of the top-left corner.
+    pub y: u16,
+    /// The width in cells.
+    pub width: u16,
+    /// The height in cells.
+    pub height: u16,
+}
+
+impl Rect {
+    /// Creates a new `Rect`.
+    ///
+    /// # Arguments
+    /// * `x` - The column of the top-left corner.
+    /// * `y` - The row of the top-left corner.
+    /// * `width` - The width in cells.
+    /// * `height` - The height in cells.
+    pub fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns a rectangle covering the whole terminal, based on its current size.
+    ///
+    /// # Returns
+    /// * `Ok(Rect)` starting at `(0, 0)` with the terminal's size.
+    /// * `Err(std::io::Error)` if the size cannot be queried.
+    pub fn terminal() -> std::io::Result<Self> {
+        let (width, height) = crossterm::terminal::size()?;
+        Ok(Self::new(0, 0, width, height))
+    }
+
+    /// Returns the column just past the right edge of the rectangle.
+    pub fn right(&self) -> u16 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// Returns the row just past the bottom edge of the rectangle.
+    pub fn bottom(&self) -> u16 {
+        self.y.saturating_add(self.height)
+    }
+
+    /// Returns whether the rectangle covers no cells.
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns whether the cell `(x, y)` lies inside the rectangle.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Returns the rectangle shrunk by `edges` on each side, e.g. the content area inside a
+    /// padding. The result is empty if the edges do not fit.
+    pub fn inner(&self, edges: Edges) -> Self {
+        let width = self.width.saturating_sub(edges.horizontal());
+        let height = self.height.saturating_sub(edges.vertical());
+        Self::new(
+            self.x.saturating_add(edges.left.min(self.width)),
+            self.y.saturating_add(edges.top.min(self.height)),
+            width,
+            height,
+        )
+    }
+
+    /// Returns the rectangle grown by `edges` on each side, e.g. the area taken up including a
+    /// margin.
+    pub fn outer(&self, edges: Edges) -> Self {
+        Self::new(
+            self.x.saturating_sub(edges.left),
+            self.y.saturating_sub(edges.top),
+            self.width.saturating_add(edges.horizontal()),
+            self.height.saturating_add(edges.vertical()),
+        )
+    }
+
+    /// Returns whether this rectangle and `other` share at least one cell.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.x < other.right()
+            && other.x < self.right()
+            && self.y < other.bottom()
+            && other.y < self.bottom()
+    }
+}
+
+/// A number of cells on each side of a rectangle, used for padding and margins.
+///
+/// # Example
+/// ```rust
+/// use nyan::rect::{Edges, Rect};
+///
+/// let area = Rect::new(0, 0, 20, 10);
+/// assert_eq!(area.inner(Edges::symmetric(1, 2)), Rect::new(2, 1, 16, 8));
+/// assert_eq!(Edges::all(1).horizontal(), 2);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Edges {
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+    pub left: u16,
+}
+
+impl Edges {
+    /// Creates edges with a size for each side, clockwise from the top (like CSS).
+    pub fn new(top: u16, right: u16, bottom: u16, left: u16) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Creates edges that are `cells` wide on every side.
+    pub fn all(cells: u16) -> Self {
+        Self::new(cells, cells, cells, cells)
+    }
+
+    /// Creates edges that are `vertical` cells on the top and bottom and `horizontal` cells on
+    /// the left and right.
+    pub fn symmetric(vertical: u16, horizontal: u16) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
+
+    /// Returns the cells taken on the left and right together.
+    pub fn horizontal(&self) -> u16 {
+        self.left.saturating_add(self.right)
+    }
+
+    /// Returns the cells taken on the top and bottom together.
+    pub fn vertical(&self) -> u16 {
+        self.top.saturating_add(self.bottom)
+    }
+
+    /// Returns whether every side is zero cells.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
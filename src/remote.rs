@@ -0,0 +1,589 @@
+//! This module provides remote sessions, which present a nyan UI to a client connected over a socket, like a telnet or SSH server does, instead of to the terminal of the process.
+//!
+//! A session is a `RemoteBackend`, which an [`App`](crate::app::App) draws to with [`App::with_backend`](crate::app::App::with_backend), and a `RemoteInput`, which reads the keys, mouse actions, pastes and window size changes the client's terminal sends. Both work on any byte stream: a `TcpStream`, an SSH channel or a pipe. Reading happens on a thread of its own, like the input thread of an [`EventQueue`](crate::event::EventQueue), so a server process keeps drawing while it waits for the client.
+//!
+//! The clipboard contents a terminal sends in answer to [`App::request_clipboard`](crate::app::App::request_clipboard) are reported as `NyanEvent::Paste`.
+//!
+//! The client's terminal decides how it sends its size. Telnet clients report it through the NAWS option, which `RemoteBackend::negotiate_telnet` asks for; other clients can be asked with `RemoteBackend::request_size`. Until one of them answers, the size passed when the session was created is used.
+//!
+//! # Structs
+//!
+//! - `RemoteBackend`: Draws to the client and knows the size of its terminal.
+//! - `RemoteInput`: Reads the events of the client on a thread.
+//!
+//! # Functions
+//!
+//! - `session(reader, writer, width, height)`: Starts a session on a reader and a writer.
+//! - `tcp_session(stream, width, height)`: Starts a session on a `TcpStream`.
+//!
+//! # Methods
+//!
+//! - `RemoteBackend::negotiate_telnet()`: Puts a telnet client in character mode and asks for its window size.
+//! - `RemoteBackend::request_size()`: Asks an xterm-compatible client for its window size.
+//! - `RemoteInput::get_event()` / `drain_events()`: Return the next / every pending event of the client.
+//! - `RemoteInput::is_connected()`: Returns whether the client is still connected.
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::backend::Backend;
+use crate::clipboard;
+use crate::errors::{NyanError, NyanResult};
+use crate::event::{EventSource, NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::{NyanInput, NyanKey};
+
+/// The most bytes kept of a sequence whose end was not read yet, e.g. a clipboard reply or a
+/// bracketed paste. Clipboards can be large, but not endless.
+const MAX_SEQUENCE: usize = 1 << 20;
+
+/// The largest width and height a client can report. Clients could otherwise make a buffered
+/// `App` allocate a screen of 65535 × 65535 cells.
+const MAX_SIZE: u16 = 1000;
+
+/// Telnet: "interpret as command".
+const IAC: u8 = 255;
+/// Telnet: the start and the end of a subnegotiation.
+const SB: u8 = 250;
+const SE: u8 = 240;
+/// Telnet: the option of the window size (RFC 1073).
+const NAWS: u8 = 31;
+
+/// Returns the size, even if a thread panicked while changing it.
+fn lock(size: &Mutex<(u16, u16)>) -> (u16, u16) {
+    match size.lock() {
+        Ok(size) => *size,
+        Err(poisoned) => *poisoned.into_inner(),
+    }
+}
+
+/// `RemoteBackend` draws to the client of a remote session.
+///
+/// The size of the client's terminal is updated by the [`RemoteInput`] of the session whenever
+/// the client reports it, so the `App` follows resizes of the remote window.
+#[derive(Debug)]
+pub struct RemoteBackend<W: Write + Debug> {
+    writer: W,
+    size: Arc<Mutex<(u16, u16)>>,
+}
+
+impl<W: Write + Debug> RemoteBackend<W> {
+    /// Asks a telnet client to leave the echo to the server, to send every key right away
+    /// instead of whole lines, and to report its window size.
+    pub fn negotiate_telnet(&mut self) -> io::Result<()> {
+        const WILL: u8 = 251;
+        const DO: u8 = 253;
+        const ECHO: u8 = 1;
+        const SUPPRESS_GO_AHEAD: u8 = 3;
+        self.writer
+            .write_all(&[IAC, WILL, ECHO, IAC, WILL, SUPPRESS_GO_AHEAD, IAC, DO, NAWS])?;
+        self.writer.flush()
+    }
+
+    /// Asks the client for the size of its window, which xterm-compatible terminals answer
+    /// with an escape sequence read by the [`RemoteInput`].
+    pub fn request_size(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\x1b[18t")?;
+        self.writer.flush()
+    }
+}
+
+impl<W: Write + Debug> Write for RemoteBackend<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W: Write + Debug> Backend for RemoteBackend<W> {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(lock(&self.size))
+    }
+}
+
+/// `RemoteInput` reads the events of the client of a remote session on a thread.
+///
+/// The thread ends when the client disconnects, as nothing more can be read.
+///
+/// # Example
+/// ```rust
+/// use nyan::event::NyanEvent;
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::remote;
+///
+/// // The client pressed `q` and then the up arrow.
+/// let (_backend, mut input) = remote::session(&b"q\x1b[A"[..], Vec::new(), 80, 24);
+/// assert_eq!(input.get_event().unwrap(), NyanEvent::Key(NyanInput::Key(NyanKey::Q)));
+/// assert_eq!(input.get_event().unwrap(), NyanEvent::Key(NyanInput::UpAllow));
+/// ```
+#[derive(Debug)]
+pub struct RemoteInput {
+    events: Receiver<NyanEvent>,
+    connected: bool,
+}
+
+impl RemoteInput {
+    /// Waits for 16 milliseconds for the next event of the client, like
+    /// [`NyanEvent::get_event`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(NyanEvent)` with the event, or `NyanEvent::Tick` if nothing happened.
+    /// - An error once the client has disconnected and every event was returned.
+    pub fn get_event(&mut self) -> NyanResult<NyanEvent> {
+        match self.events.recv_timeout(Duration::from_millis(16)) {
+            Ok(event) => Ok(event),
+            Err(RecvTimeoutError::Timeout) => Ok(NyanEvent::Tick),
+            Err(RecvTimeoutError::Disconnected) => {
+                self.connected = false;
+                Err(NyanError::Terminal(Cow::Borrowed(
+                    "the remote client has disconnected",
+                )))
+            }
+        }
+    }
+
+    /// Returns every event of the client that is already pending, without waiting.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<NyanEvent>)` with the events in the order they happened.
+    /// - An error once the client has disconnected and every event was returned.
+    pub fn drain_events(&mut self) -> NyanResult<Vec<NyanEvent>> {
+        let mut events = Vec::new();
+        loop {
+            match self.events.try_recv() {
+                Ok(event) => events.push(event),
+                Err(TryRecvError::Empty) => return Ok(events),
+                Err(TryRecvError::Disconnected) if events.is_empty() => {
+                    self.connected = false;
+                    return Err(NyanError::Terminal(Cow::Borrowed(
+                        "the remote client has disconnected",
+                    )));
+                }
+                Err(TryRecvError::Disconnected) => return Ok(events),
+            }
+        }
+    }
+
+    /// Returns whether the client was still connected when the events were last read.
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+}
+
+impl EventSource for RemoteInput {
+    /// Returns the next event of the client, or `None` once it has disconnected.
+    fn next_event(&mut self) -> NyanResult<Option<NyanEvent>> {
+        match self.get_event() {
+            Ok(event) => Ok(Some(event)),
+            Err(_) if !self.connected => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Starts a remote session drawing to `writer` and reading the client's input from `reader`,
+/// e.g. the two halves of a socket. `width` and `height` are the size of the client's terminal
+/// until it reports its own.
+///
+/// # Returns
+///
+/// The backend to create the `App` with, and the input of the client.
+///
+/// # Example
+/// ```ignore
+/// let listener = TcpListener::bind("0.0.0.0:2323")?;
+/// for stream in listener.incoming() {
+///     thread::spawn(move || -> NyanResult<()> {
+///         let (mut backend, mut input) = remote::tcp_session(stream?, 80, 24)?;
+///         backend.negotiate_telnet()?;
+///         let mut app = App::with_backend(30, backend).buffered().clear();
+///         while input.is_connected() {
+///             if let Ok(NyanEvent::Key(NyanInput::Key(NyanKey::Q))) = input.get_event() {
+///                 break;
+///             }
+///             app.draw_with(|app| dashboard.draw_all_in(app).unwrap())?;
+///         }
+///         app.exit()
+///     });
+/// }
+/// ```
+pub fn session<R, W>(
+    reader: R,
+    writer: W,
+    width: u16,
+    height: u16,
+) -> (RemoteBackend<W>, RemoteInput)
+where
+    R: Read + Send + 'static,
+    W: Write + Debug,
+{
+    let size = Arc::new(Mutex::new((width, height)));
+    let (sender, events) = mpsc::channel();
+    let resized = Arc::clone(&size);
+    thread::spawn(move || {
+        let mut reader = reader;
+        let mut decoder = Decoder::default();
+        let mut buf = [0; 1024];
+        // Stop when the client disconnects or the session is dropped.
+        while let Ok(read @ 1..) = reader.read(&mut buf) {
+            let mut gone = false;
+            decoder.feed(&buf[..read], |event| {
+                if let NyanEvent::Resize(width, height) = event {
+                    if let Ok(mut size) = resized.lock() {
+                        *size = (width, height);
+                    }
+                }
+                gone |= sender.send(event).is_err();
+            });
+            if gone {
+                break;
+            }
+        }
+    });
+    (
+        RemoteBackend { writer, size },
+        RemoteInput {
+            events,
+            connected: true,
+        },
+    )
+}
+
+/// Starts a remote session on a `TcpStream` (see [`session`]).
+///
+/// # Returns
+///
+/// - `Ok((RemoteBackend, RemoteInput))` with the backend and the input of the client.
+/// - An error if the stream cannot be split into a reader and a writer.
+pub fn tcp_session(
+    stream: TcpStream,
+    width: u16,
+    height: u16,
+) -> io::Result<(RemoteBackend<TcpStream>, RemoteInput)> {
+    stream.set_nodelay(true)?;
+    let reader = stream.try_clone()?;
+    Ok(session(reader, stream, width, height))
+}
+
+/// The result of parsing the start of the input.
+enum Parsed {
+    /// An event and the number of bytes it took.
+    Event(NyanEvent, usize),
+    /// Bytes without an event, e.g. a telnet command.
+    Skip(usize),
+    /// The start of a sequence whose end was not read yet.
+    Incomplete,
+}
+
+/// Turns the bytes a terminal sends into events.
+#[derive(Debug, Default)]
+pub(crate) struct Decoder {
+    pending: Vec<u8>,
+    /// The text pasted so far, while a bracketed paste is read.
+    paste: Option<Vec<u8>>,
+    /// Whether the last byte was `\r`, which telnet clients follow with `\n` or `\0`.
+    after_cr: bool,
+}
+
+impl Decoder {
+    /// Parses `bytes` after the bytes left over from the last call.
+    pub(crate) fn feed<F: FnMut(NyanEvent)>(&mut self, bytes: &[u8], mut emit: F) {
+        self.pending.extend_from_slice(bytes);
+        let mut start = 0;
+        while start < self.pending.len() {
+            let rest = &self.pending[start..];
+            if let Some(paste) = &mut self.paste {
+                const END: &[u8] = b"\x1b[201~";
+                match rest.windows(END.len()).position(|window| window == END) {
+                    Some(end) => {
+                        paste.extend_from_slice(&rest[..end]);
+                        let text = String::from_utf8_lossy(paste).into_owned();
+                        emit(NyanEvent::Paste(text));
+                        self.paste = None;
+                        start += end + END.len();
+                        continue;
+                    }
+                    None => {
+                        // Keep what may be the start of the end marker.
+                        let keep = rest.len().min(END.len() - 1);
+                        paste.extend_from_slice(&rest[..rest.len() - keep]);
+                        start += rest.len() - keep;
+                        // Report what was pasted so far instead of holding an endless paste.
+                        if paste.len() >= MAX_SEQUENCE {
+                            // Up to the last whole character, which the rest may end.
+                            let whole = match std::str::from_utf8(paste) {
+                                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                                _ => paste.len(),
+                            };
+                            let text = String::from_utf8_lossy(&paste[..whole]).into_owned();
+                            emit(NyanEvent::Paste(text));
+                            paste.drain(..whole);
+                        }
+                        break;
+                    }
+                }
+            }
+            if self.after_cr && matches!(rest[0], b'\n' | 0) {
+                self.after_cr = false;
+                start += 1;
+                continue;
+            }
+            self.after_cr = rest[0] == b'\r';
+            match parse(rest) {
+                Parsed::Event(event, len) => {
+                    emit(event);
+                    start += len;
+                }
+                Parsed::Skip(len) => start += len,
+                // A lone Esc is only known to be one when nothing follows it.
+                Parsed::Incomplete if rest == b"\x1b" => {
+                    emit(NyanEvent::Key(NyanInput::Esc));
+                    start += 1;
+                }
+                Parsed::Incomplete => break,
+            }
+            if rest.starts_with(b"\x1b[200~") {
+                self.paste = Some(Vec::new());
+            }
+        }
+        self.pending.drain(..start);
+    }
+}
+
+/// Returns the key of a typed character.
+pub(crate) fn key(ch: char) -> NyanInput<'static> {
+    shift_uppercase(NyanInput::Key(NyanKey::from_char(ch)), ch)
+}
+
+/// Returns `input` with Shift if `ch` is an uppercase letter. Shifted letters point to static
+/// inputs, so what a client sends never allocates them.
+fn shift_uppercase(input: NyanInput<'static>, ch: char) -> NyanInput<'static> {
+    match input.shifted() {
+        Some(shifted) if ch.is_ascii_uppercase() => shifted,
+        _ => input,
+    }
+}
+
+/// Parses the event at the start of `bytes`.
+fn parse(bytes: &[u8]) -> Parsed {
+    let input = |input, len| Parsed::Event(NyanEvent::Key(input), len);
+    match bytes[0] {
+        IAC => telnet(bytes),
+        0x1b => escape(bytes),
+        b'\r' | b'\n' => input(NyanInput::Enter, 1),
+        b'\t' => input(NyanInput::Tab, 1),
+        0x7f | 0x08 => input(NyanInput::BackSpace, 1),
+        byte @ 1..=26 => input(
+            NyanInput::Ctrl(NyanKey::from_char((b'a' + byte - 1) as char)),
+            1,
+        ),
+        0 | 0x1c..=0x1f => Parsed::Skip(1),
+        byte => {
+            let len = match byte {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+            if bytes.len() < len {
+                return Parsed::Incomplete;
+            }
+            match std::str::from_utf8(&bytes[..len]) {
+                Ok(text) => match text.chars().next() {
+                    Some(ch) => input(key(ch), len),
+                    None => Parsed::Skip(len),
+                },
+                Err(_) => Parsed::Skip(1),
+            }
+        }
+    }
+}
+
+/// Returns the resize event of a size a client reported, limited to [`MAX_SIZE`].
+fn resize(width: u16, height: u16) -> NyanEvent {
+    NyanEvent::Resize(width.min(MAX_SIZE), height.min(MAX_SIZE))
+}
+
+/// Parses a telnet command, reporting the window size of a NAWS subnegotiation.
+fn telnet(bytes: &[u8]) -> Parsed {
+    match bytes.get(1) {
+        None => Parsed::Incomplete,
+        // An escaped 255, which no terminal sends as text.
+        Some(&IAC) => Parsed::Skip(2),
+        // Subnegotiations are short; give up on endless ones.
+        Some(&SB) => match bytes[..bytes.len().min(256)]
+            .windows(2)
+            .position(|pair| pair == [IAC, SE])
+        {
+            None if bytes.len() < 256 => Parsed::Incomplete,
+            None => Parsed::Skip(2),
+            Some(end) => match &bytes[2..end] {
+                [NAWS, w1, w2, h1, h2] => Parsed::Event(
+                    resize(
+                        u16::from_be_bytes([*w1, *w2]),
+                        u16::from_be_bytes([*h1, *h2]),
+                    ),
+                    end + 2,
+                ),
+                _ => Parsed::Skip(end + 2),
+            },
+        },
+        // WILL, WONT, DO and DONT name an option.
+        Some(251..=254) if bytes.len() < 3 => Parsed::Incomplete,
+        Some(251..=254) => Parsed::Skip(3),
+        Some(_) => Parsed::Skip(2),
+    }
+}
+
+/// Parses a sequence starting with Esc.
+fn escape(bytes: &[u8]) -> Parsed {
+    let input = |input, len| Parsed::Event(NyanEvent::Key(input), len);
+    match bytes.get(1) {
+        None => Parsed::Incomplete,
+        Some(b'[') => csi(bytes),
+        Some(b']') => osc(bytes),
+        Some(b'O') => match bytes.get(2) {
+            None => Parsed::Incomplete,
+            Some(final_byte @ b'P'..=b'S') => input(NyanInput::FunctionKey(final_byte - b'O'), 3),
+            Some(b'A') => input(NyanInput::UpAllow, 3),
+            Some(b'B') => input(NyanInput::DownAllow, 3),
+            Some(b'C') => input(NyanInput::RightAllow, 3),
+            Some(b'D') => input(NyanInput::LeftAllow, 3),
+            Some(b'H') => input(NyanInput::Home, 3),
+            Some(b'F') => input(NyanInput::End, 3),
+            Some(_) => Parsed::Skip(3),
+        },
+        Some(byte) if byte.is_ascii_graphic() => {
+            let ch = *byte as char;
+            input(
+                shift_uppercase(NyanInput::Alt(NyanKey::from_char(ch)), ch),
+                2,
+            )
+        }
+        Some(_) => input(NyanInput::Esc, 1),
+    }
+}
+
+/// Parses an operating system command (`Esc ] ...`), reporting the clipboard the terminal
+/// sends in answer to [`clipboard::REQUEST`] as a paste.
+fn osc(bytes: &[u8]) -> Parsed {
+    let end = bytes
+        .iter()
+        .enumerate()
+        .skip(2)
+        .find_map(|(i, byte)| match byte {
+            0x07 => Some((i, i + 1)),
+            0x1b if bytes.get(i + 1) == Some(&b'\\') => Some((i, i + 2)),
+            _ => None,
+        });
+    let Some((end, len)) = end else {
+        return if bytes.len() < MAX_SEQUENCE {
+            Parsed::Incomplete
+        } else {
+            Parsed::Skip(2)
+        };
+    };
+    let params = String::from_utf8_lossy(&bytes[2..end]);
+    match clipboard::parse_reply(&params) {
+        Some(text) => Parsed::Event(NyanEvent::Paste(text), len),
+        None => Parsed::Skip(len),
+    }
+}
+
+/// Parses a control sequence (`Esc [ ...`).
+fn csi(bytes: &[u8]) -> Parsed {
+    let Some(end) = bytes[2..]
+        .iter()
+        .position(|byte| (0x40..=0x7e).contains(byte))
+    else {
+        // Control sequences are short; give up on endless ones.
+        return if bytes.len() < 32 {
+            Parsed::Incomplete
+        } else {
+            Parsed::Skip(2)
+        };
+    };
+    let len = end + 3;
+    let params = String::from_utf8_lossy(&bytes[2..len - 1]);
+    let numbers: Vec<u16> = params
+        .trim_start_matches('<')
+        .split(';')
+        .map(|number| number.parse().unwrap_or(0))
+        .collect();
+    let shift = numbers.get(1) == Some(&2);
+    let input = |input: NyanInput<'static>| {
+        let input = match input {
+            NyanInput::UpAllow if shift => NyanInput::Shift(&NyanInput::UpAllow),
+            NyanInput::DownAllow if shift => NyanInput::Shift(&NyanInput::DownAllow),
+            NyanInput::LeftAllow if shift => NyanInput::Shift(&NyanInput::LeftAllow),
+            NyanInput::RightAllow if shift => NyanInput::Shift(&NyanInput::RightAllow),
+            input => input,
+        };
+        Parsed::Event(NyanEvent::Key(input), len)
+    };
+    match bytes[len - 1] {
+        b'A' => input(NyanInput::UpAllow),
+        b'B' => input(NyanInput::DownAllow),
+        b'C' => input(NyanInput::RightAllow),
+        b'D' => input(NyanInput::LeftAllow),
+        b'H' => input(NyanInput::Home),
+        b'F' => input(NyanInput::End),
+        b'Z' => input(NyanInput::Shift(&NyanInput::Tab)),
+        b'I' => Parsed::Event(NyanEvent::FocusGained, len),
+        b'O' => Parsed::Event(NyanEvent::FocusLost, len),
+        b'~' => match numbers[0] {
+            1 | 7 => input(NyanInput::Home),
+            2 => input(NyanInput::Insert),
+            3 => input(NyanInput::Delete),
+            4 | 8 => input(NyanInput::End),
+            5 => input(NyanInput::PageUp),
+            6 => input(NyanInput::PageDown),
+            n @ 11..=15 => input(NyanInput::FunctionKey((n - 10) as u8)),
+            n @ 17..=21 => input(NyanInput::FunctionKey((n - 11) as u8)),
+            n @ 23..=24 => input(NyanInput::FunctionKey((n - 12) as u8)),
+            // The start of a bracketed paste, read by the decoder.
+            _ => Parsed::Skip(len),
+        },
+        b't' => match numbers[..] {
+            [8, height, width] => Parsed::Event(resize(width, height), len),
+            _ => Parsed::Skip(len),
+        },
+        final_byte @ (b'M' | b'm') if params.starts_with('<') => match numbers[..] {
+            [button, x, y] => {
+                let mouse = |kind| NyanMouse {
+                    kind,
+                    x: x.saturating_sub(1),
+                    y: y.saturating_sub(1),
+                };
+                let pressed = match button & 3 {
+                    0 => NyanMouseButton::Left,
+                    1 => NyanMouseButton::Middle,
+                    _ => NyanMouseButton::Right,
+                };
+                let kind = match button {
+                    64 => NyanMouseKind::ScrollUp,
+                    65 => NyanMouseKind::ScrollDown,
+                    66 => NyanMouseKind::ScrollLeft,
+                    67 => NyanMouseKind::ScrollRight,
+                    _ if button & 32 != 0 && button & 3 == 3 => NyanMouseKind::Moved,
+                    _ if button & 32 != 0 => NyanMouseKind::Drag(pressed),
+                    _ if final_byte == b'm' => NyanMouseKind::Up(pressed),
+                    _ => NyanMouseKind::Down(pressed),
+                };
+                Parsed::Event(NyanEvent::Mouse(mouse(kind)), len)
+            }
+            _ => Parsed::Skip(len),
+        },
+        _ => Parsed::Skip(len),
+    }
+}
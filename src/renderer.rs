@@ -0,0 +1,136 @@
+//! This module provides `Renderer`, which draws scenes on a thread of its own, so heavy application logic on the main thread cannot make it drop frames.
+//!
+//! The render thread owns the [`App`] and draws the snapshots of the scene it receives over a channel. A snapshot is an immutable copy of a [`NyanObj`], usually a clone of the scene taken once per update. The main thread sends a snapshot whenever the scene changed and goes on with its work; the render thread draws at the FPS of its `App`, always the newest snapshot, and skips those that were replaced before it got to them. Since an `App` is not `Send`, the render thread builds it itself from a closure.
+//!
+//! # Structs
+//!
+//! - `Renderer`: The render thread and the channel feeding it snapshots.
+//!
+//! # Methods
+//!
+//! - `Renderer::spawn(build)`: Starts the render thread with the `App` built by `build`.
+//! - `submit(scene)`: Sends a snapshot of the scene to be drawn.
+//! - `frames()`: Returns the number of frames drawn so far.
+//! - `stop()`: Draws the last snapshot, exits the `App` and waits for the thread.
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use crate::app::App;
+use crate::errors::{NyanError, NyanResult};
+use crate::nyan_obj::NyanObj;
+
+/// `Renderer` draws snapshots of a scene on a render thread.
+///
+/// # Example
+/// ```ignore
+/// let renderer = Renderer::spawn(|| App::new(60).alternate_screen().clear().raw_mode());
+///
+/// let mut obj = NyanObj::new();
+/// obj.add_object("score", Objects::new_text("0"), (0, 0));
+/// loop {
+///     // ... heavy simulation ...
+///     if obj.is_dirty() {
+///         renderer.submit(obj.clone())?;
+///         obj.clear_dirty();
+///     }
+/// }
+/// renderer.stop()?;
+/// ```
+#[derive(Debug)]
+pub struct Renderer {
+    sender: Option<Sender<NyanObj<'static>>>,
+    frames: Arc<AtomicU64>,
+    handle: Option<JoinHandle<NyanResult<()>>>,
+}
+
+impl Renderer {
+    /// Starts a render thread drawing with the `App` returned by `build`, which is called on
+    /// the render thread.
+    ///
+    /// # Returns
+    ///
+    /// A new `Renderer` waiting for the first snapshot.
+    pub fn spawn<F: FnOnce() -> App + Send + 'static>(build: F) -> Self {
+        let (sender, scenes) = mpsc::channel();
+        let frames = Arc::new(AtomicU64::new(0));
+        let drawn = Arc::clone(&frames);
+        let handle = thread::spawn(move || Self::run(build(), &scenes, &drawn));
+        Self {
+            sender: Some(sender),
+            frames,
+            handle: Some(handle),
+        }
+    }
+
+    /// Draws the newest snapshot each frame until the channel is closed.
+    fn run(
+        mut app: App,
+        scenes: &Receiver<NyanObj<'static>>,
+        frames: &AtomicU64,
+    ) -> NyanResult<()> {
+        while let Ok(first) = scenes.recv() {
+            // Snapshots that were replaced while the last frame was drawn are skipped.
+            let scene = scenes.try_iter().last().unwrap_or(first);
+            let mut drawn = Ok(());
+            app.draw_with(|app| drawn = scene.draw_all_in(app))?;
+            drawn?;
+            frames.fetch_add(1, Ordering::Relaxed);
+        }
+        app.exit()
+    }
+
+    /// Sends a snapshot of the scene to the render thread, replacing a snapshot that was not
+    /// drawn yet. This does not wait for the frame to be drawn.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the snapshot was sent.
+    /// - An error if the render thread has stopped, e.g. because drawing failed; `stop()`
+    ///   returns the reason.
+    pub fn submit(&self, scene: NyanObj<'static>) -> NyanResult<()> {
+        match &self.sender {
+            Some(sender) if sender.send(scene).is_ok() => Ok(()),
+            _ => Err(NyanError::DrawFailed(Cow::Borrowed(
+                "the render thread has stopped",
+            ))),
+        }
+    }
+
+    /// Returns the number of frames the render thread has drawn.
+    pub fn frames(&self) -> u64 {
+        self.frames.load(Ordering::Relaxed)
+    }
+
+    /// Draws the snapshot that is still pending, exits the `App` and waits for the render
+    /// thread to finish.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the render thread finished.
+    /// - The error that stopped the render thread, e.g. if drawing failed.
+    pub fn stop(mut self) -> NyanResult<()> {
+        self.finish()
+    }
+
+    /// Closes the channel and joins the render thread.
+    fn finish(&mut self) -> NyanResult<()> {
+        drop(self.sender.take());
+        match self.handle.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(_)) => Err(NyanError::DrawFailed(Cow::Borrowed(
+                "the render thread panicked",
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Renderer {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
@@ -0,0 +1,351 @@
+//! This module provides `SceneStack`, a stack of scenes (menus, levels, pause screens) of which the top one is shown, and `Transition`, an effect played over a number of frames when switching between them.
+//!
+//! Every scene is a [`NyanObj`]. Pushing a scene shows it on top of the previous one, which is kept for when the new one is popped. A switch may play a transition: for the first half of its frames the old scene is covered up, for the second half the new one is uncovered. The built-in transitions dim the scene away (`Fade`), sweep across it from left to right (`Wipe`), close in from both sides (`Curtain`) or cover it cell by random cell (`Dissolve`). The stack draws the right scene and the cover with `draw_in()`, which also moves a running transition on by one frame, so call it once per frame on a screen that is cleared every frame.
+//!
+//! # Structs
+//!
+//! - `SceneStack`: The scenes and the running transition.
+//! - `Transition`: A transition effect, its length in frames and its progress.
+//!
+//! # Enums
+//!
+//! - `TransitionKind`: Fade, wipe, curtain or dissolve.
+//!
+//! # Methods
+//!
+//! - `SceneStack::new()`: Creates an empty stack.
+//! - `push(scene)` / `push_with(scene, transition)`: Show a new scene, at once or with a transition.
+//! - `pop()` / `pop_with(transition)`: Go back to the previous scene.
+//! - `current()` / `current_mut()` / `len()` / `is_empty()` / `is_transitioning()`: Query the stack.
+//! - `draw_in(out, area)`: Draws the shown scene and the transition.
+//! - `Transition::new(kind, frames)` / `fade(frames)` / `wipe(frames)` / `curtain(frames)` / `dissolve(frames)`: Create a transition.
+//! - `style(style)`: Sets the style of the cover, e.g. its background.
+//! - `advance()` / `progress()` / `coverage()` / `dims()` / `is_past_middle()` / `is_finished()`: Drive and query a transition.
+//! - `draw_in(out, area)`: Draws the cover of a transition.
+
+use std::io::{self, Write};
+
+use crate::cursor::Cursor;
+use crate::errors::NyanResult;
+use crate::nyan_obj::NyanObj;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::widget;
+
+/// The `TransitionKind` enum tells how a [`Transition`] covers the screen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TransitionKind {
+    /// Dims the scene, then blanks it.
+    #[default]
+    Fade,
+    /// Covers the screen from left to right, then uncovers it from left to right.
+    Wipe,
+    /// Covers the screen from both sides to the middle, then opens from the middle.
+    Curtain,
+    /// Covers the screen cell by random cell.
+    Dissolve,
+}
+
+/// `Transition` covers the screen over the first half of its frames and uncovers it over
+/// the second half.
+///
+/// # Example
+/// ```rust
+/// use nyan::scene::Transition;
+///
+/// let mut wipe = Transition::wipe(4);
+/// wipe.advance();
+/// assert_eq!(wipe.coverage(), 0.5);
+/// wipe.advance();
+/// assert_eq!(wipe.coverage(), 1.0); // the middle: time to switch scenes
+/// wipe.advance();
+/// wipe.advance();
+/// assert!(wipe.is_finished());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Transition {
+    kind: TransitionKind,
+    frames: u32,
+    frame: u32,
+    style: NyanStyle,
+}
+
+impl Transition {
+    /// Creates a transition of `kind` that runs for `frames` frames (at least one).
+    pub fn new(kind: TransitionKind, frames: u32) -> Self {
+        Self {
+            kind,
+            frames: frames.max(1),
+            frame: 0,
+            style: NyanStyle::new(),
+        }
+    }
+
+    /// Creates a transition dimming the old scene away and the new one in.
+    pub fn fade(frames: u32) -> Self {
+        Self::new(TransitionKind::Fade, frames)
+    }
+
+    /// Creates a transition sweeping across the screen from left to right.
+    pub fn wipe(frames: u32) -> Self {
+        Self::new(TransitionKind::Wipe, frames)
+    }
+
+    /// Creates a transition closing in from both sides and opening from the middle.
+    pub fn curtain(frames: u32) -> Self {
+        Self::new(TransitionKind::Curtain, frames)
+    }
+
+    /// Creates a transition covering the screen cell by random cell.
+    pub fn dissolve(frames: u32) -> Self {
+        Self::new(TransitionKind::Dissolve, frames)
+    }
+
+    /// Sets the style the covered cells are drawn in, e.g. a background color. The cover is
+    /// drawn in the base style by default.
+    pub fn style(self, style: NyanStyle) -> Self {
+        let mut transition = self;
+        transition.style = style;
+        transition
+    }
+
+    /// Moves the transition on by one frame.
+    pub fn advance(&mut self) {
+        self.frame = (self.frame + 1).min(self.frames);
+    }
+
+    /// Returns the progress, from `0.0` at the start to `1.0` at the end.
+    pub fn progress(&self) -> f32 {
+        self.frame as f32 / self.frames as f32
+    }
+
+    /// Returns how much of the screen is covered: `0.0` at the start and the end, `1.0` in
+    /// the middle.
+    pub fn coverage(&self) -> f32 {
+        1.0 - (2.0 * self.progress() - 1.0).abs()
+    }
+
+    /// Returns whether the transition reached its middle, where the new scene is shown.
+    pub fn is_past_middle(&self) -> bool {
+        self.frame * 2 >= self.frames
+    }
+
+    /// Returns whether the transition ran all its frames.
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.frames
+    }
+
+    /// Draws the cover of the current frame over `area`. A fading transition covers nothing
+    /// until it is almost in the middle; before, the scene is drawn dimmed instead (see
+    /// [`Transition::dims`]).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the cover was drawn.
+    /// - An error if writing fails.
+    pub fn draw_in<W: Write>(&self, out: &mut W, area: Rect) -> io::Result<()> {
+        let coverage = self.coverage();
+        let width = area.width as f32;
+        for row in 0..area.height {
+            let y = area.y + row;
+            let mut start = None;
+            for column in 0..=area.width {
+                let covered = column < area.width
+                    && match self.kind {
+                        TransitionKind::Fade => coverage >= 0.8,
+                        TransitionKind::Wipe if self.is_past_middle() => {
+                            column as f32 >= width - coverage * width
+                        }
+                        TransitionKind::Wipe => (column as f32) < coverage * width,
+                        TransitionKind::Curtain => {
+                            let side = coverage * width / 2.0;
+                            (column as f32) < side || column as f32 >= width - side
+                        }
+                        TransitionKind::Dissolve => noise(column, row) < coverage,
+                    };
+                match (covered, start) {
+                    (true, None) => start = Some(column),
+                    (false, Some(from)) => {
+                        Cursor::Move(area.x + from, y).queue(out)?;
+                        let cells = " ".repeat((column - from) as usize);
+                        widget::write_styled(out, self.style, &cells)?;
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        out.flush()
+    }
+
+    /// Returns whether the scene is drawn dimmed in the current frame, which a fading
+    /// transition does on its way to and from the blank middle.
+    pub fn dims(&self) -> bool {
+        let coverage = self.coverage();
+        self.kind == TransitionKind::Fade && (0.4..0.8).contains(&coverage)
+    }
+}
+
+/// Returns a number in `0.0..1.0` that looks random but is the same for a cell every frame.
+fn noise(x: u16, y: u16) -> f32 {
+    let mut hash = (x as u32).wrapping_mul(0x9e37_79b1) ^ (y as u32).wrapping_mul(0x85eb_ca77);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x2c1b_3c6d);
+    hash ^= hash >> 12;
+    (hash >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// `SceneStack` shows the top one of a stack of scenes and plays transitions between them.
+///
+/// # Example
+/// ```rust
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+/// use nyan::rect::Rect;
+/// use nyan::scene::{SceneStack, Transition};
+///
+/// let mut menu = NyanObj::new();
+/// menu.add_object("title", Objects::new_text("MENU"), (0, 0));
+/// let mut level = NyanObj::new();
+/// level.add_object("cat", Objects::new_text("=^.^="), (0, 0));
+///
+/// let mut scenes = SceneStack::new();
+/// scenes.push(menu);
+/// scenes.push_with(level, Transition::curtain(4));
+///
+/// let area = Rect::new(0, 0, 10, 1);
+/// let mut out = Vec::new();
+/// scenes.draw_in(&mut out, area).unwrap(); // The menu, half covered.
+/// assert!(String::from_utf8(out).unwrap().contains("MENU"));
+///
+/// let mut out = Vec::new();
+/// while scenes.is_transitioning() {
+///     out.clear();
+///     scenes.draw_in(&mut out, area).unwrap(); // The level, covered, then opening.
+/// }
+/// assert!(String::from_utf8(out).unwrap().contains("=^.^="));
+/// ```
+#[derive(Default)]
+pub struct SceneStack<'a> {
+    scenes: Vec<NyanObj<'a>>,
+    /// A popped scene, shown until the transition reaches its middle.
+    leaving: Option<NyanObj<'a>>,
+    transition: Option<Transition>,
+}
+
+impl<'a> SceneStack<'a> {
+    /// Creates a stack without scenes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows `scene` on top of the current one, at once.
+    pub fn push(&mut self, scene: NyanObj<'a>) {
+        self.finish();
+        self.scenes.push(scene);
+    }
+
+    /// Shows `scene` on top of the current one, with `transition`.
+    pub fn push_with(&mut self, scene: NyanObj<'a>, transition: Transition) {
+        self.push(scene);
+        self.transition = Some(transition);
+    }
+
+    /// Removes the current scene, showing the previous one at once.
+    ///
+    /// # Returns
+    ///
+    /// The removed scene, or `None` if the stack is empty.
+    pub fn pop(&mut self) -> Option<NyanObj<'a>> {
+        self.finish();
+        self.scenes.pop()
+    }
+
+    /// Removes the current scene with `transition`; it is shown until the transition reaches
+    /// its middle and dropped then.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the stack is empty.
+    pub fn pop_with(&mut self, transition: Transition) -> bool {
+        let Some(scene) = self.pop() else {
+            return false;
+        };
+        self.leaving = Some(scene);
+        self.transition = Some(transition);
+        true
+    }
+
+    /// Returns the current scene, the top of the stack, also while a transition still shows
+    /// the previous one.
+    pub fn current(&self) -> Option<&NyanObj<'a>> {
+        self.scenes.last()
+    }
+
+    /// Returns the current scene for changes, e.g. to route an event to it.
+    pub fn current_mut(&mut self) -> Option<&mut NyanObj<'a>> {
+        self.scenes.last_mut()
+    }
+
+    /// Returns the number of scenes on the stack.
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Returns whether the stack has no scenes.
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// Returns whether a transition is running.
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Moves a running transition on by one frame, then draws the scene it shows and its
+    /// cover inside `area`. Without a transition, the current scene is drawn.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the scene was drawn.
+    /// - An error if moving the cursor or writing fails.
+    pub fn draw_in<W: Write>(&mut self, out: &mut W, area: Rect) -> NyanResult<()> {
+        let Some(transition) = &mut self.transition else {
+            return match self.scenes.last() {
+                Some(scene) => scene.draw_all_in(out),
+                None => Ok(()),
+            };
+        };
+        transition.advance();
+        let transition = *transition;
+
+        let scene = if transition.is_past_middle() {
+            self.leaving = None;
+            self.scenes.last()
+        } else if self.leaving.is_some() {
+            self.leaving.as_ref()
+        } else {
+            self.scenes.iter().rev().nth(1)
+        };
+        if let Some(scene) = scene {
+            let overlay = if transition.dims() {
+                NyanStyle::new().dim()
+            } else {
+                NyanStyle::new()
+            };
+            scene.draw_all_overlaid_in(out, overlay)?;
+        }
+        transition.draw_in(out, area)?;
+
+        if transition.is_finished() {
+            self.finish();
+        }
+        Ok(())
+    }
+
+    /// Ends a running transition.
+    fn finish(&mut self) {
+        self.transition = None;
+        self.leaving = None;
+    }
+}
@@ -0,0 +1,230 @@
+//! This module provides the `Scheduler`, which fires events and callbacks after a delay or at an interval, driven by the frame loop.
+//!
+//! A scheduler keeps its own clock, moved forward by `advance(dt)` with the time that passed, usually [`App::delta_time`](crate::app::App::delta_time). Timers due by then fire in the order they are due: callbacks are run and events are returned (or pushed into an [`EventQueue`], to arrive as `NyanEvent::User`). The `App` has a scheduler of its own for callbacks only, advanced before every frame (see [`App::scheduler`](crate::app::App::scheduler)).
+//!
+//! # Structs
+//!
+//! - `Scheduler`: The pending timers and the scheduler's clock.
+//! - `TimerId`: Identifies a timer, e.g. to cancel it.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a scheduler without timers.
+//! - `after(delay, event)` / `every(interval, event)`: Fire an event once or repeatedly.
+//! - `after_fn(delay, callback)` / `every_fn(interval, callback)`: Run a callback once or repeatedly.
+//! - `cancel(id)` / `clear()`: Remove timers.
+//! - `remaining(id)` / `len()` / `is_empty()`: Query the timers.
+//! - `advance(dt)` / `advance_into(dt, queue)`: Move the clock forward and fire the due timers.
+
+use std::fmt::Debug;
+use std::time::Duration;
+
+use crate::event::EventQueue;
+
+/// `TimerId` identifies a timer of a [`Scheduler`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimerId(u64);
+
+/// What a timer does when it fires.
+enum Task<U> {
+    Event(U),
+    Callback(Box<dyn FnMut()>),
+}
+
+/// A pending timer.
+struct Timer<U> {
+    id: TimerId,
+    /// When the timer fires next, on the scheduler's clock.
+    due: Duration,
+    interval: Option<Duration>,
+    task: Task<U>,
+}
+
+/// `Scheduler` fires events and callbacks after a delay or at an interval.
+///
+/// # Example
+/// ```rust
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use std::time::Duration;
+///
+/// use nyan::scheduler::Scheduler;
+///
+/// let second = Duration::from_secs(1);
+/// let mut scheduler = Scheduler::new();
+/// scheduler.after(second * 3, "dismiss toast");
+/// scheduler.every(second, "refresh");
+///
+/// let ticks = Rc::new(Cell::new(0));
+/// let counter = ticks.clone();
+/// scheduler.every_fn(second / 2, move || counter.set(counter.get() + 1));
+///
+/// assert_eq!(scheduler.advance(second), ["refresh"]);
+/// assert_eq!(scheduler.advance(second * 2), ["refresh", "dismiss toast", "refresh"]);
+/// assert_eq!(ticks.get(), 6);
+/// ```
+pub struct Scheduler<U = ()> {
+    timers: Vec<Timer<U>>,
+    now: Duration,
+    next_id: u64,
+}
+
+impl<U: Debug> Debug for Scheduler<U> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let timers: Vec<_> = self
+            .timers
+            .iter()
+            .map(|timer| {
+                let task = match &timer.task {
+                    Task::Event(event) => format!("{event:?}"),
+                    Task::Callback(_) => "<callback>".to_string(),
+                };
+                (timer.id, timer.due, timer.interval, task)
+            })
+            .collect();
+        fmt.debug_struct("Scheduler")
+            .field("timers", &timers)
+            .field("now", &self.now)
+            .finish()
+    }
+}
+
+impl<U> Default for Scheduler<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U> Scheduler<U> {
+    /// Creates a scheduler without timers.
+    pub fn new() -> Self {
+        Self {
+            timers: Vec::new(),
+            now: Duration::ZERO,
+            next_id: 0,
+        }
+    }
+
+    /// Fires `event` once, `delay` from now.
+    pub fn after(&mut self, delay: Duration, event: U) -> TimerId {
+        self.schedule(delay, None, Task::Event(event))
+    }
+
+    /// Fires `event` every `interval` (at least a millisecond), starting `interval` from now.
+    pub fn every(&mut self, interval: Duration, event: U) -> TimerId {
+        let interval = interval.max(Duration::from_millis(1));
+        self.schedule(interval, Some(interval), Task::Event(event))
+    }
+
+    /// Runs `callback` once, `delay` from now.
+    pub fn after_fn<F: FnMut() + 'static>(&mut self, delay: Duration, callback: F) -> TimerId {
+        self.schedule(delay, None, Task::Callback(Box::new(callback)))
+    }
+
+    /// Runs `callback` every `interval` (at least a millisecond), starting `interval` from now.
+    pub fn every_fn<F: FnMut() + 'static>(&mut self, interval: Duration, callback: F) -> TimerId {
+        let interval = interval.max(Duration::from_millis(1));
+        self.schedule(interval, Some(interval), Task::Callback(Box::new(callback)))
+    }
+
+    /// Removes the timer `id` before it fires (again).
+    ///
+    /// # Returns
+    ///
+    /// `true` if the timer was pending.
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        let before = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != before
+    }
+
+    /// Removes all timers.
+    pub fn clear(&mut self) {
+        self.timers.clear();
+    }
+
+    /// Returns how long until the timer `id` fires next, or `None` if it is not pending.
+    pub fn remaining(&self, id: TimerId) -> Option<Duration> {
+        self.timers
+            .iter()
+            .find(|timer| timer.id == id)
+            .map(|timer| timer.due.saturating_sub(self.now))
+    }
+
+    /// Returns the number of pending timers.
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Returns whether no timer is pending.
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Adds a timer.
+    fn schedule(&mut self, delay: Duration, interval: Option<Duration>, task: Task<U>) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+        self.timers.push(Timer {
+            id,
+            due: self.now + delay,
+            interval,
+            task,
+        });
+        id
+    }
+}
+
+impl<U: Clone> Scheduler<U> {
+    /// Moves the clock forward by `dt` and fires the timers due by then, in the order they are
+    /// due. A repeating timer fires once for every interval that passed.
+    ///
+    /// # Returns
+    ///
+    /// The fired events, in the order they fired. Callbacks have been run.
+    pub fn advance(&mut self, dt: Duration) -> Vec<U> {
+        let end = self.now + dt;
+        let mut fired = Vec::new();
+        loop {
+            let due = self
+                .timers
+                .iter()
+                .enumerate()
+                .filter(|(_, timer)| timer.due <= end)
+                .min_by_key(|(_, timer)| (timer.due, timer.id))
+                .map(|(index, _)| index);
+            let Some(index) = due else {
+                break;
+            };
+            let timer = &mut self.timers[index];
+            self.now = timer.due;
+            match &mut timer.task {
+                Task::Event(event) => fired.push(event.clone()),
+                Task::Callback(callback) => callback(),
+            }
+            match timer.interval {
+                Some(interval) => timer.due += interval,
+                None => {
+                    self.timers.remove(index);
+                }
+            }
+        }
+        self.now = end;
+        fired
+    }
+
+    /// Like [`Scheduler::advance`], but pushes the fired events into `queue`, so they arrive as
+    /// `NyanEvent::User` with the terminal events.
+    ///
+    /// # Returns
+    ///
+    /// The number of fired events.
+    pub fn advance_into(&mut self, dt: Duration, queue: &EventQueue<U>) -> usize {
+        let fired = self.advance(dt);
+        let count = fired.len();
+        for event in fired {
+            queue.push(event);
+        }
+        count
+    }
+}
@@ -0,0 +1,500 @@
+//! This module provides `Script`, which runs a [rhai](https://rhai.rs) script that creates and updates the objects of a `NyanObj` and responds to events, so scenes can be prototyped and modded without recompiling.
+//!
+//! Requires the `rhai` feature. A script defines hooks, each of them optional, which the application calls through `Script`:
+//!
+//! ```text
+//! fn init() {                    // once, from Script::start
+//!     this.count = 0;
+//!     add_text("title", "Pressed 0 times", 2, 1);
+//! }
+//! fn update(dt) { }              // every frame, dt in seconds
+//! fn on_key(key) {               // keys as written in the keybindings module, e.g. "Ctrl+S"
+//!     if key == "q" { quit(); return; }
+//!     this.count += 1;
+//!     set_text("title", `Pressed ${this.count} times`);
+//! }
+//! fn on_click(x, y) { }          // left mouse button pressed
+//! fn on_paste(text) { }
+//! fn on_resize(width, height) { }
+//! ```
+//!
+//! The hooks share the state of the script through `this`, an object map that lives as long as the `Script`. They change the scene with these functions, applied to the `NyanObj` when the hook returns:
+//!
+//! - `add_text(id, text, x, y)` / `add_panel(id, width, height, x, y)` / `add_custom(id, kind, props, x, y)`: Add an object, or replace the object `id` (`props` is a map, see the `plugin` module).
+//! - `set_text(id, text)` / `set_prop(id, name, value)`: Change the text of a text object / a prop of a custom object.
+//! - `move_to(id, x, y)` / `show(id)` / `hide(id)` / `remove(id)`: Move, show, hide or remove an object.
+//! - `quit()`: Asks the application to stop (see `Script::wants_quit`).
+//!
+//! What the script prints with `print` or `debug` is kept for the application instead of being written over the screen (see `Script::take_printed`).
+//!
+//! A hook fails, changing nothing, if one of its changes cannot be applied (e.g. `move_to` of an object that does not exist), or if it runs over the limits of a script: a million operations, 64 nested calls, strings of 64 KiB and arrays or maps of 10 000 elements. So a broken script cannot hang the application or run it out of memory.
+//!
+//! # Structs
+//!
+//! - `Script`: A compiled script, its state and what it asked for.
+//!
+//! # Methods
+//!
+//! - `new(source)` / `load(path)`: Compile a script from a string / a file.
+//! - `start(scene)`: Runs the top-level statements of the script and its `init` hook.
+//! - `update(scene, dt)`: Runs the `update` hook.
+//! - `handle_event(scene, event)`: Runs the hook of an event, if the script has one.
+//! - `wants_quit()` / `take_printed()`: Return whether the script called `quit()` / what it printed.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Duration;
+
+use rhai::{CallFnOptions, Dynamic, Engine, FuncArgs, Map, Scope, AST};
+
+use crate::errors::{NyanError, NyanResult};
+use crate::event::{NyanEvent, NyanMouseButton, NyanMouseKind};
+use crate::keybindings::key_name;
+use crate::nyan_obj::NyanObj;
+use crate::objects::Objects;
+use crate::plugin::Props;
+
+/// How many operations a single hook may run, so a script stuck in a loop fails instead of
+/// hanging the application.
+const MAX_OPERATIONS: u64 = 1_000_000;
+/// How deep a script may call its functions.
+const MAX_CALL_LEVELS: usize = 64;
+/// The longest string, in bytes, a script may build.
+const MAX_STRING_SIZE: usize = 64 * 1024;
+/// The most elements an array or object map of a script may have.
+const MAX_COLLECTION_SIZE: usize = 10_000;
+
+/// A change to the scene asked for by a script.
+enum Command {
+    Add {
+        id: String,
+        object: Objects<'static>,
+        at: (u16, u16),
+    },
+    SetText {
+        id: String,
+        text: String,
+    },
+    SetProp {
+        id: String,
+        name: String,
+        value: String,
+    },
+    Move {
+        id: String,
+        at: (u16, u16),
+    },
+    Visible {
+        id: String,
+        visible: bool,
+    },
+    Remove {
+        id: String,
+    },
+    Quit,
+}
+
+/// `Script` is a compiled rhai script with its state.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "rhai")]
+/// # {
+/// use nyan::event::NyanEvent;
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::script::Script;
+///
+/// let mut script = Script::new(
+///     r#"
+///     fn init() {
+///         this.count = 0;
+///         add_text("title", "Pressed 0 times", 2, 1);
+///     }
+///     fn on_key(key) {
+///         this.count += 1;
+///         set_text("title", `Pressed ${this.count} times (${key})`);
+///     }
+///     "#,
+/// )
+/// .unwrap();
+///
+/// let mut scene = NyanObj::new();
+/// script.start(&mut scene).unwrap();
+/// assert_eq!(scene.coordinate("title"), Some((2, 1)));
+///
+/// let event: NyanEvent = NyanEvent::Key(NyanInput::Ctrl(NyanKey::S));
+/// assert!(script.handle_event(&mut scene, &event).unwrap());
+/// let title = format!("{:?}", scene.object("title").unwrap());
+/// assert!(title.contains("Pressed 1 times (Ctrl+S)"));
+/// # }
+/// ```
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+    /// The `this` of the hooks.
+    state: Dynamic,
+    commands: Rc<RefCell<Vec<Command>>>,
+    printed: Rc<RefCell<Vec<String>>>,
+    quit: bool,
+}
+
+impl std::fmt::Debug for Script {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Script")
+            .field(
+                "hooks",
+                &self
+                    .ast
+                    .iter_functions()
+                    .map(|f| f.name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("state", &self.state)
+            .field("quit", &self.quit)
+            .finish()
+    }
+}
+
+impl Script {
+    /// Compiles the script `source`. Nothing of it runs until [`Script::start`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Script)` with the compiled script.
+    /// - `Err(NyanError::Script)` if the source does not compile, with the line and column.
+    pub fn new(source: &str) -> NyanResult<Self> {
+        let commands = Rc::new(RefCell::new(Vec::new()));
+        let printed = Rc::new(RefCell::new(Vec::new()));
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(MAX_CALL_LEVELS)
+            .set_max_string_size(MAX_STRING_SIZE)
+            .set_max_array_size(MAX_COLLECTION_SIZE)
+            .set_max_map_size(MAX_COLLECTION_SIZE);
+        register(&mut engine, &commands);
+        let out = Rc::clone(&printed);
+        engine.on_print(move |text| out.borrow_mut().push(text.to_string()));
+        let out = Rc::clone(&printed);
+        engine.on_debug(move |text, _, position| {
+            out.borrow_mut().push(format!("{position:?}: {text}"))
+        });
+        let ast = engine
+            .compile(source)
+            .map_err(|e| NyanError::Script(e.to_string().into()))?;
+
+        Ok(Self {
+            engine,
+            ast,
+            scope: Scope::new(),
+            state: Dynamic::from_map(Map::new()),
+            commands,
+            printed,
+            quit: false,
+        })
+    }
+
+    /// Compiles the script in the file at `path`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Script)` with the compiled script.
+    /// - `Err(NyanError::Io)` if the file cannot be read.
+    /// - `Err(NyanError::Script)` if it does not compile.
+    pub fn load<P: AsRef<Path>>(path: P) -> NyanResult<Self> {
+        Self::new(&std::fs::read_to_string(path)?)
+    }
+
+    /// Runs the top-level statements of the script, then its `init` hook, and applies the
+    /// changes they asked for to `scene`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` once the changes are applied.
+    /// - `Err(NyanError::Script)` if the script fails.
+    /// - `Err(NyanError::ObjectNotFound)` if it changes an object that does not exist.
+    pub fn start(&mut self, scene: &mut NyanObj<'static>) -> NyanResult<()> {
+        let ran = self
+            .engine
+            .run_ast_with_scope(&mut self.scope, &self.ast)
+            .map_err(|e| NyanError::Script(e.to_string().into()));
+        self.flush(scene, ran)?;
+        self.call(scene, "init", ())?;
+        Ok(())
+    }
+
+    /// Runs the `update` hook with `dt` in seconds, e.g. with
+    /// [`App::delta_time`](crate::app::App::delta_time) every frame, and applies its changes.
+    pub fn update(&mut self, scene: &mut NyanObj<'static>, dt: Duration) -> NyanResult<()> {
+        self.call(scene, "update", (dt.as_secs_f64(),))?;
+        Ok(())
+    }
+
+    /// Runs the hook of `event` and applies its changes: `on_key` for keys, `on_click` for
+    /// presses of the left mouse button, `on_paste` for pasted text and `on_resize` when the
+    /// terminal is resized.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` if the script has a hook for the event, which ran.
+    /// - `Ok(false)` if it has none, so the application may handle the event itself.
+    /// - An error if the hook fails (see [`Script::start`]).
+    pub fn handle_event<U>(
+        &mut self,
+        scene: &mut NyanObj<'static>,
+        event: &NyanEvent<U>,
+    ) -> NyanResult<bool> {
+        match event {
+            NyanEvent::Key(key) => self.call(scene, "on_key", (key_name(key),)),
+            NyanEvent::Mouse(mouse) if mouse.kind == NyanMouseKind::Down(NyanMouseButton::Left) => {
+                self.call(scene, "on_click", (mouse.x as i64, mouse.y as i64))
+            }
+            NyanEvent::Paste(text) => self.call(scene, "on_paste", (text.clone(),)),
+            NyanEvent::Resize(width, height) => {
+                self.call(scene, "on_resize", (*width as i64, *height as i64))
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Returns whether the script called `quit()`.
+    pub fn wants_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Returns what the script printed with `print` or `debug` since the last call, e.g. to
+    /// show it in a [`LogView`](crate::log_view::LogView).
+    pub fn take_printed(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.printed.borrow_mut())
+    }
+
+    /// Calls the hook `name` with `args` if the script defines it, and applies its changes.
+    ///
+    /// # Returns
+    /// `Ok(true)` if the hook is defined and ran.
+    fn call<A: FuncArgs>(
+        &mut self,
+        scene: &mut NyanObj<'static>,
+        name: &str,
+        args: A,
+    ) -> NyanResult<bool> {
+        if !self.ast.iter_functions().any(|f| f.name == name) {
+            return Ok(false);
+        }
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        let called = self
+            .engine
+            .call_fn_with_options::<Dynamic>(options, &mut self.scope, &self.ast, name, args)
+            .map_err(|e| NyanError::Script(format!("in {name}: {e}").into()));
+        self.flush(scene, called)?;
+        Ok(true)
+    }
+
+    /// Applies the changes the script asked for to `scene`, or drops them if it failed, so a
+    /// failed hook changes nothing.
+    fn flush<T>(&mut self, scene: &mut NyanObj<'static>, result: NyanResult<T>) -> NyanResult<()> {
+        let commands = std::mem::take(&mut *self.commands.borrow_mut());
+        result?;
+        // Changes that would fail partway are refused as a whole.
+        check(scene, &commands)?;
+        for command in commands {
+            self.apply(scene, command)?;
+        }
+        Ok(())
+    }
+
+    /// Applies a change asked for by the script to `scene`.
+    fn apply(&mut self, scene: &mut NyanObj<'static>, command: Command) -> NyanResult<()> {
+        match command {
+            Command::Add { id, object, at } => match scene.object_mut(id.clone()) {
+                Some(old) => {
+                    *old = object;
+                    scene.move_object(id, at)?;
+                }
+                None => scene.add_object(id, object, at),
+            },
+            Command::SetText { id, text } => match scene.object_mut(id.clone()) {
+                Some(
+                    Objects::Text(old)
+                    | Objects::Paragraph { text: old, .. }
+                    | Objects::ScrollableText { text: old, .. }
+                    | Objects::ScrollView { text: old, .. },
+                ) => *old = Cow::Owned(text),
+                Some(_) => return Err(not_text(&id)),
+                None => return Err(not_found(id)),
+            },
+            Command::SetProp { id, name, value } => {
+                match scene.object_mut(id.clone()).map(Objects::props_mut) {
+                    Some(Some(props)) => props.set(name, value),
+                    Some(None) => return Err(not_custom(&id)),
+                    None => return Err(not_found(id)),
+                }
+            }
+            Command::Move { id, at } => scene.move_object(id, at)?,
+            Command::Visible { id, visible } => scene.set_visible(id, visible)?,
+            Command::Remove { id } => scene.remove_object(id)?,
+            Command::Quit => self.quit = true,
+        }
+        Ok(())
+    }
+}
+
+/// Checks that every command can be applied to `scene` after the ones before it, so a hook
+/// applies all of its changes or none.
+fn check(scene: &NyanObj<'static>, commands: &[Command]) -> NyanResult<()> {
+    // Whether the objects added so far have text and props, or `None` once removed.
+    let mut added: HashMap<&str, Option<(bool, bool)>> = HashMap::new();
+    for command in commands {
+        let id = match command {
+            Command::Add { id, object, .. } => {
+                added.insert(id, Some(kind(object)));
+                continue;
+            }
+            Command::Quit => continue,
+            Command::SetText { id, .. }
+            | Command::SetProp { id, .. }
+            | Command::Move { id, .. }
+            | Command::Visible { id, .. }
+            | Command::Remove { id } => id,
+        };
+        let object = match added.get(id.as_str()) {
+            Some(object) => *object,
+            None => scene.object(id.as_str()).map(kind),
+        };
+        let Some((text, props)) = object else {
+            return Err(not_found(id.clone()));
+        };
+        match command {
+            Command::SetText { .. } if !text => return Err(not_text(id)),
+            Command::SetProp { .. } if !props => return Err(not_custom(id)),
+            Command::Remove { .. } => {
+                added.insert(id, None);
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether `object` has a text (for `set_text`) and props (for `set_prop`).
+fn kind(object: &Objects) -> (bool, bool) {
+    let text = matches!(
+        object,
+        Objects::Text(_)
+            | Objects::Paragraph { .. }
+            | Objects::ScrollableText { .. }
+            | Objects::ScrollView { .. }
+    );
+    (text, matches!(object, Objects::Custom { .. }))
+}
+
+/// The error of a command for an object that does not exist.
+fn not_found(id: String) -> NyanError<'static> {
+    NyanError::ObjectNotFound(Cow::Owned(id))
+}
+
+/// The error of `set_text` for an object without text.
+fn not_text(id: &str) -> NyanError<'static> {
+    NyanError::Script(format!("set_text: {id:?} is not a text object").into())
+}
+
+/// The error of `set_prop` for an object without props.
+fn not_custom(id: &str) -> NyanError<'static> {
+    NyanError::Script(format!("set_prop: {id:?} is not a custom object").into())
+}
+
+/// Returns a coordinate or size of a script as cells, clamped to what a terminal can have.
+fn cells(value: i64) -> u16 {
+    value.clamp(0, u16::MAX as i64) as u16
+}
+
+/// Registers the functions scripts change the scene with, which queue their changes in
+/// `commands`.
+fn register(engine: &mut Engine, commands: &Rc<RefCell<Vec<Command>>>) {
+    let queue = Rc::clone(commands);
+    engine.register_fn("add_text", move |id: &str, text: &str, x: i64, y: i64| {
+        queue.borrow_mut().push(Command::Add {
+            id: id.to_string(),
+            object: Objects::new_text(text.to_string()),
+            at: (cells(x), cells(y)),
+        })
+    });
+    let queue = Rc::clone(commands);
+    engine.register_fn(
+        "add_panel",
+        move |id: &str, width: i64, height: i64, x: i64, y: i64| {
+            queue.borrow_mut().push(Command::Add {
+                id: id.to_string(),
+                object: Objects::new_panel(cells(width), cells(height), None),
+                at: (cells(x), cells(y)),
+            })
+        },
+    );
+    let queue = Rc::clone(commands);
+    engine.register_fn(
+        "add_custom",
+        move |id: &str, kind: &str, props: Map, x: i64, y: i64| {
+            let props = props
+                .into_iter()
+                .fold(Props::new(), |props, (name, value)| {
+                    props.with(name.to_string(), value.to_string())
+                });
+            queue.borrow_mut().push(Command::Add {
+                id: id.to_string(),
+                object: Objects::new_custom(kind.to_string(), props),
+                at: (cells(x), cells(y)),
+            })
+        },
+    );
+    let queue = Rc::clone(commands);
+    engine.register_fn("set_text", move |id: &str, text: &str| {
+        queue.borrow_mut().push(Command::SetText {
+            id: id.to_string(),
+            text: text.to_string(),
+        })
+    });
+    let queue = Rc::clone(commands);
+    engine.register_fn("set_prop", move |id: &str, name: &str, value: Dynamic| {
+        queue.borrow_mut().push(Command::SetProp {
+            id: id.to_string(),
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    });
+    let queue = Rc::clone(commands);
+    engine.register_fn("move_to", move |id: &str, x: i64, y: i64| {
+        queue.borrow_mut().push(Command::Move {
+            id: id.to_string(),
+            at: (cells(x), cells(y)),
+        })
+    });
+    let queue = Rc::clone(commands);
+    engine.register_fn("show", move |id: &str| {
+        queue.borrow_mut().push(Command::Visible {
+            id: id.to_string(),
+            visible: true,
+        })
+    });
+    let queue = Rc::clone(commands);
+    engine.register_fn("hide", move |id: &str| {
+        queue.borrow_mut().push(Command::Visible {
+            id: id.to_string(),
+            visible: false,
+        })
+    });
+    let queue = Rc::clone(commands);
+    engine.register_fn("remove", move |id: &str| {
+        queue
+            .borrow_mut()
+            .push(Command::Remove { id: id.to_string() })
+    });
+    let queue = Rc::clone(commands);
+    engine.register_fn("quit", move || queue.borrow_mut().push(Command::Quit));
+}
@@ -0,0 +1,94 @@
+//! This module provides the `Scrollbar`, which shows how much of some content is visible and where.
+//!
+//! A scrollbar is a track of cells with a thumb on it. The size of the thumb is proportional to the visible part of the content, and its position to the scroll offset. `Objects::ScrollView` draws one on each axis that does not fit, and the struct can be used on its own to decorate custom scrolling views.
+//!
+//! # Structs
+//!
+//! - `Scrollbar`: The scroll state of one axis: the content length, the visible length and the offset.
+//!
+//! # Methods
+//!
+//! - `new(content, viewport, offset)`: Creates a scrollbar.
+//! - `symbols(track, thumb)`: Sets the characters of the track and the thumb.
+//! - `is_needed()`: Returns whether the content is larger than the viewport.
+//! - `thumb(length)`: Returns the start and size of the thumb on a track of `length` cells.
+//! - `cells(length)`: Returns the characters of a track of `length` cells.
+
+/// `Scrollbar` computes a proportional scrollbar for one axis of some scrolled content.
+///
+/// # Example
+/// ```rust
+/// use nyan::scrollbar::Scrollbar;
+///
+/// // 100 lines, 10 of them visible, scrolled to the middle.
+/// let bar = Scrollbar::new(100, 10, 45).symbols('.', '#');
+/// assert_eq!(bar.thumb(10), (5, 1));
+/// assert_eq!(bar.cells(10), ".....#....");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Scrollbar {
+    content: usize,
+    viewport: usize,
+    offset: usize,
+    track: char,
+    thumb: char,
+}
+
+impl Scrollbar {
+    /// Creates a scrollbar for `content` cells (or lines, or items), of which `viewport` are
+    /// visible starting at `offset`.
+    pub fn new(content: usize, viewport: usize, offset: usize) -> Self {
+        Self {
+            content,
+            viewport,
+            offset,
+            track: '░',
+            thumb: '█',
+        }
+    }
+
+    /// Sets the characters of the track and the thumb (`'░'` and `'█'` by default).
+    pub fn symbols(self, track: char, thumb: char) -> Self {
+        let mut bar = self;
+        bar.track = track;
+        bar.thumb = thumb;
+        bar
+    }
+
+    /// Returns whether the content is larger than the viewport, i.e. whether it can be
+    /// scrolled.
+    pub fn is_needed(&self) -> bool {
+        self.content > self.viewport
+    }
+
+    /// Returns the `(start, size)` of the thumb on a track of `length` cells.
+    ///
+    /// The thumb is rounded to whole cells and at least one cell large. It starts at the
+    /// beginning of the track when the content is scrolled to the top and ends at its end when
+    /// it is scrolled to the bottom. If the content fits, the thumb fills the whole track.
+    pub fn thumb(&self, length: u16) -> (u16, u16) {
+        if !self.is_needed() || length == 0 {
+            return (0, length);
+        }
+        let length = length as usize;
+        let size = ((length * self.viewport + self.content / 2) / self.content).clamp(1, length);
+        let max_offset = self.content - self.viewport;
+        let free = length - size;
+        let start = (self.offset.min(max_offset) * free + max_offset / 2) / max_offset;
+        (start as u16, size as u16)
+    }
+
+    /// Returns the characters of a track of `length` cells, one per cell.
+    pub fn cells(&self, length: u16) -> String {
+        let (start, size) = self.thumb(length);
+        (0..length)
+            .map(|cell| {
+                if cell >= start && cell < start + size {
+                    self.thumb
+                } else {
+                    self.track
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,313 @@
+//! This module provides the `Select` widget, a dropdown that lets the user choose one of several options.
+//!
+//! Closed, the widget shows the chosen option on a single row. Activating it (Enter or Space while it is focused, or a click) opens a popup list of all options below it; the arrow keys, Home and End move the highlight, Enter or a click chooses the highlighted option and Esc closes the list without changing the choice. The popup is drawn by the widget itself, so draw it after the widgets it overlaps.
+//!
+//! # Structs
+//!
+//! - `Select`: The options, the chosen option and whether the popup is open.
+//!
+//! # Methods
+//!
+//! - `new(options)`: Creates a dropdown with nothing chosen.
+//! - `placeholder(text)` / `max_visible(rows)` / `theme(theme)`: Set the text shown before anything is chosen, the height of the popup, and the colors.
+//! - `selected()` / `value()` / `options()` / `highlighted()`: Query the choice.
+//! - `select(index)`: Chooses an option from code.
+//! - `open()` / `close()` / `is_open()`: Open and close the popup.
+//! - `popup_area()`: Returns where the popup list is drawn.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::cursor::Cursor;
+use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::{NyanInput, NyanKey};
+use crate::layout::{self, Align};
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// `Select` is a dropdown for choosing one of several options.
+///
+/// # Example
+/// ```rust
+/// use nyan::input::NyanInput;
+/// use nyan::select::Select;
+/// use nyan::widget::Widget;
+///
+/// let mut size = Select::new(["Small", "Medium", "Large"]);
+/// size.set_focused(true);
+///
+/// size.handle_input(&NyanInput::Enter); // open the list
+/// size.handle_input(&NyanInput::DownAllow);
+/// size.handle_input(&NyanInput::Enter); // choose "Medium"
+///
+/// assert!(!size.is_open());
+/// assert_eq!(size.value(), Some("Medium"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Select<'a> {
+    options: Vec<Cow<'a, str>>,
+    selected: Option<usize>,
+    placeholder: Cow<'a, str>,
+    open: bool,
+    /// The option under the highlight while the popup is open.
+    highlighted: usize,
+    max_visible: u16,
+    focused: bool,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> Select<'a> {
+    /// Creates a dropdown of `options` with nothing chosen.
+    pub fn new<T: Into<Cow<'a, str>>, I: IntoIterator<Item = T>>(options: I) -> Self {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            selected: None,
+            placeholder: Cow::Borrowed(""),
+            open: false,
+            highlighted: 0,
+            max_visible: 8,
+            focused: false,
+            theme: Theme::default(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Sets the text shown while nothing is chosen.
+    pub fn placeholder<T: Into<Cow<'a, str>>>(self, text: T) -> Self {
+        let mut select = self;
+        select.placeholder = text.into();
+        select
+    }
+
+    /// Sets how many options the popup shows at a time (8 by default); longer lists scroll.
+    pub fn max_visible(self, rows: u16) -> Self {
+        let mut select = self;
+        select.max_visible = rows.max(1);
+        select
+    }
+
+    /// Sets the colors of the dropdown.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut select = self;
+        select.theme = theme;
+        select
+    }
+
+    /// Returns the options.
+    pub fn options(&self) -> &[Cow<'a, str>] {
+        &self.options
+    }
+
+    /// Returns the index of the chosen option.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Returns the chosen option.
+    pub fn value(&self) -> Option<&str> {
+        self.selected.map(|index| self.options[index].as_ref())
+    }
+
+    /// Returns the index of the highlighted option in the popup.
+    pub fn highlighted(&self) -> usize {
+        self.highlighted
+    }
+
+    /// Chooses the option at `index`; an index past the last option clears the choice.
+    pub fn select(&mut self, index: usize) {
+        self.selected = (index < self.options.len()).then_some(index);
+    }
+
+    /// Returns whether the popup list is open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opens the popup list with the chosen option highlighted.
+    pub fn open(&mut self) {
+        if !self.options.is_empty() {
+            self.open = true;
+            self.highlighted = self.selected.unwrap_or(0);
+        }
+    }
+
+    /// Closes the popup list without changing the choice.
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Returns the area of the popup list: directly below the widget, as wide as it.
+    pub fn popup_area(&self) -> Rect {
+        let rows = (self.options.len().min(self.max_visible as usize)) as u16;
+        Rect::new(self.area.x, self.area.bottom(), self.area.width, rows)
+    }
+
+    /// Returns the first option shown in the popup, so the highlighted one is visible.
+    fn first_visible(&self) -> usize {
+        let rows = self.max_visible as usize;
+        (self.highlighted + 1).saturating_sub(rows)
+    }
+
+    /// Moves the highlight by `delta` options, staying inside the list.
+    fn move_highlight(&mut self, delta: isize) {
+        let last = self.options.len().saturating_sub(1);
+        self.highlighted = self.highlighted.saturating_add_signed(delta).min(last);
+    }
+
+    /// Chooses the highlighted option and closes the popup.
+    fn choose(&mut self) {
+        self.select(self.highlighted);
+        self.close();
+    }
+}
+
+impl<'a> Widget for Select<'a> {
+    /// As wide as the longest option plus the arrow.
+    fn size(&self) -> (u16, u16) {
+        let longest = self
+            .options
+            .iter()
+            .map(|o| o.width())
+            .chain([self.placeholder.width()])
+            .max()
+            .unwrap_or(0);
+        ((longest + 2).min(u16::MAX as usize) as u16, 1)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() {
+            return Ok(());
+        }
+        let width = self.area.width;
+        let (text, style) = match self.value() {
+            Some(value) => (value, NyanStyle::new()),
+            None => (self.placeholder.as_ref(), self.theme.muted),
+        };
+        let field = layout::clip_text(text, 0, width.saturating_sub(2) as usize);
+        let field = layout::align_text(&field, width.saturating_sub(2), Align::Start);
+        let arrow = if self.open { " ▴" } else { " ▾" };
+        let focus = if self.focused {
+            self.theme.accent
+        } else {
+            NyanStyle::new()
+        };
+
+        Cursor::Move(self.area.x, self.area.y).queue(&mut out)?;
+        widget::write_styled(out, focus.patch(style), &field)?;
+        widget::write_styled(out, focus, &layout::clip_text(arrow, 0, width as usize))?;
+
+        if self.open {
+            let popup = self.popup_area();
+            let first = self.first_visible();
+            for row in 0..popup.height {
+                let index = first + row as usize;
+                let Some(option) = self.options.get(index) else {
+                    break;
+                };
+                let line = layout::clip_text(option, 0, width as usize);
+                let line = layout::align_text(&line, width, Align::Start);
+                Cursor::Move(popup.x, popup.y + row).queue(&mut out)?;
+                let style = if index == self.highlighted {
+                    self.theme.selection
+                } else {
+                    self.theme.text
+                };
+                widget::write_styled(out, style, &line)?;
+            }
+        }
+        out.flush()
+    }
+
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        if !self.focused {
+            return false;
+        }
+        let activate = matches!(
+            input,
+            NyanInput::Enter | NyanInput::Key(NyanKey::OtherKey(' '))
+        );
+        if !self.open {
+            if activate || *input == NyanInput::DownAllow {
+                self.open();
+                return self.open;
+            }
+            return false;
+        }
+
+        let page = self.max_visible as isize;
+        match input {
+            _ if activate => self.choose(),
+            NyanInput::Esc => self.close(),
+            NyanInput::UpAllow => self.move_highlight(-1),
+            NyanInput::DownAllow => self.move_highlight(1),
+            NyanInput::PageUp => self.move_highlight(-page),
+            NyanInput::PageDown => self.move_highlight(page),
+            NyanInput::Home => self.highlighted = 0,
+            NyanInput::End => self.move_highlight(isize::MAX),
+            // The open popup keeps all keys to itself.
+            _ => {}
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        let popup = self.popup_area();
+        match mouse.kind {
+            NyanMouseKind::Down(NyanMouseButton::Left) if self.area.contains(mouse.x, mouse.y) => {
+                if self.open {
+                    self.close();
+                } else {
+                    self.open();
+                }
+                true
+            }
+            NyanMouseKind::Down(NyanMouseButton::Left) if self.open => {
+                if popup.contains(mouse.x, mouse.y) {
+                    self.highlighted = self.first_visible() + (mouse.y - popup.y) as usize;
+                    self.choose();
+                } else {
+                    // A click anywhere else closes the popup.
+                    self.close();
+                }
+                true
+            }
+            NyanMouseKind::ScrollUp if self.open && popup.contains(mouse.x, mouse.y) => {
+                self.move_highlight(-1);
+                true
+            }
+            NyanMouseKind::ScrollDown if self.open && popup.contains(mouse.x, mouse.y) => {
+                self.move_highlight(1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if !focused {
+            self.close();
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! This module provides the `SplitPane` container, which splits a region into two panes separated by a divider that can be moved with the mouse or the keyboard.
+//!
+//! A horizontal split puts the two panes side by side with a vertical divider between them (like a file manager), a vertical split puts them on top of each other. The divider can be dragged with the left mouse button (mouse capture must be enabled on the `App`) or moved with Shift and the arrow keys. After the divider moved, apply the split again so the two objects follow their panes.
+//!
+//! # Structs
+//!
+//! - `SplitPane`: Two panes and a movable divider.
+//!
+//! # Methods
+//!
+//! - `new(direction, first, second)`: Creates a split of two objects with the divider in the middle.
+//! - `position(cells)` / `min_size(cells)`: Set the size of the first pane / the smallest size of each pane.
+//! - `regions(area)`: Returns the `Rect`s of the first pane, the divider and the second pane.
+//! - `apply(objects, area)`: Fits the two objects into their panes.
+//! - `move_divider(delta)`: Moves the divider by `delta` cells.
+//! - `handle_mouse(mouse)` / `handle_input(input)` / `handle_event(event)`: Move the divider from user input.
+//! - `draw_divider(out)`: Draws the divider line with the default border characters.
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::io::{self, Write};
+
+use crate::border;
+use crate::cursor::Cursor;
+use crate::errors::NyanResult;
+use crate::event::{NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::NyanInput;
+use crate::layout::{Direction, Layout};
+use crate::nyan_obj::NyanObj;
+use crate::rect::Rect;
+
+/// `SplitPane` splits a region into two panes separated by a movable, one cell thick divider.
+///
+/// # Example
+/// ```ignore
+/// let mut split = SplitPane::new(Direction::Horizontal, "tree", "editor").position(30);
+/// split.apply(&mut obj, Rect::terminal()?)?;
+///
+/// loop {
+///     let event = NyanEvent::get_event()?;
+///     if split.handle_event(&event) {
+///         split.apply(&mut obj, Rect::terminal()?)?;
+///     }
+///     app.draw_with(|app| {
+///         obj.draw_all_in(app).unwrap();
+///         split.draw_divider(app).unwrap();
+///     })?;
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitPane<'a> {
+    direction: Direction,
+    first: Cow<'a, str>,
+    second: Cow<'a, str>,
+    /// The size of the first pane; `None` until set, which splits in the middle.
+    position: Option<u16>,
+    min_size: u16,
+    dragging: bool,
+    /// The region the split was last applied to, for hit-testing the divider.
+    area: Cell<Rect>,
+}
+
+impl<'a> SplitPane<'a> {
+    /// Creates a split of the objects `first` and `second` with the divider in the middle.
+    ///
+    /// A `Direction::Horizontal` split puts `first` left of `second`, a
+    /// `Direction::Vertical` split puts it above.
+    pub fn new<P: Into<Cow<'a, str>>, Q: Into<Cow<'a, str>>>(
+        direction: Direction,
+        first: P,
+        second: Q,
+    ) -> Self {
+        Self {
+            direction,
+            first: first.into(),
+            second: second.into(),
+            position: None,
+            min_size: 1,
+            dragging: false,
+            area: Cell::new(Rect::default()),
+        }
+    }
+
+    /// Sets the size of the first pane in cells.
+    pub fn position(self, cells: u16) -> Self {
+        let mut split = self;
+        split.position = Some(cells);
+        split
+    }
+
+    /// Sets the smallest size of each pane, so the divider cannot hide a pane completely.
+    pub fn min_size(self, cells: u16) -> Self {
+        let mut split = self;
+        split.min_size = cells;
+        split
+    }
+
+    /// Returns whether the divider is being dragged with the mouse.
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Returns the length of `area` along the split.
+    fn length(&self, area: Rect) -> u16 {
+        match self.direction {
+            Direction::Horizontal => area.width,
+            Direction::Vertical => area.height,
+        }
+    }
+
+    /// Returns the size of the first pane inside `area`, clamped so both panes keep their
+    /// minimum size if possible.
+    fn first_size(&self, area: Rect) -> u16 {
+        let available = self.length(area).saturating_sub(1);
+        let position = self.position.unwrap_or(available / 2);
+        let max = available.saturating_sub(self.min_size);
+        position.min(max).max(self.min_size.min(available))
+    }
+
+    /// Returns the rectangles of the first pane, the divider and the second pane when the split
+    /// fills `area`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::layout::Direction;
+    /// use nyan::rect::Rect;
+    /// use nyan::split::SplitPane;
+    ///
+    /// let split = SplitPane::new(Direction::Horizontal, "left", "right").position(30);
+    /// let (left, divider, right) = split.regions(Rect::new(0, 0, 80, 24));
+    ///
+    /// assert_eq!(left, Rect::new(0, 0, 30, 24));
+    /// assert_eq!(divider, Rect::new(30, 0, 1, 24));
+    /// assert_eq!(right, Rect::new(31, 0, 49, 24));
+    /// ```
+    pub fn regions(&self, area: Rect) -> (Rect, Rect, Rect) {
+        let first = self.first_size(area);
+        let second = self.length(area).saturating_sub(first).saturating_sub(1);
+        match self.direction {
+            Direction::Horizontal => (
+                Rect::new(area.x, area.y, first, area.height),
+                Rect::new(area.x + first, area.y, 1.min(area.width), area.height),
+                Rect::new(area.x + first + 1, area.y, second, area.height),
+            ),
+            Direction::Vertical => (
+                Rect::new(area.x, area.y, area.width, first),
+                Rect::new(area.x, area.y + first, area.width, 1.min(area.height)),
+                Rect::new(area.x, area.y + first + 1, area.width, second),
+            ),
+        }
+    }
+
+    /// Fits the two objects into their panes when the split fills `area` (see
+    /// [`NyanObj::fit_object`]).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if both objects were placed.
+    /// - An error of type [`NyanError::ObjectNotFound`](crate::errors::NyanError) if one of the
+    ///   objects does not exist in `objects`.
+    pub fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        self.area.set(area);
+        let (first, _, second) = self.regions(area);
+        objects.fit_object(self.first.clone(), first)?;
+        objects.fit_object(self.second.clone(), second)?;
+        Ok(())
+    }
+
+    /// Moves the divider by `delta` cells (negative values move it left / up).
+    ///
+    /// The size of the first pane is measured in the region the split was last applied to.
+    pub fn move_divider(&mut self, delta: i32) {
+        let area = self.area.get();
+        let current = self.first_size(area) as i32;
+        self.position = Some((current + delta).clamp(0, u16::MAX as i32) as u16);
+        // Store the clamped size, so moving back works right away.
+        self.position = Some(self.first_size(area));
+    }
+
+    /// Moves the divider with the left mouse button.
+    ///
+    /// Pressing the button on the divider grabs it, dragging moves it to the pointer, and
+    /// releasing the button lets go.
+    ///
+    /// # Returns
+    /// `true` if the divider moved, so the split should be applied again.
+    pub fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        let area = self.area.get();
+        match mouse.kind {
+            NyanMouseKind::Down(NyanMouseButton::Left) => {
+                let (_, divider, _) = self.regions(area);
+                self.dragging = divider.contains(mouse.x, mouse.y);
+                false
+            }
+            NyanMouseKind::Drag(NyanMouseButton::Left) if self.dragging => {
+                let pointer = match self.direction {
+                    Direction::Horizontal => mouse.x.saturating_sub(area.x),
+                    Direction::Vertical => mouse.y.saturating_sub(area.y),
+                };
+                let before = self.first_size(area);
+                self.position = Some(pointer);
+                // Store the clamped size, so moving back works right away.
+                self.position = Some(self.first_size(area));
+                self.position != Some(before)
+            }
+            NyanMouseKind::Up(NyanMouseButton::Left) => {
+                self.dragging = false;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves the divider with Shift and the arrow keys (left / right for a horizontal split,
+    /// up / down for a vertical split).
+    ///
+    /// # Returns
+    /// `true` if the divider moved, so the split should be applied again.
+    pub fn handle_input(&mut self, input: &NyanInput) -> bool {
+        let delta = match (self.direction, input) {
+            (Direction::Horizontal, NyanInput::Shift(NyanInput::LeftAllow))
+            | (Direction::Vertical, NyanInput::Shift(NyanInput::UpAllow)) => -1,
+            (Direction::Horizontal, NyanInput::Shift(NyanInput::RightAllow))
+            | (Direction::Vertical, NyanInput::Shift(NyanInput::DownAllow)) => 1,
+            _ => return false,
+        };
+        let before = self.first_size(self.area.get());
+        self.move_divider(delta);
+        self.position != Some(before)
+    }
+
+    /// Processes any event, moving the divider on mouse actions and Shift + arrow keys.
+    ///
+    /// # Returns
+    /// `true` if the divider moved, so the split should be applied again.
+    pub fn handle_event<U>(&mut self, event: &NyanEvent<U>) -> bool {
+        match event {
+            NyanEvent::Mouse(mouse) => self.handle_mouse(mouse),
+            NyanEvent::Key(input) => self.handle_input(input),
+            _ => false,
+        }
+    }
+
+    /// Draws the divider line in the region the split was last applied to, using the default
+    /// border characters (see [`border::default_border`]).
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(std::io::Error)` if writing fails.
+    pub fn draw_divider<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let (_, divider, _) = self.regions(self.area.get());
+        if divider.is_empty() {
+            return Ok(());
+        }
+        let set = border::default_border().set();
+        match self.direction {
+            Direction::Horizontal => {
+                for y in divider.y..divider.bottom() {
+                    Cursor::Move(divider.x, y).queue(out)?;
+                    write!(out, "{}", set.vertical)?;
+                }
+            }
+            Direction::Vertical => {
+                Cursor::Move(divider.x, divider.y).queue(out)?;
+                let line = set.horizontal.to_string().repeat(divider.width as usize);
+                write!(out, "{line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Layout<'a> for SplitPane<'a> {
+    fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        SplitPane::apply(self, objects, area)
+    }
+}
@@ -0,0 +1,265 @@
+//! This module provides `AnimatedSprite`, a piece of text art that cycles through frames, each shown for its own duration.
+//!
+//! A sprite is advanced by the time that passed, usually [`App::delta_time`](crate::app::App::delta_time). Put it into a scene as [`Objects::Sprite`](crate::objects::Objects::Sprite) and advance every sprite of the scene at once with [`NyanObj::advance`](crate::nyan_obj::NyanObj::advance). The `PlayMode` decides what happens after the last frame: start over, stop, or play backwards.
+//!
+//! # Structs
+//!
+//! - `AnimatedSprite`: The frames, their durations and the playback state.
+//!
+//! # Enums
+//!
+//! - `PlayMode`: Loops, plays once, or plays back and forth.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a sprite without frames.
+//! - `frame(text, duration)` / `mode(mode)`: Add a frame and set the play mode.
+//! - `play()` / `pause()` / `reset()`: Control the playback.
+//! - `advance(dt)`: Advances the playback by the time that passed.
+//! - `current()` / `current_frame()` / `set_frame(index)`: Query and set the shown frame.
+//! - `len()` / `is_empty()` / `is_playing()` / `is_finished()`: Query the sprite.
+//! - `size()`: Returns the size of the largest frame.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::stats;
+
+/// The `PlayMode` enum tells what an [`AnimatedSprite`] does after its last frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayMode {
+    /// Starts over at the first frame.
+    #[default]
+    Loop,
+    /// Stops at the last frame.
+    Once,
+    /// Plays backwards to the first frame, then forwards again.
+    PingPong,
+}
+
+/// `AnimatedSprite` cycles through frames of text, each shown for its own duration.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::sprite::{AnimatedSprite, PlayMode};
+///
+/// let ms = Duration::from_millis;
+/// let mut cat = AnimatedSprite::new()
+///     .frame("=^.^=", ms(500))
+///     .frame("=^-^=", ms(100))
+///     .frame("=^o^=", ms(200))
+///     .mode(PlayMode::PingPong);
+///
+/// cat.advance(ms(600));
+/// assert_eq!(cat.current_frame(), "=^o^=");
+/// cat.advance(ms(200));
+/// assert_eq!(cat.current(), 1); // on the way back
+///
+/// cat.pause();
+/// cat.advance(ms(1000));
+/// assert_eq!(cat.current(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnimatedSprite<'a> {
+    frames: Vec<(Cow<'a, str>, Duration)>,
+    mode: PlayMode,
+    current: usize,
+    /// How long the current frame has been shown.
+    elapsed: Duration,
+    /// Whether a ping-pong sprite is playing backwards.
+    backwards: bool,
+    playing: bool,
+    finished: bool,
+}
+
+impl<'a> Default for AnimatedSprite<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> AnimatedSprite<'a> {
+    /// Creates a looping sprite without frames, playing.
+    pub fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            mode: PlayMode::Loop,
+            current: 0,
+            elapsed: Duration::ZERO,
+            backwards: false,
+            playing: true,
+            finished: false,
+        }
+    }
+
+    /// Adds a frame shown for `duration`. A frame may have several lines.
+    pub fn frame<T: Into<Cow<'a, str>>>(self, text: T, duration: Duration) -> Self {
+        let mut sprite = self;
+        sprite.frames.push((text.into(), duration));
+        sprite
+    }
+
+    /// Sets what happens after the last frame ([`PlayMode::Loop`] by default).
+    pub fn mode(self, mode: PlayMode) -> Self {
+        let mut sprite = self;
+        sprite.mode = mode;
+        sprite
+    }
+
+    /// Resumes the playback. A sprite that played once is started over.
+    pub fn play(&mut self) {
+        if self.finished {
+            self.reset();
+        }
+        self.playing = true;
+    }
+
+    /// Pauses the playback on the current frame.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Goes back to the first frame, keeping whether the sprite is playing.
+    pub fn reset(&mut self) {
+        self.current = 0;
+        self.elapsed = Duration::ZERO;
+        self.backwards = false;
+        self.finished = false;
+    }
+
+    /// Advances the playback by `dt`, skipping frames if `dt` is longer than they are shown.
+    ///
+    /// # Returns
+    ///
+    /// `true` if another frame is shown now.
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        if !self.playing || self.finished || self.frames.is_empty() {
+            return false;
+        }
+        let before = self.current;
+        self.elapsed += dt;
+        // Frames of no duration are skipped, but a sprite made only of them stops moving.
+        let total: Duration = self.frames.iter().map(|(_, duration)| *duration).sum();
+        if total.is_zero() {
+            return false;
+        }
+        while self.elapsed >= self.frames[self.current].1 {
+            self.elapsed -= self.frames[self.current].1;
+            if !self.step() {
+                self.finished = true;
+                self.playing = false;
+                self.elapsed = Duration::ZERO;
+                break;
+            }
+        }
+        self.current != before
+    }
+
+    /// Returns the index of the shown frame.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the text of the shown frame, or `""` if the sprite has no frames.
+    pub fn current_frame(&self) -> &str {
+        self.frames
+            .get(self.current)
+            .map_or("", |(text, _)| text.as_ref())
+    }
+
+    /// Shows the frame `index` from its start. Out-of-range indices are ignored.
+    pub fn set_frame(&mut self, index: usize) {
+        if index < self.frames.len() {
+            self.current = index;
+            self.elapsed = Duration::ZERO;
+            self.finished = false;
+        }
+    }
+
+    /// Returns the number of frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns whether the sprite has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns whether the sprite is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Returns whether a sprite in [`PlayMode::Once`] reached its last frame.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the approximate bytes of the frames.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.frames.capacity() * std::mem::size_of::<(Cow<str>, Duration)>()
+            + self
+                .frames
+                .iter()
+                .map(|(text, _)| stats::owned(text))
+                .sum::<usize>()
+    }
+
+    /// Returns the `(width, height)` of the largest frame, so the sprite keeps its size while
+    /// it plays.
+    pub fn size(&self) -> (u16, u16) {
+        let lines = || self.frames.iter().flat_map(|(text, _)| text.lines());
+        let width = lines().map(|line| line.width()).max().unwrap_or(0);
+        let height = self
+            .frames
+            .iter()
+            .map(|(text, _)| text.lines().count())
+            .max()
+            .unwrap_or(0);
+        (
+            width.min(u16::MAX as usize) as u16,
+            height.min(u16::MAX as usize) as u16,
+        )
+    }
+
+    /// Moves to the next frame of the play mode.
+    ///
+    /// # Returns
+    ///
+    /// `false` if a sprite in [`PlayMode::Once`] is at its last frame.
+    fn step(&mut self) -> bool {
+        let last = self.frames.len() - 1;
+        match self.mode {
+            PlayMode::Loop => {
+                self.current = if self.current == last {
+                    0
+                } else {
+                    self.current + 1
+                }
+            }
+            PlayMode::Once if self.current == last => return false,
+            PlayMode::Once => self.current += 1,
+            PlayMode::PingPong if last == 0 => {}
+            PlayMode::PingPong => {
+                if self.current == last {
+                    self.backwards = true;
+                } else if self.current == 0 {
+                    self.backwards = false;
+                }
+                if self.backwards {
+                    self.current -= 1;
+                } else {
+                    self.current += 1;
+                }
+            }
+        }
+        true
+    }
+}
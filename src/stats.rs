@@ -0,0 +1,110 @@
+//! This module provides `SceneStats` and `AppStats`, counts of live objects, cached output and approximate memory use, so long-running apps can notice leaks at runtime.
+//!
+//! [`NyanObj::stats`](crate::nyan_obj::NyanObj::stats) counts the objects of a scene and the output kept for cached objects; [`App::stats`](crate::app::App::stats) counts the frame buffers and pending timers of an app. A number that only grows while the app runs, such as temporary objects that are added every frame but never removed, points to a leak. Memory is approximate: it counts the collections and the text owned by them, not text that is only borrowed, and not the spare capacity of every allocation. Both implement `Display`, for a status line or a debug overlay.
+//!
+//! # Structs
+//!
+//! - `SceneStats`: The objects of a scene, the cached ones and their memory.
+//! - `AppStats`: The frame buffers and timers of an app and their memory.
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// `SceneStats` counts the objects of a [`NyanObj`](crate::nyan_obj::NyanObj).
+///
+/// # Example
+/// ```rust
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+///
+/// let mut obj = NyanObj::new();
+/// obj.add_object("title", Objects::new_text("nyan"), (0, 0));
+/// obj.add_object("log", Objects::new_text(String::from("meow")), (0, 1));
+/// obj.set_visible("title", false).unwrap();
+///
+/// let stats = obj.stats();
+/// assert_eq!(stats.objects, 2);
+/// assert_eq!(stats.visible, 1);
+/// assert_eq!(stats.cached, 0);
+/// assert!(stats.memory > 0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SceneStats {
+    /// The number of objects, hidden ones included.
+    pub objects: usize,
+    /// The number of visible objects.
+    pub visible: usize,
+    /// The number of cached objects that keep their output (see
+    /// [`NyanObj::set_cached`](crate::nyan_obj::NyanObj::set_cached)).
+    pub cached: usize,
+    /// The bytes of output kept for cached objects.
+    pub cached_bytes: usize,
+    /// The approximate bytes used by the scene, cached output included.
+    pub memory: usize,
+}
+
+impl fmt::Display for SceneStats {
+    /// Formats the stats in one line, e.g. `"12 objects (10 visible)  3 cached (1.2 KiB)  ~8.0 KiB"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} objects ({} visible)  {} cached ({})  ~{}",
+            self.objects,
+            self.visible,
+            self.cached,
+            Bytes(self.cached_bytes),
+            Bytes(self.memory)
+        )
+    }
+}
+
+/// `AppStats` counts the frame buffers and timers of an [`App`](crate::app::App).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AppStats {
+    /// The number of cells of the front and back buffers of a buffered app (see
+    /// [`App::buffered`](crate::app::App::buffered)); `0` for an app that is not buffered.
+    pub buffer_cells: usize,
+    /// The number of pending timers of the scheduler.
+    pub timers: usize,
+    /// The number of frames drawn.
+    pub frames: u64,
+    /// The approximate bytes used by the buffers and the output queued for the terminal.
+    pub memory: usize,
+}
+
+impl fmt::Display for AppStats {
+    /// Formats the stats in one line, e.g. `"frame 120  4000 cells  2 timers  ~64.0 KiB"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "frame {}  {} cells  {} timers  ~{}",
+            self.frames,
+            self.buffer_cells,
+            self.timers,
+            Bytes(self.memory)
+        )
+    }
+}
+
+/// A number of bytes, formatted in B, KiB or MiB.
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            bytes if bytes < 1024 => write!(f, "{bytes} B"),
+            bytes if bytes < 1024 * 1024 => write!(f, "{:.1} KiB", bytes as f64 / 1024.0),
+            bytes => write!(f, "{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+        }
+    }
+}
+
+/// Returns the bytes a text owns: its capacity if it is owned, nothing if it is borrowed.
+// Whether the text is owned is the point, so it cannot take a `&str`.
+#[allow(clippy::ptr_arg)]
+pub(crate) fn owned(text: &Cow<str>) -> usize {
+    match text {
+        Cow::Owned(text) => text.capacity(),
+        Cow::Borrowed(_) => 0,
+    }
+}
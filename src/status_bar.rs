@@ -0,0 +1,344 @@
+//! This module provides the `StatusBar` widget, a single row of status segments, usually pinned to the bottom of the screen.
+//!
+//! A status bar has three slots: segments on the left (e.g. the current mode), in the center (e.g. a message) and on the right (e.g. a clock or key hints). Every segment has an id and can be updated on its own. When the row is too narrow, the center gives way first, then the left slot; cut-off text ends with `…`.
+//!
+//! # Structs
+//!
+//! - `StatusBar`: The segments of the three slots and the style of the row.
+//!
+//! # Enums
+//!
+//! - `Slot`: Where a segment goes: left, center or right.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates an empty status bar.
+//! - `left(id, text)` / `center(id, text)` / `right(id, text)`: Add a segment to a slot.
+//! - `separator(text)` / `style(style)` / `theme(theme)`: Configure how segments are separated and the row is drawn.
+//! - `set(id, text)` / `set_style(id, style)` / `text(id)` / `remove(id)`: Update and query a segment.
+//! - `pin_to_bottom(area)`: Places the bar on the last row of a region.
+//! - `line()`: Returns the text of the row as it is drawn.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::cursor::Cursor;
+use crate::layout;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// The `Slot` enum tells where a segment of a [`StatusBar`] goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Slot {
+    /// After the segments before it, from the left edge.
+    Left,
+    /// In the middle of the bar.
+    Center,
+    /// Before the segments after it, ending at the right edge.
+    Right,
+}
+
+/// A segment of a status bar.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Segment<'a> {
+    id: Cow<'a, str>,
+    slot: Slot,
+    text: Cow<'a, str>,
+    style: NyanStyle,
+}
+
+/// `StatusBar` is a row of independently updated status segments.
+///
+/// # Example
+/// ```rust
+/// use nyan::rect::Rect;
+/// use nyan::status_bar::StatusBar;
+/// use nyan::widget::Widget;
+///
+/// let mut status = StatusBar::new()
+///     .left("mode", "NORMAL")
+///     .center("message", "3 files saved")
+///     .right("hints", "^Q quit")
+///     .right("clock", "12:00");
+/// status.pin_to_bottom(Rect::new(0, 0, 80, 24));
+/// assert_eq!(status.area(), Rect::new(0, 23, 80, 1));
+///
+/// status.set("clock", "12:01");
+/// assert_eq!(status.text("clock"), Some("12:01"));
+///
+/// // Narrow rows give up the center first.
+/// status.set_area(Rect::new(0, 0, 24, 1));
+/// assert_eq!(status.line(), " NORMAL ^Q quit │ 12:01 ");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StatusBar<'a> {
+    segments: Vec<Segment<'a>>,
+    separator: Cow<'a, str>,
+    style: NyanStyle,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> Default for StatusBar<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> StatusBar<'a> {
+    /// Creates a status bar without segments, drawn reversed.
+    pub fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            separator: Cow::Borrowed(" │ "),
+            style: NyanStyle::new().reverse(),
+            theme: Theme::default(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Adds a segment to the left slot.
+    pub fn left<P: Into<Cow<'a, str>>, T: Into<Cow<'a, str>>>(self, id: P, text: T) -> Self {
+        self.segment(id.into(), Slot::Left, text.into())
+    }
+
+    /// Adds a segment to the center slot.
+    pub fn center<P: Into<Cow<'a, str>>, T: Into<Cow<'a, str>>>(self, id: P, text: T) -> Self {
+        self.segment(id.into(), Slot::Center, text.into())
+    }
+
+    /// Adds a segment to the right slot.
+    pub fn right<P: Into<Cow<'a, str>>, T: Into<Cow<'a, str>>>(self, id: P, text: T) -> Self {
+        self.segment(id.into(), Slot::Right, text.into())
+    }
+
+    /// Sets the text drawn between two segments of a slot (`" │ "` by default).
+    pub fn separator<T: Into<Cow<'a, str>>>(self, text: T) -> Self {
+        let mut bar = self;
+        bar.separator = text.into();
+        bar
+    }
+
+    /// Sets the style of the whole row (reversed by default). Segment styles are layered on it.
+    pub fn style(self, style: NyanStyle) -> Self {
+        let mut bar = self;
+        bar.style = style;
+        bar
+    }
+
+    /// Sets the colors of the bar. The theme's muted style is used for the separators.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut bar = self;
+        bar.theme = theme;
+        bar
+    }
+
+    /// Replaces the text of the segment `id`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the bar has such a segment.
+    pub fn set<T: Into<Cow<'a, str>>>(&mut self, id: &str, text: T) -> bool {
+        match self.find(id) {
+            Some(segment) => {
+                segment.text = text.into();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the style of the segment `id`, e.g. to color the mode.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the bar has such a segment.
+    pub fn set_style(&mut self, id: &str, style: NyanStyle) -> bool {
+        match self.find(id) {
+            Some(segment) => {
+                segment.style = style;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the text of the segment `id`.
+    pub fn text(&self, id: &str) -> Option<&str> {
+        self.segments
+            .iter()
+            .find(|segment| segment.id == id)
+            .map(|segment| segment.text.as_ref())
+    }
+
+    /// Removes the segment `id`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the bar had such a segment.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.segments.len();
+        self.segments.retain(|segment| segment.id != id);
+        self.segments.len() != before
+    }
+
+    /// Places the bar on the last row of `area`, as wide as it.
+    pub fn pin_to_bottom(&mut self, area: Rect) {
+        let y = area.bottom().saturating_sub(1);
+        self.set_area(Rect::new(area.x, y, area.width, area.height.min(1)));
+    }
+
+    /// Returns the text of the row as it is drawn, without styles.
+    pub fn line(&self) -> String {
+        self.cells()
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect::<Vec<_>>()
+            .concat()
+    }
+
+    /// Adds a segment.
+    fn segment(self, id: Cow<'a, str>, slot: Slot, text: Cow<'a, str>) -> Self {
+        let mut bar = self;
+        bar.segments.push(Segment {
+            id,
+            slot,
+            text,
+            style: NyanStyle::new(),
+        });
+        bar
+    }
+
+    /// Returns the segment `id`.
+    fn find(&mut self, id: &str) -> Option<&mut Segment<'a>> {
+        self.segments.iter_mut().find(|segment| segment.id == id)
+    }
+
+    /// Returns the styled pieces of a slot, fitted into `width` cells.
+    fn slot(&self, slot: Slot, width: usize) -> Vec<(String, NyanStyle)> {
+        let mut pieces = Vec::new();
+        let mut left = width;
+        let segments = self.segments.iter().filter(|s| s.slot == slot);
+        for (index, segment) in segments.enumerate() {
+            if index > 0 {
+                if left <= self.separator.width() {
+                    break;
+                }
+                pieces.push((self.separator.to_string(), self.theme.muted));
+                left -= self.separator.width();
+            }
+            if left == 0 {
+                break;
+            }
+            let text = truncate(&segment.text, left);
+            left -= text.width();
+            let cut = text.width() < segment.text.width();
+            pieces.push((text, segment.style));
+            if cut || left == 0 {
+                break;
+            }
+        }
+        pieces
+    }
+
+    /// Returns the styled pieces of the whole row, padded to the area's width.
+    fn cells(&self) -> Vec<(String, NyanStyle)> {
+        let width = self.area.width as usize;
+        // One cell of padding at both ends, and at least one cell between two slots.
+        let inner = width.saturating_sub(2);
+        let total = |pieces: &[(String, NyanStyle)]| -> usize {
+            pieces.iter().map(|(text, _)| text.width()).sum()
+        };
+
+        let right = self.slot(Slot::Right, inner);
+        let right_width = total(&right);
+        let left = self.slot(Slot::Left, inner.saturating_sub(right_width + 1));
+        let left_width = total(&left);
+        let free = inner.saturating_sub(left_width + right_width);
+        let center = self.slot(Slot::Center, free.saturating_sub(2));
+        let center_width = total(&center);
+
+        // Centered in the row if it fits there, otherwise as close to the middle as possible.
+        let ideal = (inner.saturating_sub(center_width)) / 2;
+        let center_start = if center.is_empty() {
+            left_width
+        } else {
+            ideal.clamp(
+                left_width + 1,
+                (inner - right_width - center_width - 1).max(left_width + 1),
+            )
+        };
+
+        let mut cells = vec![(" ".to_string(), NyanStyle::new())];
+        cells.extend(left);
+        cells.push((" ".repeat(center_start - left_width), NyanStyle::new()));
+        cells.extend(center);
+        let used = center_start + center_width;
+        cells.push((
+            " ".repeat(inner.saturating_sub(used + right_width)),
+            NyanStyle::new(),
+        ));
+        cells.extend(right);
+        if width > 1 {
+            cells.push((" ".to_string(), NyanStyle::new()));
+        }
+        cells
+    }
+}
+
+impl<'a> Widget for StatusBar<'a> {
+    /// Every segment with its separators and a cell between the slots and at both ends.
+    fn size(&self) -> (u16, u16) {
+        let slots = [Slot::Left, Slot::Center, Slot::Right];
+        let widths: Vec<usize> = slots
+            .iter()
+            .map(|&slot| {
+                let segments: Vec<_> = self.segments.iter().filter(|s| s.slot == slot).collect();
+                let text: usize = segments.iter().map(|s| s.text.width()).sum();
+                text + self.separator.width() * segments.len().saturating_sub(1)
+            })
+            .collect();
+        let gaps = widths.iter().filter(|&&w| w > 0).count().saturating_sub(1) * 2;
+        let width = widths.iter().sum::<usize>() + gaps + 2;
+        (width.min(u16::MAX as usize) as u16, 1)
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() {
+            return Ok(());
+        }
+        Cursor::Move(self.area.x, self.area.y).queue(&mut out)?;
+        for (text, style) in self.cells() {
+            widget::write_styled(out, self.style.patch(style), &text)?;
+        }
+        out.flush()
+    }
+}
+
+/// Cuts `text` to at most `width` cells, ending it with `…` if it was cut.
+fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut cut = layout::clip_text(text, 0, width - 1);
+    // A wide character cut in half became a space.
+    let trimmed = cut.trim_end().len();
+    cut.truncate(trimmed);
+    cut.push('…');
+    cut
+}
@@ -0,0 +1,360 @@
+//! This module defines the `NyanStyle` struct, which describes how drawn text looks.
+//!
+//! A style combines an optional foreground color, an optional background color and a set of text attributes (bold, dim, italic, underline, blink, reverse, strikethrough, ...). Styles are built with a fluent builder and can be attached to any object in a `NyanObj`. The style is applied right before the object is drawn and reset right after it.
+//!
+//! # Structs
+//!
+//! - `NyanStyle`: A foreground color, a background color and text attributes.
+//! - `Attributes`: A set of `Attribute`s.
+//!
+//! # Enums
+//!
+//! - `Attribute`: A text attribute such as bold or underline.
+//!
+//! # Methods
+//!
+//! - `NyanStyle::new()`: Creates an empty style that leaves the terminal's look unchanged.
+//! - `fg(color)` / `bg(color)`: Set the foreground / background color.
+//! - `bold()` / `dim()` / `italic()` / `underline()` / `blink()` / `reverse()` / `strikethrough()`: Add a text attribute; `attribute(attribute)` adds any other `Attribute`.
+//! - `apply(out)`: Writes the escape sequence that switches the terminal to this style, downgrading colors the terminal cannot show.
+//! - `patch(other)`: Returns this style with the colors and attributes set in `other` layered on top.
+//! - `NyanStyle::reset(out)`: Writes the escape sequence that resets the styling to the base style.
+//! - `NyanStyle::base()` / `NyanStyle::set_base(style)`: Get / set the base style that every object drawn on the current thread inherits (set by an `App` with `App::default_style` during its frames).
+//!
+//! # Example
+//!
+//! ```rust
+//! use nyan::color::{self, ColorSupport, NyanColor};
+//! use nyan::style::{Attribute, NyanStyle};
+//!
+//! color::set_color_support(ColorSupport::TrueColor);
+//! let style = NyanStyle::new().fg(NyanColor::Red).bg(NyanColor::Black).bold();
+//!
+//! assert_eq!(style.fg, Some(NyanColor::Red));
+//! assert!(style.attributes.contains(Attribute::Bold));
+//!
+//! let mut out = Vec::new();
+//! style.apply(&mut out).unwrap();
+//! assert_eq!(out, b"\x1b[1;31;40m");
+//! ```
+
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::io::{self, Write};
+
+use crate::color::{self, ColorSupport, NyanColor};
+
+/// The `Attribute` enum represents a text attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Attribute {
+    Bold,
+    /// Decreased intensity (faint text).
+    Dim,
+    Italic,
+    Underline,
+    /// Blinks less than 150 times per minute.
+    SlowBlink,
+    /// Blinks 150 times per minute or more (not widely supported).
+    RapidBlink,
+    /// Swaps the foreground and background colors.
+    Reverse,
+    /// Hides the text (not widely supported).
+    Hidden,
+    /// Draws a line through the text.
+    CrossedOut,
+    DoubleUnderline,
+    Overlined,
+}
+
+impl Attribute {
+    /// Every attribute, in the order their SGR parameters are written.
+    pub const ALL: [Attribute; 11] = [
+        Attribute::Bold,
+        Attribute::Dim,
+        Attribute::Italic,
+        Attribute::Underline,
+        Attribute::SlowBlink,
+        Attribute::RapidBlink,
+        Attribute::Reverse,
+        Attribute::Hidden,
+        Attribute::CrossedOut,
+        Attribute::DoubleUnderline,
+        Attribute::Overlined,
+    ];
+
+    /// Returns the SGR parameter that enables this attribute.
+    pub fn sgr(self) -> u8 {
+        match self {
+            Attribute::Bold => 1,
+            Attribute::Dim => 2,
+            Attribute::Italic => 3,
+            Attribute::Underline => 4,
+            Attribute::SlowBlink => 5,
+            Attribute::RapidBlink => 6,
+            Attribute::Reverse => 7,
+            Attribute::Hidden => 8,
+            Attribute::CrossedOut => 9,
+            Attribute::DoubleUnderline => 21,
+            Attribute::Overlined => 53,
+        }
+    }
+
+    fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+/// A set of text attributes.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Attributes(u16);
+
+impl Debug for Attributes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Attributes {
+    /// Returns an empty set.
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns whether the set contains no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns whether the set contains `attribute`.
+    pub fn contains(&self, attribute: Attribute) -> bool {
+        self.0 & attribute.bit() != 0
+    }
+
+    /// Adds `attribute` to the set.
+    pub fn insert(&mut self, attribute: Attribute) {
+        self.0 |= attribute.bit();
+    }
+
+    /// Removes `attribute` from the set.
+    pub fn remove(&mut self, attribute: Attribute) {
+        self.0 &= !attribute.bit();
+    }
+
+    /// Iterates over the attributes in the set.
+    pub fn iter(&self) -> impl Iterator<Item = Attribute> + '_ {
+        Attribute::ALL.into_iter().filter(|a| self.contains(*a))
+    }
+}
+
+// Attributes are written as a list of their names, e.g. `["Bold", "Italic"]`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Attributes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Attributes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut attributes = Self::empty();
+        for attribute in Vec::<Attribute>::deserialize(deserializer)? {
+            attributes.insert(attribute);
+        }
+        Ok(attributes)
+    }
+}
+
+thread_local! {
+    /// The style every drawn object inherits, set by an `App` while it draws a frame on this
+    /// thread, so apps on other threads (a renderer, remote sessions, tests) keep their own.
+    static BASE_STYLE: Cell<NyanStyle> = const {
+        Cell::new(NyanStyle {
+            fg: None,
+            bg: None,
+            attributes: Attributes(0),
+        })
+    };
+}
+
+/// `NyanStyle` describes the colors and attributes of drawn text.
+///
+/// `None` colors leave the terminal's current color unchanged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NyanStyle {
+    /// The foreground (text) color.
+    pub fg: Option<NyanColor>,
+    /// The background color.
+    pub bg: Option<NyanColor>,
+    /// The text attributes.
+    pub attributes: Attributes,
+}
+
+impl NyanStyle {
+    /// Creates an empty style that leaves the terminal's look unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the foreground color.
+    pub fn fg(self, color: NyanColor) -> Self {
+        let mut style = self;
+        style.fg = Some(color);
+        style
+    }
+
+    /// Sets the background color.
+    pub fn bg(self, color: NyanColor) -> Self {
+        let mut style = self;
+        style.bg = Some(color);
+        style
+    }
+
+    /// Adds a text attribute.
+    pub fn attribute(self, attribute: Attribute) -> Self {
+        let mut style = self;
+        style.attributes.insert(attribute);
+        style
+    }
+
+    /// Makes the text bold.
+    pub fn bold(self) -> Self {
+        self.attribute(Attribute::Bold)
+    }
+
+    /// Makes the text italic.
+    pub fn italic(self) -> Self {
+        self.attribute(Attribute::Italic)
+    }
+
+    /// Underlines the text.
+    pub fn underline(self) -> Self {
+        self.attribute(Attribute::Underline)
+    }
+
+    /// Makes the text dim (faint).
+    pub fn dim(self) -> Self {
+        self.attribute(Attribute::Dim)
+    }
+
+    /// Makes the text blink.
+    pub fn blink(self) -> Self {
+        self.attribute(Attribute::SlowBlink)
+    }
+
+    /// Swaps the foreground and background colors, e.g. for status bars and selections.
+    pub fn reverse(self) -> Self {
+        self.attribute(Attribute::Reverse)
+    }
+
+    /// Draws a line through the text.
+    pub fn strikethrough(self) -> Self {
+        self.attribute(Attribute::CrossedOut)
+    }
+
+    /// Returns whether the style leaves the terminal's look unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.attributes.is_empty()
+    }
+
+    /// Returns this style with `other` layered on top.
+    ///
+    /// Colors set in `other` replace the colors of this style, and the attributes of both
+    /// styles are combined.
+    pub fn patch(self, other: NyanStyle) -> Self {
+        Self {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            attributes: Attributes(self.attributes.0 | other.attributes.0),
+        }
+    }
+
+    /// Returns the base style that every drawn object inherits.
+    ///
+    /// The base style belongs to the current thread. It is empty, except while an `App` with
+    /// a default style draws a frame (see
+    /// [`App::default_style`](crate::app::App::default_style)).
+    pub fn base() -> Self {
+        BASE_STYLE.with(Cell::get)
+    }
+
+    /// Sets the base style that every drawn object on the current thread inherits.
+    pub fn set_base(style: NyanStyle) {
+        BASE_STYLE.with(|base| base.set(style));
+    }
+
+    /// Returns the SGR parameters of this style, separated by `;`.
+    pub fn sgr(&self) -> String {
+        Sgr(self).to_string()
+    }
+
+    /// Returns this style with its colors downgraded to what a terminal with the given color
+    /// support can show (see [`NyanColor::downgrade`]). Without color support the colors are
+    /// removed and only the attributes are kept.
+    pub fn downgrade(self, support: ColorSupport) -> Self {
+        let mut style = self;
+        if support == ColorSupport::NoColor {
+            // Strip the colors, but keep attributes such as bold or reverse.
+            style.fg = None;
+            style.bg = None;
+            return style;
+        }
+        style.fg = style.fg.map(|c| c.downgrade(support));
+        style.bg = style.bg.map(|c| c.downgrade(support));
+        style
+    }
+
+    /// Writes the escape sequence that switches the terminal to this style.
+    ///
+    /// Colors are downgraded to the terminal's color support (see [`color::color_support`]).
+    /// Nothing is written for an empty style.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(std::io::Error)` if writing fails.
+    pub fn apply<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let style = self.downgrade(color::color_support());
+        if style.is_empty() {
+            return Ok(());
+        }
+        write!(out, "\x1b[{}m", Sgr(&style))
+    }
+
+    /// Writes the escape sequence that resets all colors and attributes to the base style
+    /// (see [`NyanStyle::base`]).
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(std::io::Error)` if writing fails.
+    pub fn reset<W: Write>(out: &mut W) -> io::Result<()> {
+        write!(out, "\x1b[0m")?;
+        Self::base().apply(out)
+    }
+}
+
+/// Formats the SGR parameters of a style straight into the output, so applying a style does
+/// not allocate.
+struct Sgr<'a>(&'a NyanStyle);
+
+impl std::fmt::Display for Sgr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut separator = "";
+        for attribute in self.0.attributes.iter() {
+            write!(f, "{separator}{}", attribute.sgr())?;
+            separator = ";";
+        }
+        if let Some(fg) = self.0.fg {
+            f.write_str(separator)?;
+            fg.write_code(f, false)?;
+            separator = ";";
+        }
+        if let Some(bg) = self.0.bg {
+            f.write_str(separator)?;
+            bg.write_code(f, true)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,707 @@
+//! This module provides the `TableView` widget, an interactive table with a selected row, scrolling and sortable columns.
+//!
+//! A table has a header row with the column titles and any number of data rows below it. Column widths are given as `StackSize`s, like the children of a `Stack`: a fixed number of cells, the width of the widest cell (`Auto`), or a share of the remaining space. The selected row is highlighted and kept in view while the table scrolls, and the rows can be sorted by any column, either from code, by pressing the column's number (1 to 9) or by clicking its title; sorting the same column again reverses the order. Values that all parse as numbers are sorted numerically.
+//!
+//! # Structs
+//!
+//! - `Column`: The title, width and alignment of a column.
+//! - `TableView`: The columns, the rows and the selection, sort and scroll state.
+//!
+//! # Enums
+//!
+//! - `SortOrder`: Whether a column is sorted ascending or descending.
+//!
+//! # Methods
+//!
+//! - `Column::new(title)` / `Column::width(size)` / `Column::align(align)`: Describe a column.
+//! - `TableView::new(columns)`: Creates an empty table.
+//! - `TableView::from_csv(reader)` / `TableView::from_json(value, path)`: Create a table filled from CSV data or a JSON array of objects (`csv` / `json` features).
+//! - `row(cells)` / `column_spacing(cells)` / `theme(theme)`: Add rows and configure the table.
+//! - `set_rows(rows)` / `push_row(cells)` / `len()` / `is_empty()`: Change and count the rows.
+//! - `selected()` / `selected_row()` / `select(position)`: Query and move the selection.
+//! - `sort_by(column, order)` / `toggle_sort(column)` / `sorted()` / `unsort()`: Sort the rows.
+//! - `column_widths()`: Returns the width of each column in the current area.
+//! - `take_activation()`: Returns the row activated with Enter or a click on the selection.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::cursor::Cursor;
+#[cfg(any(feature = "csv", feature = "json"))]
+use crate::errors::{NyanError, NyanResult};
+use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::{NyanInput, NyanKey};
+use crate::layout::{self, Align, StackSize};
+use crate::rect::Rect;
+use crate::scrollbar::Scrollbar;
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// The `SortOrder` enum tells in which direction a [`TableView`] is sorted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    /// Smallest value first.
+    Ascending,
+    /// Largest value first.
+    Descending,
+}
+
+/// `Column` describes one column of a [`TableView`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Column<'a> {
+    title: Cow<'a, str>,
+    width: StackSize,
+    align: Align,
+}
+
+impl<'a> Column<'a> {
+    /// Creates a column as wide as its widest cell, with its values aligned to the left.
+    pub fn new<T: Into<Cow<'a, str>>>(title: T) -> Self {
+        Self {
+            title: title.into(),
+            width: StackSize::Auto,
+            align: Align::Start,
+        }
+    }
+
+    /// Sets the width of the column: `Fixed(cells)`, `Auto` (the widest cell, including the
+    /// title) or `Flex(weight)` (a share of the space left by the other columns).
+    pub fn width(self, width: StackSize) -> Self {
+        let mut column = self;
+        column.width = width;
+        column
+    }
+
+    /// Sets how the values (and the title) are aligned in the column, e.g. `Align::End` for
+    /// numbers.
+    pub fn align(self, align: Align) -> Self {
+        let mut column = self;
+        column.align = align;
+        column
+    }
+}
+
+/// `TableView` is a table whose rows can be selected, scrolled and sorted.
+///
+/// # Example
+/// ```rust
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::layout::Align;
+/// use nyan::table_view::{Column, SortOrder, TableView};
+/// use nyan::widget::Widget;
+///
+/// let mut table = TableView::new([Column::new("Name"), Column::new("Size").align(Align::End)])
+///     .row(["notes.txt", "120"])
+///     .row(["photo.png", "20480"])
+///     .row(["a.out", "9"]);
+/// table.set_focused(true);
+///
+/// // Press "2" to sort by size; the values are compared as numbers.
+/// table.handle_input(&NyanInput::Key(NyanKey::OtherKey('2')));
+/// assert_eq!(table.sorted(), Some((1, SortOrder::Ascending)));
+/// assert_eq!(table.selected_row().unwrap()[0], "notes.txt"); // the selection stays
+///
+/// table.handle_input(&NyanInput::Home);
+/// assert_eq!(table.selected_row().unwrap()[0], "a.out");
+/// assert_eq!(table.selected(), Some(2)); // the index the row was added at
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TableView<'a> {
+    columns: Vec<Column<'a>>,
+    rows: Vec<Vec<Cow<'a, str>>>,
+    /// The indices into `rows` in display order.
+    order: Vec<usize>,
+    sort: Option<(usize, SortOrder)>,
+    /// The display position of the selected row.
+    selected: usize,
+    offset: usize,
+    column_spacing: u16,
+    activated: Option<usize>,
+    focused: bool,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> TableView<'a> {
+    /// Creates a table with the given columns and no rows.
+    pub fn new<I: IntoIterator<Item = Column<'a>>>(columns: I) -> Self {
+        Self {
+            columns: columns.into_iter().collect(),
+            rows: Vec::new(),
+            order: Vec::new(),
+            sort: None,
+            selected: 0,
+            offset: 0,
+            column_spacing: 1,
+            activated: None,
+            focused: false,
+            theme: Theme::default(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Adds a row. Missing cells are left empty and extra cells are ignored.
+    pub fn row<T: Into<Cow<'a, str>>, I: IntoIterator<Item = T>>(self, cells: I) -> Self {
+        let mut table = self;
+        table.push_row(cells);
+        table
+    }
+
+    /// Sets the space between two columns (1 cell by default).
+    pub fn column_spacing(self, cells: u16) -> Self {
+        let mut table = self;
+        table.column_spacing = cells;
+        table
+    }
+
+    /// Sets the colors of the table.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut table = self;
+        table.theme = theme;
+        table
+    }
+
+    /// Replaces all rows, keeping the sort column and selecting the first row.
+    pub fn set_rows<T, R, I>(&mut self, rows: I)
+    where
+        T: Into<Cow<'a, str>>,
+        R: IntoIterator<Item = T>,
+        I: IntoIterator<Item = R>,
+    {
+        self.rows.clear();
+        self.order.clear();
+        for row in rows {
+            self.push_row(row);
+        }
+        self.select(0);
+    }
+
+    /// Appends a row, sorted into place if the table is sorted.
+    pub fn push_row<T: Into<Cow<'a, str>>, I: IntoIterator<Item = T>>(&mut self, cells: I) {
+        let mut cells: Vec<Cow<'a, str>> = cells.into_iter().map(Into::into).collect();
+        cells.resize(self.columns.len(), Cow::Borrowed(""));
+        self.rows.push(cells);
+        self.order.push(self.rows.len() - 1);
+        self.resort();
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns whether the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the index (in the order the rows were added) of the selected row.
+    pub fn selected(&self) -> Option<usize> {
+        self.order.get(self.selected).copied()
+    }
+
+    /// Returns the cells of the selected row.
+    pub fn selected_row(&self) -> Option<&[Cow<'a, str>]> {
+        self.selected().map(|index| self.rows[index].as_slice())
+    }
+
+    /// Selects the row shown at `position` (0 is the first row below the header), clamped to
+    /// the last row, and scrolls it into view.
+    pub fn select(&mut self, position: usize) {
+        self.selected = position.min(self.rows.len().saturating_sub(1));
+        self.scroll_to_selected();
+    }
+
+    /// Sorts the rows by `column`. The selected row stays selected.
+    pub fn sort_by(&mut self, column: usize, order: SortOrder) {
+        if column < self.columns.len() {
+            self.sort = Some((column, order));
+            self.resort();
+        }
+    }
+
+    /// Sorts the rows by `column` ascending, or reverses the order if they already are sorted
+    /// by it.
+    pub fn toggle_sort(&mut self, column: usize) {
+        let order = match self.sort {
+            Some((sorted, SortOrder::Ascending)) if sorted == column => SortOrder::Descending,
+            _ => SortOrder::Ascending,
+        };
+        self.sort_by(column, order);
+    }
+
+    /// Returns the column the rows are sorted by and in which order.
+    pub fn sorted(&self) -> Option<(usize, SortOrder)> {
+        self.sort
+    }
+
+    /// Shows the rows in the order they were added again.
+    pub fn unsort(&mut self) {
+        self.sort = None;
+        self.resort();
+    }
+
+    /// Returns the width of each column when the table is drawn in its area.
+    pub fn column_widths(&self) -> Vec<u16> {
+        let width = self
+            .area
+            .width
+            .saturating_sub(u16::from(self.needs_scrollbar()));
+        self.widths(width)
+    }
+
+    /// Returns the row (its index in the order the rows were added) activated with Enter, or by
+    /// clicking the selected row, since the last call, and forgets it.
+    pub fn take_activation(&mut self) -> Option<usize> {
+        self.activated.take()
+    }
+
+    /// Sorts `order` by the sort column, keeping the same row selected.
+    fn resort(&mut self) {
+        let selected = self.selected();
+        match self.sort {
+            Some((column, order)) => {
+                let rows = &self.rows;
+                let numeric = rows
+                    .iter()
+                    .all(|row| row[column].trim().parse::<f64>().is_ok());
+                let compare = |a: &usize, b: &usize| {
+                    let (a, b) = (&rows[*a][column], &rows[*b][column]);
+                    let ordering = if numeric {
+                        let a: f64 = a.trim().parse().unwrap_or_default();
+                        let b: f64 = b.trim().parse().unwrap_or_default();
+                        // A total order, as "NaN" parses too.
+                        a.total_cmp(&b)
+                    } else {
+                        a.to_lowercase().cmp(&b.to_lowercase())
+                    };
+                    match order {
+                        SortOrder::Ascending => ordering,
+                        SortOrder::Descending => ordering.reverse(),
+                    }
+                };
+                // Equal rows stay in the order they were added.
+                self.order.sort_by(|a, b| compare(a, b).then(a.cmp(b)));
+            }
+            None => self.order.sort_unstable(),
+        }
+        if let Some(selected) = selected {
+            let position = self.order.iter().position(|&index| index == selected);
+            self.select(position.unwrap_or(0));
+        }
+    }
+
+    /// Returns how many rows fit below the header.
+    fn visible_rows(&self) -> usize {
+        self.area.height.saturating_sub(1) as usize
+    }
+
+    /// Returns whether the rows do not fit, so a scrollbar is drawn.
+    fn needs_scrollbar(&self) -> bool {
+        self.rows.len() > self.visible_rows()
+    }
+
+    /// Returns the natural width of the column at `index`: its widest cell, leaving room for
+    /// the sort arrow after the title.
+    fn natural_width(&self, index: usize) -> u16 {
+        let title = self.columns[index].title.width() + 2;
+        let widest = self
+            .rows
+            .iter()
+            .map(|row| row[index].width())
+            .max()
+            .unwrap_or(0);
+        title.max(widest).min(u16::MAX as usize) as u16
+    }
+
+    /// Splits `width` cells between the columns, like a horizontal `Stack`.
+    fn widths(&self, width: u16) -> Vec<u16> {
+        let gaps = self
+            .column_spacing
+            .saturating_mul(self.columns.len().saturating_sub(1) as u16);
+        let own = |index: usize| match self.columns[index].width {
+            StackSize::Fixed(cells) => cells,
+            StackSize::Auto => self.natural_width(index),
+            StackSize::Flex(_) => 0,
+        };
+        let used = (0..self.columns.len()).fold(gaps, |sum, i| sum.saturating_add(own(i)));
+        let free = width.saturating_sub(used) as u32;
+        let weights: u32 = self
+            .columns
+            .iter()
+            .map(|c| match c.width {
+                StackSize::Flex(weight) => weight as u32,
+                _ => 0,
+            })
+            .sum();
+
+        let mut left = width;
+        let mut widths = Vec::with_capacity(self.columns.len());
+        for (index, column) in self.columns.iter().enumerate() {
+            let size = match column.width {
+                StackSize::Flex(weight) if weights > 0 => (free * weight as u32 / weights) as u16,
+                _ => own(index),
+            };
+            let size = size.min(left);
+            widths.push(size);
+            left = left.saturating_sub(size + self.column_spacing);
+        }
+        widths
+    }
+
+    /// Returns the column under the x coordinate `x`.
+    fn column_at(&self, x: u16) -> Option<usize> {
+        let mut start = self.area.x;
+        for (index, width) in self.column_widths().into_iter().enumerate() {
+            if x >= start && x < start + width {
+                return Some(index);
+            }
+            start += width + self.column_spacing;
+        }
+        None
+    }
+
+    /// Moves the selection by `delta` rows.
+    fn move_selection(&mut self, delta: isize) {
+        self.select(self.selected.saturating_add_signed(delta));
+    }
+
+    /// Scrolls so the selected row is visible.
+    fn scroll_to_selected(&mut self) {
+        let rows = self.visible_rows().max(1);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + rows {
+            self.offset = self.selected + 1 - rows;
+        }
+    }
+
+    /// Scrolls by `delta` rows without moving the selection.
+    fn scroll_by(&mut self, delta: isize) {
+        let max = self.rows.len().saturating_sub(self.visible_rows());
+        self.offset = self.offset.saturating_add_signed(delta).min(max);
+    }
+
+    /// Returns the cells joined into one line, each padded to its column's width.
+    fn line<'c, I: IntoIterator<Item = Cow<'c, str>>>(&self, cells: I, widths: &[u16]) -> String {
+        let gap = " ".repeat(self.column_spacing as usize);
+        let cells: Vec<String> = cells
+            .into_iter()
+            .zip(widths)
+            .zip(&self.columns)
+            .map(|((cell, &width), column)| {
+                let cell = layout::clip_text(&cell, 0, width as usize);
+                layout::align_text(&cell, width, column.align)
+            })
+            .collect();
+        cells.join(&gap)
+    }
+}
+
+#[cfg(any(feature = "csv", feature = "json"))]
+impl TableView<'static> {
+    /// Creates a table with a column for each title and the given rows. Columns whose values
+    /// are all numbers are aligned to the right.
+    fn from_records(titles: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        let columns: Vec<Column<'static>> = titles
+            .into_iter()
+            .enumerate()
+            .map(|(index, title)| {
+                let numeric = rows.iter().any(|row| row.get(index).is_some())
+                    && rows.iter().all(|row| match row.get(index) {
+                        Some(cell) => cell.trim().parse::<f64>().is_ok(),
+                        None => true,
+                    });
+                let align = if numeric { Align::End } else { Align::Start };
+                Column::new(title).align(align)
+            })
+            .collect();
+        let mut table = Self::new(columns);
+        table.set_rows(rows);
+        table
+    }
+}
+
+#[cfg(feature = "csv")]
+impl TableView<'static> {
+    /// Creates a table from CSV data: the first record holds the column titles and every other
+    /// record is a row. Columns of numbers are aligned to the right.
+    ///
+    /// Requires the `csv` feature.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(TableView)` with a row for each record.
+    /// - An error if the data cannot be read or is not valid CSV.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "csv")]
+    /// # {
+    /// use nyan::table_view::TableView;
+    ///
+    /// let data = "name,size\nnotes.txt,120\nphoto.png,20480\n";
+    /// let table = TableView::from_csv(data.as_bytes()).unwrap();
+    /// assert_eq!(table.len(), 2);
+    /// assert_eq!(table.selected_row().unwrap()[1], "120");
+    /// # }
+    /// ```
+    pub fn from_csv<R: io::Read>(reader: R) -> NyanResult<Self> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+        let invalid = |e: csv::Error| NyanError::InvalidData(Cow::Owned(e.to_string()));
+        let titles = reader
+            .headers()
+            .map_err(invalid)?
+            .iter()
+            .map(str::to_string)
+            .collect();
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            rows.push(
+                record
+                    .map_err(invalid)?
+                    .iter()
+                    .map(str::to_string)
+                    .collect(),
+            );
+        }
+        Ok(Self::from_records(titles, rows))
+    }
+}
+
+#[cfg(feature = "json")]
+impl TableView<'static> {
+    /// Creates a table from the array of objects found at `path` in `value`, a JSON pointer
+    /// such as `"/data/users"` (`""` for `value` itself). Each object is a row and each key a
+    /// column, in the order `serde_json` keeps the keys in (alphabetical unless its
+    /// `preserve_order` feature is on); strings are shown as they are, `null` and
+    /// missing keys as empty cells, and nested arrays and objects as JSON.
+    ///
+    /// Requires the `json` feature.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(TableView)` with a row for each object.
+    /// - An error if nothing is found at `path`, or it is not an array of objects.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "json")]
+    /// # {
+    /// use nyan::table_view::TableView;
+    ///
+    /// let value = serde_json::json!({
+    ///     "users": [
+    ///         { "name": "mike", "age": 3 },
+    ///         { "name": "tama", "age": 5 },
+    ///     ]
+    /// });
+    /// let table = TableView::from_json(&value, "/users").unwrap();
+    /// assert_eq!(table.len(), 2);
+    /// assert_eq!(table.selected_row().unwrap(), ["3", "mike"]); // "age", then "name"
+    /// # }
+    /// ```
+    pub fn from_json(value: &serde_json::Value, path: &str) -> NyanResult<Self> {
+        let items = json_array(value, path)?;
+        let mut titles: Vec<String> = Vec::new();
+        for item in items {
+            let Some(object) = item.as_object() else {
+                return Err(NyanError::InvalidData(Cow::Owned(format!(
+                    "\"{path}\" is not an array of objects"
+                ))));
+            };
+            for key in object.keys() {
+                if !titles.contains(key) {
+                    titles.push(key.clone());
+                }
+            }
+        }
+        let rows = items
+            .iter()
+            .map(|item| {
+                titles
+                    .iter()
+                    .map(|title| item.get(title).map(json_text).unwrap_or_default())
+                    .collect()
+            })
+            .collect();
+        Ok(Self::from_records(titles, rows))
+    }
+}
+
+/// Returns the array found at the JSON pointer `path` in `value`.
+#[cfg(feature = "json")]
+pub(crate) fn json_array<'v>(
+    value: &'v serde_json::Value,
+    path: &str,
+) -> NyanResult<&'v Vec<serde_json::Value>> {
+    let found = value.pointer(path).ok_or_else(|| {
+        NyanError::InvalidData(Cow::Owned(format!("nothing is found at \"{path}\"")))
+    })?;
+    found
+        .as_array()
+        .ok_or_else(|| NyanError::InvalidData(Cow::Owned(format!("\"{path}\" is not an array"))))
+}
+
+/// Returns how a JSON value is shown in a cell: strings without quotes, `null` as nothing and
+/// everything else as JSON.
+#[cfg(feature = "json")]
+pub(crate) fn json_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+impl<'a> Widget for TableView<'a> {
+    /// Every column at its natural width (flexible columns too), and every row.
+    fn size(&self) -> (u16, u16) {
+        let gaps = self.column_spacing as usize * self.columns.len().saturating_sub(1);
+        let width = (0..self.columns.len())
+            .map(|index| match self.columns[index].width {
+                StackSize::Fixed(cells) => cells as usize,
+                _ => self.natural_width(index) as usize,
+            })
+            .sum::<usize>()
+            + gaps;
+        let height = self.rows.len() + 1;
+        (
+            width.min(u16::MAX as usize) as u16,
+            height.min(u16::MAX as usize) as u16,
+        )
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+        self.scroll_to_selected();
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.area.is_empty() {
+            return Ok(());
+        }
+        let rows = self.visible_rows();
+        let bar = Scrollbar::new(self.rows.len(), rows, self.offset);
+        let width = self.area.width - u16::from(bar.is_needed());
+        let widths = self.widths(width);
+
+        let titles = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| match self.sort {
+                Some((sorted, SortOrder::Ascending)) if sorted == index => {
+                    Cow::Owned(format!("{} ▲", column.title))
+                }
+                Some((sorted, SortOrder::Descending)) if sorted == index => {
+                    Cow::Owned(format!("{} ▼", column.title))
+                }
+                _ => Cow::Borrowed(column.title.as_ref()),
+            });
+        let header = self.line(titles, &widths);
+        Cursor::Move(self.area.x, self.area.y).queue(&mut out)?;
+        widget::write_styled(
+            out,
+            self.theme.accent.bold(),
+            &layout::align_text(&header, self.area.width, Align::Start),
+        )?;
+
+        for (row, cell) in bar.cells(rows as u16).chars().enumerate() {
+            let position = self.offset + row;
+            let (line, style) = match self.order.get(position) {
+                Some(&index) => {
+                    let cells = self.rows[index].iter().map(|c| Cow::Borrowed(c.as_ref()));
+                    let style = match (position == self.selected, self.focused) {
+                        (true, true) => self.theme.selection,
+                        (true, false) => NyanStyle::new().reverse(),
+                        _ => self.theme.text,
+                    };
+                    (self.line(cells, &widths), style)
+                }
+                None => (String::new(), NyanStyle::new()),
+            };
+            let line = layout::clip_text(&line, 0, width as usize);
+            Cursor::Move(self.area.x, self.area.y + 1 + row as u16).queue(&mut out)?;
+            widget::write_styled(out, style, &layout::align_text(&line, width, Align::Start))?;
+            if bar.is_needed() {
+                widget::write_styled(out, self.theme.muted, &cell.to_string())?;
+            }
+        }
+        out.flush()
+    }
+
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        if !self.focused {
+            return false;
+        }
+        let page = self.visible_rows().max(1) as isize;
+        match input {
+            NyanInput::UpAllow => self.move_selection(-1),
+            NyanInput::DownAllow => self.move_selection(1),
+            NyanInput::PageUp => self.move_selection(-page),
+            NyanInput::PageDown => self.move_selection(page),
+            NyanInput::Home => self.select(0),
+            NyanInput::End => self.select(usize::MAX),
+            NyanInput::Enter => self.activated = self.selected(),
+            NyanInput::Key(NyanKey::OtherKey(digit @ '1'..='9')) => {
+                let column = *digit as usize - '1' as usize;
+                if column >= self.columns.len() {
+                    return false;
+                }
+                self.toggle_sort(column);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// A click on a title sorts by its column and a click on a row selects it; clicking the
+    /// selected row activates it. The wheel scrolls without moving the selection.
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        if !self.area.contains(mouse.x, mouse.y) {
+            return false;
+        }
+        match mouse.kind {
+            NyanMouseKind::Down(NyanMouseButton::Left) if mouse.y == self.area.y => {
+                if let Some(column) = self.column_at(mouse.x) {
+                    self.toggle_sort(column);
+                }
+            }
+            NyanMouseKind::Down(NyanMouseButton::Left) => {
+                let position = self.offset + (mouse.y - self.area.y - 1) as usize;
+                if position >= self.rows.len() {
+                    return true;
+                }
+                if position == self.selected {
+                    self.activated = self.selected();
+                } else {
+                    self.select(position);
+                }
+            }
+            NyanMouseKind::ScrollUp => self.scroll_by(-1),
+            NyanMouseKind::ScrollDown => self.scroll_by(1),
+            _ => {}
+        }
+        true
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
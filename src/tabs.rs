@@ -0,0 +1,299 @@
+//! This module provides the `Tabs` container, which shows one of several views in a region below a bar of tab titles.
+//!
+//! Each tab has a title and an object of a `NyanObj` as its content. All contents share the region below the tab bar; only the content of the selected tab is drawn. Tabs can be switched with `next_tab()` / `prev_tab()` / `select()`, with Alt + 1-9, Alt + N and Alt + P, or by clicking a title (mouse capture must be enabled on the `App`).
+//!
+//! # Structs
+//!
+//! - `Tabs`: A tab bar and the content objects of its tabs.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a container without tabs.
+//! - `tab(title, id)`: Adds a tab showing the object `id`.
+//! - `select(index)` / `next_tab()` / `prev_tab()`: Switch tabs.
+//! - `selected()` / `selected_id()` / `len()`: Query the tabs.
+//! - `regions(area)` / `title_regions(area)`: Return the `Rect`s of the tab bar and the content / of each title.
+//! - `apply(objects, area)`: Fits every tab's content into the content region.
+//! - `handle_input(input)` / `handle_mouse(mouse)` / `handle_event(event)`: Switch tabs from user input.
+//! - `is_hidden(id)`: Returns whether an object is the content of a tab that is not selected.
+//! - `draw_bar(out)` / `draw_in(objects, out)`: Draw the tab bar / the scene without the hidden tabs.
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::border;
+use crate::cursor::Cursor;
+use crate::errors::NyanResult;
+use crate::event::{NyanEvent, NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::{NyanInput, NyanKey};
+use crate::layout::{self, Layout};
+use crate::nyan_obj::NyanObj;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+
+/// A tab: its title and the id of its content object.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Tab<'a> {
+    title: Cow<'a, str>,
+    content: Cow<'a, str>,
+}
+
+/// `Tabs` shows a bar of tab titles and, below it, the content of the selected tab.
+///
+/// # Example
+/// ```ignore
+/// let mut tabs = Tabs::new().tab("Files", "files").tab("Search", "search");
+/// tabs.apply(&mut obj, Rect::terminal()?)?;
+///
+/// loop {
+///     let event = NyanEvent::get_event()?;
+///     tabs.handle_event(&event);
+///     app.draw_with(|app| tabs.draw_in(&obj, app).unwrap())?;
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tabs<'a> {
+    tabs: Vec<Tab<'a>>,
+    selected: usize,
+    /// The region the tabs were last applied to, for drawing and hit-testing the bar.
+    area: Cell<Rect>,
+}
+
+impl<'a> Tabs<'a> {
+    /// Creates a container without tabs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a tab titled `title` that shows the object `id`. The first tab is selected.
+    pub fn tab<T: Into<Cow<'a, str>>, P: Into<Cow<'a, str>>>(self, title: T, id: P) -> Self {
+        let mut tabs = self;
+        tabs.tabs.push(Tab {
+            title: title.into(),
+            content: id.into(),
+        });
+        tabs
+    }
+
+    /// Returns the number of tabs.
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Returns whether the container has no tabs.
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// Returns the index of the selected tab.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Returns the id of the selected tab's content object.
+    pub fn selected_id(&self) -> Option<&str> {
+        self.tabs.get(self.selected).map(|t| t.content.as_ref())
+    }
+
+    /// Selects the tab at `index`.
+    ///
+    /// # Returns
+    /// `true` if the selected tab changed; an `index` past the last tab is ignored.
+    pub fn select(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() || index == self.selected {
+            return false;
+        }
+        self.selected = index;
+        true
+    }
+
+    /// Selects the next tab, wrapping around after the last one.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::tabs::Tabs;
+    ///
+    /// let mut tabs = Tabs::new().tab("Files", "files").tab("Search", "search");
+    /// assert!(tabs.next_tab());
+    /// assert_eq!(tabs.selected_id(), Some("search"));
+    /// assert!(tabs.next_tab());
+    /// assert_eq!(tabs.selected(), 0);
+    /// ```
+    pub fn next_tab(&mut self) -> bool {
+        match self.tabs.len() {
+            0 => false,
+            len => self.select((self.selected + 1) % len),
+        }
+    }
+
+    /// Selects the previous tab, wrapping around before the first one.
+    pub fn prev_tab(&mut self) -> bool {
+        match self.tabs.len() {
+            0 => false,
+            len => self.select((self.selected + len - 1) % len),
+        }
+    }
+
+    /// Returns the rectangles of the tab bar (the first row) and the content region below it
+    /// when the tabs fill `area`.
+    pub fn regions(&self, area: Rect) -> (Rect, Rect) {
+        let bar = area.height.min(1);
+        (
+            Rect::new(area.x, area.y, area.width, bar),
+            Rect::new(area.x, area.y + bar, area.width, area.height - bar),
+        )
+    }
+
+    /// Returns the rectangle of every title in the tab bar when the tabs fill `area`.
+    ///
+    /// Each title is padded with a space on both sides, and titles are separated by a one cell
+    /// wide line. Titles that do not fit into `area` are cut off.
+    pub fn title_regions(&self, area: Rect) -> Vec<Rect> {
+        let (bar, _) = self.regions(area);
+        let mut x = bar.x;
+        self.tabs
+            .iter()
+            .map(|tab| {
+                let width = (tab.title.width() + 2).min(u16::MAX as usize) as u16;
+                let width = width.min(bar.right().saturating_sub(x));
+                let title = Rect::new(x, bar.y, width, bar.height);
+                x = x.saturating_add(width).saturating_add(1).min(bar.right());
+                title
+            })
+            .collect()
+    }
+
+    /// Fits the content of every tab into the content region when the tabs fill `area` (see
+    /// [`NyanObj::fit_object`]), so switching tabs needs no new layout.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if all contents were placed.
+    /// - An error of type [`NyanError::ObjectNotFound`](crate::errors::NyanError) if a content
+    ///   object does not exist in `objects`.
+    pub fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        self.area.set(area);
+        let (_, content) = self.regions(area);
+        for tab in &self.tabs {
+            objects.fit_object(tab.content.clone(), content)?;
+        }
+        Ok(())
+    }
+
+    /// Switches tabs with the keyboard: Alt + 1-9 selects a tab, Alt + N the next and Alt + P
+    /// the previous one.
+    ///
+    /// # Returns
+    /// `true` if the selected tab changed.
+    pub fn handle_input(&mut self, input: &NyanInput) -> bool {
+        match input {
+            NyanInput::Alt(NyanKey::N) => self.next_tab(),
+            NyanInput::Alt(NyanKey::P) => self.prev_tab(),
+            NyanInput::Alt(NyanKey::OtherKey(digit @ '1'..='9')) => {
+                self.select(*digit as usize - '1' as usize)
+            }
+            _ => false,
+        }
+    }
+
+    /// Selects the tab whose title is clicked with the left mouse button.
+    ///
+    /// # Returns
+    /// `true` if the selected tab changed.
+    pub fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        if mouse.kind != NyanMouseKind::Down(NyanMouseButton::Left) {
+            return false;
+        }
+        let clicked = self
+            .title_regions(self.area.get())
+            .iter()
+            .position(|title| title.contains(mouse.x, mouse.y));
+        clicked.is_some_and(|index| self.select(index))
+    }
+
+    /// Processes any event, switching tabs on the keys of [`Tabs::handle_input`] and clicks on
+    /// a title.
+    ///
+    /// # Returns
+    /// `true` if the selected tab changed.
+    pub fn handle_event<U>(&mut self, event: &NyanEvent<U>) -> bool {
+        match event {
+            NyanEvent::Key(input) => self.handle_input(input),
+            NyanEvent::Mouse(mouse) => self.handle_mouse(mouse),
+            _ => false,
+        }
+    }
+
+    /// Returns whether the object `id` is the content of a tab that is not selected.
+    pub fn is_hidden(&self, id: &str) -> bool {
+        self.tabs
+            .iter()
+            .enumerate()
+            .any(|(index, tab)| index != self.selected && tab.content == id)
+    }
+
+    /// Draws the tab bar in the region the tabs were last applied to. The selected title is
+    /// drawn reversed, and titles are separated with the default border's vertical line.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(std::io::Error)` if writing fails.
+    pub fn draw_bar<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let area = self.area.get();
+        let (bar, _) = self.regions(area);
+        if bar.is_empty() {
+            return Ok(());
+        }
+        let separator = border::default_border().set().vertical;
+
+        for (index, (tab, title)) in self.tabs.iter().zip(self.title_regions(area)).enumerate() {
+            if title.is_empty() {
+                break;
+            }
+            Cursor::Move(title.x, title.y).queue(out)?;
+            let text = layout::clip_text(&format!(" {} ", tab.title), 0, title.width as usize);
+            if index == self.selected {
+                NyanStyle::base()
+                    .patch(NyanStyle::new().reverse())
+                    .apply(out)?;
+                write!(out, "{text}")?;
+                NyanStyle::reset(out)?;
+            } else {
+                write!(out, "{text}")?;
+            }
+            if title.right() < bar.right() && index + 1 < self.tabs.len() {
+                write!(out, "{separator}")?;
+            }
+        }
+        out.flush()
+    }
+
+    /// Draws the scene: every object except the contents of the tabs that are not selected,
+    /// then the tab bar.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the scene was drawn.
+    /// - An error if writing fails.
+    pub fn draw_in<W: Write>(&self, objects: &NyanObj<'a>, out: &mut W) -> NyanResult<()> {
+        for id in objects.ids().filter(|id| !self.is_hidden(id)) {
+            objects.draw_overlaid_in(out, id, NyanStyle::new())?;
+        }
+        self.draw_bar(out)?;
+        Ok(())
+    }
+
+    /// Draws the scene to stdout. See [`Tabs::draw_in`].
+    pub fn draw(&self, objects: &NyanObj<'a>) -> NyanResult<()> {
+        self.draw_in(objects, &mut io::stdout())
+    }
+}
+
+impl<'a> Layout<'a> for Tabs<'a> {
+    fn apply(&self, objects: &mut NyanObj<'a>, area: Rect) -> NyanResult<()> {
+        Tabs::apply(self, objects, area)
+    }
+}
@@ -0,0 +1,424 @@
+//! This module provides helpers to test what an app draws, so UIs can be regression-tested in CI without a terminal.
+//!
+//! A frame is drawn by an [`App`] on a [`TestBackend`] and compared as text: the glyphs of the screen, one line per row, without colors and with the spaces at the end of the rows left out. `assert_frame_eq!` compares a frame with text in the test; `assert_frame_snapshot!` compares it with a file in `tests/snapshots/` of the crate, which is only written when the `NYAN_UPDATE_SNAPSHOTS` environment variable is set, so a missing snapshot fails in CI instead of passing. When they differ, the assertion fails with the rows that changed.
+//!
+//! Whole interactive flows are tested with a `TestDriver`: a script of events, pauses and expected text, played into [`App::run`] by an `App` drawing on the driver's screen and timed by its [`MockClock`], so the test runs as fast as the machine allows and the same way every time.
+//!
+//! # Structs
+//!
+//! - `TestDriver`: A scripted event source with a screen and a clock.
+//!
+//! # Macros
+//!
+//! - `assert_frame_eq!(frame, expected)`: Compares a frame with the expected text.
+//! - `assert_frame_snapshot!(frame, name)`: Compares a frame with the snapshot `tests/snapshots/<name>.txt`.
+//!
+//! # Functions
+//!
+//! - `render(width, height, draw)`: Draws a frame on a screen of the given size and returns it.
+//! - `render_scene(scene, width, height)`: Draws every object of a `NyanObj`.
+//! - `assert_frame(frame, expected)` / `assert_snapshot(frame, path)`: The functions behind the macros.
+//! - `frame_diff(expected, actual)`: Returns the rows of two frames, marking those that differ.
+//!
+//! # Methods
+//!
+//! - `TestDriver::new(width, height)` / `app(fps)`: Create a driver and the `App` it drives.
+//! - `key(input)` / `type_text(text)` / `event(event)` / `user(event)` / `resize(width, height)`: Add events to the script.
+//! - `wait(duration)`: Adds a pause of virtual time, filled with `NyanEvent::Tick`.
+//! - `expect(text)`: Adds a check that the screen shows `text` at that point of the script.
+//! - `screen()` / `clock()` / `is_done()`: Inspect the screen, the clock and the script.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::backend::TestBackend;
+use crate::buffer::Buffer;
+use crate::clock::{Clock, MockClock};
+use crate::errors::NyanResult;
+use crate::event::{EventSource, NyanEvent};
+use crate::input::NyanInput;
+use crate::nyan_obj::NyanObj;
+use crate::remote;
+
+/// The environment variable that makes `assert_frame_snapshot!` rewrite the snapshots.
+pub const UPDATE_VAR: &str = "NYAN_UPDATE_SNAPSHOTS";
+
+/// Draws one frame with a buffered [`App`] on a [`TestBackend`] of `width` × `height` cells,
+/// and returns the screen.
+///
+/// # Returns
+///
+/// - `Ok(Buffer)` with the cells of the screen after the frame.
+/// - An error if the frame cannot be drawn.
+///
+/// # Example
+/// ```rust
+/// use nyan::testing;
+/// use std::io::Write;
+///
+/// let frame = testing::render(10, 2, |app| {
+///     write!(app, "nyan").unwrap();
+/// })
+/// .unwrap();
+/// nyan::assert_frame_eq!(frame, "nyan\n");
+/// ```
+pub fn render<F: FnOnce(&mut App)>(width: u16, height: u16, draw: F) -> NyanResult<Buffer> {
+    let backend = TestBackend::new(width, height);
+    let mut app = App::with_backend(1000, backend.clone()).buffered();
+    app.draw_with(draw)?;
+    Ok(backend.screen())
+}
+
+/// Draws every object of `scene` on a screen of `width` × `height` cells, like
+/// [`NyanObj::draw_all_in`], and returns the screen.
+///
+/// # Returns
+///
+/// - `Ok(Buffer)` with the cells of the screen after the frame.
+/// - An error if the frame or an object cannot be drawn.
+///
+/// # Example
+/// ```rust
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+/// use nyan::testing;
+///
+/// let mut scene = NyanObj::new();
+/// scene.add_object("title", Objects::new_text("nyan"), (2, 0));
+/// let frame = testing::render_scene(&scene, 10, 3).unwrap();
+/// nyan::assert_frame_eq!(frame, "  nyan\n");
+/// ```
+pub fn render_scene(scene: &NyanObj, width: u16, height: u16) -> NyanResult<Buffer> {
+    let mut drawn = Ok(());
+    let frame = render(width, height, |app| drawn = scene.draw_all_in(app))?;
+    drawn.map(|_| frame)
+}
+
+/// Panics with the rows that differ unless `frame` shows `expected`. Used by
+/// [`assert_frame_eq!`](crate::assert_frame_eq).
+///
+/// Both are compared as text: spaces at the end of the lines and empty lines at the end are
+/// left out, and a newline at the start of `expected` is ignored, so the text can start on the
+/// line after the opening quote.
+#[track_caller]
+pub fn assert_frame(frame: &Buffer, expected: &str) {
+    let actual = normalize(&frame.to_text());
+    let expected = normalize(expected.strip_prefix('\n').unwrap_or(expected));
+    if actual != expected {
+        panic!(
+            "the frame does not match the expected text:\n{}",
+            frame_diff(&expected, &actual)
+        );
+    }
+}
+
+/// Compares `frame` with the snapshot at `path`. Used by
+/// [`assert_frame_snapshot!`](crate::assert_frame_snapshot).
+///
+/// The snapshot is written, instead of compared, when the [`UPDATE_VAR`] environment variable
+/// is set.
+///
+/// # Panics
+///
+/// If the frame differs from the snapshot, the snapshot does not exist, or it cannot be read
+/// or written.
+#[track_caller]
+pub fn assert_snapshot<P: AsRef<Path>>(frame: &Buffer, path: P) {
+    let path = path.as_ref();
+    let actual = normalize(&frame.to_text());
+    if std::env::var_os(UPDATE_VAR).is_some() {
+        let written = match path.parent() {
+            Some(dir) => std::fs::create_dir_all(dir),
+            None => Ok(()),
+        }
+        .and_then(|_| std::fs::write(path, &actual));
+        if let Err(e) = written {
+            panic!("cannot write the snapshot {}: {e}", path.display());
+        }
+        return;
+    }
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => normalize(&expected),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => panic!(
+            "the snapshot {} does not exist (set {UPDATE_VAR}=1 to write it)",
+            path.display()
+        ),
+        Err(e) => panic!("cannot read the snapshot {}: {e}", path.display()),
+    };
+    if actual != expected {
+        panic!(
+            "the frame does not match the snapshot {} (set {UPDATE_VAR}=1 to update it):\n{}",
+            path.display(),
+            frame_diff(&expected, &actual)
+        );
+    }
+}
+
+/// Returns the rows of the `expected` and the `actual` frame side by side: rows that are the
+/// same once, with their number, and rows that differ twice, the expected one marked with `-`
+/// and the actual one with `+`.
+///
+/// # Example
+/// ```rust
+/// use nyan::testing;
+///
+/// assert_eq!(
+///     testing::frame_diff("Score: 10\nx3", "Score: 25\nx3"),
+///     "-   0 |Score: 10\n+   0 |Score: 25\n    1 |x3\n"
+/// );
+/// ```
+pub fn frame_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut diff = String::new();
+    for row in 0..expected.len().max(actual.len()) {
+        match (expected.get(row), actual.get(row)) {
+            (Some(old), Some(new)) if old == new => {
+                let _ = writeln!(diff, "  {row:>3} |{new}");
+            }
+            (old, new) => {
+                if let Some(old) = old {
+                    let _ = writeln!(diff, "- {row:>3} |{old}");
+                }
+                if let Some(new) = new {
+                    let _ = writeln!(diff, "+ {row:>3} |{new}");
+                }
+            }
+        }
+    }
+    diff
+}
+
+/// A step of the script of a [`TestDriver`].
+#[derive(Debug)]
+enum Step<U> {
+    Event(NyanEvent<U>),
+    Resize(u16, u16),
+    /// A pause, and the time it ends once it started.
+    Wait(Duration, Option<Instant>),
+    Expect(String),
+}
+
+/// `TestDriver` plays a script of events into an app, for integration tests of whole
+/// interactive flows without a terminal.
+///
+/// The driver owns a [`TestBackend`] and a [`MockClock`]; [`TestDriver::app`] creates an `App`
+/// drawing on the one and timed by the other. Passed to [`App::run`] as the event source, the
+/// driver returns the events of its script one per frame, waits by returning
+/// `NyanEvent::Tick` while the virtual time of a pause passes, checks the screen when it gets
+/// to an [`expect`](TestDriver::expect), and ends the loop when the script is over.
+///
+/// # Example
+/// ```rust
+/// use std::io::Write;
+/// use std::time::Duration;
+///
+/// use nyan::event::NyanEvent;
+/// use nyan::input::NyanInput;
+/// use nyan::testing::TestDriver;
+///
+/// let mut driver: TestDriver = TestDriver::new(20, 2)
+///     .type_text("Mike")
+///     .expect("Name: Mike")
+///     .key(NyanInput::Enter)
+///     .expect("Hello, Mike!")
+///     .wait(Duration::from_secs(2));
+/// let mut app = driver.app(30);
+///
+/// let mut state = (String::new(), false);
+/// app.run(
+///     &mut state,
+///     &mut driver,
+///     |(name, greeted), app| match greeted {
+///         true => write!(app, "Hello, {name}!").unwrap(),
+///         false => write!(app, "Name: {name}").unwrap(),
+///     },
+///     |(name, greeted), _, event| {
+///         match event {
+///             NyanEvent::Key(NyanInput::Enter) => *greeted = true,
+///             NyanEvent::Key(key) => name.extend(key.to_char()),
+///             _ => {}
+///         }
+///         true
+///     },
+/// )
+/// .unwrap();
+/// assert!(driver.is_done());
+/// assert!(driver.clock().elapsed() >= Duration::from_secs(2));
+/// ```
+#[derive(Debug)]
+pub struct TestDriver<U = ()> {
+    backend: TestBackend,
+    clock: MockClock,
+    steps: VecDeque<Step<U>>,
+}
+
+impl<U> TestDriver<U> {
+    /// Creates a driver with an empty script and a screen of `width` × `height` cells.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            backend: TestBackend::new(width, height),
+            clock: MockClock::new(),
+            steps: VecDeque::new(),
+        }
+    }
+
+    /// Creates a buffered `App` drawing on the screen of the driver and timed by its clock.
+    pub fn app(&self, fps: u64) -> App {
+        App::with_backend(fps, self.backend.clone())
+            .buffered()
+            .clock(self.clock.clone())
+    }
+
+    /// Adds an event to the script.
+    pub fn event(self, event: NyanEvent<U>) -> Self {
+        let mut driver = self;
+        driver.steps.push_back(Step::Event(event));
+        driver
+    }
+
+    /// Adds a key press to the script.
+    pub fn key(self, input: NyanInput<'static>) -> Self {
+        self.event(NyanEvent::Key(input))
+    }
+
+    /// Adds a key press for each character of `text`, as a terminal reports them: letters as
+    /// `NyanKey::A` to `Z`, with `Shift` when they are uppercase, and other characters as
+    /// `NyanKey::OtherKey`.
+    pub fn type_text(self, text: &str) -> Self {
+        text.chars()
+            .fold(self, |driver, ch| driver.key(remote::key(ch)))
+    }
+
+    /// Adds a user-defined event to the script, as sent through an
+    /// [`EventSender`](crate::event::EventSender).
+    pub fn user(self, event: U) -> Self {
+        self.event(NyanEvent::User(event))
+    }
+
+    /// Adds a resize of the screen to the script, reported as `NyanEvent::Resize`.
+    pub fn resize(self, width: u16, height: u16) -> Self {
+        let mut driver = self;
+        driver.steps.push_back(Step::Resize(width, height));
+        driver
+    }
+
+    /// Adds a pause to the script: `NyanEvent::Tick` is returned, one per frame, until
+    /// `duration` of virtual time has passed.
+    pub fn wait(self, duration: Duration) -> Self {
+        let mut driver = self;
+        driver.steps.push_back(Step::Wait(duration, None));
+        driver
+    }
+
+    /// Adds a check to the script: when the driver gets to it, the last frame must show
+    /// `text`, or the driver panics with the frame.
+    pub fn expect(self, text: &str) -> Self {
+        let mut driver = self;
+        driver.steps.push_back(Step::Expect(text.to_string()));
+        driver
+    }
+
+    /// Returns the cells of the screen, as the last frame left them.
+    pub fn screen(&self) -> Buffer {
+        self.backend.screen()
+    }
+
+    /// Returns the clock of the driver, e.g. to check how much virtual time passed.
+    pub fn clock(&self) -> &MockClock {
+        &self.clock
+    }
+
+    /// Returns whether every step of the script was played.
+    pub fn is_done(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl<U> EventSource<U> for TestDriver<U> {
+    fn next_event(&mut self) -> NyanResult<Option<NyanEvent<U>>> {
+        while let Some(step) = self.steps.pop_front() {
+            match step {
+                Step::Event(event) => return Ok(Some(event)),
+                Step::Resize(width, height) => {
+                    self.backend.resize(width, height);
+                    return Ok(Some(NyanEvent::Resize(width, height)));
+                }
+                Step::Wait(duration, until) => {
+                    let until = until.unwrap_or_else(|| self.clock.now() + duration);
+                    if self.clock.now() < until {
+                        self.steps.push_front(Step::Wait(duration, Some(until)));
+                        return Ok(Some(NyanEvent::Tick));
+                    }
+                }
+                Step::Expect(text) => {
+                    let frame = self.backend.screen().to_text();
+                    if !frame.contains(&text) {
+                        panic!("the screen does not show {text:?}:\n{frame}");
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Removes the spaces at the end of the lines and the empty lines at the end of `text`.
+fn normalize(text: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    let mut normalized = lines.join("\n");
+    normalized.push('\n');
+    normalized
+}
+
+/// Asserts that a frame (a [`Buffer`](crate::buffer::Buffer), e.g. from
+/// [`testing::render`](crate::testing::render)) shows the expected text, and prints the rows
+/// that differ otherwise. Colors are not compared, nor spaces at the end of the lines.
+///
+/// # Example
+/// ```rust
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+///
+/// let mut scene = NyanObj::new();
+/// scene.add_object("menu", Objects::new_list(["Start", "Quit"], 2), (0, 0));
+/// let frame = nyan::testing::render_scene(&scene, 12, 3).unwrap();
+/// nyan::assert_frame_eq!(frame, "
+/// Start
+/// Quit
+/// ");
+/// ```
+#[macro_export]
+macro_rules! assert_frame_eq {
+    ($frame:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_frame(&$frame, $expected)
+    };
+}
+
+/// Asserts that a frame (a [`Buffer`](crate::buffer::Buffer)) shows the same text as the
+/// snapshot `tests/snapshots/<name>.txt` of the calling crate. The snapshot is written, and
+/// created if it does not exist, when the `NYAN_UPDATE_SNAPSHOTS` environment variable is set.
+///
+/// # Example
+/// ```ignore
+/// let frame = nyan::testing::render_scene(&main_menu(), 40, 12)?;
+/// nyan::assert_frame_snapshot!(frame, "main_menu");
+/// ```
+#[macro_export]
+macro_rules! assert_frame_snapshot {
+    ($frame:expr, $name:expr $(,)?) => {
+        $crate::testing::assert_snapshot(
+            &$frame,
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{}.txt", $name)),
+        )
+    };
+}
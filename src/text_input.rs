@@ -0,0 +1,442 @@
+//! This module provides the `TextInput` widget, a single-line text field.
+//!
+//! The field is drawn in a box (or bare) with a visible cursor while it is focused, scrolls sideways when the text is longer than the field, and supports placeholder text, a maximum length, masking (for passwords) and a validation callback whose result is shown by coloring the field.
+//!
+//! # Structs
+//!
+//! - `TextInput`: A text field and its editing state.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates an empty, 20 cells wide text field.
+//! - `value(text)` / `placeholder(text)` / `width(cells)`: Set the initial text, the hint shown while the field is empty, and the preferred width.
+//! - `max_length(chars)` / `mask(char)` / `borderless()` / `theme(theme)`: Limit the length, hide the text, drop the box, change the colors.
+//! - `validator(func)`: Sets a callback that checks the text after every change.
+//! - `text()` / `cursor()` / `is_valid()` / `error()`: Query the field.
+//! - `set_text(text)` / `clear()`: Replace the text.
+//! - Editing keys: characters insert, Backspace / Delete remove, Left / Right / Home / End move the cursor, Ctrl + A / E jump to the start / end, Ctrl + U deletes up to the cursor, Ctrl + K from it.
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::io::{self, Write};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::border::{self, BorderStyle};
+use crate::cursor::Cursor;
+use crate::event::{NyanMouse, NyanMouseButton, NyanMouseKind};
+use crate::input::{NyanInput, NyanKey};
+use crate::layout;
+use crate::rect::{Edges, Rect};
+use crate::style::NyanStyle;
+use crate::theme::Theme;
+use crate::widget::{self, Widget};
+
+/// A validation callback: `Ok(())` if the text is valid, otherwise the error message.
+type Validator<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
+
+/// `TextInput` is a single-line text field.
+///
+/// # Example
+/// ```rust
+/// use nyan::input::{NyanInput, NyanKey};
+/// use nyan::text_input::TextInput;
+/// use nyan::widget::Widget;
+///
+/// let mut pin = TextInput::new()
+///     .max_length(4)
+///     .mask('*')
+///     .validator(|text| match text.chars().all(|c| c.is_ascii_digit()) {
+///         true => Ok(()),
+///         false => Err("digits only".into()),
+///     });
+///
+/// pin.set_focused(true);
+/// for key in ['1', '2', 'x'] {
+///     pin.handle_input(&NyanInput::Key(NyanKey::from_char(key)));
+/// }
+/// assert_eq!(pin.text(), "12x");
+/// assert_eq!(pin.error(), Some("digits only"));
+///
+/// pin.handle_input(&NyanInput::BackSpace);
+/// assert!(pin.is_valid());
+/// ```
+pub struct TextInput<'a> {
+    text: String,
+    /// The cursor position in characters.
+    cursor: usize,
+    placeholder: Cow<'a, str>,
+    width: u16,
+    max_length: Option<usize>,
+    mask: Option<char>,
+    border: Option<BorderStyle>,
+    boxed: bool,
+    validator: Option<Validator<'a>>,
+    error: Option<String>,
+    focused: bool,
+    theme: Theme,
+    area: Rect,
+}
+
+impl<'a> Debug for TextInput<'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("TextInput")
+            .field("text", &self.text)
+            .field("cursor", &self.cursor)
+            .field("placeholder", &self.placeholder)
+            .field("max_length", &self.max_length)
+            .field("mask", &self.mask)
+            .field("error", &self.error)
+            .field("focused", &self.focused)
+            .field("area", &self.area)
+            .finish()
+    }
+}
+
+impl<'a> Default for TextInput<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> TextInput<'a> {
+    /// Creates an empty text field, 20 cells wide and drawn in a box with the default border.
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            cursor: 0,
+            placeholder: Cow::Borrowed(""),
+            width: 20,
+            max_length: None,
+            mask: None,
+            border: None,
+            boxed: true,
+            validator: None,
+            error: None,
+            focused: false,
+            theme: Theme::default(),
+            area: Rect::default(),
+        }
+    }
+
+    /// Sets the initial text, with the cursor at its end.
+    pub fn value<T: Into<String>>(self, text: T) -> Self {
+        let mut input = self;
+        input.set_text(text);
+        input
+    }
+
+    /// Sets the hint shown while the field is empty.
+    pub fn placeholder<T: Into<Cow<'a, str>>>(self, text: T) -> Self {
+        let mut input = self;
+        input.placeholder = text.into();
+        input
+    }
+
+    /// Sets the preferred width of the field in cells, including the box.
+    pub fn width(self, cells: u16) -> Self {
+        let mut input = self;
+        input.width = cells;
+        input
+    }
+
+    /// Limits the text to `chars` characters; further typing is ignored.
+    pub fn max_length(self, chars: usize) -> Self {
+        let mut input = self;
+        input.max_length = Some(chars);
+        input
+    }
+
+    /// Draws every character as `mask`, e.g. `'*'` for passwords.
+    pub fn mask(self, mask: char) -> Self {
+        let mut input = self;
+        input.mask = Some(mask);
+        input
+    }
+
+    /// Sets the border of the box; `None` follows the default border (see
+    /// [`border::set_default_border`]).
+    pub fn border(self, border: Option<BorderStyle>) -> Self {
+        let mut input = self;
+        input.border = border;
+        input.boxed = true;
+        input
+    }
+
+    /// Draws the field without a box, on a single row.
+    pub fn borderless(self) -> Self {
+        let mut input = self;
+        input.boxed = false;
+        input
+    }
+
+    /// Sets the colors of the field.
+    pub fn theme(self, theme: Theme) -> Self {
+        let mut input = self;
+        input.theme = theme;
+        input
+    }
+
+    /// Sets a callback that checks the text after every change. It returns `Ok(())` for valid
+    /// text, or the message to show otherwise.
+    pub fn validator<F: Fn(&str) -> Result<(), String> + 'a>(self, validator: F) -> Self {
+        let mut input = self;
+        input.validator = Some(Box::new(validator));
+        input.validate();
+        input
+    }
+
+    /// Returns the text of the field.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the cursor position in characters.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns whether the validator accepted the text (always `true` without a validator).
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the message of the validator if it rejected the text.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Replaces the text, cut to the maximum length, and moves the cursor to its end.
+    pub fn set_text<T: Into<String>>(&mut self, text: T) {
+        self.text = text.into();
+        if let Some(max) = self.max_length {
+            if let Some((index, _)) = self.text.char_indices().nth(max) {
+                self.text.truncate(index);
+            }
+        }
+        self.cursor = self.text.chars().count();
+        self.validate();
+    }
+
+    /// Removes all text.
+    pub fn clear(&mut self) {
+        self.set_text("");
+    }
+
+    /// Runs the validator on the current text.
+    fn validate(&mut self) {
+        self.error = match &self.validator {
+            Some(validator) => validator(&self.text).err(),
+            None => None,
+        };
+    }
+
+    /// Returns the byte index of the character at `chars`.
+    fn byte_index(&self, chars: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(chars)
+            .map_or(self.text.len(), |(index, _)| index)
+    }
+
+    /// Inserts `text` at the cursor, as far as the maximum length allows.
+    fn insert(&mut self, text: &str) -> bool {
+        let room = self.max_length.map_or(usize::MAX, |max| {
+            max.saturating_sub(self.text.chars().count())
+        });
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_control())
+            .take(room)
+            .collect();
+        if text.is_empty() {
+            return false;
+        }
+        let index = self.byte_index(self.cursor);
+        self.text.insert_str(index, &text);
+        self.cursor += text.chars().count();
+        self.validate();
+        true
+    }
+
+    /// Removes the characters from `start` to `end` (in characters).
+    fn remove(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+        let range = self.byte_index(start)..self.byte_index(end);
+        self.text.replace_range(range, "");
+        self.cursor = start;
+        self.validate();
+    }
+
+    /// Returns the content area inside the box.
+    fn inner(&self) -> Rect {
+        match self.boxed {
+            true => self.area.inner(Edges::all(1)),
+            false => self.area,
+        }
+    }
+
+    /// Returns the text as drawn: masked, or the text itself.
+    fn shown(&self) -> Cow<'_, str> {
+        match self.mask {
+            Some(mask) => Cow::Owned(self.text.chars().map(|_| mask).collect()),
+            None => Cow::Borrowed(&self.text),
+        }
+    }
+
+    /// Returns the first visible cell of the text, so the cursor stays inside `width` cells.
+    fn scroll(&self, width: usize) -> usize {
+        let shown = self.shown();
+        let before: usize = shown.chars().take(self.cursor).map(char_width).sum();
+        // Leave one cell for the cursor after the last character.
+        (before + 1).saturating_sub(width)
+    }
+}
+
+/// Returns the display width of a character.
+fn char_width(ch: char) -> usize {
+    ch.width().unwrap_or(0)
+}
+
+impl<'a> Widget for TextInput<'a> {
+    fn size(&self) -> (u16, u16) {
+        (self.width, if self.boxed { 3 } else { 1 })
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    fn draw(&self, mut out: &mut dyn Write) -> io::Result<()> {
+        if self.boxed {
+            let style = match (self.is_valid(), self.focused) {
+                (false, _) => self.theme.error,
+                (true, true) => self.theme.accent,
+                (true, false) => self.theme.muted,
+            };
+            NyanStyle::base().patch(style).apply(&mut out)?;
+            self.border
+                .unwrap_or_else(border::default_border)
+                .draw(&mut out, self.area)?;
+            NyanStyle::reset(&mut out)?;
+        }
+
+        let inner = self.inner();
+        if inner.is_empty() {
+            return out.flush();
+        }
+        let width = inner.width as usize;
+        Cursor::Move(inner.x, inner.y).queue(&mut out)?;
+
+        if self.text.is_empty() && !self.placeholder.is_empty() {
+            let hint = layout::clip_text(&self.placeholder, 0, width);
+            if self.focused {
+                // The cursor sits on the first cell of the hint.
+                let mut chars = hint.chars();
+                let first = chars.next().map_or(String::from(" "), String::from);
+                widget::write_styled(out, self.theme.muted.reverse(), &first)?;
+                widget::write_styled(out, self.theme.muted, chars.as_str())?;
+            } else {
+                widget::write_styled(out, self.theme.muted, &hint)?;
+            }
+            let padding = width.saturating_sub(hint.width());
+            write!(out, "{}", " ".repeat(padding))?;
+            return out.flush();
+        }
+
+        let shown = self.shown();
+        let scroll = self.scroll(width);
+        let mut cell = 0;
+        let mut drawn = 0;
+        for (index, ch) in shown.chars().chain(std::iter::once(' ')).enumerate() {
+            let ch_width = char_width(ch);
+            let start = cell;
+            cell += ch_width;
+            if start < scroll {
+                continue;
+            }
+            if drawn + ch_width > width {
+                break;
+            }
+            let text = ch.to_string();
+            if self.focused && index == self.cursor {
+                widget::write_styled(out, NyanStyle::new().reverse(), &text)?;
+            } else if index < self.text.chars().count() {
+                write!(out, "{text}")?;
+            } else {
+                // The cell after the text only shows the cursor.
+                write!(out, " ")?;
+            }
+            drawn += ch_width;
+        }
+        write!(out, "{}", " ".repeat(width.saturating_sub(drawn)))?;
+        out.flush()
+    }
+
+    fn handle_input(&mut self, input: &NyanInput) -> bool {
+        if !self.focused {
+            return false;
+        }
+        let len = self.text.chars().count();
+        match input {
+            NyanInput::LeftAllow => self.cursor = self.cursor.saturating_sub(1),
+            NyanInput::RightAllow => self.cursor = (self.cursor + 1).min(len),
+            NyanInput::Home | NyanInput::Ctrl(NyanKey::A) => self.cursor = 0,
+            NyanInput::End | NyanInput::Ctrl(NyanKey::E) => self.cursor = len,
+            NyanInput::BackSpace => self.remove(self.cursor.saturating_sub(1), self.cursor),
+            NyanInput::Delete => self.remove(self.cursor, (self.cursor + 1).min(len)),
+            NyanInput::Ctrl(NyanKey::U) => self.remove(0, self.cursor),
+            NyanInput::Ctrl(NyanKey::K) => self.remove(self.cursor, len),
+            _ => match input.to_char() {
+                Some(ch) => {
+                    self.insert(ch.encode_utf8(&mut [0; 4]));
+                }
+                None => return false,
+            },
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, mouse: &NyanMouse) -> bool {
+        let inner = self.inner();
+        if mouse.kind != NyanMouseKind::Down(NyanMouseButton::Left)
+            || !self.area.contains(mouse.x, mouse.y)
+        {
+            return false;
+        }
+        // Moves the cursor to the clicked character.
+        let target = self.scroll(inner.width as usize) + mouse.x.saturating_sub(inner.x) as usize;
+        let mut cell = 0;
+        self.cursor = self
+            .shown()
+            .chars()
+            .take_while(|ch| {
+                cell += char_width(*ch);
+                cell <= target
+            })
+            .count();
+        true
+    }
+
+    fn handle_paste(&mut self, text: &str) -> bool {
+        self.focused && self.insert(text)
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
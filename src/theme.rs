@@ -0,0 +1,268 @@
+//! This module defines the `Theme` struct and background detection, so an application can pick a palette that is readable on the user's terminal.
+//!
+//! Terminals may have a light or a dark background, and a palette that looks good on one is often hard to read on the other. `Background::detect()` finds out which one the terminal uses: it first looks at the `COLORFGBG` environment variable set by some terminals, then asks the terminal itself with an OSC 11 query, and falls back to a dark background if neither answers. `Theme::detect()` picks the matching palette.
+//!
+//! # Enums
+//!
+//! - `Background`: Whether the terminal background is light or dark.
+//!
+//! # Structs
+//!
+//! - `Theme`: A set of styles for common roles (text, muted text, accents, selections, errors).
+//!
+//! # Methods
+//!
+//! - `Background::detect()`: Detects the terminal background (`COLORFGBG`, then OSC 11, then dark).
+//! - `Background::from_colorfgbg(value)` / `Background::from_osc11(response)`: Parse the respective answers.
+//! - `Theme::dark()` / `Theme::light()`: The built-in palettes.
+//! - `Theme::for_background(background)`: Returns the palette for a background.
+//! - `Theme::detect()`: Returns the palette for the detected background.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let theme = Theme::detect();
+//! let mut app = App::new(30).clear().default_style(theme.text);
+//! ```
+
+use std::time::Duration;
+
+use crate::color::NyanColor;
+use crate::style::NyanStyle;
+
+/// The `Background` enum tells whether the terminal background is light or dark.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Background {
+    /// A dark background (light text).
+    #[default]
+    Dark,
+    /// A light background (dark text).
+    Light,
+}
+
+impl Background {
+    /// Detects whether the terminal background is light or dark.
+    ///
+    /// The `COLORFGBG` environment variable is used if it is set. Otherwise the terminal is
+    /// asked for its background color (OSC 11), waiting at most 100 ms for the answer. If
+    /// neither works, a dark background is assumed.
+    ///
+    /// The query is only sent when stdout is a terminal. Call this before `App::draw` starts
+    /// reading events, so the answer is not mistaken for input.
+    pub fn detect() -> Self {
+        if let Some(background) = std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|v| Self::from_colorfgbg(&v))
+        {
+            return background;
+        }
+        query_osc11(Duration::from_millis(100))
+            .and_then(|response| Self::from_osc11(&response))
+            .unwrap_or_default()
+    }
+
+    /// Parses the `COLORFGBG` environment variable (`"fg;bg"`, e.g. `"15;0"`).
+    ///
+    /// The background is dark for the colors 0-6 and 8, and light for 7 and 9-15.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::theme::Background;
+    ///
+    /// assert_eq!(Background::from_colorfgbg("15;0"), Some(Background::Dark));
+    /// assert_eq!(Background::from_colorfgbg("0;default;15"), Some(Background::Light));
+    /// assert_eq!(Background::from_colorfgbg("default"), None);
+    /// ```
+    pub fn from_colorfgbg(value: &str) -> Option<Self> {
+        let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+        match bg {
+            0..=6 | 8 => Some(Background::Dark),
+            7 | 9..=15 => Some(Background::Light),
+            _ => None,
+        }
+    }
+
+    /// Parses the answer to an OSC 11 query (`ESC ] 11 ; rgb:RRRR/GGGG/BBBB BEL`).
+    ///
+    /// The background is light if the relative luminance of the color is above one half.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nyan::theme::Background;
+    ///
+    /// let dark = "\x1b]11;rgb:1e1e/1e1e/2e2e\x1b\\";
+    /// assert_eq!(Background::from_osc11(dark), Some(Background::Dark));
+    ///
+    /// let light = "\x1b]11;rgb:ffff/ffff/f0f0\x07";
+    /// assert_eq!(Background::from_osc11(light), Some(Background::Light));
+    /// ```
+    pub fn from_osc11(response: &str) -> Option<Self> {
+        let rgb = &response[response.find("rgb:")? + 4..];
+        let rgb = rgb.trim_end_matches(['\x07', '\\', '\x1b']);
+
+        let mut channels = rgb.split('/').map(|c| {
+            let digits = c.get(..c.len().min(4))?;
+            let value = u32::from_str_radix(digits, 16).ok()?;
+            let max = (1u32 << (4 * digits.len() as u32)) - 1;
+            Some(value as f32 / max as f32)
+        });
+        let r = channels.next()??;
+        let g = channels.next()??;
+        let b = channels.next()??;
+
+        let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        Some(if luminance > 0.5 {
+            Background::Light
+        } else {
+            Background::Dark
+        })
+    }
+}
+
+/// Asks the terminal for its background color and returns the raw answer.
+///
+/// The query is followed by a request for the device attributes (DA1), which every terminal
+/// answers, and in order: once that answer arrived, no answer to the query can come after it.
+/// Whatever is still on its way when `timeout` passes is discarded, so it does not turn up as
+/// keystrokes.
+#[cfg(unix)]
+fn query_osc11(timeout: Duration) -> Option<String> {
+    use std::fs::OpenOptions;
+    use std::io::{IsTerminal, Read, Write};
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::os::unix::io::AsRawFd;
+    use std::time::Instant;
+
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+
+    // Non-blocking, so a terminal that never answers cannot hang the application.
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open("/dev/tty")
+        .ok()?;
+
+    // Raw mode keeps the answer from being echoed and makes it readable without a newline.
+    let was_raw = crossterm::terminal::is_raw_mode_enabled().unwrap_or(false);
+    if !was_raw {
+        crossterm::terminal::enable_raw_mode().ok()?;
+    }
+
+    let mut response = Vec::new();
+    let mut fenced = false;
+    if tty
+        .write_all(b"\x1b]11;?\x07\x1b[c")
+        .and_then(|_| tty.flush())
+        .is_ok()
+    {
+        let start = Instant::now();
+        let mut buf = [0u8; 64];
+        while start.elapsed() < timeout {
+            match tty.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    response.extend_from_slice(&buf[..n]);
+                    if let Some(da1) = find_da1(&response) {
+                        response.truncate(da1);
+                        fenced = true;
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+                Err(_) => break,
+            }
+        }
+    }
+    if !fenced {
+        // Drop the answers that arrived too late, instead of leaving them to the input.
+        // SAFETY: `tty` is an open file descriptor for the whole call.
+        unsafe {
+            libc::tcflush(tty.as_raw_fd(), libc::TCIFLUSH);
+        }
+    }
+
+    if !was_raw {
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+    String::from_utf8(response).ok().filter(|r| !r.is_empty())
+}
+
+/// Returns where the device attributes answer (`ESC [ ? ... c`) starts, once it is complete.
+#[cfg(unix)]
+fn find_da1(response: &[u8]) -> Option<usize> {
+    let start = response.windows(3).position(|w| w == b"\x1b[?")?;
+    response[start + 3..].contains(&b'c').then_some(start)
+}
+
+/// Asks the terminal for its background color and returns the raw answer.
+#[cfg(not(unix))]
+fn query_osc11(_timeout: Duration) -> Option<String> {
+    None
+}
+
+/// `Theme` is a set of styles for the common roles of a user interface.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Theme {
+    /// The background the theme is made for.
+    pub background: Background,
+    /// Normal text, also suitable as `App::default_style`.
+    pub text: NyanStyle,
+    /// Less important text, such as hints and placeholders.
+    pub muted: NyanStyle,
+    /// Highlighted text, such as titles and focused elements.
+    pub accent: NyanStyle,
+    /// Selected items, such as the current row of a list.
+    pub selection: NyanStyle,
+    /// Error messages.
+    pub error: NyanStyle,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Returns the palette for terminals with a dark background.
+    pub fn dark() -> Self {
+        Self {
+            background: Background::Dark,
+            text: NyanStyle::new().fg(NyanColor::White),
+            muted: NyanStyle::new().fg(NyanColor::BrightBlack),
+            accent: NyanStyle::new().fg(NyanColor::BrightCyan).bold(),
+            selection: NyanStyle::new().fg(NyanColor::Black).bg(NyanColor::Cyan),
+            error: NyanStyle::new().fg(NyanColor::BrightRed).bold(),
+        }
+    }
+
+    /// Returns the palette for terminals with a light background.
+    pub fn light() -> Self {
+        Self {
+            background: Background::Light,
+            text: NyanStyle::new().fg(NyanColor::Black),
+            muted: NyanStyle::new().fg(NyanColor::BrightBlack),
+            accent: NyanStyle::new().fg(NyanColor::Blue).bold(),
+            selection: NyanStyle::new().fg(NyanColor::White).bg(NyanColor::Blue),
+            error: NyanStyle::new().fg(NyanColor::Red).bold(),
+        }
+    }
+
+    /// Returns the palette for the given background.
+    pub fn for_background(background: Background) -> Self {
+        match background {
+            Background::Dark => Self::dark(),
+            Background::Light => Self::light(),
+        }
+    }
+
+    /// Returns the palette for the detected terminal background (see [`Background::detect`]).
+    pub fn detect() -> Self {
+        Self::for_background(Background::detect())
+    }
+}
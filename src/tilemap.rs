@@ -0,0 +1,300 @@
+//! This module provides `TileMap`, a grid of styled glyphs such as the dungeon of a roguelike or the level of a platformer, and `Camera`, the part of it shown on the screen.
+//!
+//! A map is usually drawn as text, one tile per character, and loaded with `from_text()` or `load()`. Styles are given per glyph (`#` for walls in gray, `~` for water in blue), and glyphs can be marked solid for movement and collision checks. A map may be much larger than the screen: `draw_in()` draws only the tiles the camera sees into the camera's viewport, so the cost of a frame depends on the size of the screen, not of the map. Every tile takes one cell, so use glyphs of single width.
+//!
+//! # Structs
+//!
+//! - `TileMap`: The grid of tiles, their styles and which of them are solid.
+//! - `Tile`: A glyph and its style.
+//! - `Camera`: The viewport on the screen and the position in the map it shows.
+//!
+//! # Methods
+//!
+//! - `TileMap::new(width, height)` / `from_text(text)` / `load(path)`: Create a map.
+//! - `style(glyph, style)` / `solid(glyphs)`: Style glyphs and make them solid.
+//! - `get(x, y)` / `set(x, y, glyph)` / `is_solid(x, y)` / `size()`: Query and edit the tiles.
+//! - `draw_in(out, camera)`: Draws the tiles the camera sees.
+//! - `Camera::new(viewport)`: Creates a camera showing the top-left corner of a map.
+//! - `position()` / `look_at(x, y)` / `scroll_by(dx, dy)`: Move the camera.
+//! - `center_on(x, y, size)`: Centers the camera on a point, e.g. the player, without leaving the map.
+//! - `to_screen(x, y)` / `to_map(x, y)`: Convert between map and screen cells.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::cursor::Cursor;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::widget;
+
+/// `Tile` is a cell of a [`TileMap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tile {
+    /// The glyph drawn in the cell.
+    pub glyph: char,
+    /// The style the glyph is drawn in.
+    pub style: NyanStyle,
+}
+
+impl Default for Tile {
+    /// An empty cell.
+    fn default() -> Self {
+        Self {
+            glyph: ' ',
+            style: NyanStyle::new(),
+        }
+    }
+}
+
+/// `TileMap` is a grid of tiles drawn through a [`Camera`].
+///
+/// # Example
+/// ```rust
+/// use nyan::color::NyanColor;
+/// use nyan::rect::Rect;
+/// use nyan::style::NyanStyle;
+/// use nyan::tilemap::{Camera, TileMap};
+///
+/// let level = TileMap::from_text("##########\n#..~~....#\n##########")
+///     .style('#', NyanStyle::new().fg(NyanColor::BrightBlack))
+///     .style('~', NyanStyle::new().fg(NyanColor::Blue))
+///     .solid("#~");
+/// assert_eq!(level.size(), (10, 3));
+/// assert!(level.is_solid(3, 1));
+/// assert!(!level.is_solid(5, 1));
+///
+/// // A 4x3 window on the screen, following the player at (8, 1).
+/// let mut camera = Camera::new(Rect::new(0, 0, 4, 3));
+/// camera.center_on(8, 1, level.size());
+/// assert_eq!(camera.position(), (6, 0));
+/// assert_eq!(camera.to_screen(8, 1), Some((2, 1)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TileMap {
+    width: u16,
+    height: u16,
+    tiles: Vec<Tile>,
+    /// The style of each styled glyph, also for tiles set later.
+    styles: Vec<(char, NyanStyle)>,
+    solid: Vec<char>,
+}
+
+impl TileMap {
+    /// Creates a `width` x `height` map of empty tiles.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            tiles: vec![Tile::default(); width as usize * height as usize],
+            styles: Vec::new(),
+            solid: Vec::new(),
+        }
+    }
+
+    /// Creates a map with a tile for every character of `text`, one row per line. Rows shorter
+    /// than the longest one are filled with empty tiles.
+    pub fn from_text(text: &str) -> Self {
+        let rows: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut map = Self::new(
+            width.min(u16::MAX as usize) as u16,
+            rows.len().min(u16::MAX as usize) as u16,
+        );
+        for (y, row) in rows.iter().enumerate().take(map.height as usize) {
+            for (x, &glyph) in row.iter().enumerate().take(map.width as usize) {
+                map.set(x as u16, y as u16, glyph);
+            }
+        }
+        map
+    }
+
+    /// Creates a map from the text file at `path` (see [`TileMap::from_text`]).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(TileMap)` with the tiles of the file.
+    /// - An error if the file cannot be read.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::from_text(&std::fs::read_to_string(path)?))
+    }
+
+    /// Draws every tile with the glyph `glyph` in `style`, including tiles set later.
+    pub fn style(self, glyph: char, style: NyanStyle) -> Self {
+        let mut map = self;
+        map.styles.retain(|(other, _)| *other != glyph);
+        map.styles.push((glyph, style));
+        for tile in map.tiles.iter_mut().filter(|tile| tile.glyph == glyph) {
+            tile.style = style;
+        }
+        map
+    }
+
+    /// Makes the tiles with any of the glyphs in `glyphs` solid (see [`TileMap::is_solid`]).
+    pub fn solid(self, glyphs: &str) -> Self {
+        let mut map = self;
+        map.solid.extend(glyphs.chars());
+        map
+    }
+
+    /// Returns the `(width, height)` of the map in tiles.
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Returns the tile at `(x, y)`, or `None` outside of the map.
+    pub fn get(&self, x: u16, y: u16) -> Option<&Tile> {
+        self.index(x, y).map(|index| &self.tiles[index])
+    }
+
+    /// Replaces the tile at `(x, y)` with `glyph`, in the style of the glyph (see
+    /// [`TileMap::style`]).
+    ///
+    /// # Returns
+    ///
+    /// `false` if `(x, y)` is outside of the map.
+    pub fn set(&mut self, x: u16, y: u16, glyph: char) -> bool {
+        let style = self
+            .styles
+            .iter()
+            .find(|(other, _)| *other == glyph)
+            .map_or(NyanStyle::new(), |(_, style)| *style);
+        self.set_tile(x, y, Tile { glyph, style })
+    }
+
+    /// Replaces the tile at `(x, y)`, with a style of its own.
+    ///
+    /// # Returns
+    ///
+    /// `false` if `(x, y)` is outside of the map.
+    pub fn set_tile(&mut self, x: u16, y: u16, tile: Tile) -> bool {
+        match self.index(x, y) {
+            Some(index) => {
+                self.tiles[index] = tile;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether the tile at `(x, y)` is solid. Everything outside of the map is.
+    pub fn is_solid(&self, x: u16, y: u16) -> bool {
+        match self.get(x, y) {
+            Some(tile) => self.solid.contains(&tile.glyph),
+            None => true,
+        }
+    }
+
+    /// Draws the tiles `camera` sees into its viewport. Parts of the viewport past the edge
+    /// of the map are cleared.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the tiles were drawn.
+    /// - An error if writing fails.
+    pub fn draw_in<W: Write>(&self, out: &mut W, camera: &Camera) -> io::Result<()> {
+        let viewport = camera.viewport;
+        let (left, top) = camera.position;
+        let mut run = String::new();
+        for row in 0..viewport.height {
+            Cursor::Move(viewport.x, viewport.y + row).queue(out)?;
+            // Neighboring tiles of one style are written at once.
+            let mut style = None;
+            for column in 0..viewport.width {
+                let tile = left
+                    .checked_add(column)
+                    .zip(top.checked_add(row))
+                    .and_then(|(x, y)| self.get(x, y))
+                    .copied()
+                    .unwrap_or_default();
+                if style != Some(tile.style) {
+                    if let Some(style) = style {
+                        widget::write_styled(out, style, &run)?;
+                    }
+                    run.clear();
+                    style = Some(tile.style);
+                }
+                run.push(tile.glyph);
+            }
+            if let Some(style) = style {
+                widget::write_styled(out, style, &run)?;
+            }
+            run.clear();
+        }
+        out.flush()
+    }
+
+    /// Returns the index of the tile at `(x, y)`, or `None` outside of the map.
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y as usize * self.width as usize + x as usize)
+    }
+}
+
+/// `Camera` shows the part of a map starting at its position in its viewport on the screen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Camera {
+    /// The region of the screen the camera draws into.
+    pub viewport: Rect,
+    position: (u16, u16),
+}
+
+impl Camera {
+    /// Creates a camera drawing into `viewport`, showing the top-left corner of the map.
+    pub fn new(viewport: Rect) -> Self {
+        Self {
+            viewport,
+            position: (0, 0),
+        }
+    }
+
+    /// Returns the map cell shown in the top-left corner of the viewport.
+    pub fn position(&self) -> (u16, u16) {
+        self.position
+    }
+
+    /// Shows the map from the cell `(x, y)` in the top-left corner of the viewport.
+    pub fn look_at(&mut self, x: u16, y: u16) {
+        self.position = (x, y);
+    }
+
+    /// Moves the camera by `(dx, dy)` cells, stopping at the top and left edges of the map.
+    pub fn scroll_by(&mut self, dx: i32, dy: i32) {
+        let clamp =
+            |value: u16, delta: i32| (value as i32 + delta).clamp(0, u16::MAX as i32) as u16;
+        self.position = (clamp(self.position.0, dx), clamp(self.position.1, dy));
+    }
+
+    /// Centers the camera on the map cell `(x, y)`, without showing anything past the edges of
+    /// a map of `size`. A map smaller than the viewport is shown from its top-left corner.
+    pub fn center_on(&mut self, x: u16, y: u16, size: (u16, u16)) {
+        let center = |target: u16, view: u16, len: u16| {
+            target
+                .saturating_sub(view / 2)
+                .min(len.saturating_sub(view))
+        };
+        self.position = (
+            center(x, self.viewport.width, size.0),
+            center(y, self.viewport.height, size.1),
+        );
+    }
+
+    /// Returns the screen cell showing the map cell `(x, y)`, or `None` if the camera does not
+    /// see it. Use it to draw sprites and objects that live in the map.
+    pub fn to_screen(&self, x: u16, y: u16) -> Option<(u16, u16)> {
+        let column = x.checked_sub(self.position.0)?;
+        let row = y.checked_sub(self.position.1)?;
+        (column < self.viewport.width && row < self.viewport.height)
+            .then_some((self.viewport.x + column, self.viewport.y + row))
+    }
+
+    /// Returns the map cell shown in the screen cell `(x, y)`, e.g. under the mouse, or `None`
+    /// outside of the viewport.
+    pub fn to_map(&self, x: u16, y: u16) -> Option<(u16, u16)> {
+        if !self.viewport.contains(x, y) {
+            return None;
+        }
+        Some((
+            self.position.0.saturating_add(x - self.viewport.x),
+            self.position.1.saturating_add(y - self.viewport.y),
+        ))
+    }
+}
@@ -0,0 +1,296 @@
+//! This module provides `Timeline`, keyframed animations of several objects and values played back together, for intro sequences and scripted demos.
+//!
+//! Keyframes set the position or visibility of an object, or a named value, at a point in time. During playback, a timeline interpolates between the keyframes of each property with the easing of the later keyframe and applies the result to the objects of a [`NyanObj`]. Before its first keyframe a property keeps the first value, after its last one the last value. Unlike [`Animations`](crate::animation::Animations), which start tweens as the app goes, a timeline is written out in advance and can be paused, sought and looped as a whole.
+//!
+//! # Structs
+//!
+//! - `Timeline`: The keyframes of every property and the playback state.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates an empty timeline, playing.
+//! - `position(id, at, to, easing)` / `visible(id, at, visible)` / `value(name, at, value, easing)`: Add keyframes.
+//! - `looping()`: Starts over at the end.
+//! - `play()` / `pause()` / `seek(at)`: Control the playback.
+//! - `update(objects, dt)`: Advances the playback and applies the properties to the objects.
+//! - `get(name)`: Returns a named value.
+//! - `elapsed()` / `duration()` / `is_playing()` / `is_finished()`: Query the playback.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::animation::Lerp;
+use crate::easing::Easing;
+use crate::nyan_obj::NyanObj;
+
+/// The keyframes of one property, sorted by time.
+#[derive(Clone, Debug, PartialEq)]
+struct Keys<T>(Vec<(Duration, T, Easing)>);
+
+impl<T: Lerp> Keys<T> {
+    /// Adds a keyframe, replacing one at the same time.
+    fn insert(&mut self, at: Duration, value: T, easing: Easing) {
+        let index = self.0.partition_point(|(time, _, _)| *time < at);
+        match self.0.get(index) {
+            Some((time, _, _)) if *time == at => self.0[index] = (at, value, easing),
+            _ => self.0.insert(index, (at, value, easing)),
+        }
+    }
+
+    /// Returns the value at `at`, or `None` without keyframes.
+    fn sample(&self, at: Duration) -> Option<T> {
+        let index = self.0.partition_point(|(time, _, _)| *time <= at);
+        let Some(&(to_time, to, easing)) = self.0.get(index) else {
+            return self.0.last().map(|(_, value, _)| *value);
+        };
+        let Some(&(from_time, from, _)) = index.checked_sub(1).and_then(|i| self.0.get(i)) else {
+            return Some(to);
+        };
+        let t = (at - from_time).as_secs_f32() / (to_time - from_time).as_secs_f32();
+        Some(T::lerp(from, to, easing.apply(t)))
+    }
+
+    /// Returns the time of the last keyframe.
+    fn end(&self) -> Duration {
+        self.0.last().map_or(Duration::ZERO, |(time, _, _)| *time)
+    }
+}
+
+/// A property animated by a timeline.
+#[derive(Clone, Debug, PartialEq)]
+enum Track<'a> {
+    Position(Cow<'a, str>, Keys<(u16, u16)>),
+    Visibility(Cow<'a, str>, Keys<bool>),
+    Value(Cow<'a, str>, Keys<f32>),
+}
+
+/// `Timeline` plays keyframes of object positions, object visibility and named values.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::easing::Easing;
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+/// use nyan::timeline::Timeline;
+///
+/// let mut obj = NyanObj::new();
+/// obj.add_object("logo", Objects::new_text("NYAN"), (0, 0));
+/// obj.add_object("press", Objects::new_text("press any key"), (0, 2));
+///
+/// let ms = Duration::from_millis;
+/// let mut intro = Timeline::new()
+///     .position("logo", ms(0), (0, 0), Easing::Linear)
+///     .position("logo", ms(1000), (20, 0), Easing::Linear)
+///     .position("logo", ms(1500), (20, 5), Easing::Linear)
+///     .visible("press", ms(0), false)
+///     .visible("press", ms(1500), true);
+///
+/// intro.update(&mut obj, ms(500));
+/// assert_eq!(obj.coordinate("logo"), Some((10, 0)));
+/// assert_eq!(obj.is_visible("press"), Some(false));
+///
+/// intro.update(&mut obj, ms(1000));
+/// assert_eq!(obj.coordinate("logo"), Some((20, 5)));
+/// assert_eq!(obj.is_visible("press"), Some(true));
+/// assert!(intro.is_finished());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timeline<'a> {
+    tracks: Vec<Track<'a>>,
+    elapsed: Duration,
+    playing: bool,
+    looping: bool,
+}
+
+impl<'a> Default for Timeline<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Timeline<'a> {
+    /// Creates a timeline without keyframes, playing from the start.
+    pub fn new() -> Self {
+        Self {
+            tracks: Vec::new(),
+            elapsed: Duration::ZERO,
+            playing: true,
+            looping: false,
+        }
+    }
+
+    /// Places the object `id` at `to` at the time `at`, moving there from the previous
+    /// keyframe along `easing`.
+    pub fn position<P: Into<Cow<'a, str>>>(
+        self,
+        id: P,
+        at: Duration,
+        to: (u16, u16),
+        easing: Easing,
+    ) -> Self {
+        let mut timeline = self;
+        let id = id.into();
+        let keys = timeline.tracks.iter_mut().find_map(|track| match track {
+            Track::Position(other, keys) if *other == id => Some(keys),
+            _ => None,
+        });
+        match keys {
+            Some(keys) => keys.insert(at, to, easing),
+            None => {
+                let mut keys = Keys(Vec::new());
+                keys.insert(at, to, easing);
+                timeline.tracks.push(Track::Position(id, keys));
+            }
+        }
+        timeline
+    }
+
+    /// Shows or hides the object `id` at the time `at`.
+    pub fn visible<P: Into<Cow<'a, str>>>(self, id: P, at: Duration, visible: bool) -> Self {
+        let mut timeline = self;
+        let id = id.into();
+        let keys = timeline.tracks.iter_mut().find_map(|track| match track {
+            Track::Visibility(other, keys) if *other == id => Some(keys),
+            _ => None,
+        });
+        match keys {
+            Some(keys) => keys.insert(at, visible, Easing::Linear),
+            None => {
+                let mut keys = Keys(Vec::new());
+                keys.insert(at, visible, Easing::Linear);
+                timeline.tracks.push(Track::Visibility(id, keys));
+            }
+        }
+        timeline
+    }
+
+    /// Sets the value `name` to `value` at the time `at`, moving there from the previous
+    /// keyframe along `easing`. Read it with [`Timeline::get`].
+    pub fn value<P: Into<Cow<'a, str>>>(
+        self,
+        name: P,
+        at: Duration,
+        value: f32,
+        easing: Easing,
+    ) -> Self {
+        let mut timeline = self;
+        let name = name.into();
+        let keys = timeline.tracks.iter_mut().find_map(|track| match track {
+            Track::Value(other, keys) if *other == name => Some(keys),
+            _ => None,
+        });
+        match keys {
+            Some(keys) => keys.insert(at, value, easing),
+            None => {
+                let mut keys = Keys(Vec::new());
+                keys.insert(at, value, easing);
+                timeline.tracks.push(Track::Value(name, keys));
+            }
+        }
+        timeline
+    }
+
+    /// Starts the timeline over whenever it reaches its end.
+    pub fn looping(self) -> Self {
+        let mut timeline = self;
+        timeline.looping = true;
+        timeline
+    }
+
+    /// Resumes the playback. A finished timeline is started over.
+    pub fn play(&mut self) {
+        if self.is_finished() {
+            self.elapsed = Duration::ZERO;
+        }
+        self.playing = true;
+    }
+
+    /// Pauses the playback.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Jumps to the time `at`; the properties are applied at the next update.
+    pub fn seek(&mut self, at: Duration) {
+        self.elapsed = at.min(self.duration());
+    }
+
+    /// Advances the playback by `dt` and applies the positions and the visibility to the
+    /// objects. Keyframes of objects that do not exist are skipped.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the timeline is playing, so the screen needs to be redrawn. The objects are
+    /// marked dirty then (see [`NyanObj::mark_dirty`]).
+    pub fn update(&mut self, objects: &mut NyanObj, dt: Duration) -> bool {
+        if !self.playing {
+            return false;
+        }
+        let duration = self.duration();
+        self.elapsed += dt;
+        if self.elapsed >= duration {
+            if self.looping && !duration.is_zero() {
+                let over = (self.elapsed - duration).as_nanos() % duration.as_nanos();
+                self.elapsed = Duration::from_nanos(over as u64);
+            } else {
+                self.elapsed = duration;
+                self.playing = false;
+            }
+        }
+
+        for track in &self.tracks {
+            // A missing object is no reason to stop the rest of the timeline.
+            let _ = match track {
+                Track::Position(id, keys) => match keys.sample(self.elapsed) {
+                    Some(position) => objects.move_object(id.to_string(), position),
+                    None => Ok(()),
+                },
+                Track::Visibility(id, keys) => match keys.sample(self.elapsed) {
+                    Some(visible) => objects.set_visible(id.to_string(), visible),
+                    None => Ok(()),
+                },
+                Track::Value(..) => Ok(()),
+            };
+        }
+        objects.mark_dirty();
+        true
+    }
+
+    /// Returns the value `name` at the current time, or `None` if it has no keyframes.
+    pub fn get(&self, name: &str) -> Option<f32> {
+        self.tracks.iter().find_map(|track| match track {
+            Track::Value(other, keys) if other == name => keys.sample(self.elapsed),
+            _ => None,
+        })
+    }
+
+    /// Returns the current time of the playback.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the time of the last keyframe.
+    pub fn duration(&self) -> Duration {
+        self.tracks
+            .iter()
+            .map(|track| match track {
+                Track::Position(_, keys) => keys.end(),
+                Track::Visibility(_, keys) => keys.end(),
+                Track::Value(_, keys) => keys.end(),
+            })
+            .max()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns whether the timeline is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Returns whether a timeline that does not loop reached its end.
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.elapsed >= self.duration()
+    }
+}
@@ -0,0 +1,226 @@
+//! This module provides `Tooltips`, short hints attached to objects that are shown next to them while they are hovered or focused.
+//!
+//! Tooltips are attached to object ids of a [`NyanObj`]. Mouse movements are fed to `handle_mouse()`, which remembers the object under the pointer, and the focused id is set with `set_focus()`, e.g. from a [`FocusManager`](crate::focus::FocusManager). The hovered object's tooltip wins over the focused one's. A tooltip is drawn below its object, or above it if there is no room below, and shifted sideways to stay inside the screen. Draw tooltips after the scene.
+//!
+//! # Structs
+//!
+//! - `Tooltips`: The attached tooltips, the hovered and focused objects and how tooltips are drawn.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a manager without tooltips.
+//! - `tooltip(id, text)`: Attaches a tooltip to an object.
+//! - `max_width(cells)` / `style(style)`: Configure how tooltips are drawn.
+//! - `set(id, text)` / `remove(id)` / `text(id)`: Update and query a tooltip.
+//! - `handle_mouse(objects, mouse)` / `set_focus(id)`: Track the hovered and focused objects.
+//! - `active()`: Returns the id whose tooltip is shown.
+//! - `region(objects, area)`: Returns where the shown tooltip is drawn.
+//! - `draw_in(objects, out, area)`: Draws the shown tooltip.
+
+use std::borrow::Cow;
+use std::io::Write;
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::cursor::Cursor;
+use crate::errors::NyanResult;
+use crate::event::{NyanMouse, NyanMouseKind};
+use crate::layout::{self, Align};
+use crate::nyan_obj::NyanObj;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+use crate::widget;
+
+/// `Tooltips` shows a hint next to the hovered or focused object.
+///
+/// # Example
+/// ```rust
+/// use nyan::event::{NyanMouse, NyanMouseKind};
+/// use nyan::nyan_obj::NyanObj;
+/// use nyan::objects::Objects;
+/// use nyan::rect::Rect;
+/// use nyan::tooltip::Tooltips;
+///
+/// let mut obj = NyanObj::new();
+/// obj.add_object("save", Objects::new_text("[Save]"), (2, 1));
+/// obj.add_object("quit", Objects::new_text("[Quit]"), (74, 23));
+///
+/// let mut tips = Tooltips::new()
+///     .tooltip("save", "Write the file to disk")
+///     .tooltip("quit", "Leave without saving");
+/// let screen = Rect::new(0, 0, 80, 24);
+///
+/// // Hovering shows the tooltip below the object.
+/// tips.handle_mouse(&obj, &NyanMouse { kind: NyanMouseKind::Moved, x: 3, y: 1 });
+/// assert_eq!(tips.active(), Some("save"));
+/// assert_eq!(tips.region(&obj, screen), Some(Rect::new(2, 2, 24, 1)));
+///
+/// // Near the bottom-right corner it goes above and is shifted to the left.
+/// tips.handle_mouse(&obj, &NyanMouse { kind: NyanMouseKind::Moved, x: 40, y: 10 });
+/// tips.set_focus(Some("quit"));
+/// assert_eq!(tips.region(&obj, screen), Some(Rect::new(58, 22, 22, 1)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Tooltips<'a> {
+    tips: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    hovered: Option<String>,
+    focused: Option<String>,
+    max_width: u16,
+    style: NyanStyle,
+}
+
+impl<'a> Default for Tooltips<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Tooltips<'a> {
+    /// Creates a manager without tooltips, drawing them reversed and at most 40 cells wide.
+    pub fn new() -> Self {
+        Self {
+            tips: Vec::new(),
+            hovered: None,
+            focused: None,
+            max_width: 40,
+            style: NyanStyle::new().reverse(),
+        }
+    }
+
+    /// Attaches a tooltip to the object `id`, replacing its previous one.
+    pub fn tooltip<P: Into<Cow<'a, str>>, T: Into<Cow<'a, str>>>(self, id: P, text: T) -> Self {
+        let mut tips = self;
+        tips.set(id, text);
+        tips
+    }
+
+    /// Sets the widest a tooltip may be (40 cells by default); longer texts are wrapped.
+    pub fn max_width(self, cells: u16) -> Self {
+        let mut tips = self;
+        tips.max_width = cells.max(3);
+        tips
+    }
+
+    /// Sets the style of the tooltips (reversed by default).
+    pub fn style(self, style: NyanStyle) -> Self {
+        let mut tips = self;
+        tips.style = style;
+        tips
+    }
+
+    /// Attaches a tooltip to the object `id`, replacing its previous one.
+    pub fn set<P: Into<Cow<'a, str>>, T: Into<Cow<'a, str>>>(&mut self, id: P, text: T) {
+        let id = id.into();
+        let text = text.into();
+        match self.tips.iter_mut().find(|(tip, _)| *tip == id) {
+            Some((_, old)) => *old = text,
+            None => self.tips.push((id, text)),
+        }
+    }
+
+    /// Removes the tooltip of the object `id`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the object had a tooltip.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.tips.len();
+        self.tips.retain(|(tip, _)| tip != id);
+        self.tips.len() != before
+    }
+
+    /// Returns the tooltip of the object `id`.
+    pub fn text(&self, id: &str) -> Option<&str> {
+        self.tips
+            .iter()
+            .find(|(tip, _)| tip == id)
+            .map(|(_, text)| text.as_ref())
+    }
+
+    /// Remembers the object under the mouse pointer. Moves, drags and clicks are tracked.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the shown tooltip changed, so the screen needs to be redrawn.
+    pub fn handle_mouse(&mut self, objects: &NyanObj, mouse: &NyanMouse) -> bool {
+        match mouse.kind {
+            NyanMouseKind::Moved | NyanMouseKind::Drag(_) | NyanMouseKind::Down(_) => {
+                let before = self.active().map(str::to_string);
+                self.hovered = objects.hit_test(mouse.x, mouse.y).map(str::to_string);
+                self.active() != before.as_deref()
+            }
+            _ => false,
+        }
+    }
+
+    /// Sets the focused object, e.g. `tips.set_focus(focus.focused())`.
+    pub fn set_focus(&mut self, id: Option<&str>) {
+        self.focused = id.map(str::to_string);
+    }
+
+    /// Returns the id of the object whose tooltip is shown: the hovered object if it has a
+    /// tooltip, otherwise the focused one.
+    pub fn active(&self) -> Option<&str> {
+        [&self.hovered, &self.focused]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .find(|id| self.text(id).is_some())
+    }
+
+    /// Returns where the shown tooltip is drawn inside `area`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(rect)` below the object, or above it if it does not fit below, moved sideways
+    ///   to stay inside `area`.
+    /// - `None` if no tooltip is shown or its object is gone.
+    pub fn region(&self, objects: &NyanObj, area: Rect) -> Option<Rect> {
+        self.layout(objects, area).map(|(rect, _)| rect)
+    }
+
+    /// Draws the shown tooltip inside `area`. Draw it after everything else.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the tooltip was drawn, or if there is nothing to draw.
+    /// - An error if writing fails.
+    pub fn draw_in<W: Write>(&self, objects: &NyanObj, out: &mut W, area: Rect) -> NyanResult<()> {
+        let Some((rect, lines)) = self.layout(objects, area) else {
+            return Ok(());
+        };
+        for (row, line) in lines.iter().enumerate() {
+            let line = layout::align_text(line, rect.width - 2, Align::Start);
+            Cursor::Move(rect.x, rect.y + row as u16).queue(out)?;
+            widget::write_styled(out, self.style, &format!(" {line} "))?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Returns the box of the shown tooltip and its wrapped lines.
+    fn layout(&self, objects: &NyanObj, area: Rect) -> Option<(Rect, Vec<String>)> {
+        let id = self.active()?;
+        let anchor = objects.bounds(id)?;
+        let max_width = self.max_width.min(area.width);
+        if max_width < 3 || area.height == 0 {
+            return None;
+        }
+        let mut lines = layout::wrap_text(self.text(id)?, max_width - 2);
+        lines.truncate(area.height as usize);
+        let width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16 + 2;
+        let height = lines.len().max(1) as u16;
+
+        let below = anchor.bottom();
+        let y = if below + height <= area.bottom() {
+            below
+        } else if anchor.y >= area.y + height {
+            anchor.y - height
+        } else {
+            // Neither fits: overlap the object rather than leave the screen.
+            area.bottom() - height
+        };
+        let x = anchor.x.min(area.right().saturating_sub(width)).max(area.x);
+        Some((Rect::new(x, y, width, height), lines))
+    }
+}
@@ -0,0 +1,171 @@
+//! This module provides `Typewriter`, text revealed a character at a time, as in the dialogs of games and the intros of demos.
+//!
+//! A typewriter is advanced by the time that passed, usually [`App::delta_time`](crate::app::App::delta_time), and shows one more character every interval. Put it into a scene as [`Objects::Typewriter`](crate::objects::Objects::Typewriter), which takes the size of the whole text from the start so the layout does not move while it types, and advance every typewriter of the scene at once with [`NyanObj::advance`](crate::nyan_obj::NyanObj::advance). `skip()` shows the rest at once, e.g. when the player presses a key.
+//!
+//! # Structs
+//!
+//! - `Typewriter`: The text, the typing rate and how much of the text is shown.
+//!
+//! # Methods
+//!
+//! - `new(text)`: Creates a typewriter typing 30 characters per second.
+//! - `rate(chars_per_second)` / `pause(glyphs, duration)`: Set the typing speed and stop a while after punctuation.
+//! - `advance(dt)`: Types the characters due by the time that passed.
+//! - `skip()` / `reset()`: Show everything at once / start over.
+//! - `revealed()` / `text()` / `is_finished()`: Query the text.
+//! - `size()`: Returns the size of the whole text.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::stats;
+
+/// `Typewriter` reveals its text one character at a time.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use nyan::typewriter::Typewriter;
+///
+/// let ms = Duration::from_millis;
+/// let mut line = Typewriter::new("Hi. Meow!").rate(10).pause(".", ms(500));
+///
+/// line.advance(ms(300));
+/// assert_eq!(line.revealed(), "Hi.");
+/// line.advance(ms(500)); // Still pausing after the period.
+/// assert_eq!(line.revealed(), "Hi.");
+/// line.advance(ms(200));
+/// assert_eq!(line.revealed(), "Hi. M");
+///
+/// line.skip();
+/// assert_eq!(line.revealed(), "Hi. Meow!");
+/// assert!(line.is_finished());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Typewriter<'a> {
+    text: Cow<'a, str>,
+    /// The time between two characters.
+    interval: Duration,
+    /// The glyphs after which typing stops for a while, and for how long.
+    pause: (Vec<char>, Duration),
+    /// The number of characters shown.
+    shown: usize,
+    /// The time since the last character was shown.
+    elapsed: Duration,
+}
+
+impl<'a> Typewriter<'a> {
+    /// Creates a typewriter for `text`, showing nothing yet and typing 30 characters per
+    /// second.
+    pub fn new<T: Into<Cow<'a, str>>>(text: T) -> Self {
+        Self {
+            text: text.into(),
+            interval: Duration::from_secs(1) / 30,
+            pause: (Vec::new(), Duration::ZERO),
+            shown: 0,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Types `chars_per_second` characters per second (at least one).
+    pub fn rate(self, chars_per_second: u32) -> Self {
+        let mut typewriter = self;
+        typewriter.interval = Duration::from_secs(1) / chars_per_second.max(1);
+        typewriter
+    }
+
+    /// Waits `duration` longer after typing any of `glyphs`, e.g. `".!?"` for the end of a
+    /// sentence.
+    pub fn pause(self, glyphs: &str, duration: Duration) -> Self {
+        let mut typewriter = self;
+        typewriter.pause = (glyphs.chars().collect(), duration);
+        typewriter
+    }
+
+    /// Types the characters due after `dt`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if more of the text is shown now.
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        let before = self.shown;
+        self.elapsed += dt;
+        let mut chars = self.text.chars().skip(self.shown);
+        let mut last = self
+            .shown
+            .checked_sub(1)
+            .and_then(|index| self.text.chars().nth(index));
+        loop {
+            let wait = match last {
+                Some(glyph) if self.pause.0.contains(&glyph) => self.interval + self.pause.1,
+                _ => self.interval,
+            };
+            if self.elapsed < wait {
+                break;
+            }
+            let Some(next) = chars.next() else {
+                self.elapsed = Duration::ZERO;
+                break;
+            };
+            self.elapsed -= wait;
+            self.shown += 1;
+            last = Some(next);
+        }
+        self.shown != before
+    }
+
+    /// Shows the whole text at once.
+    pub fn skip(&mut self) {
+        self.shown = self.text.chars().count();
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Hides the text again, to type it from the start.
+    pub fn reset(&mut self) {
+        self.shown = 0;
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Returns the part of the text shown so far.
+    pub fn revealed(&self) -> &str {
+        match self.text.char_indices().nth(self.shown) {
+            Some((end, _)) => &self.text[..end],
+            None => &self.text,
+        }
+    }
+
+    /// Returns the whole text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns whether the whole text is shown.
+    pub fn is_finished(&self) -> bool {
+        self.revealed().len() == self.text.len()
+    }
+
+    /// Returns the approximate bytes of the text and the pause glyphs.
+    pub(crate) fn heap_size(&self) -> usize {
+        stats::owned(&self.text) + self.pause.0.capacity() * std::mem::size_of::<char>()
+    }
+
+    /// Returns the `(width, height)` of the whole text, so a typewriter keeps its size while
+    /// it types.
+    pub fn size(&self) -> (u16, u16) {
+        let width = self
+            .text
+            .lines()
+            .map(|line| line.width())
+            .max()
+            .unwrap_or(0);
+        let height = self.text.lines().count();
+        (
+            width.min(u16::MAX as usize) as u16,
+            height.min(u16::MAX as usize) as u16,
+        )
+    }
+}
@@ -0,0 +1,175 @@
+//! This module provides the `VimParser` struct, which turns a stream of `NyanInput` key presses into vim-style commands.
+//!
+//! Modal editors accept a numeric count and an optional operator before a motion, such as `12j` (move down 12 lines) or `d2w` (delete two words). `VimParser` accumulates these prefixes and emits a `VimCommand` once the motion key arrives.
+//!
+//! # Structs
+//!
+//! - `VimCommand`: A parsed command made of an optional count, an optional operator and a motion character.
+//! - `VimParser`: Accumulates counts and pending operators from key presses and emits `VimCommand`s.
+//!
+//! # Methods
+//!
+//! - `new()`: Creates a parser that knows the default vim operators (`d`, `c`, `y`, `>`, `<`).
+//! - `operators(ops: &[char])`: Replaces the set of characters treated as operators.
+//! - `feed(input: NyanInput)`: Feeds one key press and returns a `VimCommand` once a command is complete.
+//! - `pending()`: Returns the keys typed so far for an incomplete command (useful for showing in a status line).
+//! - `reset()`: Discards any incomplete command.
+//!
+//! # Example
+//!
+//! ```rust
+//! use nyan::input::{NyanInput, NyanKey};
+//! use nyan::vim::{VimCommand, VimParser};
+//!
+//! let mut parser = VimParser::new();
+//!
+//! assert_eq!(parser.feed(NyanInput::Key(NyanKey::D)), None);
+//! assert_eq!(parser.feed(NyanInput::Key(NyanKey::OtherKey('2'))), None);
+//! assert_eq!(parser.pending(), "d2");
+//!
+//! let command = parser.feed(NyanInput::Key(NyanKey::W)).unwrap();
+//! assert_eq!(
+//!     command,
+//!     VimCommand {
+//!         count: Some(2),
+//!         operator: Some('d'),
+//!         motion: 'w',
+//!     }
+//! );
+//! ```
+
+use crate::input::NyanInput;
+
+/// A vim-style command parsed by `VimParser`.
+///
+/// A doubled operator such as `dd` is reported with the operator character as its motion
+/// (`operator: Some('d'), motion: 'd'`), which conventionally means "the whole line".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VimCommand {
+    /// The numeric prefix, if one was typed. Counts before and after the operator are
+    /// multiplied together, as in vim (`2d3w` deletes six words).
+    pub count: Option<usize>,
+    /// The pending operator, if one was typed.
+    pub operator: Option<char>,
+    /// The key that completed the command.
+    pub motion: char,
+}
+
+impl VimCommand {
+    /// Returns the count, or `1` if no count was typed.
+    pub fn count_or_one(&self) -> usize {
+        self.count.unwrap_or(1)
+    }
+}
+
+/// `VimParser` accumulates numeric prefixes and pending operators from key presses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VimParser {
+    operators: Vec<char>,
+    count: Option<usize>,
+    operator: Option<char>,
+    operator_count: Option<usize>,
+}
+
+impl Default for VimParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VimParser {
+    /// Creates a new parser that treats `d`, `c`, `y`, `>` and `<` as operators.
+    pub fn new() -> Self {
+        Self {
+            operators: vec!['d', 'c', 'y', '>', '<'],
+            count: None,
+            operator: None,
+            operator_count: None,
+        }
+    }
+
+    /// Replaces the set of characters treated as operators.
+    ///
+    /// # Arguments
+    /// * `ops` - The operator characters.
+    ///
+    /// # Returns
+    /// The parser with the new operator set.
+    pub fn operators(self, ops: &[char]) -> Self {
+        let mut parser = self;
+        parser.operators = ops.to_vec();
+        parser
+    }
+
+    /// Feeds one key press into the parser.
+    ///
+    /// Digits extend the current count (a leading `0` is treated as a motion, as in vim),
+    /// operator characters become the pending operator, and any other character completes the
+    /// command. `Esc` and keys without a character discard the incomplete command.
+    ///
+    /// # Arguments
+    /// * `input` - The key press to feed.
+    ///
+    /// # Returns
+    /// * `Some(VimCommand)` when the key press completed a command.
+    /// * `None` while the command is still incomplete.
+    pub fn feed(&mut self, input: NyanInput) -> Option<VimCommand> {
+        let Some(ch) = input.to_char() else {
+            self.reset();
+            return None;
+        };
+
+        if let Some(digit) = ch.to_digit(10) {
+            let slot = if self.operator.is_some() {
+                &mut self.operator_count
+            } else {
+                &mut self.count
+            };
+
+            if digit != 0 || slot.is_some() {
+                let value = slot.unwrap_or(0);
+                *slot = Some(value.saturating_mul(10).saturating_add(digit as usize));
+                return None;
+            }
+        }
+
+        if self.operator.is_none() && self.operators.contains(&ch) {
+            self.operator = Some(ch);
+            return None;
+        }
+
+        let count = match (self.count, self.operator_count) {
+            (Some(a), Some(b)) => Some(a.saturating_mul(b)),
+            (a, b) => a.or(b),
+        };
+        let command = VimCommand {
+            count,
+            operator: self.operator,
+            motion: ch,
+        };
+        self.reset();
+        Some(command)
+    }
+
+    /// Returns the keys typed so far for the incomplete command (e.g. `"2d3"`).
+    pub fn pending(&self) -> String {
+        let mut pending = String::new();
+        if let Some(count) = self.count {
+            pending.push_str(&count.to_string());
+        }
+        if let Some(operator) = self.operator {
+            pending.push(operator);
+        }
+        if let Some(count) = self.operator_count {
+            pending.push_str(&count.to_string());
+        }
+        pending
+    }
+
+    /// Discards the incomplete command.
+    pub fn reset(&mut self) {
+        self.count = None;
+        self.operator = None;
+        self.operator_count = None;
+    }
+}
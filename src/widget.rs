@@ -0,0 +1,105 @@
+//! This module defines the `Widget` trait, the common interface of nyan's interactive components (text inputs, buttons, ...).
+//!
+//! A widget is a stateful component that occupies a rectangular area, draws itself into that area and reacts to user input. Unlike the objects of a `NyanObj`, a widget keeps its own state (the text typed into it, whether it is focused, ...) and is placed by setting its area, e.g. to a `Rect` computed by a layout.
+//!
+//! # Traits
+//!
+//! - `Widget`: Something that can be placed, drawn, focused and fed with input.
+//!
+//! # Functions
+//!
+//! - `handle_event(widget, event)`: Passes a key, mouse or paste event to a widget.
+
+use std::io::{self, Write};
+
+use crate::event::{NyanEvent, NyanMouse};
+use crate::input::NyanInput;
+use crate::rect::Rect;
+use crate::style::NyanStyle;
+
+/// `Widget` is the common interface of interactive components.
+///
+/// Only [`Widget::size`], [`Widget::area`], [`Widget::set_area`] and [`Widget::draw`] are
+/// required. Widgets that react to input override the `handle_*` methods, and the focus
+/// methods if they can be focused.
+pub trait Widget {
+    /// Returns the size `(width, height)` in cells the widget would like to have.
+    fn size(&self) -> (u16, u16);
+
+    /// Returns the area the widget is drawn in.
+    fn area(&self) -> Rect;
+
+    /// Sets the area the widget is drawn in, e.g. a region computed by a layout.
+    fn set_area(&mut self, area: Rect);
+
+    /// Draws the widget into its area.
+    ///
+    /// # Returns
+    /// * `Ok(())` on success.
+    /// * `Err(std::io::Error)` if writing fails.
+    fn draw(&self, out: &mut dyn Write) -> io::Result<()>;
+
+    /// Processes a key press.
+    ///
+    /// # Returns
+    /// `true` if the widget used the key, so it should not be handled elsewhere.
+    fn handle_input(&mut self, _input: &NyanInput) -> bool {
+        false
+    }
+
+    /// Processes a mouse action. The position is in terminal cells, not relative to the widget.
+    ///
+    /// # Returns
+    /// `true` if the widget used the action.
+    fn handle_mouse(&mut self, _mouse: &NyanMouse) -> bool {
+        false
+    }
+
+    /// Processes pasted text.
+    ///
+    /// # Returns
+    /// `true` if the widget used the text.
+    fn handle_paste(&mut self, _text: &str) -> bool {
+        false
+    }
+
+    /// Returns whether the widget can take the keyboard focus.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the widget has the keyboard focus.
+    fn is_focused(&self) -> bool {
+        false
+    }
+
+    /// Gives the keyboard focus to the widget or takes it away.
+    fn set_focused(&mut self, _focused: bool) {}
+}
+
+/// Passes a key, mouse or paste event to `widget`. Other events are ignored.
+///
+/// # Returns
+/// `true` if the widget used the event.
+pub fn handle_event<U, W: Widget + ?Sized>(widget: &mut W, event: &NyanEvent<U>) -> bool {
+    match event {
+        NyanEvent::Key(input) => widget.handle_input(input),
+        NyanEvent::Mouse(mouse) => widget.handle_mouse(mouse),
+        NyanEvent::Paste(text) => widget.handle_paste(text),
+        _ => false,
+    }
+}
+
+/// Writes `text` with `style` layered on the base style.
+pub(crate) fn write_styled(
+    mut out: &mut dyn Write,
+    style: NyanStyle,
+    text: &str,
+) -> io::Result<()> {
+    if style.is_empty() {
+        return write!(out, "{text}");
+    }
+    NyanStyle::base().patch(style).apply(&mut out)?;
+    write!(out, "{text}")?;
+    NyanStyle::reset(&mut out)
+}
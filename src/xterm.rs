@@ -0,0 +1,165 @@
+//! This module provides a bridge to [xterm.js](https://xtermjs.org), so nyan apps compiled to WebAssembly can be shown in a browser, e.g. for demos.
+//!
+//! An `Xterm` is created from JavaScript with the function that writes to the xterm.js terminal and the size of the terminal. The page forwards the terminal's `onData` and `onResize` events to it. On the Rust side, `Xterm::backend` gives the [`Backend`] an [`App`](crate::app::App) draws to with [`App::with_backend`](crate::app::App::with_backend), and `Xterm::input` the events typed into the terminal, decoded like the input of a [remote session](crate::remote).
+//!
+//! ```js
+//! const term = new Terminal();
+//! term.open(document.getElementById("terminal"));
+//! const xterm = new Xterm((data) => term.write(data), term.cols, term.rows);
+//! term.onData((data) => xterm.feed(data));
+//! term.onResize(({ cols, rows }) => xterm.resize(cols, rows));
+//! ```
+//!
+//! Requires the `wasm` feature.
+//!
+//! # Structs
+//!
+//! - `Xterm`: The connection to an xterm.js terminal, shared with JavaScript.
+//! - `XtermBackend`: Draws to the terminal.
+//! - `XtermInput`: The events typed into the terminal.
+//!
+//! # Methods
+//!
+//! - `Xterm::new(write, cols, rows)`: Connects to a terminal (called from JavaScript).
+//! - `Xterm::feed(data)` / `Xterm::resize(cols, rows)`: Pass on the events of the terminal (called from JavaScript).
+//! - `Xterm::backend()` / `Xterm::input()`: Return the backend and the input of the terminal.
+//! - `XtermInput::get_event()` / `drain_events()`: Return the next / every pending event, without waiting.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use js_sys::Function;
+use wasm_bindgen::prelude::*;
+
+use crate::backend::Backend;
+use crate::event::NyanEvent;
+use crate::remote::Decoder;
+
+/// The state shared by an [`Xterm`], its backend and its input.
+#[derive(Debug)]
+struct Shared {
+    write: Function,
+    size: (u16, u16),
+    output: Vec<u8>,
+    events: VecDeque<NyanEvent>,
+    decoder: Decoder,
+}
+
+/// `Xterm` connects nyan to an xterm.js terminal.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Xterm {
+    shared: Rc<RefCell<Shared>>,
+}
+
+#[wasm_bindgen]
+impl Xterm {
+    /// Connects to a terminal of `cols` × `rows` cells, which `write` writes a string of
+    /// output to, e.g. `(data) => term.write(data)`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(write: Function, cols: u16, rows: u16) -> Xterm {
+        Xterm {
+            shared: Rc::new(RefCell::new(Shared {
+                write,
+                size: (cols, rows),
+                output: Vec::new(),
+                events: VecDeque::new(),
+                decoder: Decoder::default(),
+            })),
+        }
+    }
+
+    /// Passes on the data the terminal reports with `onData`: typed keys, mouse actions and
+    /// pasted text.
+    pub fn feed(&self, data: &str) {
+        let mut shared = self.shared.borrow_mut();
+        let Shared {
+            decoder, events, ..
+        } = &mut *shared;
+        decoder.feed(data.as_bytes(), |event| events.push_back(event));
+    }
+
+    /// Passes on a new size of the terminal, reported with `onResize`.
+    pub fn resize(&self, cols: u16, rows: u16) {
+        let mut shared = self.shared.borrow_mut();
+        shared.size = (cols, rows);
+        shared.events.push_back(NyanEvent::Resize(cols, rows));
+    }
+}
+
+impl Xterm {
+    /// Returns the backend drawing to the terminal.
+    pub fn backend(&self) -> XtermBackend {
+        XtermBackend {
+            shared: Rc::clone(&self.shared),
+        }
+    }
+
+    /// Returns the events typed into the terminal.
+    pub fn input(&self) -> XtermInput {
+        XtermInput {
+            shared: Rc::clone(&self.shared),
+        }
+    }
+}
+
+/// `XtermBackend` draws to an xterm.js terminal. The output of a frame is passed to JavaScript
+/// at once when the frame is flushed.
+#[derive(Debug)]
+pub struct XtermBackend {
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl Write for XtermBackend {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.shared.borrow_mut().output.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut shared = self.shared.borrow_mut();
+        if shared.output.is_empty() {
+            return Ok(());
+        }
+        let output = std::mem::take(&mut shared.output);
+        let data = JsValue::from_str(&String::from_utf8_lossy(&output));
+        match shared.write.call1(&JsValue::NULL, &data) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(io::Error::other(format!("{e:?}"))),
+        }
+    }
+}
+
+impl Backend for XtermBackend {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(self.shared.borrow().size)
+    }
+}
+
+/// `XtermInput` holds the events typed into an xterm.js terminal until the app reads them.
+///
+/// A browser cannot block, so the events are returned without waiting; the page draws a frame
+/// whenever it gets the chance, e.g. from `requestAnimationFrame`.
+#[derive(Debug)]
+pub struct XtermInput {
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl XtermInput {
+    /// Returns the next event, or `NyanEvent::Tick` if nothing happened.
+    pub fn get_event(&mut self) -> NyanEvent {
+        self.shared
+            .borrow_mut()
+            .events
+            .pop_front()
+            .unwrap_or(NyanEvent::Tick)
+    }
+
+    /// Returns every pending event, in the order they happened.
+    pub fn drain_events(&mut self) -> Vec<NyanEvent> {
+        self.shared.borrow_mut().events.drain(..).collect()
+    }
+}